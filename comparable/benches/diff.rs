@@ -0,0 +1,81 @@
+// Benchmarks of comparisons of structs, vectors and maps of several sizes,
+// run with `cargo bench -p comparable`.
+
+use std::collections::HashMap;
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use comparable::Comparable;
+
+#[derive(comparable_derive::Comparable, Clone, PartialEq)]
+struct Record {
+	id: u64,
+	name: String,
+	tags: Vec<String>,
+	score: Option<i32>,
+}
+
+fn record(id: u64) -> Record {
+	Record {
+		id,
+		name: format!("record {}", id),
+		tags: vec!["a".to_string(), format!("tag {}", id % 7)],
+		score: Some(id as i32),
+	}
+}
+
+// A copy of `records` in which every tenth record has changed.
+fn changed(records: &[Record]) -> Vec<Record> {
+	records
+		.iter()
+		.enumerate()
+		.map(|(i, r)| if i % 10 == 0 { Record { score: None, ..r.clone() } } else { r.clone() })
+		.collect()
+}
+
+const SIZES: [u64; 4] = [10, 100, 1_000, 10_000];
+
+fn structs(c: &mut Criterion) {
+	let (before, after) = (record(1), Record { score: None, ..record(1) });
+	let mut group = c.benchmark_group("struct");
+	group.bench_function("comparison", |b| b.iter(|| black_box(&before).comparison(black_box(&after))));
+	group.bench_function("differs", |b| b.iter(|| black_box(&before).differs(black_box(&after))));
+	group.finish();
+}
+
+fn vecs(c: &mut Criterion) {
+	let mut group = c.benchmark_group("vec");
+	for size in SIZES {
+		let before = (0..size).map(record).collect::<Vec<_>>();
+		let after = changed(&before);
+		group.bench_with_input(BenchmarkId::new("comparison", size), &(&before, &after), |b, (before, after)| {
+			b.iter(|| before.comparison(after))
+		});
+		group.bench_with_input(BenchmarkId::new("count_changes", size), &(&before, &after), |b, (before, after)| {
+			b.iter(|| before.count_changes(after))
+		});
+	}
+	group.finish();
+}
+
+fn hashmaps(c: &mut Criterion) {
+	let mut group = c.benchmark_group("hashmap");
+	for size in SIZES {
+		let records = (0..size).map(record).collect::<Vec<_>>();
+		let before = records.iter().cloned().map(|r| (r.id, r)).collect::<HashMap<_, _>>();
+		let mut after = changed(&records).into_iter().map(|r| (r.id, r)).collect::<HashMap<_, _>>();
+		after.remove(&1);
+		after.insert(size, record(size));
+		group.bench_with_input(BenchmarkId::new("comparison", size), &(&before, &after), |b, (before, after)| {
+			b.iter(|| before.comparison(after))
+		});
+		group.bench_with_input(BenchmarkId::new("count_changes", size), &(&before, &after), |b, (before, after)| {
+			b.iter(|| before.count_changes(after))
+		});
+	}
+	group.finish();
+}
+
+criterion_group!(benches, structs, vecs, hashmaps);
+criterion_main!(benches);
@@ -0,0 +1,139 @@
+//! Building the list of changes to a `Vec` or map in an arena.
+//!
+//! Comparing collections many times a second, as a game or simulation might
+//! compare its state on every step, allocates a list of changes for each
+//! comparison, which is then soon dropped. With the `bumpalo` feature
+//! enabled, [`ComparableIn::comparison_in`] builds that list in a
+//! [`bumpalo::Bump`] arena instead, so that the lists built during a step are
+//! freed at once by resetting the arena.
+//!
+//! Only the list of changes to the collection itself is held by the arena.
+//! The changes and descriptions of its elements are built as by
+//! [`Comparable`], so those that allocate, such as the changes of derived
+//! structs or of nested collections, still use the global allocator. Elements
+//! needing no allocation of their own, such as numbers, are compared without
+//! using it at all. Derived types do not implement [`ComparableIn`]; a struct
+//! holding the state of a game can instead compare each of its collections
+//! in the arena.
+//!
+//! Changes are reported as by [`Comparable::comparison`] when no
+//! [`DiffOptions`](crate::DiffOptions) are in effect: elements of a `Vec` are
+//! compared position by position, and entries of a map by key.
+//!
+//! ```
+//! # use comparable::*;
+//! use bumpalo::Bump;
+//! use comparable::arena::ComparableIn;
+//!
+//! let mut bump = Bump::new();
+//! let before = vec![1u32, 2, 3];
+//! for step in 0..3 {
+//!     let after = vec![1, 2 + step, 3];
+//!     assert_eq!(before.comparison_in(&after, &bump).is_unchanged(), step == 0);
+//!     bump.reset();
+//! }
+//!
+//! let changes = before.comparison_in(&vec![1, 5], &bump).unwrap_changed();
+//! assert_eq!(changes[..], [VecChange::Changed(1, U32Change(2, 5)), VecChange::Removed(2, 3)]);
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+
+use bumpalo::collections::Vec as BumpVec;
+use bumpalo::Bump;
+
+use crate::map::MapChange;
+use crate::set::VecChange;
+use crate::types::{Changed, Comparable};
+
+/// Collections whose changes can be built in an arena.
+pub trait ComparableIn<'b>: Comparable {
+	/// The changes reported by [`Comparable::comparison`], in a list held by
+	/// the arena.
+	type ChangeIn: PartialEq + Debug;
+
+	/// Compare two values, building the list of their changes in `bump`.
+	#[must_use]
+	fn comparison_in(&self, other: &Self, bump: &'b Bump) -> Changed<Self::ChangeIn>;
+}
+
+fn from_changes<T>(changes: BumpVec<'_, T>) -> Changed<BumpVec<'_, T>> {
+	if changes.is_empty() {
+		Changed::Unchanged
+	} else {
+		Changed::Changed(changes)
+	}
+}
+
+impl<'b, Value: PartialEq + Comparable + 'b> ComparableIn<'b> for Vec<Value> {
+	type ChangeIn = BumpVec<'b, VecChange<Value::Desc, Value::Change>>;
+
+	fn comparison_in(&self, other: &Self, bump: &'b Bump) -> Changed<Self::ChangeIn> {
+		let mut changes = BumpVec::new_in(bump);
+		for (i, (x, y)) in self.iter().zip(other).enumerate() {
+			if let Changed::Changed(change) = x.comparison(y) {
+				changes.push(VecChange::Changed(i, change));
+			}
+		}
+		changes.extend(self.iter().enumerate().skip(other.len()).map(|(i, x)| VecChange::Removed(i, x.describe())));
+		changes.extend(other.iter().enumerate().skip(self.len()).map(|(i, y)| VecChange::Added(i, y.describe())));
+		from_changes(changes)
+	}
+}
+
+// The changes to a map, in a list held by the arena.
+type MapChangesIn<'b, Key, Value> =
+	BumpVec<'b, MapChange<Key, <Value as Comparable>::Desc, <Value as Comparable>::Change>>;
+
+// Entries are reported in the same order as by `Comparable::comparison`:
+// those of `after` in the order of their keys, followed by those removed.
+fn map_comparison_in<'a, 'b, Key, Value>(
+	after: impl Iterator<Item = (&'a Key, &'a Value)>,
+	before: impl Fn(&Key) -> Option<&'a Value>,
+	removed: impl Iterator<Item = &'a Key>,
+	bump: &'b Bump,
+) -> Changed<MapChangesIn<'b, Key, Value>>
+where
+	Key: Clone + 'a,
+	Value: Comparable + 'a,
+{
+	let mut changes = BumpVec::new_in(bump);
+	for (k, v) in after {
+		match before(k) {
+			Some(vo) => {
+				if let Changed::Changed(change) = vo.comparison(v) {
+					changes.push(MapChange::Changed(k.clone(), change));
+				}
+			}
+			None => changes.push(MapChange::Added(k.clone(), v.describe())),
+		}
+	}
+	changes.extend(removed.map(|k| MapChange::Removed(k.clone())));
+	from_changes(changes)
+}
+
+impl<'b, Key: Ord + Clone + Debug + 'b, Value: Comparable + 'b> ComparableIn<'b> for BTreeMap<Key, Value> {
+	type ChangeIn = MapChangesIn<'b, Key, Value>;
+
+	fn comparison_in(&self, other: &Self, bump: &'b Bump) -> Changed<Self::ChangeIn> {
+		map_comparison_in(other.iter(), |k| self.get(k), self.keys().filter(|k| !other.contains_key(k)), bump)
+	}
+}
+
+impl<'b, Key, Value> ComparableIn<'b> for HashMap<Key, Value>
+where
+	Key: Ord + Clone + Debug + std::hash::Hash + 'b,
+	Value: Comparable + 'b,
+{
+	type ChangeIn = MapChangesIn<'b, Key, Value>;
+
+	fn comparison_in(&self, other: &Self, bump: &'b Bump) -> Changed<Self::ChangeIn> {
+		// The keys are sorted in the arena, to report entries in key order.
+		let mut entries = BumpVec::from_iter_in(other.iter(), bump);
+		entries.sort_by_key(|(k, _)| *k);
+		let mut removed = BumpVec::from_iter_in(self.keys().filter(|k| !other.contains_key(k)), bump);
+		removed.sort();
+		map_comparison_in(entries.into_iter(), |k| self.get(k), removed.into_iter(), bump)
+	}
+}
@@ -0,0 +1,81 @@
+use std::cell::Cell;
+use std::sync::atomic::{
+	AtomicBool, AtomicI16, AtomicI32, AtomicI64, AtomicI8, AtomicIsize, AtomicU16, AtomicU32, AtomicU64, AtomicU8,
+	AtomicUsize, Ordering,
+};
+
+use crate::types::{Changed, Comparable};
+
+thread_local! {
+	static ORDERING: Cell<Ordering> = const { Cell::new(Ordering::SeqCst) };
+}
+
+// Restores the previous ordering, even on panic.
+struct Restore(Ordering);
+
+impl Drop for Restore {
+	fn drop(&mut self) {
+		ORDERING.with(|ordering| ordering.set(self.0));
+	}
+}
+
+/// Run `f` with every atomic value described or compared on this thread
+/// loaded using the given ordering, rather than `Ordering::SeqCst`. Since the
+/// values are only loaded, the ordering must be one that `load` accepts.
+///
+/// ```
+/// # use comparable::*;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// let before = AtomicUsize::new(1);
+/// let after = AtomicUsize::new(2);
+/// assert_eq!(
+///     with_atomic_ordering(Ordering::Relaxed, || before.comparison(&after)),
+///     Changed::Changed(UsizeChange(1, 2))
+/// );
+/// ```
+pub fn with_atomic_ordering<R>(ordering: Ordering, f: impl FnOnce() -> R) -> R {
+	let _restore = Restore(ORDERING.with(|current| current.replace(ordering)));
+	f()
+}
+
+pub(crate) fn atomic_ordering() -> Ordering {
+	ORDERING.with(Cell::get)
+}
+
+macro_rules! impl_comparable_for_atomic {
+	($($atomic:ident => $ty:ty),*) => {
+		$(
+			/// Atomic values are described and compared as the values they hold
+			/// when loaded, so that they report the same changes as those values.
+			impl Comparable for $atomic {
+				type Desc = $ty;
+
+				fn describe(&self) -> Self::Desc {
+					self.load(atomic_ordering())
+				}
+
+				type Change = <$ty as Comparable>::Change;
+
+				fn comparison(&self, other: &Self) -> Changed<Self::Change> {
+					let ordering = atomic_ordering();
+					self.load(ordering).comparison(&other.load(ordering))
+				}
+			}
+		)*
+	};
+}
+
+impl_comparable_for_atomic!(
+	AtomicBool => bool,
+	AtomicU8 => u8,
+	AtomicI8 => i8,
+	AtomicU16 => u16,
+	AtomicI16 => i16,
+	AtomicU32 => u32,
+	AtomicI32 => i32,
+	AtomicU64 => u64,
+	AtomicI64 => i64,
+	AtomicUsize => usize,
+	AtomicIsize => isize
+);
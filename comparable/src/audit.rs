@@ -0,0 +1,106 @@
+//! Recording who changed a value, when, and why.
+//!
+//! Changes persisted for auditing need the same few pieces of context around
+//! them: the actor responsible, the time of the change, and often a reason.
+//! An [`AuditEntry`] carries these along with the change itself, can be
+//! stored with any serde format when the `serde` feature is enabled, and is
+//! rendered for people to read by its `Display` implementation.
+//!
+//! ```
+//! # use comparable::*;
+//! use std::time::{Duration, UNIX_EPOCH};
+//! use comparable::audit::AuditEntry;
+//!
+//! let entry = AuditEntry::of("alice", &10, &15)
+//!     .unwrap()
+//!     .with_reason("monthly adjustment")
+//!     .at(UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+//! assert_eq!(entry.change, I32Change(10, 15));
+//! assert_eq!(
+//!     entry.to_string(),
+//!     "2023-11-14T22:13:20Z alice (monthly adjustment): I32Change(10, 15)"
+//! );
+//! ```
+
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::types::{Changed, Comparable};
+
+/// A change, along with who made it, when, and why.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, PartialEq, Debug)]
+pub struct AuditEntry<C> {
+	/// Who made the change, such as a user name or the name of a service.
+	pub actor: String,
+	pub timestamp: SystemTime,
+	pub reason: Option<String>,
+	pub change: C,
+}
+
+impl<C> AuditEntry<C> {
+	/// A change made by `actor`, recorded as having been made now.
+	pub fn new(actor: impl Into<String>, change: C) -> Self {
+		AuditEntry { actor: actor.into(), timestamp: SystemTime::now(), reason: None, change }
+	}
+
+	/// The changes made by `actor` from `before` to `after`, or `None` if
+	/// there are none.
+	pub fn of<T: Comparable<Change = C>>(actor: impl Into<String>, before: &T, after: &T) -> Option<Self> {
+		match before.comparison(after) {
+			Changed::Unchanged => None,
+			Changed::Changed(change) => Some(AuditEntry::new(actor, change)),
+		}
+	}
+
+	pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+		self.reason = Some(reason.into());
+		self
+	}
+
+	/// This entry, recorded as having been made at `timestamp`.
+	pub fn at(mut self, timestamp: SystemTime) -> Self {
+		self.timestamp = timestamp;
+		self
+	}
+}
+
+/// Entries are written on one line, as the timestamp in RFC 3339 format in
+/// UTC, the actor, the reason if there is one, and the change in its `Debug`
+/// format. The alternate form, `{:#}`, writes the change in the alternate
+/// `Debug` format on the following lines.
+impl<C: fmt::Debug> fmt::Display for AuditEntry<C> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write_timestamp(f, self.timestamp)?;
+		write!(f, " {}", self.actor)?;
+		if let Some(reason) = &self.reason {
+			write!(f, " ({})", reason)?;
+		}
+		if f.alternate() {
+			write!(f, ":\n{:#?}", self.change)
+		} else {
+			write!(f, ": {:?}", self.change)
+		}
+	}
+}
+
+// Times are written to the second, using the proleptic Gregorian calendar.
+fn write_timestamp(f: &mut fmt::Formatter<'_>, timestamp: SystemTime) -> fmt::Result {
+	let seconds = match timestamp.duration_since(UNIX_EPOCH) {
+		Ok(since) => since.as_secs() as i64,
+		Err(before) => -(before.duration().as_secs() as i64),
+	};
+	let (days, time) = (seconds.div_euclid(86_400), seconds.rem_euclid(86_400));
+	// Converting days since the epoch to a date, from Howard Hinnant's
+	// `civil_from_days`, with years starting in March.
+	let days = days + 719_468;
+	let era = days.div_euclid(146_097);
+	let day_of_era = days.rem_euclid(146_097);
+	let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+	let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+	let month_index = (5 * day_of_year + 2) / 153;
+	let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+	let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+	let year = year_of_era + era * 400 + i64::from(month <= 2);
+	write!(f, "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", year, month, day, time / 3600, time / 60 % 60, time % 60)
+}
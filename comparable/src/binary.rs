@@ -0,0 +1,181 @@
+use crate::types::{Changed, Comparable};
+
+/// A run of bytes that differs between two binary values: the bytes at
+/// `offset` in the old value, `old`, were replaced by `new`. Offsets always
+/// refer to positions in the old value.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, Debug)]
+pub struct BytesChange {
+	pub offset: usize,
+	pub old: Vec<u8>,
+	pub new: Vec<u8>,
+}
+
+/// The length of the blocks of the old value that are searched for in the new
+/// value when matching the parts the two have in common.
+const BLOCK_SIZE: usize = 8;
+
+/// The multiplier of the rolling hash used to find blocks of the old value in
+/// the new value.
+const HASH_BASE: u32 = 257;
+
+fn block_hash(block: &[u8]) -> u32 {
+	block.iter().fold(0u32, |hash, &byte| hash.wrapping_mul(HASH_BASE).wrapping_add(u32::from(byte)))
+}
+
+/// Compare two byte slices, reporting only the ranges that differ rather than
+/// a full copy of both values.
+///
+/// Any common prefix and suffix is skipped first. The old value is then split
+/// into blocks of eight bytes, which are looked up in the new value
+/// using a rolling hash, as rsync does, so that bytes inserted or removed in
+/// one place do not shift every byte after them into the change. Each block
+/// found is extended as far as both values agree, and the bytes between two
+/// such matches are reported as a replaced range; where that range has the
+/// same length in both values, each run of differing bytes within it is
+/// reported separately.
+pub fn bytes_comparison(old: &[u8], new: &[u8]) -> Changed<Vec<BytesChange>> {
+	let prefix = old.iter().zip(new.iter()).take_while(|(a, b)| a == b).count();
+	let suffix = old[prefix..].iter().rev().zip(new[prefix..].iter().rev()).take_while(|(a, b)| a == b).count();
+	let old_middle = &old[prefix..old.len() - suffix];
+	let new_middle = &new[prefix..new.len() - suffix];
+
+	let mut changes = Vec::new();
+	let (mut old_start, mut new_start) = (0, 0);
+	for (old_match, new_match, len) in matching_blocks(old_middle, new_middle) {
+		push_replaced(
+			&mut changes,
+			prefix + old_start,
+			&old_middle[old_start..old_match],
+			&new_middle[new_start..new_match],
+		);
+		old_start = old_match + len;
+		new_start = new_match + len;
+	}
+	push_replaced(&mut changes, prefix + old_start, &old_middle[old_start..], &new_middle[new_start..]);
+
+	Changed::from_changes(changes)
+}
+
+/// The runs of bytes common to `old` and `new`, as `(old offset, new offset,
+/// length)`, in increasing order of both offsets.
+fn matching_blocks(old: &[u8], new: &[u8]) -> Vec<(usize, usize, usize)> {
+	let mut matches = Vec::new();
+	if old.len() < BLOCK_SIZE || new.len() < BLOCK_SIZE {
+		return matches;
+	}
+
+	let mut blocks: std::collections::HashMap<u32, Vec<usize>> = std::collections::HashMap::new();
+	for offset in (0..=old.len() - BLOCK_SIZE).step_by(BLOCK_SIZE) {
+		blocks.entry(block_hash(&old[offset..offset + BLOCK_SIZE])).or_default().push(offset);
+	}
+	let outgoing = (1..BLOCK_SIZE).fold(1u32, |factor, _| factor.wrapping_mul(HASH_BASE));
+
+	let (mut old_start, mut new_start) = (0, 0);
+	let mut position = 0;
+	let mut hash = block_hash(&new[..BLOCK_SIZE]);
+	loop {
+		let found = blocks.get(&hash).and_then(|offsets| {
+			offsets.iter().copied().find(|&offset| {
+				offset >= old_start && old[offset..offset + BLOCK_SIZE] == new[position..position + BLOCK_SIZE]
+			})
+		});
+		if let Some(offset) = found {
+			let before = old[old_start..offset]
+				.iter()
+				.rev()
+				.zip(new[new_start..position].iter().rev())
+				.take_while(|(a, b)| a == b)
+				.count();
+			let after = old[offset + BLOCK_SIZE..]
+				.iter()
+				.zip(new[position + BLOCK_SIZE..].iter())
+				.take_while(|(a, b)| a == b)
+				.count();
+			let len = before + BLOCK_SIZE + after;
+			matches.push((offset - before, position - before, len));
+			old_start = offset - before + len;
+			new_start = position - before + len;
+			position = new_start;
+			if position + BLOCK_SIZE > new.len() {
+				break;
+			}
+			hash = block_hash(&new[position..position + BLOCK_SIZE]);
+		} else {
+			if position + BLOCK_SIZE >= new.len() {
+				break;
+			}
+			hash = hash
+				.wrapping_sub(u32::from(new[position]).wrapping_mul(outgoing))
+				.wrapping_mul(HASH_BASE)
+				.wrapping_add(u32::from(new[position + BLOCK_SIZE]));
+			position += 1;
+		}
+	}
+	matches
+}
+
+/// Report `old`, found at `offset` in the old value, as replaced by `new`:
+/// run by run if the two have the same length, or otherwise as a whole.
+fn push_replaced(changes: &mut Vec<BytesChange>, offset: usize, old: &[u8], new: &[u8]) {
+	if old.len() == new.len() {
+		let mut start = None;
+		for i in 0..=old.len() {
+			let differs = i < old.len() && old[i] != new[i];
+			match (start, differs) {
+				(None, true) => start = Some(i),
+				(Some(s), false) => {
+					changes.push(BytesChange { offset: offset + s, old: old[s..i].to_vec(), new: new[s..i].to_vec() });
+					start = None;
+				}
+				_ => (),
+			}
+		}
+	} else {
+		changes.push(BytesChange { offset, old: old.to_vec(), new: new.to_vec() });
+	}
+}
+
+impl Comparable for [u8] {
+	type Desc = Vec<u8>;
+
+	fn describe(&self) -> Self::Desc {
+		self.to_vec()
+	}
+
+	type Change = Vec<BytesChange>;
+
+	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
+		bytes_comparison(self, other)
+	}
+}
+
+#[cfg(feature = "bytes")]
+impl Comparable for bytes::Bytes {
+	type Desc = Vec<u8>;
+
+	fn describe(&self) -> Self::Desc {
+		self.to_vec()
+	}
+
+	type Change = Vec<BytesChange>;
+
+	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
+		bytes_comparison(self, other)
+	}
+}
+
+#[cfg(feature = "bytes")]
+impl Comparable for bytes::BytesMut {
+	type Desc = Vec<u8>;
+
+	fn describe(&self) -> Self::Desc {
+		self.to_vec()
+	}
+
+	type Change = Vec<BytesChange>;
+
+	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
+		bytes_comparison(self, other)
+	}
+}
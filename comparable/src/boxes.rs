@@ -1,3 +1,4 @@
+use crate::location::Path;
 use crate::types::{Changed, Comparable};
 
 impl<T: Comparable> Comparable for Box<T> {
@@ -12,4 +13,24 @@ impl<T: Comparable> Comparable for Box<T> {
 	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
 		self.as_ref().comparison(&**other)
 	}
+
+	fn similarity(&self, other: &Self) -> f64 {
+		self.as_ref().similarity(&**other)
+	}
+
+	fn differs(&self, other: &Self) -> bool {
+		self.as_ref().differs(&**other)
+	}
+
+	fn count_changes(&self, other: &Self) -> usize {
+		self.as_ref().count_changes(&**other)
+	}
+
+	fn first_difference(&self, other: &Self) -> Option<Path> {
+		self.as_ref().first_difference(&**other)
+	}
+
+	fn fingerprint(&self) -> Option<u64> {
+		self.as_ref().fingerprint()
+	}
 }
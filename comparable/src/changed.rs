@@ -0,0 +1,99 @@
+//! Serializing [`Changed`] values.
+//!
+//! With the `serde` feature enabled, [`Changed`] serializes as the enum it is,
+//! with the variants `Unchanged` and `Changed`, so that in JSON a value reads
+//! as `"Unchanged"` or as `{"Changed": ...}`. A field holding a `Changed`
+//! value can also be left out when it is unchanged:
+//!
+//! ```
+//! # use comparable::*;
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Report {
+//!     #[serde(default, skip_serializing_if = "Changed::is_unchanged")]
+//!     total: Changed<I32Change>,
+//! }
+//! ```
+//!
+//! The [`flat`] module serializes a `Changed` value the way an `Option` is
+//! instead, with `Changed::Unchanged` as `None` and `Changed::Changed(x)` as
+//! `x` itself, so that changes written as JSON read as `null` or as the change,
+//! without wrapping. It can be selected for a field using
+//! `#[serde(with = "comparable::changed::flat")]`, but only where the change
+//! does not itself serialize as `null`: a change such as [`Redacted`] could
+//! then not be told apart from no change at all, and would be read back as
+//! `Changed::Unchanged`. The [`tagged`] module selects the default
+//! representation in the same way.
+//!
+//! [`Redacted`]: crate::Redacted
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::types::Changed;
+
+impl<T: Serialize> Serialize for Changed<T> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		tagged::serialize(self, serializer)
+	}
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Changed<T> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		tagged::deserialize(deserializer)
+	}
+}
+
+/// Serializing `Changed::Unchanged` as `None`, and `Changed::Changed(x)` as `x`,
+/// for changes that never serialize as `None` themselves.
+pub mod flat {
+	use super::*;
+
+	pub fn serialize<T: Serialize, S: Serializer>(value: &Changed<T>, serializer: S) -> Result<S::Ok, S::Error> {
+		match value {
+			Changed::Unchanged => serializer.serialize_none(),
+			Changed::Changed(x) => serializer.serialize_some(x),
+		}
+	}
+
+	pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(
+		deserializer: D,
+	) -> Result<Changed<T>, D::Error> {
+		Option::deserialize(deserializer).map(Changed::from)
+	}
+}
+
+/// Serializing `Changed` as an enum with the variants `Unchanged` and
+/// `Changed`.
+pub mod tagged {
+	use super::*;
+
+	#[derive(Serialize)]
+	#[serde(rename = "Changed")]
+	enum TaggedRef<'a, T> {
+		Unchanged,
+		Changed(&'a T),
+	}
+
+	#[derive(Deserialize)]
+	#[serde(rename = "Changed")]
+	enum Tagged<T> {
+		Unchanged,
+		Changed(T),
+	}
+
+	pub fn serialize<T: Serialize, S: Serializer>(value: &Changed<T>, serializer: S) -> Result<S::Ok, S::Error> {
+		match value {
+			Changed::Unchanged => TaggedRef::Unchanged,
+			Changed::Changed(x) => TaggedRef::Changed(x),
+		}
+		.serialize(serializer)
+	}
+
+	pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(
+		deserializer: D,
+	) -> Result<Changed<T>, D::Error> {
+		Ok(match Tagged::deserialize(deserializer)? {
+			Tagged::Unchanged => Changed::Unchanged,
+			Tagged::Changed(x) => Changed::Changed(x),
+		})
+	}
+}
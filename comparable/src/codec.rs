@@ -0,0 +1,126 @@
+//! A compact binary encoding for changes, for protocols that send them to
+//! other copies of a value where bandwidth matters, with the `codec` feature
+//! enabled.
+//!
+//! Any type implementing `serde::Serialize` may be encoded, although this is
+//! intended for the `Change` types generated when the `serde` feature is
+//! enabled. Values are encoded with `bincode`, configured to write integers
+//! as variable-length integers, zigzag-encoded when signed, so that enum
+//! variants are written as their index in a single byte, and small changes to
+//! numbers take few bytes. Structs are written as their fields in order,
+//! without the names of the fields. The encoding is not self-describing, so a
+//! value must be decoded as the same type that it was encoded from.
+//!
+//! [`encode_changes`] writes nothing at all for `Changed::Unchanged`, so that
+//! reporting a value that has not changed costs nothing, and a single tag byte
+//! before the encoding of any change, so that a change that itself encodes to
+//! no bytes, such as [`Redacted`](crate::Redacted), is not mistaken for none.
+//!
+//! ```
+//! # use comparable::*;
+//! use comparable::codec::{decode_changes, encode_changes};
+//!
+//! let changes = vec![1, 2, 3].comparison(&vec![1, 5, 3]);
+//! let bytes = encode_changes(&changes).unwrap();
+//! assert_eq!(bytes, vec![1, 1, 1, 1, 4, 10]);
+//! assert_eq!(decode_changes::<Vec<VecChange<i32, I32Change>>>(&bytes).unwrap(), changes);
+//!
+//! assert!(encode_changes(&vec![1].comparison(&vec![1])).unwrap().is_empty());
+//! ```
+
+use std::fmt;
+
+use bincode::Options;
+use serde::de::DeserializeOwned;
+use serde::ser::Serialize;
+
+use crate::types::Changed;
+
+/// The byte written by [`encode_changes`] before the encoding of a change.
+const CHANGED_TAG: u8 = 1;
+
+/// The ways in which encoding or decoding can fail.
+#[derive(PartialEq, Debug)]
+pub enum Error {
+	/// The input ended before the value being decoded was complete.
+	UnexpectedEnd,
+	/// Bytes remained in the input after the value was decoded.
+	TrailingBytes,
+	/// A sequence or map being encoded did not report its length in advance.
+	UnknownLength,
+	/// The input was not a valid encoding of the type being decoded.
+	Invalid(String),
+	/// An error reported by the type being encoded or decoded, or by the
+	/// encoding itself.
+	Message(String),
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Error::UnexpectedEnd => write!(f, "unexpected end of input"),
+			Error::TrailingBytes => write!(f, "trailing bytes after the decoded value"),
+			Error::UnknownLength => write!(f, "sequences and maps must report their length"),
+			Error::Invalid(msg) | Error::Message(msg) => write!(f, "{}", msg),
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+impl From<bincode::Error> for Error {
+	fn from(err: bincode::Error) -> Self {
+		match *err {
+			bincode::ErrorKind::Io(ref io) if io.kind() == std::io::ErrorKind::UnexpectedEof => Error::UnexpectedEnd,
+			bincode::ErrorKind::SequenceMustHaveLength => Error::UnknownLength,
+			bincode::ErrorKind::InvalidUtf8Encoding(_)
+			| bincode::ErrorKind::InvalidBoolEncoding(_)
+			| bincode::ErrorKind::InvalidCharEncoding
+			| bincode::ErrorKind::InvalidTagEncoding(_) => Error::Invalid(err.to_string()),
+			bincode::ErrorKind::Custom(msg) => Error::Message(msg),
+			_ => Error::Message(err.to_string()),
+		}
+	}
+}
+
+fn options() -> impl Options {
+	bincode::DefaultOptions::new().with_varint_encoding().with_little_endian()
+}
+
+/// Encode any serializable value.
+pub fn encode<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, Error> {
+	Ok(options().serialize(value)?)
+}
+
+/// Decode a value of the type it was encoded from, which must use all of the
+/// input.
+pub fn decode<T: DeserializeOwned>(mut input: &[u8]) -> Result<T, Error> {
+	let value = options().deserialize_from(&mut input)?;
+	if input.is_empty() {
+		Ok(value)
+	} else {
+		Err(Error::TrailingBytes)
+	}
+}
+
+/// Encode the result of a comparison, writing no bytes if there were no
+/// changes.
+pub fn encode_changes<C: Serialize>(changes: &Changed<C>) -> Result<Vec<u8>, Error> {
+	match changes {
+		Changed::Unchanged => Ok(Vec::new()),
+		Changed::Changed(change) => {
+			let mut output = vec![CHANGED_TAG];
+			options().serialize_into(&mut output, change)?;
+			Ok(output)
+		}
+	}
+}
+
+/// Decode the result of a comparison encoded by [`encode_changes`].
+pub fn decode_changes<C: DeserializeOwned>(input: &[u8]) -> Result<Changed<C>, Error> {
+	match input.split_first() {
+		None => Ok(Changed::Unchanged),
+		Some((&CHANGED_TAG, change)) => decode(change).map(Changed::Changed),
+		Some((tag, _)) => Err(Error::Invalid(format!("invalid tag {} for a change", tag))),
+	}
+}
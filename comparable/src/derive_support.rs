@@ -0,0 +1,217 @@
+//! The items that code generated by `#[derive(Comparable)]` relies on.
+//!
+//! Derived implementations call a few helpers that are of no use to most
+//! users, but which implementations of [`Comparable`] written by hand need in
+//! order to behave like derived ones: honoring runtime field masks, gathering
+//! the changes of several fields, and describing recursive schemas. Generated
+//! code refers to these helpers only through this module, whose items follow
+//! semantic versioning like the rest of the crate, so that code generated by
+//! one release of `comparable_derive` keeps building with later compatible
+//! releases of `comparable`, and hand-written implementations interoperate
+//! with derived ones.
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! use comparable::derive_support::masked;
+//! use comparable::mask::FieldMask;
+//!
+//! // A point compared by hand, which honors field masks as derived
+//! // implementations do.
+//! #[derive(PartialEq, Debug)]
+//! struct Point {
+//!     x: i32,
+//!     y: i32,
+//! }
+//!
+//! # #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+//! #[derive(PartialEq, Debug)]
+//! enum PointChange {
+//!     X(I32Change),
+//!     Y(I32Change),
+//! }
+//!
+//! impl Comparable for Point {
+//!     type Desc = (i32, i32);
+//!
+//!     fn describe(&self) -> Self::Desc {
+//!         (self.x, self.y)
+//!     }
+//!
+//!     type Change = Vec<PointChange>;
+//!
+//!     fn comparison(&self, other: &Self) -> Changed<Self::Change> {
+//!         let mut changes = Vec::new();
+//!         changes.extend(masked("x", || self.x.comparison(&other.x)).map(PointChange::X));
+//!         changes.extend(masked("y", || self.y.comparison(&other.y)).map(PointChange::Y));
+//!         Changed::from_changes(changes)
+//!     }
+//! }
+//!
+//! #[derive(Comparable)]
+//! struct Line {
+//!     start: Point,
+//!     end: Point,
+//! }
+//!
+//! let before = Line { start: Point { x: 0, y: 0 }, end: Point { x: 1, y: 1 } };
+//! let after = Line { start: Point { x: 0, y: 5 }, end: Point { x: 2, y: 1 } };
+//! assert_eq!(
+//!     before.comparison_masked(&after, &FieldMask::include(["start.x", "end.x"])),
+//!     Changed::Changed(vec![LineChange::End(vec![PointChange::X(I32Change(1, 2))])])
+//! );
+//! ```
+
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use crate::lens::Lens;
+use crate::schema::Schema;
+use crate::string::StringChange;
+
+pub use crate::types::{Changed, Comparable, Redacted};
+
+/// Compare the field named `field` using `compare`, unless the field mask in
+/// effect excludes it, in which case it is unchanged. Within `compare`, the
+/// fields of the field's value are masked by the parts of the mask under
+/// `field`.
+pub fn masked<C>(field: &str, compare: impl FnOnce() -> Changed<C>) -> Changed<C> {
+	crate::mask::masked(field, compare)
+}
+
+/// Like [`masked`], for the other methods of [`Comparable`], where
+/// `masked_out` is the result for a field that is excluded.
+pub fn masked_or<R>(field: &str, masked_out: R, compare: impl FnOnce() -> R) -> R {
+	crate::mask::masked_or(field, masked_out, compare)
+}
+
+/// The schema of the type `T`, named `name`, built by `build` unless it is
+/// already being built, in which case `T` appears within its own schema and
+/// is described by [`Schema::Recursive`].
+pub fn named_schema<T: ?Sized>(name: &str, build: impl FnOnce() -> Schema) -> Schema {
+	crate::schema::named::<T>(name, build)
+}
+
+/// A lens for the field named `name`, reached using the given functions.
+pub fn lens<Parent: Comparable, Field: Comparable>(
+	name: &'static str,
+	get: fn(&Parent) -> &Field,
+	get_mut: fn(&mut Parent) -> &mut Field,
+	change: fn(&Parent::Change) -> Option<&Field::Change>,
+) -> Lens<Parent, Field> {
+	Lens::new(name, get, get_mut, change)
+}
+
+/// A value compared by projections of it, for fields marked with
+/// `#[comparable_by(desc = "...", eq = "...")]`, whose types, such as trait
+/// objects, cannot implement [`Comparable`] themselves. The value is
+/// described by the string given by `desc`, and two values differ when the
+/// keys given by `eq` do, with the change being that of their descriptions.
+pub struct By<'a, T: ?Sized, K> {
+	value: &'a T,
+	desc: fn(&T) -> String,
+	eq: fn(&T) -> K,
+}
+
+/// The value `value`, compared by the given projections.
+pub fn by<T: ?Sized, K: PartialEq>(value: &T, desc: fn(&T) -> String, eq: fn(&T) -> K) -> By<'_, T, K> {
+	By { value, desc, eq }
+}
+
+impl<T: ?Sized, K: PartialEq> Comparable for By<'_, T, K> {
+	type Desc = String;
+
+	fn describe(&self) -> Self::Desc {
+		(self.desc)(self.value)
+	}
+
+	type Change = StringChange;
+
+	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
+		if (self.eq)(self.value) == (other.eq)(other.value) {
+			Changed::Unchanged
+		} else {
+			Changed::Changed(StringChange(self.describe(), other.describe()))
+		}
+	}
+}
+
+/// A value of a type implementing [`Comparable`] through
+/// [`impl_comparable_for_leaf!`](crate::impl_comparable_for_leaf), whose
+/// [`Comparable::fingerprint`] is written
+/// `(&Fingerprint(value)).fingerprint()`, with [`ByHash`] and [`NoHash`] in
+/// scope. This is the value's hash if its type implements `Hash`, as is
+/// consistent with the `PartialEq` it is compared by, and `None` otherwise.
+pub struct Fingerprint<'a, T>(pub &'a T);
+
+/// The fingerprint of a value whose type implements `Hash`, which is chosen
+/// over [`NoHash`] when it applies.
+pub trait ByHash {
+	fn fingerprint(&self) -> Option<u64>;
+}
+
+impl<T: Hash> ByHash for Fingerprint<'_, T> {
+	fn fingerprint(&self) -> Option<u64> {
+		Some(crate::light::hash_of(self.0))
+	}
+}
+
+/// The fingerprint of a value whose type does not implement `Hash`.
+pub trait NoHash {
+	fn fingerprint(&self) -> Option<u64>;
+}
+
+impl<T> NoHash for &Fingerprint<'_, T> {
+	fn fingerprint(&self) -> Option<u64> {
+		None
+	}
+}
+
+/// A value compared as a whole, as [`Leaf`](crate::Leaf) values are, for
+/// fields marked with `#[comparable_leaf]`, without moving it out of the
+/// value containing it.
+pub struct LeafRef<'a, T>(&'a T);
+
+/// The value `value`, compared as a whole.
+pub fn leaf<T>(value: &T) -> LeafRef<'_, T> {
+	LeafRef(value)
+}
+
+impl<T: PartialEq + Clone + Debug> Comparable for LeafRef<'_, T> {
+	type Desc = T;
+
+	fn describe(&self) -> Self::Desc {
+		self.0.clone()
+	}
+
+	type Change = (T, T);
+
+	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
+		crate::leaf::compare(self.0, other.0)
+	}
+}
+
+#[cfg(feature = "serde")]
+pub use crate::versioned::{DeserializeUnversioned, SerializeUnversioned};
+
+/// Serialize a value of a type with `comparable_version`, along with its
+/// version.
+#[cfg(feature = "serde")]
+pub fn serialize_versioned<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+	T: SerializeUnversioned + ?Sized,
+	S: serde::Serializer,
+{
+	crate::versioned::serialize(value, serializer)
+}
+
+/// Deserialize a value of a type with `comparable_version`, migrating it
+/// from the version it was serialized with.
+#[cfg(feature = "serde")]
+pub fn deserialize_versioned<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+	T: DeserializeUnversioned<'de>,
+	D: serde::Deserializer<'de>,
+{
+	crate::versioned::deserialize(deserializer)
+}
@@ -0,0 +1,32 @@
+//! Describing values independently of comparing them.
+
+use std::fmt::Debug;
+
+use crate::types::Comparable;
+
+#[cfg(feature = "comparable_derive")]
+pub use comparable_derive::Describe;
+
+/// The describing half of [`Comparable`], for types that only need a
+/// description of their values, such as a serializable snapshot, without the
+/// means to compare them. It can be derived with `#[derive(Describe)]`, which
+/// generates only the `Desc` type and the `describe` method, and accepts the
+/// same attributes as `#[derive(Comparable)]` for influencing the `Desc` type.
+///
+/// Every type implementing [`Comparable`] also implements `Describe`, in the
+/// same way, so a type should derive one or the other but not both. Since
+/// both traits have a `describe` method, bring only one of them into scope,
+/// or call them as `Describe::describe(&value)`.
+pub trait Describe {
+	type Desc: PartialEq + Debug;
+
+	fn describe(&self) -> Self::Desc;
+}
+
+impl<T: Comparable + ?Sized> Describe for T {
+	type Desc = T::Desc;
+
+	fn describe(&self) -> Self::Desc {
+		Comparable::describe(self)
+	}
+}
@@ -0,0 +1,310 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use crate::types::Comparable;
+
+/// An algorithm for aligning the elements of two sequences, so that elements
+/// inserted or removed in the middle of a `Vec` are reported as such, rather
+/// than as changes to every element after them. See
+/// [`DiffOptions::sequence_algorithm`].
+///
+/// [`DiffOptions::sequence_algorithm`]: crate::DiffOptions::sequence_algorithm
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DiffAlgorithm {
+	/// Myers' algorithm, which finds a shortest edit script, but may align
+	/// common elements such as blank lines or closing braces in ways that look
+	/// unnatural.
+	Myers,
+	/// Patience diff, which first aligns the elements occurring exactly once in
+	/// both sequences, and uses Myers' algorithm between them. It is slower, but
+	/// its results follow the structure of the sequences more closely.
+	Patience,
+	/// Histogram diff, which extends patience diff to align the least frequent
+	/// elements first even when they are not unique. It usually gives results
+	/// as good as patience diff, more quickly.
+	Histogram,
+}
+
+/// The pairs of indices at which elements of `before` and `after` are
+/// unchanged, in increasing order of both.
+pub(crate) fn align<T: Comparable>(before: &[T], after: &[T], algorithm: DiffAlgorithm) -> Vec<(usize, usize)> {
+	let (a, b) = classify(before, after);
+	let mut matches = Vec::new();
+	match algorithm {
+		DiffAlgorithm::Myers => myers(&a, &b, 0, 0, &mut matches),
+		DiffAlgorithm::Patience => patience(&a, &b, 0, 0, &mut matches),
+		DiffAlgorithm::Histogram => histogram(&a, &b, 0, 0, &mut matches),
+	}
+	matches
+}
+
+/// The pairs of indices of elements of `before` and `after` having at least
+/// the given similarity, in increasing order of both, chosen to pair as many
+/// elements as possible, favoring the most similar.
+pub(crate) fn pair_similar<T: Comparable>(before: &[T], after: &[T], threshold: f64) -> Vec<(usize, usize)> {
+	let similarity = |i: usize, j: usize| Some(before[i].similarity(&after[j])).filter(|s| *s >= threshold);
+	// best[i][j] is the greatest score of pairs within the first `i` elements
+	// of `before` and the first `j` elements of `after`, where each pair
+	// scores one more than its similarity.
+	let mut best = vec![vec![0.0; after.len() + 1]; before.len() + 1];
+	for i in 0..before.len() {
+		for j in 0..after.len() {
+			let paired = similarity(i, j).map_or(f64::NEG_INFINITY, |s| best[i][j] + 1.0 + s);
+			best[i + 1][j + 1] = paired.max(best[i][j + 1]).max(best[i + 1][j]);
+		}
+	}
+	let mut result = Vec::new();
+	let (mut i, mut j) = (before.len(), after.len());
+	while i > 0 && j > 0 {
+		if best[i][j] == best[i - 1][j] {
+			i -= 1;
+		} else if best[i][j] == best[i][j - 1] {
+			j -= 1;
+		} else {
+			i -= 1;
+			j -= 1;
+			result.push((i, j));
+		}
+	}
+	result.reverse();
+	result
+}
+
+// Number each element so that elements that are unchanged from one another
+// share a number, which lets the algorithms below compare and count elements
+// cheaply. Elements with fingerprints are compared only with the classes
+// sharing theirs, and others with every class found so far.
+fn classify<'a, T: Comparable>(before: &'a [T], after: &'a [T]) -> (Vec<usize>, Vec<usize>) {
+	let mut classes: Vec<&'a T> = Vec::new();
+	let mut fingerprints: HashMap<u64, Vec<usize>> = HashMap::new();
+	let mut class_of = |value: &'a T| {
+		let fingerprint = value.fingerprint();
+		let found = match fingerprint.and_then(|fingerprint| fingerprints.get(&fingerprint)) {
+			Some(candidates) => candidates.iter().copied().find(|class| !classes[*class].differs(value)),
+			None if fingerprint.is_some() => None,
+			None => classes.iter().position(|class| !(*class).differs(value)),
+		};
+		found.unwrap_or_else(|| {
+			classes.push(value);
+			if let Some(fingerprint) = fingerprint {
+				fingerprints.entry(fingerprint).or_default().push(classes.len() - 1);
+			}
+			classes.len() - 1
+		})
+	};
+	let a = before.iter().map(&mut class_of).collect();
+	let b = after.iter().map(&mut class_of).collect();
+	(a, b)
+}
+
+// Each algorithm below pushes the matches found between `a` and `b` onto
+// `matches`, offset by the positions `a0` and `b0` at which `a` and `b` begin
+// in the sequences being aligned.
+type Align = fn(&[usize], &[usize], usize, usize, &mut Vec<(usize, usize)>);
+
+// Myers' algorithm in linear space: the middle snake of a shortest edit
+// script is found by searching from both ends at once, and the sequences
+// before and after it are aligned in turn, keeping only the furthest points
+// reached along each diagonal rather than those of every step.
+fn myers(a: &[usize], b: &[usize], a0: usize, b0: usize, matches: &mut Vec<(usize, usize)>) {
+	let (mut forward, mut backward) = (Vec::new(), Vec::new());
+	myers_within(a, b, a0, b0, matches, &mut forward, &mut backward)
+}
+
+fn myers_within(
+	a: &[usize],
+	b: &[usize],
+	a0: usize,
+	b0: usize,
+	matches: &mut Vec<(usize, usize)>,
+	forward: &mut Vec<usize>,
+	backward: &mut Vec<usize>,
+) {
+	let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+	let suffix = a[prefix..].iter().rev().zip(b[prefix..].iter().rev()).take_while(|(x, y)| x == y).count();
+	matches.extend((0..prefix).map(|i| (a0 + i, b0 + i)));
+	let (a_mid, b_mid) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+	if !a_mid.is_empty() && !b_mid.is_empty() {
+		let (x, y) = middle_snake(a_mid, b_mid, forward, backward);
+		let (a1, b1) = (a0 + prefix, b0 + prefix);
+		myers_within(&a_mid[..x], &b_mid[..y], a1, b1, matches, forward, backward);
+		myers_within(&a_mid[x..], &b_mid[y..], a1 + x, b1 + y, matches, forward, backward);
+	}
+	let (a_end, b_end) = (a0 + a.len() - suffix, b0 + b.len() - suffix);
+	matches.extend((0..suffix).map(|i| (a_end + i, b_end + i)));
+}
+
+// Where a shortest edit script from `a` to `b`, which differ at both ends,
+// reaches the start of its middle snake, which is neither the start nor the
+// end of both. `forward` and `backward` hold, for each diagonal `k`, the
+// furthest `x` reached from each end, and are reused between calls.
+fn middle_snake(a: &[usize], b: &[usize], forward: &mut Vec<usize>, backward: &mut Vec<usize>) -> (usize, usize) {
+	let (n, m) = (a.len() as isize, b.len() as isize);
+	let delta = n - m;
+	let odd = delta % 2 != 0;
+	let max = (n + m + 1) / 2 + 1;
+	let index = |k: isize| (k + max) as usize;
+	for v in [&mut *forward, &mut *backward] {
+		v.clear();
+		v.resize(2 * max as usize + 2, 0);
+	}
+	for d in 0..max {
+		for k in (-d..=d).rev().step_by(2) {
+			let mut x = if k == -d || (k != d && forward[index(k - 1)] < forward[index(k + 1)]) {
+				forward[index(k + 1)]
+			} else {
+				forward[index(k - 1)] + 1
+			} as isize;
+			let (x0, y0) = (x, x - k);
+			let mut y = y0;
+			while x < n && y < m && a[x as usize] == b[y as usize] {
+				x += 1;
+				y += 1;
+			}
+			forward[index(k)] = x as usize;
+			if odd && (k - delta).abs() < d && x + backward[index(delta - k)] as isize >= n {
+				return (x0 as usize, y0 as usize);
+			}
+		}
+		for k in (-d..=d).rev().step_by(2) {
+			let mut x = if k == -d || (k != d && backward[index(k - 1)] < backward[index(k + 1)]) {
+				backward[index(k + 1)]
+			} else {
+				backward[index(k - 1)] + 1
+			} as isize;
+			let mut y = x - k;
+			while x < n && y < m && a[(n - x - 1) as usize] == b[(m - y - 1) as usize] {
+				x += 1;
+				y += 1;
+			}
+			backward[index(k)] = x as usize;
+			if !odd && (k - delta).abs() <= d && x + forward[index(delta - k)] as isize >= n {
+				return ((n - x) as usize, (m - y) as usize);
+			}
+		}
+	}
+	unreachable!("the searches from both ends always meet")
+}
+
+// Match the elements common to the start and end of both sequences, calling
+// `middle` on what lies between them.
+fn trim(a: &[usize], b: &[usize], a0: usize, b0: usize, matches: &mut Vec<(usize, usize)>, middle: Align) {
+	let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+	let suffix = a[prefix..].iter().rev().zip(b[prefix..].iter().rev()).take_while(|(x, y)| x == y).count();
+	matches.extend((0..prefix).map(|i| (a0 + i, b0 + i)));
+	let (a_end, b_end) = (a.len() - suffix, b.len() - suffix);
+	if prefix < a_end && prefix < b_end {
+		middle(&a[prefix..a_end], &b[prefix..b_end], a0 + prefix, b0 + prefix, matches);
+	}
+	matches.extend((0..suffix).map(|i| (a0 + a_end + i, b0 + b_end + i)));
+}
+
+fn patience(a: &[usize], b: &[usize], a0: usize, b0: usize, matches: &mut Vec<(usize, usize)>) {
+	trim(a, b, a0, b0, matches, patience_middle)
+}
+
+fn patience_middle(a: &[usize], b: &[usize], a0: usize, b0: usize, matches: &mut Vec<(usize, usize)>) {
+	// For each element, how often it occurs in `a` and `b`, and where.
+	let mut occurrences: HashMap<usize, (usize, usize, usize, usize)> = HashMap::new();
+	for (i, x) in a.iter().enumerate() {
+		let entry = occurrences.entry(*x).or_insert((0, i, 0, 0));
+		entry.0 += 1;
+	}
+	for (j, y) in b.iter().enumerate() {
+		if let Some(entry) = occurrences.get_mut(y) {
+			entry.2 += 1;
+			entry.3 = j;
+		}
+	}
+	let unique: Vec<(usize, usize)> = a
+		.iter()
+		.filter_map(|x| match occurrences[x] {
+			(1, i, 1, j) => Some((i, j)),
+			_ => None,
+		})
+		.collect();
+	if unique.is_empty() {
+		return myers(a, b, a0, b0, matches);
+	}
+	let (mut i0, mut j0) = (0, 0);
+	for (i, j) in longest_increasing(&unique) {
+		patience(&a[i0..i], &b[j0..j], a0 + i0, b0 + j0, matches);
+		matches.push((a0 + i, b0 + j));
+		i0 = i + 1;
+		j0 = j + 1;
+	}
+	patience(&a[i0..], &b[j0..], a0 + i0, b0 + j0, matches);
+}
+
+// The longest subsequence of `pairs`, which are in increasing order of their
+// first index, that is also in increasing order of their second.
+fn longest_increasing(pairs: &[(usize, usize)]) -> Vec<(usize, usize)> {
+	// The last pair of the best subsequence found of each length, and the pair
+	// preceding each pair in the subsequence it ends.
+	let mut tails: Vec<usize> = Vec::new();
+	let mut previous: Vec<Option<usize>> = vec![None; pairs.len()];
+	for (p, (_, j)) in pairs.iter().enumerate() {
+		let length = tails.partition_point(|t| pairs[*t].1 < *j);
+		previous[p] = length.checked_sub(1).map(|l| tails[l]);
+		if length == tails.len() {
+			tails.push(p);
+		} else {
+			tails[length] = p;
+		}
+	}
+	let mut result = Vec::new();
+	let mut next = tails.last().copied();
+	while let Some(p) = next {
+		result.push(pairs[p]);
+		next = previous[p];
+	}
+	result.reverse();
+	result
+}
+
+// Elements occurring more often than this are not used to align the
+// sequences, and if there are no others, Myers' algorithm is used instead.
+const MAX_OCCURRENCES: usize = 64;
+
+fn histogram(a: &[usize], b: &[usize], a0: usize, b0: usize, matches: &mut Vec<(usize, usize)>) {
+	trim(a, b, a0, b0, matches, histogram_middle)
+}
+
+fn histogram_middle(a: &[usize], b: &[usize], a0: usize, b0: usize, matches: &mut Vec<(usize, usize)>) {
+	let mut positions: HashMap<usize, Vec<usize>> = HashMap::new();
+	for (i, x) in a.iter().enumerate() {
+		positions.entry(*x).or_default().push(i);
+	}
+	// Find the longest run of matching elements containing the element that
+	// occurs least often in `a`.
+	let mut best: Option<(usize, usize, usize, usize)> = None;
+	let mut common = false;
+	for (j, y) in b.iter().enumerate() {
+		let candidates = match positions.get(y) {
+			Some(candidates) => candidates,
+			None => continue,
+		};
+		common = true;
+		if candidates.len() > MAX_OCCURRENCES {
+			continue;
+		}
+		for i in candidates {
+			let back = a[..*i].iter().rev().zip(b[..j].iter().rev()).take_while(|(x, y)| x == y).count();
+			let forward = a[*i..].iter().zip(&b[j..]).take_while(|(x, y)| x == y).count();
+			let (start_a, start_b, len) = (i - back, j - back, back + forward);
+			let rarest = a[start_a..start_a + len].iter().map(|x| positions[x].len()).min().unwrap_or(usize::MAX);
+			if best.is_none_or(|(count, _, _, best_len)| (rarest, Reverse(len)) < (count, Reverse(best_len))) {
+				best = Some((rarest, start_a, start_b, len));
+			}
+		}
+	}
+	match best {
+		Some((_, i, j, len)) => {
+			histogram(&a[..i], &b[..j], a0, b0, matches);
+			matches.extend((0..len).map(|k| (a0 + i + k, b0 + j + k)));
+			histogram(&a[i + len..], &b[j + len..], a0 + i + len, b0 + j + len, matches);
+		}
+		None if common => myers(a, b, a0, b0, matches),
+		None => {}
+	}
+}
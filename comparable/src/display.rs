@@ -0,0 +1,227 @@
+//! Rendering changes for people to read.
+//!
+//! The `Debug` format of a change mirrors its structure, which is precise but
+//! hard to take in at a glance. [`DisplayChange`] instead renders each change
+//! as a line naming where it happened and what it was, such as `age: 41 → 42`
+//! or `tags[2]: added "new"`, and [`display`] joins these lines with `; `.
+//!
+//! With `#[comparable_display]`, `#[derive(Comparable)]` implements
+//! [`DisplayChange`] and [`std::fmt::Display`] for the generated change type,
+//! so that its changes can be written using `{}` directly.
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! #[derive(Comparable)]
+//! #[comparable_display]
+//! struct Person {
+//!     name: String,
+//!     age: u32,
+//! }
+//!
+//! let before = Person { name: "Alice".to_string(), age: 41 };
+//! let after = Person { name: "Alice".to_string(), age: 42 };
+//! let changes = before.comparison(&after).unwrap_or_default();
+//! assert_eq!(changes[0].to_string(), "age: 41 → 42");
+//! assert_eq!(comparable::display::display(&changes).to_string(), "age: 41 → 42");
+//! ```
+
+use std::fmt::{self, Debug};
+
+use crate::fs::FileMetadataChange;
+use crate::location::{Path, Segment};
+use crate::path::PathBufChange;
+use crate::scalar::*;
+use crate::string::StringChange;
+use crate::types::{Changed, Redacted};
+use crate::{MapChange, OptionChange, RangeChange, SetChange, VecChange};
+
+/// Changes that can be rendered as lines of text, one for each change.
+pub trait DisplayChange {
+	/// Add a line to `lines` for each change, where `path` is the location of
+	/// the value changed within the value compared.
+	fn display_changes(&self, path: &Path, lines: &mut Vec<String>);
+}
+
+/// Changes rendered by [`display`].
+pub struct Displayed<'a, C: ?Sized>(&'a C);
+
+/// The changes rendered as lines, joined with `; `.
+pub fn display<C: DisplayChange + ?Sized>(change: &C) -> Displayed<'_, C> {
+	Displayed(change)
+}
+
+impl<C: DisplayChange + ?Sized> fmt::Display for Displayed<'_, C> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let mut lines = Vec::new();
+		self.0.display_changes(&Path::default(), &mut lines);
+		f.write_str(&lines.join("; "))
+	}
+}
+
+/// Add the line for a change at `path`, described by `text`.
+pub fn push_line(lines: &mut Vec<String>, path: &Path, text: impl fmt::Display) {
+	if path.is_empty() {
+		lines.push(text.to_string());
+	} else {
+		lines.push(format!("{}: {}", path, text));
+	}
+}
+
+/// Add the line for a value at `path` replaced by another, written using
+/// their `Debug` format.
+pub fn push_replaced(lines: &mut Vec<String>, path: &Path, before: &dyn Debug, after: &dyn Debug) {
+	push_line(lines, path, format_args!("{:?} → {:?}", before, after))
+}
+
+impl DisplayChange for () {
+	fn display_changes(&self, _path: &Path, _lines: &mut Vec<String>) {}
+}
+
+/// Redacted fields are reported as changed, without their values.
+impl DisplayChange for Redacted {
+	fn display_changes(&self, path: &Path, lines: &mut Vec<String>) {
+		push_line(lines, path, "changed")
+	}
+}
+
+macro_rules! impl_display_for_self_describing {
+	($($change:ident),*) => {
+		$(
+			impl DisplayChange for $change {
+				fn display_changes(&self, path: &Path, lines: &mut Vec<String>) {
+					push_replaced(lines, path, &self.0, &self.1)
+				}
+			}
+		)*
+	};
+}
+
+impl_display_for_self_describing!(F32Change, F64Change, StringChange, PathBufChange);
+
+/// The changes of [`Leaf`](crate::Leaf) values, which hold the values before
+/// and after.
+impl<T: Debug> DisplayChange for (T, T) {
+	fn display_changes(&self, path: &Path, lines: &mut Vec<String>) {
+		push_replaced(lines, path, &self.0, &self.1)
+	}
+}
+
+#[cfg(feature = "ordered-float")]
+impl<T: Debug> DisplayChange for crate::numeric::OrderedFloatChange<T> {
+	fn display_changes(&self, path: &Path, lines: &mut Vec<String>) {
+		push_replaced(lines, path, &self.0, &self.1)
+	}
+}
+
+#[cfg(feature = "rust_decimal")]
+impl DisplayChange for crate::numeric::DecimalChange {
+	fn display_changes(&self, path: &Path, lines: &mut Vec<String>) {
+		push_replaced(lines, path, &self.0, &self.1)
+	}
+}
+
+impl<C: DisplayChange + ?Sized> DisplayChange for Box<C> {
+	fn display_changes(&self, path: &Path, lines: &mut Vec<String>) {
+		(**self).display_changes(path, lines)
+	}
+}
+
+impl<C: DisplayChange> DisplayChange for Changed<C> {
+	fn display_changes(&self, path: &Path, lines: &mut Vec<String>) {
+		if let Changed::Changed(change) = self {
+			change.display_changes(path, lines)
+		}
+	}
+}
+
+/// The changes of a struct with several fields, or of a collection, are
+/// rendered in order.
+impl<C: DisplayChange> DisplayChange for Vec<C> {
+	fn display_changes(&self, path: &Path, lines: &mut Vec<String>) {
+		for change in self {
+			change.display_changes(path, lines)
+		}
+	}
+}
+
+/// The changes of an array are those of its elements, by index.
+impl<C: DisplayChange, const N: usize> DisplayChange for [C; N] {
+	fn display_changes(&self, path: &Path, lines: &mut Vec<String>) {
+		for (index, change) in self.iter().enumerate() {
+			change.display_changes(&path.child(Segment::Index(index)), lines)
+		}
+	}
+}
+
+impl<Desc: Debug, C: DisplayChange> DisplayChange for OptionChange<Desc, C> {
+	fn display_changes(&self, path: &Path, lines: &mut Vec<String>) {
+		match self {
+			OptionChange::BothSome(change) => change.display_changes(path, lines),
+			OptionChange::Different(before, after) => push_replaced(lines, path, before, after),
+		}
+	}
+}
+
+impl<C: DisplayChange> DisplayChange for RangeChange<C> {
+	fn display_changes(&self, path: &Path, lines: &mut Vec<String>) {
+		match self {
+			RangeChange::Start(change) => {
+				change.display_changes(&path.child(Segment::Field("start".to_string())), lines)
+			}
+			RangeChange::End(change) => change.display_changes(&path.child(Segment::Field("end".to_string())), lines),
+		}
+	}
+}
+
+impl DisplayChange for FileMetadataChange {
+	fn display_changes(&self, path: &Path, lines: &mut Vec<String>) {
+		let field = |name: &str| path.child(Segment::Field(name.to_string()));
+		match self {
+			FileMetadataChange::Size(change) => change.display_changes(&field("size"), lines),
+			FileMetadataChange::Modified(before, after) => push_replaced(lines, &field("modified"), before, after),
+			FileMetadataChange::Hash(change) => change.display_changes(&field("hash"), lines),
+		}
+	}
+}
+
+impl<Desc: Debug, C: DisplayChange> DisplayChange for VecChange<Desc, C> {
+	fn display_changes(&self, path: &Path, lines: &mut Vec<String>) {
+		match self {
+			VecChange::Added(index, desc) => {
+				push_line(lines, &path.child(Segment::Index(*index)), format_args!("added {:?}", desc))
+			}
+			VecChange::Changed(index, change) => change.display_changes(&path.child(Segment::Index(*index)), lines),
+			VecChange::Removed(index, desc) => {
+				push_line(lines, &path.child(Segment::Index(*index)), format_args!("removed {:?}", desc))
+			}
+			VecChange::Moved { from, to } => {
+				push_line(lines, &path.child(Segment::Index(*from)), format_args!("moved to [{}]", to))
+			}
+		}
+	}
+}
+
+impl<Desc: Debug> DisplayChange for SetChange<Desc> {
+	fn display_changes(&self, path: &Path, lines: &mut Vec<String>) {
+		match self {
+			SetChange::Added(desc) => push_line(lines, path, format_args!("added {:?}", desc)),
+			SetChange::Removed(desc) => push_line(lines, path, format_args!("removed {:?}", desc)),
+		}
+	}
+}
+
+impl<Key: Debug, Desc: Debug, C: DisplayChange> DisplayChange for MapChange<Key, Desc, C> {
+	fn display_changes(&self, path: &Path, lines: &mut Vec<String>) {
+		let entry = |key: &Key| path.child(Segment::Key(format!("{:?}", key)));
+		match self {
+			MapChange::Added(key, desc) => push_line(lines, &entry(key), format_args!("added {:?}", desc)),
+			MapChange::Changed(key, change) => change.display_changes(&entry(key), lines),
+			MapChange::Removed(key) => push_line(lines, &entry(key), "removed"),
+			MapChange::Moved { from, to, change } => {
+				push_line(lines, &entry(from), format_args!("moved to [{:?}]", to));
+				change.display_changes(&entry(to), lines)
+			}
+		}
+	}
+}
@@ -0,0 +1,68 @@
+//! Reporting drift in the environment of a process.
+//!
+//! A [`snapshot`] of the environment variables is a map from their names to
+//! their values, which compares as any other map does. Deployment tools that
+//! keep one snapshot from run to run can report how the environment drifted
+//! since using [`report`], which renders the changes one variable to a line.
+//!
+//! ```
+//! use comparable::env;
+//!
+//! let before = env::snapshot();
+//! std::env::set_var("COMPARABLE_ENV_DOC", "1");
+//! assert_eq!(env::report(&before), Some("+ COMPARABLE_ENV_DOC=1".to_string()));
+//! # std::env::remove_var("COMPARABLE_ENV_DOC");
+//! ```
+
+use std::collections::BTreeMap;
+
+use crate::map::MapChange;
+use crate::string::StringChange;
+use crate::types::{Changed, Comparable};
+
+/// A change to an environment variable, by its name.
+pub type EnvChange = MapChange<String, String, StringChange>;
+
+/// The environment variables of this process, by name. Names and values that
+/// are not valid Unicode are converted lossily, rather than causing a panic as
+/// `std::env::vars` would.
+pub fn snapshot() -> BTreeMap<String, String> {
+	std::env::vars_os()
+		.map(|(name, value)| (name.to_string_lossy().into_owned(), value.to_string_lossy().into_owned()))
+		.collect()
+}
+
+/// The changes from `before` to the current environment, rendered using
+/// [`render`], or `None` if there are none.
+pub fn report(before: &BTreeMap<String, String>) -> Option<String> {
+	match before.comparison(&snapshot()) {
+		Changed::Changed(changes) => Some(render(&changes)),
+		Changed::Unchanged => None,
+	}
+}
+
+/// Render changes to the environment one variable to a line: `+ NAME=value`
+/// for a variable set, `- NAME` for one unset, and `~ NAME: old → new` for
+/// one whose value changed. A variable renamed, as reported when
+/// [`DiffOptions::map_moves`] is in effect, is written `> OLD → NEW`.
+///
+/// [`DiffOptions::map_moves`]: crate::DiffOptions::map_moves
+pub fn render(changes: &[EnvChange]) -> String {
+	let mut lines = Vec::new();
+	for change in changes {
+		match change {
+			MapChange::Added(name, value) => lines.push(format!("+ {}={}", name, value)),
+			MapChange::Changed(name, StringChange(before, after)) => {
+				lines.push(format!("~ {}: {} → {}", name, before, after))
+			}
+			MapChange::Removed(name) => lines.push(format!("- {}", name)),
+			MapChange::Moved { from, to, change } => {
+				lines.push(format!("> {} → {}", from, to));
+				if let Changed::Changed(StringChange(before, after)) = change {
+					lines.push(format!("~ {}: {} → {}", to, before, after))
+				}
+			}
+		}
+	}
+	lines.join("\n")
+}
@@ -0,0 +1,131 @@
+//! Comparisons that can fail.
+//!
+//! Some values cannot always be described or compared, such as those behind
+//! a [`Mutex`] that a panic has poisoned. [`TryComparable`] is a version of
+//! [`Comparable`] whose methods return a [`Result`], and is implemented for
+//! every [`Comparable`] type, which never fails, as well as for locks of
+//! them. Deriving [`Comparable`] with `#[comparable_try]` implements
+//! [`TryComparable`] instead, so that such values may be fields:
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! use comparable::fallible::TryComparable;
+//! use std::sync::Mutex;
+//!
+//! #[derive(Comparable)]
+//! #[comparable_try]
+//! struct Counter {
+//!     name: String,
+//!     count: Mutex<u32>,
+//! }
+//!
+//! let before = Counter { name: "a".to_string(), count: Mutex::new(1) };
+//! let after = Counter { name: "a".to_string(), count: Mutex::new(2) };
+//! assert_eq!(
+//!     before.try_comparison(&after),
+//!     Ok(Changed::Changed(vec![CounterChange::Count(U32Change(1, 2))]))
+//! );
+//! ```
+
+use std::convert::Infallible;
+use std::fmt::{self, Debug};
+use std::sync::{Mutex, RwLock};
+
+use crate::types::{Changed, Comparable};
+
+/// Why a value could not be described or compared.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Error {
+	/// A lock holding the value was poisoned by a panic while it was held.
+	Poisoned,
+	/// Any other failure, such as a value that could not be decoded.
+	Other(String),
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Error::Poisoned => write!(f, "a lock holding the value was poisoned"),
+			Error::Other(message) => write!(f, "{}", message),
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+impl From<Infallible> for Error {
+	fn from(never: Infallible) -> Self {
+		match never {}
+	}
+}
+
+impl<T> From<std::sync::PoisonError<T>> for Error {
+	fn from(_: std::sync::PoisonError<T>) -> Self {
+		Error::Poisoned
+	}
+}
+
+/// A version of [`Comparable`] whose description and comparison may fail.
+pub trait TryComparable {
+	/// Describes values of this type, as [`Comparable::Desc`] does.
+	type Desc: PartialEq + Debug;
+
+	/// Describes the changes between values of this type, as
+	/// [`Comparable::Change`] does.
+	type Change: PartialEq + Debug;
+
+	/// Describe the value, if it can be.
+	fn try_describe(&self) -> Result<Self::Desc, Error>;
+
+	/// Compare this value with another, if both can be.
+	fn try_comparison(&self, other: &Self) -> Result<Changed<Self::Change>, Error>;
+}
+
+impl<T: Comparable + ?Sized> TryComparable for T {
+	type Desc = T::Desc;
+	type Change = T::Change;
+
+	fn try_describe(&self) -> Result<Self::Desc, Error> {
+		Ok(self.describe())
+	}
+
+	fn try_comparison(&self, other: &Self) -> Result<Changed<Self::Change>, Error> {
+		Ok(self.comparison(other))
+	}
+}
+
+// A lock compared with itself is unchanged, rather than being locked twice.
+impl<T: TryComparable> TryComparable for Mutex<T> {
+	type Desc = T::Desc;
+	type Change = T::Change;
+
+	fn try_describe(&self) -> Result<Self::Desc, Error> {
+		self.lock()?.try_describe()
+	}
+
+	fn try_comparison(&self, other: &Self) -> Result<Changed<Self::Change>, Error> {
+		if std::ptr::eq(self, other) {
+			return Ok(Changed::Unchanged);
+		}
+		let (value, other) = (self.lock()?, other.lock()?);
+		value.try_comparison(&other)
+	}
+}
+
+impl<T: TryComparable> TryComparable for RwLock<T> {
+	type Desc = T::Desc;
+	type Change = T::Change;
+
+	fn try_describe(&self) -> Result<Self::Desc, Error> {
+		self.read()?.try_describe()
+	}
+
+	fn try_comparison(&self, other: &Self) -> Result<Changed<Self::Change>, Error> {
+		if std::ptr::eq(self, other) {
+			return Ok(Changed::Unchanged);
+		}
+		let (value, other) = (self.read()?, other.read()?);
+		value.try_comparison(&other)
+	}
+}
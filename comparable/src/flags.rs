@@ -0,0 +1,111 @@
+use std::fmt::Debug;
+
+use bitflags::Flags;
+
+use crate::display::{push_replaced, DisplayChange};
+use crate::location::{Path, Segment};
+use crate::tree::{push_child, ChangeTree, ToTree};
+use crate::types::Changed;
+
+/// A change to a set of flags generated by `bitflags`, as the flags that were
+/// set and those that were cleared. See [`impl_comparable_for_bitflags`].
+///
+/// [`impl_comparable_for_bitflags`]: crate::impl_comparable_for_bitflags
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, Debug)]
+pub struct FlagsChange<F> {
+	pub added: F,
+	pub removed: F,
+}
+
+/// The flags set and cleared between `before` and `after`, which is how
+/// [`impl_comparable_for_bitflags`] compares them.
+///
+/// [`impl_comparable_for_bitflags`]: crate::impl_comparable_for_bitflags
+pub fn flags_comparison<F: Flags>(before: &F, after: &F) -> Changed<FlagsChange<F>> {
+	let added = F::from_bits_retain(after.bits()).difference(F::from_bits_retain(before.bits()));
+	let removed = F::from_bits_retain(before.bits()).difference(F::from_bits_retain(after.bits()));
+	if added.is_empty() && removed.is_empty() {
+		Changed::Unchanged
+	} else {
+		Changed::Changed(FlagsChange { added, removed })
+	}
+}
+
+impl<F: Flags> FlagsChange<F> {
+	// Each named flag that changed, and whether it was set.
+	fn changed_flags(&self) -> impl Iterator<Item = (&'static str, bool)> + '_ {
+		self.added
+			.iter_names()
+			.map(|(name, _)| (name, true))
+			.chain(self.removed.iter_names().map(|(name, _)| (name, false)))
+	}
+}
+
+/// Each flag that changed is rendered as a `bool` field named after it.
+impl<F: Flags + Debug> DisplayChange for FlagsChange<F> {
+	fn display_changes(&self, path: &Path, lines: &mut Vec<String>) {
+		for (name, set) in self.changed_flags() {
+			push_replaced(lines, &path.child(Segment::Field(name.to_string())), &!set, &set)
+		}
+	}
+}
+
+/// Each flag that changed is a `bool` child named after it.
+impl<F: Flags + Debug> ToTree for FlagsChange<F> {
+	fn to_tree(&self) -> ChangeTree {
+		let mut children = Vec::new();
+		for (name, set) in self.changed_flags() {
+			push_child(&mut children, name.to_string(), ChangeTree::modified(&!set, &set))
+		}
+		ChangeTree::Children(children)
+	}
+}
+
+/// Implement [`Comparable`] for types generated by the `bitflags!` macro,
+/// which are described by their own values, and whose changes are the
+/// [`FlagsChange`] giving the flags set and cleared, rather than the values
+/// before and after. The types must implement `Clone`, `Copy`, `PartialEq`
+/// and `Debug`.
+///
+/// ```
+/// # use comparable::*;
+/// bitflags::bitflags! {
+///     #[derive(Clone, Copy, PartialEq, Debug)]
+/// #   #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+///     struct Permissions: u8 {
+///         const READ = 1;
+///         const WRITE = 2;
+///         const EXECUTE = 4;
+///     }
+/// }
+///
+/// impl_comparable_for_bitflags!(Permissions);
+///
+/// assert_eq!(
+///     (Permissions::READ | Permissions::WRITE).comparison(&(Permissions::READ | Permissions::EXECUTE)),
+///     Changed::Changed(FlagsChange { added: Permissions::EXECUTE, removed: Permissions::WRITE })
+/// );
+/// ```
+///
+/// [`Comparable`]: crate::Comparable
+#[macro_export]
+macro_rules! impl_comparable_for_bitflags {
+	($($ty:ty),* $(,)?) => {
+		$(
+			impl $crate::Comparable for $ty {
+				type Desc = $ty;
+
+				fn describe(&self) -> Self::Desc {
+					*self
+				}
+
+				type Change = $crate::FlagsChange<$ty>;
+
+				fn comparison(&self, other: &Self) -> $crate::Changed<Self::Change> {
+					$crate::flags::flags_comparison(self, other)
+				}
+			}
+		)*
+	};
+}
@@ -0,0 +1,77 @@
+use std::cell::Cell;
+
+/// How closely two floating-point values must agree for [`Comparable`] to
+/// consider them unchanged. See [`with_float_tolerance`].
+///
+/// [`Comparable`]: crate::Comparable
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum FloatTolerance {
+	/// Values must be exactly equal.
+	#[default]
+	Exact,
+	/// Values may differ by at most the given amount.
+	Absolute(f64),
+	/// Values may differ by at most the given number of units in the last
+	/// place, that is, of representable values lying between them.
+	Ulps(u32),
+}
+
+thread_local! {
+	static TOLERANCE: Cell<FloatTolerance> = const { Cell::new(FloatTolerance::Exact) };
+}
+
+// Restores the previous tolerance, even on panic.
+struct Restore(FloatTolerance);
+
+impl Drop for Restore {
+	fn drop(&mut self) {
+		TOLERANCE.with(|tolerance| tolerance.set(self.0));
+	}
+}
+
+/// Run `f` with every comparison of `f32` and `f64` values on this thread
+/// using the given tolerance, so that floating-point noise throughout a
+/// derived model does not register as a change.
+///
+/// ```
+/// # use comparable::*;
+/// assert!(!(0.1 + 0.2).comparison(&0.3).is_unchanged());
+/// with_float_tolerance(FloatTolerance::Absolute(1e-9), || {
+///     assert_eq!((0.1 + 0.2).comparison(&0.3), Changed::Unchanged);
+/// });
+/// ```
+pub fn with_float_tolerance<R>(tolerance: FloatTolerance, f: impl FnOnce() -> R) -> R {
+	let _restore = Restore(TOLERANCE.with(|current| current.replace(tolerance)));
+	f()
+}
+
+#[cfg(feature = "tokio")]
+pub(crate) fn float_tolerance() -> FloatTolerance {
+	TOLERANCE.with(Cell::get)
+}
+
+#[allow(clippy::float_cmp)]
+pub(crate) fn f64_unchanged(a: f64, b: f64) -> bool {
+	match TOLERANCE.with(Cell::get) {
+		FloatTolerance::Exact => a == b,
+		FloatTolerance::Absolute(epsilon) => a == b || (a - b).abs() <= epsilon,
+		FloatTolerance::Ulps(ulps) => {
+			a == b
+				|| (a.is_sign_negative() == b.is_sign_negative()
+					&& (a.to_bits() as i64).abs_diff(b.to_bits() as i64) <= ulps as u64)
+		}
+	}
+}
+
+#[allow(clippy::float_cmp)]
+pub(crate) fn f32_unchanged(a: f32, b: f32) -> bool {
+	match TOLERANCE.with(Cell::get) {
+		FloatTolerance::Exact => a == b,
+		FloatTolerance::Absolute(epsilon) => a == b || (a as f64 - b as f64).abs() <= epsilon,
+		FloatTolerance::Ulps(ulps) => {
+			a == b
+				|| (a.is_sign_negative() == b.is_sign_negative()
+					&& (a.to_bits() as i32).abs_diff(b.to_bits() as i32) <= ulps)
+		}
+	}
+}
@@ -0,0 +1,15 @@
+//! Writing changes in formats read by other tools, and reading them back.
+//!
+//! Most formats work from the [`ChangeTree`](crate::tree::ChangeTree) of the
+//! changes, so that they apply to changes of any type implementing
+//! [`ToTree`](crate::tree::ToTree). The JSON patch formats instead work from
+//! descriptions serialized as JSON, since they hold whole values.
+
+pub mod csv;
+#[cfg(feature = "dot")]
+pub mod dot;
+#[cfg(feature = "json")]
+pub mod json_patch;
+pub mod markdown;
+#[cfg(feature = "json")]
+pub mod merge_patch;
@@ -0,0 +1,99 @@
+//! Writing changes as comma-separated values, for spreadsheets and other
+//! tabular tools.
+//!
+//! [`write`] writes any changes implementing
+//! [`ToTree`](crate::tree::ToTree) as rows of the path, kind of change, value
+//! before and value after of each change.
+//!
+//! With `#[comparable_tree]`, `#[derive(Comparable)]` also converts a
+//! reference to the generated change type into rows of the path, value
+//! before and value after of each change, as given by
+//! [`ChangeTree::rows`], which [`write_rows`] writes with a header line.
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! use comparable::format::csv;
+//!
+//! #[derive(Comparable)]
+//! #[comparable_tree]
+//! struct Account {
+//!     owner: String,
+//!     balance: i64,
+//! }
+//!
+//! let before = Account { owner: "Alice".to_string(), balance: 10 };
+//! let after = Account { owner: "Alice, Jr.".to_string(), balance: 10 };
+//! let changes = before.comparison(&after).unwrap_or_default();
+//! let rows: Vec<(String, String, String)> = (&changes[0]).into();
+//! let mut out = Vec::new();
+//! csv::write_rows(&rows, &mut out).unwrap();
+//! assert_eq!(
+//!     String::from_utf8(out).unwrap(),
+//!     "path,old,new\nowner,\"\"\"Alice\"\"\",\"\"\"Alice, Jr.\"\"\"\n"
+//! );
+//! ```
+//!
+//! [`ChangeTree::rows`]: crate::tree::ChangeTree::rows
+
+use std::io::{self, Write};
+
+use crate::tree::{ChangeTree, ToTree};
+
+/// Write a row for each change to an individual value within `change`, giving
+/// its path, as given by [`ChangeTree::leaves`], the kind of change, which
+/// is one of `added`, `removed`, `modified` or `moved`, and the values before
+/// and after, preceded by the header `path,kind,old,new`. A value moved has
+/// the key it was moved to as its value after.
+///
+/// ```
+/// # use comparable::*;
+/// let changes = vec![1, 2].comparison(&vec![1, 3, 4]).unwrap_or_default();
+/// let mut out = Vec::new();
+/// comparable::format::csv::write(&changes, &mut out).unwrap();
+/// assert_eq!(String::from_utf8(out).unwrap(), "path,kind,old,new\n1,modified,2,3\n2,added,,4\n");
+/// ```
+///
+/// [`ChangeTree::leaves`]: crate::tree::ChangeTree::leaves
+pub fn write<C: ToTree + ?Sized, W: Write>(change: &C, mut writer: W) -> io::Result<()> {
+	write_record(&mut writer, &["path", "kind", "old", "new"])?;
+	for (path, leaf) in change.to_tree().leaves() {
+		let (kind, old, new) = match leaf {
+			ChangeTree::Added(desc) => ("added", "", desc.as_str()),
+			ChangeTree::Removed(desc) => ("removed", desc.as_str(), ""),
+			ChangeTree::Modified { before, after } => ("modified", before.as_str(), after.as_str()),
+			ChangeTree::Moved(key) => ("moved", "", key.as_str()),
+			ChangeTree::Children(_) => unreachable!("leaves have no children"),
+		};
+		write_record(&mut writer, &[&path, kind, old, new])?;
+	}
+	Ok(())
+}
+
+/// Write rows of the path, value before and value after of each change, as
+/// given by [`ChangeTree::rows`], preceded by the header `path,old,new`.
+///
+/// [`ChangeTree::rows`]: crate::tree::ChangeTree::rows
+pub fn write_rows<W: Write>(rows: &[(String, String, String)], mut writer: W) -> io::Result<()> {
+	write_record(&mut writer, &["path", "old", "new"])?;
+	for (path, old, new) in rows {
+		write_record(&mut writer, &[path, old, new])?;
+	}
+	Ok(())
+}
+
+// Write one line of fields, quoting those that would otherwise be misread,
+// as described by RFC 4180.
+fn write_record<W: Write>(writer: &mut W, fields: &[&str]) -> io::Result<()> {
+	for (i, field) in fields.iter().enumerate() {
+		if i > 0 {
+			writer.write_all(b",")?;
+		}
+		if field.contains(&[',', '"', '\n', '\r'][..]) {
+			write!(writer, "\"{}\"", field.replace('"', "\"\""))?;
+		} else {
+			writer.write_all(field.as_bytes())?;
+		}
+	}
+	writer.write_all(b"\n")
+}
@@ -0,0 +1,80 @@
+//! Writing changes as a Graphviz graph, for seeing at a glance where the
+//! changes to deeply nested values lie.
+//!
+//! With the `dot` feature, [`render`] writes any changes implementing
+//! [`ToTree`](crate::tree::ToTree) as a `digraph` in the DOT language, with a
+//! node for each value changed, linked to the values changed within it. The
+//! changes to individual values are colored by their kind: green for a value
+//! added, red for one removed, orange for one modified and blue for one moved.
+//!
+//! ```
+//! # use comparable::*;
+//! let changes = vec![1, 2].comparison(&vec![1, 3]).unwrap_or_default();
+//! assert_eq!(
+//!     comparable::format::dot::render(&changes),
+//!     "digraph changes {\n\
+//!      \x20 node [shape=box, style=filled, fillcolor=white];\n\
+//!      \x20 n0 [label=\"(root)\", fillcolor=lightgray];\n\
+//!      \x20 n1 [label=\"1\\n2 → 3\", fillcolor=orange];\n\
+//!      \x20 n0 -> n1;\n\
+//!      }\n"
+//! );
+//! ```
+
+use crate::tree::{ChangeTree, ToTree};
+
+/// The changes rendered as a graph in the DOT language, with values written
+/// using their `Debug` format.
+pub fn render<C: ToTree + ?Sized>(change: &C) -> String {
+	let mut out = String::from("digraph changes {\n  node [shape=box, style=filled, fillcolor=white];\n");
+	let mut next = 0;
+	render_node(&mut out, &mut next, None, "(root)", &change.to_tree());
+	out.push_str("}\n");
+	out
+}
+
+// Add the node for the changes under `key`, linked from its parent, and those
+// of the changes within it. Changes given the empty key are those of the
+// enclosing value, and share its node.
+fn render_node(out: &mut String, next: &mut usize, parent: Option<usize>, key: &str, tree: &ChangeTree) {
+	if key.is_empty() {
+		if let (Some(parent), ChangeTree::Children(children)) = (parent, tree) {
+			for (key, tree) in children {
+				render_node(out, next, Some(parent), key, tree)
+			}
+			return;
+		}
+	}
+	let id = *next;
+	*next += 1;
+	let (text, color) = match tree {
+		ChangeTree::Added(desc) => (format!("added {}", desc), "palegreen"),
+		ChangeTree::Removed(desc) if desc.is_empty() => ("removed".to_string(), "lightcoral"),
+		ChangeTree::Removed(desc) => (format!("removed {}", desc), "lightcoral"),
+		ChangeTree::Modified { before, after } => (format!("{} → {}", before, after), "orange"),
+		ChangeTree::Moved(to) => (format!("moved to {}", to), "lightblue"),
+		ChangeTree::Children(_) => (String::new(), "lightgray"),
+	};
+	let label = if text.is_empty() {
+		escape(key)
+	} else if key.is_empty() {
+		escape(&text)
+	} else {
+		format!("{}\\n{}", escape(key), escape(&text))
+	};
+	out.push_str(&format!("  n{} [label=\"{}\", fillcolor={}];\n", id, label, color));
+	if let Some(parent) = parent {
+		out.push_str(&format!("  n{} -> n{};\n", parent, id));
+	}
+	if let ChangeTree::Children(children) = tree {
+		for (key, tree) in children {
+			render_node(out, next, Some(id), key, tree)
+		}
+	}
+}
+
+// The text escaped for a quoted DOT string, with each line break written as
+// one within the label.
+fn escape(text: &str) -> String {
+	text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
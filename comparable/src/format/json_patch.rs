@@ -0,0 +1,372 @@
+//! Reading JSON Patch documents, as described by RFC 6902, as the changes of
+//! a value, so that incoming PATCH requests can be checked and applied as
+//! typed changes, and writing the changes between values as such documents.
+//!
+//! [`to_change`] applies the operations of a patch to the description of a
+//! value, serialized as JSON, and rebuilds the value it then describes using
+//! [`FromDesc`], returning the changes from the value to the one rebuilt. A
+//! patch whose paths do not exist within the value or its type, whose `test`
+//! operations fail, or which leaves a description that does not deserialize
+//! as the type's description is refused with an [`Error`], without changing
+//! anything. The changes can then be inspected, and applied using
+//! [`Apply`](crate::lens::Apply). This requires the `json` feature.
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! use comparable::format::json_patch::{to_change, Error, Operation};
+//! use comparable::lens::Apply;
+//! use serde_json::json;
+//!
+//! #[derive(Comparable, Clone, PartialEq, Debug)]
+//! #[comparable_desc_roundtrip]
+//! #[comparable_lens]
+//! struct Account {
+//!     owner: String,
+//!     balance: i64,
+//! }
+//!
+//! let mut account = Account { owner: "Alice".to_string(), balance: 10 };
+//! let patch: Vec<Operation> =
+//!     serde_json::from_value(json!([{ "op": "replace", "path": "/balance", "value": 20 }])).unwrap();
+//! let changes = to_change(&account, &patch).unwrap();
+//! assert_eq!(changes, Changed::Changed(vec![AccountChange::Balance(I64Change(10, 20))]));
+//!
+//! account.apply(changes.unwrap_or_default());
+//! assert_eq!(account.balance, 20);
+//!
+//! let patch: Vec<Operation> = serde_json::from_value(json!([{ "op": "remove", "path": "/email" }])).unwrap();
+//! assert_eq!(to_change(&account, &patch), Err(Error::NoSuchPath("/email".to_string())));
+//! ```
+//!
+//! In the other direction, [`json_patch`] writes the operations turning the
+//! description of one value into that of another, and [`diff`] those turning
+//! one JSON document into another.
+
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::json::ValueChange;
+use crate::lens::vec_layout;
+use crate::roundtrip::FromDesc;
+use crate::types::{Changed, Comparable};
+use crate::{MapChange, VecChange};
+
+/// An operation of a JSON Patch document, whose paths are JSON Pointers as
+/// described by RFC 6901.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum Operation {
+	/// Add a member to an object, replacing any already there, or insert an
+	/// element into an array, where the index `-` appends it.
+	Add { path: String, value: Value },
+	/// Remove the member or element at `path`.
+	Remove { path: String },
+	/// Replace the value at `path`, which must exist.
+	Replace { path: String, value: Value },
+	/// Remove the value at `from` and add it at `path`.
+	Move { from: String, path: String },
+	/// Add a copy of the value at `from` at `path`.
+	Copy { from: String, path: String },
+	/// Check that the value at `path` is equal to `value`.
+	Test { path: String, value: Value },
+}
+
+/// Why a patch could not be read as changes.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Error {
+	/// A path that is not a valid JSON Pointer, or that moves a value into
+	/// itself.
+	InvalidPath(String),
+	/// A path that does not exist within the value patched, or a value added
+	/// where its type has no place for it.
+	NoSuchPath(String),
+	/// A `test` operation whose value differed from that at its path.
+	TestFailed(String),
+	/// The patched description could not be read as a description of the
+	/// type, with the reason given by serde.
+	Invalid(String),
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Error::InvalidPath(path) => write!(f, "invalid path {:?}", path),
+			Error::NoSuchPath(path) => write!(f, "no value at path {:?}", path),
+			Error::TestFailed(path) => write!(f, "test failed at path {:?}", path),
+			Error::Invalid(msg) => write!(f, "{}", msg),
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+/// The changes from `value` to the value described once `patch` is applied
+/// to its description.
+pub fn to_change<T>(value: &T, patch: &[Operation]) -> Result<Changed<T::Change>, Error>
+where
+	T: FromDesc,
+	T::Desc: Serialize + DeserializeOwned,
+{
+	let mut doc = serde_json::to_value(value.describe()).map_err(|e| Error::Invalid(e.to_string()))?;
+	apply(&mut doc, patch)?;
+	let desc = serde_json::from_value(doc.clone()).map_err(|e| Error::Invalid(e.to_string()))?;
+	// Members the description does not have, such as a field added that the
+	// type lacks, are dropped when it is deserialized, rather than refused.
+	let read = serde_json::to_value(&desc).map_err(|e| Error::Invalid(e.to_string()))?;
+	if let Some(path) = dropped(&doc, &read, String::new()) {
+		return Err(Error::NoSuchPath(path));
+	}
+	Ok(value.comparison(&T::from_desc(desc)))
+}
+
+// The path of the first value in `doc` that is not in `read`.
+fn dropped(doc: &Value, read: &Value, path: String) -> Option<String> {
+	if doc == read {
+		return None;
+	}
+	let within = match (doc, read) {
+		(Value::Object(doc), Value::Object(read)) => doc.iter().find_map(|(key, value)| {
+			let path = format!("{}/{}", path, escape(key));
+			match read.get(key) {
+				Some(read) => dropped(value, read, path),
+				None => Some(path),
+			}
+		}),
+		(Value::Array(doc), Value::Array(read)) if doc.len() == read.len() => {
+			doc.iter().zip(read).enumerate().find_map(|(i, (doc, read))| dropped(doc, read, format!("{}/{}", path, i)))
+		}
+		_ => None,
+	};
+	within.or(Some(path))
+}
+
+/// The JSON Patch turning the description of `before` into that of `after`,
+/// both serialized as JSON. If the values are equal, the patch is empty.
+///
+/// ```
+/// # use comparable::*;
+/// use comparable::format::json_patch::{json_patch, Operation};
+/// use serde_json::json;
+///
+/// assert_eq!(
+///     json_patch(&vec![1, 2, 3], &vec![1, 5]).unwrap(),
+///     vec![
+///         Operation::Remove { path: "/2".to_string() },
+///         Operation::Replace { path: "/1".to_string(), value: json!(5) },
+///     ]
+/// );
+/// ```
+pub fn json_patch<T: Comparable + ?Sized>(before: &T, after: &T) -> serde_json::Result<Vec<Operation>>
+where
+	T::Desc: Serialize,
+{
+	Ok(diff(&serde_json::to_value(before.describe())?, &serde_json::to_value(after.describe())?))
+}
+
+/// The JSON Patch turning one JSON document into another, made from the
+/// changes found by comparing them, so that it follows any
+/// [`DiffOptions`](crate::DiffOptions) in effect. Elements removed from an
+/// array are removed first, from the last, and those added or moved are then
+/// added in the order of their new indices; members moved within an object
+/// are moved.
+///
+/// ```
+/// use comparable::format::json_patch::{apply, diff};
+/// use serde_json::json;
+///
+/// let before = json!({ "name": "Alice", "tags": ["a", "b"] });
+/// let after = json!({ "name": "Bob", "tags": ["b"], "age": 42 });
+/// let patch = diff(&before, &after);
+/// let mut doc = before.clone();
+/// apply(&mut doc, &patch).unwrap();
+/// assert_eq!(doc, after);
+/// ```
+pub fn diff(before: &Value, after: &Value) -> Vec<Operation> {
+	let mut patch = Vec::new();
+	if let Changed::Changed(change) = before.comparison(after) {
+		push_operations(before, &change, "", &mut patch);
+	}
+	patch
+}
+
+// Add the operations making the changes to `before`, the value at `path`.
+fn push_operations(before: &Value, change: &ValueChange, path: &str, patch: &mut Vec<Operation>) {
+	let at = |token: &str| format!("{}/{}", path, escape(token));
+	match (before, change) {
+		(Value::Array(elements), ValueChange::Array(changes)) => {
+			// The changes found by comparing two arrays always fit the first.
+			let layout = match vec_layout(elements.len(), changes) {
+				Ok(layout) => layout,
+				Err(_) => return,
+			};
+			let mut gone: Vec<usize> = changes
+				.iter()
+				.filter_map(|change| match change {
+					VecChange::Removed(from, _) | VecChange::Moved { from, .. } => Some(*from),
+					_ => None,
+				})
+				.collect();
+			gone.sort_unstable();
+			patch.extend(gone.into_iter().rev().map(|from| Operation::Remove { path: at(&from.to_string()) }));
+			let mut placed: Vec<(usize, &Value)> = changes
+				.iter()
+				.filter_map(|change| match change {
+					VecChange::Added(to, value) => Some((*to, value)),
+					VecChange::Moved { from, to } => Some((*to, &elements[*from])),
+					_ => None,
+				})
+				.collect();
+			placed.sort_by_key(|(to, _)| *to);
+			patch.extend(
+				placed
+					.into_iter()
+					.map(|(to, value)| Operation::Add { path: at(&to.to_string()), value: value.clone() }),
+			);
+			for change in changes {
+				if let VecChange::Changed(to, change) = change {
+					if let Some(from) = layout[*to] {
+						push_operations(&elements[from], change, &at(&to.to_string()), patch)
+					}
+				}
+			}
+		}
+		(Value::Object(members), ValueChange::Object(changes)) => {
+			for change in changes {
+				match change {
+					MapChange::Added(name, value) => {
+						patch.push(Operation::Add { path: at(name), value: value.clone() })
+					}
+					MapChange::Changed(name, change) => {
+						if let Some(value) = members.get(name) {
+							push_operations(value, change, &at(name), patch)
+						}
+					}
+					MapChange::Removed(name) => patch.push(Operation::Remove { path: at(name) }),
+					MapChange::Moved { from, to, change } => {
+						patch.push(Operation::Move { from: at(from), path: at(to) });
+						if let (Some(value), Changed::Changed(change)) = (members.get(from), change) {
+							push_operations(value, change, &at(to), patch)
+						}
+					}
+				}
+			}
+		}
+		(_, ValueChange::Replaced(_, after)) => {
+			patch.push(Operation::Replace { path: path.to_string(), value: after.clone() })
+		}
+		_ => (),
+	}
+}
+
+// A reference token of a JSON Pointer, escaped.
+fn escape(token: &str) -> String {
+	token.replace('~', "~0").replace('/', "~1")
+}
+
+/// Apply the operations of a patch to a JSON document in order. If any of
+/// them fails, the document may have been partly patched.
+pub fn apply(doc: &mut Value, patch: &[Operation]) -> Result<(), Error> {
+	for operation in patch {
+		match operation {
+			Operation::Add { path, value } => add(doc, path, value.clone())?,
+			Operation::Remove { path } => {
+				remove(doc, path)?;
+			}
+			Operation::Replace { path, value } => *get_mut(doc, path)? = value.clone(),
+			Operation::Move { from, path } => {
+				if path.starts_with(&format!("{}/", from)) {
+					return Err(Error::InvalidPath(path.clone()));
+				}
+				let value = remove(doc, from)?;
+				add(doc, path, value)?
+			}
+			Operation::Copy { from, path } => {
+				let value = get_mut(doc, from)?.clone();
+				add(doc, path, value)?
+			}
+			Operation::Test { path, value } => {
+				if get_mut(doc, path)? != value {
+					return Err(Error::TestFailed(path.clone()));
+				}
+			}
+		}
+	}
+	Ok(())
+}
+
+// The reference tokens of a JSON Pointer, unescaped.
+fn tokens(path: &str) -> Result<Vec<String>, Error> {
+	if path.is_empty() {
+		return Ok(Vec::new());
+	}
+	match path.strip_prefix('/') {
+		Some(rest) => Ok(rest.split('/').map(|token| token.replace("~1", "/").replace("~0", "~")).collect()),
+		None => Err(Error::InvalidPath(path.to_string())),
+	}
+}
+
+fn index(token: &str, len: usize, path: &str) -> Result<usize, Error> {
+	match token.parse::<usize>() {
+		Ok(i) if i < len && (token == "0" || !token.starts_with('0')) => Ok(i),
+		_ => Err(Error::NoSuchPath(path.to_string())),
+	}
+}
+
+fn child<'a>(value: &'a mut Value, token: &str, path: &str) -> Result<&'a mut Value, Error> {
+	match value {
+		Value::Object(members) => members.get_mut(token).ok_or_else(|| Error::NoSuchPath(path.to_string())),
+		Value::Array(elements) => {
+			let i = index(token, elements.len(), path)?;
+			Ok(&mut elements[i])
+		}
+		_ => Err(Error::NoSuchPath(path.to_string())),
+	}
+}
+
+fn get_mut<'a>(doc: &'a mut Value, path: &str) -> Result<&'a mut Value, Error> {
+	tokens(path)?.iter().try_fold(doc, |value, token| child(value, token, path))
+}
+
+// The value holding the one at `path`, and the last token of `path`.
+fn parent<'a>(doc: &'a mut Value, path: &str) -> Result<(&'a mut Value, String), Error> {
+	let mut tokens = tokens(path)?;
+	let last = tokens.pop().ok_or_else(|| Error::InvalidPath(path.to_string()))?;
+	let parent = tokens.iter().try_fold(doc, |value, token| child(value, token, path))?;
+	Ok((parent, last))
+}
+
+fn add(doc: &mut Value, path: &str, value: Value) -> Result<(), Error> {
+	if path.is_empty() {
+		*doc = value;
+		return Ok(());
+	}
+	let (parent, last) = parent(doc, path)?;
+	match parent {
+		Value::Object(members) => {
+			members.insert(last, value);
+		}
+		Value::Array(elements) if last == "-" => elements.push(value),
+		Value::Array(elements) => {
+			let i = index(&last, elements.len() + 1, path)?;
+			elements.insert(i, value)
+		}
+		_ => return Err(Error::NoSuchPath(path.to_string())),
+	}
+	Ok(())
+}
+
+fn remove(doc: &mut Value, path: &str) -> Result<Value, Error> {
+	let (parent, last) = parent(doc, path)?;
+	match parent {
+		Value::Object(members) => members.remove(&last).ok_or_else(|| Error::NoSuchPath(path.to_string())),
+		Value::Array(elements) => {
+			let i = index(&last, elements.len(), path)?;
+			Ok(elements.remove(i))
+		}
+		_ => Err(Error::NoSuchPath(path.to_string())),
+	}
+}
@@ -0,0 +1,105 @@
+//! Writing changes as Markdown, for posting in pull request comments, chat
+//! messages and other places that render it.
+//!
+//! [`render`] writes any changes implementing
+//! [`ToTree`](crate::tree::ToTree) either as a table, with a row for each
+//! change to an individual value, or as a list nested as the values changed
+//! are, as chosen by [`MarkdownStyle`].
+//!
+//! ```
+//! # use comparable::*;
+//! use comparable::format::markdown::{render, MarkdownStyle};
+//!
+//! let changes = vec![1, 2].comparison(&vec![1, 3, 4]).unwrap_or_default();
+//! assert_eq!(
+//!     render(&changes, MarkdownStyle::Table),
+//!     "| Path | Change | Before | After |\n\
+//!      | --- | --- | --- | --- |\n\
+//!      | `1` | modified | `2` | `3` |\n\
+//!      | `2` | added |  | `4` |\n"
+//! );
+//! assert_eq!(render(&changes, MarkdownStyle::List), "- `1`: `2` → `3`\n- `2`: added `4`\n");
+//! ```
+
+use crate::tree::{ChangeTree, ToTree};
+
+/// How [`render`] lays out changes.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MarkdownStyle {
+	/// A table with a row for each change to an individual value, giving its
+	/// path, the kind of change, and the values before and after.
+	Table,
+	/// A list with an item for each value changed, holding a list of the
+	/// changes within it, if any.
+	List,
+}
+
+/// The changes rendered as Markdown, in the given style. Values are written
+/// as code, using their `Debug` format.
+pub fn render<C: ToTree + ?Sized>(change: &C, style: MarkdownStyle) -> String {
+	let tree = change.to_tree();
+	let mut out = String::new();
+	match style {
+		MarkdownStyle::Table => {
+			out.push_str("| Path | Change | Before | After |\n| --- | --- | --- | --- |\n");
+			for (path, leaf) in tree.leaves() {
+				let (kind, before, after) = match leaf {
+					ChangeTree::Added(desc) => ("added", String::new(), code(desc)),
+					ChangeTree::Removed(desc) => ("removed", code(desc), String::new()),
+					ChangeTree::Modified { before, after } => ("modified", code(before), code(after)),
+					ChangeTree::Moved(key) => ("moved", String::new(), code(key)),
+					ChangeTree::Children(_) => unreachable!("leaves have no children"),
+				};
+				let cells = [code(&path), kind.to_string(), before, after];
+				// A pipe would end the cell, even within code.
+				let cells = cells.iter().map(|cell| cell.replace('|', "\\|")).collect::<Vec<_>>();
+				out.push_str(&format!("| {} |\n", cells.join(" | ")));
+			}
+		}
+		MarkdownStyle::List => render_items(&mut out, 0, String::new(), &tree),
+	}
+	out
+}
+
+// Add the items for the changes under `key`, at the given depth of nesting.
+// Changes given the empty key are those of the enclosing value.
+fn render_items(out: &mut String, depth: usize, key: String, tree: &ChangeTree) {
+	let indent = "  ".repeat(depth);
+	let label = if key.is_empty() { String::new() } else { format!("{}: ", code(&key)) };
+	let text = match tree {
+		ChangeTree::Added(desc) => format!("added {}", code(desc)),
+		ChangeTree::Removed(desc) if desc.is_empty() => "removed".to_string(),
+		ChangeTree::Removed(desc) => format!("removed {}", code(desc)),
+		ChangeTree::Modified { before, after } => format!("{} → {}", code(before), code(after)),
+		ChangeTree::Moved(to) => format!("moved to {}", code(to)),
+		ChangeTree::Children(children) => {
+			let depth = if key.is_empty() {
+				depth
+			} else {
+				out.push_str(&format!("{}- {}\n", indent, code(&key)));
+				depth + 1
+			};
+			for (key, tree) in children {
+				render_items(out, depth, key.clone(), tree)
+			}
+			return;
+		}
+	};
+	out.push_str(&format!("{}- {}{}\n", indent, label, text));
+}
+
+// The text as a code span, fenced by more backticks than it contains in a
+// row, and written on one line.
+fn code(text: &str) -> String {
+	if text.is_empty() {
+		return String::new();
+	}
+	let text = text.replace('\n', " ");
+	let longest = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+	let fence = "`".repeat(longest + 1);
+	if text.starts_with('`') || text.ends_with('`') {
+		format!("{} {} {}", fence, text, fence)
+	} else {
+		format!("{}{}{}", fence, text, fence)
+	}
+}
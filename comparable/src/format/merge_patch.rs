@@ -0,0 +1,113 @@
+//! Writing changes as JSON Merge Patch documents, as described by RFC 7386,
+//! which many REST APIs accept for partial updates.
+//!
+//! A merge patch is an object holding only the members that changed: a
+//! member added or modified holds its new value, a member removed is `null`,
+//! and an object changed within holds a merge patch of its own. Arrays and
+//! other values are replaced whole, since the format has no way to change
+//! part of them. As the patch must hold the values themselves, rather than
+//! the changes found by [`Comparable::comparison`], [`merge_patch`] builds it
+//! from the descriptions of the values, serialized as JSON, which requires
+//! the `json` feature.
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! use comparable::format::merge_patch::{apply, merge_patch};
+//! use serde_json::json;
+//!
+//! #[derive(Comparable)]
+//! struct Account {
+//!     owner: String,
+//!     balance: i64,
+//!     nickname: Option<String>,
+//! }
+//!
+//! let before = Account { owner: "Alice".to_string(), balance: 10, nickname: Some("Al".to_string()) };
+//! let after = Account { owner: "Alice".to_string(), balance: 20, nickname: None };
+//! let patch = merge_patch(&before, &after).unwrap();
+//! assert_eq!(patch, json!({ "balance": 20, "nickname": null }));
+//!
+//! let mut target = json!({ "owner": "Alice", "balance": 10, "nickname": "Al" });
+//! apply(&mut target, &patch);
+//! assert_eq!(target, json!({ "owner": "Alice", "balance": 20 }));
+//! ```
+//!
+//! Since `null` removes a member, a member whose new value is `null`, such as
+//! an `Option` that became `None`, is removed when the patch is applied,
+//! rather than being set to `null`.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::types::Comparable;
+
+/// The merge patch turning the description of `before` into that of `after`,
+/// both serialized as JSON. If the values are equal, the patch is empty.
+pub fn merge_patch<T: Comparable + ?Sized>(before: &T, after: &T) -> serde_json::Result<Value>
+where
+	T::Desc: Serialize,
+{
+	Ok(diff(&serde_json::to_value(before.describe())?, &serde_json::to_value(after.describe())?))
+}
+
+/// The merge patch turning one JSON document into another. If both are
+/// objects, the patch holds only the members that differ; otherwise it is
+/// the document `after` itself.
+///
+/// ```
+/// use comparable::format::merge_patch::diff;
+/// use serde_json::json;
+///
+/// assert_eq!(diff(&json!({ "a": [1, 2], "b": 1 }), &json!({ "a": [1, 3], "b": 1 })), json!({ "a": [1, 3] }));
+/// assert_eq!(diff(&json!({ "a": 1 }), &json!({ "a": 1 })), json!({}));
+/// assert_eq!(diff(&json!([1]), &json!([2])), json!([2]));
+/// ```
+pub fn diff(before: &Value, after: &Value) -> Value {
+	match (before, after) {
+		(Value::Object(before), Value::Object(after)) => {
+			let mut patch = Map::new();
+			for key in before.keys() {
+				if !after.contains_key(key) {
+					patch.insert(key.clone(), Value::Null);
+				}
+			}
+			for (key, value) in after {
+				match before.get(key) {
+					Some(old) if old == value => {}
+					Some(old) => {
+						patch.insert(key.clone(), diff(old, value));
+					}
+					None => {
+						patch.insert(key.clone(), value.clone());
+					}
+				}
+			}
+			Value::Object(patch)
+		}
+		_ => after.clone(),
+	}
+}
+
+/// Apply a merge patch to a JSON document, as RFC 7386 describes.
+pub fn apply(target: &mut Value, patch: &Value) {
+	let patch = match patch {
+		Value::Object(patch) => patch,
+		_ => {
+			*target = patch.clone();
+			return;
+		}
+	};
+	if !target.is_object() {
+		*target = Value::Object(Map::new());
+	}
+	if let Value::Object(target) = target {
+		for (key, value) in patch {
+			if value.is_null() {
+				target.remove(key);
+			} else {
+				apply(target.entry(key.clone()).or_insert(Value::Null), value);
+			}
+		}
+	}
+}
@@ -0,0 +1,290 @@
+//! Comparing files and directory trees on disk.
+//!
+//! Files are compared by the hashes of their contents, taken a chunk at a
+//! time, so that neither file need be held in memory. The changes to a file
+//! are the byte ranges whose chunks differ, with adjacent chunks merged into
+//! one range, and the changes to a directory tree are reported as for a map
+//! from the relative path of each file to its contents, which is how backup
+//! and deployment tools usually want to see them.
+//!
+//! A [`DirTree`] is instead a snapshot of a directory tree, which may be kept
+//! and compared later with another snapshot, reporting the changes to the
+//! [`FileMetadata`] of each file modified.
+//!
+//! ```
+//! # use comparable::*;
+//! use comparable::fs::{diff_dirs, diff_files};
+//! use std::path::PathBuf;
+//!
+//! # let root = std::env::temp_dir().join(format!("comparable-fs-doc-{}", std::process::id()));
+//! # std::fs::create_dir_all(root.join("before")).unwrap();
+//! # std::fs::create_dir_all(root.join("after")).unwrap();
+//! std::fs::write(root.join("before/data"), b"aaaabbbbcccc").unwrap();
+//! std::fs::write(root.join("after/data"), b"aaaaXbbbcccc").unwrap();
+//! std::fs::write(root.join("after/new"), b"hello").unwrap();
+//!
+//! assert_eq!(
+//!     diff_files(root.join("before/data"), root.join("after/data"), 4).unwrap(),
+//!     Changed::Changed(vec![4..8])
+//! );
+//! assert_eq!(
+//!     diff_dirs(root.join("before"), root.join("after"), 4).unwrap(),
+//!     Changed::Changed(vec![
+//!         MapChange::Changed(PathBuf::from("data"), vec![4..8]),
+//!         MapChange::Added(PathBuf::from("new"), 5),
+//!     ])
+//! );
+//! # std::fs::remove_dir_all(root).unwrap();
+//! ```
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::hash::Hasher;
+use std::io::{self, Read};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::light::hash_of;
+use crate::map::MapChange;
+use crate::types::{Changed, Comparable};
+use crate::U64Change;
+
+/// The chunk size used by tools that have no reason to choose another.
+pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// The hashes of the contents of a file, one for each chunk of `chunk_size`
+/// bytes. These may be kept in place of the file itself, for comparison with
+/// a later version of it. The hashes are only comparable between builds of
+/// the same version of Rust, and should not be persisted beyond that.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FileHashes {
+	pub len: u64,
+	pub chunk_size: usize,
+	pub chunks: Vec<u64>,
+}
+
+impl FileHashes {
+	/// The hashes of the file at `path`.
+	pub fn of(path: impl AsRef<Path>, chunk_size: usize) -> io::Result<Self> {
+		FileHashes::read(File::open(path)?, chunk_size)
+	}
+
+	/// The hashes of everything read from `reader`.
+	pub fn read(mut reader: impl Read, chunk_size: usize) -> io::Result<Self> {
+		assert!(chunk_size > 0, "chunk size must not be zero");
+		let mut hashes = FileHashes { len: 0, chunk_size, chunks: Vec::new() };
+		let mut buf = vec![0; chunk_size];
+		loop {
+			let read = read_chunk(&mut reader, &mut buf)?;
+			if read == 0 {
+				break;
+			}
+			hashes.len += read as u64;
+			hashes.chunks.push(hash_of(&buf[..read]));
+			if read < chunk_size {
+				break;
+			}
+		}
+		Ok(hashes)
+	}
+
+	/// The byte ranges whose contents differ from those of `other`, with the
+	/// bytes past the end of the shorter file counting as changed. Both must
+	/// have been hashed using the same chunk size.
+	pub fn changed_ranges(&self, other: &Self) -> Changed<Vec<Range<u64>>> {
+		assert_eq!(self.chunk_size, other.chunk_size, "files were hashed using different chunk sizes");
+		let size = self.chunk_size as u64;
+		let len = self.len.max(other.len);
+		let mut ranges: Vec<Range<u64>> = Vec::new();
+		for i in 0..self.chunks.len().max(other.chunks.len()) {
+			if self.chunks.get(i) != other.chunks.get(i) {
+				let start = i as u64 * size;
+				let end = (start + size).min(len);
+				match ranges.last_mut() {
+					Some(last) if last.end == start => last.end = end,
+					_ => ranges.push(start..end),
+				}
+			}
+		}
+		Changed::from_changes(ranges)
+	}
+}
+
+// Fill `buf` as far as possible, returning less than its length only at the
+// end of `reader`.
+fn read_chunk(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+	let mut read = 0;
+	while read < buf.len() {
+		match reader.read(&mut buf[read..]) {
+			Ok(0) => break,
+			Ok(n) => read += n,
+			Err(err) if err.kind() == io::ErrorKind::Interrupted => (),
+			Err(err) => return Err(err),
+		}
+	}
+	Ok(read)
+}
+
+/// The byte ranges that differ between two files, compared in chunks of
+/// `chunk_size` bytes.
+pub fn diff_files(
+	before: impl AsRef<Path>,
+	after: impl AsRef<Path>,
+	chunk_size: usize,
+) -> io::Result<Changed<Vec<Range<u64>>>> {
+	Ok(FileHashes::of(before, chunk_size)?.changed_ranges(&FileHashes::of(after, chunk_size)?))
+}
+
+/// A change to a file in a directory tree: a file added is described by its
+/// length, and a file changed by the byte ranges that differ.
+pub type FileChange = MapChange<PathBuf, u64, Vec<Range<u64>>>;
+
+/// The files that differ between two directory trees, by their paths relative
+/// to `before` and `after`, with files compared in chunks of `chunk_size`
+/// bytes. Only regular files are compared: symbolic links and
+/// other special files are passed over.
+pub fn diff_dirs(
+	before: impl AsRef<Path>,
+	after: impl AsRef<Path>,
+	chunk_size: usize,
+) -> io::Result<Changed<Vec<FileChange>>> {
+	let before = files(before.as_ref())?;
+	let mut after = files(after.as_ref())?;
+	let mut changes = Vec::new();
+	for (path, old) in before {
+		match after.remove(&path) {
+			Some(new) => {
+				if let Changed::Changed(ranges) = diff_files(old, new, chunk_size)? {
+					changes.push(MapChange::Changed(path, ranges));
+				}
+			}
+			None => changes.push(MapChange::Removed(path)),
+		}
+	}
+	for (path, new) in after {
+		let len = new.metadata()?.len();
+		changes.push(MapChange::Added(path, len));
+	}
+	changes.sort_by(|x, y| change_path(x).cmp(change_path(y)));
+	Ok(Changed::from_changes(changes))
+}
+
+fn change_path<Desc, Change>(change: &MapChange<PathBuf, Desc, Change>) -> &PathBuf {
+	match change {
+		MapChange::Added(path, _) | MapChange::Changed(path, _) | MapChange::Removed(path) => path,
+		MapChange::Moved { to, .. } => to,
+	}
+}
+
+/// The regular files beneath `root`, by their paths relative to it.
+pub(crate) fn files(root: &Path) -> io::Result<BTreeMap<PathBuf, PathBuf>> {
+	let mut found = BTreeMap::new();
+	let mut pending = vec![PathBuf::new()];
+	while let Some(dir) = pending.pop() {
+		for entry in std::fs::read_dir(root.join(&dir))? {
+			let entry = entry?;
+			let kind = entry.file_type()?;
+			let path = dir.join(entry.file_name());
+			if kind.is_dir() {
+				pending.push(path);
+			} else if kind.is_file() {
+				found.insert(path, entry.path());
+			}
+		}
+	}
+	Ok(found)
+}
+
+/// What is known of a file in a [`DirTree`]: its size, the time it was last
+/// modified, if the platform records one, and a hash of its contents.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FileMetadata {
+	pub size: u64,
+	pub modified: Option<SystemTime>,
+	pub hash: u64,
+}
+
+impl FileMetadata {
+	/// The metadata of the file at `path`, reading it to hash its contents.
+	pub fn of(path: impl AsRef<Path>) -> io::Result<Self> {
+		let mut file = File::open(path)?;
+		let metadata = file.metadata()?;
+		let mut hasher = DefaultHasher::new();
+		let mut buf = vec![0; DEFAULT_CHUNK_SIZE];
+		loop {
+			let read = read_chunk(&mut file, &mut buf)?;
+			hasher.write(&buf[..read]);
+			if read < buf.len() {
+				break;
+			}
+		}
+		Ok(FileMetadata { size: metadata.len(), modified: metadata.modified().ok(), hash: hasher.finish() })
+	}
+}
+
+/// A change to the metadata of a file in a [`DirTree`]. A file rewritten
+/// with the same contents changes only in the time it was modified.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, Debug)]
+pub enum FileMetadataChange {
+	Size(U64Change),
+	Modified(Option<SystemTime>, Option<SystemTime>),
+	Hash(U64Change),
+}
+
+impl Comparable for FileMetadata {
+	type Desc = FileMetadata;
+
+	fn describe(&self) -> Self::Desc {
+		self.clone()
+	}
+
+	type Change = Vec<FileMetadataChange>;
+
+	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
+		let mut changes = Vec::new();
+		changes.extend(self.size.comparison(&other.size).map(FileMetadataChange::Size));
+		if self.modified != other.modified {
+			changes.push(FileMetadataChange::Modified(self.modified, other.modified));
+		}
+		changes.extend(self.hash.comparison(&other.hash).map(FileMetadataChange::Hash));
+		Changed::from_changes(changes)
+	}
+}
+
+/// A snapshot of the regular files beneath a directory, by their paths
+/// relative to it, to be compared with a later snapshot of the same tree or
+/// with a snapshot of another. The changes are those of the map of files, so
+/// that a file modified reports the changes to its [`FileMetadata`].
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct DirTree {
+	pub files: BTreeMap<PathBuf, FileMetadata>,
+}
+
+impl DirTree {
+	/// Snapshot the files beneath `root`, reading each to hash its contents.
+	pub fn snapshot(root: impl AsRef<Path>) -> io::Result<Self> {
+		let files = files(root.as_ref())?
+			.into_iter()
+			.map(|(path, full)| Ok((path, FileMetadata::of(full)?)))
+			.collect::<io::Result<_>>()?;
+		Ok(DirTree { files })
+	}
+}
+
+impl Comparable for DirTree {
+	type Desc = BTreeMap<PathBuf, FileMetadata>;
+
+	fn describe(&self) -> Self::Desc {
+		self.files.clone()
+	}
+
+	type Change = Vec<MapChange<PathBuf, FileMetadata, Vec<FileMetadataChange>>>;
+
+	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
+		self.files.comparison(&other.files)
+	}
+}
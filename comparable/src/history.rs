@@ -0,0 +1,137 @@
+use crate::lens::Apply;
+use crate::types::Changed;
+
+// A step through a history: the changes making it, and those undoing it.
+#[derive(Debug)]
+struct Step<C> {
+	forward: C,
+	backward: C,
+}
+
+/// An undo/redo history of the changes made to a value. Only the initial and
+/// current states are kept: each step between them is recorded as the
+/// changes making it and the inverse changes undoing it, which are applied to
+/// the current value with [`Apply`] to move through the history.
+///
+/// Applying a change consumes it, so a step that is undone or redone compares
+/// the value before and after to recover the changes that were applied. A
+/// step whose changes leave the value as it was when applied, such as one
+/// changing only fields marked with `comparable_redact`, is dropped.
+///
+/// ```
+/// # use comparable::*;
+/// let mut history = History::new(1);
+/// history.record(2);
+/// history.record(3);
+/// assert_eq!(history.undo(), Some(&I32Change(3, 2)));
+/// assert_eq!(history.undo(), Some(&I32Change(2, 1)));
+/// assert_eq!(history.undo(), None);
+/// assert_eq!(history.redo(), Some(&I32Change(1, 2)));
+/// assert_eq!(*history.current(), 2);
+/// ```
+#[derive(Debug)]
+pub struct History<T: Apply + Clone> {
+	initial: T,
+	current: T,
+	// The steps that can be undone, the most recent last.
+	undo: Vec<Step<T::Change>>,
+	// The steps that can be redone, the next to redo last.
+	redo: Vec<Step<T::Change>>,
+}
+
+impl<T: Apply + Clone> History<T> {
+	pub fn new(initial: T) -> Self {
+		History { current: initial.clone(), initial, undo: Vec::new(), redo: Vec::new() }
+	}
+
+	/// The current state of the value.
+	pub fn current(&self) -> &T {
+		&self.current
+	}
+
+	/// Record a new state, returning the changes from the current one. If
+	/// there are none nothing is recorded; otherwise any steps that could
+	/// have been redone are discarded.
+	pub fn record(&mut self, value: T) -> Option<&T::Change> {
+		let step = match (self.current.comparison(&value), value.comparison(&self.current)) {
+			(Changed::Changed(forward), Changed::Changed(backward)) => Step { forward, backward },
+			_ => return None,
+		};
+		self.current = value;
+		self.redo.clear();
+		self.undo.push(step);
+		self.undo.last().map(|step| &step.forward)
+	}
+
+	/// Return to the previous state by applying the inverse of the changes
+	/// that led to the current one, returning those inverse changes, or
+	/// `None` if there is nothing to undo.
+	pub fn undo(&mut self) -> Option<&T::Change> {
+		let Step { forward, backward } = self.undo.pop()?;
+		let backward = Self::reapply(&mut self.current, backward)?;
+		self.redo.push(Step { forward, backward });
+		self.redo.last().map(|step| &step.backward)
+	}
+
+	/// Move forward to the next state after an undo by applying the changes
+	/// that led to it, returning those changes, or `None` if there is
+	/// nothing to redo.
+	pub fn redo(&mut self) -> Option<&T::Change> {
+		let Step { forward, backward } = self.redo.pop()?;
+		let forward = Self::reapply(&mut self.current, forward)?;
+		self.undo.push(Step { forward, backward });
+		self.undo.last().map(|step| &step.forward)
+	}
+
+	// Apply `change` to `value`, and return it again, as found by comparing
+	// the value before and after it was applied.
+	fn reapply(value: &mut T, change: T::Change) -> Option<T::Change> {
+		let before = value.clone();
+		value.apply(change);
+		match before.comparison(value) {
+			Changed::Changed(change) => Some(change),
+			Changed::Unchanged => None,
+		}
+	}
+
+	pub fn can_undo(&self) -> bool {
+		!self.undo.is_empty()
+	}
+
+	pub fn can_redo(&self) -> bool {
+		!self.redo.is_empty()
+	}
+
+	/// The number of steps that can currently be undone.
+	pub fn undo_len(&self) -> usize {
+		self.undo.len()
+	}
+
+	/// The number of steps that can currently be redone.
+	pub fn redo_len(&self) -> usize {
+		self.redo.len()
+	}
+
+	/// Compose the oldest steps of the history together so that at most
+	/// `max_undo` steps remain that can be undone. The steps are composed by
+	/// applying their changes in turn to the initial state, which is always
+	/// kept, so undoing the oldest remaining step returns to it directly, as
+	/// if all of the steps folded into it had happened at once; for this
+	/// reason at least one step is kept, unless the steps folded together
+	/// return to the initial state, when none is.
+	pub fn compact(&mut self, max_undo: usize) {
+		let max_undo = max_undo.max(1);
+		if self.undo.len() > max_undo {
+			let excess = self.undo.len() - max_undo;
+			let mut state = self.initial.clone();
+			for step in self.undo.drain(..=excess) {
+				state.apply(step.forward);
+			}
+			if let (Changed::Changed(forward), Changed::Changed(backward)) =
+				(self.initial.comparison(&state), state.comparison(&self.initial))
+			{
+				self.undo.insert(0, Step { forward, backward });
+			}
+		}
+	}
+}
@@ -0,0 +1,217 @@
+//! Ignoring changes to fields chosen by patterns given at runtime.
+//!
+//! An [`IgnoreSet`] is a set of patterns over dotted field paths, as written
+//! in a [`FieldMask`](crate::mask::FieldMask), in which `*` matches any part
+//! of a field name and `**` matches any number of fields. Loaded from
+//! configuration, it lets operators quiet noisy fields, such as
+//! `*.updated_at` or `metrics.**`, without changes to code.
+//!
+//! Within [`with_ignore_set`], every derived comparison leaves out the fields
+//! matched, as if they were unchanged. Changes already found can instead be
+//! filtered after the fact, using [`IgnoreSet::filter_tree`].
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! use comparable::ignore::with_ignore_set;
+//!
+//! #[derive(Comparable)]
+//! struct Job {
+//!     name: String,
+//!     updated_at: u64,
+//! }
+//!
+//! let ignore = IgnoreSet::parse("# timestamps change on every run\nupdated_at\n").unwrap();
+//! let before = Job { name: "build".to_string(), updated_at: 1 };
+//! let after = Job { name: "build".to_string(), updated_at: 2 };
+//! assert_eq!(with_ignore_set(&ignore, || before.comparison(&after)), Changed::Unchanged);
+//! ```
+
+use std::fmt;
+
+use crate::mask::{with_filter, Filter, Selection};
+use crate::tree::ChangeTree;
+
+/// A set of patterns naming the fields whose changes are ignored.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct IgnoreSet {
+	patterns: Vec<Pattern>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+struct Pattern {
+	text: String,
+	segments: Vec<Segment>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Segment {
+	// `**`, matching any number of field names, including none.
+	Any,
+	// A field name, in which `*` matches any run of characters and `?` any
+	// one character.
+	Name(String),
+}
+
+/// A pattern that could not be read, with the reason why.
+#[derive(Clone, PartialEq, Debug)]
+pub struct PatternError {
+	pub pattern: String,
+	pub reason: &'static str,
+}
+
+impl fmt::Display for PatternError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "invalid ignore pattern `{}`: {}", self.pattern, self.reason)
+	}
+}
+
+impl std::error::Error for PatternError {}
+
+impl IgnoreSet {
+	/// A set ignoring the fields matched by any of the given patterns.
+	pub fn new<I>(patterns: I) -> Result<Self, PatternError>
+	where
+		I: IntoIterator,
+		I::Item: AsRef<str>,
+	{
+		let patterns =
+			patterns.into_iter().map(|pattern| Pattern::parse(pattern.as_ref())).collect::<Result<_, _>>()?;
+		Ok(IgnoreSet { patterns })
+	}
+
+	/// Read a set from text with one pattern to a line, such as a file of
+	/// configuration. Blank lines and lines starting with `#` are skipped.
+	pub fn parse(text: &str) -> Result<Self, PatternError> {
+		IgnoreSet::new(text.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')))
+	}
+
+	/// The patterns making up this set, as they were given.
+	pub fn patterns(&self) -> impl Iterator<Item = &str> {
+		self.patterns.iter().map(|pattern| pattern.text.as_str())
+	}
+
+	/// Whether the field at the given dotted path is ignored, either because a
+	/// pattern matches it or because one matches a field containing it.
+	pub fn is_ignored(&self, path: &str) -> bool {
+		let path = path.split('.').filter(|name| !name.is_empty()).collect::<Vec<_>>();
+		(0..=path.len()).any(|len| self.matches(&path[..len]))
+	}
+
+	/// The changes in `tree` not ignored by this set, where the path of each
+	/// change is the keys leading to it, as given by
+	/// [`ChangeTree::leaves`]. Unlike when comparing, these include the
+	/// indices and keys of elements of collections, so that `items.*.price`
+	/// names the price of every element of `items`.
+	pub fn filter_tree(&self, tree: ChangeTree) -> ChangeTree {
+		if self.matches(&[]) {
+			return ChangeTree::Children(Vec::new());
+		}
+		self.filter_within(&mut Vec::new(), tree)
+	}
+
+	fn filter_within(&self, path: &mut Vec<String>, tree: ChangeTree) -> ChangeTree {
+		match tree {
+			ChangeTree::Children(children) => ChangeTree::Children(
+				children
+					.into_iter()
+					.filter_map(|(key, tree)| {
+						if key.is_empty() {
+							return Some((key, self.filter_within(path, tree)));
+						}
+						path.push(key.clone());
+						let names = path.iter().map(String::as_str).collect::<Vec<_>>();
+						let kept = if self.matches(&names) { None } else { Some(self.filter_within(path, tree)) };
+						path.pop();
+						kept.filter(|tree| !tree.is_empty()).map(|tree| (key, tree))
+					})
+					.collect(),
+			),
+			leaf => leaf,
+		}
+	}
+
+	fn matches(&self, path: &[&str]) -> bool {
+		self.patterns.iter().any(|pattern| matches(&pattern.segments, path))
+	}
+
+	// How the field named `field`, within the fields named by `path`, takes
+	// part in a comparison.
+	pub(crate) fn select(&self, path: &[String], field: &str) -> Selection {
+		let path = path.iter().map(String::as_str).chain(Some(field)).collect::<Vec<_>>();
+		if self.matches(&path) {
+			Selection::Skip
+		} else if self.patterns.iter().any(|pattern| may_match_within(&pattern.segments, &path)) {
+			Selection::Nested
+		} else {
+			Selection::Whole
+		}
+	}
+}
+
+impl Pattern {
+	fn parse(text: &str) -> Result<Self, PatternError> {
+		let error = |reason| Err(PatternError { pattern: text.to_string(), reason });
+		if text.is_empty() {
+			return error("the pattern is empty");
+		}
+		let mut segments = Vec::new();
+		for name in text.split('.') {
+			segments.push(match name {
+				"" => return error("a field name is empty"),
+				"**" => Segment::Any,
+				_ if name.contains("**") => return error("`**` must stand for a whole field name"),
+				_ => Segment::Name(name.to_string()),
+			});
+		}
+		Ok(Pattern { text: text.to_string(), segments })
+	}
+}
+
+/// Run `f` with every derived comparison on this thread leaving out the
+/// fields ignored by `ignore`, which are reported as unchanged. Paths pass
+/// through collections and options as they do for a
+/// [`FieldMask`](crate::mask::FieldMask), and this takes the place of any
+/// mask in effect.
+pub fn with_ignore_set<R>(ignore: &IgnoreSet, f: impl FnOnce() -> R) -> R {
+	with_filter(Filter::Ignore(ignore.clone()), f)
+}
+
+// Whether the pattern matches the whole of `path`.
+fn matches(pattern: &[Segment], path: &[&str]) -> bool {
+	match pattern.split_first() {
+		None => path.is_empty(),
+		Some((Segment::Any, rest)) => matches(rest, path) || (!path.is_empty() && matches(pattern, &path[1..])),
+		Some((Segment::Name(glob), rest)) => {
+			!path.is_empty() && matches_name(glob.as_bytes(), path[0].as_bytes()) && matches(rest, &path[1..])
+		}
+	}
+}
+
+// Whether the pattern could match a path of fields within `path`, starting
+// with it and naming at least one more field.
+fn may_match_within(pattern: &[Segment], path: &[&str]) -> bool {
+	match pattern.split_first() {
+		None => false,
+		Some(_) if path.is_empty() => true,
+		Some((Segment::Any, rest)) => may_match_within(rest, path) || may_match_within(pattern, &path[1..]),
+		Some((Segment::Name(glob), rest)) => {
+			matches_name(glob.as_bytes(), path[0].as_bytes()) && may_match_within(rest, &path[1..])
+		}
+	}
+}
+
+// Whether a field name matches a pattern, in which `*` matches any run of
+// characters and `?` any one character.
+fn matches_name(glob: &[u8], name: &[u8]) -> bool {
+	match glob.split_first() {
+		None => name.is_empty(),
+		Some((b'*', rest)) => (0..=name.len()).any(|skip| matches_name(rest, &name[skip..])),
+		Some((b'?', rest)) => {
+			// Skip a whole character, which may take more than one byte.
+			let len = std::str::from_utf8(name).ok().and_then(|s| s.chars().next()).map_or(0, char::len_utf8);
+			len > 0 && matches_name(rest, &name[len..])
+		}
+		Some((c, rest)) => name.first() == Some(c) && matches_name(rest, &name[1..]),
+	}
+}
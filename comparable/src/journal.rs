@@ -0,0 +1,107 @@
+use std::time::SystemTime;
+
+use crate::types::{Changed, Comparable};
+
+/// A single change recorded in a [`Journal`], along with the time at which it
+/// was recorded.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, Debug)]
+pub struct JournalEntry<Change> {
+	pub timestamp: SystemTime,
+	pub change: Change,
+}
+
+/// An append-only log of the changes made to a value since a base snapshot.
+///
+/// Since changes only describe what happened to a value, replaying them onto
+/// the snapshot requires a function that knows how to apply a change of that
+/// type. When the `serde` feature is enabled the journal can be serialized
+/// with any serde format, so that it may be persisted and replayed later.
+///
+/// ```
+/// # use comparable::*;
+/// let mut journal = Journal::new(10);
+/// journal.record(&11);
+/// journal.record(&11);
+/// journal.record(&15);
+/// assert_eq!(journal.len(), 2);
+/// assert_eq!(journal.replay(|value, change: &I32Change| *value = change.1), 15);
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+	feature = "serde",
+	serde(bound(
+		serialize = "T: serde::Serialize, T::Change: serde::Serialize",
+		deserialize = "T: serde::Deserialize<'de>, T::Change: serde::Deserialize<'de>"
+	))
+)]
+#[derive(Debug)]
+pub struct Journal<T: Comparable + Clone> {
+	snapshot: T,
+	latest: T,
+	entries: Vec<JournalEntry<T::Change>>,
+	compact_after: Option<usize>,
+}
+
+impl<T: Comparable + Clone> Journal<T> {
+	pub fn new(snapshot: T) -> Self {
+		Journal { latest: snapshot.clone(), snapshot, entries: Vec::new(), compact_after: None }
+	}
+
+	/// Automatically compact the journal whenever it holds more than `limit`
+	/// entries.
+	pub fn with_compaction(mut self, limit: usize) -> Self {
+		self.compact_after = Some(limit);
+		self
+	}
+
+	/// Record the changes between the most recently recorded state and
+	/// `value`. Nothing is appended if the value has not changed.
+	pub fn record(&mut self, value: &T) -> Option<&JournalEntry<T::Change>> {
+		match self.latest.comparison(value) {
+			Changed::Unchanged => None,
+			Changed::Changed(change) => {
+				if self.compact_after.is_some_and(|limit| self.entries.len() >= limit) {
+					self.compact();
+				}
+				self.latest = value.clone();
+				self.entries.push(JournalEntry { timestamp: SystemTime::now(), change });
+				self.entries.last()
+			}
+		}
+	}
+
+	/// The state from which the recorded changes are replayed.
+	pub fn snapshot(&self) -> &T {
+		&self.snapshot
+	}
+
+	pub fn entries(&self) -> &[JournalEntry<T::Change>] {
+		&self.entries
+	}
+
+	pub fn len(&self) -> usize {
+		self.entries.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	/// Rebuild the most recent state by applying each recorded change in turn
+	/// to a copy of the snapshot.
+	pub fn replay(&self, mut apply: impl FnMut(&mut T, &T::Change)) -> T {
+		let mut value = self.snapshot.clone();
+		for entry in &self.entries {
+			apply(&mut value, &entry.change);
+		}
+		value
+	}
+
+	/// Replace the snapshot with the most recently recorded state, discarding
+	/// all of the entries leading up to it.
+	pub fn compact(&mut self) {
+		self.snapshot = self.latest.clone();
+		self.entries.clear();
+	}
+}
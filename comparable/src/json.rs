@@ -0,0 +1,326 @@
+//! Comparing JSON documents.
+//!
+//! With the `json` feature, [`Comparable`] is implemented for
+//! `serde_json::Value`, so that documents whose shape is not known ahead of
+//! time, such as configuration files or the bodies of API responses, can be
+//! compared as precisely as typed values. A value is described by itself, and
+//! its changes are a [`ValueChange`]: arrays are compared as a `Vec` and
+//! objects as a map, with their members keyed by name, while any other value
+//! that differs, or a value replaced by one of another kind, is reported as
+//! replaced.
+//!
+//! ```
+//! # use comparable::*;
+//! use comparable::json::ValueChange;
+//! use serde_json::json;
+//!
+//! let before = json!({ "name": "Alice", "tags": ["a"] });
+//! let after = json!({ "name": "Alice", "tags": ["a", "b"], "age": 42 });
+//! assert_eq!(
+//!     before.comparison(&after),
+//!     Changed::Changed(ValueChange::Object(vec![
+//!         MapChange::Added("age".to_string(), json!(42)),
+//!         MapChange::Changed("tags".to_string(), ValueChange::Array(vec![VecChange::Added(1, json!("b"))])),
+//!     ]))
+//! );
+//! assert_eq!(
+//!     comparable::display::display(&before.comparison(&after)).to_string(),
+//!     "[\"age\"]: added 42; [\"tags\"][1]: added \"b\""
+//! );
+//! ```
+//!
+//! Changes to documents are applied using [`Apply`], and written as a JSON
+//! Patch document using
+//! [`format::json_patch::diff`](crate::format::json_patch::diff). Values are
+//! rendered by [`DisplayChange`] and [`ToTree`] as JSON text.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+
+use serde_json::{Map, Value};
+
+use crate::display::{push_line, DisplayChange};
+use crate::lens::{
+	apply_map, check_map, expect_current, verify_entry, verify_within, Apply, ApplyError, ConflictError,
+};
+use crate::location::{Path, Segment};
+use crate::map::{with_owned_key, MapChange};
+use crate::roundtrip::{FromDesc, IntoDesc};
+use crate::set::VecChange;
+use crate::tree::{push_child, ChangeTree, ToTree};
+use crate::types::{Changed, Comparable};
+
+/// The changes to a JSON value.
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+pub enum ValueChange {
+	/// The value was replaced by another, such as a number or string that
+	/// changed, or an array that became an object.
+	Replaced(Value, Value),
+	/// The changes to the elements of an array, as for a `Vec`.
+	Array(Vec<VecChange<Value, ValueChange>>),
+	/// The changes to the members of an object, as for a map keyed by name.
+	Object(Vec<MapChange<String, Value, ValueChange>>),
+}
+
+// The members of an object, in the order of their names, whatever the order
+// kept by the map.
+fn members(object: &Map<String, Value>) -> BTreeMap<&String, &Value> {
+	object.iter().collect()
+}
+
+impl Comparable for Value {
+	type Desc = Value;
+
+	fn describe(&self) -> Self::Desc {
+		self.clone()
+	}
+
+	type Change = ValueChange;
+
+	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
+		match (self, other) {
+			(Value::Array(before), Value::Array(after)) => before.comparison(after).map(ValueChange::Array),
+			(Value::Object(before), Value::Object(after)) => members(before)
+				.comparison(&members(after))
+				.map(|changes| ValueChange::Object(changes.into_iter().map(with_owned_key).collect())),
+			_ if self == other => Changed::Unchanged,
+			_ => Changed::Changed(ValueChange::Replaced(self.clone(), other.clone())),
+		}
+	}
+
+	fn similarity(&self, other: &Self) -> f64 {
+		match (self, other) {
+			(Value::Array(before), Value::Array(after)) => before.similarity(after),
+			(Value::Object(before), Value::Object(after)) => members(before).similarity(&members(after)),
+			_ if self == other => 1.0,
+			_ => 0.0,
+		}
+	}
+
+	fn differs(&self, other: &Self) -> bool {
+		match (self, other) {
+			(Value::Array(before), Value::Array(after)) => before.differs(after),
+			(Value::Object(before), Value::Object(after)) => members(before).differs(&members(after)),
+			_ => self != other,
+		}
+	}
+
+	fn count_changes(&self, other: &Self) -> usize {
+		match (self, other) {
+			(Value::Array(before), Value::Array(after)) => before.count_changes(after),
+			(Value::Object(before), Value::Object(after)) => members(before).count_changes(&members(after)),
+			_ => usize::from(self != other),
+		}
+	}
+
+	fn first_difference(&self, other: &Self) -> Option<Path> {
+		match (self, other) {
+			(Value::Array(before), Value::Array(after)) => before.first_difference(after),
+			(Value::Object(before), Value::Object(after)) => members(before).first_difference(&members(after)),
+			_ => (self != other).then(Path::default),
+		}
+	}
+
+	fn fingerprint(&self) -> Option<u64> {
+		let mut hasher = DefaultHasher::new();
+		hash_value(self, &mut hasher);
+		Some(hasher.finish())
+	}
+}
+
+// Hash a value so that values that are equal hash alike, whatever the order
+// of the members of their objects. Integers are hashed as their JSON text,
+// and floating-point numbers by their bits, with `-0.0` as `0.0`, which it
+// equals.
+fn hash_value(value: &Value, hasher: &mut DefaultHasher) {
+	std::mem::discriminant(value).hash(hasher);
+	match value {
+		Value::Null => (),
+		Value::Bool(b) => b.hash(hasher),
+		Value::Number(n) => match n.as_f64().filter(|_| n.is_f64()) {
+			Some(f) => (f + 0.0).to_bits().hash(hasher),
+			None => n.to_string().hash(hasher),
+		},
+		Value::String(s) => s.hash(hasher),
+		Value::Array(elements) => {
+			elements.len().hash(hasher);
+			elements.iter().for_each(|element| hash_value(element, hasher))
+		}
+		Value::Object(object) => {
+			object.len().hash(hasher);
+			for (name, value) in members(object) {
+				name.hash(hasher);
+				hash_value(value, hasher)
+			}
+		}
+	}
+}
+
+impl FromDesc for Value {
+	fn from_desc(desc: Self::Desc) -> Self {
+		desc
+	}
+}
+
+impl IntoDesc for Value {
+	fn into_desc(self) -> Self::Desc {
+		self
+	}
+}
+
+impl Apply for Value {
+	fn apply(&mut self, change: Self::Change) {
+		match (self, change) {
+			(Value::Array(elements), ValueChange::Array(changes)) => elements.apply(changes),
+			(Value::Object(members), ValueChange::Object(changes)) => apply_map(
+				members,
+				changes,
+				|members, key, value| {
+					members.insert(key, value);
+				},
+				|members, key| members.remove(key),
+				|members, key| members.get_mut(key),
+			),
+			(value, ValueChange::Replaced(_, after)) => *value = after,
+			_ => (),
+		}
+	}
+
+	fn check(&self, change: &Self::Change) -> Result<(), ApplyError> {
+		match (self, change) {
+			(Value::Array(elements), ValueChange::Array(changes)) => elements.check(changes),
+			(Value::Object(members), ValueChange::Object(changes)) => check_map(
+				members,
+				changes,
+				|members, key| members.get(key),
+				|_, value, change| value.check(change),
+				|_, _| Ok(()),
+			),
+			(_, ValueChange::Replaced(..)) => Ok(()),
+			_ => Err(ApplyError::VariantMismatch(Path::default())),
+		}
+	}
+
+	fn verify(&self, change: &Self::Change) -> Result<(), ConflictError> {
+		match (self, change) {
+			(Value::Array(elements), ValueChange::Array(changes)) => elements.verify(changes),
+			(Value::Object(members), ValueChange::Object(changes)) => check_map(
+				members,
+				changes,
+				|members, key| members.get(key),
+				|_, value, change| value.verify(change),
+				|_, _| Ok(()),
+			),
+			(_, ValueChange::Replaced(before, _)) => expect_current(self, before),
+			_ => Err(ApplyError::VariantMismatch(Path::default()).into()),
+		}
+	}
+
+	fn verify_from(&self, base: &Self, change: &Self::Change) -> Result<(), ConflictError> {
+		match (self, base, change) {
+			(Value::Array(elements), Value::Array(base), ValueChange::Array(changes)) => {
+				elements.verify_from(base, changes)
+			}
+			(Value::Object(members), Value::Object(base), ValueChange::Object(changes)) => check_map(
+				members,
+				changes,
+				|members, key| members.get(key),
+				|key, value, change| verify_within(value, base.get(key), change),
+				|key, value| verify_entry(value, base.get(key)),
+			),
+			_ => self.verify(change),
+		}
+	}
+}
+
+/// Values are written as JSON text, and members keyed by the `Debug` format
+/// of their names, as for maps.
+impl DisplayChange for ValueChange {
+	fn display_changes(&self, path: &Path, lines: &mut Vec<String>) {
+		match self {
+			ValueChange::Replaced(before, after) => push_line(lines, path, format_args!("{} → {}", before, after)),
+			ValueChange::Array(changes) => {
+				for change in changes {
+					let element = |index: &usize| path.child(Segment::Index(*index));
+					match change {
+						VecChange::Added(index, value) => {
+							push_line(lines, &element(index), format_args!("added {}", value))
+						}
+						VecChange::Changed(index, change) => change.display_changes(&element(index), lines),
+						VecChange::Removed(index, value) => {
+							push_line(lines, &element(index), format_args!("removed {}", value))
+						}
+						VecChange::Moved { from, to } => {
+							push_line(lines, &element(from), format_args!("moved to [{}]", to))
+						}
+					}
+				}
+			}
+			ValueChange::Object(changes) => {
+				for change in changes {
+					let member = |name: &String| path.child(Segment::Key(format!("{:?}", name)));
+					match change {
+						MapChange::Added(name, value) => {
+							push_line(lines, &member(name), format_args!("added {}", value))
+						}
+						MapChange::Changed(name, change) => change.display_changes(&member(name), lines),
+						MapChange::Removed(name) => push_line(lines, &member(name), "removed"),
+						MapChange::Moved { from, to, change } => {
+							push_line(lines, &member(from), format_args!("moved to [{:?}]", to));
+							change.display_changes(&member(to), lines)
+						}
+					}
+				}
+			}
+		}
+	}
+}
+
+/// Values are written as JSON text, and members keyed by the `Debug` format
+/// of their names, as for maps.
+impl ToTree for ValueChange {
+	fn to_tree(&self) -> ChangeTree {
+		let mut children = Vec::new();
+		match self {
+			ValueChange::Replaced(before, after) => {
+				return ChangeTree::Modified { before: before.to_string(), after: after.to_string() }
+			}
+			ValueChange::Array(changes) => {
+				for change in changes {
+					match change {
+						VecChange::Added(index, value) => {
+							children.push((index.to_string(), ChangeTree::Added(value.to_string())))
+						}
+						VecChange::Changed(index, change) => {
+							push_child(&mut children, index.to_string(), change.to_tree())
+						}
+						VecChange::Removed(index, value) => {
+							children.push((index.to_string(), ChangeTree::Removed(value.to_string())))
+						}
+						VecChange::Moved { from, to } => {
+							children.push((from.to_string(), ChangeTree::Moved(to.to_string())))
+						}
+					}
+				}
+			}
+			ValueChange::Object(changes) => {
+				let key = |name: &String| format!("{:?}", name);
+				for change in changes {
+					match change {
+						MapChange::Added(name, value) => {
+							children.push((key(name), ChangeTree::Added(value.to_string())))
+						}
+						MapChange::Changed(name, change) => push_child(&mut children, key(name), change.to_tree()),
+						MapChange::Removed(name) => children.push((key(name), ChangeTree::Removed(String::new()))),
+						MapChange::Moved { from, to, change } => {
+							children.push((key(from), ChangeTree::Moved(key(to))));
+							push_child(&mut children, key(to), change.to_tree())
+						}
+					}
+				}
+			}
+		}
+		ChangeTree::Children(children)
+	}
+}
@@ -0,0 +1,72 @@
+use std::fmt::Debug;
+use std::ops::{Deref, DerefMut};
+
+use crate::types::{Changed, Comparable};
+
+/// A value compared as a whole, for types that do not implement
+/// [`Comparable`] themselves. It is described by a clone of the value, and
+/// its change holds the values before and after, so the type need only
+/// implement `PartialEq`, `Clone` and `Debug`.
+///
+/// ```
+/// # use comparable::*;
+/// #[derive(Clone, PartialEq, Debug)]
+/// struct Version(u32, u32);
+///
+/// assert_changes!(&Leaf(Version(1, 0)), &Leaf(Version(1, 0)), Changed::Unchanged);
+/// assert_changes!(&Leaf(Version(1, 0)), &Leaf(Version(1, 1)), Changed::Changed((Version(1, 0), Version(1, 1))));
+/// ```
+///
+/// A field of a derived type may be compared in this way without wrapping
+/// it, by marking it with `#[comparable_leaf]`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct Leaf<T>(pub T);
+
+impl<T> Leaf<T> {
+	/// The value compared.
+	pub fn into_inner(self) -> T {
+		self.0
+	}
+}
+
+impl<T> From<T> for Leaf<T> {
+	fn from(value: T) -> Self {
+		Leaf(value)
+	}
+}
+
+impl<T> Deref for Leaf<T> {
+	type Target = T;
+
+	fn deref(&self) -> &T {
+		&self.0
+	}
+}
+
+impl<T> DerefMut for Leaf<T> {
+	fn deref_mut(&mut self) -> &mut T {
+		&mut self.0
+	}
+}
+
+impl<T: PartialEq + Clone + Debug> Comparable for Leaf<T> {
+	type Desc = T;
+
+	fn describe(&self) -> Self::Desc {
+		self.0.clone()
+	}
+
+	type Change = (T, T);
+
+	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
+		compare(&self.0, &other.0)
+	}
+}
+
+pub(crate) fn compare<T: PartialEq + Clone>(value: &T, other: &T) -> Changed<(T, T)> {
+	if value != other {
+		Changed::Changed((value.clone(), other.clone()))
+	} else {
+		Changed::Unchanged
+	}
+}
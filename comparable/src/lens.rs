@@ -0,0 +1,797 @@
+//! Following the changes to a single field of a struct.
+//!
+//! A [`Lens`] focuses on one field of a struct: it reads and writes the field
+//! within a value, picks out the field's changes from those of the struct,
+//! and applies changes of the field back to a value using [`Apply`]. This
+//! suits user interfaces, where each part of a view depends on only some of
+//! the fields of a model, and should be updated only when those change.
+//!
+//! Lenses are derived along with [`Comparable`] using `#[comparable_lens]`,
+//! which adds a method returning a lens for each field, named after the field
+//! with `_lens` appended, and implements [`Apply`] for the struct. Enums with
+//! `#[comparable_lens]` implement [`Apply`] alone.
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! #[derive(Comparable, Clone)]
+//! #[comparable_lens]
+//! struct Model {
+//!     title: String,
+//!     count: u32,
+//! }
+//!
+//! let before = Model { title: "Inbox".to_string(), count: 3 };
+//! let after = Model { title: "Inbox".to_string(), count: 4 };
+//! let changes = before.comparison(&after).unwrap_or_default();
+//! assert_eq!(Model::title_lens().change(&changes), None);
+//! assert_eq!(Model::count_lens().change(&changes), Some(&U32Change(3, 4)));
+//!
+//! let mut view = before.clone();
+//! Model::count_lens().apply(&mut view, U32Change(3, 4));
+//! assert_eq!(view.count, 4);
+//! ```
+//!
+//! [`Comparable`]: crate::Comparable
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::{self, Debug};
+use std::hash::Hash;
+use std::path::PathBuf;
+
+use crate::leaf::Leaf;
+use crate::location::{Path, Segment};
+use crate::path::PathBufChange;
+use crate::roundtrip::FromDesc;
+use crate::scalar::*;
+use crate::set::{SetChange, VecChange};
+use crate::string::StringChange;
+use crate::types::Comparable;
+use crate::MapChange;
+use crate::OptionChange;
+
+/// Types that can be brought up to date with the changes reported by
+/// [`Comparable::comparison`]: applying the changes from `a` to `b` to a value
+/// equal to `a` makes it equal to `b`.
+///
+/// This is implemented for scalars, strings, options, boxes, vectors, sets and
+/// maps, and for structs and enums deriving `Comparable` with
+/// `#[comparable_lens]`. Fields marked with
+/// `comparable_redact` cannot be brought up to date, since their changes are
+/// not reported, and are left as they are.
+pub trait Apply: Comparable {
+	fn apply(&mut self, change: Self::Change);
+
+	/// Whether the change can be applied to this value, without applying it:
+	/// changes within an `Option` or an enum must find it holding the same
+	/// variant, the indices of the elements of a vector that are changed,
+	/// removed or moved must be within its bounds, and the entries of a map
+	/// or members of a set that are changed, removed or moved must exist,
+	/// while those added must not. Changes that replace a value can always be
+	/// applied. This
+	/// lets changes from elsewhere, such as a request to update some state,
+	/// be refused before any of them is applied.
+	///
+	/// ```
+	/// # use comparable::*;
+	/// use comparable::lens::{Apply, ApplyError};
+	/// use comparable::location::{Path, Segment};
+	///
+	/// let scores: std::collections::BTreeMap<&str, u8> = vec![("ann", 1)].into_iter().collect();
+	/// assert_eq!(scores.check(&vec![MapChange::Changed("ann", U8Change(1, 2))]), Ok(()));
+	/// assert_eq!(
+	///     scores.check(&vec![MapChange::Removed("bo")]),
+	///     Err(ApplyError::MissingKey(Path::from(vec![Segment::Key("\"bo\"".to_string())])))
+	/// );
+	/// ```
+	fn check(&self, _change: &Self::Change) -> Result<(), ApplyError> {
+		Ok(())
+	}
+
+	/// Whether the change was made from this value, as well as fitting it as
+	/// [`Apply::check`] requires: the values that the change replaces, such
+	/// as those before of scalars and of `Option`s that become `Some` or
+	/// `None`, must be those the value holds now. This is how changes made by
+	/// one copy of a value are guarded against others made to it meanwhile,
+	/// as with a compare-and-swap. The elements of vectors that are removed
+	/// must hold the values described by their changes, and entries added to
+	/// maps must still be absent, so that an entry inserted meanwhile is not
+	/// overwritten. The changes of maps do not hold the values of the entries
+	/// they remove, which are verified by [`Apply::verify_from`] instead.
+	///
+	/// ```
+	/// # use comparable::*;
+	/// use comparable::lens::{Apply, ConflictError};
+	/// use comparable::location::Path;
+	///
+	/// let mut count = 3u32;
+	/// assert_eq!(count.try_apply(U32Change(3, 4)), Ok(()));
+	/// assert_eq!(
+	///     count.try_apply(U32Change(3, 5)),
+	///     Err(ConflictError::Stale { path: Path::default(), expected: "3".to_string(), found: "4".to_string() })
+	/// );
+	/// assert_eq!(count, 4);
+	/// ```
+	fn verify(&self, change: &Self::Change) -> Result<(), ConflictError> {
+		self.check(change).map_err(ConflictError::Invalid)
+	}
+
+	/// Apply the change only if [`Apply::verify`] finds that it was made from
+	/// this value, leaving the value as it was otherwise.
+	fn try_apply(&mut self, change: Self::Change) -> Result<(), ConflictError> {
+		self.verify(&change)?;
+		self.apply(change);
+		Ok(())
+	}
+
+	/// As [`Apply::verify`], given also `base`, the value the change was made
+	/// from, so that the entries of maps that the change removes must still
+	/// hold the values they held in `base`, wherever those maps are within
+	/// this value.
+	///
+	/// ```
+	/// # use comparable::*;
+	/// use comparable::lens::{Apply, ConflictError};
+	/// use comparable::location::{Path, Segment};
+	///
+	/// let base: std::collections::BTreeMap<&str, u8> = vec![("ann", 1), ("bo", 2)].into_iter().collect();
+	/// let changes = vec![MapChange::Removed("bo")];
+	/// let mut scores = base.clone();
+	/// scores.insert("bo", 3);
+	/// assert_eq!(scores.verify(&changes), Ok(()));
+	/// assert_eq!(
+	///     scores.verify_from(&base, &changes),
+	///     Err(ConflictError::Stale {
+	///         path: Path::from(vec![Segment::Key("\"bo\"".to_string())]),
+	///         expected: "2".to_string(),
+	///         found: "3".to_string(),
+	///     })
+	/// );
+	/// ```
+	fn verify_from(&self, base: &Self, change: &Self::Change) -> Result<(), ConflictError> {
+		let _ = base;
+		self.verify(change)
+	}
+
+	/// Apply the change only if [`Apply::verify_from`] finds that it was made
+	/// from `base` and that this value has not changed where the change
+	/// applies, leaving the value as it was otherwise.
+	fn try_apply_from(&mut self, base: &Self, change: Self::Change) -> Result<(), ConflictError> {
+		self.verify_from(base, &change)?;
+		self.apply(change);
+		Ok(())
+	}
+}
+
+/// Why a change cannot be applied to a value, with the location of the part
+/// of the value that does not fit it.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ApplyError {
+	/// The change is to a variant other than the value's, such as changes
+	/// within an `Option` that is `None`.
+	VariantMismatch(Path),
+	/// The change is to an entry of a map that does not exist.
+	MissingKey(Path),
+	/// The change adds an entry to a map that already exists.
+	KeyExists(Path),
+	/// The change is to an element of a vector beyond its end.
+	IndexOutOfRange(Path),
+}
+
+impl ApplyError {
+	/// The location of the part of the value that does not fit the change.
+	pub fn path(&self) -> &Path {
+		match self {
+			ApplyError::VariantMismatch(path)
+			| ApplyError::MissingKey(path)
+			| ApplyError::KeyExists(path)
+			| ApplyError::IndexOutOfRange(path) => path,
+		}
+	}
+
+	/// This error, for a value within the given segment of a larger value.
+	pub fn within(self, segment: Segment) -> Self {
+		match self {
+			ApplyError::VariantMismatch(path) => ApplyError::VariantMismatch(path.within(segment)),
+			ApplyError::MissingKey(path) => ApplyError::MissingKey(path.within(segment)),
+			ApplyError::KeyExists(path) => ApplyError::KeyExists(path.within(segment)),
+			ApplyError::IndexOutOfRange(path) => ApplyError::IndexOutOfRange(path.within(segment)),
+		}
+	}
+}
+
+impl fmt::Display for ApplyError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ApplyError::VariantMismatch(path) => write!(f, "{} is a different variant than the change", path),
+			ApplyError::MissingKey(path) => write!(f, "{} does not exist", path),
+			ApplyError::KeyExists(path) => write!(f, "{} already exists", path),
+			ApplyError::IndexOutOfRange(path) => write!(f, "{} is beyond the end of the vector", path),
+		}
+	}
+}
+
+impl std::error::Error for ApplyError {}
+
+/// Why a change cannot be applied to a value by [`Apply::try_apply`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ConflictError {
+	/// The value at `path` is not the one the change was made from, written
+	/// with the value expected and that found in their `Debug` format.
+	Stale { path: Path, expected: String, found: String },
+	/// The change does not fit the value, as reported by [`Apply::check`].
+	Invalid(ApplyError),
+}
+
+impl ConflictError {
+	/// The location of the part of the value that does not fit the change.
+	pub fn path(&self) -> &Path {
+		match self {
+			ConflictError::Stale { path, .. } => path,
+			ConflictError::Invalid(error) => error.path(),
+		}
+	}
+
+	/// This error, for a value within the given segment of a larger value.
+	pub fn within(self, segment: Segment) -> Self {
+		match self {
+			ConflictError::Stale { path, expected, found } => {
+				ConflictError::Stale { path: path.within(segment), expected, found }
+			}
+			ConflictError::Invalid(error) => ConflictError::Invalid(error.within(segment)),
+		}
+	}
+}
+
+impl From<ApplyError> for ConflictError {
+	fn from(error: ApplyError) -> Self {
+		ConflictError::Invalid(error)
+	}
+}
+
+impl fmt::Display for ConflictError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ConflictError::Stale { path, expected, found } => {
+				write!(f, "{} was expected to be {}, but is {}", path, expected, found)
+			}
+			ConflictError::Invalid(error) => write!(f, "{}", error),
+		}
+	}
+}
+
+impl std::error::Error for ConflictError {}
+
+/// Verify that a value replaced by a change is the one it was made from, for
+/// implementations of [`Apply::verify`].
+pub fn expect_current<T: PartialEq + Debug + ?Sized>(current: &T, expected: &T) -> Result<(), ConflictError> {
+	if current == expected {
+		Ok(())
+	} else {
+		Err(ConflictError::Stale {
+			path: Path::default(),
+			expected: format!("{:?}", expected),
+			found: format!("{:?}", current),
+		})
+	}
+}
+
+// Verify that a value removed by a change is the one it was made from.
+pub(crate) fn verify_removed<Value: Comparable>(value: &Value, desc: &Value::Desc) -> Result<(), ConflictError> {
+	expect_current(&value.describe(), desc)
+}
+
+// Verify that an entry removed from a map by a change holds the value it held
+// in the map the change was made from, if it was there.
+pub(crate) fn verify_entry<Value: Comparable>(value: &Value, base: Option<&Value>) -> Result<(), ConflictError> {
+	base.map_or(Ok(()), |base| expect_current(&value.describe(), &base.describe()))
+}
+
+// Verify changes to a part of a value given the same part of the value they
+// were made from, if it has one.
+pub(crate) fn verify_within<Value: Apply>(
+	value: &Value,
+	base: Option<&Value>,
+	change: &Value::Change,
+) -> Result<(), ConflictError> {
+	match base {
+		Some(base) => value.verify_from(base, change),
+		None => value.verify(change),
+	}
+}
+
+// The errors of checking changes, which can be placed within larger values.
+pub(crate) trait Within: From<ApplyError> {
+	fn within(self, segment: Segment) -> Self;
+}
+
+impl Within for ApplyError {
+	fn within(self, segment: Segment) -> Self {
+		ApplyError::within(self, segment)
+	}
+}
+
+impl Within for ConflictError {
+	fn within(self, segment: Segment) -> Self {
+		ConflictError::within(self, segment)
+	}
+}
+
+/// A view of one field of a struct, and of the changes to it.
+pub struct Lens<Parent: Comparable, Field: Comparable> {
+	name: &'static str,
+	get: fn(&Parent) -> &Field,
+	get_mut: fn(&mut Parent) -> &mut Field,
+	change: fn(&Parent::Change) -> Option<&Field::Change>,
+}
+
+impl<Parent: Comparable, Field: Comparable> Lens<Parent, Field> {
+	// Called by derived methods returning lenses, through
+	// `derive_support::lens`.
+	#[doc(hidden)]
+	pub fn new(
+		name: &'static str,
+		get: fn(&Parent) -> &Field,
+		get_mut: fn(&mut Parent) -> &mut Field,
+		change: fn(&Parent::Change) -> Option<&Field::Change>,
+	) -> Self {
+		Lens { name, get, get_mut, change }
+	}
+
+	/// The name of the field, as written in a
+	/// [`FieldMask`](crate::mask::FieldMask).
+	pub fn name(&self) -> &'static str {
+		self.name
+	}
+
+	/// The field within a value.
+	pub fn get<'a>(&self, parent: &'a Parent) -> &'a Field {
+		(self.get)(parent)
+	}
+
+	/// The field within a value, for updating.
+	pub fn get_mut<'a>(&self, parent: &'a mut Parent) -> &'a mut Field {
+		(self.get_mut)(parent)
+	}
+
+	/// The changes to the field among the changes to the struct, if any.
+	pub fn change<'a>(&self, change: &'a Parent::Change) -> Option<&'a Field::Change> {
+		(self.change)(change)
+	}
+
+	/// Apply changes of the field to the field within a value.
+	pub fn apply(&self, parent: &mut Parent, change: Field::Change)
+	where
+		Field: Apply,
+	{
+		self.get_mut(parent).apply(change)
+	}
+}
+
+impl Apply for () {
+	fn apply(&mut self, _change: Self::Change) {}
+}
+
+impl<T: PartialEq + Clone + Debug> Apply for Leaf<T> {
+	fn apply(&mut self, change: Self::Change) {
+		let (_, after) = change;
+		self.0 = after;
+	}
+
+	fn verify(&self, change: &Self::Change) -> Result<(), ConflictError> {
+		expect_current(&self.0, &change.0)
+	}
+}
+
+macro_rules! impl_apply_for_self_describing {
+	($($ty:ty => $change:ident),*) => {
+		$(
+			impl Apply for $ty {
+				fn apply(&mut self, change: Self::Change) {
+					let $change(_, after) = change;
+					*self = after;
+				}
+
+				fn verify(&self, change: &Self::Change) -> Result<(), ConflictError> {
+					expect_current(self, &change.0)
+				}
+			}
+		)*
+	};
+}
+
+impl_apply_for_self_describing!(
+	f32 => F32Change,
+	f64 => F64Change,
+	String => StringChange,
+	PathBuf => PathBufChange
+);
+
+impl<T: Apply> Apply for Box<T> {
+	fn apply(&mut self, change: Self::Change) {
+		(**self).apply(change)
+	}
+
+	fn check(&self, change: &Self::Change) -> Result<(), ApplyError> {
+		(**self).check(change)
+	}
+
+	fn verify(&self, change: &Self::Change) -> Result<(), ConflictError> {
+		(**self).verify(change)
+	}
+
+	fn verify_from(&self, base: &Self, change: &Self::Change) -> Result<(), ConflictError> {
+		(**self).verify_from(base, change)
+	}
+}
+
+impl<T: Apply + FromDesc> Apply for Option<T> {
+	fn apply(&mut self, change: Self::Change) {
+		match change {
+			OptionChange::BothSome(change) => {
+				if let Some(value) = self {
+					value.apply(change)
+				}
+			}
+			OptionChange::Different(_, after) => *self = after.map(T::from_desc),
+		}
+	}
+
+	fn check(&self, change: &Self::Change) -> Result<(), ApplyError> {
+		match (change, self) {
+			(OptionChange::BothSome(change), Some(value)) => value.check(change),
+			(OptionChange::BothSome(_), None) => Err(ApplyError::VariantMismatch(Path::default())),
+			(OptionChange::Different(..), _) => Ok(()),
+		}
+	}
+
+	fn verify(&self, change: &Self::Change) -> Result<(), ConflictError> {
+		match (change, self) {
+			(OptionChange::BothSome(change), Some(value)) => value.verify(change),
+			(OptionChange::BothSome(_), None) => Err(ApplyError::VariantMismatch(Path::default()).into()),
+			(OptionChange::Different(before, _), _) => expect_current(&self.as_ref().map(T::describe), before),
+		}
+	}
+
+	fn verify_from(&self, base: &Self, change: &Self::Change) -> Result<(), ConflictError> {
+		match (change, self) {
+			(OptionChange::BothSome(change), Some(value)) => verify_within(value, base.as_ref(), change),
+			_ => self.verify(change),
+		}
+	}
+}
+
+// The changes to a map, applied using the given ways to reach its entries.
+pub(crate) fn apply_map<M, Key, Value: Apply + FromDesc>(
+	map: &mut M,
+	changes: Vec<MapChange<Key, Value::Desc, Value::Change>>,
+	insert: impl Fn(&mut M, Key, Value),
+	remove: impl Fn(&mut M, &Key) -> Option<Value>,
+	get_mut: impl for<'a> Fn(&'a mut M, &Key) -> Option<&'a mut Value>,
+) {
+	for change in changes {
+		match change {
+			MapChange::Added(key, desc) => insert(map, key, Value::from_desc(desc)),
+			MapChange::Changed(key, change) => {
+				if let Some(value) = get_mut(map, &key) {
+					value.apply(change)
+				}
+			}
+			MapChange::Removed(key) => {
+				remove(map, &key);
+			}
+			MapChange::Moved { from, to, change } => {
+				if let Some(mut value) = remove(map, &from) {
+					if let crate::Changed::Changed(change) = change {
+						value.apply(change)
+					}
+					insert(map, to, value)
+				}
+			}
+		}
+	}
+}
+
+// Whether the changes to a map can be applied, in order, using the given way
+// to reach its entries, checking the changes of each entry using `nested` and
+// each entry removed using `removed`, both given the entry's key. Entries
+// added and removed by earlier changes are tracked, so that an entry may be
+// removed and then added again.
+pub(crate) fn check_map<'a, M, Key: PartialEq + Debug + 'a, Value: Apply + 'a, E: Within>(
+	map: &'a M,
+	changes: &'a [MapChange<Key, Value::Desc, Value::Change>],
+	get: impl Fn(&'a M, &Key) -> Option<&'a Value>,
+	nested: impl Fn(&Key, &Value, &Value::Change) -> Result<(), E>,
+	removed_value: impl Fn(&Key, &Value) -> Result<(), E>,
+) -> Result<(), E> {
+	let entry = |key: &Key| Path::from(vec![Segment::Key(format!("{:?}", key))]);
+	let mut added: Vec<&Key> = Vec::new();
+	let mut removed: Vec<&Key> = Vec::new();
+	let exists = |key: &Key, added: &[&Key], removed: &[&Key]| {
+		added.contains(&key) || (get(map, key).is_some() && !removed.contains(&key))
+	};
+	for change in changes {
+		match change {
+			MapChange::Added(key, _) => {
+				if exists(key, &added, &removed) {
+					return Err(ApplyError::KeyExists(entry(key)).into());
+				}
+				added.push(key);
+			}
+			MapChange::Changed(key, change) => {
+				if !exists(key, &added, &removed) {
+					return Err(ApplyError::MissingKey(entry(key)).into());
+				}
+				if let Some(value) = get(map, key).filter(|_| !added.contains(&key)) {
+					nested(key, value, change).map_err(|e| e.within(Segment::Key(format!("{:?}", key))))?
+				}
+			}
+			MapChange::Removed(key) => {
+				if !exists(key, &added, &removed) {
+					return Err(ApplyError::MissingKey(entry(key)).into());
+				}
+				if let Some(value) = get(map, key).filter(|_| !added.contains(&key)) {
+					removed_value(key, value).map_err(|e| e.within(Segment::Key(format!("{:?}", key))))?
+				}
+				added.retain(|added| *added != key);
+				removed.push(key);
+			}
+			MapChange::Moved { from, to, change } => {
+				if !exists(from, &added, &removed) {
+					return Err(ApplyError::MissingKey(entry(from)).into());
+				}
+				if from != to && exists(to, &added, &removed) {
+					return Err(ApplyError::KeyExists(entry(to)).into());
+				}
+				if let (Some(value), crate::Changed::Changed(change)) =
+					(get(map, from).filter(|_| !added.contains(&from)), change)
+				{
+					nested(from, value, change).map_err(|e| e.within(Segment::Key(format!("{:?}", from))))?
+				}
+				added.retain(|added| *added != from);
+				removed.push(from);
+				removed.retain(|removed| *removed != to);
+				added.push(to);
+			}
+		}
+	}
+	Ok(())
+}
+
+impl<Key: Ord + Clone + Debug, Value: Apply + FromDesc> Apply for BTreeMap<Key, Value> {
+	fn apply(&mut self, change: Self::Change) {
+		apply_map(
+			self,
+			change,
+			|map, key, value| {
+				map.insert(key, value);
+			},
+			|map, key| map.remove(key),
+			|map, key| map.get_mut(key),
+		)
+	}
+
+	fn check(&self, change: &Self::Change) -> Result<(), ApplyError> {
+		check_map(self, change, |map, key| map.get(key), |_, value, change| value.check(change), |_, _| Ok(()))
+	}
+
+	fn verify(&self, change: &Self::Change) -> Result<(), ConflictError> {
+		check_map(self, change, |map, key| map.get(key), |_, value, change| value.verify(change), |_, _| Ok(()))
+	}
+
+	fn verify_from(&self, base: &Self, change: &Self::Change) -> Result<(), ConflictError> {
+		check_map(
+			self,
+			change,
+			|map, key| map.get(key),
+			|key, value, change| verify_within(value, base.get(key), change),
+			|key, value| verify_entry(value, base.get(key)),
+		)
+	}
+}
+
+impl<Key: Ord + Clone + Debug + Hash, Value: Apply + FromDesc> Apply for HashMap<Key, Value> {
+	fn apply(&mut self, change: Self::Change) {
+		apply_map(
+			self,
+			change,
+			|map, key, value| {
+				map.insert(key, value);
+			},
+			|map, key| map.remove(key),
+			|map, key| map.get_mut(key),
+		)
+	}
+
+	fn check(&self, change: &Self::Change) -> Result<(), ApplyError> {
+		check_map(self, change, |map, key| map.get(key), |_, value, change| value.check(change), |_, _| Ok(()))
+	}
+
+	fn verify(&self, change: &Self::Change) -> Result<(), ConflictError> {
+		check_map(self, change, |map, key| map.get(key), |_, value, change| value.verify(change), |_, _| Ok(()))
+	}
+
+	fn verify_from(&self, base: &Self, change: &Self::Change) -> Result<(), ConflictError> {
+		check_map(
+			self,
+			change,
+			|map, key| map.get(key),
+			|key, value, change| verify_within(value, base.get(key), change),
+			|key, value| verify_entry(value, base.get(key)),
+		)
+	}
+}
+
+// Where the elements of a vector of `len` elements are after the changes to
+// it: for each index after, the index before of the element placed there, or
+// `None` for those added. The changes give the indices before of the elements
+// removed and moved, and the indices after of those added, moved and changed,
+// and the elements kept fill the indices left after in their order.
+pub(crate) fn vec_layout<Desc, Change>(
+	len: usize,
+	changes: &[VecChange<Desc, Change>],
+) -> Result<Vec<Option<usize>>, ApplyError> {
+	let element = |index| Path::from(vec![Segment::Index(index)]);
+	let mut gone = vec![false; len];
+	let mut placed = Vec::new();
+	for change in changes {
+		let from = match change {
+			VecChange::Removed(from, _) => *from,
+			VecChange::Moved { from, to } => {
+				placed.push((*to, Some(*from)));
+				*from
+			}
+			VecChange::Added(to, _) => {
+				placed.push((*to, None));
+				continue;
+			}
+			VecChange::Changed(..) => continue,
+		};
+		match gone.get_mut(from) {
+			None => return Err(ApplyError::IndexOutOfRange(element(from))),
+			Some(true) => return Err(ApplyError::MissingKey(element(from))),
+			Some(gone) => *gone = true,
+		}
+	}
+	let mut slots = vec![None; gone.iter().filter(|gone| !**gone).count() + placed.len()];
+	for (to, from) in placed {
+		match slots.get_mut(to) {
+			None => return Err(ApplyError::IndexOutOfRange(element(to))),
+			Some(Some(_)) => return Err(ApplyError::KeyExists(element(to))),
+			Some(slot) => *slot = Some(from),
+		}
+	}
+	let mut kept = (0..len).filter(|from| !gone[*from]);
+	let layout: Vec<Option<usize>> = slots.into_iter().map(|slot| slot.unwrap_or_else(|| kept.next())).collect();
+	for change in changes {
+		if let VecChange::Changed(to, _) = change {
+			if *to >= layout.len() {
+				return Err(ApplyError::IndexOutOfRange(element(*to)));
+			}
+		}
+	}
+	Ok(layout)
+}
+
+// Whether the changes to a vector can be applied, checking the changes of
+// each element using `nested`, given its index before, and the description of
+// each element removed using `removed`. Changes to elements that were added
+// are not checked, since the vector does not hold them yet.
+fn check_vec<Value: Apply, E: Within>(
+	vec: &[Value],
+	changes: &[VecChange<Value::Desc, Value::Change>],
+	nested: impl Fn(usize, &Value, &Value::Change) -> Result<(), E>,
+	removed: impl Fn(&Value, &Value::Desc) -> Result<(), E>,
+) -> Result<(), E> {
+	let layout = vec_layout(vec.len(), changes)?;
+	for change in changes {
+		match change {
+			VecChange::Changed(to, change) => {
+				if let Some(from) = layout[*to] {
+					nested(from, &vec[from], change).map_err(|e| e.within(Segment::Index(from)))?
+				}
+			}
+			VecChange::Removed(from, desc) => {
+				removed(&vec[*from], desc).map_err(|e| e.within(Segment::Index(*from)))?
+			}
+			VecChange::Added(..) | VecChange::Moved { .. } => (),
+		}
+	}
+	Ok(())
+}
+
+impl<Value: PartialEq + Apply + FromDesc> Apply for Vec<Value> {
+	fn apply(&mut self, change: Self::Change) {
+		// Changes that do not fit the vector, as reported by `check`, leave
+		// it as it was.
+		let layout = match vec_layout(self.len(), &change) {
+			Ok(layout) => layout,
+			Err(_) => return,
+		};
+		let mut before: Vec<Option<Value>> = std::mem::take(self).into_iter().map(Some).collect();
+		let mut after: Vec<Option<Value>> =
+			layout.into_iter().map(|from| from.and_then(|from| before[from].take())).collect();
+		for change in change {
+			match change {
+				VecChange::Added(to, desc) => after[to] = Some(Value::from_desc(desc)),
+				VecChange::Changed(to, change) => {
+					if let Some(value) = &mut after[to] {
+						value.apply(change)
+					}
+				}
+				VecChange::Removed(..) | VecChange::Moved { .. } => (),
+			}
+		}
+		*self = after.into_iter().flatten().collect();
+	}
+
+	fn check(&self, change: &Self::Change) -> Result<(), ApplyError> {
+		check_vec(self, change, |_, value, change| value.check(change), |_, _| Ok(()))
+	}
+
+	fn verify(&self, change: &Self::Change) -> Result<(), ConflictError> {
+		check_vec(self, change, |_, value, change| value.verify(change), verify_removed)
+	}
+
+	fn verify_from(&self, base: &Self, change: &Self::Change) -> Result<(), ConflictError> {
+		check_vec(self, change, |index, value, change| verify_within(value, base.get(index), change), verify_removed)
+	}
+}
+
+// Whether the changes to a set can be applied, in order, to one holding
+// members with the given descriptions.
+fn check_set<Desc: PartialEq + Debug>(members: Vec<Desc>, changes: &[SetChange<Desc>]) -> Result<(), ApplyError> {
+	let member = |desc: &Desc| Path::from(vec![Segment::Key(format!("{:?}", desc))]);
+	let mut members: Vec<&Desc> = members.iter().collect();
+	let mut added: Vec<&Desc> = Vec::new();
+	for change in changes {
+		match change {
+			SetChange::Added(desc) => {
+				if members.contains(&desc) || added.contains(&desc) {
+					return Err(ApplyError::KeyExists(member(desc)));
+				}
+				added.push(desc);
+			}
+			SetChange::Removed(desc) => {
+				if let Some(position) = added.iter().position(|added| *added == desc) {
+					added.remove(position);
+				} else if let Some(position) = members.iter().position(|member| *member == desc) {
+					members.remove(position);
+				} else {
+					return Err(ApplyError::MissingKey(member(desc)));
+				}
+			}
+		}
+	}
+	Ok(())
+}
+
+impl<Value: Ord + FromDesc> Apply for BTreeSet<Value> {
+	fn apply(&mut self, change: Self::Change) {
+		for change in change {
+			match change {
+				SetChange::Added(desc) => self.insert(Value::from_desc(desc)),
+				SetChange::Removed(desc) => self.remove(&Value::from_desc(desc)),
+			};
+		}
+	}
+
+	fn check(&self, change: &Self::Change) -> Result<(), ApplyError> {
+		check_set(self.describe(), change)
+	}
+}
+
+impl<Value: Ord + Hash + FromDesc> Apply for HashSet<Value> {
+	fn apply(&mut self, change: Self::Change) {
+		for change in change {
+			match change {
+				SetChange::Added(desc) => self.insert(Value::from_desc(desc)),
+				SetChange::Removed(desc) => self.remove(&Value::from_desc(desc)),
+			};
+		}
+	}
+
+	fn check(&self, change: &Self::Change) -> Result<(), ApplyError> {
+		check_set(self.describe(), change)
+	}
+}
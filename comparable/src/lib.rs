@@ -1,5 +1,6 @@
 #![allow(clippy::derive_partial_eq_without_eq)]
 #![allow(clippy::too_long_first_doc_paragraph)]
+#![allow(clippy::doc_overindented_list_items)]
 
 //! The `comparable` crate defines the trait [`Comparable`], along with a derive
 //! macro for auto-generating instances of this trait for most data types.
@@ -38,6 +39,9 @@
 //!    c. Use [`assert_changes!`] between the initial state and the resulting state
 //!       to assert that whatever happened is exactly what you expected to happen.
 //!
+//! The items needed in most tests, including the trait, its derive macro and
+//! [`assert_changes!`], can be imported at once with `use comparable::prelude::*;`.
+//!
 //! The main benefit of this approach over the usual method of "probing" the
 //! resulting state -- to ensure it changed as you expected it to-- is that it
 //! asserts against the exhaustive set of changes to ensure that no unintended
@@ -45,6 +49,26 @@
 //! both a positive and a negative test: checking for what you expect to see as
 //! well as what you don't expect to see.
 //!
+//! For simple uses, the free functions [`diff`], [`describe`] and [`changed`]
+//! compare and describe values without the [`Comparable`] trait being imported:
+//!
+//! ```
+//! # use comparable_derive::*;
+//! #[derive(Comparable)]
+//! struct Point {
+//!     x: i32,
+//!     y: i32,
+//! }
+//!
+//! let (before, after) = (Point { x: 1, y: 2 }, Point { x: 1, y: 3 });
+//! assert!(comparable::changed(&before, &after));
+//! assert_eq!(
+//!     comparable::diff(&before, &after),
+//!     comparable::Changed::Changed(vec![PointChange::Y(comparable::I32Change(2, 3))])
+//! );
+//! assert_eq!(comparable::describe(&before), PointDesc { x: 1, y: 2 });
+//! ```
+//!
 //! # The Comparable trait
 //!
 //! The [`Comparable`] trait has two associated types and two methods, one pair
@@ -108,6 +132,28 @@
 //! For scalars, the [`Comparable::Desc`] type is the same as the type it's
 //! describing, and these are called "self-describing".
 //!
+//! If only the description of a type is needed, for example to take
+//! serializable snapshots of it, deriving `Describe` instead of `Comparable`
+//! generates just the `Desc` type and an implementation of the
+//! [`describe::Describe`] trait, without any of the machinery for changes. The
+//! fields of such a type need only implement `Describe`, which every
+//! [`Comparable`] type does:
+//!
+//! ```
+//! # use comparable_derive::*;
+//! use comparable::describe::Describe;
+//!
+//! #[derive(Describe)]
+//! struct Snapshot {
+//!   name: String,
+//!   size: u64
+//! }
+//!
+//! assert_eq!(
+//!   Snapshot { name: "a".to_string(), size: 1 }.describe(),
+//!   SnapshotDesc { name: "a".to_string(), size: 1 }
+//! );
+//! ```
 //! There are other macro attributes provided for customizing things even further,
 //! which are covered below, beginning at the section on [Structures](#structs).
 //!
@@ -133,6 +179,29 @@
 //! structs and enums, so more detail is given below in the section discussing
 //! each of these types.
 //!
+//! When only whether two values differ matters, [`Comparable::differs`] answers
+//! this without building any changes. For the standard collections and for types
+//! deriving `Comparable`, it stops at the first difference found, which makes it
+//! much cheaper than checking the result of `comparison` for large values that
+//! usually compare equal.
+//!
+//! Similarly, [`Comparable::count_changes`] counts the fields, elements and
+//! entries that differ between two values, again without building the changes,
+//! for when only the extent of a change matters, such as when deciding whether
+//! enough has changed to act on it.
+//!
+//! For assertion messages about large values, [`Comparable::first_difference`]
+//! gives the location of the first value found to differ, such as
+//! `items[0].price`, as a [`location::Path`].
+//!
+//! With the `serde` feature enabled, `Changed` is serialized as the enum it is.
+//! The `changed` module describes how to serialize it as an `Option` instead,
+//! so that unchanged values appear as `null` in formats such as JSON.
+//!
+//! The `wire` module specifies the serialized form of every change, so that
+//! programs written in other languages can read and write changes, and
+//! versions it, so that a reader can reject a form it does not understand.
+//!
 //! # Scalars
 //!
 //! [`Comparable`] traits have been implemented for all of the basic scalar types.
@@ -152,6 +221,42 @@
 //! );
 //! ```
 //!
+//! The same is implemented for the integer types up to `u128` and `i128`, their
+//! `NonZero` forms, `Duration`, `SystemTime`, and the addresses in `std::net`.
+//! Other types that are compared as a whole, because they are `Copy` and
+//! `PartialEq`, may be given the same implementation in a line with
+//! `impl_comparable_for_leaf!`, which names the change structure to define:
+//!
+//! ```
+//! # use comparable::*;
+//! #[derive(Clone, Copy, PartialEq, Debug)]
+//! struct Celsius(i16);
+//!
+//! impl_comparable_for_leaf!(Celsius => CelsiusChange);
+//!
+//! assert_changes!(&Celsius(20), &Celsius(21), Changed::Changed(CelsiusChange(Celsius(20), Celsius(21))));
+//! ```
+//!
+//! Floating-point values are compared exactly by default. To keep rounding noise
+//! from registering as a change, comparisons made within a call to
+//! [`with_float_tolerance`] allow values to differ by an absolute amount, or by a
+//! number of units in the last place:
+//!
+//! ```
+//! # use comparable::*;
+//! with_float_tolerance(FloatTolerance::Absolute(1e-9), || {
+//!     assert_changes!(&(0.1 + 0.2), &0.3, Changed::Unchanged);
+//! });
+//! ```
+//! Strings whose differences in case or surrounding whitespace are not
+//! significant may be wrapped in [`CaseInsensitive`] or [`Trimmed`], which are
+//! still described exactly as written:
+//!
+//! ```
+//! # use comparable::*;
+//! assert_changes!(&CaseInsensitive("Hello"), &CaseInsensitive("HELLO"), Changed::Unchanged);
+//! assert_changes!(&Trimmed(" name "), &Trimmed("name"), Changed::Unchanged);
+//! ```
 //! # Vec and Set Collections
 //!
 //! The set collections for which [`Comparable`] has been implemented are: `Vec`,
@@ -162,6 +267,24 @@
 //! likely report every item as changed from there until the end of the vector, at
 //! which point it will report an added member.
 //!
+//! When elements are only reordered, setting `DiffOptions::sequence_moves` with
+//! [`with_diff_options`] reports each element found unchanged at a new index as
+//! `VecChange::Moved { from, to }`, rather than as a change at every index
+//! involved.
+//!
+//! Insertions and removals in the middle of a `Vec` can be recognized by setting
+//! `DiffOptions::sequence_algorithm` to one of the [`DiffAlgorithm`]s, `Myers`,
+//! `Patience` or `Histogram`, which align the elements of both vectors before
+//! comparing them. Patience and histogram diffs are slower than Myers' algorithm,
+//! but often give results that follow the structure of the data more closely.
+//!
+//! When elements are aligned, those between aligned elements are paired up by
+//! position and reported as changed. Setting `DiffOptions::sequence_similarity`
+//! pairs them only when they are at least as similar as the given threshold,
+//! using [`Comparable::similarity`], so that an element of a `Vec` of structs
+//! which changed in a single field is reported as changed, rather than removed
+//! and added again.
+//!
 //! `HashSet` and `BTreeSet` types both report changes the same way, using the
 //! `SetChange` type. Note that in order for `HashSet` change results to be
 //! deterministic, the values in a `HashSet` must support the `Ord` trait so they
@@ -259,6 +382,73 @@
 //!     test_comparable_bar
 //! ```
 //!
+//! # Binary Data
+//!
+//! Byte slices (`[u8]`), as well as `bytes::Bytes` and `bytes::BytesMut` when
+//! the `bytes` feature is enabled, are compared as binary data: rather than
+//! reporting a full copy of both values, their [`Comparable::Change`] is a
+//! `Vec<BytesChange>` giving the offset of each run of differing bytes, together
+//! with the old and new contents of that run. The parts the two values have in
+//! common are found block by block, as rsync does, so that bytes inserted or
+//! removed in one place are reported as such, rather than as a change to every
+//! byte after them. Since `Vec<u8>` is compared element-wise like any other
+//! `Vec`, compare `v.as_slice()` instead to get the binary representation.
+//!
+//! ```
+//! # use comparable::*;
+//! assert_changes!(
+//!     &&b"hello world"[..],
+//!     &&b"jello world"[..],
+//!     Changed::Changed(vec![BytesChange { offset: 0, old: b"h".to_vec(), new: b"j".to_vec() }]),
+//! );
+//! ```
+//!
+//! # Exact Numbers
+//!
+//! When the `ordered-float` feature is enabled, [`Comparable`] is implemented for
+//! `OrderedFloat` and `NotNan`, and when the `rust_decimal` feature is enabled,
+//! for `Decimal`, so that financial and other numeric models using them can
+//! derive `Comparable`. Each is described by the number it holds, and its change
+//! carries the difference between the two values along with the values
+//! themselves, as `OrderedFloatChange(before, after, difference)` or
+//! `DecimalChange(before, after, difference)`:
+//!
+//! ```ignore
+//! use rust_decimal::Decimal;
+//!
+//! assert_eq!(
+//!     Decimal::new(1050, 2).comparison(&Decimal::new(1200, 2)),
+//!     Changed::Changed(DecimalChange(Decimal::new(1050, 2), Decimal::new(1200, 2), Decimal::new(150, 2)))
+//! );
+//! ```
+//!
+//!
+//! # Flags
+//!
+//! When the `bitflags` feature is enabled, `impl_comparable_for_bitflags!`
+//! implements [`Comparable`] for types generated by the `bitflags!` macro. Rather
+//! than reporting the old and new values, their changes give the flags that were
+//! set and those that were cleared, as a `FlagsChange { added, removed }`, and
+//! are rendered by `comparable_display` and `comparable_tree` as a change to a
+//! `bool` for each of those flags:
+//!
+//! ```ignore
+//! bitflags::bitflags! {
+//!     #[derive(Clone, Copy, PartialEq, Debug)]
+//!     struct Permissions: u8 {
+//!         const READ = 1;
+//!         const WRITE = 2;
+//!     }
+//! }
+//!
+//! impl_comparable_for_bitflags!(Permissions);
+//!
+//! assert_eq!(
+//!     Permissions::READ.comparison(&Permissions::WRITE),
+//!     Changed::Changed(FlagsChange { added: Permissions::WRITE, removed: Permissions::READ })
+//! );
+//! ```
+//!
 //! # Map Collections
 //!
 //! The map collections for which [`Comparable`] has been implemented are:
@@ -303,6 +493,156 @@
 //! );
 //! ```
 //!
+//! When a value is removed from one key and added under another, such as a user
+//! stored by name who has been renamed, these are normally reported as unrelated
+//! `MapChange::Removed` and `MapChange::Added` changes. Setting
+//! `DiffOptions::map_moves` with [`with_diff_options`] instead pairs them up
+//! as `MapChange::Moved { from, to, change }`, when the two values are at least as
+//! similar as the given threshold, using [`Comparable::similarity`]. For types
+//! deriving `Comparable`, similarity is the average similarity of their fields.
+//!
+//! For large collections of large values, describing every element added or
+//! removed can be costly. The `light` module offers changes to `Vec` and map
+//! values that refer to elements only by index or key, along with a hash of
+//! their values, and that can be turned into the full changes later, from the
+//! original collections.
+//!
+//! Comparing collections many times a second, as a game or simulation might on
+//! every step of its state, allocates a fresh list of changes each time. With
+//! the `bumpalo` feature, `arena::ComparableIn::comparison_in` builds that list
+//! for a `Vec` or map in a `bumpalo::Bump` arena instead, which is freed all at
+//! once when reset. Only the list itself is held by the arena; the changes of its
+//! elements are allocated as usual.
+//!
+//! Comparing large collections from async code can stall the executor. With the
+//! `tokio` feature, `task::diff_blocking` runs a comparison on tokio's pool of
+//! blocking threads, and `task::diff_slices_yielding` compares two slices in
+//! chunks, yielding to the executor between them. Both honor the settings in
+//! effect where they are called.
+//!
+//! Files and directory trees on disk can be compared using the `fs` module,
+//! which hashes the contents of files a chunk at a time, reporting the byte
+//! ranges that differ in a file, and the files added, removed or changed in a
+//! directory tree as `MapChange`s keyed by their relative paths.
+//! A `fs::DirTree` is a snapshot of a directory tree, implementing [`Comparable`]
+//! so that the files modified report changes to their size, modification time
+//! and the hash of their contents.
+//!
+//! To report how the environment of a process drifted between runs, keep an
+//! `env::snapshot()` of its variables, and pass it later to `env::report`,
+//! which renders the variables set, unset or changed since, one to a line.
+//!
+//! # Shared Pointers
+//!
+//! [`Comparable`] is implemented for `Rc`, `Arc` and `RefCell`, so that graphs of
+//! shared values such as `Rc<RefCell<Node>>` may be compared, even when they
+//! contain cycles. Two pointers to the same allocation are always unchanged, and
+//! each pair of allocations is compared at most once within the outermost shared
+//! pointer being compared.
+//!
+//! Likewise, each allocation is described in full only the first time it is
+//! encountered; later occurrences are described as `SharedDesc::Seen(n)`, where
+//! `n` counts the allocations described before it:
+//!
+//! ```
+//! # use comparable::*;
+//! # use std::rc::Rc;
+//! let shared = Rc::new(5);
+//! assert_eq!(
+//!     Rc::new((shared.clone(), shared)).describe(),
+//!     SharedDesc::Value((SharedDesc::Value(5), SharedDesc::Seen(1)))
+//! );
+//! ```
+//!
+//!
+//! # Ranges
+//!
+//! [`Comparable`] is implemented for `Range` and `RangeInclusive`, whose changes
+//! report the movement of each end separately, as a [`RangeChange::Start`] or
+//! [`RangeChange::End`] holding the change to that end:
+//!
+//! ```
+//! # use comparable::*;
+//! assert_eq!(
+//!     (9..17).comparison(&(8..18)),
+//!     Changed::Changed(vec![RangeChange::Start(I32Change(9, 8)), RangeChange::End(I32Change(17, 18))])
+//! );
+//! assert_eq!((1..=5).comparison(&(1..=6)), Changed::Changed(vec![RangeChange::End(I32Change(5, 6))]));
+//! ```
+//!
+//! # Atomic Values
+//!
+//! [`Comparable`] is implemented for the atomic integer types and `AtomicBool`,
+//! which are described and compared as the values they hold, so that structs of
+//! shared state, such as counters updated by other threads, can be compared
+//! directly by a thread monitoring them. Values are loaded using
+//! `Ordering::SeqCst`, unless the comparison is made within
+//! [`with_atomic_ordering`]:
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+//!
+//! #[derive(Comparable)]
+//! struct Stats {
+//!     requests: AtomicU64,
+//!     healthy: AtomicBool,
+//! }
+//!
+//! let before = Stats { requests: AtomicU64::new(10), healthy: AtomicBool::new(true) };
+//! let after = Stats { requests: AtomicU64::new(12), healthy: AtomicBool::new(true) };
+//! assert_eq!(
+//!     with_atomic_ordering(Ordering::Acquire, || before.comparison(&after)),
+//!     Changed::Changed(vec![StatsChange::Requests(U64Change(10, 12))])
+//! );
+//! ```
+//!
+//! # Persistent Collections
+//!
+//! When the `im` or `im-rc` features are enabled, [`Comparable`] is also
+//! implemented for the `Vector`, `HashMap` and `OrdMap` types of the
+//! corresponding crate. These report changes exactly like `Vec`, `HashMap` and
+//! `BTreeMap` respectively, but take advantage of structural sharing: two
+//! collections that share the same root are reported as unchanged without
+//! visiting any of their elements, and `OrdMap` skips over any subtrees the two
+//! maps have in common.
+//! # JSON Values
+//!
+//! When the `json` feature is enabled, [`Comparable`] is implemented for
+//! `serde_json::Value`, so that documents without a Rust type of their own, such
+//! as configuration files, can be compared. Arrays are compared as a `Vec`, and
+//! objects as a map keyed by member name, while any other values that differ are
+//! reported as replaced, in a `json::ValueChange`:
+//!
+//! ```ignore
+//! use serde_json::json;
+//!
+//! assert_eq!(
+//!     json!({ "port": 80 }).comparison(&json!({ "port": 8080 })),
+//!     Changed::Changed(ValueChange::Object(vec![MapChange::Changed(
+//!         "port".to_string(),
+//!         ValueChange::Replaced(json!(80), json!(8080))
+//!     )]))
+//! );
+//! ```
+//!
+//! `format::json_patch::diff` writes these changes as a JSON Patch (RFC 6902)
+//! document. The `delta-cli` program of this workspace compares two JSON, YAML or
+//! TOML files this way, writing the changes as colored lines of text or as a JSON
+//! Patch or JSON Merge Patch document.
+//!
+//! With the `wasm` feature, `wasm::diff_json` exports the same comparison to
+//! JavaScript through `wasm-bindgen`, returning the `ValueChange` between two
+//! JavaScript values as plain objects, or `null` if they are the same, so that a
+//! web frontend reports changes as its Rust backend does.
+//!
+//! The `comparable_python` crate of this repository builds a `comparable` Python
+//! module, using maturin, whose `diff`, `apply` and `merge` functions compare,
+//! update and combine JSON values in the same way, exchanging changes in the
+//! form `ValueChange` is serialized as JSON. Since building and testing it needs
+//! a Python interpreter, it is left out of the workspace, and is tested with
+//! `cargo test --manifest-path comparable_python/Cargo.toml`.
 //! # <a name="structs"></a>Structures
 //!
 //! Differencing arbitrary structures was the original motive for creating
@@ -350,18 +690,14 @@
 //!     type Change = Vec<MyStructChange>;
 //!
 //!     fn comparison(&self, other: &Self) -> Changed<Self::Change> {
-//!         let changes: Self::Change = vec![
-//!             self.bar.comparison(&other.bar).map(MyStructChange::Bar),
-//!             self.baz.comparison(&other.baz).map(MyStructChange::Baz),
-//!         ]
+//!         Changed::from_changes(
+//!             vec![
+//!                 self.bar.comparison(&other.bar).map(MyStructChange::Bar),
+//!                 self.baz.comparison(&other.baz).map(MyStructChange::Baz),
+//!             ]
 //!             .into_iter()
-//!             .flatten()
-//!             .collect();
-//!         if changes.is_empty() {
-//!             Changed::Unchanged
-//!         } else {
-//!             Changed::Changed(changes)
-//!         }
+//!             .flatten(),
+//!         )
 //!     }
 //! }
 //! ```
@@ -374,6 +710,80 @@
 //! fields is `#[comparable_ignore]`, which must be used if the type in question
 //! cannot be compared for differences.
 //!
+//! ## Field attribute: `comparable_redact`
+//!
+//! Fields holding sensitive data, such as passwords or keys, can be marked with
+//! `#[comparable_redact]`. They are still compared, but both their description
+//! and any change to them are replaced by the [`Redacted`] marker, so that their
+//! values never appear in test output or logs:
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! #[derive(Comparable)]
+//! pub struct Login {
+//!     user: String,
+//!     #[comparable_redact]
+//!     password: String,
+//! }
+//!
+//! assert_eq!(
+//!     Login { user: "me".to_string(), password: "old".to_string() }
+//!         .comparison(&Login { user: "me".to_string(), password: "new".to_string() }),
+//!     Changed::Changed(vec![LoginChange::Password(Redacted)])
+//! );
+//! ```
+//!
+//! ## Field attribute: `comparable_by`
+//!
+//! Fields whose types cannot implement [`Comparable`], such as trait objects,
+//! can instead be compared by projections of their values, with
+//! `#[comparable_by(desc = "...", eq = "...")]`. In each expression, `self` is a
+//! reference to the field's value. The field is described by `desc`, as a
+//! string, and changes when `eq` does, with the change being that of its
+//! descriptions. If `eq` is omitted, the descriptions themselves are compared:
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! pub trait Plugin {
+//!     fn name(&self) -> String;
+//!     fn id(&self) -> u32;
+//! }
+//!
+//! #[derive(Comparable)]
+//! pub struct Host {
+//!     #[comparable_by(desc = "self.name()", eq = "self.id()")]
+//!     plugin: Box<dyn Plugin>,
+//! }
+//! ```
+//! ## Field attribute: `comparable_leaf`
+//!
+//! A field whose type does not implement [`Comparable`] but is `PartialEq`,
+//! `Clone` and `Debug` may be compared as a whole by marking it with
+//! `#[comparable_leaf]`. It is then treated as though it were wrapped in
+//! [`Leaf`]: it is described by a clone of its value, and its change is a tuple
+//! of the values before and after.
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! # #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+//! #[derive(Clone, PartialEq, Debug)]
+//! pub struct Version(u32, u32);
+//!
+//! #[derive(Comparable)]
+//! pub struct Package {
+//!     #[comparable_leaf]
+//!     version: Version,
+//! }
+//!
+//! assert_changes!(
+//!     &Package { version: Version(1, 0) },
+//!     &Package { version: Version(1, 1) },
+//!     Changed::Changed(PackageChange { version: (Version(1, 0), Version(1, 1)) }),
+//! );
+//! ```
 //! ## Field attribute: `comparable_synthetic`
 //!
 //! The `#[comparable_synthetic { <BINDINGS...> }]` attribute allows you to attach
@@ -493,6 +903,20 @@
 //! checksum hash, for example, or large data structures that you don't need to
 //! ever display by their Merkle root hash.
 //!
+//! Both attributes may also be placed on individual fields, to override just
+//! how that field is described while the rest of the description is derived as
+//! usual. Here too `self` refers to the whole value, not the field:
+//!
+//! ```
+//! # use comparable_derive::*;
+//! #[derive(Comparable)]
+//! struct Login {
+//!   user: String,
+//!   #[describe_type(String)]
+//!   #[describe_body(format!("{} characters", self.password.len()))]
+//!   password: String,
+//! }
+//! ```
 //! ### Macro attribute: `compare_default`
 //!
 //! When the `#[compare_default]` attribute macro is used, the
@@ -506,7 +930,7 @@
 //!     type Desc = Self::Change;
 //!
 //!     fn describe(&self) -> Self::Desc {
-//!         MyStruct::default().comparison(self).unwrap_or_default()
+//!         comparable::from_default(self).unwrap_or_default()
 //!     }
 //!
 //!     type Change = Vec<MyStructChange>;
@@ -515,6 +939,53 @@
 //! }
 //! ```
 //!
+//! An enum's change type has no `Default`, so with `#[compare_default]` an enum
+//! is instead described by `Changed<Self::Change>`: `Unchanged` for the default
+//! value, the changes to its fields for a value of the same variant, and
+//! `Different` for a value of another variant. So that this is not circular, the
+//! `Different` variant of the change type then holds the mirror description type
+//! generated for the enum, rather than [`Comparable::Desc`]:
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! #[derive(Comparable, Default)]
+//! #[compare_default]
+//! enum Mode {
+//!     #[default]
+//!     Off,
+//!     On { level: u8 },
+//! }
+//!
+//! assert_eq!(Mode::Off.describe(), Changed::Unchanged);
+//! assert_eq!(
+//!     Mode::On { level: 2 }.describe(),
+//!     Changed::Changed(ModeChange::Different(ModeDesc::Off, ModeDesc::On { level: 2 }))
+//! );
+//! ```
+//!
+//! The same comparison is available for any type that implements both
+//! `Default` and [`Comparable`] through the [`from_default`] function. For
+//! derived types, `#[comparable_from_default]` additionally generates an inherent
+//! `comparison_from_default` method that does the same thing:
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! #[derive(Comparable, Default)]
+//! #[comparable_from_default]
+//! struct Settings {
+//!   verbose: bool,
+//!   retries: u8,
+//! }
+//!
+//! let settings = Settings { verbose: false, retries: 3 };
+//! assert_eq!(
+//!   settings.comparison_from_default(),
+//!   Changed::Changed(vec![SettingsChange::Retries(U8Change(0, 3))])
+//! );
+//! assert_eq!(settings.comparison_from_default(), from_default(&settings));
+//! ```
 //! Note that changes for structures are always a vector, since this allows
 //! changes to be reported separately for each field. More on this in the
 //! following section.
@@ -528,11 +999,385 @@
 //! -- you can use `#[comparable_public]` and `#[comparable_private]` to be
 //! explicit about the visibility of these generated types.
 //!
+//! ## Macro attribute: `comparable_module`
+//!
+//! Rather than placing the generated `Desc` and `Change` types alongside the
+//! original type, `#[comparable_module = "name"]` places them in a child module
+//! of that name, together with the implementation of [`Comparable`]. The module
+//! has the same visibility as the generated types would otherwise have had, as
+//! determined by the attributes above. Since a module can only be defined once,
+//! each type using this attribute must name a different module:
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! #[derive(Comparable)]
+//! #[comparable_module = "point_changes"]
+//! pub struct Point {
+//!     x: i32,
+//!     y: i32,
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(
+//!         Point { x: 1, y: 2 }.comparison(&Point { x: 1, y: 3 }),
+//!         Changed::Changed(vec![point_changes::PointChange::Y(I32Change(2, 3))])
+//!     );
+//! }
+//! ```
+//!
+//! ## Macro attribute: `comparable_crate`
+//!
+//! The generated code refers to this crate by the name under which the crate
+//! being compiled depends on it, so that it still works if the dependency has
+//! been renamed in `Cargo.toml`. Where the crate is reached some other way, such
+//! as through a re-export by a facade crate, `#[comparable_crate = "path"]`
+//! gives the path to use instead:
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! mod facade {
+//!     pub mod inner {
+//!         pub use comparable::*;
+//!     }
+//! }
+//!
+//! #[derive(Comparable)]
+//! #[comparable_crate = "crate::facade::inner"]
+//! struct Point {
+//!     x: i32,
+//!     y: i32,
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(
+//!         Point { x: 1, y: 2 }.comparison(&Point { x: 1, y: 3 }),
+//!         Changed::Changed(vec![PointChange::Y(I32Change(2, 3))])
+//!     );
+//! }
+//! ```
+//!
+//! ## Macro attribute: `comparable_bound`
+//!
+//! For a generic type, each field whose type mentions the type's parameters is
+//! required to implement [`Comparable`], such as `Vec<T>: Comparable`, which is
+//! not always what is needed. A field compared with `comparable_by` need not be
+//! comparable at all, and a type may want bounds of its own. With
+//! `#[comparable_bound(...)]`, the predicates given are used instead of those
+//! inferred, in addition to the type's own bounds. The attribute may be given more
+//! than once, and the predicates of each are combined:
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! #[derive(Comparable)]
+//! #[comparable_bound(T: std::fmt::Display)]
+//! struct Tagged<T> {
+//!     #[comparable_by(desc = "self.to_string()")]
+//!     tag: T,
+//!     count: u8,
+//! }
+//! ```
+//!
+//! ## Macro attribute: `comparable_desc_roundtrip`
+//!
+//! A description usually captures everything about a value, so that it could
+//! serve as a snapshot of that value to be persisted and restored later. The
+//! `#[comparable_desc_roundtrip]` attribute implements the traits
+//! [`roundtrip::IntoDesc`], which turns a value into its description without
+//! cloning, and [`roundtrip::FromDesc`], which rebuilds a value from its
+//! description:
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! use comparable::roundtrip::{FromDesc, IntoDesc};
+//!
+//! #[derive(Comparable, PartialEq, Debug)]
+//! #[comparable_desc_roundtrip]
+//! struct Account {
+//!     owner: String,
+//!     balance: i64,
+//! }
+//!
+//! let desc = Account { owner: "alice".to_string(), balance: 10 }.into_desc();
+//! assert_eq!(Account::from_desc(desc), Account { owner: "alice".to_string(), balance: 10 });
+//! ```
+//!
+//! This requires every field to be fully described, and so cannot be used with
+//! fields that are ignored, redacted, synthetic or given a custom description,
+//! except for marker fields such as `PhantomData`, which are rebuilt using
+//! `Default`. Every field type must implement both traits, as do the scalars and
+//! standard collections, but not shared pointers, whose descriptions may only
+//! refer to values seen elsewhere.
+//!
+//! ## Macro attribute: `comparable_version`
+//!
+//! When descriptions or changes are persisted for a long time, the types they
+//! were generated from may change in the meantime. With the `serde` feature
+//! enabled, `#[comparable_version = N]` makes the generated `Desc` and `Change`
+//! types serialize along with the version `N`, and `#[comparable_migrate]`
+//! allows values serialized at other versions to be converted when they are
+//! read back, as described in the `versioned` module.
+//!
+//! ## Macro attribute: `comparable_from`
+//!
+//! When a struct replaces an earlier one, such as during a migration between
+//! versions of a configuration, `#[comparable_from(Before)]` implements
+//! [`ComparableFrom`] for it, comparing fields of the same name, which
+//! must have the same type. Fields that `Before` lacks are marked with
+//! `#[comparable_added]` and reported with their descriptions, and fields of
+//! `Before` that this struct lacks are named by `removed(...)`:
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! struct ConfigV1 {
+//!     name: String,
+//!     verbose: bool,
+//! }
+//!
+//! #[derive(Comparable)]
+//! #[comparable_from(ConfigV1, removed(verbose))]
+//! struct ConfigV2 {
+//!     name: String,
+//!     #[comparable_added]
+//!     timeout: u64,
+//! }
+//!
+//! let before = ConfigV1 { name: "server".to_string(), verbose: true };
+//! let after = ConfigV2 { name: "server".to_string(), timeout: 30 };
+//! assert_eq!(
+//!     after.comparison_from(&before),
+//!     Changed::Changed(vec![
+//!         ConfigV2FromConfigV1Change::AddedTimeout(30),
+//!         ConfigV2FromConfigV1Change::Removed("verbose".to_string()),
+//!     ])
+//! );
+//! ```
+//!
+//! ## Macro attribute: `comparable_schema`
+//!
+//! Programs exchanging changes must agree on the shape of the types involved.
+//! With `#[comparable_schema]`, the derive also implements `HasSchema` from the
+//! `schema` module, whose `Schema` records the names and schemas of the fields
+//! compared, and the variants of enums. Schemas are themselves `Comparable`, so
+//! a program can compare the schema received from a peer at startup with its
+//! own, and learn of each field or variant added or removed, and each field
+//! whose type has changed:
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! use comparable::schema::HasSchema;
+//!
+//! #[derive(Comparable)]
+//! #[comparable_schema]
+//! struct Account {
+//!     id: u64,
+//!     owner: String,
+//! }
+//!
+//! assert_eq!(Account::schema().comparison(&Account::schema()), Changed::Unchanged);
+//! ```
+//!
+//! ## Macro attribute: `comparable_lens`
+//!
+//! With `#[comparable_lens]`, a struct gains a method for each field, such as
+//! `Model::title_lens()`, returning a `lens::Lens` that picks out the changes to
+//! that field from the changes to the struct, and applies changes of the field
+//! to a value. The struct also implements `lens::Apply`, which brings a value up
+//! to date with the changes reported for it. This lets a user interface update
+//! only the parts of a view whose fields have changed. An enum with
+//! `#[comparable_lens]` implements `lens::Apply` without lenses, and replaces a
+//! value of another variant using `roundtrip::FromDesc`, as derived with
+//! `#[comparable_desc_roundtrip]`.
+//!
+//! Before applying changes that came from elsewhere, `Apply::check` reports
+//! whether they fit a value without changing it, refusing changes within an
+//! `Option` or enum holding another variant, changes to elements beyond the end
+//! of a `Vec`, and changes to map entries or set members that do not exist, with
+//! the location of the part that does not fit.
+//!
+//! To guard against changes made to a value since the changes being applied were
+//! made from it, `Apply::try_apply` first verifies that the values each change
+//! replaces, such as the value before of a scalar, are those the value holds
+//! now, and otherwise returns a `lens::ConflictError` without changing anything,
+//! as a compare-and-swap would. The elements that the changes remove from
+//! vectors must also hold the values they were removed with, and map entries
+//! that they add must not have been inserted meanwhile. Since the changes of
+//! maps do not hold the values of the entries they remove, those are verified
+//! only by `Apply::try_apply_from`, which is also given the value the changes
+//! were made from.
+//!
+//! ## Macro attribute: `comparable_sql`
+//!
+//! For a struct mirroring a row of a table, `#[comparable_sql(table = "users",
+//! key = "id")]` implements `sql::SqlChangeset`, whose `sql_update` method turns
+//! the struct's changes into a parameterized statement, such as `UPDATE users
+//! SET email = ? WHERE id = ?`, along with its parameters. Each field compared
+//! is the column of the same name, and only the columns that changed are set;
+//! when none did, there is no statement.
+//! With the `diesel` feature, `sql::diesel::DieselUpdate` runs the statement
+//! with Diesel, and with the `sqlx` feature, `sql::sqlx::query_builder` starts
+//! an sqlx `QueryBuilder` holding it, with the placeholders of its database.
+//!
+//! ## Macro attribute: `comparable_display`
+//!
+//! With `#[comparable_display]`, the generated `Change` type implements
+//! `Display`, rendering each change on one line as the path of what changed and
+//! how, such as `age: 41 → 42` or `tags[1]: added "b"`, with several changes
+//! separated by `; `. The same rendering is available for any change through
+//! `display::display`, which also covers the `Vec` of changes reported for
+//! structs with several fields.
+//!
+//! ## Macro attribute: `comparable_tree`
+//!
+//! With `#[comparable_tree]`, the generated `Change` type implements
+//! `tree::ToTree`, whose `to_tree` method converts its changes to a
+//! `tree::ChangeTree`: a single type holding the changes within a value, keyed by
+//! field name, index or map key, down to the values added, removed, modified or
+//! moved. Code rendering, storing or presenting changes can then work with any
+//! type compared, rather than with each generated `Change` type.
+//!
+//! A reference to a change also converts into rows of the path, value before and
+//! value after of each change, as `Vec<(String, String, String)>`, which
+//! `format::csv::write_rows` writes as comma-separated values for spreadsheets.
+//! Any changes implementing `ToTree` can also be written using `format::csv::write`,
+//! whose rows add the kind of each change: `added`, `removed`, `modified` or
+//! `moved`.
+//! `format::markdown::render` renders them as a Markdown table or nested list,
+//! for posting in pull request comments and chat messages.
+//! With the `dot` feature, `format::dot::render` renders them as a Graphviz graph,
+//! coloring each value by whether it was added, removed, modified or moved.
+//! With the `json` feature, `format::merge_patch::merge_patch` writes the changes
+//! between two values as a JSON Merge Patch (RFC 7386) document, holding only
+//! the members changed, with `null` for those removed, as many REST APIs accept.
+//! In the other direction, `format::json_patch::to_change` reads a JSON Patch
+//! (RFC 6902) document as the typed changes it makes to a value, refusing paths
+//! that do not exist, so that PATCH requests can be checked before being applied
+//! with `lens::Apply`.
+//! With the `tracing` feature, `trace::log_changes` emits a `tracing` event
+//! within a given span for each change, with its path, kind and values before
+//! and after as fields, so that changes land in existing observability
+//! pipelines.
+//!
+//! ## Macro attribute: `comparable_try`
+//!
+//! Some values cannot always be described or compared, such as those behind a
+//! `Mutex` that a panic has poisoned. With `#[comparable_try]`, a struct
+//! implements [`fallible::TryComparable`] instead of [`Comparable`], whose
+//! `try_describe` and `try_comparison` methods return a `Result`, failing with
+//! the first of its fields that fails. Its fields need only implement
+//! [`fallible::TryComparable`], as every [`Comparable`] type does, along with
+//! `Mutex` and `RwLock` of them. The change type is a list of changes to each
+//! field, as for a struct with several fields:
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! use comparable::fallible::TryComparable;
+//! use std::sync::Mutex;
+//!
+//! #[derive(Comparable)]
+//! #[comparable_try]
+//! struct Counter {
+//!     name: String,
+//!     count: Mutex<u32>,
+//! }
+//!
+//! let before = Counter { name: "a".to_string(), count: Mutex::new(1) };
+//! let after = Counter { name: "a".to_string(), count: Mutex::new(2) };
+//! assert_eq!(
+//!     before.try_comparison(&after),
+//!     Ok(Changed::Changed(vec![CounterChange::Count(U32Change(1, 2))]))
+//! );
+//! ```
+//!
+//! ## Runtime field masks
+//!
+//! Every type implementing `Comparable` also has a `comparison_masked` method,
+//! from the [`MaskedComparison`] trait, which compares two values considering
+//! only some of their fields, chosen at runtime by a [`mask::FieldMask`] of
+//! dotted field paths. This lets a service honor the fields requested by its
+//! clients, much like protobuf's `FieldMask`, without a separate type for each
+//! selection:
+//!
+//! ```rust
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! use comparable::mask::FieldMask;
+//!
+//! #[derive(Comparable)]
+//! struct Address {
+//!     street: String,
+//!     city: String,
+//! }
+//!
+//! #[derive(Comparable)]
+//! struct Customer {
+//!     name: String,
+//!     address: Address,
+//! }
+//!
+//! let before = Customer {
+//!     name: "Alice".to_string(),
+//!     address: Address { street: "Main St".to_string(), city: "Springfield".to_string() },
+//! };
+//! let after = Customer {
+//!     name: "Alicia".to_string(),
+//!     address: Address { street: "Elm St".to_string(), city: "Springfield".to_string() },
+//! };
+//! assert_eq!(
+//!     before.comparison_masked(&after, &FieldMask::exclude(["name", "address.city"])),
+//!     Changed::Changed(vec![CustomerChange::Address(vec![AddressChange::Street(
+//!         StringChange("Main St".to_string(), "Elm St".to_string())
+//!     )])])
+//! );
+//! ```
+//!
+//! Fields that are masked out are reported as unchanged. Masks can also be
+//! converted to and from protobuf's `google.protobuf.FieldMask`, either as a list
+//! of paths or in its JSON form, using `FieldMask::paths` and
+//! `FieldMask::to_protobuf`.
+//!
+//! To write these paths without typos, the `Change` type of a struct or enum
+//! lists the names of its fields compared in the constant `FIELDS`, such as
+//! `CustomerChange::FIELDS`. With `#[comparable_paths]`, the derive also
+//! generates a module named after the type in snake case, whose `path` module
+//! holds a constant for each field, so that masks such as
+//! `FieldMask::include([customer::path::NAME])` are checked when compiled. The
+//! paths of nested fields are formed by joining these, as in
+//! `[customer::path::ADDRESS, address::path::CITY].join(".")`.
+//!
+//! To quiet noisy fields without changes to code, an `IgnoreSet` reads patterns
+//! over field paths at runtime, one to a line, in which `*` matches any part of
+//! a field name and `**` any number of fields, such as `*.updated_at` or
+//! `metrics.**`. Comparisons made within `ignore::with_ignore_set` report the
+//! fields matched as unchanged, and `IgnoreSet::filter_tree` removes them from
+//! changes already found.
+//!
+//! Masks also let a `watch::Store` notify consumers of a value about only the
+//! parts of it they care about. Each callback subscribes to a path, and when the
+//! store's value is replaced using `Store::update`, only the callbacks whose
+//! paths differ between the old and new values are called.
+//!
+//! Rather than calling back, a subscription made with `Store::subscribe_sink`
+//! sends the changes to its part of the value into a `sink::ChangeSink`, such as
+//! the sending end of a `std::sync::mpsc` channel, or of a `crossbeam-channel`
+//! or `tokio::sync::broadcast` channel with the `crossbeam` or `tokio` features,
+//! as `Tracked::send_changes` does with the changes made to a tracked value.
+//!
 //! ### Special case: Unit structs
 //!
 //! If a struct has no fields it can never change, and so only a unitary
 //! [`Comparable::Desc`] type is generated.
 //!
+//! The same is true of a struct whose fields are all marked
+//! `#[comparable_ignore]`: its values are never changed, and its
+//! [`Comparable::Change`] type is `()`.
+//!
 //! ### Special case: Singleton structs
 //!
 //! If a struct has only one fields, whether named or unnamed, it no longer makes
@@ -573,6 +1418,39 @@
 //! }
 //! ```
 //!
+//! So that the changes of the field can be reached without naming it, or its
+//! position in a tuple struct, such a `Change` type also has `new` and
+//! `into_inner` methods to wrap and unwrap them, and dereferences to them:
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! #[derive(Comparable)]
+//! struct Meters(u32);
+//!
+//! let change = Meters(1).comparison(&Meters(2)).unwrap_changed();
+//! assert_eq!(*change, U32Change(1, 2));
+//! assert_eq!(change, MetersChange::new(U32Change(1, 2)));
+//! assert_eq!(change.into_inner(), U32Change(1, 2));
+//! ```
+//!
+//! ### Macro attribute: `comparable_transparent`
+//!
+//! Newtypes such as `struct UserId(u64)` often add nothing to a comparison but an
+//! extra layer of wrapping. Marking a struct having a single field with
+//! `#[comparable_transparent]` makes it reuse the `Desc` and `Change` types of
+//! that field directly, rather than generating types of its own:
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! #[derive(Comparable)]
+//! #[comparable_transparent]
+//! struct UserId(u64);
+//!
+//! assert_eq!(UserId(1).describe(), 1);
+//! assert_eq!(UserId(1).comparison(&UserId(2)), Changed::Changed(U64Change(1, 2)));
+//! ```
 //! ## Deriving `Comparable` for structs: the `Change` type
 //!
 //! By default for structs, deriving [`Comparable`] creates an `enum` with
@@ -604,6 +1482,13 @@
 //! }
 //! ```
 //!
+//! The changes of a multi-field struct are always listed in the order its fields
+//! were declared, whichever of them changed, so that the same comparison gives
+//! the same `Vec` every time. Changes gathered some other way, such as from
+//! several comparisons, can be put into a canonical order by their paths using
+//! [`merge::sort_changes`], with repeated changes removed by
+//! [`merge::dedup_changes`].
+//!
 //! Note that if a struct has only one field, there is no reason to specify
 //! changes using a vector, since either the struct is unchanged or just that one
 //! field has changed. For this reason, singleton structs optimize away the vector
@@ -627,6 +1512,41 @@
 //! with many, many fields can be represented by a small change set if most of the
 //! other fields were left untouched.
 //!
+//! ### Field attribute: `comparable_flatten`
+//!
+//! When a field of a struct with several fields is itself such a struct, its
+//! changes are normally nested as a vector within a single change to the
+//! containing struct. Marking the field with `#[comparable_flatten]` instead
+//! reports each of the field's changes as a separate entry in the containing
+//! struct's vector of changes:
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! #[derive(Comparable)]
+//! struct Size {
+//!     width: u32,
+//!     height: u32,
+//! }
+//!
+//! #[derive(Comparable)]
+//! struct Window {
+//!     title: String,
+//!     #[comparable_flatten]
+//!     size: Size,
+//! }
+//!
+//! assert_eq!(
+//!     Window { title: "a".to_string(), size: Size { width: 1, height: 2 } }
+//!         .comparison(&Window { title: "a".to_string(), size: Size { width: 3, height: 4 } }),
+//!     Changed::Changed(vec![
+//!         WindowChange::Size(SizeChange::Width(U32Change(1, 3))),
+//!         WindowChange::Size(SizeChange::Height(U32Change(2, 4))),
+//!     ])
+//! );
+//! ```
+//!
+//! This only applies to fields whose own `Change` type is a vector of changes.
 //! # <a name="enums"></a>Enumerations
 //!
 //! Enumerations are handled quite differently from structures, for the reason
@@ -754,6 +1674,31 @@
 //! }
 //! ```
 //!
+//! A whole variant may also be marked with `#[comparable_ignore]`, such as one
+//! holding a resource that cannot be compared. Its fields are then neither
+//! described nor compared: it is described as a unit variant, unless it is given
+//! a description of its own with `describe_type` and `describe_body`, and any
+//! two values of it are `Unchanged`. A change to or from another variant is
+//! still reported as `Different`:
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! #[derive(Comparable)]
+//! enum Source {
+//!     Path(String),
+//!     #[comparable_ignore]
+//!     Open(std::fs::File),
+//! }
+//!
+//! let open = || Source::Open(std::fs::File::open("Cargo.toml").unwrap());
+//! assert_eq!(open().comparison(&open()), Changed::Unchanged);
+//! assert_eq!(
+//!     Source::Path("a".to_string()).comparison(&open()),
+//!     Changed::Changed(SourceChange::Different(SourceDesc::Path("a".to_string()), SourceDesc::Open))
+//! );
+//! ```
+//!
 //! ## Deriving `Comparable` for enums: the `Desc` type
 //!
 //! By default for enums, deriving [`Comparable`] creates a "mirror" of that
@@ -775,6 +1720,60 @@
 //!
 //! **TODO**: jww (2021-11-01): Allow for synthetic fields in enum variants.
 //!
+//! In particular, `describe_type` and `describe_body` may be placed on the enum
+//! itself, describing it by a compact value of your choosing in place of the
+//! mirror, which is then not generated. The `Different` variant of its `Change`
+//! type holds these descriptions, while values of the same variant are compared
+//! field by field as usual:
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! #[derive(Comparable, Debug)]
+//! #[describe_type(String)]
+//! #[describe_body(format!("{:?}", self))]
+//! enum Level {
+//!     Low,
+//!     High { limit: u8 },
+//! }
+//!
+//! assert_eq!(
+//!     Level::Low.comparison(&Level::High { limit: 4 }),
+//!     Changed::Changed(LevelChange::Different("Low".to_string(), "High { limit: 4 }".to_string()))
+//! );
+//! ```
+//!
+//! ### Variant attributes: `no_description`, `describe_type` and `describe_body`
+//!
+//! Variants carrying large payloads can be left out of the `Desc` type by
+//! marking them with `#[no_description]`, which makes their description a unit
+//! variant. Alternatively, a variant may be given a custom description using
+//! `describe_type` and `describe_body`, where the body is evaluated with `self`
+//! bound to the whole value. In either case the payloads of these variants are
+//! still compared as usual.
+//!
+//! ```
+//! # use comparable_derive::*;
+//! #[derive(Comparable)]
+//! enum Payload {
+//!     Small(u8),
+//!     #[no_description]
+//!     Large(Vec<u8>),
+//!     #[describe_type(usize)]
+//!     #[describe_body(match self { Payload::Sized(data) => data.len(), _ => 0 })]
+//!     Sized(Vec<u8>),
+//! }
+//! ```
+//!
+//! This generates the following `Desc` type:
+//!
+//! ```
+//! enum PayloadDesc {
+//!     Small(<u8 as comparable::Comparable>::Desc),
+//!     Large,
+//!     Sized(usize),
+//! }
+//! ```
 //! ## Deriving `Comparable` for enums: the `Change` type
 //!
 //! By default for enums, deriving [`Comparable`] create a related `enum` where
@@ -793,6 +1792,129 @@
 //! type is also wrapped in a `Changed` structure, to reflect whether that field
 //! of the variant changed or not.
 //!
+//! Enums marked `#[non_exhaustive]` produce `Desc` and `Change` types that are
+//! also `#[non_exhaustive]`, and their `Change` type has a `Different` variant
+//! even when they have only one variant, so that adding variants later does not
+//! change the shape of the changes reported. Explicit discriminants, such as
+//! `Low = 1`, have no effect on how values are compared.
+//!
+//! An enum with a single variant that has nothing to compare, because it has no
+//! fields or they are all ignored, is never changed either, and so its
+//! [`Comparable::Change`] type is `()`, unless it is `#[non_exhaustive]`. This
+//! is not so for an enum with several such variants: which variant a value holds
+//! is still compared, as it is for any enum without fields, and a value changing
+//! from one to another is reported as `Different`.
+//!
+//! ## Macro attribute: `comparable_transitions`
+//!
+//! When an enum changes from one variant to another, its change is
+//! `Different(before, after)`, which says nothing in its type about which
+//! variants were involved. For enums modeling state machines, where these
+//! transitions are what matters, `#[comparable_transitions]` also generates a
+//! `Transition` type with a variant for each transition, named
+//! `<From>To<To>` and holding the descriptions of both values, along with a
+//! `transition` method giving the transition between two values. Code auditing
+//! transitions can then match on them exhaustively:
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! #[derive(Comparable)]
+//! #[comparable_transitions]
+//! enum Job {
+//!     Queued,
+//!     Running { pid: u32 },
+//! }
+//!
+//! assert_eq!(
+//!     Job::Queued.transition(&Job::Running { pid: 7 }),
+//!     Some(JobTransition::QueuedToRunning { before: JobDesc::Queued, after: JobDesc::Running { pid: 7 } })
+//! );
+//! assert_eq!(Job::Running { pid: 7 }.transition(&Job::Running { pid: 8 }), None);
+//! ```
+//!
+//! ## Macro attribute: `comparable_variant_only`
+//!
+//! For enums modeling coarse state, where only which variant a value is matters,
+//! `#[comparable_variant_only]` compares values by their variant alone. Changes
+//! to the fields of a variant are not reported, and no types are generated for
+//! them: the `Change` type has only its `Different` variant, holding the
+//! descriptions of both values.
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! #[derive(Comparable)]
+//! #[comparable_variant_only]
+//! enum Connection {
+//!     Idle,
+//!     Open { bytes: u64 },
+//! }
+//!
+//! assert_eq!(Connection::Open { bytes: 1 }.comparison(&Connection::Open { bytes: 2 }), Changed::Unchanged);
+//! assert_eq!(
+//!     Connection::Idle.comparison(&Connection::Open { bytes: 2 }),
+//!     Changed::Changed(ConnectionChange::Different(ConnectionDesc::Idle, ConnectionDesc::Open { bytes: 2 }))
+//! );
+//! ```
+//!
+//! ## Field and variant attribute: `comparable_rename`
+//!
+//! The names chosen for the variants of a `Change` type, such as `Field0` for an
+//! unnamed field or `Both<Variant>` for an enum variant, can be replaced using
+//! `#[comparable_rename = "Name"]` on the field or variant in question:
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! #[derive(Comparable)]
+//! struct Point(#[comparable_rename = "X"] i32, #[comparable_rename = "Y"] i32);
+//!
+//! #[derive(Comparable)]
+//! enum Shape {
+//!     #[comparable_rename = "Radius"]
+//!     Circle(u32),
+//!     Square(u32),
+//! }
+//!
+//! assert_eq!(Point(1, 2).comparison(&Point(1, 3)), Changed::Changed(vec![PointChange::Y(I32Change(2, 3))]));
+//! assert_eq!(Shape::Circle(1).comparison(&Shape::Circle(2)), Changed::Changed(ShapeChange::Radius(U32Change(1, 2))));
+//! ```
+//!
+//! ## Field attribute: `comparable_group`
+//!
+//! The changes of related fields can be kept together by marking each of them
+//! with `#[comparable_group("name")]`. Rather than a variant for each field, the
+//! `Change` type of a struct with several fields then has a variant for each
+//! group, named after it and placed where the first of its fields would be,
+//! holding the changes of the group's fields in a type of their own. Field
+//! masks still name the fields themselves, while rendered changes, such as
+//! those of `comparable_display` and `comparable_tree`, are nested under the
+//! group's name:
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! #[derive(Comparable)]
+//! #[comparable_display]
+//! struct Server {
+//!     name: String,
+//!     #[comparable_group("network")]
+//!     host: String,
+//!     #[comparable_group("network")]
+//!     port: u16,
+//! }
+//!
+//! let before = Server { name: "web".to_string(), host: "localhost".to_string(), port: 80 };
+//! let after = Server { name: "web".to_string(), host: "localhost".to_string(), port: 81 };
+//! let changes = before.comparison(&after);
+//! assert_eq!(
+//!     changes,
+//!     Changed::Changed(vec![ServerChange::Network(vec![ServerNetworkChange::Port(U16Change(80, 81))])])
+//! );
+//! assert_eq!(changes.unwrap_or_default()[0].to_string(), "network.port: 80 → 81");
+//! ```
+//!
 //! ## Field attribute: `variant_struct_fields`
 //!
 //! Note that it is possible to treat variant fields as though they were structs,
@@ -864,15 +1986,10 @@
 //!              MyEnum::Two { two: y0, two_more: y1 }) => {
 //!                 let c0 = x0.comparison(&y0);
 //!                 let c1 = x1.comparison(&y1);
-//!                 let changes: Vec<MyEnumTwoChange> = vec![
+//!                 Changed::from_changes(vec![
 //!                     c0.map(MyEnumTwoChange::Two),
 //!                     c1.map(MyEnumTwoChange::TwoMore),
-//!                 ].into_iter().flatten().collect();
-//!                 if changes.is_empty() {
-//!                     Changed::Unchanged
-//!                 } else {
-//!                     Changed::Changed(MyEnumChange::BothTwo(changes))
-//!                 }
+//!                 ].into_iter().flatten()).map(MyEnumChange::BothTwo)
 //!             }
 //!             (MyEnum::Three, MyEnum::Three) => Changed::Unchanged,
 //!             (_, _) => Changed::Changed(
@@ -888,52 +2005,230 @@
 //! [`Comparable::Desc`] or [`Comparable::Change`] types are omitted and it is
 //! always reported as unchanged.
 //!
+//! # Recursive Types
+//!
+//! A type whose fields refer to the type itself, such as `Box<Self>` or
+//! `Option<Box<Node>>`, would otherwise have `Desc` and `Change` types of
+//! infinite size. The descriptions and changes of such fields are therefore
+//! boxed. For types that are recursive through one another, mark one of the
+//! fields involved with `#[comparable_box]` to do the same:
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! #[derive(Comparable)]
+//! enum Expr {
+//!     Add(Box<Expr>, Box<Expr>),
+//!     Lit(i64),
+//! }
+//!
+//! assert_eq!(
+//!     Expr::Add(Box::new(Expr::Lit(1)), Box::new(Expr::Lit(2)))
+//!         .comparison(&Expr::Add(Box::new(Expr::Lit(1)), Box::new(Expr::Lit(3)))),
+//!     Changed::Changed(ExprChange::BothAdd(
+//!         Changed::Unchanged,
+//!         Changed::Changed(Box::new(ExprChange::BothLit(I64Change(2, 3))))
+//!     ))
+//! );
+//! ```
+//!
+//! Recursive types with generic parameters are not yet supported.
+//! # Generic Types
+//!
+//! Structures and enumerations with generic parameters may also derive
+//! [`Comparable`], including those with lifetimes, const parameters, and default
+//! type parameters. The derived implementation requires each field type that
+//! mentions one of these parameters to implement [`Comparable`] itself.
+//!
+//! Since the generated `Desc` and `Change` types only contain the descriptions
+//! and changes of the original fields, each field type that mentions a generic
+//! parameter becomes a parameter of the generated types. For example, the `Desc`
+//! type for `Labeled<T>` below is `LabeledDesc<<T as Comparable>::Desc>`.
+//! Fields of the zero-sized marker types `PhantomData` and `PhantomPinned` are
+//! ignored, since there is nothing to compare, unless they are marked with
+//! `#[comparable_include]`.
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! # use std::marker::PhantomData;
+//! #[derive(Comparable)]
+//! struct Labeled<T> {
+//!     label: String,
+//!     value: T,
+//!     marker: PhantomData<T>,
+//! }
+//!
+//! assert_eq!(
+//!     Labeled { label: "x".to_string(), value: 1u8, marker: PhantomData }
+//!         .comparison(&Labeled { label: "x".to_string(), value: 2u8, marker: PhantomData }),
+//!     Changed::Changed(vec![LabeledChange::Value(U8Change(1, 2))])
+//! );
+//! ```
+//!
+//! Field types that are associated types of a parameter, such as `I::Item` or
+//! `<F as Future>::Output`, are treated the same way: the derived implementation
+//! requires `Option<I::Item>: Comparable`, not anything of `I` itself, so that
+//! wrappers of iterators or futures can be compared whenever the items they
+//! produce can be:
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! #[derive(Comparable)]
+//! struct Peeked<I: Iterator> {
+//!     peeked: Option<I::Item>,
+//!     position: usize,
+//! }
+//!
+//! assert_eq!(
+//!     Peeked::<std::vec::IntoIter<u8>> { peeked: Some(1), position: 0 }.describe(),
+//!     PeekedDesc { peeked: Some(1), position: 0 }
+//! );
+//! ```
 //! # <a name="unions"></a>Unions
 //!
 //! Unions cannot derive [`Comparable`] instances at the present time.
+#[cfg(feature = "bumpalo")]
+pub mod arena;
 #[doc(hidden)]
 pub mod array;
 #[doc(hidden)]
+pub mod atomic;
+pub mod audit;
+#[doc(hidden)]
+pub mod binary;
+#[doc(hidden)]
 pub mod boxes;
+#[cfg(feature = "serde")]
+pub mod changed;
+#[cfg(feature = "codec")]
+pub mod codec;
+pub mod derive_support;
+pub mod describe;
+#[doc(hidden)]
+pub mod diff;
+pub mod display;
 #[doc(hidden)]
 pub mod empty;
+pub mod env;
+pub mod fallible;
+#[cfg(feature = "bitflags")]
+#[doc(hidden)]
+pub mod flags;
+#[doc(hidden)]
+pub mod float;
+pub mod format;
+pub mod fs;
+#[doc(hidden)]
+pub mod history;
+pub mod ignore;
+#[doc(hidden)]
+pub mod journal;
+#[cfg(feature = "json")]
+pub mod json;
+#[doc(hidden)]
+pub mod leaf;
+pub mod lens;
+pub mod light;
+pub mod location;
 #[doc(hidden)]
 pub mod map;
 #[doc(hidden)]
+pub mod marker;
+pub mod mask;
+pub mod merge;
+#[cfg(any(feature = "ordered-float", feature = "rust_decimal"))]
+#[doc(hidden)]
+pub mod numeric;
+#[doc(hidden)]
 pub mod opt;
 #[doc(hidden)]
+pub mod options;
+#[doc(hidden)]
 pub mod path;
+#[cfg(any(feature = "im", feature = "im-rc"))]
+#[doc(hidden)]
+pub mod persistent;
+pub mod prelude;
+#[doc(hidden)]
+pub mod range;
+pub mod roundtrip;
 #[doc(hidden)]
 pub mod scalar;
+pub mod schema;
 #[doc(hidden)]
 pub mod set;
 #[doc(hidden)]
+pub mod shared;
+pub mod sink;
+pub mod sql;
+#[doc(hidden)]
 pub mod string;
+#[cfg(feature = "codec")]
+pub mod sync;
+#[cfg(feature = "tokio")]
+pub mod task;
+pub mod testing;
+#[cfg(feature = "tracing")]
+pub mod trace;
+#[doc(hidden)]
+pub mod tracked;
+pub mod tree;
 #[doc(hidden)]
 pub mod tuple;
 #[doc(hidden)]
 pub mod types;
+#[cfg(feature = "serde")]
+pub mod versioned;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod watch;
+#[cfg(feature = "serde")]
+pub mod wire;
 
+#[doc(hidden)]
+pub use crate::binary::*;
+#[cfg(feature = "bitflags")]
+#[doc(hidden)]
+pub use crate::flags::FlagsChange;
 #[doc(hidden)]
 pub use crate::map::*;
+#[cfg(any(feature = "ordered-float", feature = "rust_decimal"))]
+#[doc(hidden)]
+pub use crate::numeric::*;
 #[doc(hidden)]
 pub use crate::opt::*;
 #[doc(hidden)]
 pub use crate::path::*;
 #[doc(hidden)]
+pub use crate::range::*;
+#[doc(hidden)]
 pub use crate::scalar::*;
 #[doc(hidden)]
 pub use crate::set::*;
 #[doc(hidden)]
+pub use crate::shared::*;
+#[doc(hidden)]
 pub use crate::string::*;
 
-pub use crate::types::{Changed, Comparable};
+pub use crate::atomic::with_atomic_ordering;
+pub use crate::diff::DiffAlgorithm;
+pub use crate::float::{with_float_tolerance, FloatTolerance};
+pub use crate::history::History;
+pub use crate::ignore::IgnoreSet;
+pub use crate::journal::{Journal, JournalEntry};
+pub use crate::leaf::Leaf;
+pub use crate::mask::MaskedComparison;
+pub use crate::options::{with_diff_options, DiffOptions};
+pub use crate::tracked::Tracked;
+pub use crate::types::{changed, describe, diff, from_default, Changed, Comparable, ComparableFrom, Redacted};
 
 /// Assert that two values of a type have changed in the way described.
 #[macro_export]
 macro_rules! assert_changes {
 	($left:expr, $right:expr, $expected:expr$(,)?) => {{
-		use comparable::Comparable;
+		use $crate::Comparable;
 		assert_eq!($expected, $left.comparison(&$right));
 	}};
 }
@@ -951,7 +2246,7 @@ macro_rules! assert_changes {
 #[macro_export]
 macro_rules! pretty_assert_changes {
 	($left:expr, $right:expr, $expected:expr$(,)?) => {{
-		use comparable::Comparable;
+		use $crate::Comparable;
 		pretty_assertions::assert_eq!($expected, $left.comparison(&$right));
 	}};
 }
@@ -961,7 +2256,7 @@ macro_rules! pretty_assert_changes {
 #[macro_export]
 macro_rules! prop_assert_changes {
 	($left:expr, $right:expr, $expected:expr$(,)?) => {{
-		use comparable::Comparable;
+		use $crate::Comparable;
 		proptest::prop_assert_eq!($expected, $left.comparison(&$right));
 	}};
 }
@@ -971,7 +2266,7 @@ macro_rules! prop_assert_changes {
 #[macro_export]
 macro_rules! prop_pretty_assert_changes {
 	($left:expr, $right:expr, $expected:expr$(,)?) => {{
-		use comparable::Comparable;
+		use $crate::Comparable;
 		let changes = $left.comparison(&$right);
 		if $expected != changes {
 			return Err(proptest::test_runner::TestCaseError::fail(format!(
@@ -0,0 +1,192 @@
+//! Comparing large collections without copying their elements.
+//!
+//! The changes reported for a `Vec` or a map include a description of every
+//! element added or removed, which for large collections of large elements
+//! can cost more than the comparison itself. A [`LightChange`] instead refers
+//! to the element by its index or key, along with a hash of its value, and the
+//! full changes can be recovered later from the original collections, using
+//! [`LightComparable::hydrate`].
+//!
+//! ```
+//! # use comparable::*;
+//! use comparable::light::{LightChange, LightComparable};
+//!
+//! let before = vec!["a".to_string(), "b".to_string()];
+//! let after = vec!["a".to_string(), "c".to_string(), "d".to_string()];
+//! let changes = before.light_comparison(&after).unwrap_or_default();
+//! assert!(matches!(changes[..], [LightChange::Changed(1, _, _), LightChange::Added(2, _)]));
+//! assert_eq!(
+//!     before.hydrate(&after, &changes),
+//!     Some(vec![
+//!         VecChange::Changed(1, StringChange("b".to_string(), "c".to_string())),
+//!         VecChange::Added(2, "d".to_string()),
+//!     ])
+//! );
+//! ```
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+
+use crate::map::{to_btreemap, MapChange};
+use crate::set::VecChange;
+use crate::types::{Changed, Comparable};
+
+/// A change to an element of a collection, identified by its index or key,
+/// with hashes of the values involved in place of their descriptions.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum LightChange<Key> {
+	/// An element was added, whose value has the given hash.
+	Added(Key, u64),
+	/// An element was changed, from and to values with the given hashes.
+	Changed(Key, u64, u64),
+	/// An element was removed, whose value had the given hash.
+	Removed(Key, u64),
+}
+
+/// Collections that can report their changes as [`LightChange`]s.
+pub trait LightComparable: Comparable {
+	/// How the elements of the collection are identified.
+	type Key;
+
+	/// The changes between two collections, which are those reported by
+	/// [`Comparable::comparison`], without the descriptions of elements. The
+	/// elements of a `Vec` are compared position by position, as when no
+	/// [`DiffOptions`](crate::DiffOptions) are in effect.
+	fn light_comparison(&self, other: &Self) -> Changed<Vec<LightChange<Self::Key>>>;
+
+	/// The full changes corresponding to `changes`, which were found by
+	/// comparing this collection with `other`. If either collection has since
+	/// changed in a way that `changes` no longer describes, as found by the
+	/// hashes of the elements involved, the result is `None`.
+	fn hydrate(&self, other: &Self, changes: &[LightChange<Self::Key>]) -> Option<Self::Change>;
+}
+
+pub(crate) fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	value.hash(&mut hasher);
+	hasher.finish()
+}
+
+// The value found, if it is still the value with the given hash.
+fn matching<T: Hash>(value: Option<&T>, hash: u64) -> Option<&T> {
+	value.filter(|v| hash_of(*v) == hash)
+}
+
+impl<Value: PartialEq + Hash + Comparable> LightComparable for Vec<Value> {
+	type Key = usize;
+
+	fn light_comparison(&self, other: &Self) -> Changed<Vec<LightChange<usize>>> {
+		let mut changes = Vec::new();
+		for (i, (x, y)) in self.iter().zip(other).enumerate() {
+			if x.differs(y) {
+				changes.push(LightChange::Changed(i, hash_of(x), hash_of(y)));
+			}
+		}
+		changes.extend(self.iter().enumerate().skip(other.len()).map(|(i, x)| LightChange::Removed(i, hash_of(x))));
+		changes.extend(other.iter().enumerate().skip(self.len()).map(|(i, y)| LightChange::Added(i, hash_of(y))));
+		Changed::from_changes(changes)
+	}
+
+	fn hydrate(&self, other: &Self, changes: &[LightChange<usize>]) -> Option<Self::Change> {
+		changes
+			.iter()
+			.map(|change| match change {
+				LightChange::Added(i, hash) => Some(VecChange::Added(*i, matching(other.get(*i), *hash)?.describe())),
+				LightChange::Removed(i, hash) => {
+					Some(VecChange::Removed(*i, matching(self.get(*i), *hash)?.describe()))
+				}
+				LightChange::Changed(i, from, to) => {
+					let mut change = matching(self.get(*i), *from)?.comparison(matching(other.get(*i), *to)?);
+					Some(VecChange::Changed(*i, Changed::take(&mut change)?))
+				}
+			})
+			.collect()
+	}
+}
+
+impl<Key: Ord + Clone + Debug, Value: Hash + Comparable> LightComparable for BTreeMap<Key, Value> {
+	type Key = Key;
+
+	fn light_comparison(&self, other: &Self) -> Changed<Vec<LightChange<Key>>> {
+		light_map_comparison(self, other)
+	}
+
+	fn hydrate(&self, other: &Self, changes: &[LightChange<Key>]) -> Option<Self::Change> {
+		hydrate_map(self, other, changes, |map, key| map.get(key))
+	}
+}
+
+impl<Key: Ord + Clone + Debug, Value: Hash + Comparable> LightComparable for HashMap<Key, Value> {
+	type Key = Key;
+
+	fn light_comparison(&self, other: &Self) -> Changed<Vec<LightChange<Key>>> {
+		light_map_comparison(&to_btreemap(self), &to_btreemap(other))
+			.map(|changes| changes.into_iter().map(|change| change.map_key(|k| (*k).clone())).collect())
+	}
+
+	fn hydrate(&self, other: &Self, changes: &[LightChange<Key>]) -> Option<Self::Change> {
+		// The keys of a `HashMap` cannot be looked up without `Key: Hash`, so
+		// they are found in key order instead.
+		let (before, after) = (to_btreemap(self), to_btreemap(other));
+		hydrate_map(&before, &after, changes, |map, key| map.get(key).copied())
+	}
+}
+
+impl<Key> LightChange<Key> {
+	fn map_key<K>(self, f: impl FnOnce(Key) -> K) -> LightChange<K> {
+		match self {
+			LightChange::Added(k, hash) => LightChange::Added(f(k), hash),
+			LightChange::Changed(k, from, to) => LightChange::Changed(f(k), from, to),
+			LightChange::Removed(k, hash) => LightChange::Removed(f(k), hash),
+		}
+	}
+}
+
+// Entries are reported in the same order as by `Comparable::comparison`.
+fn light_map_comparison<Key: Ord + Clone, Value: Hash + Comparable>(
+	before: &BTreeMap<Key, Value>,
+	after: &BTreeMap<Key, Value>,
+) -> Changed<Vec<LightChange<Key>>> {
+	let mut changes = Vec::new();
+	for (k, v) in after {
+		match before.get(k) {
+			Some(vo) if vo.differs(v) => changes.push(LightChange::Changed(k.clone(), hash_of(vo), hash_of(v))),
+			Some(_) => (),
+			None => changes.push(LightChange::Added(k.clone(), hash_of(v))),
+		}
+	}
+	changes.extend(
+		before.iter().filter(|(k, _)| !after.contains_key(k)).map(|(k, v)| LightChange::Removed(k.clone(), hash_of(v))),
+	);
+	Changed::from_changes(changes)
+}
+
+fn hydrate_map<'a, M, Key, Value>(
+	before: &'a M,
+	after: &'a M,
+	changes: &[LightChange<Key>],
+	get: impl Fn(&'a M, &Key) -> Option<&'a Value>,
+) -> Option<<BTreeMap<Key, Value> as Comparable>::Change>
+where
+	Key: Ord + Clone + Debug,
+	Value: Hash + Comparable + 'a,
+{
+	changes
+		.iter()
+		.map(|change| match change {
+			LightChange::Added(k, hash) => {
+				Some(MapChange::Added(k.clone(), matching(get(after, k), *hash)?.describe()))
+			}
+			LightChange::Removed(k, hash) => {
+				matching(get(before, k), *hash)?;
+				Some(MapChange::Removed(k.clone()))
+			}
+			LightChange::Changed(k, from, to) => {
+				let mut change = matching(get(before, k), *from)?.comparison(matching(get(after, k), *to)?);
+				Some(MapChange::Changed(k.clone(), Changed::take(&mut change)?))
+			}
+		})
+		.collect()
+}
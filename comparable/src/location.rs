@@ -0,0 +1,96 @@
+//! Locating values within a larger value.
+//!
+//! [`Comparable::first_difference`] reports where two values first differ as
+//! a [`Path`], leading from the values compared to the field, element or
+//! entry that differs. This is most useful in assertion messages for large
+//! values, where the full set of changes would be overwhelming.
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! #[derive(Comparable, PartialEq)]
+//! struct Item {
+//!     name: String,
+//!     price: u32,
+//! }
+//!
+//! #[derive(Comparable)]
+//! struct Order {
+//!     items: Vec<Item>,
+//! }
+//!
+//! let before = Order { items: vec![Item { name: "Pen".to_string(), price: 2 }] };
+//! let after = Order { items: vec![Item { name: "Pen".to_string(), price: 3 }] };
+//! assert_eq!(before.first_difference(&after).unwrap().to_string(), "items[0].price");
+//! ```
+//!
+//! [`Comparable::first_difference`]: crate::Comparable::first_difference
+
+use std::fmt;
+
+/// One step of a [`Path`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Segment {
+	/// A field of a struct or enum variant, by name, or by index for tuple
+	/// fields, as in a [`FieldMask`](crate::mask::FieldMask).
+	Field(String),
+	/// The element at an index of a sequence.
+	Index(usize),
+	/// The entry for a key of a map, written using the key's `Debug` format.
+	Key(String),
+}
+
+/// The location of a value within another, as a sequence of [`Segment`]s. The
+/// empty path is the location of the value itself.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Path(Vec<Segment>);
+
+impl Path {
+	/// The segments of this path, from outermost to innermost.
+	pub fn segments(&self) -> &[Segment] {
+		&self.0
+	}
+
+	/// Whether this is the path of the value itself.
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// This path, taken from within the given segment of a larger value.
+	pub fn within(mut self, segment: Segment) -> Self {
+		self.0.insert(0, segment);
+		self
+	}
+
+	/// The path of the given segment within the value at this path.
+	pub fn child(&self, segment: Segment) -> Self {
+		let mut segments = self.0.clone();
+		segments.push(segment);
+		Path(segments)
+	}
+}
+
+impl From<Vec<Segment>> for Path {
+	fn from(segments: Vec<Segment>) -> Self {
+		Path(segments)
+	}
+}
+
+/// Paths are written in the manner of Rust expressions, such as
+/// `items[0].price` or `scores["alice"]`, with the empty path written as `.`.
+impl fmt::Display for Path {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.0.is_empty() {
+			return f.write_str(".");
+		}
+		for (i, segment) in self.0.iter().enumerate() {
+			match segment {
+				Segment::Field(name) if i == 0 => f.write_str(name)?,
+				Segment::Field(name) => write!(f, ".{}", name)?,
+				Segment::Index(index) => write!(f, "[{}]", index)?,
+				Segment::Key(key) => write!(f, "[{}]", key)?,
+			}
+		}
+		Ok(())
+	}
+}
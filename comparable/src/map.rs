@@ -1,7 +1,10 @@
 // use serde;
-use std::collections::{BTreeMap, HashMap};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt::Debug;
 
+use crate::location::{Path, Segment};
+use crate::options::diff_options;
 use crate::types::{Changed, Comparable};
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -10,6 +13,16 @@ pub enum MapChange<Key, Desc, Change> {
 	Added(Key, Desc),
 	Changed(Key, Change),
 	Removed(Key),
+	/// The value at `from` was removed and added again under `to`, with the
+	/// given changes. This is only reported when enabled by
+	/// [`DiffOptions::map_moves`].
+	///
+	/// [`DiffOptions::map_moves`]: crate::DiffOptions::map_moves
+	Moved {
+		from: Key,
+		to: Key,
+		change: Changed<Change>,
+	},
 }
 
 impl<Key: Ord + Clone + Debug, Value: Comparable> Comparable for BTreeMap<Key, Value> {
@@ -23,40 +36,107 @@ impl<Key: Ord + Clone + Debug, Value: Comparable> Comparable for BTreeMap<Key, V
 
 	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
 		let mut changes = Vec::new();
-		changes.append(
-			&mut other
-				.iter()
-				.flat_map(|(k, v)| {
-					if let Some(vo) = self.get(k) {
-						vo.comparison(v).map(|changes| MapChange::Changed(k.clone(), changes))
-					} else {
-						Changed::Changed(MapChange::Added(k.clone(), v.describe()))
+		for (k, v) in other {
+			match self.get(k) {
+				Some(vo) => {
+					if let Changed::Changed(change) = vo.comparison(v) {
+						changes.push(MapChange::Changed(k.clone(), change));
 					}
-				})
-				.collect(),
-		);
-		changes.append(
-			&mut self
-				.iter()
-				.flat_map(|(k, _v)| {
-					if !other.contains_key(k) {
-						Changed::Changed(MapChange::Removed(k.clone()))
-					} else {
-						Changed::Unchanged
-					}
-				})
-				.collect(),
-		);
-		if changes.is_empty() {
-			Changed::Unchanged
-		} else {
-			Changed::Changed(changes)
+				}
+				None => changes.push(MapChange::Added(k.clone(), v.describe())),
+			}
+		}
+		changes.extend(self.keys().filter(|k| !other.contains_key(k)).map(|k| MapChange::Removed(k.clone())));
+		if let Some(threshold) = diff_options().map_moves {
+			changes = detect_moves(self, other, changes, threshold);
 		}
+		Changed::from_changes(changes)
+	}
+
+	fn differs(&self, other: &Self) -> bool {
+		self.len() != other.len() || self.iter().any(|(k, v)| other.get(k).is_none_or(|o| v.differs(o)))
+	}
+
+	fn count_changes(&self, other: &Self) -> usize {
+		let changed: usize = self.iter().map(|(k, v)| other.get(k).map_or(1, |o| v.count_changes(o))).sum();
+		changed + other.keys().filter(|k| !self.contains_key(k)).count()
+	}
+
+	fn first_difference(&self, other: &Self) -> Option<Path> {
+		let mut keys = self.keys().chain(other.keys().filter(|k| !self.contains_key(k))).collect::<Vec<_>>();
+		keys.sort();
+		keys.into_iter().find_map(|k| {
+			let path = match (self.get(k), other.get(k)) {
+				(Some(v), Some(o)) => v.first_difference(o),
+				(_, _) => Some(Path::default()),
+			};
+			path.map(|path| path.within(Segment::Key(format!("{:?}", k))))
+		})
 	}
 }
 
-fn to_btreemap<K: Clone + Ord, V>(map: &HashMap<K, V>) -> BTreeMap<K, &V> {
-	map.iter().map(|(k, v)| (k.clone(), v)).collect::<BTreeMap<K, &V>>().into_iter().collect()
+// Pair up removed and added entries whose values are at least as similar as
+// `threshold`, most similar first, replacing each pair with a move reported
+// in place of the addition.
+fn detect_moves<Key: Ord + Clone, Value: Comparable>(
+	before: &BTreeMap<Key, Value>,
+	after: &BTreeMap<Key, Value>,
+	changes: Vec<MapChange<Key, Value::Desc, Value::Change>>,
+	threshold: f64,
+) -> Vec<MapChange<Key, Value::Desc, Value::Change>> {
+	let removed: Vec<&Key> =
+		changes.iter().filter_map(|change| if let MapChange::Removed(k) = change { Some(k) } else { None }).collect();
+	let added: Vec<&Key> =
+		changes.iter().filter_map(|change| if let MapChange::Added(k, _) = change { Some(k) } else { None }).collect();
+	let mut candidates = Vec::new();
+	for (i, from) in removed.iter().enumerate() {
+		for (j, to) in added.iter().enumerate() {
+			let similarity = before[*from].similarity(&after[*to]);
+			if similarity >= threshold {
+				candidates.push((similarity, i, j));
+			}
+		}
+	}
+	// The sort is stable, so that ties are broken in key order.
+	candidates.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+	let mut moved_from = vec![false; removed.len()];
+	let mut moves: BTreeMap<Key, Key> = BTreeMap::new();
+	for (_, i, j) in candidates {
+		if !moved_from[i] && !moves.contains_key(added[j]) {
+			moved_from[i] = true;
+			moves.insert(added[j].clone(), removed[i].clone());
+		}
+	}
+	let moved: BTreeSet<Key> = moves.values().cloned().collect();
+	changes
+		.into_iter()
+		.filter_map(|change| match change {
+			MapChange::Added(to, desc) => match moves.remove(&to) {
+				Some(from) => {
+					let change = before[&from].comparison(&after[&to]);
+					Some(MapChange::Moved { from, to, change })
+				}
+				None => Some(MapChange::Added(to, desc)),
+			},
+			MapChange::Removed(from) if moved.contains(&from) => None,
+			change => Some(change),
+		})
+		.collect()
+}
+
+// Entries are compared in key order, and keys are only cloned for the entries
+// reported as changed.
+pub(crate) fn to_btreemap<K: Ord, V>(map: &HashMap<K, V>) -> BTreeMap<&K, &V> {
+	map.iter().collect()
+}
+
+pub(crate) fn with_owned_key<K: Clone, D, C>(change: MapChange<&K, D, C>) -> MapChange<K, D, C> {
+	match change {
+		MapChange::Added(k, desc) => MapChange::Added(k.clone(), desc),
+		MapChange::Changed(k, change) => MapChange::Changed(k.clone(), change),
+		MapChange::Removed(k) => MapChange::Removed(k.clone()),
+		MapChange::Moved { from, to, change } => MapChange::Moved { from: from.clone(), to: to.clone(), change },
+	}
 }
 
 impl<Key: Ord + Clone + Debug, Value: Comparable> Comparable for HashMap<Key, Value> {
@@ -69,6 +149,20 @@ impl<Key: Ord + Clone + Debug, Value: Comparable> Comparable for HashMap<Key, Va
 	type Change = Vec<MapChange<Key, Value::Desc, Value::Change>>;
 
 	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
-		to_btreemap(self).comparison(&to_btreemap(other))
+		to_btreemap(self)
+			.comparison(&to_btreemap(other))
+			.map(|changes| changes.into_iter().map(with_owned_key).collect())
+	}
+
+	fn differs(&self, other: &Self) -> bool {
+		self.len() != other.len() || to_btreemap(self).differs(&to_btreemap(other))
+	}
+
+	fn count_changes(&self, other: &Self) -> usize {
+		to_btreemap(self).count_changes(&to_btreemap(other))
+	}
+
+	fn first_difference(&self, other: &Self) -> Option<Path> {
+		to_btreemap(self).first_difference(&to_btreemap(other))
 	}
 }
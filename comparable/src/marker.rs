@@ -0,0 +1,27 @@
+use std::marker::{PhantomData, PhantomPinned};
+
+use crate::types::{Changed, Comparable};
+
+impl<T: ?Sized> Comparable for PhantomData<T> {
+	type Desc = ();
+
+	fn describe(&self) -> Self::Desc {}
+
+	type Change = ();
+
+	fn comparison(&self, _other: &Self) -> Changed<Self::Change> {
+		Changed::Unchanged
+	}
+}
+
+impl Comparable for PhantomPinned {
+	type Desc = ();
+
+	fn describe(&self) -> Self::Desc {}
+
+	type Change = ();
+
+	fn comparison(&self, _other: &Self) -> Changed<Self::Change> {
+		Changed::Unchanged
+	}
+}
@@ -0,0 +1,325 @@
+//! Choosing at runtime which fields take part in a comparison.
+//!
+//! A [`FieldMask`] is a set of dotted field paths, such as `"address.city"`,
+//! naming either the only fields to compare, or the fields to leave out, in
+//! the manner of protobuf's `FieldMask`. Every type implementing
+//! [`Comparable`] has a `comparison_masked` method, from the
+//! [`MaskedComparison`] trait, that compares two values under a mask, so that
+//! a service can honor the fields requested by its clients. Fields that are
+//! masked out are reported as unchanged.
+//!
+//! Paths follow field names through nested structs and enum variants, and
+//! pass through collections and options unchanged, so `"items.price"` names
+//! the `price` field of each element of `items`. Tuple fields are named by
+//! their index. Types that do not derive [`Comparable`] are compared whole
+//! once they are reached.
+//!
+//! Masks can be converted to and from the paths of a protobuf `FieldMask`,
+//! using [`FieldMask::paths`] and [`FieldMask::include`], or its JSON form,
+//! using [`FieldMask::to_protobuf`] and [`FieldMask::from_protobuf`], so that
+//! masks received from clients can be honored directly.
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! use comparable::mask::FieldMask;
+//!
+//! #[derive(Comparable)]
+//! struct Account {
+//!     name: String,
+//!     balance: u64,
+//! }
+//!
+//! let before = Account { name: "Alice".to_string(), balance: 10 };
+//! let after = Account { name: "Alicia".to_string(), balance: 20 };
+//! assert_eq!(
+//!     before.comparison_masked(&after, &FieldMask::include(["balance"])),
+//!     Changed::Changed(vec![AccountChange::Balance(U64Change(10, 20))])
+//! );
+//! ```
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use crate::ignore::IgnoreSet;
+use crate::types::{Changed, Comparable};
+
+/// A set of field paths to either include in or exclude from a comparison.
+#[derive(Clone, PartialEq, Debug)]
+pub struct FieldMask {
+	exclude: bool,
+	root: Node,
+}
+
+// A node is `whole` when a path ends at it, in which case its children are
+// not consulted.
+#[derive(Clone, PartialEq, Debug, Default)]
+struct Node {
+	whole: bool,
+	fields: BTreeMap<String, Node>,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum Selection {
+	Skip,
+	Whole,
+	Nested,
+}
+
+impl FieldMask {
+	/// A mask comparing only the fields at the given paths, and everything
+	/// within them.
+	pub fn include<I>(paths: I) -> Self
+	where
+		I: IntoIterator,
+		I::Item: AsRef<str>,
+	{
+		FieldMask { exclude: false, root: Node::from_paths(paths) }
+	}
+
+	/// A mask comparing everything except the fields at the given paths.
+	pub fn exclude<I>(paths: I) -> Self
+	where
+		I: IntoIterator,
+		I::Item: AsRef<str>,
+	{
+		FieldMask { exclude: true, root: Node::from_paths(paths) }
+	}
+
+	/// Whether this mask names the fields to leave out, rather than the only
+	/// fields to compare.
+	pub fn is_exclude(&self) -> bool {
+		self.exclude
+	}
+
+	/// The dotted paths making up this mask, in sorted order. Paths within
+	/// another path of the mask are redundant, and are left out.
+	///
+	/// These are the `paths` of a protobuf `FieldMask`, such as that of the
+	/// `prost-types` crate, which can be converted back using
+	/// [`FieldMask::include`].
+	pub fn paths(&self) -> Vec<String> {
+		let mut paths = Vec::new();
+		self.root.collect_paths(&mut String::new(), &mut paths);
+		paths
+	}
+
+	/// The form of this mask used by protobuf's JSON mapping of
+	/// `google.protobuf.FieldMask`, in which paths are separated by commas and
+	/// field names are written in lower camel case. Since protobuf masks only
+	/// ever select fields, this is `None` for a mask excluding fields.
+	///
+	/// ```
+	/// # use comparable::mask::FieldMask;
+	/// let mask = FieldMask::include(["user.display_name", "photo"]);
+	/// assert_eq!(mask.to_protobuf().unwrap(), "photo,user.displayName");
+	/// assert_eq!(FieldMask::from_protobuf("photo,user.displayName"), mask);
+	/// ```
+	pub fn to_protobuf(&self) -> Option<String> {
+		if self.exclude {
+			return None;
+		}
+		let paths: Vec<String> = self
+			.paths()
+			.iter()
+			.map(|path| path.split('.').map(to_lower_camel_case).collect::<Vec<_>>().join("."))
+			.collect();
+		Some(paths.join(","))
+	}
+
+	/// Read a mask including the fields named in the JSON form of a protobuf
+	/// `google.protobuf.FieldMask`. See [`FieldMask::to_protobuf`].
+	pub fn from_protobuf(mask: &str) -> Self {
+		FieldMask::include(
+			mask.split(',')
+				.filter(|path| !path.is_empty())
+				.map(|path| path.split('.').map(to_snake_case).collect::<Vec<_>>().join(".")),
+		)
+	}
+
+	fn select(&self, path: &[String], field: &str) -> Selection {
+		let mut node = &self.root;
+		for name in path.iter().map(String::as_str).chain(Some(field)) {
+			match node.fields.get(name) {
+				Some(child) => node = child,
+				None if self.exclude => return Selection::Whole,
+				None => return Selection::Skip,
+			}
+		}
+		match (node.whole, self.exclude) {
+			(true, true) => Selection::Skip,
+			(true, false) => Selection::Whole,
+			(false, _) => Selection::Nested,
+		}
+	}
+}
+
+impl Node {
+	fn from_paths<I>(paths: I) -> Self
+	where
+		I: IntoIterator,
+		I::Item: AsRef<str>,
+	{
+		let mut root = Node::default();
+		for path in paths {
+			let mut node = &mut root;
+			for name in path.as_ref().split('.').filter(|name| !name.is_empty()) {
+				node = node.fields.entry(name.to_string()).or_default();
+			}
+			node.whole = true;
+		}
+		root
+	}
+
+	fn collect_paths(&self, prefix: &mut String, paths: &mut Vec<String>) {
+		if self.whole {
+			paths.push(prefix.clone());
+			return;
+		}
+		for (name, child) in &self.fields {
+			let len = prefix.len();
+			if len > 0 {
+				prefix.push('.');
+			}
+			prefix.push_str(name);
+			child.collect_paths(prefix, paths);
+			prefix.truncate(len);
+		}
+	}
+}
+
+fn to_lower_camel_case(name: &str) -> String {
+	let mut result = String::with_capacity(name.len());
+	let mut upper = false;
+	for c in name.chars() {
+		if c == '_' {
+			upper = true;
+		} else if upper {
+			result.extend(c.to_uppercase());
+			upper = false;
+		} else {
+			result.push(c);
+		}
+	}
+	result
+}
+
+fn to_snake_case(name: &str) -> String {
+	let mut result = String::with_capacity(name.len());
+	for c in name.chars() {
+		if c.is_uppercase() {
+			result.push('_');
+			result.extend(c.to_lowercase());
+		} else {
+			result.push(c);
+		}
+	}
+	result
+}
+
+// What chooses the fields compared within a scope.
+#[derive(Clone)]
+pub(crate) enum Filter {
+	Mask(FieldMask),
+	Ignore(IgnoreSet),
+}
+
+impl Filter {
+	fn select(&self, path: &[String], field: &str) -> Selection {
+		match self {
+			Filter::Mask(mask) => mask.select(path, field),
+			Filter::Ignore(ignore) => ignore.select(path, field),
+		}
+	}
+}
+
+// The filter in effect on this thread, and the path of the field currently
+// being compared.
+struct Scope {
+	mask: Rc<Filter>,
+	path: Vec<String>,
+}
+
+thread_local! {
+	static SCOPE: RefCell<Option<Scope>> = const { RefCell::new(None) };
+}
+
+// Restores the previous scope, even on panic.
+struct Restore(Option<Scope>);
+
+impl Drop for Restore {
+	fn drop(&mut self) {
+		SCOPE.with(|scope| *scope.borrow_mut() = self.0.take());
+	}
+}
+
+fn with_scope<R>(scope: Option<Scope>, f: impl FnOnce() -> R) -> R {
+	let _restore = Restore(SCOPE.with(|current| current.replace(scope)));
+	f()
+}
+
+// The mask in effect on this thread and the path of the field being compared,
+// for carrying them over to another thread with `with_current_mask`.
+#[cfg(feature = "tokio")]
+pub(crate) fn current_mask() -> Option<(Filter, Vec<String>)> {
+	SCOPE.with(|scope| scope.borrow().as_ref().map(|scope| ((*scope.mask).clone(), scope.path.clone())))
+}
+
+#[cfg(feature = "tokio")]
+pub(crate) fn with_current_mask<R>(mask: Option<(Filter, Vec<String>)>, f: impl FnOnce() -> R) -> R {
+	with_scope(mask.map(|(mask, path)| Scope { mask: Rc::new(mask), path }), f)
+}
+
+/// Comparing two values under a [`FieldMask`], implemented for every type
+/// implementing [`Comparable`].
+pub trait MaskedComparison: Comparable {
+	/// Compare this value with another, considering only the fields selected
+	/// by the given mask.
+	#[must_use]
+	fn comparison_masked(&self, other: &Self, mask: &FieldMask) -> Changed<Self::Change> {
+		with_field_mask(mask, || self.comparison(other))
+	}
+}
+
+impl<T: Comparable + ?Sized> MaskedComparison for T {}
+
+/// Run `f` with every derived comparison on this thread honoring the given
+/// mask. This is what [`MaskedComparison::comparison_masked`] uses, and is
+/// useful for masking several comparisons at once.
+pub fn with_field_mask<R>(mask: &FieldMask, f: impl FnOnce() -> R) -> R {
+	with_filter(Filter::Mask(mask.clone()), f)
+}
+
+pub(crate) fn with_filter<R>(filter: Filter, f: impl FnOnce() -> R) -> R {
+	with_scope(Some(Scope { mask: Rc::new(filter), path: Vec::new() }), f)
+}
+
+// Called by derived implementations of `Comparable` around the comparison of
+// each field, through `derive_support::masked`.
+#[doc(hidden)]
+pub fn masked<C>(field: &str, compare: impl FnOnce() -> Changed<C>) -> Changed<C> {
+	masked_or(field, Changed::Unchanged, compare)
+}
+
+// Like `masked`, for the other methods of `Comparable`, where `masked_out` is
+// the result for a field that is excluded.
+#[doc(hidden)]
+pub fn masked_or<R>(field: &str, masked_out: R, compare: impl FnOnce() -> R) -> R {
+	let selection = SCOPE.with(|scope| scope.borrow().as_ref().map(|scope| scope.mask.select(&scope.path, field)));
+	match selection {
+		None => compare(),
+		Some(Selection::Skip) => masked_out,
+		Some(Selection::Whole) => with_scope(None, compare),
+		Some(Selection::Nested) => {
+			let scope = SCOPE.with(|scope| {
+				scope.borrow().as_ref().map(|scope| {
+					let mut path = scope.path.clone();
+					path.push(field.to_string());
+					Scope { mask: Rc::clone(&scope.mask), path }
+				})
+			});
+			with_scope(scope, compare)
+		}
+	}
+}
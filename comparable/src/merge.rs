@@ -0,0 +1,147 @@
+//! Combining the changes made independently to two copies of the same value.
+
+use std::fmt::Debug;
+
+use crate::fs::FileMetadataChange;
+use crate::map::MapChange;
+use crate::range::RangeChange;
+use crate::set::VecChange;
+
+/// Identifies the part of a value that a single entry in a list of changes
+/// refers to, such as a field of a struct, an index of a `Vec`, or a key of a
+/// map. Two entries with the same key are considered to overlap.
+///
+/// This is implemented by `#[derive(Comparable)]` for the `Change` type of
+/// structs with more than one field, where the key is the field's name. Keys
+/// are also what [`sort_changes`] and [`dedup_changes`] order and compare
+/// changes by.
+pub trait MergeKey {
+	fn merge_key(&self) -> String;
+}
+
+impl<Desc, Change> MergeKey for VecChange<Desc, Change> {
+	fn merge_key(&self) -> String {
+		match self {
+			VecChange::Added(i, _)
+			| VecChange::Changed(i, _)
+			| VecChange::Removed(i, _)
+			| VecChange::Moved { from: i, .. } => i.to_string(),
+		}
+	}
+}
+
+impl<Key: Debug, Desc, Change> MergeKey for MapChange<Key, Desc, Change> {
+	fn merge_key(&self) -> String {
+		match self {
+			MapChange::Added(k, _)
+			| MapChange::Changed(k, _)
+			| MapChange::Removed(k)
+			| MapChange::Moved { from: k, .. } => format!("{:?}", k),
+		}
+	}
+}
+
+impl<Change> MergeKey for RangeChange<Change> {
+	fn merge_key(&self) -> String {
+		match self {
+			RangeChange::Start(_) => "start".to_string(),
+			RangeChange::End(_) => "end".to_string(),
+		}
+	}
+}
+
+impl MergeKey for FileMetadataChange {
+	fn merge_key(&self) -> String {
+		match self {
+			FileMetadataChange::Size(_) => "size".to_string(),
+			FileMetadataChange::Modified(_, _) => "modified".to_string(),
+			FileMetadataChange::Hash(_) => "hash".to_string(),
+		}
+	}
+}
+
+/// Two changes made to the same part of a value, which cannot both be kept.
+#[derive(PartialEq, Debug)]
+pub struct Conflict<Change> {
+	pub path: String,
+	pub left: Change,
+	pub right: Change,
+}
+
+/// Merge two sets of changes computed against the same base value. Changes to
+/// different parts of the value are combined, as are identical changes made
+/// on both sides; if both sides changed the same part in different ways, all
+/// such conflicts are reported instead.
+///
+/// The merged changes are those of `left`, followed by any from `right` that
+/// did not also appear in `left`.
+///
+/// ```
+/// # use comparable::*;
+/// # use comparable::merge::*;
+/// let base = vec![1, 2, 3];
+/// let left = base.comparison(&vec![10, 2, 3]).unwrap_or_default();
+/// let right = base.comparison(&vec![1, 2, 30]).unwrap_or_default();
+/// assert_eq!(
+///     merge_changes(left, right),
+///     Ok(vec![VecChange::Changed(0, I32Change(1, 10)), VecChange::Changed(2, I32Change(3, 30))]),
+/// );
+/// ```
+pub fn merge_changes<C: MergeKey + PartialEq>(left: Vec<C>, right: Vec<C>) -> Result<Vec<C>, Vec<Conflict<C>>> {
+	let left_paths = left.iter().map(|c| c.merge_key()).collect::<Vec<_>>();
+	let mut left = left.into_iter().map(Some).collect::<Vec<_>>();
+	let mut added = Vec::new();
+	let mut conflicts = Vec::new();
+	for change in right {
+		let path = change.merge_key();
+		match left_paths.iter().position(|p| *p == path) {
+			None => added.push(change),
+			Some(i) => match left[i].take() {
+				Some(l) if l == change => left[i] = Some(l),
+				Some(l) => conflicts.push(Conflict { path, left: l, right: change }),
+				None => (),
+			},
+		}
+	}
+	if conflicts.is_empty() {
+		Ok(left.into_iter().flatten().chain(added).collect())
+	} else {
+		Err(conflicts)
+	}
+}
+
+/// Sort changes by their merge keys, so that the same changes are listed in
+/// the same order however they were found, as golden-file tests of
+/// serialized changes need. The sort is stable, so that changes with the same
+/// key, such as the removal and addition of entries at the same index of a
+/// `Vec`, keep their relative order.
+///
+/// The changes of a derived struct with several fields are already listed in
+/// the order its fields were declared, which this does not preserve unless
+/// that order is also that of their names.
+///
+/// ```
+/// # use comparable::*;
+/// # use comparable::merge::*;
+/// let mut changes: Vec<VecChange<i32, I32Change>> =
+///     vec![VecChange::Changed(2, I32Change(3, 30)), VecChange::Changed(0, I32Change(1, 10))];
+/// sort_changes(&mut changes);
+/// assert_eq!(changes, vec![VecChange::Changed(0, I32Change(1, 10)), VecChange::Changed(2, I32Change(3, 30))]);
+/// ```
+pub fn sort_changes<C: MergeKey>(changes: &mut [C]) {
+	changes.sort_by_cached_key(|change| change.merge_key())
+}
+
+/// Remove changes that repeat an earlier change with the same merge key, such
+/// as those gathered from several comparisons of the same values, keeping
+/// the order of the rest.
+pub fn dedup_changes<C: MergeKey + PartialEq>(changes: &mut Vec<C>) {
+	let mut kept: Vec<(String, C)> = Vec::with_capacity(changes.len());
+	for change in changes.drain(..) {
+		let key = change.merge_key();
+		if !kept.iter().any(|(k, c)| *k == key && *c == change) {
+			kept.push((key, change));
+		}
+	}
+	changes.extend(kept.into_iter().map(|(_, change)| change));
+}
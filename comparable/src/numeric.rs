@@ -0,0 +1,70 @@
+use crate::types::{Changed, Comparable};
+
+/// A change to an `OrderedFloat` or `NotNan` value, holding the values before
+/// and after, and the difference between them, which is the value after less
+/// the value before.
+#[cfg(feature = "ordered-float")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, Debug)]
+pub struct OrderedFloatChange<T>(pub T, pub T, pub T);
+
+#[cfg(feature = "ordered-float")]
+macro_rules! impl_comparable_for_ordered_float {
+	($($ty:ident),*) => {
+		$(
+			/// The values are described by the floats they wrap, and are unchanged
+			/// when they are equal by the wrapper's own definition, under which
+			/// `NaN` equals itself.
+			impl<T: ordered_float::FloatCore + std::fmt::Debug> Comparable for ordered_float::$ty<T> {
+				type Desc = T;
+
+				fn describe(&self) -> Self::Desc {
+					self.into_inner()
+				}
+
+				type Change = OrderedFloatChange<T>;
+
+				fn comparison(&self, other: &Self) -> Changed<Self::Change> {
+					if self != other {
+						let (before, after) = (self.into_inner(), other.into_inner());
+						Changed::Changed(OrderedFloatChange(before, after, after - before))
+					} else {
+						Changed::Unchanged
+					}
+				}
+			}
+		)*
+	};
+}
+
+#[cfg(feature = "ordered-float")]
+impl_comparable_for_ordered_float!(OrderedFloat, NotNan);
+
+/// A change to a `Decimal` value, holding the values before and after, and
+/// the difference between them, which is the value after less the value
+/// before.
+#[cfg(feature = "rust_decimal")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, Debug)]
+pub struct DecimalChange(pub rust_decimal::Decimal, pub rust_decimal::Decimal, pub rust_decimal::Decimal);
+
+/// Decimals are unchanged when they are numerically equal, whatever their
+/// scale, so that `1.0` and `1.00` are the same value.
+#[cfg(feature = "rust_decimal")]
+impl Comparable for rust_decimal::Decimal {
+	type Desc = rust_decimal::Decimal;
+
+	fn describe(&self) -> Self::Desc {
+		*self
+	}
+
+	type Change = DecimalChange;
+
+	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
+		if self != other {
+			Changed::Changed(DecimalChange(*self, *other, *other - *self))
+		} else {
+			Changed::Unchanged
+		}
+	}
+}
@@ -1,5 +1,6 @@
 // use serde;
 
+use crate::location::Path;
 use crate::types::{Changed, Comparable};
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -25,4 +26,36 @@ impl<T: Comparable> Comparable for Option<T> {
 			(_, _) => Changed::Changed(OptionChange::Different(self.describe(), other.describe())),
 		}
 	}
+
+	fn similarity(&self, other: &Self) -> f64 {
+		match (self, other) {
+			(None, None) => 1.0,
+			(Some(x), Some(y)) => x.similarity(y),
+			(_, _) => 0.0,
+		}
+	}
+
+	fn differs(&self, other: &Self) -> bool {
+		match (self, other) {
+			(None, None) => false,
+			(Some(x), Some(y)) => x.differs(y),
+			(_, _) => true,
+		}
+	}
+
+	fn count_changes(&self, other: &Self) -> usize {
+		match (self, other) {
+			(None, None) => 0,
+			(Some(x), Some(y)) => x.count_changes(y),
+			(_, _) => 1,
+		}
+	}
+
+	fn first_difference(&self, other: &Self) -> Option<Path> {
+		match (self, other) {
+			(None, None) => None,
+			(Some(x), Some(y)) => x.first_difference(y),
+			(_, _) => Some(Path::default()),
+		}
+	}
 }
@@ -0,0 +1,74 @@
+use std::cell::Cell;
+
+use crate::diff::DiffAlgorithm;
+
+/// Options for how changes are reported, which make comparisons more
+/// thorough at some cost in speed. See [`with_diff_options`].
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct DiffOptions {
+	/// Report an entry of a `BTreeMap` or `HashMap` that was removed from one
+	/// key and added under another as [`MapChange::Moved`], when the two values
+	/// have at least the given [`Comparable::similarity`]. For example,
+	/// `Some(1.0)` only recognizes values that moved without changing.
+	///
+	/// [`MapChange::Moved`]: crate::MapChange::Moved
+	/// [`Comparable::similarity`]: crate::Comparable::similarity
+	pub map_moves: Option<f64>,
+	/// Report an element of a `Vec` that is found unchanged at a new index as
+	/// [`VecChange::Moved`], rather than as changes at both indices.
+	///
+	/// [`VecChange::Moved`]: crate::VecChange::Moved
+	pub sequence_moves: bool,
+	/// Align the elements of a `Vec` using the given algorithm before comparing
+	/// them, so that elements inserted or removed in the middle are reported
+	/// as added or removed. Changed and added elements are then identified by
+	/// their index in the new `Vec`, and removed elements by their index in
+	/// the old one. Without an algorithm, elements are compared index by index,
+	/// which is fastest. This has no effect when `sequence_moves` is set.
+	pub sequence_algorithm: Option<DiffAlgorithm>,
+	/// When aligning the elements of a `Vec`, report a removed and an added
+	/// element between the same aligned elements as a change, only when they
+	/// have at least the given [`Comparable::similarity`], rather than
+	/// pairing them by position. This keeps an element that changed slightly
+	/// from appearing to be removed and added again, even when other elements
+	/// were added or removed around it. Elements are aligned with Myers'
+	/// algorithm unless `sequence_algorithm` says otherwise.
+	///
+	/// [`Comparable::similarity`]: crate::Comparable::similarity
+	pub sequence_similarity: Option<f64>,
+}
+
+thread_local! {
+	static OPTIONS: Cell<DiffOptions> = Cell::new(DiffOptions::default());
+}
+
+// Restores the previous options, even on panic.
+struct Restore(DiffOptions);
+
+impl Drop for Restore {
+	fn drop(&mut self) {
+		OPTIONS.with(|options| options.set(self.0));
+	}
+}
+
+/// Run `f` with every comparison on this thread using the given options.
+///
+/// ```
+/// # use comparable::*;
+/// # use std::collections::BTreeMap;
+/// let before: BTreeMap<_, _> = vec![("ann", 1)].into_iter().collect();
+/// let after: BTreeMap<_, _> = vec![("anne", 1)].into_iter().collect();
+/// let options = DiffOptions { map_moves: Some(1.0), ..DiffOptions::default() };
+/// assert_eq!(
+///     with_diff_options(options, || before.comparison(&after)),
+///     Changed::Changed(vec![MapChange::Moved { from: "ann", to: "anne", change: Changed::Unchanged }])
+/// );
+/// ```
+pub fn with_diff_options<R>(options: DiffOptions, f: impl FnOnce() -> R) -> R {
+	let _restore = Restore(OPTIONS.with(|current| current.replace(options)));
+	f()
+}
+
+pub(crate) fn diff_options() -> DiffOptions {
+	OPTIONS.with(Cell::get)
+}
@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::hash::{BuildHasher, Hash};
+
+use crate::map::MapChange;
+use crate::set::VecChange;
+use crate::types::{Changed, Comparable};
+
+fn to_btreemap<'a, K: 'a + Clone + Ord, V>(iter: impl Iterator<Item = (&'a K, &'a V)>) -> BTreeMap<K, &'a V> {
+	iter.map(|(k, v)| (k.clone(), v)).collect()
+}
+
+// The `im` and `im-rc` crates expose the same API, differing only in whether
+// their nodes are shared through `Arc` or `Rc`, so the same impls are
+// generated for both. Wherever possible the comparison first checks whether
+// two collections share their root node, in which case they are known to be
+// identical without visiting any of their elements.
+macro_rules! impl_comparable_for_persistent {
+	($im:ident) => {
+		impl<Value: Clone + PartialEq + Comparable> Comparable for ::$im::Vector<Value> {
+			type Desc = Vec<Value::Desc>;
+
+			fn describe(&self) -> Self::Desc {
+				self.iter().map(|x| x.describe()).collect()
+			}
+
+			type Change = Vec<VecChange<Value::Desc, Value::Change>>;
+
+			fn comparison(&self, other: &Self) -> Changed<Self::Change> {
+				if self.ptr_eq(other) {
+					return Changed::Unchanged;
+				}
+				let mut changes = Vec::new();
+				for (i, value) in self.iter().enumerate() {
+					match other.get(i) {
+						None => changes.push(VecChange::Removed(i, value.describe())),
+						Some(other_value) => {
+							if let Changed::Changed(change) = value.comparison(other_value) {
+								changes.push(VecChange::Changed(i, change));
+							}
+						}
+					}
+				}
+				for (i, value) in other.iter().enumerate().skip(self.len()) {
+					changes.push(VecChange::Added(i, value.describe()));
+				}
+				Changed::from_changes(changes)
+			}
+		}
+
+		impl<Key, Value, S> Comparable for ::$im::HashMap<Key, Value, S>
+		where
+			Key: Hash + Eq + Ord + Clone + Debug,
+			Value: Clone + Comparable,
+			S: BuildHasher,
+		{
+			type Desc = BTreeMap<Key, Value::Desc>;
+
+			fn describe(&self) -> Self::Desc {
+				self.iter().map(|(k, v)| (k.clone(), v.describe())).collect()
+			}
+
+			type Change = Vec<MapChange<Key, Value::Desc, Value::Change>>;
+
+			fn comparison(&self, other: &Self) -> Changed<Self::Change> {
+				if self.ptr_eq(other) {
+					return Changed::Unchanged;
+				}
+				to_btreemap(self.iter()).comparison(&to_btreemap(other.iter()))
+			}
+		}
+
+		impl<Key, Value> Comparable for ::$im::OrdMap<Key, Value>
+		where
+			Key: Ord + Clone + Debug,
+			Value: Clone + PartialEq + Comparable,
+		{
+			type Desc = BTreeMap<Key, Value::Desc>;
+
+			fn describe(&self) -> Self::Desc {
+				self.iter().map(|(k, v)| (k.clone(), v.describe())).collect()
+			}
+
+			type Change = Vec<MapChange<Key, Value::Desc, Value::Change>>;
+
+			fn comparison(&self, other: &Self) -> Changed<Self::Change> {
+				if self.ptr_eq(other) {
+					return Changed::Unchanged;
+				}
+				// `OrdMap::diff` skips over any subtrees shared by both maps.
+				// Removals are reported after additions and changes, to match
+				// the output of the `BTreeMap` impl.
+				let mut changes = Vec::new();
+				let mut removals = Vec::new();
+				for item in self.diff(other) {
+					match item {
+						::$im::ordmap::DiffItem::Add(k, v) => changes.push(MapChange::Added(k.clone(), v.describe())),
+						::$im::ordmap::DiffItem::Update { old: (k, old), new: (_, new) } => {
+							if let Changed::Changed(change) = old.comparison(new) {
+								changes.push(MapChange::Changed(k.clone(), change));
+							}
+						}
+						::$im::ordmap::DiffItem::Remove(k, _) => removals.push(MapChange::Removed(k.clone())),
+					}
+				}
+				changes.append(&mut removals);
+				Changed::from_changes(changes)
+			}
+		}
+	};
+}
+
+#[cfg(feature = "im")]
+impl_comparable_for_persistent!(im);
+
+#[cfg(feature = "im-rc")]
+impl_comparable_for_persistent!(im_rc);
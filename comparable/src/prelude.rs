@@ -0,0 +1,36 @@
+//! The items needed in most uses of the crate, for importing all at once.
+//!
+//! Along with the [`Comparable`] trait, and its derive macro when the
+//! `derive` feature is enabled, this brings in [`Changed`], [`Apply`],
+//! [`MaskedComparison`], the free functions [`diff`], [`describe`] and
+//! [`changed`], the wrappers that change how values are compared, and
+//! [`assert_changes!`](crate::assert_changes).
+//! The types generated for each scalar, collection and derived type, such as
+//! `I32Change`, are not included.
+//!
+//! ```
+//! # #[cfg(feature = "derive")]
+//! # {
+//! use comparable::prelude::*;
+//!
+//! #[derive(Comparable)]
+//! struct Point {
+//!     x: i32,
+//!     y: i32,
+//! }
+//!
+//! let point = Point { x: 1, y: 2 };
+//! assert!(changed(&point, &Point { x: 1, y: 3 }));
+//! assert_eq!(diff(&point, &Point { x: 1, y: 3 }).unwrap_changed().len(), 1);
+//! assert_changes!(&point, &Point { x: 1, y: 2 }, Changed::Unchanged);
+//! # }
+//! ```
+
+pub use crate::lens::Apply;
+pub use crate::{
+	assert_changes, changed, describe, diff, CaseInsensitive, Changed, Comparable, Leaf, MaskedComparison, Redacted,
+	Tracked, Trimmed,
+};
+
+#[cfg(feature = "comparable_derive")]
+pub use comparable_derive::Describe;
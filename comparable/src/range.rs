@@ -0,0 +1,81 @@
+use std::ops::{Range, RangeInclusive};
+
+use crate::location::{Path, Segment};
+use crate::types::{Changed, Comparable};
+
+/// A change to one end of a range, as reported for `Range` and
+/// `RangeInclusive`. A range both of whose ends moved has a change for each.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, Debug)]
+pub enum RangeChange<Change> {
+	Start(Change),
+	End(Change),
+}
+
+// The changes to the ends of two ranges, given as the ends of each.
+fn range_comparison<T: Comparable>(start: (&T, &T), end: (&T, &T)) -> Changed<Vec<RangeChange<T::Change>>> {
+	let mut changes = Vec::new();
+	changes.extend(start.0.comparison(start.1).map(RangeChange::Start));
+	changes.extend(end.0.comparison(end.1).map(RangeChange::End));
+	Changed::from_changes(changes)
+}
+
+fn range_first_difference<T: Comparable>(start: (&T, &T), end: (&T, &T)) -> Option<Path> {
+	start
+		.0
+		.first_difference(start.1)
+		.map(|path| path.within(Segment::Field("start".to_string())))
+		.or_else(|| end.0.first_difference(end.1).map(|path| path.within(Segment::Field("end".to_string()))))
+}
+
+impl<T: Comparable> Comparable for Range<T> {
+	type Desc = Range<T::Desc>;
+
+	fn describe(&self) -> Self::Desc {
+		self.start.describe()..self.end.describe()
+	}
+
+	type Change = Vec<RangeChange<T::Change>>;
+
+	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
+		range_comparison((&self.start, &other.start), (&self.end, &other.end))
+	}
+
+	fn differs(&self, other: &Self) -> bool {
+		self.start.differs(&other.start) || self.end.differs(&other.end)
+	}
+
+	fn count_changes(&self, other: &Self) -> usize {
+		self.start.count_changes(&other.start) + self.end.count_changes(&other.end)
+	}
+
+	fn first_difference(&self, other: &Self) -> Option<Path> {
+		range_first_difference((&self.start, &other.start), (&self.end, &other.end))
+	}
+}
+
+impl<T: Comparable> Comparable for RangeInclusive<T> {
+	type Desc = RangeInclusive<T::Desc>;
+
+	fn describe(&self) -> Self::Desc {
+		self.start().describe()..=self.end().describe()
+	}
+
+	type Change = Vec<RangeChange<T::Change>>;
+
+	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
+		range_comparison((self.start(), other.start()), (self.end(), other.end()))
+	}
+
+	fn differs(&self, other: &Self) -> bool {
+		self.start().differs(other.start()) || self.end().differs(other.end())
+	}
+
+	fn count_changes(&self, other: &Self) -> usize {
+		self.start().count_changes(other.start()) + self.end().count_changes(other.end())
+	}
+
+	fn first_difference(&self, other: &Self) -> Option<Path> {
+		range_first_difference((self.start(), other.start()), (self.end(), other.end()))
+	}
+}
@@ -0,0 +1,324 @@
+//! Converting descriptions back into the values they describe.
+//!
+//! A description is normally only a report of a value, but when it captures
+//! everything about that value it can also serve as a snapshot of it, to be
+//! persisted and later restored. [`IntoDesc`] turns a value into its
+//! description without cloning, and [`FromDesc`] rebuilds the value.
+//!
+//! Both are implemented for the scalars and standard collections, and can be
+//! derived along with [`Comparable`] using `#[comparable_desc_roundtrip]`.
+//! Types whose descriptions leave something out, such as shared pointers,
+//! whose descriptions only refer to values already seen, do not implement
+//! them.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::Debug;
+use std::marker::{PhantomData, PhantomPinned};
+use std::ops::{Range, RangeInclusive};
+use std::path::PathBuf;
+use std::sync::atomic::{
+	AtomicBool, AtomicI16, AtomicI32, AtomicI64, AtomicI8, AtomicIsize, AtomicU16, AtomicU32, AtomicU64, AtomicU8,
+	AtomicUsize,
+};
+
+use crate::leaf::Leaf;
+use crate::string::{CaseInsensitive, Trimmed};
+use crate::types::Comparable;
+
+/// Types that can be rebuilt from their descriptions, such that
+/// `T::from_desc(value.describe())` is equal to `value`.
+///
+/// ```
+/// # use comparable::*;
+/// use comparable::roundtrip::FromDesc;
+///
+/// let value = vec![Some(1), None];
+/// assert_eq!(Vec::<Option<i32>>::from_desc(value.describe()), value);
+/// ```
+pub trait FromDesc: Comparable + Sized {
+	fn from_desc(desc: Self::Desc) -> Self;
+}
+
+/// Types that can be turned into their descriptions by value, giving the same
+/// result as [`Comparable::describe`] without cloning what they hold.
+pub trait IntoDesc: Comparable + Sized {
+	fn into_desc(self) -> Self::Desc;
+}
+
+macro_rules! impl_roundtrip_for_self_describing {
+	($($ty:ty),*) => {
+		$(
+			impl FromDesc for $ty {
+				fn from_desc(desc: Self::Desc) -> Self {
+					desc
+				}
+			}
+
+			impl IntoDesc for $ty {
+				fn into_desc(self) -> Self::Desc {
+					self
+				}
+			}
+		)*
+	};
+}
+
+impl_roundtrip_for_self_describing!((), f32, f64, String, PathBuf);
+
+impl<S: AsRef<str> + From<String>> FromDesc for CaseInsensitive<S> {
+	fn from_desc(desc: Self::Desc) -> Self {
+		CaseInsensitive(S::from(desc))
+	}
+}
+
+impl<S: AsRef<str> + Into<String>> IntoDesc for CaseInsensitive<S> {
+	fn into_desc(self) -> Self::Desc {
+		self.0.into()
+	}
+}
+
+impl<S: AsRef<str> + From<String>> FromDesc for Trimmed<S> {
+	fn from_desc(desc: Self::Desc) -> Self {
+		Trimmed(S::from(desc))
+	}
+}
+
+impl<S: AsRef<str> + Into<String>> IntoDesc for Trimmed<S> {
+	fn into_desc(self) -> Self::Desc {
+		self.0.into()
+	}
+}
+
+impl<T: PartialEq + Clone + Debug> FromDesc for Leaf<T> {
+	fn from_desc(desc: Self::Desc) -> Self {
+		Leaf(desc)
+	}
+}
+
+impl<T: PartialEq + Clone + Debug> IntoDesc for Leaf<T> {
+	fn into_desc(self) -> Self::Desc {
+		self.0
+	}
+}
+
+impl<T: FromDesc> FromDesc for Option<T> {
+	fn from_desc(desc: Self::Desc) -> Self {
+		desc.map(T::from_desc)
+	}
+}
+
+impl<T: IntoDesc> IntoDesc for Option<T> {
+	fn into_desc(self) -> Self::Desc {
+		self.map(T::into_desc)
+	}
+}
+
+impl<T: FromDesc> FromDesc for Box<T> {
+	fn from_desc(desc: Self::Desc) -> Self {
+		Box::new(T::from_desc(desc))
+	}
+}
+
+impl<T: IntoDesc> IntoDesc for Box<T> {
+	fn into_desc(self) -> Self::Desc {
+		(*self).into_desc()
+	}
+}
+
+impl<T: FromDesc> FromDesc for Range<T> {
+	fn from_desc(desc: Self::Desc) -> Self {
+		T::from_desc(desc.start)..T::from_desc(desc.end)
+	}
+}
+
+impl<T: IntoDesc> IntoDesc for Range<T> {
+	fn into_desc(self) -> Self::Desc {
+		self.start.into_desc()..self.end.into_desc()
+	}
+}
+
+impl<T: FromDesc> FromDesc for RangeInclusive<T> {
+	fn from_desc(desc: Self::Desc) -> Self {
+		let (start, end) = desc.into_inner();
+		T::from_desc(start)..=T::from_desc(end)
+	}
+}
+
+impl<T: IntoDesc> IntoDesc for RangeInclusive<T> {
+	fn into_desc(self) -> Self::Desc {
+		let (start, end) = self.into_inner();
+		start.into_desc()..=end.into_desc()
+	}
+}
+
+impl<T: FromDesc> FromDesc for RefCell<T> {
+	fn from_desc(desc: Self::Desc) -> Self {
+		RefCell::new(T::from_desc(desc))
+	}
+}
+
+impl<T: IntoDesc> IntoDesc for RefCell<T> {
+	fn into_desc(self) -> Self::Desc {
+		self.into_inner().into_desc()
+	}
+}
+
+macro_rules! impl_roundtrip_for_atomic {
+	($($atomic:ident),*) => {
+		$(
+			impl FromDesc for $atomic {
+				fn from_desc(desc: Self::Desc) -> Self {
+					$atomic::new(desc)
+				}
+			}
+
+			impl IntoDesc for $atomic {
+				fn into_desc(self) -> Self::Desc {
+					self.into_inner()
+				}
+			}
+		)*
+	};
+}
+
+impl_roundtrip_for_atomic!(
+	AtomicBool,
+	AtomicU8,
+	AtomicI8,
+	AtomicU16,
+	AtomicI16,
+	AtomicU32,
+	AtomicI32,
+	AtomicU64,
+	AtomicI64,
+	AtomicUsize,
+	AtomicIsize
+);
+
+impl<T: ?Sized> FromDesc for PhantomData<T> {
+	fn from_desc(_desc: Self::Desc) -> Self {
+		PhantomData
+	}
+}
+
+impl<T: ?Sized> IntoDesc for PhantomData<T> {
+	fn into_desc(self) -> Self::Desc {}
+}
+
+impl FromDesc for PhantomPinned {
+	fn from_desc(_desc: Self::Desc) -> Self {
+		PhantomPinned
+	}
+}
+
+impl IntoDesc for PhantomPinned {
+	fn into_desc(self) -> Self::Desc {}
+}
+
+impl<T: FromDesc, const N: usize> FromDesc for [T; N] {
+	fn from_desc(desc: Self::Desc) -> Self {
+		desc.map(T::from_desc)
+	}
+}
+
+impl<T: IntoDesc, const N: usize> IntoDesc for [T; N] {
+	fn into_desc(self) -> Self::Desc {
+		self.map(T::into_desc)
+	}
+}
+
+impl<Value: PartialEq + FromDesc> FromDesc for Vec<Value> {
+	fn from_desc(desc: Self::Desc) -> Self {
+		desc.into_iter().map(Value::from_desc).collect()
+	}
+}
+
+impl<Value: PartialEq + IntoDesc> IntoDesc for Vec<Value> {
+	fn into_desc(self) -> Self::Desc {
+		self.into_iter().map(Value::into_desc).collect()
+	}
+}
+
+impl<Value: Ord + FromDesc> FromDesc for BTreeSet<Value> {
+	fn from_desc(desc: Self::Desc) -> Self {
+		desc.into_iter().map(Value::from_desc).collect()
+	}
+}
+
+impl<Value: Ord + IntoDesc> IntoDesc for BTreeSet<Value> {
+	fn into_desc(self) -> Self::Desc {
+		self.into_iter().map(Value::into_desc).collect()
+	}
+}
+
+impl<Value: std::hash::Hash + Ord + FromDesc> FromDesc for HashSet<Value> {
+	fn from_desc(desc: Self::Desc) -> Self {
+		desc.into_iter().map(Value::from_desc).collect()
+	}
+}
+
+impl<Value: std::hash::Hash + Ord + IntoDesc> IntoDesc for HashSet<Value> {
+	fn into_desc(self) -> Self::Desc {
+		self.into_iter().map(Value::into_desc).collect()
+	}
+}
+
+impl<Key: Ord + Clone + Debug, Value: FromDesc> FromDesc for BTreeMap<Key, Value> {
+	fn from_desc(desc: Self::Desc) -> Self {
+		desc.into_iter().map(|(k, v)| (k, Value::from_desc(v))).collect()
+	}
+}
+
+impl<Key: Ord + Clone + Debug, Value: IntoDesc> IntoDesc for BTreeMap<Key, Value> {
+	fn into_desc(self) -> Self::Desc {
+		self.into_iter().map(|(k, v)| (k, v.into_desc())).collect()
+	}
+}
+
+impl<Key: std::hash::Hash + Ord + Clone + Debug, Value: FromDesc> FromDesc for HashMap<Key, Value> {
+	fn from_desc(desc: Self::Desc) -> Self {
+		desc.into_iter().map(|(k, v)| (k, Value::from_desc(v))).collect()
+	}
+}
+
+impl<Key: std::hash::Hash + Ord + Clone + Debug, Value: IntoDesc> IntoDesc for HashMap<Key, Value> {
+	fn into_desc(self) -> Self::Desc {
+		self.into_iter().map(|(k, v)| (k, v.into_desc())).collect()
+	}
+}
+
+macro_rules! impl_roundtrip_for_tuples {
+	($a:ident $x:ident) => {
+		impl<$a: FromDesc> FromDesc for ($a,) {
+			fn from_desc(desc: Self::Desc) -> Self {
+				($a::from_desc(desc),)
+			}
+		}
+
+		impl<$a: IntoDesc> IntoDesc for ($a,) {
+			fn into_desc(self) -> Self::Desc {
+				self.0.into_desc()
+			}
+		}
+	};
+	($a:ident $x:ident, $($as:ident $xs:ident),+) => {
+		impl<$a: FromDesc, $($as: FromDesc),+> FromDesc for ($a, $($as),+) {
+			fn from_desc(desc: Self::Desc) -> Self {
+				let ($x, $($xs),+) = desc;
+				($a::from_desc($x), $($as::from_desc($xs)),+)
+			}
+		}
+
+		impl<$a: IntoDesc, $($as: IntoDesc),+> IntoDesc for ($a, $($as),+) {
+			fn into_desc(self) -> Self::Desc {
+				let ($x, $($xs),+) = self;
+				($x.into_desc(), $($xs.into_desc()),+)
+			}
+		}
+
+		impl_roundtrip_for_tuples!($($as $xs),+);
+	};
+}
+
+impl_roundtrip_for_tuples!(A1 a1, A2 a2, A3 a3, A4 a4, A5 a5, A6 a6, A7 a7, A8 a8, A9 a9, A10 a10, A11 a11, A12 a12);
@@ -1,263 +1,172 @@
 // use serde;
 
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::num::{
+	NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128, NonZeroU16, NonZeroU32,
+	NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+use std::time::{Duration, SystemTime};
+
+use crate::float::{f32_unchanged, f64_unchanged};
 use crate::types::{Changed, Comparable};
 
-impl Comparable for () {
-	type Desc = ();
-
-	fn describe(&self) -> Self::Desc {
-		*self
-	}
-
-	type Change = ();
-
-	fn comparison(&self, _other: &Self) -> Changed<Self::Change> {
-		Changed::Unchanged
-	}
-}
-
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(PartialEq, Debug)]
-pub struct BoolChange(pub bool, pub bool);
-
-impl Comparable for bool {
-	type Desc = bool;
-
-	fn describe(&self) -> Self::Desc {
-		*self
-	}
-
-	type Change = BoolChange;
-
-	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
-		if self != other {
-			Changed::Changed(BoolChange(*self, *other))
-		} else {
-			Changed::Unchanged
+/// Implement [`Comparable`] for types whose values are compared as a whole,
+/// as the scalars are. Each type is described by its own value, and its
+/// change is a new struct, named after the `=>`, holding the values before
+/// and after. Any attributes given first are applied to each of these
+/// structs. The types must implement `Copy`, `PartialEq` and `Debug`.
+///
+/// Along with [`Comparable`], this implements the traits that the scalars
+/// implement: [`Apply`], [`FromDesc`], [`IntoDesc`] and [`HasSchema`] for the
+/// type, and [`DisplayChange`] and [`ToTree`] for its change.
+///
+/// ```
+/// # use comparable::*;
+/// #[derive(Clone, Copy, PartialEq, Debug)]
+/// struct Celsius(i16);
+///
+/// impl_comparable_for_leaf!(Celsius => CelsiusChange);
+///
+/// assert_eq!(Celsius(20).comparison(&Celsius(21)), Changed::Changed(CelsiusChange(Celsius(20), Celsius(21))));
+/// ```
+///
+/// [`Comparable`]: crate::Comparable
+/// [`Apply`]: crate::lens::Apply
+/// [`FromDesc`]: crate::roundtrip::FromDesc
+/// [`IntoDesc`]: crate::roundtrip::IntoDesc
+/// [`HasSchema`]: crate::schema::HasSchema
+/// [`DisplayChange`]: crate::display::DisplayChange
+/// [`ToTree`]: crate::tree::ToTree
+#[macro_export]
+macro_rules! impl_comparable_for_leaf {
+	(@leaf [$($attrs:tt)*]) => {};
+	(@leaf [$($attrs:tt)*] #[$attr:meta] $($rest:tt)*) => {
+		$crate::impl_comparable_for_leaf!(@leaf [$($attrs)* #[$attr]] $($rest)*);
+	};
+	(@leaf [$($attrs:tt)*] $ty:ty => $change:ident $(, $($rest:tt)*)?) => {
+		$($attrs)*
+		#[derive(PartialEq, Debug)]
+		pub struct $change(pub $ty, pub $ty);
+
+		impl $crate::Comparable for $ty {
+			type Desc = $ty;
+
+			fn describe(&self) -> Self::Desc {
+				*self
+			}
+
+			type Change = $change;
+
+			fn comparison(&self, other: &Self) -> $crate::Changed<Self::Change> {
+				if self != other {
+					$crate::Changed::Changed($change(*self, *other))
+				} else {
+					$crate::Changed::Unchanged
+				}
+			}
+
+			fn fingerprint(&self) -> Option<u64> {
+				#[allow(unused_imports)]
+				use $crate::derive_support::{ByHash, NoHash};
+				(&$crate::derive_support::Fingerprint(self)).fingerprint()
+			}
 		}
-	}
-}
-
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(PartialEq, Debug)]
-pub struct U8Change(pub u8, pub u8);
-
-impl Comparable for u8 {
-	type Desc = u8;
-
-	fn describe(&self) -> Self::Desc {
-		*self
-	}
 
-	type Change = U8Change;
+		impl $crate::lens::Apply for $ty {
+			fn apply(&mut self, change: Self::Change) {
+				*self = change.1;
+			}
 
-	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
-		if self != other {
-			Changed::Changed(U8Change(*self, *other))
-		} else {
-			Changed::Unchanged
+			fn verify(&self, change: &Self::Change) -> Result<(), $crate::lens::ConflictError> {
+				$crate::lens::expect_current(self, &change.0)
+			}
 		}
-	}
-}
 
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(PartialEq, Debug)]
-pub struct I8Change(pub i8, pub i8);
-
-impl Comparable for i8 {
-	type Desc = i8;
-
-	fn describe(&self) -> Self::Desc {
-		*self
-	}
-
-	type Change = I8Change;
-
-	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
-		if self != other {
-			Changed::Changed(I8Change(*self, *other))
-		} else {
-			Changed::Unchanged
+		impl $crate::roundtrip::FromDesc for $ty {
+			fn from_desc(desc: Self::Desc) -> Self {
+				desc
+			}
 		}
-	}
-}
-
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(PartialEq, Debug)]
-
-pub struct U16Change(pub u16, pub u16);
 
-impl Comparable for u16 {
-	type Desc = u16;
-
-	fn describe(&self) -> Self::Desc {
-		*self
-	}
-
-	type Change = U16Change;
-
-	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
-		if self != other {
-			Changed::Changed(U16Change(*self, *other))
-		} else {
-			Changed::Unchanged
+		impl $crate::roundtrip::IntoDesc for $ty {
+			fn into_desc(self) -> Self::Desc {
+				self
+			}
 		}
-	}
-}
 
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(PartialEq, Debug)]
-pub struct I16Change(pub i16, pub i16);
-
-impl Comparable for i16 {
-	type Desc = i16;
-
-	fn describe(&self) -> Self::Desc {
-		*self
-	}
-
-	type Change = I16Change;
-
-	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
-		if self != other {
-			Changed::Changed(I16Change(*self, *other))
-		} else {
-			Changed::Unchanged
+		impl $crate::schema::HasSchema for $ty {
+			fn schema() -> $crate::schema::Schema {
+				$crate::schema::Schema::Scalar(stringify!($ty).to_string())
+			}
 		}
-	}
-}
-
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(PartialEq, Debug)]
-pub struct U32Change(pub u32, pub u32);
-
-impl Comparable for u32 {
-	type Desc = u32;
-
-	fn describe(&self) -> Self::Desc {
-		*self
-	}
-
-	type Change = U32Change;
 
-	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
-		if self != other {
-			Changed::Changed(U32Change(*self, *other))
-		} else {
-			Changed::Unchanged
+		impl $crate::display::DisplayChange for $change {
+			fn display_changes(&self, path: &$crate::location::Path, lines: &mut Vec<String>) {
+				$crate::display::push_replaced(lines, path, &self.0, &self.1)
+			}
 		}
-	}
-}
-
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(PartialEq, Debug)]
-pub struct I32Change(pub i32, pub i32);
-
-impl Comparable for i32 {
-	type Desc = i32;
-
-	fn describe(&self) -> Self::Desc {
-		*self
-	}
-
-	type Change = I32Change;
-
-	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
-		if self != other {
-			Changed::Changed(I32Change(*self, *other))
-		} else {
-			Changed::Unchanged
-		}
-	}
-}
-
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(PartialEq, Debug)]
-pub struct U64Change(pub u64, pub u64);
-
-impl Comparable for u64 {
-	type Desc = u64;
-
-	fn describe(&self) -> Self::Desc {
-		*self
-	}
 
-	type Change = U64Change;
-
-	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
-		if self != other {
-			Changed::Changed(U64Change(*self, *other))
-		} else {
-			Changed::Unchanged
+		impl $crate::tree::ToTree for $change {
+			fn to_tree(&self) -> $crate::tree::ChangeTree {
+				$crate::tree::ChangeTree::modified(&self.0, &self.1)
+			}
 		}
-	}
-}
 
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(PartialEq, Debug)]
-
-pub struct I64Change(pub i64, pub i64);
-
-impl Comparable for i64 {
-	type Desc = i64;
-
-	fn describe(&self) -> Self::Desc {
-		*self
-	}
-
-	type Change = I64Change;
-
-	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
-		if self != other {
-			Changed::Changed(I64Change(*self, *other))
-		} else {
-			Changed::Unchanged
-		}
-	}
+		$crate::impl_comparable_for_leaf!(@leaf [$($attrs)*] $($($rest)*)?);
+	};
+	($($rest:tt)*) => {
+		$crate::impl_comparable_for_leaf!(@leaf [] $($rest)*);
+	};
 }
 
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(PartialEq, Debug)]
-pub struct UsizeChange(pub usize, pub usize);
-
-impl Comparable for usize {
-	type Desc = usize;
+impl Comparable for () {
+	type Desc = ();
 
 	fn describe(&self) -> Self::Desc {
 		*self
 	}
 
-	type Change = UsizeChange;
+	type Change = ();
 
-	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
-		if self != other {
-			Changed::Changed(UsizeChange(*self, *other))
-		} else {
-			Changed::Unchanged
-		}
+	fn comparison(&self, _other: &Self) -> Changed<Self::Change> {
+		Changed::Unchanged
 	}
 }
 
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(PartialEq, Debug)]
-pub struct IsizeChange(pub isize, pub isize);
-
-impl Comparable for isize {
-	type Desc = isize;
-
-	fn describe(&self) -> Self::Desc {
-		*self
-	}
-
-	type Change = IsizeChange;
-
-	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
-		if self != other {
-			Changed::Changed(IsizeChange(*self, *other))
-		} else {
-			Changed::Unchanged
-		}
-	}
+impl_comparable_for_leaf! {
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	bool => BoolChange,
+	u8 => U8Change,
+	i8 => I8Change,
+	u16 => U16Change,
+	i16 => I16Change,
+	u32 => U32Change,
+	i32 => I32Change,
+	u64 => U64Change,
+	i64 => I64Change,
+	u128 => U128Change,
+	i128 => I128Change,
+	usize => UsizeChange,
+	isize => IsizeChange,
+	char => CharChange,
+	NonZeroU8 => NonZeroU8Change,
+	NonZeroI8 => NonZeroI8Change,
+	NonZeroU16 => NonZeroU16Change,
+	NonZeroI16 => NonZeroI16Change,
+	NonZeroU32 => NonZeroU32Change,
+	NonZeroI32 => NonZeroI32Change,
+	NonZeroU64 => NonZeroU64Change,
+	NonZeroI64 => NonZeroI64Change,
+	NonZeroU128 => NonZeroU128Change,
+	NonZeroI128 => NonZeroI128Change,
+	NonZeroUsize => NonZeroUsizeChange,
+	NonZeroIsize => NonZeroIsizeChange,
+	Duration => DurationChange,
+	SystemTime => SystemTimeChange,
+	IpAddr => IpAddrChange,
+	Ipv4Addr => Ipv4AddrChange,
+	Ipv6Addr => Ipv6AddrChange,
+	SocketAddr => SocketAddrChange,
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -273,9 +182,8 @@ impl Comparable for f32 {
 
 	type Change = F32Change;
 
-	#[allow(clippy::float_cmp)]
 	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
-		if self != other {
+		if !f32_unchanged(*self, *other) {
 			Changed::Changed(F32Change(*self, *other))
 		} else {
 			Changed::Unchanged
@@ -296,34 +204,11 @@ impl Comparable for f64 {
 
 	type Change = F64Change;
 
-	#[allow(clippy::float_cmp)]
 	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
-		if self != other {
+		if !f64_unchanged(*self, *other) {
 			Changed::Changed(F64Change(*self, *other))
 		} else {
 			Changed::Unchanged
 		}
 	}
 }
-
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(PartialEq, Debug)]
-pub struct CharChange(pub char, pub char);
-
-impl Comparable for char {
-	type Desc = char;
-
-	fn describe(&self) -> Self::Desc {
-		*self
-	}
-
-	type Change = CharChange;
-
-	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
-		if self != other {
-			Changed::Changed(CharChange(*self, *other))
-		} else {
-			Changed::Unchanged
-		}
-	}
-}
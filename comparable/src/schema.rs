@@ -0,0 +1,361 @@
+//! Describing the shape of types, to detect when two builds disagree about it.
+//!
+//! The changes reported by one build of a program can only be understood by
+//! another if both agree on the shape of the types involved: the names and
+//! types of their fields, and the variants of their enums. A [`Schema`]
+//! records that shape, and comparing the schemas of two builds, such as one
+//! received from a peer at startup with the local one, reports each field or
+//! variant that was added, removed or changed in type.
+//!
+//! Schemas are provided by the [`HasSchema`] trait, which is implemented for
+//! the standard types supported by [`Comparable`], and by
+//! `#[derive(Comparable)]` for types marked with `#[comparable_schema]`.
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! use comparable::location::{Path, Segment};
+//! use comparable::schema::{HasSchema, SchemaChange};
+//!
+//! mod v1 {
+//!     # use comparable_derive::*;
+//!     #[derive(Comparable)]
+//!     #[comparable_schema]
+//!     pub struct Account {
+//!         pub id: u64,
+//!         pub owner: String,
+//!     }
+//! }
+//!
+//! #[derive(Comparable)]
+//! #[comparable_schema]
+//! struct Account {
+//!     id: u32,
+//!     owner: String,
+//!     email: Option<String>,
+//! }
+//!
+//! assert_eq!(
+//!     v1::Account::schema().comparison(&Account::schema()),
+//!     Changed::Changed(vec![
+//!         SchemaChange::Changed(
+//!             Path::from(vec![Segment::Field("id".to_string())]),
+//!             u64::schema(),
+//!             u32::schema(),
+//!         ),
+//!         SchemaChange::FieldAdded(
+//!             Path::from(vec![Segment::Field("email".to_string())]),
+//!             <Option<String>>::schema(),
+//!         ),
+//!     ])
+//! );
+//! ```
+//!
+//! [`Comparable`]: crate::Comparable
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::leaf::Leaf;
+use crate::location::{Path, Segment};
+use crate::types::{Changed, Comparable, Redacted};
+
+/// The shape of a type.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Schema {
+	/// A type without further structure, such as `u32` or `String`, by name.
+	Scalar(String),
+	/// An optional value.
+	Option(Box<Schema>),
+	/// A sequence of elements, such as a `Vec` or an array.
+	Sequence(Box<Schema>),
+	/// A set of members.
+	Set(Box<Schema>),
+	/// A map from keys to values.
+	Map(Box<Schema>, Box<Schema>),
+	/// A tuple of the given elements.
+	Tuple(Vec<Schema>),
+	/// A struct with the given fields, which for tuple structs are named by
+	/// their index.
+	Struct { name: String, fields: Vec<Field> },
+	/// An enum with the given variants.
+	Enum { name: String, variants: Vec<Variant> },
+	/// A reference to a struct or enum, by name, from within its own schema.
+	Recursive(String),
+}
+
+/// A field of a struct or enum variant.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Field {
+	pub name: String,
+	pub schema: Schema,
+}
+
+/// A variant of an enum.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Variant {
+	pub name: String,
+	pub fields: Vec<Field>,
+}
+
+impl Field {
+	pub fn new(name: &str, schema: Schema) -> Self {
+		Field { name: name.to_string(), schema }
+	}
+}
+
+impl Variant {
+	pub fn new(name: &str, fields: Vec<Field>) -> Self {
+		Variant { name: name.to_string(), fields }
+	}
+}
+
+/// Types whose shape can be described by a [`Schema`].
+pub trait HasSchema {
+	fn schema() -> Schema;
+}
+
+thread_local! {
+	static BUILDING: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+// Removes the type whose schema was being built, even on panic.
+struct Finish;
+
+impl Drop for Finish {
+	fn drop(&mut self) {
+		BUILDING.with(|building| building.borrow_mut().pop());
+	}
+}
+
+// Called by derived implementations of `HasSchema`, through
+// `derive_support::named_schema`, so that a type appearing within its own
+// schema is described by `Schema::Recursive`.
+#[doc(hidden)]
+pub fn named<T: ?Sized>(name: &str, build: impl FnOnce() -> Schema) -> Schema {
+	let key = std::any::type_name::<T>();
+	if BUILDING.with(|building| building.borrow().contains(&key)) {
+		return Schema::Recursive(name.to_string());
+	}
+	BUILDING.with(|building| building.borrow_mut().push(key));
+	let _finish = Finish;
+	build()
+}
+
+/// A difference between two schemas, at the path of the field or variant
+/// concerned, written as in a [`FieldMask`](crate::mask::FieldMask). The
+/// fields of an enum variant are found within the variant's name.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum SchemaChange {
+	/// A field was added, with the given schema.
+	FieldAdded(Path, Schema),
+	/// A field was removed, which had the given schema.
+	FieldRemoved(Path, Schema),
+	/// A variant was added.
+	VariantAdded(Path),
+	/// A variant was removed.
+	VariantRemoved(Path),
+	/// The type found at a path changed, from and to the given schemas.
+	Changed(Path, Schema, Schema),
+}
+
+/// Fields and variants are matched by name, so that reordering them is not
+/// counted as a change. The schemas of collections are compared element by
+/// element, with any changes reported at the collection's own path.
+impl Comparable for Schema {
+	type Desc = Schema;
+
+	fn describe(&self) -> Self::Desc {
+		self.clone()
+	}
+
+	type Change = Vec<SchemaChange>;
+
+	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
+		let mut changes = Vec::new();
+		compare_schemas(&mut Vec::new(), self, other, &mut changes);
+		Changed::from_changes(changes)
+	}
+}
+
+fn at(path: &[Segment], name: &str) -> Path {
+	let mut segments = path.to_vec();
+	segments.push(Segment::Field(name.to_string()));
+	Path::from(segments)
+}
+
+fn compare_schemas(path: &mut Vec<Segment>, before: &Schema, after: &Schema, changes: &mut Vec<SchemaChange>) {
+	match (before, after) {
+		(Schema::Option(x), Schema::Option(y))
+		| (Schema::Sequence(x), Schema::Sequence(y))
+		| (Schema::Set(x), Schema::Set(y)) => compare_schemas(path, x, y, changes),
+		(Schema::Map(xk, xv), Schema::Map(yk, yv)) => {
+			compare_schemas(path, xk, yk, changes);
+			compare_schemas(path, xv, yv, changes);
+		}
+		(Schema::Tuple(xs), Schema::Tuple(ys)) if xs.len() == ys.len() => {
+			for (i, (x, y)) in xs.iter().zip(ys).enumerate() {
+				path.push(Segment::Field(i.to_string()));
+				compare_schemas(path, x, y, changes);
+				path.pop();
+			}
+		}
+		(Schema::Struct { name: x, fields: xs }, Schema::Struct { name: y, fields: ys }) if x == y => {
+			compare_fields(path, xs, ys, changes)
+		}
+		(Schema::Enum { name: x, variants: xs }, Schema::Enum { name: y, variants: ys }) if x == y => {
+			for y in ys {
+				path.push(Segment::Field(y.name.clone()));
+				match xs.iter().find(|x| x.name == y.name) {
+					Some(x) => compare_fields(path, &x.fields, &y.fields, changes),
+					None => changes.push(SchemaChange::VariantAdded(Path::from(path.clone()))),
+				}
+				path.pop();
+			}
+			changes.extend(
+				xs.iter()
+					.filter(|x| !ys.iter().any(|y| y.name == x.name))
+					.map(|x| SchemaChange::VariantRemoved(at(path, &x.name))),
+			);
+		}
+		// Only the shape of a type outside of its own schema is compared, where
+		// it is described in full.
+		(Schema::Recursive(x), Schema::Recursive(y)) if x == y => (),
+		_ if before != after => {
+			changes.push(SchemaChange::Changed(Path::from(path.clone()), before.clone(), after.clone()))
+		}
+		_ => (),
+	}
+}
+
+fn compare_fields(path: &mut Vec<Segment>, before: &[Field], after: &[Field], changes: &mut Vec<SchemaChange>) {
+	for y in after {
+		match before.iter().find(|x| x.name == y.name) {
+			Some(x) => {
+				path.push(Segment::Field(y.name.clone()));
+				compare_schemas(path, &x.schema, &y.schema, changes);
+				path.pop();
+			}
+			None => changes.push(SchemaChange::FieldAdded(at(path, &y.name), y.schema.clone())),
+		}
+	}
+	changes.extend(
+		before
+			.iter()
+			.filter(|x| !after.iter().any(|y| y.name == x.name))
+			.map(|x| SchemaChange::FieldRemoved(at(path, &x.name), x.schema.clone())),
+	);
+}
+
+macro_rules! impl_scalar_schema {
+	($($ty:ty => $name:expr),* $(,)?) => {
+		$(
+			impl HasSchema for $ty {
+				fn schema() -> Schema {
+					Schema::Scalar($name.to_string())
+				}
+			}
+		)*
+	};
+}
+
+impl_scalar_schema! {
+	() => "()",
+	f32 => "f32",
+	f64 => "f64",
+	String => "String",
+	&str => "str",
+	PathBuf => "PathBuf",
+	Redacted => "Redacted",
+}
+
+/// Values compared as a whole are described by the name of their type.
+impl<T> HasSchema for Leaf<T> {
+	fn schema() -> Schema {
+		Schema::Scalar(std::any::type_name::<T>().to_string())
+	}
+}
+
+impl<T: HasSchema> HasSchema for Option<T> {
+	fn schema() -> Schema {
+		Schema::Option(Box::new(T::schema()))
+	}
+}
+
+impl<T: HasSchema> HasSchema for Vec<T> {
+	fn schema() -> Schema {
+		Schema::Sequence(Box::new(T::schema()))
+	}
+}
+
+impl<T: HasSchema, const N: usize> HasSchema for [T; N] {
+	fn schema() -> Schema {
+		Schema::Sequence(Box::new(T::schema()))
+	}
+}
+
+impl<T: HasSchema> HasSchema for BTreeSet<T> {
+	fn schema() -> Schema {
+		Schema::Set(Box::new(T::schema()))
+	}
+}
+
+impl<T: HasSchema> HasSchema for HashSet<T> {
+	fn schema() -> Schema {
+		Schema::Set(Box::new(T::schema()))
+	}
+}
+
+impl<K: HasSchema, V: HasSchema> HasSchema for BTreeMap<K, V> {
+	fn schema() -> Schema {
+		Schema::Map(Box::new(K::schema()), Box::new(V::schema()))
+	}
+}
+
+impl<K: HasSchema, V: HasSchema> HasSchema for HashMap<K, V> {
+	fn schema() -> Schema {
+		Schema::Map(Box::new(K::schema()), Box::new(V::schema()))
+	}
+}
+
+// Pointers are compared by the values they point to, and so share their
+// schemas.
+macro_rules! impl_pointer_schema {
+	($($ptr:ident),*) => {
+		$(
+			impl<T: HasSchema> HasSchema for $ptr<T> {
+				fn schema() -> Schema {
+					T::schema()
+				}
+			}
+		)*
+	};
+}
+
+impl_pointer_schema!(Box, Rc, Arc, RefCell);
+
+macro_rules! impl_tuple_schema {
+	($a:ident) => {
+		impl<$a: HasSchema> HasSchema for ($a,) {
+			fn schema() -> Schema {
+				Schema::Tuple(vec![$a::schema()])
+			}
+		}
+	};
+	($b:ident, $($a:ident),+) => {
+		impl<$b: HasSchema, $($a: HasSchema),+> HasSchema for ($b, $($a),+) {
+			fn schema() -> Schema {
+				Schema::Tuple(vec![$b::schema(), $($a::schema()),+])
+			}
+		}
+		impl_tuple_schema!($($a),+);
+	};
+}
+
+impl_tuple_schema!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12);
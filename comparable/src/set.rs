@@ -2,6 +2,9 @@ use std::collections::{BTreeSet, HashSet};
 use std::fmt::Debug;
 // use serde;
 
+use crate::diff::{align, pair_similar, DiffAlgorithm};
+use crate::location::{Path, Segment};
+use crate::options::diff_options;
 use crate::types::{Changed, Comparable};
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -10,6 +13,14 @@ pub enum VecChange<Desc, Change> {
 	Added(usize, Desc),
 	Changed(usize, Change),
 	Removed(usize, Desc),
+	/// The element at index `from` is unchanged at index `to`. This is only
+	/// reported when enabled by [`DiffOptions::sequence_moves`].
+	///
+	/// [`DiffOptions::sequence_moves`]: crate::DiffOptions::sequence_moves
+	Moved {
+		from: usize,
+		to: usize,
+	},
 }
 
 impl<Value: PartialEq + Comparable> Comparable for Vec<Value> {
@@ -22,6 +33,14 @@ impl<Value: PartialEq + Comparable> Comparable for Vec<Value> {
 	type Change = Vec<VecChange<Value::Desc, Value::Change>>;
 
 	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
+		let options = diff_options();
+		if options.sequence_moves {
+			return compare_with_moves(self, other);
+		}
+		if options.sequence_algorithm.is_some() || options.sequence_similarity.is_some() {
+			let algorithm = options.sequence_algorithm.unwrap_or(DiffAlgorithm::Myers);
+			return compare_aligned(self, other, algorithm, options.sequence_similarity);
+		}
 		let mut changes = Vec::new();
 		let other_len = other.len();
 		for i in 0..self.len() {
@@ -37,12 +56,114 @@ impl<Value: PartialEq + Comparable> Comparable for Vec<Value> {
 				changes.push(VecChange::Added(i, other[i].describe()));
 			}
 		}
-		if changes.is_empty() {
-			Changed::Unchanged
-		} else {
-			Changed::Changed(changes)
+		Changed::from_changes(changes)
+	}
+
+	fn differs(&self, other: &Self) -> bool {
+		self.len() != other.len() || self.iter().zip(other).any(|(x, y)| x.differs(y))
+	}
+
+	fn count_changes(&self, other: &Self) -> usize {
+		let changed: usize = self.iter().zip(other).map(|(x, y)| x.count_changes(y)).sum();
+		changed + self.len().abs_diff(other.len())
+	}
+
+	fn first_difference(&self, other: &Self) -> Option<Path> {
+		let changed = self
+			.iter()
+			.zip(other)
+			.enumerate()
+			.find_map(|(index, (x, y))| x.first_difference(y).map(|path| path.within(Segment::Index(index))));
+		changed.or_else(|| {
+			let index = self.len().min(other.len());
+			(self.len() != other.len()).then(|| Path::default().within(Segment::Index(index)))
+		})
+	}
+}
+
+// Elements unchanged at the same index are left alone, and each remaining
+// element of `other` is matched with the first remaining element of `before`
+// that it is unchanged from. Elements left unmatched at the same index in both
+// are changed, and others are added or removed.
+fn compare_with_moves<Value: Comparable>(
+	before: &[Value],
+	after: &[Value],
+) -> Changed<Vec<VecChange<Value::Desc, Value::Change>>> {
+	let kept: Vec<bool> = (0..before.len()).map(|i| i < after.len() && !before[i].differs(&after[i])).collect();
+	let mut matched = kept.clone();
+	let mut changes = Vec::new();
+	let mut unmatched = Vec::new();
+	for (to, value) in after.iter().enumerate() {
+		if kept.get(to) == Some(&true) {
+			continue;
+		}
+		match (0..before.len()).find(|from| !matched[*from] && !before[*from].differs(value)) {
+			Some(from) => {
+				matched[from] = true;
+				changes.push(VecChange::Moved { from, to });
+			}
+			None => unmatched.push(to),
+		}
+	}
+	let mut removed = Vec::new();
+	for (from, value) in before.iter().enumerate() {
+		if !matched[from] {
+			match unmatched.iter().position(|to| *to == from) {
+				Some(position) => {
+					unmatched.remove(position);
+					if let Changed::Changed(change) = value.comparison(&after[from]) {
+						changes.push(VecChange::Changed(from, change));
+					}
+				}
+				None => removed.push(VecChange::Removed(from, value.describe())),
+			}
+		}
+	}
+	changes.extend(unmatched.into_iter().map(|to| VecChange::Added(to, after[to].describe())));
+	changes.sort_by_key(|change| match change {
+		VecChange::Added(i, _)
+		| VecChange::Changed(i, _)
+		| VecChange::Removed(i, _)
+		| VecChange::Moved { to: i, .. } => *i,
+	});
+	changes.extend(removed);
+	Changed::from_changes(changes)
+}
+
+// Between each pair of aligned elements, the elements of `before` and `after`
+// are paired up, either by offset or by similarity, and reported as changed,
+// and any left over are removed or added.
+fn compare_aligned<Value: Comparable>(
+	before: &[Value],
+	after: &[Value],
+	algorithm: DiffAlgorithm,
+	similarity: Option<f64>,
+) -> Changed<Vec<VecChange<Value::Desc, Value::Change>>> {
+	let mut changes = Vec::new();
+	let (mut i0, mut j0) = (0, 0);
+	for (i, j) in align(before, after, algorithm).into_iter().chain(Some((before.len(), after.len()))) {
+		let pairs = match similarity {
+			Some(threshold) => pair_similar(&before[i0..i], &after[j0..j], threshold),
+			None => (0..(i - i0).min(j - j0)).map(|k| (k, k)).collect(),
+		};
+		let (mut paired_from, mut paired_to) = (vec![false; i - i0], vec![false; j - j0]);
+		for (from, to) in &pairs {
+			paired_from[*from] = true;
+			paired_to[*to] = true;
+			if let Changed::Changed(change) = before[i0 + from].comparison(&after[j0 + to]) {
+				changes.push(VecChange::Changed(j0 + to, change));
+			}
 		}
+		changes.extend(
+			(i0..i)
+				.filter(|from| !paired_from[from - i0])
+				.map(|from| VecChange::Removed(from, before[from].describe())),
+		);
+		changes.extend((j0..j).filter(|to| !paired_to[to - j0]).map(|to| VecChange::Added(to, after[to].describe())));
+		i0 = i + 1;
+		j0 = j + 1;
 	}
+	Changed::from_changes(changes)
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -63,35 +184,16 @@ impl<Value: Ord + Comparable> Comparable for BTreeSet<Value> {
 
 	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
 		let mut changes = Vec::new();
-		changes.append(
-			&mut other
-				.iter()
-				.flat_map(|v| {
-					if self.contains(v) {
-						Changed::Unchanged
-					} else {
-						Changed::Changed(SetChange::Added(v.describe()))
-					}
-				})
-				.collect(),
-		);
-		changes.append(
-			&mut self
-				.iter()
-				.flat_map(|v| {
-					if !other.contains(v) {
-						Changed::Changed(SetChange::Removed(v.describe()))
-					} else {
-						Changed::Unchanged
-					}
-				})
-				.collect(),
-		);
-		if changes.is_empty() {
-			Changed::Unchanged
-		} else {
-			Changed::Changed(changes)
-		}
+		changes.extend(other.iter().filter(|v| !self.contains(v)).map(|v| SetChange::Added(v.describe())));
+		changes.extend(self.iter().filter(|v| !other.contains(v)).map(|v| SetChange::Removed(v.describe())));
+		Changed::from_changes(changes)
+	}
+	fn differs(&self, other: &Self) -> bool {
+		self.len() != other.len() || self.iter().any(|v| !other.contains(v))
+	}
+
+	fn count_changes(&self, other: &Self) -> usize {
+		self.iter().filter(|v| !other.contains(v)).count() + other.iter().filter(|v| !self.contains(v)).count()
 	}
 }
 
@@ -105,39 +207,22 @@ impl<Value: std::hash::Hash + Ord + Comparable> Comparable for HashSet<Value> {
 	type Change = Vec<SetChange<Value::Desc>>;
 
 	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
-		let mut changes = Vec::new();
-		let mut others = other.iter().collect::<Vec<&Value>>();
-		others.sort();
-		changes.append(
-			&mut others
-				.iter()
-				.flat_map(|v| {
-					if self.contains(v) {
-						Changed::Unchanged
-					} else {
-						Changed::Changed(SetChange::Added(v.describe()))
-					}
-				})
-				.collect(),
-		);
-		let mut selfs = self.iter().collect::<Vec<&Value>>();
-		selfs.sort();
-		changes.append(
-			&mut selfs
-				.iter()
-				.flat_map(|v| {
-					if !other.contains(v) {
-						Changed::Changed(SetChange::Removed(v.describe()))
-					} else {
-						Changed::Unchanged
-					}
-				})
-				.collect(),
-		);
-		if changes.is_empty() {
-			Changed::Unchanged
-		} else {
-			Changed::Changed(changes)
-		}
+		// Only the members reported are sorted, so that changes are given in a
+		// stable order.
+		let mut added = other.iter().filter(|v| !self.contains(v)).collect::<Vec<&Value>>();
+		added.sort();
+		let mut removed = self.iter().filter(|v| !other.contains(v)).collect::<Vec<&Value>>();
+		removed.sort();
+		let mut changes = Vec::with_capacity(added.len() + removed.len());
+		changes.extend(added.into_iter().map(|v| SetChange::Added(v.describe())));
+		changes.extend(removed.into_iter().map(|v| SetChange::Removed(v.describe())));
+		Changed::from_changes(changes)
+	}
+	fn differs(&self, other: &Self) -> bool {
+		self.len() != other.len() || self.iter().any(|v| !other.contains(v))
+	}
+
+	fn count_changes(&self, other: &Self) -> usize {
+		self.iter().filter(|v| !other.contains(v)).count() + other.iter().filter(|v| !self.contains(v)).count()
 	}
 }
@@ -0,0 +1,127 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::types::{Changed, Comparable};
+
+/// The description of a value behind a shared pointer, such as [`Rc`] or
+/// [`Arc`]. The first time a given allocation is described its value is
+/// described in full; every later occurrence of the same allocation, within
+/// the outermost shared pointer being described, is described as `Seen(n)`,
+/// where `n` counts the allocations described in full before it. This keeps
+/// shared subtrees from being described more than once, and allows cyclic
+/// structures to be described at all.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, Debug)]
+pub enum SharedDesc<Desc> {
+	Value(Desc),
+	Seen(usize),
+}
+
+#[derive(Default)]
+struct Visits<K> {
+	depth: usize,
+	seen: K,
+}
+
+thread_local! {
+	static DESCRIBING: RefCell<Visits<HashMap<usize, usize>>> = RefCell::new(Visits::default());
+	static COMPARING: RefCell<Visits<HashSet<(usize, usize)>>> = RefCell::new(Visits::default());
+}
+
+// Leaves the outermost shared pointer being visited, even on panic, and
+// forgets the allocations visited within it.
+struct Leave<K: 'static>(&'static std::thread::LocalKey<RefCell<Visits<K>>>, fn(&mut K));
+
+impl<K: 'static> Drop for Leave<K> {
+	fn drop(&mut self) {
+		let clear = self.1;
+		self.0.with(|visits| {
+			let mut visits = visits.borrow_mut();
+			visits.depth -= 1;
+			if visits.depth == 0 {
+				clear(&mut visits.seen);
+			}
+		});
+	}
+}
+
+fn describe_shared<T: Comparable + ?Sized>(address: usize, value: &T) -> SharedDesc<T::Desc> {
+	let seen = DESCRIBING.with(|visits| {
+		let mut visits = visits.borrow_mut();
+		visits.depth += 1;
+		let count = visits.seen.len();
+		match visits.seen.get(&address) {
+			Some(n) => Some(*n),
+			None => {
+				visits.seen.insert(address, count);
+				None
+			}
+		}
+	});
+	let _leave = Leave(&DESCRIBING, HashMap::clear);
+	match seen {
+		Some(n) => SharedDesc::Seen(n),
+		None => SharedDesc::Value(value.describe()),
+	}
+}
+
+// Two pointers to the same allocation are unchanged, as is any pair of
+// allocations already compared within the outermost shared pointer being
+// compared, which is what allows cyclic structures to be compared.
+fn compare_shared<T: Comparable + ?Sized>(addresses: (usize, usize), value: &T, other: &T) -> Changed<T::Change> {
+	if addresses.0 == addresses.1 {
+		return Changed::Unchanged;
+	}
+	let seen = COMPARING.with(|visits| {
+		let mut visits = visits.borrow_mut();
+		visits.depth += 1;
+		!visits.seen.insert(addresses)
+	});
+	let _leave = Leave(&COMPARING, HashSet::clear);
+	if seen {
+		Changed::Unchanged
+	} else {
+		value.comparison(other)
+	}
+}
+
+macro_rules! impl_comparable_for_shared {
+	($ptr:ident) => {
+		impl<T: Comparable + ?Sized> Comparable for $ptr<T> {
+			type Desc = SharedDesc<T::Desc>;
+
+			fn describe(&self) -> Self::Desc {
+				describe_shared($ptr::as_ptr(self) as *const u8 as usize, &**self)
+			}
+
+			type Change = T::Change;
+
+			fn comparison(&self, other: &Self) -> Changed<Self::Change> {
+				compare_shared(
+					($ptr::as_ptr(self) as *const u8 as usize, $ptr::as_ptr(other) as *const u8 as usize),
+					&**self,
+					&**other,
+				)
+			}
+		}
+	};
+}
+
+impl_comparable_for_shared!(Rc);
+impl_comparable_for_shared!(Arc);
+
+impl<T: Comparable + ?Sized> Comparable for RefCell<T> {
+	type Desc = T::Desc;
+
+	fn describe(&self) -> Self::Desc {
+		self.borrow().describe()
+	}
+
+	type Change = T::Change;
+
+	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
+		self.borrow().comparison(&*other.borrow())
+	}
+}
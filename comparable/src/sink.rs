@@ -0,0 +1,84 @@
+//! Sending changes into event pipelines.
+//!
+//! A [`ChangeSink`] is anywhere changes can be sent, such as the sending end
+//! of a channel, so that the changes found by [`Tracked::send_changes`] or by
+//! a [`Store`] subscription made with [`Store::subscribe_sink`] reach other
+//! threads or tasks as events. It is implemented for the senders of
+//! `std::sync::mpsc`, of `crossbeam-channel` with the `crossbeam` feature, and
+//! of `tokio::sync::broadcast` with the `tokio` feature, as well as for `Vec`,
+//! which collects them.
+//!
+//! ```
+//! # use comparable::*;
+//! use std::sync::mpsc;
+//!
+//! let (sender, receiver) = mpsc::channel();
+//! let mut tracked = Tracked::new(vec![1, 2]);
+//! tracked.push(3);
+//! assert_eq!(tracked.send_changes(&mut sender.clone()), Ok(true));
+//! assert_eq!(tracked.send_changes(&mut sender.clone()), Ok(false));
+//! assert_eq!(receiver.try_recv(), Ok(vec![VecChange::Added(2, 3)]));
+//! ```
+//!
+//! [`Tracked::send_changes`]: crate::Tracked::send_changes
+//! [`Store`]: crate::watch::Store
+//! [`Store::subscribe_sink`]: crate::watch::Store::subscribe_sink
+
+use std::convert::Infallible;
+use std::sync::mpsc;
+
+/// A destination for changes of type `C`.
+pub trait ChangeSink<C> {
+	/// Why a change could not be sent, such as the receiving end of a channel
+	/// having been dropped.
+	type Error;
+
+	fn send_change(&mut self, change: C) -> Result<(), Self::Error>;
+}
+
+impl<C> ChangeSink<C> for Vec<C> {
+	type Error = Infallible;
+
+	fn send_change(&mut self, change: C) -> Result<(), Self::Error> {
+		self.push(change);
+		Ok(())
+	}
+}
+
+impl<C> ChangeSink<C> for mpsc::Sender<C> {
+	type Error = mpsc::SendError<C>;
+
+	fn send_change(&mut self, change: C) -> Result<(), Self::Error> {
+		self.send(change)
+	}
+}
+
+/// Sending blocks while the channel is full.
+impl<C> ChangeSink<C> for mpsc::SyncSender<C> {
+	type Error = mpsc::SendError<C>;
+
+	fn send_change(&mut self, change: C) -> Result<(), Self::Error> {
+		self.send(change)
+	}
+}
+
+/// Sending blocks while a bounded channel is full.
+#[cfg(feature = "crossbeam")]
+impl<C> ChangeSink<C> for crossbeam_channel::Sender<C> {
+	type Error = crossbeam_channel::SendError<C>;
+
+	fn send_change(&mut self, change: C) -> Result<(), Self::Error> {
+		self.send(change)
+	}
+}
+
+/// Sending fails when there are no receivers, and otherwise never blocks;
+/// receivers that fall behind miss the oldest changes instead.
+#[cfg(feature = "tokio")]
+impl<C> ChangeSink<C> for tokio::sync::broadcast::Sender<C> {
+	type Error = tokio::sync::broadcast::error::SendError<C>;
+
+	fn send_change(&mut self, change: C) -> Result<(), Self::Error> {
+		self.send(change).map(|_| ())
+	}
+}
@@ -0,0 +1,244 @@
+//! Turning the changes to a struct into SQL `UPDATE` statements.
+//!
+//! When a struct mirrors a row of a table, its changes say exactly which
+//! columns need writing. With `#[comparable_sql(table = "users", key = "id")]`,
+//! `#[derive(Comparable)]` implements [`SqlChangeset`], which describes the
+//! parameterized statement updating only the changed columns of the row whose
+//! key is that of the value before the change. The statement is not run; it
+//! can be handed to a database driver, or logged for auditing. There is no
+//! statement when none of the columns changed.
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! use comparable::sql::{SqlChangeset, SqlValue};
+//!
+//! #[derive(Comparable)]
+//! #[comparable_sql(table = "users", key = "id")]
+//! struct User {
+//!     id: u64,
+//!     name: String,
+//!     email: Option<String>,
+//! }
+//!
+//! let before = User { id: 7, name: "Alice".to_string(), email: None };
+//! let after = User { id: 7, name: "Alice".to_string(), email: Some("alice@example.com".to_string()) };
+//! let update = before.sql_update(&before.comparison(&after).unwrap_or_default()).unwrap();
+//! assert_eq!(update.statement(), "UPDATE users SET email = ? WHERE id = ?");
+//! assert_eq!(
+//!     update.parameters(),
+//!     vec![&SqlValue::Text("alice@example.com".to_string()), &SqlValue::Unsigned(7)]
+//! );
+//! assert!(before.sql_update(&before.comparison(&before).unwrap_or_default()).is_none());
+//! ```
+//!
+//! With the `diesel` or `sqlx` features, the statement can be run by those
+//! crates, using [`diesel::DieselUpdate`] or [`sqlx::query_builder`].
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::num::TryFromIntError;
+
+use crate::scalar::*;
+use crate::string::StringChange;
+use crate::types::Comparable;
+use crate::OptionChange;
+
+#[cfg(feature = "diesel")]
+pub mod diesel;
+#[cfg(feature = "sqlx")]
+pub mod sqlx;
+
+/// Why a statement could not be run.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Error {
+	/// The statement sets no columns, which is not valid SQL.
+	NoAssignments,
+	/// An unsigned value is too large for the signed integers it is bound as.
+	TooLarge(TryFromIntError),
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Error::NoAssignments => write!(f, "an UPDATE statement must set at least one column"),
+			Error::TooLarge(err) => write!(f, "{}", err),
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+impl From<TryFromIntError> for Error {
+	fn from(err: TryFromIntError) -> Self {
+		Error::TooLarge(err)
+	}
+}
+
+/// A value bound to a parameter of a statement.
+#[derive(Clone, PartialEq, Debug)]
+pub enum SqlValue {
+	Null,
+	Boolean(bool),
+	Integer(i64),
+	Unsigned(u64),
+	Real(f64),
+	Text(String),
+}
+
+/// Values that can be bound to a parameter of a statement.
+pub trait ToSql {
+	fn to_sql(&self) -> SqlValue;
+}
+
+/// Changes to a column, which can report the column's new value.
+pub trait ChangeToSql {
+	fn new_value(&self) -> SqlValue;
+}
+
+/// A parameterized `UPDATE` statement, setting the given columns of the row
+/// with the given key.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SqlUpdate {
+	pub table: String,
+	/// The columns to set, and their new values, in the order of the changes
+	/// they were found in.
+	pub assignments: Vec<(String, SqlValue)>,
+	/// The key column, and the key of the row to update.
+	pub key: (String, SqlValue),
+}
+
+impl SqlUpdate {
+	/// The text of the statement, with a `?` placeholder for each parameter.
+	pub fn statement(&self) -> String {
+		let assignments =
+			self.assignments.iter().map(|(column, _)| format!("{} = ?", column)).collect::<Vec<_>>().join(", ");
+		format!("UPDATE {} SET {} WHERE {} = ?", self.table, assignments, self.key.0)
+	}
+
+	/// The values of the statement's parameters, in order.
+	pub fn parameters(&self) -> Vec<&SqlValue> {
+		self.assignments.iter().map(|(_, value)| value).chain(Some(&self.key.1)).collect()
+	}
+
+	/// The same statement, with its unsigned values made signed, for
+	/// databases without unsigned columns. Fails if one of them is too large.
+	pub fn signed(self) -> Result<SqlUpdate, TryFromIntError> {
+		let signed = |value| match value {
+			SqlValue::Unsigned(u) => i64::try_from(u).map(SqlValue::Integer),
+			value => Ok(value),
+		};
+		Ok(SqlUpdate {
+			table: self.table,
+			assignments: self
+				.assignments
+				.into_iter()
+				.map(|(column, value)| signed(value).map(|value| (column, value)))
+				.collect::<Result<_, _>>()?,
+			key: (self.key.0, signed(self.key.1)?),
+		})
+	}
+}
+
+/// Structs whose changes can be written to the row of a table holding them.
+pub trait SqlChangeset: Comparable {
+	/// The statement applying `change`, found by comparing this value with
+	/// another, to the row holding this value, or `None` if `change` sets no
+	/// columns, since an `UPDATE` must set at least one.
+	fn sql_update(&self, change: &Self::Change) -> Option<SqlUpdate>;
+}
+
+macro_rules! impl_sql_for_scalars {
+	($($ty:ty => $change:ident, $variant:ident, $as:ty);* $(;)?) => {
+		$(
+			impl ToSql for $ty {
+				fn to_sql(&self) -> SqlValue {
+					SqlValue::$variant(<$as>::from(*self))
+				}
+			}
+
+			impl ChangeToSql for $change {
+				fn new_value(&self) -> SqlValue {
+					self.1.to_sql()
+				}
+			}
+		)*
+	};
+}
+
+impl_sql_for_scalars! {
+	bool => BoolChange, Boolean, bool;
+	u8 => U8Change, Unsigned, u64;
+	i8 => I8Change, Integer, i64;
+	u16 => U16Change, Unsigned, u64;
+	i16 => I16Change, Integer, i64;
+	u32 => U32Change, Unsigned, u64;
+	i32 => I32Change, Integer, i64;
+	u64 => U64Change, Unsigned, u64;
+	i64 => I64Change, Integer, i64;
+	f32 => F32Change, Real, f64;
+	f64 => F64Change, Real, f64;
+}
+
+impl ToSql for usize {
+	fn to_sql(&self) -> SqlValue {
+		SqlValue::Unsigned(*self as u64)
+	}
+}
+
+impl ChangeToSql for UsizeChange {
+	fn new_value(&self) -> SqlValue {
+		self.1.to_sql()
+	}
+}
+
+impl ToSql for isize {
+	fn to_sql(&self) -> SqlValue {
+		SqlValue::Integer(*self as i64)
+	}
+}
+
+impl ChangeToSql for IsizeChange {
+	fn new_value(&self) -> SqlValue {
+		self.1.to_sql()
+	}
+}
+
+impl ToSql for char {
+	fn to_sql(&self) -> SqlValue {
+		SqlValue::Text(self.to_string())
+	}
+}
+
+impl ChangeToSql for CharChange {
+	fn new_value(&self) -> SqlValue {
+		self.1.to_sql()
+	}
+}
+
+impl ToSql for String {
+	fn to_sql(&self) -> SqlValue {
+		SqlValue::Text(self.clone())
+	}
+}
+
+impl ChangeToSql for StringChange {
+	fn new_value(&self) -> SqlValue {
+		self.1.to_sql()
+	}
+}
+
+impl<T: ToSql> ToSql for Option<T> {
+	fn to_sql(&self) -> SqlValue {
+		self.as_ref().map_or(SqlValue::Null, ToSql::to_sql)
+	}
+}
+
+impl<Desc: ToSql, Change: ChangeToSql> ChangeToSql for OptionChange<Option<Desc>, Change> {
+	fn new_value(&self) -> SqlValue {
+		match self {
+			OptionChange::BothSome(change) => change.new_value(),
+			OptionChange::Different(_, after) => after.to_sql(),
+		}
+	}
+}
@@ -0,0 +1,99 @@
+//! Running the statements of [`SqlChangeset`](super::SqlChangeset) with
+//! Diesel.
+//!
+//! Diesel's `AsChangeset` names the columns it sets at compile time, by their
+//! types, while the columns changed between two values are only known once
+//! they are compared. [`DieselUpdate`] is instead a whole `UPDATE` statement
+//! implementing `QueryFragment` for any backend, setting only the changed
+//! columns, and run by `RunQueryDsl::execute` like any other statement.
+//!
+//! ```ignore
+//! use comparable::sql::{diesel::DieselUpdate, SqlChangeset};
+//! use diesel::RunQueryDsl;
+//!
+//! if let Some(update) = before.sql_update(&changes) {
+//!     DieselUpdate::new(update)?.execute(&mut connection)?;
+//! }
+//! ```
+
+use diesel::backend::Backend;
+use diesel::query_builder::{AstPass, QueryFragment, QueryId};
+use diesel::serialize::ToSql;
+use diesel::sql_types::{BigInt, Bool, Double, HasSqlType, Text};
+use diesel::{QueryResult, RunQueryDsl};
+
+use super::{Error, SqlUpdate, SqlValue};
+
+/// An `UPDATE` statement that Diesel can run. Table and column names are
+/// quoted as identifiers, and values are bound to parameters.
+#[derive(Clone, PartialEq, Debug)]
+pub struct DieselUpdate(SqlUpdate);
+
+impl DieselUpdate {
+	/// The statement setting the columns of `update`, whose unsigned values
+	/// are bound as `BigInt`, since few databases have unsigned columns.
+	/// Fails if one of them is too large for a `BigInt`, or if `update` sets
+	/// no columns.
+	pub fn new(update: SqlUpdate) -> Result<Self, Error> {
+		if update.assignments.is_empty() {
+			return Err(Error::NoAssignments);
+		}
+		Ok(DieselUpdate(update.signed()?))
+	}
+
+	fn push_value<'b, DB>(out: &mut AstPass<'_, 'b, DB>, value: &'b SqlValue) -> QueryResult<()>
+	where
+		DB: Backend + HasSqlType<Bool> + HasSqlType<BigInt> + HasSqlType<Double> + HasSqlType<Text>,
+		bool: ToSql<Bool, DB>,
+		i64: ToSql<BigInt, DB>,
+		f64: ToSql<Double, DB>,
+		String: ToSql<Text, DB>,
+	{
+		match value {
+			SqlValue::Null => {
+				out.push_sql("NULL");
+				Ok(())
+			}
+			SqlValue::Boolean(b) => out.push_bind_param::<Bool, _>(b),
+			SqlValue::Integer(i) => out.push_bind_param::<BigInt, _>(i),
+			SqlValue::Unsigned(_) => unreachable!("unsigned values are made signed by DieselUpdate::new"),
+			SqlValue::Real(r) => out.push_bind_param::<Double, _>(r),
+			SqlValue::Text(s) => out.push_bind_param::<Text, _>(s),
+		}
+	}
+}
+
+impl<DB> QueryFragment<DB> for DieselUpdate
+where
+	DB: Backend + HasSqlType<Bool> + HasSqlType<BigInt> + HasSqlType<Double> + HasSqlType<Text>,
+	bool: ToSql<Bool, DB>,
+	i64: ToSql<BigInt, DB>,
+	f64: ToSql<Double, DB>,
+	String: ToSql<Text, DB>,
+{
+	fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, DB>) -> QueryResult<()> {
+		let update = &self.0;
+		out.push_sql("UPDATE ");
+		out.push_identifier(&update.table)?;
+		out.push_sql(" SET ");
+		for (i, (column, value)) in update.assignments.iter().enumerate() {
+			if i > 0 {
+				out.push_sql(", ");
+			}
+			out.push_identifier(column)?;
+			out.push_sql(" = ");
+			Self::push_value(&mut out, value)?;
+		}
+		out.push_sql(" WHERE ");
+		out.push_identifier(&update.key.0)?;
+		out.push_sql(" = ");
+		Self::push_value(&mut out, &update.key.1)
+	}
+}
+
+impl QueryId for DieselUpdate {
+	type QueryId = ();
+	const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<Conn> RunQueryDsl<Conn> for DieselUpdate {}
@@ -0,0 +1,77 @@
+//! Running the statements of [`SqlChangeset`](super::SqlChangeset) with
+//! sqlx.
+//!
+//! [`query_builder`] starts an sqlx `QueryBuilder` with an `UPDATE`
+//! statement setting only the changed columns, using the placeholders of
+//! the database it is built for, such as `$1` for PostgreSQL and `?` for
+//! SQLite, with the new values bound to them. Table and column names are
+//! quoted as identifiers of that database.
+//!
+//! ```ignore
+//! use comparable::sql::{sqlx::query_builder, SqlChangeset};
+//!
+//! if let Some(update) = before.sql_update(&changes) {
+//!     query_builder::<sqlx::Postgres>(&update)?.build().execute(&pool).await?;
+//! }
+//! ```
+
+use std::convert::TryFrom;
+
+use sqlx::{Database, Encode, QueryBuilder, Type};
+
+use super::{Error, SqlUpdate, SqlValue};
+
+/// A query builder holding the statement of `update`, whose unsigned values
+/// are bound as `i64`, since few databases have unsigned columns. Fails if
+/// one of them is too large for an `i64`, or if `update` sets no columns.
+/// More may be pushed onto the statement, such as a `RETURNING` clause.
+pub fn query_builder<'args, DB>(update: &SqlUpdate) -> Result<QueryBuilder<'args, DB>, Error>
+where
+	DB: Database,
+	<DB as Database>::Arguments<'args>: Default,
+	bool: Encode<'args, DB> + Type<DB>,
+	i64: Encode<'args, DB> + Type<DB>,
+	f64: Encode<'args, DB> + Type<DB>,
+	String: Encode<'args, DB> + Type<DB>,
+{
+	if update.assignments.is_empty() {
+		return Err(Error::NoAssignments);
+	}
+	let mut builder = QueryBuilder::new(format!("UPDATE {} SET ", identifier::<DB>(&update.table)));
+	for (i, (column, value)) in update.assignments.iter().enumerate() {
+		if i > 0 {
+			builder.push(", ");
+		}
+		builder.push(format!("{} = ", identifier::<DB>(column)));
+		push_value(&mut builder, value)?;
+	}
+	builder.push(format!(" WHERE {} = ", identifier::<DB>(&update.key.0)));
+	push_value(&mut builder, &update.key.1)?;
+	Ok(builder)
+}
+
+// Quote `name` as an identifier, with the backticks of MySQL or the double
+// quotes of standard SQL, doubling any quotes within it as Diesel does.
+fn identifier<DB: Database>(name: &str) -> String {
+	let quote = if DB::NAME == "MySQL" { "`" } else { "\"" };
+	format!("{}{}{}", quote, name.replace(quote, &quote.repeat(2)), quote)
+}
+
+fn push_value<'args, DB>(builder: &mut QueryBuilder<'args, DB>, value: &SqlValue) -> Result<(), Error>
+where
+	DB: Database,
+	bool: Encode<'args, DB> + Type<DB>,
+	i64: Encode<'args, DB> + Type<DB>,
+	f64: Encode<'args, DB> + Type<DB>,
+	String: Encode<'args, DB> + Type<DB>,
+{
+	match value {
+		SqlValue::Null => builder.push("NULL"),
+		SqlValue::Boolean(b) => builder.push_bind(*b),
+		SqlValue::Integer(i) => builder.push_bind(*i),
+		SqlValue::Unsigned(u) => builder.push_bind(i64::try_from(*u)?),
+		SqlValue::Real(r) => builder.push_bind(*r),
+		SqlValue::Text(s) => builder.push_bind(s.clone()),
+	};
+	Ok(())
+}
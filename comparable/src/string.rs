@@ -20,6 +20,14 @@ impl Comparable for String {
 			Changed::Unchanged
 		}
 	}
+
+	fn differs(&self, other: &Self) -> bool {
+		self != other
+	}
+
+	fn fingerprint(&self) -> Option<u64> {
+		Some(crate::light::hash_of(self))
+	}
 }
 
 impl Comparable for &str {
@@ -38,4 +46,75 @@ impl Comparable for &str {
 			Changed::Unchanged
 		}
 	}
+
+	fn differs(&self, other: &Self) -> bool {
+		self != other
+	}
+
+	fn fingerprint(&self) -> Option<u64> {
+		Some(crate::light::hash_of(self))
+	}
+}
+
+macro_rules! normalized_string {
+	($(#[$doc:meta])* $name:ident, |$s:ident| $normalize:expr) => {
+		$(#[$doc])*
+		#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+		#[cfg_attr(feature = "serde", serde(transparent))]
+		#[derive(Clone, Debug, Default)]
+		pub struct $name<S = String>(pub S);
+
+		impl<S: AsRef<str>> $name<S> {
+			fn normalized(&self) -> String {
+				let $s = self.0.as_ref();
+				$normalize
+			}
+		}
+
+		impl<S> std::ops::Deref for $name<S> {
+			type Target = S;
+
+			fn deref(&self) -> &S {
+				&self.0
+			}
+		}
+
+		impl<S> From<S> for $name<S> {
+			fn from(value: S) -> Self {
+				$name(value)
+			}
+		}
+
+		impl<S: AsRef<str>> Comparable for $name<S> {
+			type Desc = String;
+
+			fn describe(&self) -> Self::Desc {
+				self.0.as_ref().to_string()
+			}
+
+			type Change = StringChange;
+
+			fn comparison(&self, other: &Self) -> Changed<Self::Change> {
+				if self.normalized() != other.normalized() {
+					Changed::Changed(StringChange(self.describe(), other.describe()))
+				} else {
+					Changed::Unchanged
+				}
+			}
+		}
+	};
 }
+
+normalized_string!(
+	/// A string that is only considered changed if it differs other than in
+	/// the case of its letters. It is described exactly as written.
+	CaseInsensitive,
+	|s| s.to_lowercase()
+);
+
+normalized_string!(
+	/// A string that is only considered changed if it differs other than in
+	/// leading or trailing whitespace. It is described exactly as written.
+	Trimmed,
+	|s| s.trim().to_string()
+);
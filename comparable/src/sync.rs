@@ -0,0 +1,232 @@
+//! Keeping remote copies of a value in sync by sending only its changes, with
+//! the `codec` feature enabled.
+//!
+//! A [`Publisher`] owns the value being shared. Each time the value is
+//! published it produces a [`Message`] holding the changes since the previous
+//! version, encoded with [`crate::codec`], which is sent to a [`Subscriber`]
+//! by whatever transport the application uses. The subscriber applies the
+//! changes to its own copy and replies with the version it has reached, which
+//! the publisher records as acknowledged.
+//!
+//! A subscriber that misses a message can no longer apply the ones after it,
+//! and reports this as [`Error::Desync`]. The publisher then resynchronizes it
+//! from the version it has, by sending all of the changes since that version
+//! at once, or sending a snapshot of the whole value if that version is no
+//! longer known.
+//!
+//! Since changes only describe what happened to a value, the subscriber needs
+//! a function that knows how to apply a change of that type, and snapshots
+//! require the type to implement [`FromDesc`].
+//!
+//! ```
+//! # use comparable::*;
+//! use comparable::sync::{Error, Publisher, Subscriber};
+//!
+//! let apply = |value: &mut i32, change: I32Change| *value = change.1;
+//! let mut publisher = Publisher::new(1);
+//! let mut subscriber = Subscriber::new();
+//!
+//! let version = subscriber.receive(&publisher.snapshot().unwrap(), apply).unwrap();
+//! publisher.acknowledge(version);
+//!
+//! let _lost = publisher.publish(&2).unwrap().unwrap();
+//! let message = publisher.publish(&3).unwrap().unwrap();
+//! assert!(matches!(subscriber.receive(&message, apply), Err(Error::Desync { .. })));
+//!
+//! let resync = publisher.resync(subscriber.version()).unwrap();
+//! publisher.acknowledge(subscriber.receive(&resync, apply).unwrap());
+//! assert_eq!(subscriber.value(), Some(&3));
+//! ```
+
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::codec::{self, decode, decode_changes, encode, encode_changes};
+use crate::roundtrip::FromDesc;
+use crate::types::{Changed, Comparable};
+
+/// A message sent from a [`Publisher`] to a [`Subscriber`]. Messages may
+/// themselves be encoded using [`codec::encode`].
+#[derive(Serialize, serde::Deserialize, PartialEq, Debug)]
+pub enum Message {
+	/// The encoded description of the whole value at the given version.
+	Snapshot { version: u64, desc: Vec<u8> },
+	/// The encoded changes that turn the value at version `base` into the
+	/// value at `version`.
+	Changes { base: u64, version: u64, changes: Vec<u8> },
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Error {
+	/// A message could not be encoded or decoded.
+	Codec(codec::Error),
+	/// Changes were received for a version other than the one the subscriber
+	/// has, which is `None` if it has not yet received a snapshot.
+	Desync { version: Option<u64>, base: u64 },
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Error::Codec(err) => write!(f, "{}", err),
+			Error::Desync { version: Some(version), base } => {
+				write!(f, "received changes to version {} while at version {}", base, version)
+			}
+			Error::Desync { version: None, base } => {
+				write!(f, "received changes to version {} before any snapshot", base)
+			}
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+impl From<codec::Error> for Error {
+	fn from(err: codec::Error) -> Self {
+		Error::Codec(err)
+	}
+}
+
+/// The sending side of a synchronized value.
+///
+/// Every version published since the last one acknowledged is kept, so that
+/// a subscriber at any of them can be brought up to date by sending only
+/// changes. Acknowledging versions regularly keeps this history short.
+#[derive(Debug)]
+pub struct Publisher<T: Comparable + Clone> {
+	// The last acknowledged state comes first, and the latest state last.
+	states: Vec<(u64, T)>,
+}
+
+impl<T: Comparable + Clone> Publisher<T>
+where
+	T::Desc: Serialize,
+	T::Change: Serialize,
+{
+	pub fn new(value: T) -> Self {
+		Publisher { states: vec![(0, value)] }
+	}
+
+	/// The most recently published version.
+	pub fn version(&self) -> u64 {
+		self.latest().0
+	}
+
+	/// The most recently published value.
+	pub fn value(&self) -> &T {
+		&self.latest().1
+	}
+
+	fn latest(&self) -> &(u64, T) {
+		self.states.last().expect("Publisher has no states")
+	}
+
+	/// A snapshot of the most recently published value, from which a new
+	/// subscriber can begin.
+	pub fn snapshot(&self) -> Result<Message, Error> {
+		let (version, value) = self.latest();
+		Ok(Message::Snapshot { version: *version, desc: encode(&value.describe())? })
+	}
+
+	/// Publish a new version of the value, returning the changes since the
+	/// previous version to be sent to subscribers. Nothing is published if the
+	/// value has not changed.
+	pub fn publish(&mut self, value: &T) -> Result<Option<Message>, Error> {
+		let (base, latest) = self.latest();
+		let base = *base;
+		match latest.comparison(value) {
+			Changed::Unchanged => Ok(None),
+			changes => {
+				let changes = encode_changes(&changes)?;
+				self.states.push((base + 1, value.clone()));
+				Ok(Some(Message::Changes { base, version: base + 1, changes }))
+			}
+		}
+	}
+
+	/// Record that a subscriber has reached `version`, forgetting the versions
+	/// before it.
+	pub fn acknowledge(&mut self, version: u64) {
+		if let Some(i) = self.states.iter().position(|(v, _)| *v == version) {
+			self.states.drain(..i);
+		}
+	}
+
+	/// The last version acknowledged by a subscriber.
+	pub fn acknowledged(&self) -> u64 {
+		self.states[0].0
+	}
+
+	/// Bring a subscriber at `version` up to date, after it has reported a
+	/// desync. If that version is still known this sends only the changes
+	/// made since then; otherwise it sends a snapshot.
+	pub fn resync(&self, version: Option<u64>) -> Result<Message, Error> {
+		match self.states.iter().find(|(v, _)| Some(*v) == version) {
+			Some((base, value)) => {
+				let (latest, latest_value) = self.latest();
+				Ok(Message::Changes {
+					base: *base,
+					version: *latest,
+					changes: encode_changes(&value.comparison(latest_value))?,
+				})
+			}
+			None => self.snapshot(),
+		}
+	}
+}
+
+/// The receiving side of a synchronized value, which holds nothing until it
+/// has received a snapshot.
+#[derive(Debug)]
+pub struct Subscriber<T: FromDesc> {
+	state: Option<(u64, T)>,
+}
+
+impl<T: FromDesc> Default for Subscriber<T> {
+	fn default() -> Self {
+		Subscriber { state: None }
+	}
+}
+
+impl<T: FromDesc> Subscriber<T>
+where
+	T::Desc: DeserializeOwned,
+	T::Change: DeserializeOwned,
+{
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// The version of the value held, if any.
+	pub fn version(&self) -> Option<u64> {
+		self.state.as_ref().map(|(version, _)| *version)
+	}
+
+	pub fn value(&self) -> Option<&T> {
+		self.state.as_ref().map(|(_, value)| value)
+	}
+
+	/// Receive a message from the publisher, applying any changes it holds
+	/// using the given function, and returning the version that has been
+	/// reached, which should be acknowledged. Messages for versions already
+	/// reached are ignored.
+	pub fn receive(&mut self, message: &Message, apply: impl FnOnce(&mut T, T::Change)) -> Result<u64, Error> {
+		match (message, &mut self.state) {
+			(Message::Snapshot { version, desc }, state) => {
+				*state = Some((*version, T::from_desc(decode(desc)?)));
+				Ok(*version)
+			}
+			(Message::Changes { version, .. }, Some((current, _))) if *version <= *current => Ok(*current),
+			(Message::Changes { base, version, changes }, Some((current, value))) if *base == *current => {
+				if let Changed::Changed(change) = decode_changes(changes)? {
+					apply(value, change);
+				}
+				*current = *version;
+				Ok(*version)
+			}
+			(Message::Changes { base, .. }, _) => Err(Error::Desync { version: self.version(), base: *base }),
+		}
+	}
+}
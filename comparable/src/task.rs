@@ -0,0 +1,118 @@
+//! Comparing large values from async code.
+//!
+//! Comparing large collections takes long enough to stall an async executor,
+//! delaying every other task scheduled on the same thread. With the `tokio`
+//! feature, [`diff_blocking`] moves such a comparison onto tokio's pool of
+//! blocking threads, and [`diff_slices_yielding`] compares two slices in
+//! chunks, yielding to the executor between them.
+//!
+//! Both compare values as if where they are called, honoring the field mask,
+//! [`DiffOptions`], float tolerance and atomic ordering in effect there, even
+//! though the futures they return may be polled elsewhere.
+//!
+//! ```
+//! # use comparable::*;
+//! use std::sync::Arc;
+//! use comparable::task::diff_blocking;
+//!
+//! # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+//! let before = Arc::new(vec![1, 2, 3]);
+//! let after = Arc::new(vec![1, 2, 4]);
+//! assert_eq!(diff_blocking(before, after).await, Changed::Changed(vec![VecChange::Changed(2, I32Change(3, 4))]));
+//! # });
+//! ```
+//!
+//! [`DiffOptions`]: crate::DiffOptions
+
+use std::future::Future;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use crate::atomic::{atomic_ordering, with_atomic_ordering};
+use crate::float::{float_tolerance, with_float_tolerance, FloatTolerance};
+use crate::mask::{current_mask, with_current_mask, Filter};
+use crate::options::{diff_options, with_diff_options, DiffOptions};
+use crate::set::VecChange;
+use crate::types::{Changed, Comparable};
+
+// The settings affecting comparisons on the calling thread, to be applied
+// wherever the comparison is carried out.
+struct Settings {
+	options: DiffOptions,
+	tolerance: FloatTolerance,
+	ordering: Ordering,
+	mask: Option<(Filter, Vec<String>)>,
+}
+
+impl Settings {
+	fn current() -> Self {
+		Settings {
+			options: diff_options(),
+			tolerance: float_tolerance(),
+			ordering: atomic_ordering(),
+			mask: current_mask(),
+		}
+	}
+
+	fn apply<R>(&self, f: impl FnOnce() -> R) -> R {
+		with_diff_options(self.options, || {
+			with_float_tolerance(self.tolerance, || {
+				with_atomic_ordering(self.ordering, || with_current_mask(self.mask.clone(), f))
+			})
+		})
+	}
+}
+
+/// Compare `before` with `after` on tokio's pool of blocking threads, so that
+/// the executor can run other tasks meanwhile. A panic during the comparison
+/// is resumed in the calling task.
+pub fn diff_blocking<T>(before: Arc<T>, after: Arc<T>) -> impl Future<Output = Changed<T::Change>>
+where
+	T: Comparable + Send + Sync + 'static,
+	T::Change: Send + 'static,
+{
+	let settings = Settings::current();
+	async move {
+		tokio::task::spawn_blocking(move || settings.apply(|| before.comparison(&after)))
+			.await
+			.unwrap_or_else(|err| std::panic::resume_unwind(err.into_panic()))
+	}
+}
+
+/// Compare two slices as a `Vec` is compared, element by element, yielding
+/// to the executor after every `chunk` elements compared. The changes are
+/// those of comparing the slices as `Vec`s without [`DiffOptions`] aligning
+/// their elements, which are compared index by index.
+///
+/// [`DiffOptions`]: crate::DiffOptions
+pub fn diff_slices_yielding<'a, T: Comparable>(
+	before: &'a [T],
+	after: &'a [T],
+	chunk: usize,
+) -> impl Future<Output = Changed<Vec<VecChange<T::Desc, T::Change>>>> + 'a {
+	let settings = Settings::current();
+	let chunk = chunk.max(1);
+	async move {
+		let len = before.len().max(after.len());
+		let mut changes = Vec::new();
+		let mut start = 0;
+		while start < len {
+			let end = (start + chunk).min(len);
+			settings.apply(|| {
+				for i in start..end {
+					match (before.get(i), after.get(i)) {
+						(Some(x), Some(y)) => {
+							changes.extend(x.comparison(y).map(|change| VecChange::Changed(i, change)))
+						}
+						(Some(x), None) => changes.push(VecChange::Removed(i, x.describe())),
+						(None, Some(y)) => changes.push(VecChange::Added(i, y.describe())),
+						(None, None) => (),
+					}
+				}
+			});
+			start = end;
+			tokio::task::yield_now().await;
+		}
+		Changed::from_changes(changes)
+	}
+}
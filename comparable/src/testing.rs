@@ -0,0 +1,60 @@
+//! Helpers for checking that implementations of [`Comparable`] obey the laws
+//! expected of them. These are most useful when written by hand, or when
+//! derived for types whose fields use custom descriptions.
+//!
+//! Each helper panics when a law does not hold, so they may be used in plain
+//! unit tests as well as inside `proptest!` blocks. With the `proptest`
+//! feature enabled, [`Changed`] also implements `proptest::arbitrary::Arbitrary`.
+
+use crate::types::{Changed, Comparable};
+
+/// Assert the laws relating any two values of a type:
+///
+/// - a value is unchanged when compared with itself;
+/// - `a` differs from `b` exactly when `b` differs from `a`;
+/// - values that do not differ have the same description.
+pub fn assert_comparable_laws<T: Comparable>(a: &T, b: &T) {
+	assert_eq!(a.comparison(a), Changed::Unchanged, "value is changed when compared with itself");
+	assert_eq!(b.comparison(b), Changed::Unchanged, "value is changed when compared with itself");
+	let forward = a.comparison(b);
+	let backward = b.comparison(a);
+	assert_eq!(
+		forward.is_unchanged(),
+		backward.is_unchanged(),
+		"comparison is not symmetric: {:?} versus {:?}",
+		forward,
+		backward
+	);
+	if forward.is_unchanged() {
+		assert_eq!(a.describe(), b.describe(), "unchanged values have different descriptions");
+	}
+}
+
+/// Assert that applying the changes from `a` to `b`, using the given function,
+/// turns `a` into a value that no longer differs from `b`.
+pub fn assert_apply_law<T, F>(a: &T, b: &T, apply: F)
+where
+	T: Comparable + Clone,
+	F: FnOnce(&mut T, T::Change),
+{
+	let mut value = a.clone();
+	if let Changed::Changed(change) = a.comparison(b) {
+		apply(&mut value, change);
+	}
+	assert_eq!(value.comparison(b), Changed::Unchanged, "applying the changes did not produce the expected value");
+}
+
+#[cfg(feature = "proptest")]
+impl<T: proptest::arbitrary::Arbitrary + std::fmt::Debug + 'static> proptest::arbitrary::Arbitrary for Changed<T> {
+	type Parameters = T::Parameters;
+	type Strategy = proptest::strategy::BoxedStrategy<Changed<T>>;
+
+	fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+		use proptest::strategy::{LazyJust, Strategy};
+		proptest::prop_oneof![
+			LazyJust::new(|| Changed::Unchanged),
+			proptest::arbitrary::any_with::<T>(args).prop_map(Changed::Changed),
+		]
+		.boxed()
+	}
+}
@@ -0,0 +1,39 @@
+//! Emitting changes as `tracing` events, so that they land in the same
+//! observability pipelines as the rest of a program's logs.
+//!
+//! [`log_changes`] emits an event within a given span for each change to an
+//! individual value within any changes implementing [`ToTree`], with the
+//! fields `path`, `kind`, `old` and `new`, as written by
+//! [`format::csv::write`](crate::format::csv::write).
+//!
+//! ```
+//! # use comparable::*;
+//! let span = tracing::info_span!("reconcile", service = "billing");
+//! let changes = vec![1, 2].comparison(&vec![1, 3, 4]).unwrap_or_default();
+//! // Emits `path = "1", kind = "modified", old = "2", new = "3"` and
+//! // `path = "2", kind = "added", old = "", new = "4"` within `span`.
+//! comparable::trace::log_changes(&span, &changes);
+//! ```
+
+use tracing::Span;
+
+use crate::tree::{ChangeTree, ToTree};
+
+/// Emit an `INFO` event within `span` for each change to an individual value
+/// within `change`, whose fields are its path, as given by
+/// [`ChangeTree::leaves`], the kind of change, which is one of `added`,
+/// `removed`, `modified` or `moved`, and the values before and after,
+/// written using their `Debug` format. A value moved has the key it was
+/// moved to as its value after.
+pub fn log_changes<C: ToTree + ?Sized>(span: &Span, change: &C) {
+	for (path, leaf) in change.to_tree().leaves() {
+		let (kind, old, new) = match leaf {
+			ChangeTree::Added(desc) => ("added", "", desc.as_str()),
+			ChangeTree::Removed(desc) => ("removed", desc.as_str(), ""),
+			ChangeTree::Modified { before, after } => ("modified", before.as_str(), after.as_str()),
+			ChangeTree::Moved(key) => ("moved", "", key.as_str()),
+			ChangeTree::Children(_) => unreachable!("leaves have no children"),
+		};
+		tracing::info!(parent: span, path = path.as_str(), kind, old, new, "changed");
+	}
+}
@@ -0,0 +1,90 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::sink::ChangeSink;
+use crate::types::{Changed, Comparable};
+
+/// A wrapper that keeps a snapshot of the value it holds, so that the changes
+/// made to it through [`DerefMut`] can be obtained later without the caller
+/// having to keep a copy of the old value around.
+///
+/// ```
+/// # use comparable::*;
+/// let mut tracked = Tracked::new(vec![1, 2]);
+/// tracked.push(3);
+/// assert_eq!(tracked.take_changes(), Changed::Changed(vec![VecChange::Added(2, 3)]));
+/// assert_eq!(tracked.take_changes(), Changed::Unchanged);
+/// ```
+#[derive(Debug)]
+pub struct Tracked<T: Comparable + Clone> {
+	value: T,
+	snapshot: T,
+}
+
+impl<T: Comparable + Clone> Tracked<T> {
+	pub fn new(value: T) -> Self {
+		Tracked { snapshot: value.clone(), value }
+	}
+
+	/// The value as it was when tracking began, or when the changes were last
+	/// taken.
+	pub fn snapshot(&self) -> &T {
+		&self.snapshot
+	}
+
+	/// Report the changes made since the last snapshot, without taking a new
+	/// one.
+	pub fn changes(&self) -> Changed<T::Change> {
+		self.snapshot.comparison(&self.value)
+	}
+
+	/// Report the changes made since the last snapshot, and take a new
+	/// snapshot of the current value so that subsequent calls only report
+	/// changes made from this point on.
+	pub fn take_changes(&mut self) -> Changed<T::Change> {
+		let changes = self.changes();
+		if !changes.is_unchanged() {
+			self.snapshot = self.value.clone();
+		}
+		changes
+	}
+
+	/// Send the changes made since the last snapshot to `sink`, if there are
+	/// any, and take a new snapshot as [`Tracked::take_changes`] does. Returns
+	/// whether changes were sent.
+	pub fn send_changes<S: ChangeSink<T::Change>>(&mut self, sink: &mut S) -> Result<bool, S::Error> {
+		match self.take_changes() {
+			Changed::Unchanged => Ok(false),
+			Changed::Changed(changes) => sink.send_change(changes).map(|()| true),
+		}
+	}
+
+	pub fn into_inner(self) -> T {
+		self.value
+	}
+}
+
+impl<T: Comparable + Clone + Default> Default for Tracked<T> {
+	fn default() -> Self {
+		Tracked::new(T::default())
+	}
+}
+
+impl<T: Comparable + Clone> From<T> for Tracked<T> {
+	fn from(value: T) -> Self {
+		Tracked::new(value)
+	}
+}
+
+impl<T: Comparable + Clone> Deref for Tracked<T> {
+	type Target = T;
+
+	fn deref(&self) -> &T {
+		&self.value
+	}
+}
+
+impl<T: Comparable + Clone> DerefMut for Tracked<T> {
+	fn deref_mut(&mut self) -> &mut T {
+		&mut self.value
+	}
+}
@@ -0,0 +1,304 @@
+//! Changes as a tree of a single type.
+//!
+//! Each type deriving `Comparable` has its own change type, which suits code
+//! that knows the type compared, but not code that should work with any
+//! changes, such as renderers, serializers and user interfaces. A
+//! [`ChangeTree`] erases the type of the changes, keeping their structure:
+//! changes within a value are [`ChangeTree::Children`] keyed by field name,
+//! index or map key, and the changes of the values themselves are leaves,
+//! with the values written using their `Debug` format.
+//!
+//! With `#[comparable_tree]`, `#[derive(Comparable)]` implements [`ToTree`]
+//! for the generated change type.
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! use comparable::tree::{ChangeTree, ToTree};
+//!
+//! #[derive(Comparable)]
+//! #[comparable_tree]
+//! struct Person {
+//!     name: String,
+//!     age: u32,
+//!     tags: Vec<String>,
+//! }
+//!
+//! let before = Person { name: "Alice".to_string(), age: 41, tags: vec![] };
+//! let after = Person { name: "Alice".to_string(), age: 42, tags: vec!["new".to_string()] };
+//! let changes = before.comparison(&after).unwrap_or_default();
+//! assert_eq!(
+//!     changes.to_tree(),
+//!     ChangeTree::Children(vec![
+//!         ("age".to_string(), ChangeTree::modified(&41, &42)),
+//!         (
+//!             "tags".to_string(),
+//!             ChangeTree::Children(vec![("0".to_string(), ChangeTree::Added("\"new\"".to_string()))])
+//!         ),
+//!     ])
+//! );
+//! ```
+
+use std::fmt::Debug;
+
+use crate::fs::FileMetadataChange;
+use crate::path::PathBufChange;
+use crate::scalar::*;
+use crate::string::StringChange;
+use crate::types::{Changed, Redacted};
+use crate::{MapChange, OptionChange, RangeChange, SetChange, VecChange};
+
+/// The changes to a value, whatever its type.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, PartialEq, Debug)]
+pub enum ChangeTree {
+	/// A value that was added, such as an element of a sequence.
+	Added(String),
+	/// A value that was removed, or the empty string if its changes do not
+	/// say what it was, as for the entries removed from maps.
+	Removed(String),
+	/// A value that was replaced by another.
+	Modified { before: String, after: String },
+	/// A value that was moved, unchanged, to the given key.
+	Moved(String),
+	/// Changes within a value, in the order they were found. A key may
+	/// appear more than once, such as the index of an element removed from
+	/// a sequence and that of another added in its place.
+	Children(Vec<(String, ChangeTree)>),
+}
+
+impl ChangeTree {
+	/// A value replaced by another, written using their `Debug` format.
+	pub fn modified(before: &dyn Debug, after: &dyn Debug) -> Self {
+		ChangeTree::Modified { before: format!("{:?}", before), after: format!("{:?}", after) }
+	}
+
+	/// Whether there are no changes at all.
+	pub fn is_empty(&self) -> bool {
+		matches!(self, ChangeTree::Children(children) if children.is_empty())
+	}
+
+	/// The changes to individual values within this tree, with the path to
+	/// each, which is the keys leading to it joined with `.`.
+	pub fn leaves(&self) -> Vec<(String, &ChangeTree)> {
+		let mut leaves = Vec::new();
+		self.collect_leaves(String::new(), &mut leaves);
+		leaves
+	}
+
+	fn collect_leaves<'a>(&'a self, path: String, leaves: &mut Vec<(String, &'a ChangeTree)>) {
+		match self {
+			ChangeTree::Children(children) => {
+				for (key, tree) in children {
+					let path = match (path.is_empty(), key.is_empty()) {
+						(_, true) => path.clone(),
+						(true, false) => key.clone(),
+						(false, false) => format!("{}.{}", path, key),
+					};
+					tree.collect_leaves(path, leaves)
+				}
+			}
+			leaf => leaves.push((path, leaf)),
+		}
+	}
+
+	/// The changes to individual values within this tree as rows of their
+	/// path, as given by [`ChangeTree::leaves`], and their values before and
+	/// after, written as text for tabular exports. A value added has an empty
+	/// value before, and one removed an empty value after. A value moved has
+	/// the key it was moved to as its value after.
+	pub fn rows(&self) -> Vec<(String, String, String)> {
+		self.leaves()
+			.into_iter()
+			.map(|(path, leaf)| {
+				let (before, after) = match leaf {
+					ChangeTree::Added(desc) => (String::new(), desc.clone()),
+					ChangeTree::Removed(desc) => (desc.clone(), String::new()),
+					ChangeTree::Modified { before, after } => (before.clone(), after.clone()),
+					ChangeTree::Moved(key) => (String::new(), key.clone()),
+					ChangeTree::Children(_) => unreachable!("leaves have no children"),
+				};
+				(path, before, after)
+			})
+			.collect()
+	}
+
+	// The changes within this tree, with any change to the value itself
+	// given the empty key.
+	fn into_children(self) -> Vec<(String, ChangeTree)> {
+		match self {
+			ChangeTree::Children(children) => children,
+			tree => vec![(String::new(), tree)],
+		}
+	}
+}
+
+/// Add the changes under `key` to `children`, unless there are none.
+pub fn push_child(children: &mut Vec<(String, ChangeTree)>, key: String, tree: ChangeTree) {
+	if !tree.is_empty() {
+		children.push((key, tree));
+	}
+}
+
+/// Changes that can be converted to a [`ChangeTree`].
+pub trait ToTree {
+	fn to_tree(&self) -> ChangeTree;
+}
+
+impl ToTree for () {
+	fn to_tree(&self) -> ChangeTree {
+		ChangeTree::Children(Vec::new())
+	}
+}
+
+/// Redacted fields are reported as modified, without their values.
+impl ToTree for Redacted {
+	fn to_tree(&self) -> ChangeTree {
+		ChangeTree::modified(self, self)
+	}
+}
+
+macro_rules! impl_tree_for_self_describing {
+	($($change:ident),*) => {
+		$(
+			impl ToTree for $change {
+				fn to_tree(&self) -> ChangeTree {
+					ChangeTree::modified(&self.0, &self.1)
+				}
+			}
+		)*
+	};
+}
+
+impl_tree_for_self_describing!(F32Change, F64Change, StringChange, PathBufChange);
+
+/// The changes of [`Leaf`](crate::Leaf) values, which hold the values before
+/// and after.
+impl<T: Debug> ToTree for (T, T) {
+	fn to_tree(&self) -> ChangeTree {
+		ChangeTree::modified(&self.0, &self.1)
+	}
+}
+
+#[cfg(feature = "ordered-float")]
+impl<T: Debug> ToTree for crate::numeric::OrderedFloatChange<T> {
+	fn to_tree(&self) -> ChangeTree {
+		ChangeTree::modified(&self.0, &self.1)
+	}
+}
+
+#[cfg(feature = "rust_decimal")]
+impl ToTree for crate::numeric::DecimalChange {
+	fn to_tree(&self) -> ChangeTree {
+		ChangeTree::modified(&self.0, &self.1)
+	}
+}
+
+impl<C: ToTree + ?Sized> ToTree for Box<C> {
+	fn to_tree(&self) -> ChangeTree {
+		(**self).to_tree()
+	}
+}
+
+impl<C: ToTree> ToTree for Changed<C> {
+	fn to_tree(&self) -> ChangeTree {
+		match self {
+			Changed::Unchanged => ChangeTree::Children(Vec::new()),
+			Changed::Changed(change) => change.to_tree(),
+		}
+	}
+}
+
+/// The changes of a struct with several fields, or of a collection, are
+/// gathered into a single node.
+impl<C: ToTree> ToTree for Vec<C> {
+	fn to_tree(&self) -> ChangeTree {
+		ChangeTree::Children(self.iter().flat_map(|change| change.to_tree().into_children()).collect())
+	}
+}
+
+/// The changes of an array are those of its elements, by index.
+impl<C: ToTree, const N: usize> ToTree for [C; N] {
+	fn to_tree(&self) -> ChangeTree {
+		let mut children = Vec::new();
+		for (index, change) in self.iter().enumerate() {
+			push_child(&mut children, index.to_string(), change.to_tree())
+		}
+		ChangeTree::Children(children)
+	}
+}
+
+impl<Desc: Debug, C: ToTree> ToTree for OptionChange<Desc, C> {
+	fn to_tree(&self) -> ChangeTree {
+		match self {
+			OptionChange::BothSome(change) => change.to_tree(),
+			OptionChange::Different(before, after) => ChangeTree::modified(before, after),
+		}
+	}
+}
+
+impl<C: ToTree> ToTree for RangeChange<C> {
+	fn to_tree(&self) -> ChangeTree {
+		let mut children = Vec::new();
+		match self {
+			RangeChange::Start(change) => push_child(&mut children, "start".to_string(), change.to_tree()),
+			RangeChange::End(change) => push_child(&mut children, "end".to_string(), change.to_tree()),
+		}
+		ChangeTree::Children(children)
+	}
+}
+
+impl ToTree for FileMetadataChange {
+	fn to_tree(&self) -> ChangeTree {
+		let mut children = Vec::new();
+		match self {
+			FileMetadataChange::Size(change) => push_child(&mut children, "size".to_string(), change.to_tree()),
+			FileMetadataChange::Modified(before, after) => {
+				push_child(&mut children, "modified".to_string(), ChangeTree::modified(before, after))
+			}
+			FileMetadataChange::Hash(change) => push_child(&mut children, "hash".to_string(), change.to_tree()),
+		}
+		ChangeTree::Children(children)
+	}
+}
+
+impl<Desc: Debug, C: ToTree> ToTree for VecChange<Desc, C> {
+	fn to_tree(&self) -> ChangeTree {
+		let (index, tree) = match self {
+			VecChange::Added(index, desc) => (index, ChangeTree::Added(format!("{:?}", desc))),
+			VecChange::Changed(index, change) => (index, change.to_tree()),
+			VecChange::Removed(index, desc) => (index, ChangeTree::Removed(format!("{:?}", desc))),
+			VecChange::Moved { from, to } => (from, ChangeTree::Moved(to.to_string())),
+		};
+		ChangeTree::Children(vec![(index.to_string(), tree)])
+	}
+}
+
+/// The elements of a set are keyed by their `Debug` format.
+impl<Desc: Debug> ToTree for SetChange<Desc> {
+	fn to_tree(&self) -> ChangeTree {
+		let (desc, tree) = match self {
+			SetChange::Added(desc) => (desc, ChangeTree::Added(format!("{:?}", desc))),
+			SetChange::Removed(desc) => (desc, ChangeTree::Removed(format!("{:?}", desc))),
+		};
+		ChangeTree::Children(vec![(format!("{:?}", desc), tree)])
+	}
+}
+
+/// The entries of a map are keyed by the `Debug` format of their keys.
+impl<Key: Debug, Desc: Debug, C: ToTree> ToTree for MapChange<Key, Desc, C> {
+	fn to_tree(&self) -> ChangeTree {
+		let key = |key: &Key| format!("{:?}", key);
+		let mut children = Vec::new();
+		match self {
+			MapChange::Added(k, desc) => children.push((key(k), ChangeTree::Added(format!("{:?}", desc)))),
+			MapChange::Changed(k, change) => push_child(&mut children, key(k), change.to_tree()),
+			MapChange::Removed(k) => children.push((key(k), ChangeTree::Removed(String::new()))),
+			MapChange::Moved { from, to, change } => {
+				children.push((key(from), ChangeTree::Moved(key(to))));
+				push_child(&mut children, key(to), change.to_tree())
+			}
+		}
+		ChangeTree::Children(children)
+	}
+}
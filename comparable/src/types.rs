@@ -1,7 +1,10 @@
 use std::fmt::Debug;
+use std::iter::FromIterator;
 
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(PartialEq, Debug)]
+use crate::location::Path;
+
+/// Whether a value changed, and if so, how.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum Changed<T> {
 	Unchanged,
 	Changed(T),
@@ -9,6 +12,7 @@ pub enum Changed<T> {
 
 impl<T> Changed<T> {
 	#[inline]
+	#[must_use]
 	pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Changed<U> {
 		match self {
 			Changed::Unchanged => Changed::Unchanged,
@@ -23,6 +27,8 @@ impl<T> Changed<T> {
 		}
 	}
 
+	/// Take the change, if there is one, as a list of changes, leaving this
+	/// unchanged.
 	pub fn to_changes(&mut self) -> Vec<T> {
 		match std::mem::take(self) {
 			Changed::Unchanged => vec![],
@@ -30,12 +36,111 @@ impl<T> Changed<T> {
 		}
 	}
 
+	/// Whether there is no change. Every comparison reports its changes
+	/// through `Changed`, whether the type compared is a scalar, a collection,
+	/// or a struct or enum deriving [`Comparable`], so this is how generic
+	/// code asks whether any two values differ. A change itself is never
+	/// empty: the list of changes to a struct's fields is `Unchanged` rather
+	/// than empty when no field changed.
+	///
+	/// ```
+	/// # use comparable::*;
+	/// # use comparable_derive::*;
+	/// #[derive(Comparable)]
+	/// struct Point {
+	///     x: i32,
+	///     y: i32,
+	/// }
+	///
+	/// fn unchanged<T: Comparable>(a: &T, b: &T) -> bool {
+	///     a.comparison(b).is_unchanged()
+	/// }
+	///
+	/// assert!(unchanged(&Point { x: 1, y: 2 }, &Point { x: 1, y: 2 }));
+	/// assert!(!unchanged(&Point { x: 1, y: 2 }, &Point { x: 1, y: 3 }));
+	/// assert!(unchanged(&vec![1, 2], &vec![1, 2]));
+	/// ```
+	#[must_use]
 	pub fn is_unchanged(&self) -> bool {
 		match self {
 			Changed::Unchanged => true,
 			Changed::Changed(_) => false,
 		}
 	}
+
+	/// The change, panicking if there is none. The panic is reported at the
+	/// call site, which makes this convenient in tests and examples.
+	///
+	/// ```
+	/// # use comparable::*;
+	/// assert_eq!(1u8.comparison(&2).unwrap_changed(), U8Change(1, 2));
+	/// ```
+	#[track_caller]
+	pub fn unwrap_changed(self) -> T {
+		match self {
+			Changed::Changed(x) => x,
+			Changed::Unchanged => panic!("called `Changed::unwrap_changed()` on an `Unchanged` value"),
+		}
+	}
+
+	/// The change, panicking with the given message if there is none.
+	#[track_caller]
+	pub fn expect_changed(self, msg: &str) -> T {
+		match self {
+			Changed::Changed(x) => x,
+			Changed::Unchanged => panic!("{}", msg),
+		}
+	}
+
+	/// Iterate over the change, if there is one.
+	pub fn iter(&self) -> std::option::IntoIter<&T> {
+		match self {
+			Changed::Unchanged => None.into_iter(),
+			Changed::Changed(x) => Some(x).into_iter(),
+		}
+	}
+}
+
+impl<T> Changed<Vec<T>> {
+	/// A list of the given changes, which is unchanged if there are none.
+	/// This is how the changes gathered for the fields of a struct are
+	/// reported, and saves implementations of [`Comparable`] for collections
+	/// and other types from checking for themselves.
+	///
+	/// ```
+	/// # use comparable::*;
+	/// let mut changes = Vec::new();
+	/// changes.extend(1u8.comparison(&1));
+	/// assert_eq!(Changed::from_changes(changes), Changed::Unchanged);
+	/// assert_eq!(
+	///     Changed::from_changes([1u8, 2, 3].iter().zip(&[1u8, 5, 3]).flat_map(|(x, y)| x.comparison(y))),
+	///     Changed::Changed(vec![U8Change(2, 5)])
+	/// );
+	/// ```
+	pub fn from_changes<I: IntoIterator<Item = T>>(changes: I) -> Self {
+		let changes: Vec<T> = changes.into_iter().collect();
+		if changes.is_empty() {
+			Changed::Unchanged
+		} else {
+			Changed::Changed(changes)
+		}
+	}
+}
+
+impl<T: Debug> Changed<T> {
+	/// Panic, showing the change, if there is one. The panic is reported at
+	/// the call site.
+	///
+	/// ```
+	/// # use comparable::*;
+	/// 1u8.comparison(&1).unwrap_unchanged();
+	/// ```
+	#[track_caller]
+	pub fn unwrap_unchanged(self) {
+		if let Changed::Changed(x) = self {
+			panic!("called `Changed::unwrap_unchanged()` on a `Changed` value: {:?}", x)
+		}
+	}
 }
 
 impl<T: Default> Changed<T> {
@@ -72,6 +177,43 @@ impl<T> Iterator for Changed<T> {
 	}
 }
 
+impl<'a, T> IntoIterator for &'a Changed<T> {
+	type Item = &'a T;
+	type IntoIter = std::option::IntoIter<&'a T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+/// Gathering changes into a list, which is unchanged if they all are.
+impl<T> FromIterator<Changed<T>> for Changed<Vec<T>> {
+	fn from_iter<I: IntoIterator<Item = Changed<T>>>(iter: I) -> Self {
+		Changed::from_changes(iter.into_iter().flatten())
+	}
+}
+
+/// Adding changes to a list of changes leaves it changed if any were added.
+impl<T> Extend<T> for Changed<Vec<T>> {
+	fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+		let mut iter = iter.into_iter().peekable();
+		if iter.peek().is_none() {
+			return;
+		}
+		match self {
+			Changed::Unchanged => *self = Changed::Changed(iter.collect()),
+			Changed::Changed(changes) => changes.extend(iter),
+		}
+	}
+}
+
+/// The description and change reported for fields marked with
+/// `#[comparable_redact]`. It shows that such a field changed, without
+/// revealing either its old or its new value.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Redacted;
+
 pub trait Comparable {
 	/// Describes the type under consideration. For types that use
 	/// `#[derive(Comparable)]` this is a mirror of the type itself, where all
@@ -92,10 +234,73 @@ pub trait Comparable {
 	/// the complete set of differences looks like. This is used by the
 	/// `comparable::assert_changes` function so that tests can ensure that
 	/// what was expected to happen did happen -- and nothing more.
+	#[must_use]
 	fn comparison(&self, other: &Self) -> Changed<Self::Change>;
+
+	/// How alike two values are, from `0.0` for values with nothing in common
+	/// to `1.0` for values that are unchanged. This is used to recognize a
+	/// value that has moved within a collection while also changing, such as
+	/// an entry of a map found under a new key. Unless implemented otherwise,
+	/// values are either unchanged or have nothing in common; for types using
+	/// `#[derive(Comparable)]` it is the average similarity of their fields.
+	fn similarity(&self, other: &Self) -> f64 {
+		if self.differs(other) {
+			0.0
+		} else {
+			1.0
+		}
+	}
+
+	/// Whether two values differ, which is whether [`Comparable::comparison`]
+	/// reports any changes. This is implemented without building the changes
+	/// themselves for the standard collections and for types using
+	/// `#[derive(Comparable)]`, which stop at the first difference, making it
+	/// much cheaper when only the answer is needed.
+	fn differs(&self, other: &Self) -> bool {
+		!self.comparison(other).is_unchanged()
+	}
+
+	/// The number of values found to differ between two values, counting
+	/// fields, elements and entries individually, and one for each element or
+	/// entry added or removed, without building the changes themselves. This
+	/// is useful for metrics, or for acting only on more than a certain number
+	/// of changes. Elements of a `Vec` are counted position by position, as
+	/// when no [`DiffOptions`] are in effect.
+	///
+	/// [`DiffOptions`]: crate::DiffOptions
+	fn count_changes(&self, other: &Self) -> usize {
+		usize::from(self.differs(other))
+	}
+
+	/// The location of the first value found to differ between two values, or
+	/// `None` if they do not differ. Fields are searched in the order they are
+	/// declared, elements of a `Vec` by position, and entries of a map in the
+	/// order of their keys. Sets, and values that are not searched further,
+	/// are reported as a whole. See the [`location`] module.
+	///
+	/// [`location`]: crate::location
+	fn first_difference(&self, other: &Self) -> Option<Path> {
+		if self.differs(other) {
+			Some(Path::default())
+		} else {
+			None
+		}
+	}
+
+	/// A hash of the value, the same for any two values that do not differ,
+	/// or `None` if there is none, as unless implemented otherwise. It is
+	/// implemented for the scalars, strings and JSON values, so that long
+	/// sequences of them can be aligned, as by
+	/// [`DiffOptions::sequence_algorithm`], without comparing every element
+	/// of one with every element of the other.
+	///
+	/// [`DiffOptions::sequence_algorithm`]: crate::DiffOptions::sequence_algorithm
+	fn fingerprint(&self) -> Option<u64> {
+		None
+	}
 }
 
-impl<T: Comparable> Comparable for &T {
+impl<T: Comparable + ?Sized> Comparable for &T {
 	type Desc = T::Desc;
 
 	fn describe(&self) -> Self::Desc {
@@ -107,4 +312,84 @@ impl<T: Comparable> Comparable for &T {
 	fn comparison(&self, other: &Self) -> Changed<Self::Change> {
 		(*self).comparison(other)
 	}
+
+	fn similarity(&self, other: &Self) -> f64 {
+		(*self).similarity(other)
+	}
+
+	fn differs(&self, other: &Self) -> bool {
+		(*self).differs(other)
+	}
+
+	fn count_changes(&self, other: &Self) -> usize {
+		(*self).count_changes(other)
+	}
+
+	fn first_difference(&self, other: &Self) -> Option<Path> {
+		(*self).first_difference(other)
+	}
+
+	fn fingerprint(&self) -> Option<u64> {
+		(*self).fingerprint()
+	}
+}
+
+/// Report how `value` differs from the default value of its type.
+///
+/// ```
+/// # use comparable::*;
+/// assert_eq!(from_default(&vec![1]), Changed::Changed(vec![VecChange::Added(0, 1)]));
+/// ```
+#[must_use]
+pub fn from_default<T: Default + Comparable>(value: &T) -> Changed<T::Change> {
+	T::default().comparison(value)
+}
+
+/// The changes from `before` to `after`, as [`Comparable::comparison`]
+/// reports them.
+///
+/// ```
+/// # use comparable::*;
+/// assert_eq!(diff(&1u8, &2), Changed::Changed(U8Change(1, 2)));
+/// ```
+#[must_use]
+pub fn diff<T: Comparable + ?Sized>(before: &T, after: &T) -> Changed<T::Change> {
+	before.comparison(after)
+}
+
+/// The description of `value`, as [`Comparable::describe`] gives it.
+///
+/// ```
+/// # use comparable::*;
+/// assert_eq!(describe(&vec![1u8, 2]), vec![1, 2]);
+/// ```
+#[must_use]
+pub fn describe<T: Comparable + ?Sized>(value: &T) -> T::Desc {
+	value.describe()
+}
+
+/// Whether `before` and `after` differ, as [`Comparable::differs`] tells.
+///
+/// ```
+/// # use comparable::*;
+/// assert!(changed(&"a", &"b"));
+/// assert!(!changed(&"a", &"a"));
+/// ```
+#[must_use]
+pub fn changed<T: Comparable + ?Sized>(before: &T, after: &T) -> bool {
+	before.differs(after)
+}
+
+/// Comparison with a value of another type, such as an earlier version of the
+/// same structure, during a migration. This is implemented by
+/// `#[derive(Comparable)]` for each type named by a `comparable_from`
+/// attribute, by matching fields of the same name.
+pub trait ComparableFrom<Before> {
+	/// Reflects the changes from a value of type `Before` to a value of this
+	/// type.
+	type Change: PartialEq + Debug;
+
+	/// The changes from `before` to this value.
+	#[must_use]
+	fn comparison_from(&self, before: &Before) -> Changed<Self::Change>;
 }
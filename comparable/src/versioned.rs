@@ -0,0 +1,123 @@
+//! Versioning the serialized forms of generated types, so that descriptions
+//! and changes persisted by an older version of a type can still be read
+//! after the type has changed.
+//!
+//! With the `serde` feature enabled, `#[comparable_version = N]` makes the
+//! `Desc` and `Change` types generated for a type serialize as a pair of the
+//! version `N` and the value itself. When a value with a different version is
+//! deserialized, it is passed to [`Migrate::from_versioned`], which by default
+//! reports an error. Adding `#[comparable_migrate]` leaves [`Migrate`] to be
+//! implemented by hand, usually by deserializing a copy of the type as it was
+//! at that version and converting it:
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! use comparable::versioned::Migrate;
+//!
+//! #[derive(Comparable)]
+//! #[comparable_version = 2]
+//! #[comparable_migrate]
+//! struct Size(u64);
+//!
+//! impl Migrate for SizeDesc {
+//!     fn from_versioned<'de, D: serde::Deserializer<'de>>(version: u32, deserializer: D) -> Result<Self, D::Error> {
+//!         match version {
+//!             // Version 1 recorded sizes in kilobytes.
+//!             1 => Ok(SizeDesc(<u64 as serde::Deserialize>::deserialize(deserializer)? * 1024)),
+//!             _ => Err(serde::de::Error::custom("unknown version")),
+//!         }
+//!     }
+//! }
+//! # impl Migrate for SizeChange {}
+//!
+//! use serde::de::value::{Error, SeqDeserializer};
+//!
+//! // A size of 3 serialized at version 1.
+//! let serialized = SeqDeserializer::<_, Error>::new(vec![1u64, 3].into_iter());
+//! assert_eq!(<SizeDesc as serde::Deserialize>::deserialize(serialized), Ok(SizeDesc(3072)));
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::{self, DeserializeSeed, SeqAccess, Visitor};
+use serde::ser::SerializeTuple;
+use serde::{Deserializer, Serialize, Serializer};
+
+/// The version of a generated type, given by `#[comparable_version = N]`.
+pub trait Versioned {
+	const VERSION: u32;
+}
+
+/// Converting values serialized by other versions of a type into the current
+/// version of that type.
+pub trait Migrate: Sized {
+	/// Deserialize a value that was serialized at the given version, which is
+	/// not the current one.
+	fn from_versioned<'de, D: Deserializer<'de>>(version: u32, _deserializer: D) -> Result<Self, D::Error> {
+		Err(de::Error::custom(format!("cannot migrate from version {}", version)))
+	}
+}
+
+// The serialization of a versioned type without its version, as it would be
+// derived without `comparable_version`.
+#[doc(hidden)]
+pub trait SerializeUnversioned: Versioned {
+	fn serialize_unversioned<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+}
+
+#[doc(hidden)]
+pub trait DeserializeUnversioned<'de>: Versioned + Migrate {
+	fn deserialize_unversioned<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+}
+
+struct Unversioned<'a, T: ?Sized>(&'a T);
+
+impl<T: SerializeUnversioned + ?Sized> Serialize for Unversioned<'_, T> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.0.serialize_unversioned(serializer)
+	}
+}
+
+#[doc(hidden)]
+pub fn serialize<T: SerializeUnversioned + ?Sized, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+	let mut tuple = serializer.serialize_tuple(2)?;
+	tuple.serialize_element(&T::VERSION)?;
+	tuple.serialize_element(&Unversioned(value))?;
+	tuple.end()
+}
+
+#[doc(hidden)]
+pub fn deserialize<'de, T: DeserializeUnversioned<'de>, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+	deserializer.deserialize_tuple(2, VersionedVisitor(PhantomData))
+}
+
+struct VersionedVisitor<T>(PhantomData<T>);
+
+impl<'de, T: DeserializeUnversioned<'de>> Visitor<'de> for VersionedVisitor<T> {
+	type Value = T;
+
+	fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "a version followed by a value")
+	}
+
+	fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+		let version = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+		seq.next_element_seed(AtVersion(version, PhantomData))?.ok_or_else(|| de::Error::invalid_length(1, &self))
+	}
+}
+
+struct AtVersion<T>(u32, PhantomData<T>);
+
+impl<'de, T: DeserializeUnversioned<'de>> DeserializeSeed<'de> for AtVersion<T> {
+	type Value = T;
+
+	fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<T, D::Error> {
+		if self.0 == T::VERSION {
+			T::deserialize_unversioned(deserializer)
+		} else {
+			T::from_versioned(self.0, deserializer)
+		}
+	}
+}
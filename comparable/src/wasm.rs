@@ -0,0 +1,51 @@
+//! Comparing JSON values from JavaScript.
+//!
+//! With the `wasm` feature, [`diff_json`] is exported through `wasm-bindgen`,
+//! so that a web frontend built for `wasm32-unknown-unknown` compares
+//! documents exactly as a Rust backend does. Its arguments are read as
+//! `serde_json::Value`s, and the changes between them are returned as the
+//! [`ValueChange`] the backend would serialize as JSON, or `null` if there
+//! are none:
+//!
+//! ```js
+//! import { diff_json } from "./pkg/comparable.js";
+//!
+//! diff_json({ port: 80 }, { port: 8080 });
+//! // { Object: [{ Changed: ["port", { Replaced: [80, 8080] }] }] }
+//! ```
+//!
+//! Changes are found using the [`DiffOptions`](crate::DiffOptions) in effect
+//! on the calling thread, which are the defaults unless set from Rust.
+//!
+//! [`Journal::record`](crate::Journal::record) and
+//! [`AuditEntry::new`](crate::audit::AuditEntry::new) read the system clock,
+//! which panics on `wasm32-unknown-unknown`, and should not be used there.
+
+use serde::Serialize;
+use serde_json::Value;
+use wasm_bindgen::prelude::*;
+
+use crate::json::ValueChange;
+use crate::types::{Changed, Comparable};
+
+/// The changes from `a` to `b`, as a [`ValueChange`] written as plain
+/// JavaScript objects and arrays, or `null` if they are the same. Throws an
+/// error if either cannot be read as JSON, as with functions or `BigInt`s.
+#[wasm_bindgen]
+pub fn diff_json(a: JsValue, b: JsValue) -> JsValue {
+	let read = |value: JsValue| -> Value {
+		serde_wasm_bindgen::from_value(value).unwrap_or_else(|e| wasm_bindgen::throw_str(&e.to_string()))
+	};
+	match read(a).comparison(&read(b)) {
+		Changed::Unchanged => JsValue::NULL,
+		Changed::Changed(change) => write(&change),
+	}
+}
+
+// Objects are written as plain objects rather than `Map`s, as `JSON.parse`
+// would give them.
+fn write(change: &ValueChange) -> JsValue {
+	change
+		.serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+		.unwrap_or_else(|e| wasm_bindgen::throw_str(&e.to_string()))
+}
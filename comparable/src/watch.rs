@@ -0,0 +1,123 @@
+//! Notifying interested parties when parts of a value change.
+//!
+//! A [`Store`] holds a value, along with callbacks subscribed to parts of it,
+//! each named by a dotted field path, as in a
+//! [`FieldMask`](crate::mask::FieldMask). When the value is replaced using
+//! [`Store::update`], only the callbacks whose parts differ between the old
+//! and new values are called, so that each consumer of a large state hears
+//! only about what concerns it.
+//!
+//! ```
+//! # use comparable::*;
+//! # use comparable_derive::*;
+//! use std::cell::Cell;
+//! use std::rc::Rc;
+//! use comparable::watch::Store;
+//!
+//! #[derive(Comparable, Clone)]
+//! struct Settings {
+//!     theme: String,
+//!     volume: u8,
+//! }
+//!
+//! let mut store = Store::new(Settings { theme: "dark".to_string(), volume: 5 });
+//! let volume_changes = Rc::new(Cell::new(0));
+//! let counter = volume_changes.clone();
+//! store.subscribe("volume", move |_old, _new| counter.set(counter.get() + 1));
+//!
+//! store.update(Settings { theme: "light".to_string(), volume: 5 });
+//! assert_eq!(volume_changes.get(), 0);
+//! store.update(Settings { theme: "light".to_string(), volume: 7 });
+//! assert_eq!(volume_changes.get(), 1);
+//! ```
+
+use crate::mask::{with_field_mask, FieldMask};
+use crate::sink::ChangeSink;
+use crate::types::{Changed, Comparable};
+
+/// Identifies a subscription to a [`Store`], so that it can be cancelled.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct SubscriptionId(u64);
+
+// Called with the old and new values.
+type Callback<T> = Box<dyn FnMut(&T, &T)>;
+
+struct Subscription<T> {
+	id: SubscriptionId,
+	// `None` for a subscription to the whole value.
+	mask: Option<FieldMask>,
+	callback: Callback<T>,
+}
+
+/// A value, and the callbacks to call when parts of it change.
+pub struct Store<T> {
+	value: T,
+	subscriptions: Vec<Subscription<T>>,
+	next_id: u64,
+}
+
+impl<T: Comparable> Store<T> {
+	pub fn new(value: T) -> Self {
+		Store { value, subscriptions: Vec::new(), next_id: 0 }
+	}
+
+	/// The current value.
+	pub fn get(&self) -> &T {
+		&self.value
+	}
+
+	/// Call `callback` with the old and new values whenever the part of the
+	/// value at `path`, or anything within it, changes. The empty path
+	/// subscribes to every change.
+	pub fn subscribe(&mut self, path: &str, callback: impl FnMut(&T, &T) + 'static) -> SubscriptionId {
+		let id = SubscriptionId(self.next_id);
+		self.next_id += 1;
+		let mask = if path.is_empty() { None } else { Some(FieldMask::include([path])) };
+		self.subscriptions.push(Subscription { id, mask, callback: Box::new(callback) });
+		id
+	}
+
+	/// Send the changes to the part of the value at `path`, or anything within
+	/// it, to `sink` whenever they occur, as [`Store::subscribe`] would call
+	/// a callback. Changes that cannot be sent, such as when the receiving end
+	/// of a channel has been dropped, are discarded.
+	pub fn subscribe_sink<S: ChangeSink<T::Change> + 'static>(&mut self, path: &str, mut sink: S) -> SubscriptionId {
+		let mask = if path.is_empty() { None } else { Some(FieldMask::include([path])) };
+		self.subscribe(path, move |old, new| {
+			let changes = match &mask {
+				None => old.comparison(new),
+				Some(mask) => with_field_mask(mask, || old.comparison(new)),
+			};
+			if let Changed::Changed(changes) = changes {
+				let _ = sink.send_change(changes);
+			}
+		})
+	}
+
+	/// Cancel a subscription, returning whether it was still active.
+	pub fn unsubscribe(&mut self, id: SubscriptionId) -> bool {
+		let len = self.subscriptions.len();
+		self.subscriptions.retain(|subscription| subscription.id != id);
+		self.subscriptions.len() != len
+	}
+
+	/// Replace the value, calling the callbacks of every subscription whose
+	/// part of the value changed, in the order they were subscribed. Returns
+	/// the previous value.
+	pub fn update(&mut self, value: T) -> T {
+		let old = std::mem::replace(&mut self.value, value);
+		let new = &self.value;
+		if old.differs(new) {
+			for subscription in &mut self.subscriptions {
+				let changed = match &subscription.mask {
+					None => true,
+					Some(mask) => with_field_mask(mask, || old.differs(new)),
+				};
+				if changed {
+					(subscription.callback)(&old, new);
+				}
+			}
+		}
+		old
+	}
+}
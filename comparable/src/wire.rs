@@ -0,0 +1,101 @@
+//! The wire format of serialized changes, for programs in other languages
+//! that read or write them.
+//!
+//! With the `serde` feature enabled, descriptions and changes serialize
+//! through serde's data model as set out below, which is stable within a
+//! version of the format. The current version is [`VERSION`]; any change to
+//! the encodings listed here will increase it. The examples are written as
+//! JSON, which is how most decoders will see them, but the same structure
+//! applies to any self-describing format.
+//!
+//! # Changed
+//!
+//! `Changed::Unchanged` is `"Unchanged"`, and `Changed::Changed(x)` is
+//! `{"Changed": x}`, as serde represents enums, so that a change which is
+//! itself `null` is not mistaken for no change at all.
+//!
+//! # Library types
+//!
+//! | Type | Change |
+//! |------|--------|
+//! | scalars, `String`, `char` | `[before, after]` |
+//! | [`Redacted`](crate::Redacted) | `null` |
+//! | `Option<T>` | `{"BothSome": change}` or `{"Different": [before, after]}` |
+//! | `Vec<T>` | a list of `{"Added": [index, desc]}`, `{"Changed": [index, change]}`, `{"Removed": [index, desc]}` or `{"Moved": {"from": index, "to": index}}` |
+//! | sets | a list of `{"Added": desc}` or `{"Removed": desc}` |
+//! | maps | a list of `{"Added": [key, desc]}`, `{"Changed": [key, change]}`, `{"Removed": key}` or `{"Moved": {"from": key, "to": key, "change": change}}` |
+//! | `Range<T>` | a list of `{"Start": change}` or `{"End": change}` |
+//!
+//! Before and after values are descriptions, a number being its own
+//! description, and an absent `Option` is `null`.
+//!
+//! # Derived types
+//!
+//! The description of a derived type has the same shape as the type, with
+//! each field replaced by its description, so that a struct is an object of
+//! its named fields and an enum is externally tagged, as serde derives them.
+//!
+//! The change of a struct with several fields is a list of
+//! `{"Field": change}`, one for each field that changed in the order of the
+//! fields, where `Field` is the name of the field in upper camel case, or for
+//! a tuple struct `Field0`, `Field1` and so on. A struct with one named field
+//! has the change `{"field": change}`, and a tuple struct with one field has
+//! the change of that field.
+//!
+//! The change of an enum is `{"Different": [before, after]}` when the
+//! variants differ, and otherwise `{"BothVariant": changes}`, which holds a
+//! [`Changed`] value for each field of the variant, in the form the variant
+//! itself has: for `Variant { a, b }` an object `{"a": ..., "b": ...}`, for
+//! `Variant(a, b)` a list, and for `Variant(a)` the single value. A variant
+//! without fields is never changed.
+//!
+//! # Envelopes
+//!
+//! Changes sent to another program can be wrapped in an [`Envelope`],
+//! `{"version": 1, "changes": "Unchanged"}` or
+//! `{"version": 1, "changes": {"Changed": change}}`, so that a reader can
+//! refuse a version it does not understand rather than misreading it:
+//!
+//! ```
+//! # use comparable::*;
+//! use comparable::wire::Envelope;
+//!
+//! let envelope = Envelope::new(1u8.comparison(&2u8));
+//! assert_eq!(envelope.version, comparable::wire::VERSION);
+//! assert_eq!(envelope.changes, Changed::Changed(U8Change(1, 2)));
+//! ```
+
+use serde::de::{self, Deserializer};
+use serde::{Deserialize, Serialize};
+
+use crate::types::Changed;
+
+/// The version of the wire format described by this module.
+pub const VERSION: u32 = 1;
+
+/// Changes together with the version of the wire format they were
+/// serialized with. Deserializing an envelope of any other version fails.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(bound(serialize = "C: Serialize", deserialize = "C: Deserialize<'de>"))]
+pub struct Envelope<C> {
+	#[serde(deserialize_with = "supported")]
+	pub version: u32,
+	#[serde(with = "crate::changed::tagged")]
+	pub changes: Changed<C>,
+}
+
+impl<C> Envelope<C> {
+	/// Wrap changes in an envelope of the current version.
+	pub fn new(changes: Changed<C>) -> Self {
+		Envelope { version: VERSION, changes }
+	}
+}
+
+fn supported<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+	let version = u32::deserialize(deserializer)?;
+	if version == VERSION {
+		Ok(version)
+	} else {
+		Err(de::Error::custom(format!("unsupported wire format version {}", version)))
+	}
+}
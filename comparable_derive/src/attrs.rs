@@ -1,6 +1,7 @@
-use quote::format_ident;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
 
-use crate::utils::has_attr;
+use crate::utils::{crate_path, has_attr, local, rebind_self};
 
 pub struct Attributes {
 	pub describe_type: Option<syn::Type>,
@@ -9,10 +10,153 @@ pub struct Attributes {
 	pub self_describing: bool,
 	pub variant_struct_fields: bool,
 	pub compare_default: bool,
+	pub comparable_from_default: bool,
+	pub comparable_desc_roundtrip: bool,
+	pub comparable_transparent: bool,
 	pub comparable_public: bool,
 	pub comparable_private: bool,
 	pub comparable_desc_suffix: syn::Ident,
 	pub comparable_change_suffix: syn::Ident,
+	pub comparable_module: Option<syn::Ident>,
+	pub comparable_version: Option<syn::LitInt>,
+	pub comparable_migrate: bool,
+	pub comparable_schema: bool,
+	pub comparable_paths: bool,
+	pub comparable_lens: bool,
+	pub comparable_sql: Option<ComparableSql>,
+	pub comparable_display: bool,
+	pub comparable_tree: bool,
+	pub comparable_transitions: bool,
+	pub comparable_variant_only: bool,
+	pub comparable_try: bool,
+	pub non_exhaustive: bool,
+	pub comparable_from: Vec<ComparableFrom>,
+	pub comparable_bound: Option<Vec<syn::WherePredicate>>,
+}
+
+// The argument of `#[comparable_from(Before, removed(a, b))]`, naming another
+// struct that this one is compared with, and the fields of that struct that
+// this one lacks.
+pub struct ComparableFrom {
+	pub before: syn::Path,
+	pub removed: Vec<syn::Ident>,
+}
+
+impl syn::parse::Parse for ComparableFrom {
+	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+		let before = input.parse()?;
+		let mut removed = Vec::new();
+		if input.parse::<Option<syn::Token![,]>>()?.is_some() {
+			let keyword = input.parse::<syn::Ident>()?;
+			if keyword != "removed" {
+				return Err(syn::Error::new(keyword.span(), "expected `removed(...)`"));
+			}
+			let fields;
+			syn::parenthesized!(fields in input);
+			removed = fields.parse_terminated::<_, syn::Token![,]>(syn::Ident::parse)?.into_iter().collect();
+		}
+		Ok(ComparableFrom { before, removed })
+	}
+}
+
+// The arguments of `#[comparable_sql(table = "users", key = "id")]`, naming
+// the table holding the struct's rows, and the field holding their keys.
+pub struct ComparableSql {
+	pub table: String,
+	pub key: syn::Ident,
+}
+
+impl ComparableSql {
+	fn from(attr: &syn::Attribute) -> Self {
+		let usage = "comparable_sql must be written as #[comparable_sql(table = \"name\", key = \"field\")]";
+		let args = match attr.parse_meta() {
+			Ok(syn::Meta::List(list)) => list.nested,
+			_ => panic!("{}", usage),
+		};
+		let (mut table, mut key) = (None, None);
+		for arg in args {
+			match arg {
+				syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+					path,
+					lit: syn::Lit::Str(value),
+					..
+				})) => {
+					if path.is_ident("table") {
+						table = Some(value.value());
+					} else if path.is_ident("key") {
+						key = Some(value.parse::<syn::Ident>().expect("The key of comparable_sql must name a field"));
+					} else {
+						panic!("{}", usage);
+					}
+				}
+				_ => panic!("{}", usage),
+			}
+		}
+		match (table, key) {
+			(Some(table), Some(key)) => ComparableSql { table, key },
+			_ => panic!("{}", usage),
+		}
+	}
+}
+
+// The arguments of `#[comparable_by(desc = "self.name()", eq = "self.id()")]`,
+// for a field whose type cannot implement `Comparable`, such as a trait
+// object. In each expression, `self` stands for a reference to the field's
+// value. The field is described by `desc`, converted to a string, and differs
+// when `eq` does, or when its description does if `eq` is not given.
+pub struct ComparableBy {
+	pub desc: TokenStream,
+	pub eq: Option<TokenStream>,
+}
+
+impl ComparableBy {
+	pub fn from(attr: &syn::Attribute) -> Self {
+		let usage = "comparable_by must be written as #[comparable_by(desc = \"...\", eq = \"...\")]";
+		let args = match attr.parse_meta() {
+			Ok(syn::Meta::List(list)) => list.nested,
+			_ => panic!("{}", usage),
+		};
+		let expr = |value: &syn::LitStr| {
+			let expr = value.parse::<syn::Expr>().unwrap_or_else(|err| panic!("{}: {}", usage, err));
+			rebind_self(quote!(#expr), &local("__value"))
+		};
+		let (mut desc, mut eq) = (None, None);
+		for arg in args {
+			match arg {
+				syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+					path,
+					lit: syn::Lit::Str(value),
+					..
+				})) => {
+					if path.is_ident("desc") {
+						desc = Some(expr(&value));
+					} else if path.is_ident("eq") {
+						eq = Some(expr(&value));
+					} else {
+						panic!("{}", usage);
+					}
+				}
+				_ => panic!("{}", usage),
+			}
+		}
+		ComparableBy { desc: desc.unwrap_or_else(|| panic!("{}", usage)), eq }
+	}
+
+	// The field's value, given by a reference to it, wrapped so that it can be
+	// compared.
+	pub fn wrap(&self, value: &TokenStream) -> TokenStream {
+		let krate = crate_path();
+		let desc = &self.desc;
+		let eq = self.eq.as_ref().map(|eq| quote!(#eq)).unwrap_or_else(|| quote!(ToString::to_string(&(#desc))));
+		let var = local("__value");
+		quote! {
+			#krate::derive_support::by(
+				#value,
+				|#var| ToString::to_string(&(#desc)),
+				|#var| #eq,
+			)
+		}
+	}
 }
 
 impl Attributes {
@@ -28,14 +172,59 @@ impl Attributes {
 
 			variant_struct_fields: has_attr(attrs, "variant_struct_fields").is_some(),
 			compare_default: has_attr(attrs, "compare_default").is_some(),
+			comparable_from_default: has_attr(attrs, "comparable_from_default").is_some(),
+			comparable_desc_roundtrip: has_attr(attrs, "comparable_desc_roundtrip").is_some(),
+			comparable_transparent: has_attr(attrs, "comparable_transparent").is_some(),
 
 			comparable_public: has_attr(attrs, "comparable_public").is_some(),
 			comparable_private: has_attr(attrs, "comparable_private").is_some(),
 
 			comparable_desc_suffix: attr_to_ident(attrs, "comparable_desc_suffix", "Desc"),
 			comparable_change_suffix: attr_to_ident(attrs, "comparable_change_suffix", "Change"),
+			comparable_module: name_value_ident(attrs, "comparable_module"),
+			comparable_version: has_attr(attrs, "comparable_version").map(|attr| match attr.parse_meta() {
+				Ok(syn::Meta::NameValue(syn::MetaNameValue { lit: syn::Lit::Int(version), .. })) => version,
+				_ => panic!("comparable_version must be written as #[comparable_version = N]"),
+			}),
+			comparable_migrate: has_attr(attrs, "comparable_migrate").is_some(),
+			comparable_schema: has_attr(attrs, "comparable_schema").is_some(),
+			comparable_paths: has_attr(attrs, "comparable_paths").is_some(),
+			comparable_lens: has_attr(attrs, "comparable_lens").is_some(),
+			comparable_sql: has_attr(attrs, "comparable_sql").map(ComparableSql::from),
+			comparable_display: has_attr(attrs, "comparable_display").is_some(),
+			comparable_tree: has_attr(attrs, "comparable_tree").is_some(),
+			comparable_transitions: has_attr(attrs, "comparable_transitions").is_some(),
+			comparable_variant_only: has_attr(attrs, "comparable_variant_only").is_some(),
+			comparable_try: has_attr(attrs, "comparable_try").is_some(),
+			non_exhaustive: has_attr(attrs, "non_exhaustive").is_some(),
+			comparable_from: attrs
+				.iter()
+				.filter(|attr| attr.path.is_ident("comparable_from"))
+				.map(|attr| {
+					attr.parse_args::<ComparableFrom>()
+						.unwrap_or_else(|err| panic!("Failed to parse \"comparable_from\" attribute: {}", err))
+				})
+				.collect(),
+			comparable_bound: has_attr(attrs, "comparable_bound").map(|_| {
+				attrs
+					.iter()
+					.filter(|attr| attr.path.is_ident("comparable_bound"))
+					.flat_map(|attr| {
+						attr.parse_args_with(
+							syn::punctuated::Punctuated::<syn::WherePredicate, syn::Token![,]>::parse_terminated,
+						)
+						.unwrap_or_else(|err| panic!("Failed to parse \"comparable_bound\" attribute: {}", err))
+					})
+					.collect()
+			}),
 		}
 	}
+
+	// The version given by `comparable_version`, and whether `comparable_migrate`
+	// was given with it.
+	pub fn versioning(&self) -> Option<(&syn::LitInt, bool)> {
+		self.comparable_version.as_ref().map(|version| (version, self.comparable_migrate))
+	}
 }
 
 fn attr_to_ident(attrs: &[syn::Attribute], name: &str, suffix: &str) -> syn::Ident {
@@ -43,3 +232,13 @@ fn attr_to_ident(attrs: &[syn::Attribute], name: &str, suffix: &str) -> syn::Ide
 		.map(|x| x.parse_args::<syn::Ident>().unwrap_or_else(|_| panic!("Failed to parse \"{}\" attribute", name)))
 		.unwrap_or_else(|| format_ident!("{}", suffix))
 }
+
+// Attributes written as `#[name = "Ident"]`.
+pub fn name_value_ident(attrs: &[syn::Attribute], name: &str) -> Option<syn::Ident> {
+	has_attr(attrs, name).map(|attr| match attr.parse_meta() {
+		Ok(syn::Meta::NameValue(syn::MetaNameValue { lit: syn::Lit::Str(value), .. })) => {
+			value.parse::<syn::Ident>().unwrap_or_else(|_| panic!("Argument to {} must be a valid identifier", name))
+		}
+		_ => panic!("{} must be written as #[{} = \"Name\"]", name, name),
+	})
+}
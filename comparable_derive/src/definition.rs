@@ -26,13 +26,181 @@ impl quote::ToTokens for Definition {
 
 impl Definition {
 	pub fn assoc_type(ty: &syn::Type, name: &str) -> syn::Type {
+		let krate = crate_path();
 		let ident = format_ident!("{}", name);
-		syn::parse2(quote!(<#ty as comparable::Comparable>::#ident))
+		syn::parse2(quote!(<#ty as #krate::Comparable>::#ident))
 			.unwrap_or_else(|_| panic!("Failed to parse associated type"))
 	}
 
+	pub fn trait_assoc_type(trait_path: &TokenStream, ty: &syn::Type, name: &str) -> syn::Type {
+		let ident = format_ident!("{}", name);
+		syn::parse2(quote!(<#ty as #trait_path>::#ident)).unwrap_or_else(|_| panic!("Failed to parse associated type"))
+	}
+
+	// A field or enum variant may provide its own description using
+	// `describe_type` and `describe_body`, exactly as a whole type can. The
+	// body is evaluated with `self` bound to the value containing it.
+	pub fn describe_attrs(attrs: &[syn::Attribute], name: &dyn std::fmt::Display) -> Option<(syn::Type, syn::Expr)> {
+		let attrs = Attributes::from(attrs);
+		match (attrs.describe_type, attrs.describe_body) {
+			(Some(ty), Some(body)) => Some((ty, body)),
+			(None, None) => None,
+			_ => panic!("{} must use both describe_type and describe_body, or neither", name),
+		}
+	}
+
+	fn field_name(field: &syn::Field) -> String {
+		field.ident.as_ref().map(|ident| format!("Field {}", ident)).unwrap_or_else(|| "Unnamed field".to_string())
+	}
+
+	// Fields marked with `comparable_redact` are still compared, but both their
+	// descriptions and their changes are replaced by `comparable::Redacted`.
+	pub fn is_redacted(field: &syn::Field) -> bool {
+		has_attr(&field.attrs, "comparable_redact").is_some()
+	}
+
+	// Fields marked with `comparable_by` are compared by projections of their
+	// values, and are described by strings.
+	pub fn compared_by(field: &syn::Field) -> Option<ComparableBy> {
+		has_attr(&field.attrs, "comparable_by").map(ComparableBy::from)
+	}
+
+	// Fields marked with `comparable_leaf` are compared as a whole, as though
+	// wrapped in `comparable::Leaf`, so that their types need not implement
+	// `Comparable`.
+	pub fn is_leaf(field: &syn::Field) -> bool {
+		has_attr(&field.attrs, "comparable_leaf").is_some()
+	}
+
+	// The type that a field is compared as.
+	pub fn compared_type(field: &syn::Field) -> syn::Type {
+		let krate = crate_path();
+		let ty = &field.ty;
+		if Self::is_leaf(field) {
+			syn::parse2(quote!(#krate::Leaf<#ty>)).expect("Failed to parse Leaf type")
+		} else {
+			ty.clone()
+		}
+	}
+
+	// The field's value, given by a reference to it, wrapped so that it can be
+	// compared, when its own type is not what it is compared as.
+	fn wrapped(field: &syn::Field, value: &TokenStream) -> Option<TokenStream> {
+		let krate = crate_path();
+		match Self::compared_by(field) {
+			Some(by) => Some(by.wrap(value)),
+			None if Self::is_leaf(field) => {
+				let ty = &field.ty;
+				Some(quote!(#krate::derive_support::leaf::<#ty>(#value)))
+			}
+			None => None,
+		}
+	}
+
+	fn redacted_type() -> syn::Type {
+		let krate = crate_path();
+		syn::parse2(quote!(#krate::Redacted)).expect("Failed to parse Redacted type")
+	}
+
+	// Fields marked with `comparable_box`, which includes any field whose type
+	// refers to the type containing it, have their descriptions and changes
+	// boxed, so that the generated types for recursive types are finite.
+	pub fn is_boxed(field: &syn::Field) -> bool {
+		has_attr(&field.attrs, "comparable_box").is_some()
+	}
+
+	fn boxed_type(field: &syn::Field, ty: syn::Type) -> syn::Type {
+		if Self::is_boxed(field) {
+			syn::parse2(quote!(Box<#ty>)).expect("Failed to parse Box type")
+		} else {
+			ty
+		}
+	}
+
+	pub fn field_desc_type(describe_trait: &TokenStream, field: &syn::Field) -> syn::Type {
+		if Self::is_redacted(field) {
+			return Self::redacted_type();
+		}
+		if Self::compared_by(field).is_some() {
+			return syn::parse2(quote!(String)).expect("Failed to parse String type");
+		}
+		Self::boxed_type(
+			field,
+			Self::describe_attrs(&field.attrs, &Self::field_name(field))
+				.map(|(ty, _)| ty)
+				.unwrap_or_else(|| Self::trait_assoc_type(describe_trait, &Self::compared_type(field), "Desc")),
+		)
+	}
+
+	// `value` is a reference to the field's value.
+	pub fn field_describe(describe_trait: &TokenStream, field: &syn::Field, value: &TokenStream) -> TokenStream {
+		let krate = crate_path();
+		if Self::is_redacted(field) {
+			return quote!(#krate::Redacted);
+		}
+		let describe = match (Self::wrapped(field, value), Self::describe_attrs(&field.attrs, &Self::field_name(field)))
+		{
+			(Some(wrapped), _) => quote!(#krate::Comparable::describe(&#wrapped)),
+			(None, Some((_, body))) => quote!(#body),
+			(None, None) => quote!(#describe_trait::describe(#value)),
+		};
+		if Self::is_boxed(field) {
+			quote!(Box::new(#describe))
+		} else {
+			describe
+		}
+	}
+
+	pub fn field_change_type(field: &syn::Field) -> syn::Type {
+		let krate = crate_path();
+		if Self::is_redacted(field) {
+			Self::redacted_type()
+		} else if Self::compared_by(field).is_some() {
+			Self::boxed_type(
+				field,
+				syn::parse2(quote!(#krate::StringChange)).expect("Failed to parse StringChange type"),
+			)
+		} else {
+			Self::boxed_type(field, Self::assoc_type(&Self::compared_type(field), "Change"))
+		}
+	}
+
+	// A field marked with `comparable_flatten`, in a struct with several
+	// fields, contributes each of the changes in its own list of changes as a
+	// separate change to the containing struct.
+	pub fn is_flattened(field: &syn::Field) -> bool {
+		has_attr(&field.attrs, "comparable_flatten").is_some()
+	}
+
+	// `self_value` is the field's value, and `other_value` a reference to the
+	// value it is compared with. The comparison is skipped when the field is
+	// masked out by a runtime field mask.
+	pub fn field_comparison(
+		index: usize,
+		field: &syn::Field,
+		self_value: &TokenStream,
+		other_value: &TokenStream,
+	) -> TokenStream {
+		let krate = crate_path();
+		let self_value = Self::wrapped(field, &quote!(&#self_value)).unwrap_or_else(|| self_value.clone());
+		let other_value = match Self::wrapped(field, other_value) {
+			Some(wrapped) => quote!(&#wrapped),
+			None => other_value.clone(),
+		};
+		let comparison = if Self::is_redacted(field) {
+			quote!(#self_value.comparison(#other_value).map(|_| #krate::Redacted))
+		} else if Self::is_boxed(field) {
+			quote!(#self_value.comparison(#other_value).map(Box::new))
+		} else {
+			quote!(#self_value.comparison(#other_value))
+		};
+		let name = field.ident.as_ref().map(ToString::to_string).unwrap_or_else(|| index.to_string());
+		quote!(#krate::derive_support::masked(#name, || #comparison))
+	}
+
 	pub fn changed_type(ty: &syn::Type) -> syn::Type {
-		syn::parse2(quote!(comparable::Changed<#ty>)).unwrap_or_else(|_| panic!("Failed to parse Changed type"))
+		let krate = crate_path();
+		syn::parse2(quote!(#krate::Changed<#ty>)).unwrap_or_else(|_| panic!("Failed to parse Changed type"))
 	}
 
 	pub fn variant_name_from_field(index: usize, name: &Option<syn::Ident>) -> syn::Ident {
@@ -43,21 +211,413 @@ impl Definition {
 		}
 	}
 
+	// The name given by `#[comparable_rename = "Name"]` on a field or variant.
+	pub fn renamed(attrs: &[syn::Attribute]) -> Option<syn::Ident> {
+		name_value_ident(attrs, "comparable_rename")
+	}
+
+	// The name of the variant holding a field's changes, in the `Change` type
+	// of a struct with several fields.
+	pub fn field_variant_name(r: &FieldRef) -> syn::Ident {
+		Self::renamed(&r.field.attrs).unwrap_or_else(|| Self::variant_name_from_field(r.index, &r.field.ident))
+	}
+
+	// The name of the variant holding an enum variant's changes, when both
+	// values are of that variant.
+	pub fn both_variant_name(variant: &syn::Variant) -> syn::Ident {
+		Self::renamed(&variant.attrs).unwrap_or_else(|| format_ident!("Both{}", &variant.ident))
+	}
+
+	//
+	// Transparent newtypes
+	//
+	// A struct marked with `comparable_transparent` uses the `Desc` and
+	// `Change` types of its only field, rather than generating its own.
+	pub fn transparent_field(inputs: &Inputs) -> (syn::Type, syn::Member) {
+		let mut fields = match &inputs.input.data {
+			syn::Data::Struct(st) => map_fields(false, st.fields.iter(), true, |r| {
+				(r.field.ty.clone(), Self::field_member(r.index, r.field))
+			}),
+			_ => Vec::new(),
+		};
+		if fields.len() != 1 {
+			panic!("comparable_transparent requires a struct with exactly one field");
+		}
+		fields.remove(0)
+	}
+
+	pub fn generate_transparent_desc_type(inputs: &Inputs) -> Self {
+		let describe_trait = &inputs.describe_trait;
+		let (ty, name) = Self::transparent_field(inputs);
+		Self {
+			ty: Some(Self::trait_assoc_type(describe_trait, &ty, "Desc")),
+			definition: None,
+			method_body: quote!(#describe_trait::describe(&self.#name)),
+		}
+	}
+
+	pub fn generate_transparent_change_type(inputs: &Inputs) -> Self {
+		let (ty, name) = Self::transparent_field(inputs);
+		Self {
+			ty: Some(Self::assoc_type(&ty, "Change")),
+			definition: None,
+			method_body: quote!(self.#name.comparison(&other.#name)),
+		}
+	}
+
+	// The body of `similarity`, which is the average similarity of the fields
+	// compared, or `None` to keep the default.
+	pub fn generate_similarity_body(inputs: &Inputs) -> Option<TokenStream> {
+		let method = FieldMethod {
+			name: quote!(similarity),
+			masked_out: None,
+			in_field: None,
+			different: quote!(0.0),
+			empty: quote!(1.0),
+		};
+		Self::generate_field_method_body(inputs, &method, |similarities| {
+			if similarities.len() == 1 {
+				similarities[0].clone()
+			} else {
+				let count = similarities.len() as f64;
+				quote!((#(#similarities)+*) / #count)
+			}
+		})
+	}
+
+	// The body of `differs`, which stops at the first field that differs.
+	pub fn generate_differs_body(inputs: &Inputs) -> Option<TokenStream> {
+		let method = FieldMethod {
+			name: quote!(differs),
+			masked_out: Some(quote!(false)),
+			in_field: None,
+			different: quote!(true),
+			empty: quote!(false),
+		};
+		Self::generate_field_method_body(inputs, &method, |differs| quote!(#(#differs)||*))
+	}
+
+	// The body of `count_changes`, which is the total count of the fields.
+	pub fn generate_count_changes_body(inputs: &Inputs) -> Option<TokenStream> {
+		let method = FieldMethod {
+			name: quote!(count_changes),
+			masked_out: Some(quote!(0)),
+			in_field: None,
+			different: quote!(1),
+			empty: quote!(0),
+		};
+		Self::generate_field_method_body(inputs, &method, |counts| quote!(#(#counts)+*))
+	}
+
+	// The body of `first_difference`, which is the path of the first field that
+	// differs, placed within that field.
+	pub fn generate_first_difference_body(inputs: &Inputs) -> Option<TokenStream> {
+		let krate = crate_path();
+		let method = FieldMethod {
+			name: quote!(first_difference),
+			masked_out: Some(quote!(None)),
+			in_field: Some(|field_name, call| {
+				let krate = crate_path();
+				quote!(#call.map(|path| path.within(#krate::location::Segment::Field(#field_name.to_string()))))
+			}),
+			different: quote!(Some(#krate::location::Path::default())),
+			empty: quote!(None),
+		};
+		Self::generate_field_method_body(inputs, &method, |paths| {
+			let (first, rest) = paths.split_first().expect("at least one field");
+			quote!(#first #(.or_else(|| #rest))*)
+		})
+	}
+
+	// The body of a method of `Comparable` taking `self` and `other`, which
+	// calls that method for each field compared and combines the results, or
+	// `None` to keep the default when there are no fields.
+	fn generate_field_method_body(
+		inputs: &Inputs,
+		method: &FieldMethod,
+		combine: impl Fn(&[TokenStream]) -> TokenStream,
+	) -> Option<TokenStream> {
+		let krate = crate_path();
+		let FieldMethod { name, masked_out, in_field, different, empty } = method;
+		let call = |index: usize, field: &syn::Field, self_value: TokenStream, other_value: TokenStream| {
+			let field_name = field.ident.as_ref().map(ToString::to_string).unwrap_or_else(|| index.to_string());
+			let (self_value, other_value) =
+				match (Self::wrapped(field, &self_value), Self::wrapped(field, &other_value)) {
+					(Some(self_wrapped), Some(other_wrapped)) => (quote!(&#self_wrapped), quote!(&#other_wrapped)),
+					_ => (self_value, other_value),
+				};
+			let call = quote!(#krate::Comparable::#name(#self_value, #other_value));
+			let call = match masked_out {
+				Some(masked_out) => quote!(#krate::derive_support::masked_or(#field_name, #masked_out, || #call)),
+				None => call,
+			};
+			match in_field {
+				Some(in_field) => in_field(&field_name, call),
+				None => call,
+			}
+		};
+		match &inputs.input.data {
+			syn::Data::Struct(_) if inputs.attrs.comparable_transparent => {
+				let (_, member) = Self::transparent_field(inputs);
+				Some(quote!(#krate::Comparable::#name(&self.#member, &other.#member)))
+			}
+			syn::Data::Struct(st) => {
+				let calls = map_fields(true, st.fields.iter(), true, |r| {
+					let self_value = (*r.accessor)(&format_ident!("self"));
+					let other_value = (*r.accessor)(&format_ident!("other"));
+					call(r.index, r.field, quote!(&#self_value), quote!(&#other_value))
+				});
+				if calls.is_empty() {
+					None
+				} else {
+					Some(combine(&calls))
+				}
+			}
+			syn::Data::Enum(en) if !en.variants.is_empty() && inputs.attrs.comparable_variant_only => {
+				Some(compare_variants(empty, different))
+			}
+			syn::Data::Enum(en) if !en.variants.is_empty() => {
+				let branches = en.variants.iter().map(|variant| {
+					let variant_name = &variant.ident;
+					let fields = map_fields(false, variant.fields.iter(), false, |r| {
+						let self_var = local(&format!("self_var{}", r.index));
+						let other_var = local(&format!("other_var{}", r.index));
+						if is_ignored(r.field) {
+							(quote!(_), quote!(_), None)
+						} else {
+							let call = call(r.index, r.field, quote!(#self_var), quote!(#other_var));
+							(quote!(#self_var), quote!(#other_var), Some(call))
+						}
+					});
+					let calls = fields.iter().filter_map(|(_, _, call)| call.clone()).collect::<Vec<_>>();
+					let self_vars = fields.iter().map(|(v, _, _)| v);
+					let other_vars = fields.iter().map(|(_, v, _)| v);
+					let (self_capture, other_capture) = match &variant.fields {
+						syn::Fields::Named(named) => {
+							let names = named.named.iter().map(|f| &f.ident).collect::<Vec<_>>();
+							(quote!({ #(#names: #self_vars),* }), quote!({ #(#names: #other_vars),* }))
+						}
+						syn::Fields::Unnamed(_) => (quote!((#(#self_vars),*)), quote!((#(#other_vars),*))),
+						syn::Fields::Unit => (quote!(), quote!()),
+					};
+					let result = if calls.is_empty() { empty.clone() } else { combine(&calls) };
+					quote!((Self::#variant_name #self_capture, Self::#variant_name #other_capture) => #result)
+				});
+				let otherwise = if en.variants.len() > 1 {
+					quote!(_ => #different,)
+				} else if inputs.attrs.non_exhaustive {
+					quote!(#[allow(unreachable_patterns)] _ => #different,)
+				} else {
+					quote!()
+				};
+				Some(quote! {
+					match (self, other) {
+						#(#branches,)*
+						#otherwise
+					}
+				})
+			}
+			_ => None,
+		}
+	}
+
+	pub fn field_member(index: usize, field: &syn::Field) -> syn::Member {
+		field.ident.clone().map(syn::Member::Named).unwrap_or_else(|| syn::Member::Unnamed(syn::Index::from(index)))
+	}
+
+	//
+	// Schema
+	//
+	// With `comparable_schema`, the schema lists the fields that are compared,
+	// by the names used in field masks, with redacted fields described as
+	// `comparable::Redacted`.
+	pub fn generate_schema_body(inputs: &Inputs) -> TokenStream {
+		let krate = crate_path();
+		let field_schema = |r: &FieldRef| {
+			let name = r.field.ident.as_ref().map(ToString::to_string).unwrap_or_else(|| r.index.to_string());
+			let ty = if Self::is_redacted(r.field) {
+				Self::redacted_type()
+			} else if Self::compared_by(r.field).is_some() {
+				syn::parse2(quote!(String)).expect("Failed to parse String type")
+			} else {
+				Self::compared_type(r.field)
+			};
+			quote!(#krate::schema::Field::new(#name, <#ty as #krate::schema::HasSchema>::schema()))
+		};
+		let name = inputs.input.ident.to_string();
+		let body = match &inputs.input.data {
+			syn::Data::Struct(_) if inputs.attrs.comparable_transparent => {
+				let (ty, _) = Self::transparent_field(inputs);
+				return quote!(<#ty as #krate::schema::HasSchema>::schema());
+			}
+			syn::Data::Struct(st) => {
+				let fields = map_fields(true, st.fields.iter(), true, field_schema);
+				quote! {
+					#krate::schema::Schema::Struct {
+						name: #name.to_string(),
+						fields: vec![#(#fields),*],
+					}
+				}
+			}
+			syn::Data::Enum(en) => {
+				let variants = en.variants.iter().map(|variant| {
+					let variant_name = variant.ident.to_string();
+					let fields = map_fields(false, variant.fields.iter(), true, field_schema);
+					quote!(#krate::schema::Variant::new(#variant_name, vec![#(#fields),*]))
+				});
+				quote! {
+					#krate::schema::Schema::Enum {
+						name: #name.to_string(),
+						variants: vec![#(#variants),*],
+					}
+				}
+			}
+			syn::Data::Union(_) => panic!("Comparable derivation not available for unions"),
+		};
+		quote!(#krate::derive_support::named_schema::<Self>(#name, || #body))
+	}
+
+	//
+	// Desc round trip
+	//
+	// With `comparable_desc_roundtrip`, values are converted to and from their
+	// descriptions field by field, which requires every field to be fully
+	// described. Ignored marker fields, such as `PhantomData`, are rebuilt
+	// using `Default`.
+	pub fn generate_desc_roundtrip(inputs: &Inputs) -> (TokenStream, TokenStream) {
+		let krate = crate_path();
+		let attrs = &inputs.attrs;
+		if attrs.no_description
+			|| attrs.compare_default
+			|| attrs.describe_type.is_some()
+			|| attrs.describe_body.is_some()
+		{
+			panic!("comparable_desc_roundtrip requires a generated Desc type");
+		}
+		if attrs.self_describing {
+			return (quote!(desc), quote!(self));
+		}
+		let type_name = &inputs.input.ident;
+		let desc_name = format_ident!("{}{}", type_name, attrs.comparable_desc_suffix);
+		let (from_arms, into_arms): (Vec<TokenStream>, Vec<TokenStream>) = match &inputs.input.data {
+			syn::Data::Struct(st) if attrs.comparable_transparent => {
+				let (_, member) = Self::transparent_field(inputs);
+				let defaults = Self::roundtrip_defaults(&st.fields);
+				return (
+					quote!(#type_name { #member: #krate::roundtrip::FromDesc::from_desc(desc), #(#defaults),* }),
+					quote!(#krate::roundtrip::IntoDesc::into_desc(self.#member)),
+				);
+			}
+			syn::Data::Struct(st) => vec![Self::roundtrip_arms(&st.fields, &quote!(#type_name), &quote!(#desc_name))],
+			syn::Data::Enum(en) => map_variants(en.variants.iter(), |variant| {
+				if has_attr(&variant.attrs, "no_description").is_some()
+					|| Self::describe_attrs(&variant.attrs, &format!("Variant {}", variant.ident)).is_some()
+				{
+					panic!("comparable_desc_roundtrip requires variant {} to be fully described", variant.ident);
+				}
+				let variant_name = &variant.ident;
+				Self::roundtrip_arms(
+					&variant.fields,
+					&quote!(#type_name::#variant_name),
+					&quote!(#desc_name::#variant_name),
+				)
+			}),
+			syn::Data::Union(_un) => panic!("comparable_desc_roundtrip not implemented for unions"),
+		}
+		.into_iter()
+		.unzip();
+		(
+			quote! {
+				match desc {
+					#(#from_arms),*
+				}
+			},
+			quote! {
+				match self {
+					#(#into_arms),*
+				}
+			},
+		)
+	}
+
+	// Ignored marker fields are absent from descriptions, and are rebuilt using
+	// `Default`. Any other field that is not fully described cannot be
+	// recovered from the description at all.
+	fn roundtrip_defaults(fields: &syn::Fields) -> Vec<TokenStream> {
+		for field in fields {
+			if has_attr(&field.attrs, "comparable_ignore").is_some()
+				|| has_attr(&field.attrs, "comparable_synthetic").is_some()
+				|| Self::is_redacted(field)
+				|| Self::compared_by(field).is_some()
+				|| Self::describe_attrs(&field.attrs, &Self::field_name(field)).is_some()
+			{
+				panic!("comparable_desc_roundtrip requires {} to be fully described", Self::field_name(field));
+			}
+		}
+		fields
+			.iter()
+			.enumerate()
+			.filter(|(_, field)| is_ignored(field))
+			.map(|(index, field)| {
+				let member = Self::field_member(index, field);
+				quote!(#member: Default::default())
+			})
+			.collect()
+	}
+
+	// The match arms converting a description of the given fields into a
+	// value, and a value into its description.
+	fn roundtrip_arms(
+		fields: &syn::Fields,
+		value_path: &TokenStream,
+		desc_path: &TokenStream,
+	) -> (TokenStream, TokenStream) {
+		let krate = crate_path();
+		let described = fields.iter().enumerate().filter(|(_, field)| !is_ignored(field)).collect::<Vec<_>>();
+		let (mut from_values, mut into_pats, mut from_pats, mut into_values) =
+			(Vec::new(), Vec::new(), Vec::new(), Vec::new());
+		for (desc_index, (index, field)) in described.into_iter().enumerate() {
+			let member = Self::field_member(index, field);
+			let desc_member = Self::field_member(desc_index, field);
+			let var = local(&format!("var{}", index));
+			// Fields compared as a whole are their own descriptions.
+			let (from_desc, into_desc) = if Self::is_leaf(field) && Self::is_boxed(field) {
+				(quote!(*#var), quote!(Box::new(#var)))
+			} else if Self::is_leaf(field) {
+				(quote!(#var), quote!(#var))
+			} else if Self::is_boxed(field) {
+				(
+					quote!(#krate::roundtrip::FromDesc::from_desc(*#var)),
+					quote!(Box::new(#krate::roundtrip::IntoDesc::into_desc(#var))),
+				)
+			} else {
+				(
+					quote!(#krate::roundtrip::FromDesc::from_desc(#var)),
+					quote!(#krate::roundtrip::IntoDesc::into_desc(#var)),
+				)
+			};
+			from_pats.push(quote!(#desc_member: #var));
+			from_values.push(quote!(#member: #from_desc));
+			into_pats.push(quote!(#member: #var));
+			into_values.push(quote!(#desc_member: #into_desc));
+		}
+		from_values.extend(Self::roundtrip_defaults(fields));
+		(
+			quote!(#desc_path { #(#from_pats),* } => #value_path { #(#from_values),* }),
+			quote!(#value_path { #(#into_pats,)* .. } => #desc_path { #(#into_values),* }),
+		)
+	}
+
 	//
 	// Desc associated type
 	//
 	// NOTE: Never called if inputs.attrs.no_description is true.
 	pub fn generate_desc_type(inputs: &Inputs) -> Self {
-		let type_name = &inputs.input.ident;
+		let krate = crate_path();
 		let desc_name = format_ident!("{}{}", &inputs.input.ident, inputs.attrs.comparable_desc_suffix);
-		let desc_type = generate_type_definition(
-			&inputs.visibility,
-			&desc_name,
-			&map_on_fields_over_data(true, &inputs.input.data, |r| syn::Field {
-				ty: Self::assoc_type(&r.field.ty, "Desc"),
-				..r.field.clone()
-			}),
-		);
+		let desc_type = Self::generate_mirror_desc_type(inputs);
+		let default_enum = Self::compares_enum_with_default(inputs);
+		let desc_ty = &desc_type.ty;
 		Self {
 			ty: Some(
 				inputs
@@ -67,24 +627,26 @@ impl Definition {
 					.unwrap_or(
 						&syn::parse2(if inputs.attrs.self_describing {
 							quote!(Self)
+						} else if default_enum {
+							quote!(#krate::Changed<Self::Change>)
 						} else if inputs.attrs.compare_default {
 							quote!(Self::Change)
 						} else if let Some(ty) = &inputs.attrs.describe_type {
 							quote!(#ty)
 						} else {
-							quote!(#desc_name)
+							quote!(#desc_ty)
 						})
 						.expect("Failed to parse Desc type name"),
 					)
 					.clone(),
 			),
 			definition: if inputs.attrs.self_describing
-				|| inputs.attrs.compare_default
+				|| (inputs.attrs.compare_default && !default_enum)
 				|| inputs.attrs.describe_type.is_some()
 			{
 				None
 			} else {
-				Some(quote!(#desc_type))
+				Some(desc_type.definition)
 			},
 			method_body: inputs
 				.attrs
@@ -96,21 +658,65 @@ impl Definition {
 				)
 				.unwrap_or(if inputs.attrs.self_describing {
 					quote!(self.clone())
+				} else if default_enum {
+					quote!(#krate::from_default(self))
 				} else if inputs.attrs.compare_default {
-					quote!(#type_name::default().comparison(self).unwrap_or_default())
+					quote!(#krate::from_default(self).unwrap_or_default())
 				} else {
-					Self::generate_describe_method_body(&inputs.input.ident, &desc_name, &inputs.input.data)
+					Self::generate_describe_method_body(
+						&inputs.describe_trait,
+						&inputs.input.ident,
+						&desc_name,
+						&inputs.input.data,
+					)
 				}),
 		}
 	}
 
+	// With `compare_default`, an enum is described by its change from the
+	// default value. Values of different variants are then described by their
+	// mirror types in the change, which is still generated.
+	fn compares_enum_with_default(inputs: &Inputs) -> bool {
+		inputs.attrs.compare_default
+			&& !inputs.attrs.no_description
+			&& !inputs.attrs.self_describing
+			&& inputs.attrs.describe_type.is_none()
+			&& inputs.attrs.describe_body.is_none()
+			&& matches!(inputs.input.data, syn::Data::Enum(_))
+	}
+
+	// The type mirroring the type itself, with the descriptions of its fields.
+	fn generate_mirror_desc_type(inputs: &Inputs) -> GeneratedType {
+		let desc_name = format_ident!("{}{}", &inputs.input.ident, inputs.attrs.comparable_desc_suffix);
+		generate_type_definition(
+			&inputs.visibility,
+			&desc_name,
+			&match &inputs.input.data {
+				syn::Data::Enum(en) => create_desc_type_for_enums(&inputs.describe_trait, en),
+				data => map_on_fields_over_data(true, data, |r| syn::Field {
+					ty: Self::field_desc_type(&inputs.describe_trait, r.field),
+					..r.field.clone()
+				}),
+			},
+			&inputs.self_type,
+			&inputs.input.generics,
+			inputs.attrs.versioning(),
+			inputs.attrs.non_exhaustive,
+		)
+	}
+
 	//
 	// describe method
 	//
-	fn generate_describe_method_body(type_name: &syn::Ident, desc_name: &syn::Ident, data: &syn::Data) -> TokenStream {
+	fn generate_describe_method_body(
+		describe_trait: &TokenStream,
+		type_name: &syn::Ident,
+		desc_name: &syn::Ident,
+		data: &syn::Data,
+	) -> TokenStream {
 		match data {
-			syn::Data::Struct(st) => generate_describe_body_for_structs(desc_name, st),
-			syn::Data::Enum(en) => generate_describe_body_for_enums(type_name, desc_name, en),
+			syn::Data::Struct(st) => generate_describe_body_for_structs(describe_trait, desc_name, st),
+			syn::Data::Enum(en) => generate_describe_body_for_enums(describe_trait, type_name, desc_name, en),
 			syn::Data::Union(_un) => {
 				panic!("comparable_derive::generate_describe_body not implemented for unions")
 			}
@@ -123,53 +729,417 @@ impl Definition {
 	pub fn generate_change_type(inputs: &Inputs) -> Self {
 		let type_name = &inputs.input.ident;
 		let change_name = format_ident!("{}{}", type_name, inputs.attrs.comparable_change_suffix);
-		let change_type = Self::create_change_type(&inputs.attrs, &inputs.input.ident, &inputs.input.data).map(
-			|(ch_ty, helper_tys)| {
-				let ch_def = generate_type_definition(&inputs.visibility, &change_name, &ch_ty);
-				let helper_defs =
-					helper_tys.iter().map(|(name, ty)| generate_type_definition(&inputs.visibility, name, ty));
-				quote! {
+		let default_enum = Self::compares_enum_with_default(inputs);
+		let desc_type = if default_enum {
+			Self::generate_mirror_desc_type(inputs).ty
+		} else {
+			Self::assoc_type(&self_type(), "Desc")
+		};
+		let change_type = Self::create_change_type(&inputs.attrs, &inputs.input.ident, &inputs.input.data, &desc_type)
+			.map(|(ch_ty, helper_tys)| {
+				let generate =
+					|name: &syn::Ident, ty: &syn::Data, version: Option<(&syn::LitInt, bool)>, non_exhaustive: bool| {
+						generate_type_definition(
+							&inputs.visibility,
+							name,
+							ty,
+							&inputs.self_type,
+							&inputs.input.generics,
+							version,
+							non_exhaustive,
+						)
+					};
+				let helpers = helper_tys.iter().map(|(name, ty)| generate(name, ty, None, false)).collect::<Vec<_>>();
+				// The change type refers to its helper types by name alone, which
+				// must be applied to any parameters they were given.
+				let ch_ty = helper_tys.iter().zip(&helpers).fold(ch_ty, |data, ((name, _), helper)| {
+					map_field_types(&data, |ty| replace_ident(ty, name, &helper.ty))
+				});
+				let ch = generate(&change_name, &ch_ty, inputs.attrs.versioning(), inputs.attrs.non_exhaustive);
+				let groups = match &inputs.input.data {
+					syn::Data::Struct(st) => field_groups(type_name, &inputs.attrs.comparable_change_suffix, st),
+					_ => Vec::new(),
+				};
+				let merge_key_impl = if let syn::Data::Struct(st) = &inputs.input.data {
+					generate_merge_key_impl_for_structs(&change_name, &ch, st, &groups)
+				} else {
+					quote!()
+				};
+				let fields_const = Self::generate_fields_const(&change_name, &ch, &Self::mask_field_names(inputs));
+				let newtype_impls = match &inputs.input.data {
+					syn::Data::Struct(_) => Self::generate_newtype_impls(&change_name, &ch),
+					_ => quote!(),
+				};
+				// The names of the changes held by the variants of each type
+				// generated, for struct types: those of fields or of groups for
+				// the change type, and those of the grouped fields for the
+				// types of each group.
+				let variant_names = match &inputs.input.data {
+					syn::Data::Struct(st) => std::iter::once(change_variant_names(st, !groups.is_empty()))
+						.chain(groups.iter().map(|group| group_field_names(st, group)))
+						.map(Some)
+						.collect(),
+					_ => vec![None],
+				};
+				let types = std::iter::once((&change_name, &ch))
+					.chain(helper_tys.iter().map(|(name, _)| name).zip(&helpers))
+					.zip(&variant_names)
+					.collect::<Vec<_>>();
+				let display_impl = if inputs.attrs.comparable_display {
+					if types.len() != variant_names.len() {
+						panic!("comparable_display is not supported with variant_struct_fields");
+					}
+					let impls =
+						types.iter().map(|((name, ty), names)| Self::generate_display_impl(name, ty, names.as_deref()));
+					quote!(#(#impls)*)
+				} else {
+					quote!()
+				};
+				let tree_impl = if inputs.attrs.comparable_tree {
+					if types.len() != variant_names.len() {
+						panic!("comparable_tree is not supported with variant_struct_fields");
+					}
+					let impls =
+						types.iter().map(|((name, ty), names)| Self::generate_tree_impl(name, ty, names.as_deref()));
+					quote!(#(#impls)*)
+				} else {
+					quote!()
+				};
+				let ch_def = &ch.definition;
+				let helper_defs = helpers.iter().map(|helper| &helper.definition);
+				let definition = quote! {
 					#ch_def
 					#(#helper_defs)*
-				}
-			},
-		);
+					#merge_key_impl
+					#fields_const
+					#newtype_impls
+					#display_impl
+					#tree_impl
+				};
+				(ch.ty, definition)
+			});
 		Self {
-			ty: if change_type.is_some() {
+			ty: change_type.as_ref().and_then(|(ch_ty, _)| {
 				(if let syn::Data::Struct(st) = &inputs.input.data {
 					match field_count(true, st.fields.iter()) {
 						0 => None,
-						1 => Some(quote!(#change_name)),
-						_ => Some(quote!(Vec<#change_name>)),
+						1 => Some(quote!(#ch_ty)),
+						_ => Some(quote!(Vec<#ch_ty>)),
 					}
 				} else {
-					Some(quote!(#change_name))
+					Some(quote!(#ch_ty))
 				})
 				.map(|ty| syn::parse2(ty).expect("Failed to parse Change type name"))
-			} else {
-				None
-			},
-			definition: change_type,
+			}),
+			definition: change_type.map(|(_, definition)| definition),
 			method_body: Self::generate_comparison_method_body(
 				&inputs.attrs,
 				type_name,
 				&change_name,
 				&inputs.input.data,
+				// Values of different variants are described by their mirror
+				// type, rather than by their change from the default value.
+				default_enum.then(|| {
+					Self::generate_describe_method_body(
+						&inputs.describe_trait,
+						type_name,
+						&format_ident!("{}{}", type_name, inputs.attrs.comparable_desc_suffix),
+						&inputs.input.data,
+					)
+				}),
 			),
 		}
 	}
 
+	// The names of the fields that are compared, as written in field masks,
+	// with the fields of an enum's variants listed once for each name.
+	pub fn mask_field_names(inputs: &Inputs) -> Vec<String> {
+		let name =
+			|r: &FieldRef| r.field.ident.as_ref().map(ToString::to_string).unwrap_or_else(|| r.index.to_string());
+		match &inputs.input.data {
+			syn::Data::Struct(st) => map_fields(true, st.fields.iter(), true, name),
+			syn::Data::Enum(_) if inputs.attrs.comparable_variant_only => Vec::new(),
+			syn::Data::Enum(en) => {
+				let mut names = Vec::new();
+				for variant in &en.variants {
+					for field in map_fields(false, variant.fields.iter(), true, name) {
+						if !names.contains(&field) {
+							names.push(field);
+						}
+					}
+				}
+				names
+			}
+			syn::Data::Union(_) => Vec::new(),
+		}
+	}
+
+	fn generate_fields_const(change_name: &syn::Ident, change: &GeneratedType, names: &[String]) -> TokenStream {
+		let params = change.params.iter().map(|(param, _)| param).collect::<Vec<_>>();
+		let params = if params.is_empty() { quote!() } else { quote!(<#(#params),*>) };
+		quote! {
+			impl #params #change_name #params {
+				/// The names of the fields compared, as written in a
+				/// [`FieldMask`](comparable::mask::FieldMask).
+				pub const FIELDS: &'static [&'static str] = &[#(#names),*];
+			}
+		}
+	}
+
+	// The change type of a struct with a single field only wraps the changes
+	// of that field, which it can be built from, unwrapped to, and
+	// dereferenced to. It cannot implement `From` for those changes, since
+	// their type is a projection through a trait of another crate, which
+	// coherence cannot tell apart from the change type itself.
+	fn generate_newtype_impls(change_name: &syn::Ident, change: &GeneratedType) -> TokenStream {
+		let field = match &change.data {
+			syn::Data::Struct(st) if st.fields.len() == 1 => st.fields.iter().next().expect("Found no field"),
+			_ => return quote!(),
+		};
+		let (vis, ty, member) = (&field.vis, &field.ty, Self::field_member(0, field));
+		let params = change.params.iter().map(|(param, _)| param).collect::<Vec<_>>();
+		let params = if params.is_empty() { quote!() } else { quote!(<#(#params),*>) };
+		quote! {
+			impl #params #change_name #params {
+				/// The change type holding the changes of the only field
+				/// compared.
+				#vis fn new(change: #ty) -> Self {
+					#change_name { #member: change }
+				}
+
+				/// The changes of the only field compared.
+				#vis fn into_inner(self) -> #ty {
+					self.#member
+				}
+			}
+
+			impl #params std::ops::Deref for #change_name #params {
+				type Target = #ty;
+
+				fn deref(&self) -> &Self::Target {
+					&self.#member
+				}
+			}
+
+			impl #params std::ops::DerefMut for #change_name #params {
+				fn deref_mut(&mut self) -> &mut Self::Target {
+					&mut self.#member
+				}
+			}
+		}
+	}
+
+	// With `comparable_display`, the change type renders the changes of each
+	// field at the path of that field, as written in a field mask. An enum's
+	// `Different` variant renders the descriptions of both values.
+	fn generate_display_impl(
+		change_name: &syn::Ident,
+		change: &GeneratedType,
+		variant_names: Option<&[String]>,
+	) -> TokenStream {
+		let krate = crate_path();
+		let (arms, bounds) = Self::change_type_arms(
+			change_name,
+			change,
+			variant_names,
+			&quote!(#krate::display::DisplayChange),
+			|name, binding| {
+				quote! {
+					#krate::display::DisplayChange::display_changes(
+						#binding,
+						&path.child(#krate::location::Segment::Field(#name.to_string())),
+						lines,
+					)
+				}
+			},
+			|fields| quote!({ #(#fields;)* }),
+			|before, after| quote!(#krate::display::push_replaced(lines, path, #before, #after)),
+		);
+		let params = change.params.iter().map(|(param, _)| param).collect::<Vec<_>>();
+		let params = if params.is_empty() { quote!() } else { quote!(<#(#params),*>) };
+		quote! {
+			impl #params #krate::display::DisplayChange for #change_name #params where #(#bounds),* {
+				fn display_changes(&self, path: &#krate::location::Path, lines: &mut Vec<String>) {
+					match self {
+						#(#arms,)*
+					}
+				}
+			}
+
+			impl #params std::fmt::Display for #change_name #params where #(#bounds),* {
+				fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+					std::fmt::Display::fmt(&#krate::display::display(self), f)
+				}
+			}
+		}
+	}
+
+	// With `comparable_tree`, the change type converts to a `ChangeTree`, with
+	// the changes of each field under the name of that field, as written in a
+	// field mask. An enum's `Different` variant holds the descriptions of both
+	// values. A reference to a change also converts to the tree's rows, for
+	// tabular exports.
+	fn generate_tree_impl(
+		change_name: &syn::Ident,
+		change: &GeneratedType,
+		variant_names: Option<&[String]>,
+	) -> TokenStream {
+		let krate = crate_path();
+		let (arms, bounds) = Self::change_type_arms(
+			change_name,
+			change,
+			variant_names,
+			&quote!(#krate::tree::ToTree),
+			|name, binding| {
+				quote!(#krate::tree::push_child(
+					&mut children,
+					#name.to_string(),
+					#krate::tree::ToTree::to_tree(#binding),
+				))
+			},
+			|fields| {
+				quote!({
+					let mut children = Vec::new();
+					#(#fields;)*
+					#krate::tree::ChangeTree::Children(children)
+				})
+			},
+			|before, after| quote!(#krate::tree::ChangeTree::modified(#before, #after)),
+		);
+		let params = change.params.iter().map(|(param, _)| param).collect::<Vec<_>>();
+		let params = if params.is_empty() { quote!() } else { quote!(<#(#params),*>) };
+		quote! {
+			impl #params #krate::tree::ToTree for #change_name #params where #(#bounds),* {
+				fn to_tree(&self) -> #krate::tree::ChangeTree {
+					match self {
+						#(#arms,)*
+					}
+				}
+			}
+
+			impl #params std::convert::From<&#change_name #params> for Vec<(String, String, String)>
+			where
+				#(#bounds),*
+			{
+				fn from(change: &#change_name #params) -> Self {
+					#krate::tree::ToTree::to_tree(change).rows()
+				}
+			}
+		}
+	}
+
+	// The arms of a match on a change type, for implementing a trait of changes
+	// in terms of the changes of each field. `field` is given the name of a
+	// field and the binding of its changes, `combine` is given the results of
+	// `field` for the fields of a variant, and `different` handles an enum's
+	// `Different` variant, given the bindings of its two descriptions. For a
+	// struct, `variant_names` are the names of the changes held by each
+	// variant of the change type, and for an enum they are `None`. Also returns the bounds needed on the change type's parameters.
+	fn change_type_arms(
+		change_name: &syn::Ident,
+		change: &GeneratedType,
+		variant_names: Option<&[String]>,
+		field_trait: &TokenStream,
+		field: impl Fn(&str, &syn::Ident) -> TokenStream,
+		combine: impl Fn(Vec<TokenStream>) -> TokenStream,
+		different: impl Fn(&syn::Ident, &syn::Ident) -> TokenStream,
+	) -> (Vec<TokenStream>, Vec<TokenStream>) {
+		let is_enum = variant_names.is_none();
+		let mut bounds = Vec::new();
+		let mut bound = |ty: &syn::Type, bound: TokenStream| {
+			if change.params.iter().any(|(param, _)| mentions_ident(quote!(#ty), param)) {
+				bounds.push(quote!(#ty: #bound));
+			}
+		};
+		// The changes of a struct with several fields are held by a variant for
+		// each field or group, which may have been renamed, whereas the variants
+		// of an enum hold fields keeping the names of the original fields.
+		let field_name = |index: usize, f: &syn::Field| match variant_names {
+			Some(names) => names[index].clone(),
+			None => f.ident.as_ref().map(ToString::to_string).unwrap_or_else(|| index.to_string()),
+		};
+		let bindings =
+			|fields: &syn::Fields| (0..fields.len()).map(|i| local(&format!("change{}", i))).collect::<Vec<_>>();
+		let arms = match &change.data {
+			syn::Data::Struct(st) => {
+				let bindings = bindings(&st.fields);
+				let pattern = Self::fields_pattern(&st.fields, &bindings);
+				st.fields.iter().for_each(|f| bound(&f.ty, field_trait.clone()));
+				let fields = st.fields.iter().zip(&bindings).enumerate().map(|(i, (f, b))| field(&field_name(i, f), b));
+				let body = combine(fields.collect());
+				vec![quote!(#change_name #pattern => #body)]
+			}
+			syn::Data::Enum(en) => en
+				.variants
+				.iter()
+				.enumerate()
+				.map(|(index, variant)| {
+					let variant_name = &variant.ident;
+					let bindings = bindings(&variant.fields);
+					let pattern = Self::fields_pattern(&variant.fields, &bindings);
+					if is_enum && variant_name == "Different" {
+						variant.fields.iter().for_each(|f| bound(&f.ty, quote!(std::fmt::Debug)));
+						let different = different(&bindings[0], &bindings[1]);
+						return quote!(#change_name::#variant_name #pattern => #different);
+					}
+					variant.fields.iter().for_each(|f| bound(&f.ty, field_trait.clone()));
+					let fields = variant
+						.fields
+						.iter()
+						.zip(&bindings)
+						.enumerate()
+						.map(|(i, (f, b))| field(&field_name(if is_enum { i } else { index }, f), b));
+					let body = combine(fields.collect());
+					quote!(#change_name::#variant_name #pattern => #body)
+				})
+				.collect(),
+			syn::Data::Union(_) => Vec::new(),
+		};
+		(arms, bounds)
+	}
+
+	// A pattern binding each of the given fields to the given names.
+	fn fields_pattern(fields: &syn::Fields, bindings: &[syn::Ident]) -> TokenStream {
+		match fields {
+			syn::Fields::Named(named) => {
+				let names = named.named.iter().map(|f| &f.ident);
+				quote!({ #(#names: #bindings),* })
+			}
+			syn::Fields::Unnamed(_) => quote!((#(#bindings),*)),
+			syn::Fields::Unit => quote!(),
+		}
+	}
+
 	fn create_change_type(
 		attrs: &Attributes,
 		type_name: &syn::Ident,
 		data: &syn::Data,
+		desc_type: &syn::Type,
 	) -> Option<(syn::Data, Vec<(syn::Ident, syn::Data)>)> {
 		match data {
-			syn::Data::Struct(st) => create_change_type_for_structs(st).map(|x| (x, Vec::new())),
+			syn::Data::Struct(_) if attrs.comparable_variant_only => {
+				panic!("comparable_variant_only is only supported for enums")
+			}
+			syn::Data::Struct(st) => {
+				create_change_type_for_structs(st, &field_groups(type_name, &attrs.comparable_change_suffix, st))
+			}
+			syn::Data::Enum(_) if attrs.comparable_variant_only && attrs.variant_struct_fields => {
+				panic!("comparable_variant_only is not supported with variant_struct_fields")
+			}
+			syn::Data::Enum(en) if attrs.comparable_variant_only => Some((
+				create_change_type_for_enums(&variants_without_fields(en), attrs.non_exhaustive, desc_type),
+				Vec::new(),
+			)),
 			syn::Data::Enum(en) => Some(if attrs.variant_struct_fields {
-				create_change_type_for_enums_with_helpers(type_name, &attrs.comparable_change_suffix, en)
+				create_change_type_for_enums_with_helpers(
+					type_name,
+					&attrs.comparable_change_suffix,
+					en,
+					attrs.non_exhaustive,
+					desc_type,
+				)
 			} else {
-				(create_change_type_for_enums(type_name, en), Vec::new())
+				(create_change_type_for_enums(en, attrs.non_exhaustive, desc_type), Vec::new())
 			}),
 			syn::Data::Union(_un) => {
 				panic!("comparable_derive::generate_change_type not implemented for unions")
@@ -185,16 +1155,38 @@ impl Definition {
 		type_name: &syn::Ident,
 		change_name: &syn::Ident,
 		data: &syn::Data,
+		describe: Option<TokenStream>,
 	) -> TokenStream {
+		let krate = crate_path();
 		match data {
-			syn::Data::Struct(st) => generate_comparison_body_for_structs(change_name, st),
+			syn::Data::Struct(st) => generate_comparison_body_for_structs(
+				change_name,
+				st,
+				&field_groups(type_name, &attrs.comparable_change_suffix, st),
+			),
 			syn::Data::Enum(en) => {
-				if en.variants.is_empty() {
+				let different = match describe {
+					Some(body) => {
+						let other_body = rebind_self(body.clone(), &format_ident!("other"));
+						quote!(#change_name::Different(#body, #other_body))
+					}
+					None => quote! {
+						#change_name::Different(
+							#krate::Comparable::describe(self),
+							#krate::Comparable::describe(other),
+						)
+					},
+				};
+				if en.variants.is_empty()
+					|| (attrs.comparable_variant_only && en.variants.len() < 2 && !attrs.non_exhaustive)
+				{
 					quote! {
-						comparable::Changed::Unchanged
+						#krate::Changed::Unchanged
 					}
+				} else if attrs.comparable_variant_only {
+					compare_variants(&quote!(#krate::Changed::Unchanged), &quote!(#krate::Changed::Changed(#different)))
 				} else {
-					EnumDetails::from(attrs, type_name, change_name, en).generate_comparison_body(change_name)
+					EnumDetails::from(attrs, type_name, change_name, en).generate_comparison_body(&different)
 				}
 			}
 			syn::Data::Union(_un) => {
@@ -203,3 +1195,17 @@ impl Definition {
 		}
 	}
 }
+
+// A method of `Comparable` generated from the same method of each field.
+struct FieldMethod {
+	name: TokenStream,
+	// The result for a field excluded by a runtime field mask, if the method
+	// honors masks.
+	masked_out: Option<TokenStream>,
+	// Adjusts the result for a field, given the field's name.
+	in_field: Option<fn(&str, TokenStream) -> TokenStream>,
+	// The result for values of different enum variants.
+	different: TokenStream,
+	// The result for values without fields.
+	empty: TokenStream,
+}
@@ -4,31 +4,44 @@ use std::iter::FromIterator;
 
 use crate::attrs::*;
 use crate::definition::*;
+use crate::inputs::*;
 use crate::structs::*;
 use crate::utils::*;
 
 pub fn generate_describe_body_for_enums(
+	describe_trait: &TokenStream,
 	type_name: &syn::Ident,
 	desc_name: &syn::Ident,
 	en: &syn::DataEnum,
 ) -> TokenStream {
 	let cases = map_variants(en.variants.iter(), |variant| {
 		let variant_name = &variant.ident;
+		if has_attr(&variant.attrs, "no_description").is_some() {
+			return quote! {
+				#type_name::#variant_name { .. } => #desc_name::#variant_name
+			};
+		}
+		if let Some((_, body)) = variant_describe_attrs(variant) {
+			return quote! {
+				#type_name::#variant_name { .. } => #desc_name::#variant_name(#body)
+			};
+		}
 		match &variant.fields {
 			syn::Fields::Named(named) => {
 				let (field_indices, field_names): (Vec<syn::Ident>, Vec<syn::Ident>) =
 					map_fields(false, named.named.iter(), false, |r| {
 						(
-							format_ident!("var{}", r.index),
+							local(&format!("var{}", r.index)),
 							r.field.ident.as_ref().expect("Found unnamed field in named struct").clone(),
 						)
 					})
 					.into_iter()
 					.unzip();
-				let (field_indices_without_ignored, field_names_without_ignored): (Vec<syn::Ident>, Vec<syn::Ident>) =
+				let (field_descriptions, field_names_without_ignored): (Vec<TokenStream>, Vec<syn::Ident>) =
 					map_fields(false, named.named.iter(), true, |r| {
+						let var = local(&format!("var{}", r.index));
 						(
-							format_ident!("var{}", r.index),
+							Definition::field_describe(describe_trait, r.field, &quote!(#var)),
 							r.field.ident.as_ref().expect("Found unnamed field in named struct").clone(),
 						)
 					})
@@ -36,17 +49,19 @@ pub fn generate_describe_body_for_enums(
 					.unzip();
 				quote! {
 					#type_name::#variant_name { #(#field_names: #field_indices),* } =>
-					#desc_name::#variant_name { #(#field_names_without_ignored: #field_indices_without_ignored.describe()),* }
+					#desc_name::#variant_name { #(#field_names_without_ignored: #field_descriptions),* }
 				}
 			}
 			syn::Fields::Unnamed(unnamed) => {
-				let vars = map_fields(false, unnamed.unnamed.iter(), false, |r| format_ident!("var{}", r.index));
-				let vars_without_ignored: Vec<syn::Ident> =
-					map_fields(false, unnamed.unnamed.iter(), true, |r| format_ident!("var{}", r.index));
+				let vars = map_fields(false, unnamed.unnamed.iter(), false, |r| local(&format!("var{}", r.index)));
+				let field_descriptions = map_fields(false, unnamed.unnamed.iter(), true, |r| {
+					let var = local(&format!("var{}", r.index));
+					Definition::field_describe(describe_trait, r.field, &quote!(#var))
+				});
 
 				quote! {
 					#type_name::#variant_name(#(#vars),*) =>
-					#desc_name::#variant_name(#(#vars_without_ignored.describe()),*)
+					#desc_name::#variant_name(#(#field_descriptions),*)
 				}
 			}
 			syn::Fields::Unit => {
@@ -67,14 +82,49 @@ pub fn generate_describe_body_for_enums(
 	}
 }
 
-pub fn create_change_type_for_enums(type_name: &syn::Ident, en: &syn::DataEnum) -> syn::Data {
+fn variant_describe_attrs(variant: &syn::Variant) -> Option<(syn::Type, syn::Expr)> {
+	Definition::describe_attrs(&variant.attrs, &format!("Variant {}", variant.ident))
+}
+
+pub fn create_desc_type_for_enums(describe_trait: &TokenStream, en: &syn::DataEnum) -> syn::Data {
+	syn::Data::Enum(syn::DataEnum {
+		variants: FromIterator::from_iter(map_variants(en.variants.iter(), |variant| syn::Variant {
+			fields: if has_attr(&variant.attrs, "no_description").is_some() {
+				syn::Fields::Unit
+			} else if let Some((ty, _)) = variant_describe_attrs(variant) {
+				syn::Fields::Unnamed(syn::FieldsUnnamed {
+					unnamed: FromIterator::from_iter(vec![syn::Field {
+						ident: None,
+						ty,
+						attrs: Default::default(),
+						vis: syn::Visibility::Inherited,
+						colon_token: Default::default(),
+					}]),
+					paren_token: Default::default(),
+				})
+			} else {
+				map_on_fields(true, &variant.fields, |r| syn::Field {
+					ty: Definition::field_desc_type(describe_trait, r.field),
+					..r.field.clone()
+				})
+			},
+			..variant.clone()
+		})),
+		..*en
+	})
+}
+
+// The `Different` variant is left out for enums with a single variant, unless
+// they are `#[non_exhaustive]`, since any variant added to them later makes it
+// necessary.
+pub fn create_change_type_for_enums(en: &syn::DataEnum, non_exhaustive: bool, desc_type: &syn::Type) -> syn::Data {
 	syn::Data::Enum(syn::DataEnum {
 		variants: FromIterator::from_iter(
 			map_variants(en.variants.iter(), |variant| {
 				let many_fields = variant.fields.len() > 1;
 				let mapped_fields = map_on_fields(false, &variant.fields, |r| syn::Field {
 					ty: {
-						let change_type = Definition::assoc_type(&r.field.ty, "Change");
+						let change_type = Definition::field_change_type(r.field);
 						if many_fields {
 							Definition::changed_type(&change_type)
 						} else {
@@ -87,7 +137,7 @@ pub fn create_change_type_for_enums(type_name: &syn::Ident, en: &syn::DataEnum)
 					None
 				} else {
 					Some(syn::Variant {
-						ident: format_ident!("Both{}", &variant.ident),
+						ident: Definition::both_variant_name(variant),
 						fields: { mapped_fields },
 						..variant.clone()
 					})
@@ -95,7 +145,7 @@ pub fn create_change_type_for_enums(type_name: &syn::Ident, en: &syn::DataEnum)
 			})
 			.into_iter()
 			.flatten()
-			.chain(if en.variants.len() < 2 {
+			.chain(if en.variants.len() < 2 && !non_exhaustive {
 				vec![]
 			} else {
 				vec![syn::Variant {
@@ -103,7 +153,7 @@ pub fn create_change_type_for_enums(type_name: &syn::Ident, en: &syn::DataEnum)
 					fields: syn::Fields::Unnamed({
 						let desc_field = syn::Field {
 							ident: None,
-							ty: Definition::assoc_type(&ident_to_type(type_name), "Desc"),
+							ty: desc_type.clone(),
 							attrs: Default::default(),
 							vis: syn::Visibility::Inherited,
 							colon_token: Default::default(),
@@ -122,6 +172,30 @@ pub fn create_change_type_for_enums(type_name: &syn::Ident, en: &syn::DataEnum)
 	})
 }
 
+// With `#[comparable_variant_only]`, only the variant of a value is compared,
+// so the change type is that of an enum whose variants have no fields.
+pub fn variants_without_fields(en: &syn::DataEnum) -> syn::DataEnum {
+	syn::DataEnum {
+		variants: FromIterator::from_iter(map_variants(en.variants.iter(), |variant| syn::Variant {
+			fields: syn::Fields::Unit,
+			..variant.clone()
+		})),
+		..en.clone()
+	}
+}
+
+// The result of comparing the variants of `self` and `other`, given the
+// result when they are the same and when they are different.
+pub fn compare_variants(same: &TokenStream, different: &TokenStream) -> TokenStream {
+	quote! {
+		if std::mem::discriminant(self) == std::mem::discriminant(other) {
+			#same
+		} else {
+			#different
+		}
+	}
+}
+
 // For each multi-field variant in an enum, the function below generates a
 // helper [`Comparable::Change`] struct and set that variant's type for the
 // enum's [`Comparable::Change`] to be `Vec<Change>`. However, we aren't using
@@ -131,6 +205,8 @@ pub fn create_change_type_for_enums_with_helpers(
 	type_name: &syn::Ident,
 	change_suffix: &syn::Ident,
 	en: &syn::DataEnum,
+	non_exhaustive: bool,
+	desc_type: &syn::Type,
 ) -> (syn::Data, Vec<(syn::Ident, syn::Data)>) {
 	let mut helper_structs: Vec<(syn::Ident, syn::Data)> = Vec::new();
 	let helper_structs_ref = &mut helper_structs;
@@ -140,25 +216,26 @@ pub fn create_change_type_for_enums_with_helpers(
 				if variant.fields.is_empty() {
 					None
 				} else {
-					let apply_change_to_field = |r: &FieldRef| syn::Field {
-						ty: Definition::assoc_type(&r.field.ty, "Change"),
-						..r.field.clone()
-					};
+					let apply_change_to_field =
+						|r: &FieldRef| syn::Field { ty: Definition::field_change_type(r.field), ..r.field.clone() };
 					Some(syn::Variant {
-						ident: format_ident!("Both{}", &variant.ident),
+						ident: Definition::both_variant_name(variant),
 						fields: {
 							if variant.fields.len() == 1 {
 								// A map isn't needed, but it fits the pattern
 								map_on_fields(false, &variant.fields, apply_change_to_field)
 							} else {
 								let fields_struct = &data_from_variant(variant);
-								let fields_change_struct =
-									create_change_type_for_structs(if let syn::Data::Struct(st) = &fields_struct {
+								let fields_change_struct = create_change_type_for_structs(
+									if let syn::Data::Struct(st) = &fields_struct {
 										st
 									} else {
 										panic!("field_struct is not a struct!")
-									})
-									.unwrap();
+									},
+									&[],
+								)
+								.unwrap()
+								.0;
 
 								let fields_change_name =
 									format_ident!("{}{}{}", type_name, &variant.ident, change_suffix);
@@ -182,7 +259,7 @@ pub fn create_change_type_for_enums_with_helpers(
 			})
 			.into_iter()
 			.flatten()
-			.chain(if en.variants.len() < 2 {
+			.chain(if en.variants.len() < 2 && !non_exhaustive {
 				vec![]
 			} else {
 				vec![syn::Variant {
@@ -190,7 +267,7 @@ pub fn create_change_type_for_enums_with_helpers(
 					fields: syn::Fields::Unnamed({
 						let desc_field = syn::Field {
 							ident: None,
-							ty: Definition::assoc_type(&ident_to_type(type_name), "Desc"),
+							ty: desc_type.clone(),
 							attrs: Default::default(),
 							vis: syn::Visibility::Inherited,
 							colon_token: Default::default(),
@@ -215,15 +292,18 @@ struct FieldDetails {
 	self_var: syn::Ident,
 	other_var: syn::Ident,
 	changes_var: syn::Ident,
+	comparison: TokenStream,
 	is_ignored: bool,
 }
 
 impl FieldDetails {
-	fn from(index: usize, is_ignored: bool) -> Self {
-		let self_var = format_ident!("self_var{}", index);
-		let other_var = format_ident!("other_var{}", index);
-		let changes_var = format_ident!("changes_var{}", index);
-		FieldDetails { self_var, other_var, changes_var, is_ignored }
+	fn from(index: usize, field: &syn::Field) -> Self {
+		let self_var = local(&format!("self_var{}", index));
+		let other_var = local(&format!("other_var{}", index));
+		let changes_var = local(&format!("changes_var{}", index));
+		let comparison = Definition::field_comparison(index, field, &quote!(#self_var), &quote!(&#other_var));
+		let is_ignored = is_ignored(field);
+		FieldDetails { self_var, other_var, changes_var, comparison, is_ignored }
 	}
 }
 
@@ -263,21 +343,14 @@ impl VariantFields {
 		self.field_details().iter().map(|d| d.self_var.clone()).collect()
 	}
 
-	fn self_vars_filtered(&self) -> Vec<syn::Ident> {
-		self.field_details()
-			.iter()
-			.filter_map(|d| if !d.is_ignored { Some(d.self_var.clone()) } else { None })
-			.collect()
-	}
-
 	fn other_vars(&self) -> Vec<syn::Ident> {
 		self.field_details().iter().map(|d| d.other_var.clone()).collect()
 	}
 
-	fn other_vars_filtered(&self) -> Vec<syn::Ident> {
+	fn comparisons(&self) -> Vec<TokenStream> {
 		self.field_details()
 			.iter()
-			.filter_map(|d| if !d.is_ignored { Some(d.other_var.clone()) } else { None })
+			.filter_map(|d| if !d.is_ignored { Some(d.comparison.clone()) } else { None })
 			.collect()
 	}
 
@@ -287,16 +360,6 @@ impl VariantFields {
 			.filter_map(|d| if !d.is_ignored { Some(d.changes_var.clone()) } else { None })
 			.collect()
 	}
-
-	pub fn map_basic_field_info<R>(&self, mut f: impl FnMut(usize, &Option<syn::Ident>) -> R) -> Vec<R> {
-		match self {
-			VariantFields::Named(m) => {
-				m.iter().zip(0usize..).map(|((k, _), index)| f(index, &Some(k.clone()))).collect()
-			}
-			VariantFields::Unnamed(v) => v.iter().zip(0usize..).map(|(_, index)| f(index, &None)).collect(),
-			VariantFields::Unit => Vec::new(),
-		}
-	}
 }
 
 #[derive(Clone)]
@@ -315,18 +378,16 @@ impl VariantDetails {
 				map_fields(false, named.named.iter(), false, |r| {
 					(
 						r.field.ident.as_ref().expect("Unexpected unnamed field").clone(),
-						FieldDetails::from(r.index, has_attr(&r.field.attrs, "comparable_ignore").is_some()),
+						FieldDetails::from(r.index, r.field),
 					)
 				})
 				.into_iter()
 				.collect(),
 			),
 			syn::Fields::Unnamed(unnamed) => VariantFields::Unnamed(
-				map_fields(false, unnamed.unnamed.iter(), false, |r| {
-					FieldDetails::from(r.index, has_attr(&r.field.attrs, "comparable_ignore").is_some())
-				})
-				.into_iter()
-				.collect(),
+				map_fields(false, unnamed.unnamed.iter(), false, |r| FieldDetails::from(r.index, r.field))
+					.into_iter()
+					.collect(),
 			),
 			syn::Fields::Unit => VariantFields::Unit,
 		};
@@ -371,46 +432,44 @@ impl VariantDetails {
 		change_name: &syn::Ident,
 		variant: &syn::Variant,
 	) -> Self {
+		let krate = crate_path();
 		let variant_name = &variant.ident;
 
 		let VariantDetails { fields, fields_self_capture, fields_other_capture, fields_assignment, match_branch: _ } =
 			&self;
 
-		let both_ident = format_ident!("Both{}", variant_name);
-		let self_vars_without_ignored = fields.self_vars_filtered();
+		let both_ident = Definition::both_variant_name(variant);
 		let changes_vars = fields.changes_vars();
-		let other_vars_without_ignored = fields.other_vars_filtered();
+		let comparisons = fields.comparisons();
 
 		let return_result = if changes_vars.is_empty() {
-			quote!(comparable::Changed::Unchanged)
+			quote!(#krate::Changed::Unchanged)
 		} else if fields.self_vars().len() == 1 {
 			quote! {
 				#(#changes_vars.map(
-					|changes_var0|
+					|#changes_vars|
 					#change_name::#both_ident #fields_assignment))*
 			}
 		} else if attrs.variant_struct_fields {
 			let fields_change_name = format_ident!("{}{}{}", type_name, variant_name, attrs.comparable_change_suffix);
-			let capitalized_field_names = fields.map_basic_field_info(Definition::variant_name_from_field);
+			let capitalized_field_names =
+				map_fields(false, variant.fields.iter(), true, Definition::field_variant_name);
 			quote! {
-				let changes: Vec<#fields_change_name> = vec![
-					#(#changes_vars.map(#fields_change_name::#capitalized_field_names)),*
-				]
+				#krate::Changed::from_changes(
+					vec![
+						#(#changes_vars.map(#fields_change_name::#capitalized_field_names)),*
+					]
 					.into_iter()
 					.flatten()
-					.collect();
-				if changes.is_empty() {
-					comparable::Changed::Unchanged
-				} else {
-					comparable::Changed::Changed(#change_name::#both_ident(changes))
-				}
+				)
+				.map(#change_name::#both_ident)
 			}
 		} else {
 			quote! {
 				if #(#changes_vars.is_unchanged())&&* {
-					comparable::Changed::Unchanged
+					#krate::Changed::Unchanged
 				} else {
-					comparable::Changed::Changed(
+					#krate::Changed::Changed(
 						#change_name::#both_ident #fields_assignment
 					)
 				}
@@ -420,7 +479,7 @@ impl VariantDetails {
 		self.match_branch = quote! {
 			(#type_name::#variant_name #fields_self_capture,
 			 #type_name::#variant_name #fields_other_capture) => {
-				#(let #changes_vars = #self_vars_without_ignored.comparison(&#other_vars_without_ignored);)*
+				#(let #changes_vars = #comparisons;)*
 				#return_result
 			}
 		};
@@ -431,6 +490,7 @@ impl VariantDetails {
 #[derive(Clone)]
 pub struct EnumDetails {
 	variants: Vec<VariantDetails>,
+	non_exhaustive: bool,
 }
 
 impl EnumDetails {
@@ -441,6 +501,7 @@ impl EnumDetails {
 			})
 			.into_iter()
 			.collect(),
+			non_exhaustive: attrs.non_exhaustive,
 		}
 	}
 
@@ -448,12 +509,21 @@ impl EnumDetails {
 		self.variants.iter().map(|d| d.match_branch.clone()).collect()
 	}
 
-	pub fn generate_comparison_body(&self, change_name: &syn::Ident) -> TokenStream {
+	// `different` is the change between values of different variants.
+	pub fn generate_comparison_body(&self, different: &TokenStream) -> TokenStream {
+		let krate = crate_path();
 		let match_branches = self.match_branches();
+		let different = quote! {
+			(_, _) => #krate::Changed::Changed(#different)
+		};
+		// The wildcard arm of a `#[non_exhaustive]` enum with a single variant is
+		// unreachable, but is kept to match its change type.
 		let default_case = if match_branches.len() > 1 {
+			different
+		} else if self.non_exhaustive {
 			quote! {
-				(_, _) => comparable::Changed::Changed(
-					#change_name::Different(self.describe(), other.describe()))
+				#[allow(unreachable_patterns)]
+				#different
 			}
 		} else {
 			quote!()
@@ -466,3 +536,269 @@ impl EnumDetails {
 		}
 	}
 }
+
+// With `#[comparable_transitions]`, an enum has a `Transition` type with a
+// variant for each change from one of its variants to another, named
+// `<From>To<To>` and holding the descriptions of both values, and a method
+// giving the transition between two values, if they differ in variant.
+// Returns the definition of the type, and the implementation of the method.
+pub fn generate_transitions_for_enums(inputs: &Inputs) -> (TokenStream, TokenStream) {
+	let krate = crate_path();
+	let name = &inputs.input.ident;
+	let en = match &inputs.input.data {
+		syn::Data::Enum(en) => en,
+		_ => panic!("comparable_transitions is only supported for enums"),
+	};
+	let transition_name = format_ident!("{}Transition", name);
+	let desc_type = Definition::assoc_type(&self_type(), "Desc");
+	let mut variants: Vec<syn::Variant> = Vec::new();
+	let mut arms = Vec::new();
+	for from in &en.variants {
+		for to in en.variants.iter().filter(|to| to.ident != from.ident) {
+			let (from_name, to_name) = (&from.ident, &to.ident);
+			let variant = format_ident!("{}To{}", from_name, to_name);
+			variants.push(
+				syn::parse2(quote!(#variant { before: #desc_type, after: #desc_type }))
+					.expect("Failed to parse transition variant"),
+			);
+			arms.push(quote! {
+				(Self::#from_name { .. }, Self::#to_name { .. }) => Some(#transition_name::#variant {
+					before: #krate::Comparable::describe(self),
+					after: #krate::Comparable::describe(other),
+				})
+			});
+		}
+	}
+	let transition = generate_type_definition(
+		&inputs.visibility,
+		&transition_name,
+		&syn::Data::Enum(syn::DataEnum {
+			variants: FromIterator::from_iter(variants),
+			enum_token: Default::default(),
+			brace_token: Default::default(),
+		}),
+		&inputs.self_type,
+		&inputs.input.generics,
+		None,
+		false,
+	);
+	let ty = &transition.ty;
+	let (impl_generics, type_generics, _) = inputs.input.generics.split_for_impl();
+	let where_clause = inputs.where_clause(&quote!(#krate::Comparable));
+	(
+		transition.definition,
+		quote! {
+			impl #impl_generics #name #type_generics #where_clause {
+				/// The transition from this value to `other`, if they are
+				/// different variants.
+				pub fn transition(&self, other: &Self) -> Option<#ty> {
+					match (self, other) {
+						#(#arms,)*
+						_ => None,
+					}
+				}
+			}
+		},
+	)
+}
+
+// With `#[comparable_lens]`, an enum implements `Apply`: the changes within a
+// variant are applied to the fields of a value holding that variant, and a
+// change to another variant replaces the value with one rebuilt from its
+// description, which requires the enum to implement `FromDesc`. Such a change
+// is verified by comparing the value's description with that before.
+pub fn generate_apply_for_enums(inputs: &Inputs) -> TokenStream {
+	let krate = crate_path();
+	let name = &inputs.input.ident;
+	let en = match &inputs.input.data {
+		syn::Data::Enum(en) => en,
+		_ => panic!("comparable_lens is only supported for structs and enums"),
+	};
+	if inputs.attrs.variant_struct_fields {
+		panic!("comparable_lens is not supported with variant_struct_fields");
+	}
+	let change_name = format_ident!("{}{}", name, inputs.attrs.comparable_change_suffix);
+	let different = en.variants.len() > 1 || inputs.attrs.non_exhaustive;
+	let variants = if inputs.attrs.comparable_variant_only { Vec::new() } else { en.variants.iter().collect() };
+
+	// For each variant with changes of its own, the patterns matching a value
+	// holding it and its changes, and how the changes of each of its fields
+	// are applied, checked and verified.
+	let arms: Vec<_> = variants
+		.into_iter()
+		.filter(|variant| variant.fields.iter().any(|field| !is_ignored(field)))
+		.map(|variant| {
+			let many_fields = variant.fields.len() > 1;
+			let fields = variant.fields.iter().enumerate().map(|(index, field)| {
+				let self_var = local(&format!("self_var{}", index));
+				let base_var = local(&format!("base_var{}", index));
+				let change_var = local(&format!("changes_var{}", index));
+				let field_name = field.ident.as_ref().map(ToString::to_string).unwrap_or_else(|| index.to_string());
+				let unchecked = Definition::is_redacted(field) || Definition::compared_by(field).is_some();
+				let (self_pat, change_pat) = if is_ignored(field) {
+					(quote!(_), None)
+				} else if unchecked {
+					(quote!(_), Some(quote!(_)))
+				} else {
+					(quote!(#self_var), Some(quote!(#change_var)))
+				};
+				let within = |call: TokenStream| {
+					quote! {
+						#call.map_err(|e| e.within(#krate::location::Segment::Field(#field_name.to_string())))?;
+					}
+				};
+				let leaf = Definition::is_leaf(field);
+				let base_pat = if is_ignored(field) || unchecked || leaf { quote!(_) } else { quote!(#base_var) };
+				let (apply, check, verify, verify_from) = if is_ignored(field) || unchecked {
+					(quote!(), quote!(), quote!(), quote!())
+				} else if leaf {
+					let verify = within(quote!(#krate::lens::expect_current(#self_var, &#change_var.0)));
+					(
+						quote!(*#self_var = #change_var.1;),
+						quote!(let _ = (#self_var, #change_var);),
+						verify.clone(),
+						verify,
+					)
+				} else {
+					let (owned, borrowed) = if Definition::is_boxed(field) {
+						(quote!(*#change_var), quote!(&**#change_var))
+					} else {
+						(quote!(#change_var), quote!(#change_var))
+					};
+					(
+						quote!(#krate::lens::Apply::apply(#self_var, #owned);),
+						within(quote!(#krate::lens::Apply::check(#self_var, #borrowed))),
+						within(quote!(#krate::lens::Apply::verify(#self_var, #borrowed))),
+						within(quote!(#krate::lens::Apply::verify_from(#self_var, #base_var, #borrowed))),
+					)
+				};
+				// The changes of each field of a variant with several are
+				// reported as `Changed`.
+				let unless_unchanged = |body: TokenStream| {
+					if many_fields && !body.is_empty() {
+						quote! {
+							if let #krate::Changed::Changed(#change_var) = #change_var {
+								#body
+							}
+						}
+					} else {
+						body
+					}
+				};
+				(
+					field.ident.clone(),
+					(self_pat, base_pat),
+					change_pat,
+					(
+						unless_unchanged(apply),
+						unless_unchanged(check),
+						unless_unchanged(verify),
+						unless_unchanged(verify_from),
+					),
+				)
+			});
+			let fields: Vec<_> = fields.collect();
+			let (self_capture, base_capture, change_capture) = match &variant.fields {
+				syn::Fields::Named(_) => {
+					let self_pats = fields.iter().map(|(ident, (self_pat, _), _, _)| quote!(#ident: #self_pat));
+					let base_pats = fields.iter().map(|(ident, (_, base_pat), _, _)| quote!(#ident: #base_pat));
+					let change_pats = fields
+						.iter()
+						.filter_map(|(ident, _, change_pat, _)| change_pat.as_ref().map(|pat| quote!(#ident: #pat)));
+					(quote!({ #(#self_pats),* }), quote!({ #(#base_pats),* }), quote!({ #(#change_pats),* }))
+				}
+				_ => {
+					let self_pats = fields.iter().map(|(_, (self_pat, _), _, _)| self_pat);
+					let base_pats = fields.iter().map(|(_, (_, base_pat), _, _)| base_pat);
+					let change_pats = fields.iter().filter_map(|(_, _, change_pat, _)| change_pat.as_ref());
+					(quote!((#(#self_pats),*)), quote!((#(#base_pats),*)), quote!((#(#change_pats),*)))
+				}
+			};
+			let variant_name = &variant.ident;
+			let both_ident = Definition::both_variant_name(variant);
+			let pattern = quote!((#name::#variant_name #self_capture, #change_name::#both_ident #change_capture));
+			let base_pattern = quote! {
+				(
+					#name::#variant_name #self_capture,
+					#name::#variant_name #base_capture,
+					#change_name::#both_ident #change_capture,
+				)
+			};
+			let applies = fields.iter().map(|(_, _, _, (apply, _, _, _))| apply);
+			let checks = fields.iter().map(|(_, _, _, (_, check, _, _))| check);
+			let verifies = fields.iter().map(|(_, _, _, (_, _, verify, _))| verify);
+			let verifies_from = fields.iter().map(|(_, _, _, (_, _, _, verify_from))| verify_from);
+			(
+				quote!(#pattern => { #(#applies)* }),
+				quote!(#pattern => { #(#checks)* }),
+				quote!(#pattern => { #(#verifies)* }),
+				quote!(#base_pattern => { #(#verifies_from)* Ok(()) }),
+			)
+		})
+		.collect();
+	let apply_arms = arms.iter().map(|(apply, _, _, _)| apply);
+	let check_arms = arms.iter().map(|(_, check, _, _)| check);
+	let verify_arms = arms.iter().map(|(_, _, verify, _)| verify);
+	let verify_from_arms = arms.iter().map(|(_, _, _, verify_from)| verify_from);
+	let (apply_different, check_different, verify_different) = if different {
+		(
+			quote!((value, #change_name::Different(_, after)) => *value = #krate::roundtrip::FromDesc::from_desc(after),),
+			quote!((_, #change_name::Different(..)) => (),),
+			quote! {
+				(value, #change_name::Different(before, _)) => {
+					#krate::lens::expect_current(&#krate::Comparable::describe(value), before)?
+				}
+			},
+		)
+	} else {
+		(quote!(), quote!(), quote!())
+	};
+
+	let (impl_generics, type_generics, _) = inputs.input.generics.split_for_impl();
+	let mut where_clause = inputs
+		.where_clause(&quote!(#krate::lens::Apply))
+		.unwrap_or_else(|| syn::WhereClause { where_token: Default::default(), predicates: Default::default() });
+	if different {
+		where_clause.predicates.push(syn::parse_quote!(Self: #krate::roundtrip::FromDesc));
+	}
+	quote! {
+		impl #impl_generics #krate::lens::Apply for #name #type_generics #where_clause {
+			fn apply(&mut self, change: Self::Change) {
+				match (self, change) {
+					#(#apply_arms)*
+					#apply_different
+					#[allow(unreachable_patterns)]
+					_ => (),
+				}
+			}
+
+			fn check(&self, change: &Self::Change) -> Result<(), #krate::lens::ApplyError> {
+				match (self, change) {
+					#(#check_arms)*
+					#check_different
+					#[allow(unreachable_patterns)]
+					_ => return Err(#krate::lens::ApplyError::VariantMismatch(#krate::location::Path::default())),
+				}
+				Ok(())
+			}
+
+			fn verify(&self, change: &Self::Change) -> Result<(), #krate::lens::ConflictError> {
+				match (self, change) {
+					#(#verify_arms)*
+					#verify_different
+					#[allow(unreachable_patterns)]
+					_ => return Err(#krate::lens::ApplyError::VariantMismatch(#krate::location::Path::default()).into()),
+				}
+				Ok(())
+			}
+
+			fn verify_from(&self, base: &Self, change: &Self::Change) -> Result<(), #krate::lens::ConflictError> {
+				match (self, base, change) {
+					#(#verify_from_arms)*
+					#[allow(unreachable_patterns)]
+					_ => #krate::lens::Apply::verify(self, change),
+				}
+			}
+		}
+	}
+}
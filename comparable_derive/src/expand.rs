@@ -0,0 +1,109 @@
+// Snapshots of the code derived for the types in `tests/expand`, so that
+// changes to what the derives generate are seen in review, rather than only
+// when they break a use of the generated types.
+//
+// Each `NAME.rs` there holds types deriving `Comparable` or `Describe`, and
+// `NAME.expanded.rs` the code derived for them, printed by prettyplease, so
+// that the snapshots do not depend on the version of rustfmt installed. The
+// `serde` feature adds to the code derived, which is kept in
+// `NAME.serde.expanded.rs` instead. Running the tests with
+// `COMPARABLE_EXPAND=overwrite` writes the snapshots of the features enabled,
+// rather than comparing with them:
+//
+//     COMPARABLE_EXPAND=overwrite cargo test -p comparable_derive
+//     COMPARABLE_EXPAND=overwrite cargo test -p comparable_derive --features serde
+
+use std::path::{Path, PathBuf};
+
+use proc_macro2::TokenStream;
+
+use crate::utils::has_attr;
+
+const SUFFIX: &str = if cfg!(feature = "serde") { "serde.expanded.rs" } else { "expanded.rs" };
+
+// The types of an input file, in order.
+struct Items(Vec<syn::DeriveInput>);
+
+impl syn::parse::Parse for Items {
+	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+		let mut items = Vec::new();
+		while !input.is_empty() {
+			items.push(input.parse()?);
+		}
+		Ok(Items(items))
+	}
+}
+
+// The code derived for each type, by the derives it names.
+fn expand(source: &str) -> TokenStream {
+	let Items(items) = syn::parse_str(source).expect("Failed to parse input");
+	items
+		.into_iter()
+		.flat_map(|item| {
+			let derives = has_attr(&item.attrs, "derive")
+				.and_then(|attr| {
+					attr.parse_args_with(syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated)
+						.ok()
+				})
+				.unwrap_or_default();
+			derives
+				.into_iter()
+				.filter_map(|derive| {
+					if derive.is_ident("Comparable") {
+						Some(crate::derive_comparable(item.clone()))
+					} else if derive.is_ident("Describe") {
+						Some(crate::derive_describe(item.clone()))
+					} else {
+						None
+					}
+				})
+				.collect::<Vec<_>>()
+		})
+		.collect()
+}
+
+fn unparse(tokens: TokenStream) -> String {
+	let file = syn::parse2::<syn::File>(tokens.clone())
+		.unwrap_or_else(|err| panic!("The expansion is not valid Rust: {}\n{}", err, tokens));
+	prettyplease::unparse(&file)
+}
+
+fn inputs(dir: &Path) -> Vec<PathBuf> {
+	let mut inputs = std::fs::read_dir(dir)
+		.expect("Failed to read tests/expand")
+		.map(|entry| entry.expect("Failed to read tests/expand").path())
+		.filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+		.filter(|path| !path.to_string_lossy().ends_with(".expanded.rs"))
+		.collect::<Vec<_>>();
+	inputs.sort();
+	inputs
+}
+
+#[test]
+fn test_expansions() {
+	let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("expand");
+	let overwrite = std::env::var("COMPARABLE_EXPAND").is_ok_and(|mode| mode == "overwrite");
+	let mut failures = Vec::new();
+	for input in inputs(&dir) {
+		let source = std::fs::read_to_string(&input).expect("Failed to read input");
+		let expanded = unparse(expand(&source));
+		let snapshot = input.with_extension(SUFFIX);
+		if overwrite {
+			std::fs::write(&snapshot, expanded).expect("Failed to write snapshot");
+			continue;
+		}
+		match std::fs::read_to_string(&snapshot) {
+			Ok(expected) if expected == expanded => (),
+			Ok(expected) => {
+				let line = expected.lines().zip(expanded.lines()).take_while(|(a, b)| a == b).count() + 1;
+				failures.push(format!("{} differs from line {}", snapshot.display(), line));
+			}
+			Err(_) => failures.push(format!("{} is missing", snapshot.display())),
+		}
+	}
+	assert!(
+		failures.is_empty(),
+		"The derived code has changed; if that is intended, run with COMPARABLE_EXPAND=overwrite:\n{}",
+		failures.join("\n")
+	);
+}
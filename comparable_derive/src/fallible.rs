@@ -0,0 +1,118 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::definition::*;
+use crate::inputs::*;
+use crate::utils::*;
+
+// With `#[comparable_try]`, `TryComparable` is implemented instead of
+// `Comparable`, so that the fields of a struct may be values that cannot
+// always be described or compared, such as those behind a lock. The change
+// type is a list of the changes to each field, as it is for a struct with
+// several fields, and the first field that fails makes the whole fail.
+pub fn generate_try_comparable(inputs: &Inputs) -> TokenStream {
+	let krate = crate_path();
+	let st = match &inputs.input.data {
+		syn::Data::Struct(st) => st,
+		_ => panic!("comparable_try is only supported for structs"),
+	};
+	let trait_path = quote!(#krate::fallible::TryComparable);
+	let name = &inputs.input.ident;
+	let desc_name = format_ident!("{}{}", name, inputs.attrs.comparable_desc_suffix);
+	let change_name = format_ident!("{}{}", name, inputs.attrs.comparable_change_suffix);
+
+	let fields = map_fields(false, st.fields.iter(), true, |r| {
+		if Definition::is_redacted(r.field)
+			|| Definition::compared_by(r.field).is_some()
+			|| Definition::is_leaf(r.field)
+		{
+			panic!("comparable_try does not support comparable_redact, comparable_by or comparable_leaf");
+		}
+		let value = (r.accessor)(&format_ident!("self"));
+		let other = (r.accessor)(&format_ident!("other"));
+		(r.field.clone(), Definition::variant_name_from_field(r.index, &r.field.ident), value, other)
+	});
+
+	let generate = |type_name: &syn::Ident, data: &syn::Data| {
+		generate_type_definition(
+			&inputs.visibility,
+			type_name,
+			data,
+			&inputs.self_type,
+			&inputs.input.generics,
+			None,
+			inputs.attrs.non_exhaustive,
+		)
+	};
+	let desc = generate(
+		&desc_name,
+		&map_on_fields_over_datastruct(false, st, |r| syn::Field {
+			ty: Definition::trait_assoc_type(&trait_path, &r.field.ty, "Desc"),
+			..r.field.clone()
+		}),
+	);
+	let variants = fields.iter().map(|(field, variant, _, _)| {
+		let ty = Definition::trait_assoc_type(&trait_path, &field.ty, "Change");
+		quote!(#variant(#ty))
+	});
+	let change = generate(
+		&change_name,
+		&syn::parse2::<syn::DeriveInput>(quote!(enum #change_name { #(#variants),* }))
+			.expect("Failed to parse Change type")
+			.data,
+	);
+
+	// The fields of the description, which for a tuple struct are numbered
+	// without the fields that are ignored.
+	let desc_members = fields.iter().enumerate().map(|(index, (field, _, _, _))| match &field.ident {
+		Some(ident) => quote!(#ident),
+		None => {
+			let index = syn::Index::from(index);
+			quote!(#index)
+		}
+	});
+	let described = fields.iter().map(|(_, _, value, _)| quote!(#trait_path::try_describe(&#value)?));
+	let changes = local("changes");
+	let compared = fields.iter().map(|(_, variant, value, other)| {
+		quote!(#changes.extend(#trait_path::try_comparison(&#value, &#other)?.map(#change_name::#variant));)
+	});
+
+	let (impl_generics, type_generics, _) = inputs.input.generics.split_for_impl();
+	let where_clause = inputs.where_clause(&trait_path);
+	let desc_def = &desc.definition;
+	let desc_ty = &desc.ty;
+	// A struct whose fields are all ignored is never changed.
+	let (change_def, change_ty, comparison) = if fields.is_empty() {
+		(quote!(), quote!(()), quote!(Ok(#krate::Changed::Unchanged)))
+	} else {
+		let (definition, ty) = (&change.definition, &change.ty);
+		(
+			quote!(#definition),
+			quote!(Vec<#ty>),
+			quote! {
+				let mut #changes = Vec::new();
+				#(#compared)*
+				Ok(#krate::Changed::from_changes(#changes))
+			},
+		)
+	};
+	quote! {
+		#desc_def
+		#change_def
+
+		impl #impl_generics #trait_path for #name #type_generics #where_clause {
+			type Desc = #desc_ty;
+			fn try_describe(&self) -> Result<Self::Desc, #krate::fallible::Error> {
+				Ok(#desc_name { #(#desc_members: #described),* })
+			}
+
+			type Change = #change_ty;
+			fn try_comparison(
+				&self,
+				other: &Self,
+			) -> Result<#krate::Changed<Self::Change>, #krate::fallible::Error> {
+				#comparison
+			}
+		}
+	}
+}
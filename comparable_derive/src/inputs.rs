@@ -1,17 +1,85 @@
-use proc_macro2::Span;
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::quote;
 
 use crate::attrs::*;
 use crate::definition::*;
 use crate::outputs::*;
+use crate::utils::*;
 
 pub struct Inputs<'a> {
 	pub attrs: Attributes,
 	pub input: &'a syn::DeriveInput,
 	pub visibility: syn::Visibility,
+	// The trait providing the `Desc` type and `describe` method of each field.
+	pub describe_trait: TokenStream,
+	// The type being derived for, applied to its own generic parameters.
+	pub self_type: syn::Type,
+}
+
+// Whether a type refers to the type `name`, such as `Box<Self>` or
+// `Option<Box<Node>>`. Paths to associated items such as `Self::N` are not
+// counted.
+fn is_recursive(ty: &syn::Type, name: &syn::Ident) -> bool {
+	fn refers(tokens: TokenStream, name: &syn::Ident) -> bool {
+		let mut tokens = tokens.into_iter().peekable();
+		while let Some(tt) = tokens.next() {
+			let found = match tt {
+				TokenTree::Group(group) => refers(group.stream(), name),
+				TokenTree::Ident(ident) if ident == *name || ident == "Self" => {
+					!matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == ':')
+				}
+				_ => false,
+			};
+			if found {
+				return true;
+			}
+		}
+		false
+	}
+	refers(quote!(#ty), name)
+}
+
+// Mark every field whose type refers to the type containing it with
+// `comparable_box`.
+pub fn box_recursive_fields(input: &mut syn::DeriveInput) {
+	let name = input.ident.clone();
+	let mark = |fields: &mut syn::Fields| {
+		for field in fields.iter_mut() {
+			if has_attr(&field.attrs, "comparable_box").is_none() && is_recursive(&field.ty, &name) {
+				field.attrs.push(syn::parse_quote!(#[comparable_box]));
+			}
+		}
+	};
+	match &mut input.data {
+		syn::Data::Struct(st) => mark(&mut st.fields),
+		syn::Data::Enum(en) => en.variants.iter_mut().for_each(|variant| mark(&mut variant.fields)),
+		syn::Data::Union(_un) => (),
+	}
+}
+
+// A variant marked with `comparable_ignore` has each of its fields ignored,
+// so that two values of it are unchanged, and is described without them
+// unless it is given a description of its own.
+pub fn ignore_variants(input: &mut syn::DeriveInput) {
+	if let syn::Data::Enum(en) = &mut input.data {
+		for variant in en.variants.iter_mut().filter(|variant| has_attr(&variant.attrs, "comparable_ignore").is_some())
+		{
+			for field in variant.fields.iter_mut() {
+				if has_attr(&field.attrs, "comparable_ignore").is_none() {
+					field.attrs.push(syn::parse_quote!(#[comparable_ignore]));
+				}
+			}
+			if has_attr(&variant.attrs, "describe_type").is_none()
+				&& has_attr(&variant.attrs, "no_description").is_none()
+			{
+				variant.attrs.push(syn::parse_quote!(#[no_description]));
+			}
+		}
+	}
 }
 
 impl<'a> Inputs<'a> {
-	pub fn from(input: &'a syn::DeriveInput) -> Self {
+	pub fn from(input: &'a syn::DeriveInput, describe_trait: TokenStream) -> Self {
 		let attrs = Attributes::from(&input.attrs);
 
 		let visibility = if attrs.comparable_private {
@@ -22,17 +90,85 @@ impl<'a> Inputs<'a> {
 			input.vis.clone()
 		};
 
-		Inputs { attrs, input, visibility }
+		let name = &input.ident;
+		let (_, type_generics, _) = input.generics.split_for_impl();
+		let self_type = syn::parse2(quote!(#name #type_generics)).expect("Failed to parse type name");
+
+		Inputs { attrs, input, visibility, describe_trait, self_type }
+	}
+
+	// Every field type that mentions the type's generic parameters must itself
+	// implement `trait_path`, in addition to any bounds given by the type,
+	// or for fields compared as a whole, what `comparable::Leaf` requires.
+	// With `comparable_bound`, its predicates are used instead.
+	pub fn where_clause(&self, trait_path: &TokenStream) -> Option<syn::WhereClause> {
+		let generics = &self.input.generics;
+		if let Some(bounds) = &self.attrs.comparable_bound {
+			let mut where_clause = generics.where_clause.clone().unwrap_or_else(|| syn::WhereClause {
+				where_token: Default::default(),
+				predicates: Default::default(),
+			});
+			where_clause.predicates.extend(bounds.iter().cloned());
+			return Some(where_clause);
+		}
+		let bounded = |r: &FieldRef| {
+			let ty = &r.field.ty;
+			if Definition::is_leaf(r.field) {
+				(ty.clone(), quote!(#ty: PartialEq + Clone + std::fmt::Debug))
+			} else {
+				(ty.clone(), quote!(#ty: #trait_path))
+			}
+		};
+		let field_bounds = match &self.input.data {
+			syn::Data::Struct(st) => map_fields(true, st.fields.iter(), true, bounded),
+			syn::Data::Enum(en) => {
+				en.variants.iter().flat_map(|variant| map_fields(false, variant.fields.iter(), true, bounded)).collect()
+			}
+			syn::Data::Union(_un) => Vec::new(),
+		};
+		let mut where_clause = generics.where_clause.clone();
+		// Fields referring to the type itself are left out, since requiring them
+		// to be comparable would require the type to already be comparable.
+		for (_, bound) in field_bounds
+			.into_iter()
+			.filter(|(ty, _)| mentions_generics(quote!(#ty), generics) && !is_recursive(ty, &self.input.ident))
+		{
+			where_clause
+				.get_or_insert_with(|| syn::WhereClause {
+					where_token: Default::default(),
+					predicates: Default::default(),
+				})
+				.predicates
+				.push(syn::parse2(bound).expect("Failed to parse where clause"));
+		}
+		where_clause
 	}
 
 	pub fn process_data(&self) -> Outputs {
+		if self.attrs.comparable_transparent {
+			return Outputs {
+				desc: Some(Definition::generate_transparent_desc_type(self)),
+				change: Some(Definition::generate_transparent_change_type(self)),
+			};
+		}
+
 		let is_unitary = match &self.input.data {
 			syn::Data::Struct(st) => match &st.fields {
 				syn::Fields::Unit => true,
 				syn::Fields::Unnamed(unnamed) => unnamed.unnamed.is_empty(),
 				syn::Fields::Named(named) => named.named.is_empty(),
 			},
-			syn::Data::Enum(en) => en.variants.is_empty(),
+			// An enum whose only variant has nothing to compare, since its
+			// fields are ignored, has no changes to report either. With several
+			// variants, which of them a value holds is still compared, as for
+			// any enum without fields.
+			syn::Data::Enum(en) => {
+				en.variants.len() < 2
+					&& !self.attrs.non_exhaustive
+					&& en.variants.iter().all(|variant| {
+						self.attrs.comparable_variant_only || field_count(true, variant.fields.iter()) == 0
+					})
+			}
 			syn::Data::Union(_st) => {
 				panic!("Comparable derivation not available for unions");
 			}
@@ -43,4 +179,24 @@ impl<'a> Inputs<'a> {
 			change: if is_unitary { None } else { Some(Definition::generate_change_type(self)) },
 		}
 	}
+
+	pub fn process_describe_data(&self) -> Outputs {
+		if let syn::Data::Union(_st) = &self.input.data {
+			panic!("Describe derivation not available for unions");
+		}
+		if self.attrs.compare_default {
+			panic!("compare_default requires #[derive(Comparable)]");
+		}
+
+		Outputs {
+			desc: if self.attrs.comparable_transparent {
+				Some(Definition::generate_transparent_desc_type(self))
+			} else if self.attrs.no_description {
+				None
+			} else {
+				Some(Definition::generate_desc_type(self))
+			},
+			change: None,
+		}
+	}
 }
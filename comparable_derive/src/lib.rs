@@ -1,6 +1,9 @@
 mod attrs;
 mod definition;
 mod enums;
+#[cfg(test)]
+mod expand;
+mod fallible;
 mod inputs;
 mod outputs;
 mod structs;
@@ -15,16 +18,96 @@ mod utils;
 		self_describing,
 		variant_struct_fields,
 		compare_default,
+		comparable_from_default,
+		comparable_desc_roundtrip,
 		comparable_public,
 		comparable_private,
 		comparable_synthetic,
 		comparable_desc_suffix,
 		comparable_change_suffix,
 		comparable_ignore,
+		comparable_include,
+		comparable_box,
+		comparable_redact,
+		comparable_by,
+		comparable_leaf,
+		comparable_group,
+		comparable_rename,
+		comparable_flatten,
+		comparable_transparent,
+		comparable_module,
+		comparable_version,
+		comparable_migrate,
+		comparable_from,
+		comparable_added,
+		comparable_schema,
+		comparable_paths,
+		comparable_lens,
+		comparable_sql,
+		comparable_display,
+		comparable_tree,
+		comparable_transitions,
+		comparable_variant_only,
+		comparable_crate,
+		comparable_bound,
+		comparable_try,
 	)
 )]
 pub fn comparable_macro(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-	let input = syn::parse_macro_input!(input as syn::DeriveInput);
-	let inputs = crate::inputs::Inputs::from(&input);
-	inputs.process_data().generate(&inputs).into()
+	derive_comparable(syn::parse_macro_input!(input as syn::DeriveInput)).into()
+}
+
+// The code derived by `#[derive(Comparable)]`, apart from the compiler's
+// interface to procedural macros so that the tests can expand it.
+fn derive_comparable(mut input: syn::DeriveInput) -> proc_macro2::TokenStream {
+	crate::inputs::box_recursive_fields(&mut input);
+	crate::inputs::ignore_variants(&mut input);
+	crate::utils::with_crate_path(crate::utils::resolve_crate_path(&input.attrs), || {
+		let path = crate::utils::crate_path();
+		let inputs = crate::inputs::Inputs::from(&input, quote::quote!(#path::Comparable));
+		if inputs.attrs.comparable_try {
+			return crate::fallible::generate_try_comparable(&inputs);
+		}
+		inputs.process_data().generate(&inputs)
+	})
+}
+
+#[proc_macro_derive(
+	Describe,
+	attributes(
+		describe_type,
+		describe_body,
+		no_description,
+		self_describing,
+		comparable_public,
+		comparable_private,
+		comparable_synthetic,
+		comparable_desc_suffix,
+		comparable_ignore,
+		comparable_include,
+		comparable_box,
+		comparable_redact,
+		comparable_by,
+		comparable_leaf,
+		comparable_transparent,
+		comparable_module,
+		comparable_version,
+		comparable_migrate,
+		comparable_crate,
+		comparable_bound,
+	)
+)]
+pub fn describe_macro(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+	derive_describe(syn::parse_macro_input!(input as syn::DeriveInput)).into()
+}
+
+// The code derived by `#[derive(Describe)]`.
+fn derive_describe(mut input: syn::DeriveInput) -> proc_macro2::TokenStream {
+	crate::inputs::box_recursive_fields(&mut input);
+	crate::inputs::ignore_variants(&mut input);
+	crate::utils::with_crate_path(crate::utils::resolve_crate_path(&input.attrs), || {
+		let path = crate::utils::crate_path();
+		let inputs = crate::inputs::Inputs::from(&input, quote::quote!(#path::describe::Describe));
+		inputs.process_describe_data().generate_describe(&inputs)
+	})
 }
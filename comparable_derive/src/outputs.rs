@@ -1,8 +1,11 @@
+use convert_case::{Case, Casing};
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 
 use crate::definition::*;
+use crate::enums::*;
 use crate::inputs::*;
+use crate::structs::*;
 use crate::utils::*;
 
 pub struct Outputs {
@@ -12,14 +15,15 @@ pub struct Outputs {
 
 impl Outputs {
 	pub fn generate(self, inputs: &Inputs) -> TokenStream {
+		let krate = crate_path();
 		let Outputs { desc, change } = self;
 
 		let impl_comparable = Self::impl_comparable(
-			&inputs.input.ident,
+			inputs,
 			desc.as_ref().and_then(|d| d.ty.as_ref()).unwrap_or(&unit_type()),
 			desc.as_ref().map(|d| &d.method_body).unwrap_or(&quote!()),
 			change.as_ref().and_then(|c| c.ty.as_ref()).unwrap_or(&unit_type()),
-			change.as_ref().map(|c| &c.method_body).unwrap_or(&quote!(comparable::Changed::Unchanged)),
+			change.as_ref().map(|c| &c.method_body).unwrap_or(&quote!(#krate::Changed::Unchanged)),
 		);
 
 		#[allow(unused_variables)] // compiler doesn't see the use of x
@@ -27,32 +31,274 @@ impl Outputs {
 		#[allow(unused_variables)] // compiler doesn't see the use of x
 		let change = change.map(|x| quote!(#x)).unwrap_or_default();
 
+		let impl_from_default =
+			if inputs.attrs.comparable_from_default { Self::impl_from_default(inputs) } else { quote!() };
+		let impl_desc_roundtrip =
+			if inputs.attrs.comparable_desc_roundtrip { Self::impl_desc_roundtrip(inputs) } else { quote!() };
+		let impl_schema = if inputs.attrs.comparable_schema { Self::impl_schema(inputs) } else { quote!() };
+		let impl_lenses = match &inputs.input.data {
+			_ if !inputs.attrs.comparable_lens => quote!(),
+			syn::Data::Enum(_) => generate_apply_for_enums(inputs),
+			_ => generate_lenses_for_structs(inputs),
+		};
+		let impl_sql = inputs.attrs.comparable_sql.as_ref().map(|sql| generate_sql_changeset_for_structs(inputs, sql));
+		let (transitions, impl_transitions) = if inputs.attrs.comparable_transitions {
+			generate_transitions_for_enums(inputs)
+		} else {
+			(quote!(), quote!())
+		};
+		let (from_changes, impl_comparable_from): (Vec<TokenStream>, Vec<TokenStream>) =
+			inputs.attrs.comparable_from.iter().map(|from| generate_comparable_from_for_structs(inputs, from)).unzip();
+
+		let paths = if inputs.attrs.comparable_paths { Self::paths_module(inputs) } else { quote!() };
+
+		let generated = Self::in_module(
+			inputs,
+			quote! {
+				#desc
+				#change
+				#transitions
+				#(#from_changes)*
+			},
+			quote! {
+				#impl_comparable
+				#impl_from_default
+				#impl_desc_roundtrip
+				#impl_schema
+				#impl_lenses
+				#impl_sql
+				#impl_transitions
+				#(#impl_comparable_from)*
+			},
+		);
+		quote! {
+			#generated
+			#paths
+		}
+	}
+
+	// With `comparable_paths`, a module named after the type in snake case
+	// holds a `path` module, with a constant for the mask path of each field
+	// compared, named after the field in upper snake case.
+	fn paths_module(inputs: &Inputs) -> TokenStream {
+		let vis = &inputs.visibility;
+		let module = format_ident!("{}", inputs.input.ident.to_string().to_case(Case::Snake));
+		let names = Definition::mask_field_names(inputs);
+		let consts = names.iter().map(|name| {
+			let upper = if name.starts_with(|c: char| c.is_ascii_digit()) {
+				format_ident!("FIELD_{}", name)
+			} else {
+				format_ident!("{}", name.to_case(Case::UpperSnake))
+			};
+			quote!(pub const #upper: &str = #name;)
+		});
+		quote! {
+			#vis mod #module {
+				pub mod path {
+					#(#consts)*
+				}
+			}
+		}
+	}
+
+	fn impl_from_default(inputs: &Inputs) -> TokenStream {
+		let krate = crate_path();
+		let name = &inputs.input.ident;
+		let (impl_generics, type_generics, _) = inputs.input.generics.split_for_impl();
+		let mut where_clause = inputs
+			.where_clause(&quote!(#krate::Comparable))
+			.unwrap_or_else(|| syn::WhereClause { where_token: Default::default(), predicates: Default::default() });
+		where_clause.predicates.push(syn::parse_quote!(Self: Default));
+		quote! {
+			impl #impl_generics #name #type_generics #where_clause {
+				/// Report how this value differs from the default value of its type.
+				pub fn comparison_from_default(&self) -> #krate::Changed<<Self as #krate::Comparable>::Change> {
+					#krate::from_default(self)
+				}
+			}
+		}
+	}
+
+	fn impl_desc_roundtrip(inputs: &Inputs) -> TokenStream {
+		let krate = crate_path();
+		let name = &inputs.input.ident;
+		let (impl_generics, type_generics, _) = inputs.input.generics.split_for_impl();
+		let from_where_clause = inputs.where_clause(&quote!(#krate::roundtrip::FromDesc));
+		let into_where_clause = inputs.where_clause(&quote!(#krate::roundtrip::IntoDesc));
+		let (from_desc_body, into_desc_body) = Definition::generate_desc_roundtrip(inputs);
+		quote! {
+			impl #impl_generics #krate::roundtrip::FromDesc for #name #type_generics #from_where_clause {
+				fn from_desc(desc: <Self as #krate::Comparable>::Desc) -> Self {
+					#from_desc_body
+				}
+			}
+
+			impl #impl_generics #krate::roundtrip::IntoDesc for #name #type_generics #into_where_clause {
+				fn into_desc(self) -> <Self as #krate::Comparable>::Desc {
+					#into_desc_body
+				}
+			}
+		}
+	}
+
+	fn impl_schema(inputs: &Inputs) -> TokenStream {
+		let krate = crate_path();
+		let name = &inputs.input.ident;
+		let (impl_generics, type_generics, _) = inputs.input.generics.split_for_impl();
+		let where_clause = inputs.where_clause(&quote!(#krate::schema::HasSchema));
+		let body = Definition::generate_schema_body(inputs);
+		quote! {
+			impl #impl_generics #krate::schema::HasSchema for #name #type_generics #where_clause {
+				fn schema() -> #krate::schema::Schema {
+					#body
+				}
+			}
+		}
+	}
+
+	pub fn generate_describe(self, inputs: &Inputs) -> TokenStream {
+		let krate = crate_path();
+		let name = &inputs.input.ident;
+		let (impl_generics, type_generics, _) = inputs.input.generics.split_for_impl();
+		let where_clause = inputs.where_clause(&inputs.describe_trait);
+		let describe_type = self.desc.as_ref().and_then(|d| d.ty.clone()).unwrap_or_else(unit_type);
+		let describe_body = self.desc.as_ref().map(|d| d.method_body.clone()).unwrap_or_default();
+		#[allow(unused_variables)] // compiler doesn't see the use of x
+		let desc = self.desc.map(|x| quote!(#x)).unwrap_or_default();
+
+		Self::in_module(
+			inputs,
+			desc,
+			quote! {
+				impl #impl_generics #krate::describe::Describe for #name #type_generics #where_clause {
+					type Desc = #describe_type;
+					fn describe(&self) -> Self::Desc {
+						#describe_body
+					}
+				}
+			},
+		)
+	}
+
+	// With `#[comparable_module = "name"]`, the generated types are placed in a
+	// child module of that name, along with the trait implementation that
+	// refers to them. Since the module sits one level deeper than the original
+	// type, every visibility given to the generated types and their fields is
+	// widened by one level, so that they remain usable wherever they would have
+	// been without the module.
+	fn in_module(inputs: &Inputs, definitions: TokenStream, implementation: TokenStream) -> TokenStream {
+		let module = match &inputs.attrs.comparable_module {
+			None => {
+				return quote! {
+					#definitions
+					#implementation
+				}
+			}
+			Some(module) => module,
+		};
+		let vis = &inputs.visibility;
+		let items = syn::parse2::<syn::File>(definitions)
+			.expect("Failed to parse generated definitions")
+			.items
+			.into_iter()
+			.map(|item| match item {
+				syn::Item::Struct(mut st) => {
+					st.vis = nested_visibility(&st.vis);
+					st.fields.iter_mut().for_each(|field| field.vis = nested_visibility(&field.vis));
+					syn::Item::Struct(st)
+				}
+				syn::Item::Enum(mut en) => {
+					en.vis = nested_visibility(&en.vis);
+					syn::Item::Enum(en)
+				}
+				item => item,
+			});
 		quote! {
-			#desc
-			#change
-			#impl_comparable
+			#vis mod #module {
+				use super::*;
+				#(#items)*
+				#implementation
+			}
 		}
 	}
 
 	fn impl_comparable(
-		name: &syn::Ident,
+		inputs: &Inputs,
 		describe_type: &syn::Type,
 		describe_body: &TokenStream,
 		change_type: &syn::Type,
 		change_body: &TokenStream,
 	) -> TokenStream {
+		let krate = crate_path();
+		let name = &inputs.input.ident;
+		let (impl_generics, type_generics, _) = inputs.input.generics.split_for_impl();
+		let where_clause = inputs.where_clause(&quote!(#krate::Comparable));
+		let similarity = Definition::generate_similarity_body(inputs).map(|body| {
+			quote! {
+				fn similarity(&self, other: &Self) -> f64 {
+					#body
+				}
+			}
+		});
+		let differs = Definition::generate_differs_body(inputs).map(|body| {
+			quote! {
+				fn differs(&self, other: &Self) -> bool {
+					#body
+				}
+			}
+		});
+		let count_changes = Definition::generate_count_changes_body(inputs).map(|body| {
+			quote! {
+				fn count_changes(&self, other: &Self) -> usize {
+					#body
+				}
+			}
+		});
+		let first_difference = Definition::generate_first_difference_body(inputs).map(|body| {
+			quote! {
+				fn first_difference(&self, other: &Self) -> Option<#krate::location::Path> {
+					#body
+				}
+			}
+		});
 		quote! {
-			impl comparable::Comparable for #name {
+			impl #impl_generics #krate::Comparable for #name #type_generics #where_clause {
 				type Desc = #describe_type;
 				fn describe(&self) -> Self::Desc {
 					#describe_body
 				}
 
 				type Change = #change_type;
-				fn comparison(&self, other: &Self) -> comparable::Changed<Self::Change> {
+				fn comparison(&self, other: &Self) -> #krate::Changed<Self::Change> {
 					#change_body
 				}
+
+				#similarity
+
+				#differs
+
+				#count_changes
+
+				#first_difference
 			}
 		}
 	}
 }
+
+fn nested_visibility(vis: &syn::Visibility) -> syn::Visibility {
+	let widened = match vis {
+		syn::Visibility::Inherited => quote!(pub(super)),
+		syn::Visibility::Restricted(restricted) => {
+			let path = &restricted.path;
+			if path.is_ident("self") {
+				quote!(pub(super))
+			} else if path.segments.first().is_some_and(|s| s.ident == "crate") {
+				return vis.clone();
+			} else {
+				quote!(pub(in super::#path))
+			}
+		}
+		_ => return vis.clone(),
+	};
+	syn::parse2(widened).expect("Failed to parse visibility")
+}
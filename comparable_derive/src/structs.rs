@@ -1,33 +1,43 @@
 use convert_case::{Case, Casing};
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 use std::iter::FromIterator;
 
+use crate::attrs::*;
 use crate::definition::*;
+use crate::inputs::*;
 use crate::utils::*;
 
-pub fn generate_describe_body_for_structs(desc_name: &syn::Ident, st: &syn::DataStruct) -> TokenStream {
+pub fn generate_describe_body_for_structs(
+	describe_trait: &TokenStream,
+	desc_name: &syn::Ident,
+	st: &syn::DataStruct,
+) -> TokenStream {
 	match &st.fields {
 		syn::Fields::Named(named) => {
-			let (field_names, field_accessors): (Vec<syn::Ident>, Vec<syn::Expr>) =
+			let (field_names, field_descriptions): (Vec<syn::Ident>, Vec<TokenStream>) =
 				map_fields(true, named.named.iter(), true, |r| {
+					let accessor = (*r.accessor)(&format_ident!("self"));
 					(
 						r.field.ident.as_ref().expect("Found unnamed field in named struct").clone(),
-						(*r.accessor)(&format_ident!("self")),
+						Definition::field_describe(describe_trait, r.field, &quote!(&#accessor)),
 					)
 				})
 				.into_iter()
 				.unzip();
 			quote! {
 				#desc_name {
-					#(#field_names: #field_accessors.describe()),*
+					#(#field_names: #field_descriptions),*
 				}
 			}
 		}
 		syn::Fields::Unnamed(unnamed) => {
-			let field_indices = map_fields(false, unnamed.unnamed.iter(), true, |r| syn::Index::from(r.index));
+			let field_descriptions = map_fields(false, unnamed.unnamed.iter(), true, |r| {
+				let idx = syn::Index::from(r.index);
+				Definition::field_describe(describe_trait, r.field, &quote!(&self.#idx))
+			});
 			quote! {
-				#desc_name(#(self.#field_indices.describe()),*)
+				#desc_name(#(#field_descriptions),*)
 			}
 		}
 		syn::Fields::Unit => {
@@ -36,7 +46,75 @@ pub fn generate_describe_body_for_structs(desc_name: &syn::Ident, st: &syn::Data
 	}
 }
 
-pub fn create_change_type_for_structs(st: &syn::DataStruct) -> Option<syn::Data> {
+// A group of fields marked with `#[comparable_group("name")]`, whose changes
+// are held together by a single variant of the `Change` type of a struct with
+// several fields, in a `Vec` of changes of their own type.
+pub struct FieldGroup {
+	pub name: String,
+	pub variant: syn::Ident,
+	pub change_name: syn::Ident,
+}
+
+pub fn field_group_name(field: &syn::Field) -> Option<String> {
+	has_attr(&field.attrs, "comparable_group").map(|attr| {
+		attr.parse_args::<syn::LitStr>()
+			.map(|name| name.value())
+			.unwrap_or_else(|_| panic!("comparable_group must be written as #[comparable_group(\"name\")]"))
+	})
+}
+
+// The groups of a struct's fields, in the order in which they first appear.
+// The fields of a struct with a single field are never grouped.
+pub fn field_groups(type_name: &syn::Ident, change_suffix: &syn::Ident, st: &syn::DataStruct) -> Vec<FieldGroup> {
+	if field_count(true, st.fields.iter()) < 2 {
+		return Vec::new();
+	}
+	let mut groups: Vec<FieldGroup> = Vec::new();
+	for name in map_fields(true, st.fields.iter(), true, |r| field_group_name(r.field)).into_iter().flatten() {
+		if !groups.iter().any(|group| group.name == name) {
+			let variant = format_ident!("{}", name.to_case(Case::Pascal));
+			let change_name = format_ident!("{}{}{}", type_name, variant, change_suffix);
+			groups.push(FieldGroup { name, variant, change_name });
+		}
+	}
+	groups
+}
+
+// The names by which the changes held by each variant of a struct's `Change`
+// type are known: those of the fields, as written in field masks, or those of
+// the groups holding them.
+pub fn change_variant_names(st: &syn::DataStruct, grouped: bool) -> Vec<String> {
+	let mut names = Vec::new();
+	for (field, group) in map_fields(true, st.fields.iter(), true, |r| {
+		let name = r.field.ident.as_ref().map(ToString::to_string).unwrap_or_else(|| r.index.to_string());
+		(name, field_group_name(r.field).filter(|_| grouped))
+	}) {
+		match group {
+			Some(group) if names.contains(&group) => (),
+			Some(group) => names.push(group),
+			None => names.push(field),
+		}
+	}
+	names
+}
+
+// The names of the fields in a group, as written in field masks.
+pub fn group_field_names(st: &syn::DataStruct, group: &FieldGroup) -> Vec<String> {
+	map_fields(true, st.fields.iter(), true, |r| {
+		let name = r.field.ident.as_ref().map(ToString::to_string).unwrap_or_else(|| r.index.to_string());
+		Some(name).filter(|_| field_group_name(r.field).as_ref() == Some(&group.name))
+	})
+	.into_iter()
+	.flatten()
+	.collect()
+}
+
+// Returns the `Change` type of a struct, and the types holding the changes of
+// each group of its fields.
+pub fn create_change_type_for_structs(
+	st: &syn::DataStruct,
+	groups: &[FieldGroup],
+) -> Option<(syn::Data, Vec<(syn::Ident, syn::Data)>)> {
 	// Produce a vec that takes ignore fields into account.
 	match field_count(true, st.fields.iter()) {
 		0 => None,
@@ -45,19 +123,21 @@ pub fn create_change_type_for_structs(st: &syn::DataStruct) -> Option<syn::Data>
 			// that could occur is in the single field, we only need to store that
 			// change data, rather than the varying combinations that could occur
 			// in the case of multiple fields.
-			Some(map_on_fields_over_datastruct(true, st, |r| syn::Field {
-				ty: Definition::assoc_type(&r.field.ty, "Change"),
-				..r.field.clone()
-			}))
+			Some((
+				map_on_fields_over_datastruct(true, st, |r| syn::Field {
+					ty: Definition::field_change_type(r.field),
+					..r.field.clone()
+				}),
+				Vec::new(),
+			))
 		}
 		_ => {
-			let change_field = |r: &FieldRef| -> syn::Variant {
-				let ident: syn::Ident = Definition::variant_name_from_field(r.index, &r.field.ident);
+			let variant = |ident: syn::Ident, ty: syn::Type| -> syn::Variant {
 				syn::Variant {
 					ident,
 					fields: syn::Fields::Unnamed(syn::FieldsUnnamed {
 						unnamed: FromIterator::from_iter(vec![syn::Field {
-							ty: Definition::assoc_type(&r.field.ty, "Change"),
+							ty,
 							attrs: Default::default(),
 							vis: syn::Visibility::Inherited,
 							ident: Default::default(),
@@ -69,42 +149,88 @@ pub fn create_change_type_for_structs(st: &syn::DataStruct) -> Option<syn::Data>
 					discriminant: Default::default(),
 				}
 			};
+			let change_field = |r: &FieldRef| -> (Option<String>, syn::Variant) {
+				let ty = if Definition::is_flattened(r.field) {
+					let ty = Definition::field_change_type(r.field);
+					syn::parse2(quote!(<#ty as IntoIterator>::Item)).expect("Failed to parse flattened Change type")
+				} else {
+					Definition::field_change_type(r.field)
+				};
+				(
+					field_group_name(r.field).filter(|_| !groups.is_empty()),
+					variant(Definition::field_variant_name(r), ty),
+				)
+			};
 
-			let variants = match &st.fields {
+			let fields = match &st.fields {
 				syn::Fields::Named(named) => map_fields(true, named.named.iter(), true, change_field),
 				syn::Fields::Unnamed(unnamed) => map_fields(true, unnamed.unnamed.iter(), true, change_field),
 				syn::Fields::Unit => Vec::new(),
 			};
+			let enumeration = |variants: Vec<syn::Variant>| {
+				syn::Data::Enum(syn::DataEnum {
+					variants: FromIterator::from_iter(variants),
+					enum_token: Default::default(),
+					brace_token: Default::default(),
+				})
+			};
 
-			Some(syn::Data::Enum(syn::DataEnum {
-				variants: FromIterator::from_iter(variants),
-				enum_token: Default::default(),
-				brace_token: Default::default(),
-			}))
+			// Each group takes the place of the first of its fields.
+			let mut variants = Vec::new();
+			let mut group_variants: Vec<Vec<syn::Variant>> = groups.iter().map(|_| Vec::new()).collect();
+			for (group, field_variant) in fields {
+				match group.and_then(|name| groups.iter().position(|group| group.name == name)) {
+					Some(index) => {
+						if group_variants[index].is_empty() {
+							let change_name = &groups[index].change_name;
+							variants.push(variant(
+								groups[index].variant.clone(),
+								syn::parse2(quote!(Vec<#change_name>)).expect("Failed to parse group Change type"),
+							));
+						}
+						group_variants[index].push(field_variant);
+					}
+					None => variants.push(field_variant),
+				}
+			}
+			let helpers = groups
+				.iter()
+				.zip(group_variants)
+				.map(|(group, variants)| (group.change_name.clone(), enumeration(variants)))
+				.collect();
+			Some((enumeration(variants), helpers))
 		}
 	}
 }
 
-pub fn generate_comparison_body_for_structs(change_name: &syn::Ident, st: &syn::DataStruct) -> TokenStream {
-	let (field_names_and_comparisons, field_variants): (Vec<(TokenStream, TokenStream)>, Vec<syn::Ident>) =
-		map_fields(true, st.fields.iter(), true, |r: &FieldRef| -> ((TokenStream, TokenStream), syn::Ident) {
-			let idx = syn::Index::from(r.index);
-			let (name, variant) = if let Some(name) = r.field.ident.as_ref() {
-				(quote!(#name), syn::Ident::new(&name.to_string().to_case(Case::Pascal), Span::call_site()))
-			} else {
-				(quote!(#idx), format_ident!("Field{}", r.index))
-			};
-			let self_value = (*r.accessor)(&format_ident!("self"));
-			let other_value = (*r.accessor)(&format_ident!("other"));
-			((name, quote!(#self_value.comparison(&#other_value))), variant)
-		})
-		.into_iter()
-		.unzip();
-	let (field_names, comparisons): (Vec<TokenStream>, Vec<TokenStream>) =
-		field_names_and_comparisons.into_iter().unzip();
+pub fn generate_comparison_body_for_structs(
+	change_name: &syn::Ident,
+	st: &syn::DataStruct,
+	groups: &[FieldGroup],
+) -> TokenStream {
+	let krate = crate_path();
+	let fields = map_fields(true, st.fields.iter(), true, |r: &FieldRef| {
+		let idx = syn::Index::from(r.index);
+		let name = if let Some(name) = r.field.ident.as_ref() { quote!(#name) } else { quote!(#idx) };
+		let variant = Definition::field_variant_name(r);
+		let group = field_group_name(r.field).and_then(|name| groups.iter().position(|group| group.name == name));
+		let field_change_name = group.map(|index| &groups[index].change_name).unwrap_or(change_name);
+		let self_value = (*r.accessor)(&format_ident!("self"));
+		let other_value = (*r.accessor)(&format_ident!("other"));
+		let comparison = Definition::field_comparison(r.index, r.field, &quote!(#self_value), &quote!(&#other_value));
+		let changes = if Definition::is_flattened(r.field) {
+			quote!(#comparison.flatten().map(#field_change_name::#variant))
+		} else {
+			quote!(#comparison.map(#field_change_name::#variant))
+		};
+		(name, comparison, group, changes)
+	});
+	let field_names = fields.iter().map(|(name, _, _, _)| name).collect::<Vec<_>>();
+	let comparisons = fields.iter().map(|(_, comparison, _, _)| comparison).collect::<Vec<_>>();
+	let fields_changes = fields.iter().map(|(_, _, group, changes)| (*group, changes)).collect::<Vec<_>>();
 
 	if comparisons.is_empty() {
-		quote!(comparable::Changed::Unchanged)
+		quote!(#krate::Changed::Unchanged)
 	} else if comparisons.len() == 1 {
 		if let syn::Fields::Unnamed(_) = st.fields {
 			quote! {
@@ -116,17 +242,476 @@ pub fn generate_comparison_body_for_structs(change_name: &syn::Ident, st: &syn::
 			}
 		}
 	} else {
+		let (changes, group_changes) = (local("changes"), local("group_changes"));
+		// The changes of a group are gathered where its first field is.
+		let mut gathered = Vec::new();
+		let mut extends = Vec::new();
+		for (group, field_changes) in &fields_changes {
+			match group {
+				None => extends.push(quote!(#changes.extend(#field_changes);)),
+				Some(index) if !gathered.contains(index) => {
+					gathered.push(*index);
+					let variant = &groups[*index].variant;
+					let group_fields_changes =
+						fields_changes.iter().filter(|(group, _)| group == &Some(*index)).map(|(_, changes)| changes);
+					extends.push(quote! {
+						let mut #group_changes: Vec<_> = Vec::new();
+						#(#group_changes.extend(#group_fields_changes);)*
+						#changes.extend(#krate::Changed::from_changes(#group_changes).map(#change_name::#variant));
+					});
+				}
+				Some(_) => (),
+			}
+		}
 		quote! {
-			let changes: Vec<#change_name> = vec![
-				#(#comparisons.map(#change_name::#field_variants)),*
-			]
-				.into_iter()
-				.flatten()
-				.collect();
-			if changes.is_empty() {
-				comparable::Changed::Unchanged
+			let mut #changes: Vec<_> = Vec::new();
+			#(#extends)*
+			#krate::Changed::from_changes(#changes)
+		}
+	}
+}
+
+pub fn generate_merge_key_impl_for_structs(
+	change_name: &syn::Ident,
+	change: &GeneratedType,
+	st: &syn::DataStruct,
+	groups: &[FieldGroup],
+) -> TokenStream {
+	let krate = crate_path();
+	if field_count(true, st.fields.iter()) < 2 {
+		return quote!();
+	}
+	let payloads = match &change.data {
+		syn::Data::Enum(en) => en.variants.iter().map(|v| v.fields.iter().next().map(|f| f.ty.clone())).collect(),
+		_ => Vec::new(),
+	};
+	// The changes of a group are keyed by the group's name, so that changes
+	// to any of its fields are merged as one.
+	let mut grouped = Vec::new();
+	let (branches, flattened): (Vec<TokenStream>, Vec<bool>) = map_fields(true, st.fields.iter(), true, |r| {
+		let variant = Definition::field_variant_name(r);
+		let name = r.field.ident.as_ref().map(|name| name.to_string()).unwrap_or_else(|| r.index.to_string());
+		match groups.iter().find(|group| Some(&group.name) == field_group_name(r.field).as_ref()) {
+			Some(group) if grouped.contains(&group.name) => None,
+			Some(group) => {
+				grouped.push(group.name.clone());
+				let (variant, name) = (&group.variant, &group.name);
+				Some((quote!(#change_name::#variant(_) => #name.to_string()), false))
+			}
+			None if Definition::is_flattened(r.field) => Some((
+				quote!(#change_name::#variant(change) => format!("{}.{}", #name, #krate::merge::MergeKey::merge_key(change))),
+				true,
+			)),
+			None => Some((quote!(#change_name::#variant(_) => #name.to_string()), false)),
+		}
+	})
+	.into_iter()
+	.flatten()
+	.unzip();
+	// The changes of a flattened field may be one of the change type's
+	// parameters, which must then provide its own key.
+	let bounds = payloads
+		.iter()
+		.zip(flattened)
+		.filter_map(|(ty, flattened)| ty.as_ref().filter(|_| flattened))
+		.filter(|ty| change.params.iter().any(|(param, _)| matches!(ty, syn::Type::Path(p) if p.path.is_ident(param))))
+		.map(|ty| quote!(#ty: #krate::merge::MergeKey))
+		.collect::<Vec<_>>();
+	let params = change.params.iter().map(|(param, _)| param).collect::<Vec<_>>();
+	let (impl_params, type_params) =
+		if params.is_empty() { (quote!(), quote!()) } else { (quote!(<#(#params),*>), quote!(<#(#params),*>)) };
+	quote! {
+		impl #impl_params #krate::merge::MergeKey for #change_name #type_params where #(#bounds),* {
+			fn merge_key(&self) -> String {
+				match self {
+					#(#branches),*
+				}
+			}
+		}
+	}
+}
+
+// With `#[comparable_from(Before)]`, a struct is compared with `Before` by
+// matching fields of the same name, which must have the same type. Fields
+// marked with `comparable_added` are reported as added, and the fields of
+// `Before` named by `removed(...)` as removed. Returns the definition of the
+// change type, and the implementation of `ComparableFrom`.
+pub fn generate_comparable_from_for_structs(inputs: &Inputs, from: &ComparableFrom) -> (TokenStream, TokenStream) {
+	let krate = crate_path();
+	let name = &inputs.input.ident;
+	let named = match &inputs.input.data {
+		syn::Data::Struct(syn::DataStruct { fields: syn::Fields::Named(named), .. }) => named,
+		_ => panic!("comparable_from is only supported for structs with named fields"),
+	};
+	let before = &from.before;
+	let before_name = &before.segments.last().expect("Failed to parse comparable_from type").ident;
+	let change_name = format_ident!("{}From{}{}", name, before_name, inputs.attrs.comparable_change_suffix);
+
+	let variant = |ident: syn::Ident, ty: syn::Type| -> syn::Variant {
+		syn::parse2(quote!(#ident(#ty))).expect("Failed to parse comparable_from variant")
+	};
+	let changes = local("changes");
+	let (variants, pushes): (Vec<syn::Variant>, Vec<TokenStream>) = map_fields(true, named.named.iter(), true, |r| {
+		let field = r.field.ident.as_ref().expect("Found unnamed field in named struct");
+		let variant_name = Definition::field_variant_name(r);
+		if has_attr(&r.field.attrs, "comparable_added").is_some() {
+			let variant_name = format_ident!("Added{}", variant_name);
+			let describe = Definition::field_describe(&inputs.describe_trait, r.field, &quote!(&self.#field));
+			(
+				variant(variant_name.clone(), Definition::field_desc_type(&inputs.describe_trait, r.field)),
+				quote!(#changes.push(#change_name::#variant_name(#describe));),
+			)
+		} else {
+			let comparison =
+				Definition::field_comparison(r.index, r.field, &quote!(before.#field), &quote!(&self.#field));
+			(
+				variant(variant_name.clone(), Definition::field_change_type(r.field)),
+				quote!(#changes.extend(#comparison.map(#change_name::#variant_name));),
+			)
+		}
+	})
+	.into_iter()
+	.unzip();
+	let (removed_variant, removed) = if from.removed.is_empty() {
+		(None, Vec::new())
+	} else {
+		let removed = from.removed.iter().map(|field| {
+			let field = field.to_string();
+			quote!(#changes.push(#change_name::Removed(#field.to_string()));)
+		});
+		(Some(variant(format_ident!("Removed"), syn::parse_quote!(String))), removed.collect())
+	};
+
+	let change = generate_type_definition(
+		&inputs.visibility,
+		&change_name,
+		&syn::Data::Enum(syn::DataEnum {
+			enum_token: Default::default(),
+			brace_token: Default::default(),
+			variants: variants.into_iter().chain(removed_variant).collect(),
+		}),
+		&inputs.self_type,
+		&inputs.input.generics,
+		None,
+		false,
+	);
+	let change_ty = &change.ty;
+	let (impl_generics, type_generics, _) = inputs.input.generics.split_for_impl();
+	let where_clause = inputs.where_clause(&quote!(#krate::Comparable));
+	let implementation = quote! {
+		impl #impl_generics #krate::ComparableFrom<#before> for #name #type_generics #where_clause {
+			type Change = Vec<#change_ty>;
+
+			fn comparison_from(&self, before: &#before) -> #krate::Changed<Self::Change> {
+				let mut #changes = Vec::new();
+				#(#pushes)*
+				#(#removed)*
+				#krate::Changed::from_changes(#changes)
+			}
+		}
+	};
+	(change.definition, implementation)
+}
+
+// How the changes of a field are guarded by `Apply`: checked, verified, or
+// verified given the value they were made from, as `base`.
+#[derive(Clone, Copy, PartialEq)]
+enum Guard {
+	Check,
+	Verify,
+	VerifyFrom,
+}
+
+// With `#[comparable_lens]`, a struct implements `Apply`, and has a method
+// returning a `Lens` for each field compared, other than synthetic, redacted
+// and flattened fields, whose changes cannot be applied to the field alone.
+pub fn generate_lenses_for_structs(inputs: &Inputs) -> TokenStream {
+	let krate = crate_path();
+	let name = &inputs.input.ident;
+	let st = match &inputs.input.data {
+		syn::Data::Struct(st) => st,
+		_ => panic!("comparable_lens is only supported for structs"),
+	};
+	let change_name = format_ident!("{}{}", name, inputs.attrs.comparable_change_suffix);
+	let apply =
+		|member: &syn::Member, change: TokenStream| quote!(#krate::lens::Apply::apply(&mut self.#member, #change));
+	let count = field_count(true, st.fields.iter());
+	let transparent = inputs.attrs.comparable_transparent;
+
+	// For each field compared, the lens method if it has one, and how its
+	// changes are applied, checked and verified.
+	let fields: Vec<_> = map_fields(true, st.fields.iter(), true, |r| {
+		if count > 1 && field_group_name(r.field).is_some() {
+			panic!("comparable_lens is not supported with comparable_group");
+		}
+		let member = Definition::field_member(r.index, r.field);
+		let synthetic = !st.fields.iter().any(|field| field.ident == r.field.ident);
+		// Fields compared by projections cannot be brought up to date.
+		let redacted = Definition::is_redacted(r.field) || Definition::compared_by(r.field).is_some();
+		let boxed = Definition::is_boxed(r.field);
+		// Fields compared as a whole take the value after the change, and
+		// have no lens, since their types are not comparable themselves.
+		let leaf = Definition::is_leaf(r.field);
+		let apply = |member: &syn::Member, change: TokenStream| {
+			if leaf {
+				quote!(self.#member = (#change).1)
 			} else {
-				comparable::Changed::Changed(changes)
+				apply(member, change)
+			}
+		};
+		let field_name = r.field.ident.as_ref().map(ToString::to_string).unwrap_or_else(|| r.index.to_string());
+		// Fields compared as a whole can always be replaced, though their
+		// values before are verified, and the changes of flattened fields are
+		// checked when applied.
+		let unchecked = synthetic || redacted || Definition::is_flattened(r.field);
+		let guard = |guard: Guard, change: TokenStream| {
+			let call = match guard {
+				_ if leaf => quote!(#krate::lens::expect_current(&self.#member, &(#change).0)),
+				Guard::Check => quote!(#krate::lens::Apply::check(&self.#member, #change)),
+				Guard::Verify => quote!(#krate::lens::Apply::verify(&self.#member, #change)),
+				Guard::VerifyFrom => quote!(#krate::lens::Apply::verify_from(&self.#member, &base.#member, #change)),
+			};
+			if unchecked || (leaf && guard == Guard::Check) {
+				quote!(let _ = #change;)
+			} else if transparent {
+				quote!(#call?;)
+			} else {
+				quote! {
+					#call.map_err(|e| e.within(#krate::location::Segment::Field(#field_name.to_string())))?;
+				}
+			}
+		};
+		let check = |change: TokenStream| {
+			(
+				guard(Guard::Check, change.clone()),
+				guard(Guard::Verify, change.clone()),
+				guard(Guard::VerifyFrom, change),
+			)
+		};
+		let variant = Definition::field_variant_name(r);
+		let (extract, apply, (check, verify, verify_from)) = if transparent {
+			(quote!(Some(change)), apply(&member, quote!(change)), check(quote!(change)))
+		} else if count == 1 {
+			let change_member = match &r.field.ident {
+				Some(ident) => syn::Member::Named(ident.clone()),
+				None => syn::Member::Unnamed(syn::Index::from(0)),
+			};
+			let extract =
+				if boxed { quote!(Some(&*change.#change_member)) } else { quote!(Some(&change.#change_member)) };
+			let apply = if synthetic || redacted {
+				quote!(let _ = change;)
+			} else if boxed {
+				apply(&member, quote!(*change.#change_member))
+			} else {
+				apply(&member, quote!(change.#change_member))
+			};
+			let check =
+				if boxed { check(quote!(&*change.#change_member)) } else { check(quote!(&change.#change_member)) };
+			(extract, apply, check)
+		} else {
+			let found = if boxed { quote!(Some(&**change)) } else { quote!(Some(change)) };
+			let extract = quote! {
+				change.iter().find_map(|change| match change {
+					#change_name::#variant(change) => #found,
+					_ => None,
+				})
+			};
+			let apply = if synthetic || redacted {
+				quote!(#change_name::#variant(_) => ())
+			} else if Definition::is_flattened(r.field) {
+				let apply = apply(&member, quote!(std::iter::once(change).collect()));
+				quote!(#change_name::#variant(change) => #apply)
+			} else if boxed {
+				let apply = apply(&member, quote!(*change));
+				quote!(#change_name::#variant(change) => #apply)
+			} else {
+				let apply = apply(&member, quote!(change));
+				quote!(#change_name::#variant(change) => #apply)
+			};
+			let (check, verify, verify_from) = if boxed { check(quote!(&**change)) } else { check(quote!(change)) };
+			let arm = |guard: TokenStream| quote!(#change_name::#variant(change) => { #guard });
+			(extract, apply, (arm(check), arm(verify), arm(verify_from)))
+		};
+		let lens = if synthetic || redacted || leaf || Definition::is_flattened(r.field) {
+			None
+		} else {
+			let method = match &r.field.ident {
+				Some(ident) => format_ident!("{}_lens", ident),
+				None => format_ident!("field_{}_lens", r.index),
+			};
+			let ty = &r.field.ty;
+			let doc = format!("A lens for the field `{}`.", field_name);
+			Some(quote! {
+				#[doc = #doc]
+				pub fn #method() -> #krate::lens::Lens<Self, #ty> {
+					#krate::derive_support::lens(
+						#field_name,
+						|value| &value.#member,
+						|value| &mut value.#member,
+						|change| #extract,
+					)
+				}
+			})
+		};
+		(lens, apply, (check, verify, verify_from))
+	});
+	let lenses = fields.iter().filter_map(|(lens, _, _)| lens.as_ref());
+	let applies = fields.iter().map(|(_, apply, _)| apply);
+	let checks: Vec<_> = fields.iter().map(|(_, _, (check, _, _))| check).collect();
+	let verifies: Vec<_> = fields.iter().map(|(_, _, (_, verify, _))| verify).collect();
+	let verifies_from: Vec<_> = fields.iter().map(|(_, _, (_, _, verify_from))| verify_from).collect();
+
+	// Checking and verifying visit the changes of each field alike.
+	let guard_body = |guards: &[&TokenStream]| {
+		if transparent || count == 1 {
+			quote!(#(#guards)*)
+		} else if count == 0 {
+			quote!(let _ = change;)
+		} else {
+			quote! {
+				for change in change {
+					match change {
+						#(#guards)*
+					}
+				}
+			}
+		}
+	};
+	let (check_body, verify_body, verify_from_body) =
+		(guard_body(&checks), guard_body(&verifies), guard_body(&verifies_from));
+	let apply_body = if transparent || count == 1 {
+		quote!(#(#applies)*)
+	} else if count == 0 {
+		quote!(let _ = change;)
+	} else {
+		quote! {
+			for change in change {
+				match change {
+					#(#applies,)*
+				}
+			}
+		}
+	};
+	let (impl_generics, type_generics, _) = inputs.input.generics.split_for_impl();
+	let apply_where_clause = inputs.where_clause(&quote!(#krate::lens::Apply));
+	let where_clause = inputs.where_clause(&quote!(#krate::Comparable));
+	quote! {
+		impl #impl_generics #krate::lens::Apply for #name #type_generics #apply_where_clause {
+			fn apply(&mut self, change: Self::Change) {
+				#apply_body
+			}
+
+			fn check(&self, change: &Self::Change) -> Result<(), #krate::lens::ApplyError> {
+				#check_body
+				Ok(())
+			}
+
+			fn verify(&self, change: &Self::Change) -> Result<(), #krate::lens::ConflictError> {
+				#verify_body
+				Ok(())
+			}
+
+			fn verify_from(&self, base: &Self, change: &Self::Change) -> Result<(), #krate::lens::ConflictError> {
+				let _ = base;
+				#verify_from_body
+				Ok(())
+			}
+		}
+
+		impl #impl_generics #name #type_generics #where_clause {
+			#(#lenses)*
+		}
+	}
+}
+
+// With `#[comparable_sql(table = "...", key = "...")]`, a struct implements
+// `SqlChangeset`, with each field compared being the column of the same name.
+// The changes of redacted and flattened fields do not give a column's value.
+pub fn generate_sql_changeset_for_structs(inputs: &Inputs, sql: &ComparableSql) -> TokenStream {
+	let krate = crate_path();
+	let name = &inputs.input.ident;
+	let named = match &inputs.input.data {
+		syn::Data::Struct(syn::DataStruct { fields: syn::Fields::Named(named), .. }) => named,
+		_ => panic!("comparable_sql is only supported for structs with named fields"),
+	};
+	let key = &sql.key;
+	let key_type = match named.named.iter().find(|field| field.ident.as_ref() == Some(key)) {
+		Some(field) => &field.ty,
+		None => panic!("The key of comparable_sql must name a field of {}", name),
+	};
+	let change_name = format_ident!("{}{}", name, inputs.attrs.comparable_change_suffix);
+	let count = field_count(true, named.named.iter());
+	let mut column_types = Vec::new();
+	let assignments = map_fields(true, named.named.iter(), true, |r| {
+		let field = r.field.ident.as_ref().expect("Found unnamed field in named struct");
+		let column = field.to_string();
+		let variant = Definition::field_variant_name(r);
+		let pattern = |binding: TokenStream| {
+			if count == 1 {
+				quote!(#change_name { #field: #binding })
+			} else {
+				quote!(#change_name::#variant(#binding))
+			}
+		};
+		if !named.named.iter().any(|f| f.ident == r.field.ident) {
+			let pattern = pattern(quote!(_));
+			return quote!(#pattern => ());
+		}
+		if count > 1 && field_group_name(r.field).is_some() {
+			panic!("comparable_sql is not supported with comparable_group");
+		}
+		if Definition::is_redacted(r.field) || Definition::is_flattened(r.field) {
+			panic!("comparable_sql cannot find the value of the column {}", column);
+		}
+		column_types.push(r.field.ty.clone());
+		let value = if Definition::is_boxed(r.field) { quote!(&**change) } else { quote!(change) };
+		let pattern = pattern(quote!(change));
+		quote!(#pattern => assignments.push((#column.to_string(), #krate::sql::ChangeToSql::new_value(#value))))
+	});
+	let changes = if count == 1 { quote!(std::iter::once(change)) } else { quote!(change) };
+	let body = if count == 0 {
+		quote! {
+			let _ = change;
+			let assignments = Vec::new();
+		}
+	} else {
+		quote! {
+			let mut assignments = Vec::new();
+			for change in #changes {
+				match change {
+					#(#assignments,)*
+				}
+			}
+		}
+	};
+
+	let table = &sql.table;
+	let key_column = key.to_string();
+	let generics = &inputs.input.generics;
+	let (impl_generics, type_generics, _) = generics.split_for_impl();
+	let mut where_clause = inputs.where_clause(&quote!(#krate::Comparable));
+	let bounds = column_types
+		.iter()
+		.map(|ty| quote!(<#ty as #krate::Comparable>::Change: #krate::sql::ChangeToSql))
+		.chain(Some(quote!(#key_type: #krate::sql::ToSql)));
+	for bound in bounds.filter(|bound| mentions_generics(bound.clone(), generics)) {
+		where_clause
+			.get_or_insert_with(|| syn::parse_quote!(where))
+			.predicates
+			.push(syn::parse2(bound).expect("Failed to parse where clause"));
+	}
+	quote! {
+		impl #impl_generics #krate::sql::SqlChangeset for #name #type_generics #where_clause {
+			fn sql_update(&self, change: &Self::Change) -> Option<#krate::sql::SqlUpdate> {
+				#body
+				if assignments.is_empty() {
+					return None;
+				}
+				Some(#krate::sql::SqlUpdate {
+					table: #table.to_string(),
+					assignments,
+					key: (#key_column.to_string(), #krate::sql::ToSql::to_sql(&self.#key)),
+				})
 			}
 		}
 	}
@@ -1,8 +1,16 @@
-use proc_macro2::TokenStream;
-use quote::quote;
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{format_ident, quote};
+use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::iter::FromIterator;
 
+// A variable of the generated code, resolved only where the generated code
+// names it, so that it neither shadows nor is shadowed by the names used in
+// code given to attributes, such as the functions called by `comparable_by`.
+pub fn local(name: &str) -> syn::Ident {
+	syn::Ident::new(name, proc_macro2::Span::mixed_site())
+}
+
 pub fn unit_type() -> syn::Type {
 	syn::Type::Tuple(syn::TypeTuple {
 		paren_token: syn::token::Paren { span: proc_macro2::Span::call_site() },
@@ -14,6 +22,12 @@ pub fn ident_to_type(ident: &syn::Ident) -> syn::Type {
 	syn::parse2(quote!(#ident)).unwrap_or_else(|_| panic!("Failed to parse type"))
 }
 
+// `Self` within a generated type definition is replaced by the type being
+// derived for; see `generate_type_definition`.
+pub fn self_type() -> syn::Type {
+	syn::parse2(quote!(Self)).unwrap_or_else(|_| panic!("Failed to parse type"))
+}
+
 #[allow(dead_code)]
 pub fn vec_type(ty: &syn::Type) -> syn::Type {
 	syn::parse2(quote!(Vec<#ty>)).unwrap_or_else(|_| panic!("Failed to parse Vec type"))
@@ -23,6 +37,171 @@ pub fn has_attr<'a>(attrs: &'a [syn::Attribute], attr_name: &str) -> Option<&'a
 	attrs.iter().find(|attr| attr.path.is_ident(attr_name))
 }
 
+// Fields are ignored if they are marked with `comparable_ignore`, or if they
+// are zero-sized markers such as `PhantomData`, which have nothing to compare
+// unless they are marked with `comparable_include`.
+pub fn is_ignored(field: &syn::Field) -> bool {
+	has_attr(&field.attrs, "comparable_ignore").is_some()
+		|| (is_marker(&field.ty) && has_attr(&field.attrs, "comparable_include").is_none())
+}
+
+fn is_marker(ty: &syn::Type) -> bool {
+	match ty {
+		syn::Type::Path(path) => {
+			path.path.segments.last().is_some_and(|s| s.ident == "PhantomData" || s.ident == "PhantomPinned")
+		}
+		_ => false,
+	}
+}
+
+pub fn mentions_generics(tokens: TokenStream, generics: &syn::Generics) -> bool {
+	let names = generics
+		.params
+		.iter()
+		.map(|param| match param {
+			syn::GenericParam::Type(ty) => ty.ident.clone(),
+			syn::GenericParam::Lifetime(lt) => lt.lifetime.ident.clone(),
+			syn::GenericParam::Const(c) => c.ident.clone(),
+		})
+		.collect::<Vec<_>>();
+	mentions(tokens, &names)
+}
+
+// Whether the given tokens mention the given identifier, such as one of the
+// parameters of a generated type.
+pub fn mentions_ident(tokens: TokenStream, name: &syn::Ident) -> bool {
+	mentions(tokens, std::slice::from_ref(name))
+}
+
+fn mentions(tokens: TokenStream, names: &[syn::Ident]) -> bool {
+	tokens.into_iter().any(|tt| match tt {
+		TokenTree::Group(group) => mentions(group.stream(), names),
+		TokenTree::Ident(ident) => names.contains(&ident),
+		_ => false,
+	})
+}
+
+// Replace `self` in an expression with the given identifier, so that the
+// expression can be evaluated within a closure taking a value by that name.
+pub fn rebind_self(tokens: TokenStream, name: &syn::Ident) -> TokenStream {
+	tokens
+		.into_iter()
+		.map(|tt| match tt {
+			TokenTree::Group(group) => {
+				let mut rebound = proc_macro2::Group::new(group.delimiter(), rebind_self(group.stream(), name));
+				rebound.set_span(group.span());
+				TokenTree::Group(rebound)
+			}
+			TokenTree::Ident(ident) if ident == "self" => TokenTree::Ident(name.clone()),
+			tt => tt,
+		})
+		.collect()
+}
+
+thread_local! {
+	// The path by which the code being generated reaches the `comparable`
+	// crate, as set by `with_crate_path`.
+	static CRATE_PATH: RefCell<TokenStream> = RefCell::new(quote!(::comparable));
+}
+
+// The path by which generated code reaches the `comparable` crate: that
+// given by `#[comparable_crate = "path"]`, or else the name under which the
+// crate being compiled depends on it, which may have been renamed.
+pub fn resolve_crate_path(attrs: &[syn::Attribute]) -> TokenStream {
+	if let Some(attr) = has_attr(attrs, "comparable_crate") {
+		return match attr.parse_meta() {
+			Ok(syn::Meta::NameValue(syn::MetaNameValue { lit: syn::Lit::Str(value), .. })) => {
+				let path = value
+					.parse::<syn::Path>()
+					.unwrap_or_else(|_| panic!("Argument to comparable_crate must be a path"));
+				quote!(#path)
+			}
+			_ => panic!("comparable_crate must be written as #[comparable_crate = \"path\"]"),
+		};
+	}
+	match proc_macro_crate::crate_name("comparable") {
+		Ok(proc_macro_crate::FoundCrate::Name(name)) => {
+			let name = format_ident!("{}", name);
+			quote!(::#name)
+		}
+		// The crate's own doctests, tests and benchmarks depend on it by name.
+		Ok(proc_macro_crate::FoundCrate::Itself) | Err(_) => quote!(::comparable),
+	}
+}
+
+// Generate code using `f`, in which `crate_path` gives `path`, as the options
+// of a comparison are given to the code comparing values within
+// `comparable::with_diff_options`.
+pub fn with_crate_path<R>(path: TokenStream, f: impl FnOnce() -> R) -> R {
+	let previous = CRATE_PATH.with(|current| current.replace(path));
+	let result = f();
+	CRATE_PATH.with(|current| *current.borrow_mut() = previous);
+	result
+}
+
+// The path by which generated code reaches the `comparable` crate, such as
+// `::comparable`, which code generators write in front of the items of the
+// crate that they use.
+pub fn crate_path() -> TokenStream {
+	CRATE_PATH.with(|current| current.borrow().clone())
+}
+
+// Replace `Self` in a field type with the type it refers to, so that the type
+// can be used in the definition of another type.
+pub fn replace_self(ty: &syn::Type, self_type: &syn::Type) -> syn::Type {
+	replace_ident(ty, &format_ident!("Self"), self_type)
+}
+
+pub fn replace_ident(ty: &syn::Type, name: &syn::Ident, replacement: &syn::Type) -> syn::Type {
+	fn replace(tokens: TokenStream, name: &syn::Ident, replacement: &syn::Type) -> TokenStream {
+		let mut result = TokenStream::new();
+		let mut tokens = tokens.into_iter().peekable();
+		while let Some(tt) = tokens.next() {
+			match tt {
+				TokenTree::Group(group) => {
+					let mut replaced =
+						proc_macro2::Group::new(group.delimiter(), replace(group.stream(), name, replacement));
+					replaced.set_span(group.span());
+					result.extend(quote!(#replaced));
+				}
+				// A path such as `Self::N` must be written `<Foo<T>>::N`.
+				TokenTree::Ident(ident) if ident == *name => {
+					if matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == ':') {
+						result.extend(quote!(<#replacement>));
+					} else {
+						result.extend(quote!(#replacement));
+					}
+				}
+				tt => result.extend(quote!(#tt)),
+			}
+		}
+		result
+	}
+	syn::parse2(replace(quote!(#ty), name, replacement)).expect("Failed to parse field type")
+}
+
+pub fn map_field_types(data: &syn::Data, mut f: impl FnMut(&syn::Type) -> syn::Type) -> syn::Data {
+	let mut map_fields = |fields: &syn::Fields| {
+		let mut fields = fields.clone();
+		fields.iter_mut().filter(|field| !is_ignored(field)).for_each(|field| field.ty = f(&field.ty));
+		fields
+	};
+	match data {
+		syn::Data::Struct(st) => syn::Data::Struct(syn::DataStruct { fields: map_fields(&st.fields), ..st.clone() }),
+		syn::Data::Enum(en) => syn::Data::Enum(syn::DataEnum {
+			variants: en
+				.variants
+				.iter()
+				.map(|variant| syn::Variant { fields: map_fields(&variant.fields), ..variant.clone() })
+				.collect(),
+			..en.clone()
+		}),
+		syn::Data::Union(_un) => {
+			panic!("comparable_derive::map_field_types not implemented for unions")
+		}
+	}
+}
+
 #[allow(dead_code)]
 pub fn data_from_variant(variant: &syn::Variant) -> syn::Data {
 	syn::Data::Struct(syn::DataStruct {
@@ -138,7 +317,7 @@ pub fn map_fields<'a, 'b: 'a, R>(
 				});
 			}
 		}
-		if has_attr(&field.attrs, "comparable_ignore").is_none() || !allow_ignore {
+		if !is_ignored(field) || !allow_ignore {
 			result.push(f(&FieldRef { index, field, accessor: standard_accessor(index, field) }));
 		}
 		index += 1;
@@ -183,8 +362,47 @@ fn parse_synthetics(tokens: &TokenStream) -> Result<BTreeMap<syn::Ident, syn::Ex
 		.collect())
 }
 
-pub fn generate_type_definition(visibility: &syn::Visibility, type_name: &syn::Ident, data: &syn::Data) -> TokenStream {
-	let (keyword, body) = match data {
+// A type generated by the derive, such as `FooDesc` or `FooChange`.
+pub struct GeneratedType {
+	pub definition: TokenStream,
+	// The generated type, applied to the arguments of its parameters.
+	pub ty: syn::Type,
+	// The generated type's parameters, and the field types they stand for.
+	pub params: Vec<(syn::Ident, syn::Type)>,
+	// The fields of the generated type, using those parameters.
+	pub data: syn::Data,
+}
+
+// `Self` in a field type refers to the original type, and not to the type
+// being generated. Any field type that mentions one of the original type's
+// generic parameters becomes a parameter of the generated type instead, so
+// that the generated type needs no bounds of its own: for example,
+// `FooDesc<__T0>` with `__T0` being `<T as Comparable>::Desc`.
+pub fn generate_type_definition(
+	visibility: &syn::Visibility,
+	type_name: &syn::Ident,
+	data: &syn::Data,
+	self_type: &syn::Type,
+	generics: &syn::Generics,
+	// The version given by `comparable_version`, and whether `comparable_migrate`
+	// was given with it.
+	version: Option<(&syn::LitInt, bool)>,
+	// Whether the type is `#[non_exhaustive]`, as the type it is generated
+	// from is, since fields or variants added to that type add them here.
+	non_exhaustive: bool,
+) -> GeneratedType {
+	let mut params: Vec<(syn::Ident, syn::Type)> = Vec::new();
+	let data = map_field_types(data, |ty| {
+		let ty = replace_self(ty, self_type);
+		if mentions_generics(quote!(#ty), generics) {
+			let param = format_ident!("__T{}", params.len());
+			params.push((param.clone(), ty));
+			ident_to_type(&param)
+		} else {
+			ty
+		}
+	});
+	let (keyword, body) = match &data {
 		syn::Data::Struct(st) => (
 			quote!(struct),
 			match &st.fields {
@@ -258,16 +476,97 @@ pub fn generate_type_definition(visibility: &syn::Visibility, type_name: &syn::I
 			panic!("comparable_derive::generate_type_definition not implemented for unions")
 		}
 	};
-	let derive_serde = if cfg!(feature = "serde") {
+	let (param_names, param_types): (Vec<&syn::Ident>, Vec<&syn::Type>) = params.iter().map(|(p, t)| (p, t)).unzip();
+	let (derive_serde, versioned) = match version {
+		_ if !cfg!(feature = "serde") => (quote! {}, quote! {}),
+		None => (
+			quote! {
+				#[derive(serde::Serialize, serde::Deserialize)]
+			},
+			quote! {},
+		),
+		// With `remote = "Self"`, serde derives inherent functions rather than
+		// trait implementations, which the implementations below wrap with
+		// the version.
+		Some((version, migrate)) => (
+			quote! {
+				#[derive(serde::Serialize, serde::Deserialize)]
+				#[serde(remote = "Self")]
+			},
+			generate_versioned_serde(type_name, &param_names, version, migrate),
+		),
+	};
+	let (declaration, ty) = if params.is_empty() {
+		(quote!(), ident_to_type(type_name))
+	} else {
+		(
+			quote!(<#(#param_names),*>),
+			syn::parse2(quote!(#type_name<#(#param_types),*>)).expect("Failed to parse generated type"),
+		)
+	};
+	let non_exhaustive = if non_exhaustive { quote!(#[non_exhaustive]) } else { quote!() };
+	GeneratedType {
+		definition: quote! {
+			#derive_serde
+			#[derive(PartialEq, Debug)]
+			#non_exhaustive
+			#visibility #keyword #type_name #declaration #body
+			#versioned
+		},
+		ty,
+		params,
+		data,
+	}
+}
+
+fn generate_versioned_serde(
+	type_name: &syn::Ident,
+	params: &[&syn::Ident],
+	version: &syn::LitInt,
+	migrate: bool,
+) -> TokenStream {
+	let krate = crate_path();
+	let ty = quote!(#type_name<#(#params),*>);
+	let default_migrate = if migrate {
+		quote!()
+	} else {
 		quote! {
-			#[derive(serde::Serialize, serde::Deserialize)]
+			impl<#(#params),*> #krate::versioned::Migrate for #ty {}
 		}
-	} else {
-		quote! {}
 	};
 	quote! {
-		#derive_serde
-		#[derive(PartialEq, Debug)]
-		#visibility #keyword #type_name#body
+		#default_migrate
+
+		impl<#(#params),*> #krate::versioned::Versioned for #ty {
+			const VERSION: u32 = #version;
+		}
+
+		impl<#(#params),*> serde::Serialize for #ty where #(#params: serde::Serialize),* {
+			fn serialize<__S: serde::Serializer>(&self, serializer: __S) -> Result<__S::Ok, __S::Error> {
+				#krate::derive_support::serialize_versioned(self, serializer)
+			}
+		}
+
+		impl<#(#params),*> #krate::derive_support::SerializeUnversioned for #ty where #(#params: serde::Serialize),* {
+			fn serialize_unversioned<__S: serde::Serializer>(&self, serializer: __S) -> Result<__S::Ok, __S::Error> {
+				Self::serialize(self, serializer)
+			}
+		}
+
+		impl<'de, #(#params),*> serde::Deserialize<'de> for #ty where #(#params: serde::Deserialize<'de>),* {
+			fn deserialize<__D: serde::Deserializer<'de>>(deserializer: __D) -> Result<Self, __D::Error> {
+				#krate::derive_support::deserialize_versioned(deserializer)
+			}
+		}
+
+		impl<'de, #(#params),*> #krate::derive_support::DeserializeUnversioned<'de> for #ty
+		where
+			Self: #krate::versioned::Migrate,
+			#(#params: serde::Deserialize<'de>),*
+		{
+			fn deserialize_unversioned<__D: serde::Deserializer<'de>>(deserializer: __D) -> Result<Self, __D::Error> {
+				Self::deserialize(deserializer)
+			}
+		}
 	}
 }
@@ -0,0 +1,46 @@
+#[derive(Comparable)]
+#[describe_type(String)]
+#[describe_body(self.to_string())]
+struct Version(#[comparable_ignore] Vec<u8>);
+
+#[derive(Comparable)]
+#[self_describing]
+#[compare_default]
+#[comparable_from_default]
+struct Limits {
+	min: u8,
+	max: u8,
+}
+
+#[derive(Comparable)]
+enum Payload {
+	#[no_description]
+	Large(Vec<u8>),
+	#[describe_type(usize)]
+	#[describe_body(0)]
+	Sized { len: usize },
+	Small(u8),
+}
+
+#[derive(Describe)]
+struct Described {
+	name: String,
+	#[comparable_ignore]
+	cache: Vec<u8>,
+}
+
+#[derive(Comparable, Debug)]
+#[describe_type(String)]
+#[describe_body(format!("{:?}", self))]
+enum Level {
+	Low,
+	High { limit: u8 },
+}
+
+#[derive(Comparable, Default)]
+#[compare_default]
+enum Mode {
+	#[default]
+	Off,
+	On { level: u8 },
+}
@@ -0,0 +1,544 @@
+impl ::comparable::Comparable for Version {
+    type Desc = String;
+    fn describe(&self) -> Self::Desc {
+        self.to_string()
+    }
+    type Change = ();
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        ::comparable::Changed::Unchanged
+    }
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+enum LimitsChange {
+    Min(<u8 as ::comparable::Comparable>::Change),
+    Max(<u8 as ::comparable::Comparable>::Change),
+}
+impl ::comparable::merge::MergeKey for LimitsChange {
+    fn merge_key(&self) -> String {
+        match self {
+            LimitsChange::Min(_) => "min".to_string(),
+            LimitsChange::Max(_) => "max".to_string(),
+        }
+    }
+}
+impl LimitsChange {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["min", "max"];
+}
+impl ::comparable::Comparable for Limits {
+    type Desc = Self;
+    fn describe(&self) -> Self::Desc {
+        self.clone()
+    }
+    type Change = Vec<LimitsChange>;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        let mut changes: Vec<_> = Vec::new();
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "min",
+                        || self.min.comparison(&other.min),
+                    )
+                    .map(LimitsChange::Min),
+            );
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "max",
+                        || self.max.comparison(&other.max),
+                    )
+                    .map(LimitsChange::Max),
+            );
+        ::comparable::Changed::from_changes(changes)
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        (::comparable::Comparable::similarity(&self.min, &other.min)
+            + ::comparable::Comparable::similarity(&self.max, &other.max)) / 2f64
+    }
+    fn differs(&self, other: &Self) -> bool {
+        ::comparable::derive_support::masked_or(
+            "min",
+            false,
+            || ::comparable::Comparable::differs(&self.min, &other.min),
+        )
+            || ::comparable::derive_support::masked_or(
+                "max",
+                false,
+                || ::comparable::Comparable::differs(&self.max, &other.max),
+            )
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        ::comparable::derive_support::masked_or(
+            "min",
+            0,
+            || ::comparable::Comparable::count_changes(&self.min, &other.min),
+        )
+            + ::comparable::derive_support::masked_or(
+                "max",
+                0,
+                || ::comparable::Comparable::count_changes(&self.max, &other.max),
+            )
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        ::comparable::derive_support::masked_or(
+                "min",
+                None,
+                || ::comparable::Comparable::first_difference(&self.min, &other.min),
+            )
+            .map(|path| {
+                path.within(::comparable::location::Segment::Field("min".to_string()))
+            })
+            .or_else(|| {
+                ::comparable::derive_support::masked_or(
+                        "max",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            &self.max,
+                            &other.max,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("max".to_string()),
+                            )
+                    })
+            })
+    }
+}
+impl Limits
+where
+    Self: Default,
+{
+    /// Report how this value differs from the default value of its type.
+    pub fn comparison_from_default(
+        &self,
+    ) -> ::comparable::Changed<<Self as ::comparable::Comparable>::Change> {
+        ::comparable::from_default(self)
+    }
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+enum PayloadDesc {
+    Large,
+    Sized(usize),
+    Small(<u8 as ::comparable::Comparable>::Desc),
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+enum PayloadChange {
+    BothLarge(<Vec<u8> as ::comparable::Comparable>::Change),
+    BothSized { len: <usize as ::comparable::Comparable>::Change },
+    BothSmall(<u8 as ::comparable::Comparable>::Change),
+    Different(
+        <Payload as ::comparable::Comparable>::Desc,
+        <Payload as ::comparable::Comparable>::Desc,
+    ),
+}
+impl PayloadChange {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["0", "len"];
+}
+impl ::comparable::Comparable for Payload {
+    type Desc = PayloadDesc;
+    fn describe(&self) -> Self::Desc {
+        match self {
+            Payload::Large { .. } => PayloadDesc::Large,
+            Payload::Sized { .. } => PayloadDesc::Sized(0),
+            Payload::Small(var0) => {
+                PayloadDesc::Small(::comparable::Comparable::describe(var0))
+            }
+        }
+    }
+    type Change = PayloadChange;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        match (self, other) {
+            (Payload::Large(self_var0), Payload::Large(other_var0)) => {
+                let changes_var0 = ::comparable::derive_support::masked(
+                    "0",
+                    || self_var0.comparison(&other_var0),
+                );
+                changes_var0.map(|changes_var0| PayloadChange::BothLarge(changes_var0))
+            }
+            (Payload::Sized { len: self_var0 }, Payload::Sized { len: other_var0 }) => {
+                let changes_var0 = ::comparable::derive_support::masked(
+                    "len",
+                    || self_var0.comparison(&other_var0),
+                );
+                changes_var0
+                    .map(|changes_var0| PayloadChange::BothSized {
+                        len: changes_var0,
+                    })
+            }
+            (Payload::Small(self_var0), Payload::Small(other_var0)) => {
+                let changes_var0 = ::comparable::derive_support::masked(
+                    "0",
+                    || self_var0.comparison(&other_var0),
+                );
+                changes_var0.map(|changes_var0| PayloadChange::BothSmall(changes_var0))
+            }
+            (_, _) => {
+                ::comparable::Changed::Changed(
+                    PayloadChange::Different(
+                        ::comparable::Comparable::describe(self),
+                        ::comparable::Comparable::describe(other),
+                    ),
+                )
+            }
+        }
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        match (self, other) {
+            (Self::Large(self_var0), Self::Large(other_var0)) => {
+                ::comparable::Comparable::similarity(self_var0, other_var0)
+            }
+            (Self::Sized { len: self_var0 }, Self::Sized { len: other_var0 }) => {
+                ::comparable::Comparable::similarity(self_var0, other_var0)
+            }
+            (Self::Small(self_var0), Self::Small(other_var0)) => {
+                ::comparable::Comparable::similarity(self_var0, other_var0)
+            }
+            _ => 0.0,
+        }
+    }
+    fn differs(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Large(self_var0), Self::Large(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    false,
+                    || ::comparable::Comparable::differs(self_var0, other_var0),
+                )
+            }
+            (Self::Sized { len: self_var0 }, Self::Sized { len: other_var0 }) => {
+                ::comparable::derive_support::masked_or(
+                    "len",
+                    false,
+                    || ::comparable::Comparable::differs(self_var0, other_var0),
+                )
+            }
+            (Self::Small(self_var0), Self::Small(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    false,
+                    || ::comparable::Comparable::differs(self_var0, other_var0),
+                )
+            }
+            _ => true,
+        }
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        match (self, other) {
+            (Self::Large(self_var0), Self::Large(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    0,
+                    || ::comparable::Comparable::count_changes(self_var0, other_var0),
+                )
+            }
+            (Self::Sized { len: self_var0 }, Self::Sized { len: other_var0 }) => {
+                ::comparable::derive_support::masked_or(
+                    "len",
+                    0,
+                    || ::comparable::Comparable::count_changes(self_var0, other_var0),
+                )
+            }
+            (Self::Small(self_var0), Self::Small(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    0,
+                    || ::comparable::Comparable::count_changes(self_var0, other_var0),
+                )
+            }
+            _ => 1,
+        }
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        match (self, other) {
+            (Self::Large(self_var0), Self::Large(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                        "0",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            self_var0,
+                            other_var0,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("0".to_string()),
+                            )
+                    })
+            }
+            (Self::Sized { len: self_var0 }, Self::Sized { len: other_var0 }) => {
+                ::comparable::derive_support::masked_or(
+                        "len",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            self_var0,
+                            other_var0,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("len".to_string()),
+                            )
+                    })
+            }
+            (Self::Small(self_var0), Self::Small(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                        "0",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            self_var0,
+                            other_var0,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("0".to_string()),
+                            )
+                    })
+            }
+            _ => Some(::comparable::location::Path::default()),
+        }
+    }
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+struct DescribedDesc {
+    name: <String as ::comparable::describe::Describe>::Desc,
+}
+impl ::comparable::describe::Describe for Described {
+    type Desc = DescribedDesc;
+    fn describe(&self) -> Self::Desc {
+        DescribedDesc {
+            name: ::comparable::describe::Describe::describe(&self.name),
+        }
+    }
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+enum LevelChange {
+    BothHigh { limit: <u8 as ::comparable::Comparable>::Change },
+    Different(
+        <Level as ::comparable::Comparable>::Desc,
+        <Level as ::comparable::Comparable>::Desc,
+    ),
+}
+impl LevelChange {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["limit"];
+}
+impl ::comparable::Comparable for Level {
+    type Desc = String;
+    fn describe(&self) -> Self::Desc {
+        format!("{:?}", self)
+    }
+    type Change = LevelChange;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        match (self, other) {
+            (Level::Low, Level::Low) => ::comparable::Changed::Unchanged,
+            (Level::High { limit: self_var0 }, Level::High { limit: other_var0 }) => {
+                let changes_var0 = ::comparable::derive_support::masked(
+                    "limit",
+                    || self_var0.comparison(&other_var0),
+                );
+                changes_var0
+                    .map(|changes_var0| LevelChange::BothHigh {
+                        limit: changes_var0,
+                    })
+            }
+            (_, _) => {
+                ::comparable::Changed::Changed(
+                    LevelChange::Different(
+                        ::comparable::Comparable::describe(self),
+                        ::comparable::Comparable::describe(other),
+                    ),
+                )
+            }
+        }
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        match (self, other) {
+            (Self::Low, Self::Low) => 1.0,
+            (Self::High { limit: self_var0 }, Self::High { limit: other_var0 }) => {
+                ::comparable::Comparable::similarity(self_var0, other_var0)
+            }
+            _ => 0.0,
+        }
+    }
+    fn differs(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Low, Self::Low) => false,
+            (Self::High { limit: self_var0 }, Self::High { limit: other_var0 }) => {
+                ::comparable::derive_support::masked_or(
+                    "limit",
+                    false,
+                    || ::comparable::Comparable::differs(self_var0, other_var0),
+                )
+            }
+            _ => true,
+        }
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        match (self, other) {
+            (Self::Low, Self::Low) => 0,
+            (Self::High { limit: self_var0 }, Self::High { limit: other_var0 }) => {
+                ::comparable::derive_support::masked_or(
+                    "limit",
+                    0,
+                    || ::comparable::Comparable::count_changes(self_var0, other_var0),
+                )
+            }
+            _ => 1,
+        }
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        match (self, other) {
+            (Self::Low, Self::Low) => None,
+            (Self::High { limit: self_var0 }, Self::High { limit: other_var0 }) => {
+                ::comparable::derive_support::masked_or(
+                        "limit",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            self_var0,
+                            other_var0,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("limit".to_string()),
+                            )
+                    })
+            }
+            _ => Some(::comparable::location::Path::default()),
+        }
+    }
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+enum ModeDesc {
+    Off,
+    On { level: <u8 as ::comparable::Comparable>::Desc },
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+enum ModeChange {
+    BothOn { level: <u8 as ::comparable::Comparable>::Change },
+    Different(ModeDesc, ModeDesc),
+}
+impl ModeChange {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["level"];
+}
+impl ::comparable::Comparable for Mode {
+    type Desc = ::comparable::Changed<Self::Change>;
+    fn describe(&self) -> Self::Desc {
+        ::comparable::from_default(self)
+    }
+    type Change = ModeChange;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        match (self, other) {
+            (Mode::Off, Mode::Off) => ::comparable::Changed::Unchanged,
+            (Mode::On { level: self_var0 }, Mode::On { level: other_var0 }) => {
+                let changes_var0 = ::comparable::derive_support::masked(
+                    "level",
+                    || self_var0.comparison(&other_var0),
+                );
+                changes_var0
+                    .map(|changes_var0| ModeChange::BothOn {
+                        level: changes_var0,
+                    })
+            }
+            (_, _) => {
+                ::comparable::Changed::Changed(
+                    ModeChange::Different(
+                        match self {
+                            Mode::Off => ModeDesc::Off,
+                            Mode::On { level: var0 } => {
+                                ModeDesc::On {
+                                    level: ::comparable::Comparable::describe(var0),
+                                }
+                            }
+                        },
+                        match other {
+                            Mode::Off => ModeDesc::Off,
+                            Mode::On { level: var0 } => {
+                                ModeDesc::On {
+                                    level: ::comparable::Comparable::describe(var0),
+                                }
+                            }
+                        },
+                    ),
+                )
+            }
+        }
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        match (self, other) {
+            (Self::Off, Self::Off) => 1.0,
+            (Self::On { level: self_var0 }, Self::On { level: other_var0 }) => {
+                ::comparable::Comparable::similarity(self_var0, other_var0)
+            }
+            _ => 0.0,
+        }
+    }
+    fn differs(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Off, Self::Off) => false,
+            (Self::On { level: self_var0 }, Self::On { level: other_var0 }) => {
+                ::comparable::derive_support::masked_or(
+                    "level",
+                    false,
+                    || ::comparable::Comparable::differs(self_var0, other_var0),
+                )
+            }
+            _ => true,
+        }
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        match (self, other) {
+            (Self::Off, Self::Off) => 0,
+            (Self::On { level: self_var0 }, Self::On { level: other_var0 }) => {
+                ::comparable::derive_support::masked_or(
+                    "level",
+                    0,
+                    || ::comparable::Comparable::count_changes(self_var0, other_var0),
+                )
+            }
+            _ => 1,
+        }
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        match (self, other) {
+            (Self::Off, Self::Off) => None,
+            (Self::On { level: self_var0 }, Self::On { level: other_var0 }) => {
+                ::comparable::derive_support::masked_or(
+                        "level",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            self_var0,
+                            other_var0,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("level".to_string()),
+                            )
+                    })
+            }
+            _ => Some(::comparable::location::Path::default()),
+        }
+    }
+}
@@ -0,0 +1,1102 @@
+#[derive(PartialEq, Debug)]
+enum EmptyDesc {}
+impl ::comparable::Comparable for Empty {
+    type Desc = EmptyDesc;
+    fn describe(&self) -> Self::Desc {
+        panic!("Cannot construct empty enum")
+    }
+    type Change = ();
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        ::comparable::Changed::Unchanged
+    }
+}
+#[derive(PartialEq, Debug)]
+pub enum ShapeDesc {
+    Point,
+    Circle(<u32 as ::comparable::Comparable>::Desc),
+    Segment(
+        <i32 as ::comparable::Comparable>::Desc,
+        <i32 as ::comparable::Comparable>::Desc,
+    ),
+    Rectangle {
+        width: <u32 as ::comparable::Comparable>::Desc,
+        height: <u32 as ::comparable::Comparable>::Desc,
+    },
+}
+#[derive(PartialEq, Debug)]
+pub enum ShapeChange {
+    BothCircle(<u32 as ::comparable::Comparable>::Change),
+    BothSegment(
+        ::comparable::Changed<<i32 as ::comparable::Comparable>::Change>,
+        ::comparable::Changed<<i32 as ::comparable::Comparable>::Change>,
+    ),
+    BothRectangle {
+        width: ::comparable::Changed<<u32 as ::comparable::Comparable>::Change>,
+        height: ::comparable::Changed<<u32 as ::comparable::Comparable>::Change>,
+    },
+    Different(
+        <Shape as ::comparable::Comparable>::Desc,
+        <Shape as ::comparable::Comparable>::Desc,
+    ),
+}
+impl ShapeChange {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["0", "1", "width", "height"];
+}
+impl ::comparable::Comparable for Shape {
+    type Desc = ShapeDesc;
+    fn describe(&self) -> Self::Desc {
+        match self {
+            Shape::Point => ShapeDesc::Point,
+            Shape::Circle(var0) => {
+                ShapeDesc::Circle(::comparable::Comparable::describe(var0))
+            }
+            Shape::Segment(var0, var1) => {
+                ShapeDesc::Segment(
+                    ::comparable::Comparable::describe(var0),
+                    ::comparable::Comparable::describe(var1),
+                )
+            }
+            Shape::Rectangle { width: var0, height: var1 } => {
+                ShapeDesc::Rectangle {
+                    width: ::comparable::Comparable::describe(var0),
+                    height: ::comparable::Comparable::describe(var1),
+                }
+            }
+        }
+    }
+    type Change = ShapeChange;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        match (self, other) {
+            (Shape::Point, Shape::Point) => ::comparable::Changed::Unchanged,
+            (Shape::Circle(self_var0), Shape::Circle(other_var0)) => {
+                let changes_var0 = ::comparable::derive_support::masked(
+                    "0",
+                    || self_var0.comparison(&other_var0),
+                );
+                changes_var0.map(|changes_var0| ShapeChange::BothCircle(changes_var0))
+            }
+            (
+                Shape::Segment(self_var0, self_var1),
+                Shape::Segment(other_var0, other_var1),
+            ) => {
+                let changes_var0 = ::comparable::derive_support::masked(
+                    "0",
+                    || self_var0.comparison(&other_var0),
+                );
+                let changes_var1 = ::comparable::derive_support::masked(
+                    "1",
+                    || self_var1.comparison(&other_var1),
+                );
+                if changes_var0.is_unchanged() && changes_var1.is_unchanged() {
+                    ::comparable::Changed::Unchanged
+                } else {
+                    ::comparable::Changed::Changed(
+                        ShapeChange::BothSegment(changes_var0, changes_var1),
+                    )
+                }
+            }
+            (
+                Shape::Rectangle { width: self_var0, height: self_var1 },
+                Shape::Rectangle { width: other_var0, height: other_var1 },
+            ) => {
+                let changes_var0 = ::comparable::derive_support::masked(
+                    "width",
+                    || self_var0.comparison(&other_var0),
+                );
+                let changes_var1 = ::comparable::derive_support::masked(
+                    "height",
+                    || self_var1.comparison(&other_var1),
+                );
+                if changes_var0.is_unchanged() && changes_var1.is_unchanged() {
+                    ::comparable::Changed::Unchanged
+                } else {
+                    ::comparable::Changed::Changed(ShapeChange::BothRectangle {
+                        width: changes_var0,
+                        height: changes_var1,
+                    })
+                }
+            }
+            (_, _) => {
+                ::comparable::Changed::Changed(
+                    ShapeChange::Different(
+                        ::comparable::Comparable::describe(self),
+                        ::comparable::Comparable::describe(other),
+                    ),
+                )
+            }
+        }
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        match (self, other) {
+            (Self::Point, Self::Point) => 1.0,
+            (Self::Circle(self_var0), Self::Circle(other_var0)) => {
+                ::comparable::Comparable::similarity(self_var0, other_var0)
+            }
+            (
+                Self::Segment(self_var0, self_var1),
+                Self::Segment(other_var0, other_var1),
+            ) => {
+                (::comparable::Comparable::similarity(self_var0, other_var0)
+                    + ::comparable::Comparable::similarity(self_var1, other_var1)) / 2f64
+            }
+            (
+                Self::Rectangle { width: self_var0, height: self_var1 },
+                Self::Rectangle { width: other_var0, height: other_var1 },
+            ) => {
+                (::comparable::Comparable::similarity(self_var0, other_var0)
+                    + ::comparable::Comparable::similarity(self_var1, other_var1)) / 2f64
+            }
+            _ => 0.0,
+        }
+    }
+    fn differs(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Point, Self::Point) => false,
+            (Self::Circle(self_var0), Self::Circle(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    false,
+                    || ::comparable::Comparable::differs(self_var0, other_var0),
+                )
+            }
+            (
+                Self::Segment(self_var0, self_var1),
+                Self::Segment(other_var0, other_var1),
+            ) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    false,
+                    || ::comparable::Comparable::differs(self_var0, other_var0),
+                )
+                    || ::comparable::derive_support::masked_or(
+                        "1",
+                        false,
+                        || ::comparable::Comparable::differs(self_var1, other_var1),
+                    )
+            }
+            (
+                Self::Rectangle { width: self_var0, height: self_var1 },
+                Self::Rectangle { width: other_var0, height: other_var1 },
+            ) => {
+                ::comparable::derive_support::masked_or(
+                    "width",
+                    false,
+                    || ::comparable::Comparable::differs(self_var0, other_var0),
+                )
+                    || ::comparable::derive_support::masked_or(
+                        "height",
+                        false,
+                        || ::comparable::Comparable::differs(self_var1, other_var1),
+                    )
+            }
+            _ => true,
+        }
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        match (self, other) {
+            (Self::Point, Self::Point) => 0,
+            (Self::Circle(self_var0), Self::Circle(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    0,
+                    || ::comparable::Comparable::count_changes(self_var0, other_var0),
+                )
+            }
+            (
+                Self::Segment(self_var0, self_var1),
+                Self::Segment(other_var0, other_var1),
+            ) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    0,
+                    || ::comparable::Comparable::count_changes(self_var0, other_var0),
+                )
+                    + ::comparable::derive_support::masked_or(
+                        "1",
+                        0,
+                        || ::comparable::Comparable::count_changes(self_var1, other_var1),
+                    )
+            }
+            (
+                Self::Rectangle { width: self_var0, height: self_var1 },
+                Self::Rectangle { width: other_var0, height: other_var1 },
+            ) => {
+                ::comparable::derive_support::masked_or(
+                    "width",
+                    0,
+                    || ::comparable::Comparable::count_changes(self_var0, other_var0),
+                )
+                    + ::comparable::derive_support::masked_or(
+                        "height",
+                        0,
+                        || ::comparable::Comparable::count_changes(self_var1, other_var1),
+                    )
+            }
+            _ => 1,
+        }
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        match (self, other) {
+            (Self::Point, Self::Point) => None,
+            (Self::Circle(self_var0), Self::Circle(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                        "0",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            self_var0,
+                            other_var0,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("0".to_string()),
+                            )
+                    })
+            }
+            (
+                Self::Segment(self_var0, self_var1),
+                Self::Segment(other_var0, other_var1),
+            ) => {
+                ::comparable::derive_support::masked_or(
+                        "0",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            self_var0,
+                            other_var0,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("0".to_string()),
+                            )
+                    })
+                    .or_else(|| {
+                        ::comparable::derive_support::masked_or(
+                                "1",
+                                None,
+                                || ::comparable::Comparable::first_difference(
+                                    self_var1,
+                                    other_var1,
+                                ),
+                            )
+                            .map(|path| {
+                                path
+                                    .within(
+                                        ::comparable::location::Segment::Field("1".to_string()),
+                                    )
+                            })
+                    })
+            }
+            (
+                Self::Rectangle { width: self_var0, height: self_var1 },
+                Self::Rectangle { width: other_var0, height: other_var1 },
+            ) => {
+                ::comparable::derive_support::masked_or(
+                        "width",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            self_var0,
+                            other_var0,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("width".to_string()),
+                            )
+                    })
+                    .or_else(|| {
+                        ::comparable::derive_support::masked_or(
+                                "height",
+                                None,
+                                || ::comparable::Comparable::first_difference(
+                                    self_var1,
+                                    other_var1,
+                                ),
+                            )
+                            .map(|path| {
+                                path
+                                    .within(
+                                        ::comparable::location::Segment::Field("height".to_string()),
+                                    )
+                            })
+                    })
+            }
+            _ => Some(::comparable::location::Path::default()),
+        }
+    }
+}
+#[derive(PartialEq, Debug)]
+enum EventDesc {
+    Moved {
+        x: <i32 as ::comparable::Comparable>::Desc,
+        y: <i32 as ::comparable::Comparable>::Desc,
+    },
+    Renamed(<String as ::comparable::Comparable>::Desc),
+}
+#[derive(PartialEq, Debug)]
+enum EventChange {
+    BothMoved(Vec<EventMovedChange>),
+    BothRenamed(<String as ::comparable::Comparable>::Change),
+    Different(
+        <Event as ::comparable::Comparable>::Desc,
+        <Event as ::comparable::Comparable>::Desc,
+    ),
+}
+#[derive(PartialEq, Debug)]
+enum EventMovedChange {
+    X(<i32 as ::comparable::Comparable>::Change),
+    Y(<i32 as ::comparable::Comparable>::Change),
+}
+impl EventChange {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["x", "y", "0"];
+}
+impl ::comparable::Comparable for Event {
+    type Desc = EventDesc;
+    fn describe(&self) -> Self::Desc {
+        match self {
+            Event::Moved { x: var0, y: var1 } => {
+                EventDesc::Moved {
+                    x: ::comparable::Comparable::describe(var0),
+                    y: ::comparable::Comparable::describe(var1),
+                }
+            }
+            Event::Renamed(var0) => {
+                EventDesc::Renamed(::comparable::Comparable::describe(var0))
+            }
+        }
+    }
+    type Change = EventChange;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        match (self, other) {
+            (
+                Event::Moved { x: self_var0, y: self_var1 },
+                Event::Moved { x: other_var0, y: other_var1 },
+            ) => {
+                let changes_var0 = ::comparable::derive_support::masked(
+                    "x",
+                    || self_var0.comparison(&other_var0),
+                );
+                let changes_var1 = ::comparable::derive_support::masked(
+                    "y",
+                    || self_var1.comparison(&other_var1),
+                );
+                ::comparable::Changed::from_changes(
+                        vec![
+                            changes_var0.map(EventMovedChange::X), changes_var1
+                            .map(EventMovedChange::Y)
+                        ]
+                            .into_iter()
+                            .flatten(),
+                    )
+                    .map(EventChange::BothMoved)
+            }
+            (Event::Renamed(self_var0), Event::Renamed(other_var0)) => {
+                let changes_var0 = ::comparable::derive_support::masked(
+                    "0",
+                    || self_var0.comparison(&other_var0),
+                );
+                changes_var0.map(|changes_var0| EventChange::BothRenamed(changes_var0))
+            }
+            (_, _) => {
+                ::comparable::Changed::Changed(
+                    EventChange::Different(
+                        ::comparable::Comparable::describe(self),
+                        ::comparable::Comparable::describe(other),
+                    ),
+                )
+            }
+        }
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        match (self, other) {
+            (
+                Self::Moved { x: self_var0, y: self_var1 },
+                Self::Moved { x: other_var0, y: other_var1 },
+            ) => {
+                (::comparable::Comparable::similarity(self_var0, other_var0)
+                    + ::comparable::Comparable::similarity(self_var1, other_var1)) / 2f64
+            }
+            (Self::Renamed(self_var0), Self::Renamed(other_var0)) => {
+                ::comparable::Comparable::similarity(self_var0, other_var0)
+            }
+            _ => 0.0,
+        }
+    }
+    fn differs(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Self::Moved { x: self_var0, y: self_var1 },
+                Self::Moved { x: other_var0, y: other_var1 },
+            ) => {
+                ::comparable::derive_support::masked_or(
+                    "x",
+                    false,
+                    || ::comparable::Comparable::differs(self_var0, other_var0),
+                )
+                    || ::comparable::derive_support::masked_or(
+                        "y",
+                        false,
+                        || ::comparable::Comparable::differs(self_var1, other_var1),
+                    )
+            }
+            (Self::Renamed(self_var0), Self::Renamed(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    false,
+                    || ::comparable::Comparable::differs(self_var0, other_var0),
+                )
+            }
+            _ => true,
+        }
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        match (self, other) {
+            (
+                Self::Moved { x: self_var0, y: self_var1 },
+                Self::Moved { x: other_var0, y: other_var1 },
+            ) => {
+                ::comparable::derive_support::masked_or(
+                    "x",
+                    0,
+                    || ::comparable::Comparable::count_changes(self_var0, other_var0),
+                )
+                    + ::comparable::derive_support::masked_or(
+                        "y",
+                        0,
+                        || ::comparable::Comparable::count_changes(self_var1, other_var1),
+                    )
+            }
+            (Self::Renamed(self_var0), Self::Renamed(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    0,
+                    || ::comparable::Comparable::count_changes(self_var0, other_var0),
+                )
+            }
+            _ => 1,
+        }
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        match (self, other) {
+            (
+                Self::Moved { x: self_var0, y: self_var1 },
+                Self::Moved { x: other_var0, y: other_var1 },
+            ) => {
+                ::comparable::derive_support::masked_or(
+                        "x",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            self_var0,
+                            other_var0,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("x".to_string()),
+                            )
+                    })
+                    .or_else(|| {
+                        ::comparable::derive_support::masked_or(
+                                "y",
+                                None,
+                                || ::comparable::Comparable::first_difference(
+                                    self_var1,
+                                    other_var1,
+                                ),
+                            )
+                            .map(|path| {
+                                path
+                                    .within(
+                                        ::comparable::location::Segment::Field("y".to_string()),
+                                    )
+                            })
+                    })
+            }
+            (Self::Renamed(self_var0), Self::Renamed(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                        "0",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            self_var0,
+                            other_var0,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("0".to_string()),
+                            )
+                    })
+            }
+            _ => Some(::comparable::location::Path::default()),
+        }
+    }
+}
+#[derive(PartialEq, Debug)]
+#[non_exhaustive]
+enum StatusDesc {
+    Up,
+    Down(<String as ::comparable::Comparable>::Desc),
+}
+#[derive(PartialEq, Debug)]
+#[non_exhaustive]
+enum StatusChange {
+    BothDown(<String as ::comparable::Comparable>::Change),
+    Different(
+        <Status as ::comparable::Comparable>::Desc,
+        <Status as ::comparable::Comparable>::Desc,
+    ),
+}
+impl StatusChange {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["0"];
+}
+impl ::comparable::Comparable for Status {
+    type Desc = StatusDesc;
+    fn describe(&self) -> Self::Desc {
+        match self {
+            Status::Up => StatusDesc::Up,
+            Status::Down(var0) => {
+                StatusDesc::Down(::comparable::Comparable::describe(var0))
+            }
+        }
+    }
+    type Change = StatusChange;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        match (self, other) {
+            (Status::Up, Status::Up) => ::comparable::Changed::Unchanged,
+            (Status::Down(self_var0), Status::Down(other_var0)) => {
+                let changes_var0 = ::comparable::derive_support::masked(
+                    "0",
+                    || self_var0.comparison(&other_var0),
+                );
+                changes_var0.map(|changes_var0| StatusChange::BothDown(changes_var0))
+            }
+            (_, _) => {
+                ::comparable::Changed::Changed(
+                    StatusChange::Different(
+                        ::comparable::Comparable::describe(self),
+                        ::comparable::Comparable::describe(other),
+                    ),
+                )
+            }
+        }
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        match (self, other) {
+            (Self::Up, Self::Up) => 1.0,
+            (Self::Down(self_var0), Self::Down(other_var0)) => {
+                ::comparable::Comparable::similarity(self_var0, other_var0)
+            }
+            _ => 0.0,
+        }
+    }
+    fn differs(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Up, Self::Up) => false,
+            (Self::Down(self_var0), Self::Down(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    false,
+                    || ::comparable::Comparable::differs(self_var0, other_var0),
+                )
+            }
+            _ => true,
+        }
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        match (self, other) {
+            (Self::Up, Self::Up) => 0,
+            (Self::Down(self_var0), Self::Down(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    0,
+                    || ::comparable::Comparable::count_changes(self_var0, other_var0),
+                )
+            }
+            _ => 1,
+        }
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        match (self, other) {
+            (Self::Up, Self::Up) => None,
+            (Self::Down(self_var0), Self::Down(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                        "0",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            self_var0,
+                            other_var0,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("0".to_string()),
+                            )
+                    })
+            }
+            _ => Some(::comparable::location::Path::default()),
+        }
+    }
+}
+#[derive(PartialEq, Debug)]
+enum ExprDesc {
+    Add(
+        Box<<Box<Expr> as ::comparable::Comparable>::Desc>,
+        Box<<Box<Expr> as ::comparable::Comparable>::Desc>,
+    ),
+    Neg(Box<<Box<Expr> as ::comparable::Comparable>::Desc>),
+    Lit(<i64 as ::comparable::Comparable>::Desc),
+}
+#[derive(PartialEq, Debug)]
+enum ExprChange {
+    BothAdd(
+        ::comparable::Changed<Box<<Box<Expr> as ::comparable::Comparable>::Change>>,
+        ::comparable::Changed<Box<<Box<Expr> as ::comparable::Comparable>::Change>>,
+    ),
+    BothNeg(Box<<Box<Expr> as ::comparable::Comparable>::Change>),
+    BothLit(<i64 as ::comparable::Comparable>::Change),
+    Different(
+        <Expr as ::comparable::Comparable>::Desc,
+        <Expr as ::comparable::Comparable>::Desc,
+    ),
+}
+impl ExprChange {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["0", "1"];
+}
+impl ::comparable::Comparable for Expr {
+    type Desc = ExprDesc;
+    fn describe(&self) -> Self::Desc {
+        match self {
+            Expr::Add(var0, var1) => {
+                ExprDesc::Add(
+                    Box::new(::comparable::Comparable::describe(var0)),
+                    Box::new(::comparable::Comparable::describe(var1)),
+                )
+            }
+            Expr::Neg(var0) => {
+                ExprDesc::Neg(Box::new(::comparable::Comparable::describe(var0)))
+            }
+            Expr::Lit(var0) => ExprDesc::Lit(::comparable::Comparable::describe(var0)),
+        }
+    }
+    type Change = ExprChange;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        match (self, other) {
+            (Expr::Add(self_var0, self_var1), Expr::Add(other_var0, other_var1)) => {
+                let changes_var0 = ::comparable::derive_support::masked(
+                    "0",
+                    || self_var0.comparison(&other_var0).map(Box::new),
+                );
+                let changes_var1 = ::comparable::derive_support::masked(
+                    "1",
+                    || self_var1.comparison(&other_var1).map(Box::new),
+                );
+                if changes_var0.is_unchanged() && changes_var1.is_unchanged() {
+                    ::comparable::Changed::Unchanged
+                } else {
+                    ::comparable::Changed::Changed(
+                        ExprChange::BothAdd(changes_var0, changes_var1),
+                    )
+                }
+            }
+            (Expr::Neg(self_var0), Expr::Neg(other_var0)) => {
+                let changes_var0 = ::comparable::derive_support::masked(
+                    "0",
+                    || self_var0.comparison(&other_var0).map(Box::new),
+                );
+                changes_var0.map(|changes_var0| ExprChange::BothNeg(changes_var0))
+            }
+            (Expr::Lit(self_var0), Expr::Lit(other_var0)) => {
+                let changes_var0 = ::comparable::derive_support::masked(
+                    "0",
+                    || self_var0.comparison(&other_var0),
+                );
+                changes_var0.map(|changes_var0| ExprChange::BothLit(changes_var0))
+            }
+            (_, _) => {
+                ::comparable::Changed::Changed(
+                    ExprChange::Different(
+                        ::comparable::Comparable::describe(self),
+                        ::comparable::Comparable::describe(other),
+                    ),
+                )
+            }
+        }
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        match (self, other) {
+            (Self::Add(self_var0, self_var1), Self::Add(other_var0, other_var1)) => {
+                (::comparable::Comparable::similarity(self_var0, other_var0)
+                    + ::comparable::Comparable::similarity(self_var1, other_var1)) / 2f64
+            }
+            (Self::Neg(self_var0), Self::Neg(other_var0)) => {
+                ::comparable::Comparable::similarity(self_var0, other_var0)
+            }
+            (Self::Lit(self_var0), Self::Lit(other_var0)) => {
+                ::comparable::Comparable::similarity(self_var0, other_var0)
+            }
+            _ => 0.0,
+        }
+    }
+    fn differs(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Add(self_var0, self_var1), Self::Add(other_var0, other_var1)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    false,
+                    || ::comparable::Comparable::differs(self_var0, other_var0),
+                )
+                    || ::comparable::derive_support::masked_or(
+                        "1",
+                        false,
+                        || ::comparable::Comparable::differs(self_var1, other_var1),
+                    )
+            }
+            (Self::Neg(self_var0), Self::Neg(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    false,
+                    || ::comparable::Comparable::differs(self_var0, other_var0),
+                )
+            }
+            (Self::Lit(self_var0), Self::Lit(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    false,
+                    || ::comparable::Comparable::differs(self_var0, other_var0),
+                )
+            }
+            _ => true,
+        }
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        match (self, other) {
+            (Self::Add(self_var0, self_var1), Self::Add(other_var0, other_var1)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    0,
+                    || ::comparable::Comparable::count_changes(self_var0, other_var0),
+                )
+                    + ::comparable::derive_support::masked_or(
+                        "1",
+                        0,
+                        || ::comparable::Comparable::count_changes(self_var1, other_var1),
+                    )
+            }
+            (Self::Neg(self_var0), Self::Neg(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    0,
+                    || ::comparable::Comparable::count_changes(self_var0, other_var0),
+                )
+            }
+            (Self::Lit(self_var0), Self::Lit(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    0,
+                    || ::comparable::Comparable::count_changes(self_var0, other_var0),
+                )
+            }
+            _ => 1,
+        }
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        match (self, other) {
+            (Self::Add(self_var0, self_var1), Self::Add(other_var0, other_var1)) => {
+                ::comparable::derive_support::masked_or(
+                        "0",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            self_var0,
+                            other_var0,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("0".to_string()),
+                            )
+                    })
+                    .or_else(|| {
+                        ::comparable::derive_support::masked_or(
+                                "1",
+                                None,
+                                || ::comparable::Comparable::first_difference(
+                                    self_var1,
+                                    other_var1,
+                                ),
+                            )
+                            .map(|path| {
+                                path
+                                    .within(
+                                        ::comparable::location::Segment::Field("1".to_string()),
+                                    )
+                            })
+                    })
+            }
+            (Self::Neg(self_var0), Self::Neg(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                        "0",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            self_var0,
+                            other_var0,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("0".to_string()),
+                            )
+                    })
+            }
+            (Self::Lit(self_var0), Self::Lit(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                        "0",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            self_var0,
+                            other_var0,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("0".to_string()),
+                            )
+                    })
+            }
+            _ => Some(::comparable::location::Path::default()),
+        }
+    }
+}
+#[derive(PartialEq, Debug)]
+enum ConnectionDesc {
+    Idle,
+    Open {
+        peer: <String as ::comparable::Comparable>::Desc,
+        bytes: <u64 as ::comparable::Comparable>::Desc,
+    },
+}
+#[derive(PartialEq, Debug)]
+enum ConnectionChange {
+    Different(
+        <Connection as ::comparable::Comparable>::Desc,
+        <Connection as ::comparable::Comparable>::Desc,
+    ),
+}
+impl ConnectionChange {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &[];
+}
+impl ::comparable::Comparable for Connection {
+    type Desc = ConnectionDesc;
+    fn describe(&self) -> Self::Desc {
+        match self {
+            Connection::Idle => ConnectionDesc::Idle,
+            Connection::Open { peer: var0, bytes: var1 } => {
+                ConnectionDesc::Open {
+                    peer: ::comparable::Comparable::describe(var0),
+                    bytes: ::comparable::Comparable::describe(var1),
+                }
+            }
+        }
+    }
+    type Change = ConnectionChange;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        if std::mem::discriminant(self) == std::mem::discriminant(other) {
+            ::comparable::Changed::Unchanged
+        } else {
+            ::comparable::Changed::Changed(
+                ConnectionChange::Different(
+                    ::comparable::Comparable::describe(self),
+                    ::comparable::Comparable::describe(other),
+                ),
+            )
+        }
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        if std::mem::discriminant(self) == std::mem::discriminant(other) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+    fn differs(&self, other: &Self) -> bool {
+        if std::mem::discriminant(self) == std::mem::discriminant(other) {
+            false
+        } else {
+            true
+        }
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        if std::mem::discriminant(self) == std::mem::discriminant(other) { 0 } else { 1 }
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        if std::mem::discriminant(self) == std::mem::discriminant(other) {
+            None
+        } else {
+            Some(::comparable::location::Path::default())
+        }
+    }
+}
+#[derive(PartialEq, Debug)]
+enum SourceDesc {
+    File(<String as ::comparable::Comparable>::Desc),
+    Open,
+}
+#[derive(PartialEq, Debug)]
+enum SourceChange {
+    BothFile(<String as ::comparable::Comparable>::Change),
+    Different(
+        <Source as ::comparable::Comparable>::Desc,
+        <Source as ::comparable::Comparable>::Desc,
+    ),
+}
+impl SourceChange {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["0"];
+}
+impl ::comparable::Comparable for Source {
+    type Desc = SourceDesc;
+    fn describe(&self) -> Self::Desc {
+        match self {
+            Source::File(var0) => {
+                SourceDesc::File(::comparable::Comparable::describe(var0))
+            }
+            Source::Open { .. } => SourceDesc::Open,
+        }
+    }
+    type Change = SourceChange;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        match (self, other) {
+            (Source::File(self_var0), Source::File(other_var0)) => {
+                let changes_var0 = ::comparable::derive_support::masked(
+                    "0",
+                    || self_var0.comparison(&other_var0),
+                );
+                changes_var0.map(|changes_var0| SourceChange::BothFile(changes_var0))
+            }
+            (
+                Source::Open(self_var0, self_var1),
+                Source::Open(other_var0, other_var1),
+            ) => ::comparable::Changed::Unchanged,
+            (_, _) => {
+                ::comparable::Changed::Changed(
+                    SourceChange::Different(
+                        ::comparable::Comparable::describe(self),
+                        ::comparable::Comparable::describe(other),
+                    ),
+                )
+            }
+        }
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        match (self, other) {
+            (Self::File(self_var0), Self::File(other_var0)) => {
+                ::comparable::Comparable::similarity(self_var0, other_var0)
+            }
+            (Self::Open(_, _), Self::Open(_, _)) => 1.0,
+            _ => 0.0,
+        }
+    }
+    fn differs(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::File(self_var0), Self::File(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    false,
+                    || ::comparable::Comparable::differs(self_var0, other_var0),
+                )
+            }
+            (Self::Open(_, _), Self::Open(_, _)) => false,
+            _ => true,
+        }
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        match (self, other) {
+            (Self::File(self_var0), Self::File(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    0,
+                    || ::comparable::Comparable::count_changes(self_var0, other_var0),
+                )
+            }
+            (Self::Open(_, _), Self::Open(_, _)) => 0,
+            _ => 1,
+        }
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        match (self, other) {
+            (Self::File(self_var0), Self::File(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                        "0",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            self_var0,
+                            other_var0,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("0".to_string()),
+                            )
+                    })
+            }
+            (Self::Open(_, _), Self::Open(_, _)) => None,
+            _ => Some(::comparable::location::Path::default()),
+        }
+    }
+}
+#[derive(PartialEq, Debug)]
+enum ClosedDesc {
+    Handle,
+}
+impl ::comparable::Comparable for Closed {
+    type Desc = ClosedDesc;
+    fn describe(&self) -> Self::Desc {
+        match self {
+            Closed::Handle { .. } => ClosedDesc::Handle,
+        }
+    }
+    type Change = ();
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        ::comparable::Changed::Unchanged
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        match (self, other) {
+            (Self::Handle(_), Self::Handle(_)) => 1.0,
+        }
+    }
+    fn differs(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Handle(_), Self::Handle(_)) => false,
+        }
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        match (self, other) {
+            (Self::Handle(_), Self::Handle(_)) => 0,
+        }
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        match (self, other) {
+            (Self::Handle(_), Self::Handle(_)) => None,
+        }
+    }
+}
@@ -0,0 +1,51 @@
+#[derive(Comparable)]
+enum Empty {}
+
+#[derive(Comparable)]
+pub enum Shape {
+	Point,
+	Circle(u32),
+	Segment(i32, i32),
+	Rectangle { width: u32, height: u32 },
+}
+
+#[derive(Comparable)]
+#[variant_struct_fields]
+enum Event {
+	Moved { x: i32, y: i32 },
+	Renamed(String),
+}
+
+#[derive(Comparable)]
+#[non_exhaustive]
+enum Status {
+	Up,
+	Down(String),
+}
+
+#[derive(Comparable)]
+enum Expr {
+	Add(Box<Expr>, Box<Expr>),
+	Neg(Box<Self>),
+	Lit(i64),
+}
+
+#[derive(Comparable)]
+#[comparable_variant_only]
+enum Connection {
+	Idle,
+	Open { peer: String, bytes: u64 },
+}
+
+#[derive(Comparable)]
+enum Source {
+	File(String),
+	#[comparable_ignore]
+	Open(std::fs::File, u64),
+}
+
+#[derive(Comparable)]
+enum Closed {
+	#[comparable_ignore]
+	Handle(std::fs::File),
+}
@@ -0,0 +1,1140 @@
+#[derive(PartialEq, Debug)]
+struct AccountDesc {
+    owner: <String as ::comparable::Comparable>::Desc,
+    balance: <i64 as ::comparable::Comparable>::Desc,
+}
+#[derive(PartialEq, Debug)]
+enum AccountChange {
+    Owner(<String as ::comparable::Comparable>::Change),
+    Balance(<i64 as ::comparable::Comparable>::Change),
+}
+impl ::comparable::merge::MergeKey for AccountChange {
+    fn merge_key(&self) -> String {
+        match self {
+            AccountChange::Owner(_) => "owner".to_string(),
+            AccountChange::Balance(_) => "balance".to_string(),
+        }
+    }
+}
+impl AccountChange {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["owner", "balance"];
+}
+impl ::comparable::display::DisplayChange for AccountChange {
+    fn display_changes(
+        &self,
+        path: &::comparable::location::Path,
+        lines: &mut Vec<String>,
+    ) {
+        match self {
+            AccountChange::Owner(change0) => {
+                ::comparable::display::DisplayChange::display_changes(
+                    change0,
+                    &path
+                        .child(
+                            ::comparable::location::Segment::Field("owner".to_string()),
+                        ),
+                    lines,
+                );
+            }
+            AccountChange::Balance(change0) => {
+                ::comparable::display::DisplayChange::display_changes(
+                    change0,
+                    &path
+                        .child(
+                            ::comparable::location::Segment::Field("balance".to_string()),
+                        ),
+                    lines,
+                );
+            }
+        }
+    }
+}
+impl std::fmt::Display for AccountChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&::comparable::display::display(self), f)
+    }
+}
+impl ::comparable::tree::ToTree for AccountChange {
+    fn to_tree(&self) -> ::comparable::tree::ChangeTree {
+        match self {
+            AccountChange::Owner(change0) => {
+                let mut children = Vec::new();
+                ::comparable::tree::push_child(
+                    &mut children,
+                    "owner".to_string(),
+                    ::comparable::tree::ToTree::to_tree(change0),
+                );
+                ::comparable::tree::ChangeTree::Children(children)
+            }
+            AccountChange::Balance(change0) => {
+                let mut children = Vec::new();
+                ::comparable::tree::push_child(
+                    &mut children,
+                    "balance".to_string(),
+                    ::comparable::tree::ToTree::to_tree(change0),
+                );
+                ::comparable::tree::ChangeTree::Children(children)
+            }
+        }
+    }
+}
+impl std::convert::From<&AccountChange> for Vec<(String, String, String)> {
+    fn from(change: &AccountChange) -> Self {
+        ::comparable::tree::ToTree::to_tree(change).rows()
+    }
+}
+impl ::comparable::Comparable for Account {
+    type Desc = AccountDesc;
+    fn describe(&self) -> Self::Desc {
+        AccountDesc {
+            owner: ::comparable::Comparable::describe(&self.owner),
+            balance: ::comparable::Comparable::describe(&self.balance),
+        }
+    }
+    type Change = Vec<AccountChange>;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        let mut changes: Vec<_> = Vec::new();
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "owner",
+                        || self.owner.comparison(&other.owner),
+                    )
+                    .map(AccountChange::Owner),
+            );
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "balance",
+                        || self.balance.comparison(&other.balance),
+                    )
+                    .map(AccountChange::Balance),
+            );
+        ::comparable::Changed::from_changes(changes)
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        (::comparable::Comparable::similarity(&self.owner, &other.owner)
+            + ::comparable::Comparable::similarity(&self.balance, &other.balance)) / 2f64
+    }
+    fn differs(&self, other: &Self) -> bool {
+        ::comparable::derive_support::masked_or(
+            "owner",
+            false,
+            || ::comparable::Comparable::differs(&self.owner, &other.owner),
+        )
+            || ::comparable::derive_support::masked_or(
+                "balance",
+                false,
+                || ::comparable::Comparable::differs(&self.balance, &other.balance),
+            )
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        ::comparable::derive_support::masked_or(
+            "owner",
+            0,
+            || ::comparable::Comparable::count_changes(&self.owner, &other.owner),
+        )
+            + ::comparable::derive_support::masked_or(
+                "balance",
+                0,
+                || ::comparable::Comparable::count_changes(&self.balance, &other.balance),
+            )
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        ::comparable::derive_support::masked_or(
+                "owner",
+                None,
+                || ::comparable::Comparable::first_difference(&self.owner, &other.owner),
+            )
+            .map(|path| {
+                path.within(::comparable::location::Segment::Field("owner".to_string()))
+            })
+            .or_else(|| {
+                ::comparable::derive_support::masked_or(
+                        "balance",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            &self.balance,
+                            &other.balance,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field(
+                                    "balance".to_string(),
+                                ),
+                            )
+                    })
+            })
+    }
+}
+impl ::comparable::roundtrip::FromDesc for Account {
+    fn from_desc(desc: <Self as ::comparable::Comparable>::Desc) -> Self {
+        match desc {
+            AccountDesc { owner: var0, balance: var1 } => {
+                Account {
+                    owner: ::comparable::roundtrip::FromDesc::from_desc(var0),
+                    balance: ::comparable::roundtrip::FromDesc::from_desc(var1),
+                }
+            }
+        }
+    }
+}
+impl ::comparable::roundtrip::IntoDesc for Account {
+    fn into_desc(self) -> <Self as ::comparable::Comparable>::Desc {
+        match self {
+            Account { owner: var0, balance: var1, .. } => {
+                AccountDesc {
+                    owner: ::comparable::roundtrip::IntoDesc::into_desc(var0),
+                    balance: ::comparable::roundtrip::IntoDesc::into_desc(var1),
+                }
+            }
+        }
+    }
+}
+impl ::comparable::schema::HasSchema for Account {
+    fn schema() -> ::comparable::schema::Schema {
+        ::comparable::derive_support::named_schema::<
+            Self,
+        >(
+            "Account",
+            || ::comparable::schema::Schema::Struct {
+                name: "Account".to_string(),
+                fields: vec![
+                    ::comparable::schema::Field::new("owner", < String as
+                    ::comparable::schema::HasSchema > ::schema()),
+                    ::comparable::schema::Field::new("balance", < i64 as
+                    ::comparable::schema::HasSchema > ::schema())
+                ],
+            },
+        )
+    }
+}
+impl ::comparable::lens::Apply for Account {
+    fn apply(&mut self, change: Self::Change) {
+        for change in change {
+            match change {
+                AccountChange::Owner(change) => {
+                    ::comparable::lens::Apply::apply(&mut self.owner, change)
+                }
+                AccountChange::Balance(change) => {
+                    ::comparable::lens::Apply::apply(&mut self.balance, change)
+                }
+            }
+        }
+    }
+    fn check(
+        &self,
+        change: &Self::Change,
+    ) -> Result<(), ::comparable::lens::ApplyError> {
+        for change in change {
+            match change {
+                AccountChange::Owner(change) => {
+                    ::comparable::lens::Apply::check(&self.owner, change)
+                        .map_err(|e| {
+                            e
+                                .within(
+                                    ::comparable::location::Segment::Field("owner".to_string()),
+                                )
+                        })?;
+                }
+                AccountChange::Balance(change) => {
+                    ::comparable::lens::Apply::check(&self.balance, change)
+                        .map_err(|e| {
+                            e
+                                .within(
+                                    ::comparable::location::Segment::Field(
+                                        "balance".to_string(),
+                                    ),
+                                )
+                        })?;
+                }
+            }
+        }
+        Ok(())
+    }
+    fn verify(
+        &self,
+        change: &Self::Change,
+    ) -> Result<(), ::comparable::lens::ConflictError> {
+        for change in change {
+            match change {
+                AccountChange::Owner(change) => {
+                    ::comparable::lens::Apply::verify(&self.owner, change)
+                        .map_err(|e| {
+                            e
+                                .within(
+                                    ::comparable::location::Segment::Field("owner".to_string()),
+                                )
+                        })?;
+                }
+                AccountChange::Balance(change) => {
+                    ::comparable::lens::Apply::verify(&self.balance, change)
+                        .map_err(|e| {
+                            e
+                                .within(
+                                    ::comparable::location::Segment::Field(
+                                        "balance".to_string(),
+                                    ),
+                                )
+                        })?;
+                }
+            }
+        }
+        Ok(())
+    }
+    fn verify_from(
+        &self,
+        base: &Self,
+        change: &Self::Change,
+    ) -> Result<(), ::comparable::lens::ConflictError> {
+        let _ = base;
+        for change in change {
+            match change {
+                AccountChange::Owner(change) => {
+                    ::comparable::lens::Apply::verify_from(
+                            &self.owner,
+                            &base.owner,
+                            change,
+                        )
+                        .map_err(|e| {
+                            e
+                                .within(
+                                    ::comparable::location::Segment::Field("owner".to_string()),
+                                )
+                        })?;
+                }
+                AccountChange::Balance(change) => {
+                    ::comparable::lens::Apply::verify_from(
+                            &self.balance,
+                            &base.balance,
+                            change,
+                        )
+                        .map_err(|e| {
+                            e
+                                .within(
+                                    ::comparable::location::Segment::Field(
+                                        "balance".to_string(),
+                                    ),
+                                )
+                        })?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+impl Account {
+    ///A lens for the field `owner`.
+    pub fn owner_lens() -> ::comparable::lens::Lens<Self, String> {
+        ::comparable::derive_support::lens(
+            "owner",
+            |value| &value.owner,
+            |value| &mut value.owner,
+            |change| {
+                change
+                    .iter()
+                    .find_map(|change| match change {
+                        AccountChange::Owner(change) => Some(change),
+                        _ => None,
+                    })
+            },
+        )
+    }
+    ///A lens for the field `balance`.
+    pub fn balance_lens() -> ::comparable::lens::Lens<Self, i64> {
+        ::comparable::derive_support::lens(
+            "balance",
+            |value| &value.balance,
+            |value| &mut value.balance,
+            |change| {
+                change
+                    .iter()
+                    .find_map(|change| match change {
+                        AccountChange::Balance(change) => Some(change),
+                        _ => None,
+                    })
+            },
+        )
+    }
+}
+mod account {
+    pub mod path {
+        pub const OWNER: &str = "owner";
+        pub const BALANCE: &str = "balance";
+    }
+}
+#[derive(PartialEq, Debug)]
+struct UserDesc {
+    id: <u64 as ::comparable::Comparable>::Desc,
+    name: <String as ::comparable::Comparable>::Desc,
+}
+#[derive(PartialEq, Debug)]
+enum UserChange {
+    Id(<u64 as ::comparable::Comparable>::Change),
+    Name(<String as ::comparable::Comparable>::Change),
+}
+impl ::comparable::merge::MergeKey for UserChange {
+    fn merge_key(&self) -> String {
+        match self {
+            UserChange::Id(_) => "id".to_string(),
+            UserChange::Name(_) => "name".to_string(),
+        }
+    }
+}
+impl UserChange {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["id", "name"];
+}
+impl ::comparable::Comparable for User {
+    type Desc = UserDesc;
+    fn describe(&self) -> Self::Desc {
+        UserDesc {
+            id: ::comparable::Comparable::describe(&self.id),
+            name: ::comparable::Comparable::describe(&self.name),
+        }
+    }
+    type Change = Vec<UserChange>;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        let mut changes: Vec<_> = Vec::new();
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "id",
+                        || self.id.comparison(&other.id),
+                    )
+                    .map(UserChange::Id),
+            );
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "name",
+                        || self.name.comparison(&other.name),
+                    )
+                    .map(UserChange::Name),
+            );
+        ::comparable::Changed::from_changes(changes)
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        (::comparable::Comparable::similarity(&self.id, &other.id)
+            + ::comparable::Comparable::similarity(&self.name, &other.name)) / 2f64
+    }
+    fn differs(&self, other: &Self) -> bool {
+        ::comparable::derive_support::masked_or(
+            "id",
+            false,
+            || ::comparable::Comparable::differs(&self.id, &other.id),
+        )
+            || ::comparable::derive_support::masked_or(
+                "name",
+                false,
+                || ::comparable::Comparable::differs(&self.name, &other.name),
+            )
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        ::comparable::derive_support::masked_or(
+            "id",
+            0,
+            || ::comparable::Comparable::count_changes(&self.id, &other.id),
+        )
+            + ::comparable::derive_support::masked_or(
+                "name",
+                0,
+                || ::comparable::Comparable::count_changes(&self.name, &other.name),
+            )
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        ::comparable::derive_support::masked_or(
+                "id",
+                None,
+                || ::comparable::Comparable::first_difference(&self.id, &other.id),
+            )
+            .map(|path| {
+                path.within(::comparable::location::Segment::Field("id".to_string()))
+            })
+            .or_else(|| {
+                ::comparable::derive_support::masked_or(
+                        "name",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            &self.name,
+                            &other.name,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("name".to_string()),
+                            )
+                    })
+            })
+    }
+}
+impl ::comparable::sql::SqlChangeset for User {
+    fn sql_update(&self, change: &Self::Change) -> Option<::comparable::sql::SqlUpdate> {
+        let mut assignments = Vec::new();
+        for change in change {
+            match change {
+                UserChange::Id(change) => {
+                    assignments
+                        .push((
+                            "id".to_string(),
+                            ::comparable::sql::ChangeToSql::new_value(change),
+                        ))
+                }
+                UserChange::Name(change) => {
+                    assignments
+                        .push((
+                            "name".to_string(),
+                            ::comparable::sql::ChangeToSql::new_value(change),
+                        ))
+                }
+            }
+        }
+        if assignments.is_empty() {
+            return None;
+        }
+        Some(::comparable::sql::SqlUpdate {
+            table: "users".to_string(),
+            assignments,
+            key: ("id".to_string(), ::comparable::sql::ToSql::to_sql(&self.id)),
+        })
+    }
+}
+#[derive(PartialEq, Debug)]
+pub struct ConfigDesc {
+    name: <String as ::comparable::Comparable>::Desc,
+    timeout: <u64 as ::comparable::Comparable>::Desc,
+}
+#[derive(PartialEq, Debug)]
+pub enum ConfigChange {
+    Name(<String as ::comparable::Comparable>::Change),
+    Timeout(<u64 as ::comparable::Comparable>::Change),
+}
+impl ::comparable::merge::MergeKey for ConfigChange {
+    fn merge_key(&self) -> String {
+        match self {
+            ConfigChange::Name(_) => "name".to_string(),
+            ConfigChange::Timeout(_) => "timeout".to_string(),
+        }
+    }
+}
+impl ConfigChange {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["name", "timeout"];
+}
+#[derive(PartialEq, Debug)]
+pub enum ConfigFromConfigChange {
+    Name(<String as ::comparable::Comparable>::Change),
+    AddedTimeout(<u64 as ::comparable::Comparable>::Desc),
+    Removed(String),
+}
+impl ::comparable::Comparable for Config {
+    type Desc = ConfigDesc;
+    fn describe(&self) -> Self::Desc {
+        ConfigDesc {
+            name: ::comparable::Comparable::describe(&self.name),
+            timeout: ::comparable::Comparable::describe(&self.timeout),
+        }
+    }
+    type Change = Vec<ConfigChange>;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        let mut changes: Vec<_> = Vec::new();
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "name",
+                        || self.name.comparison(&other.name),
+                    )
+                    .map(ConfigChange::Name),
+            );
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "timeout",
+                        || self.timeout.comparison(&other.timeout),
+                    )
+                    .map(ConfigChange::Timeout),
+            );
+        ::comparable::Changed::from_changes(changes)
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        (::comparable::Comparable::similarity(&self.name, &other.name)
+            + ::comparable::Comparable::similarity(&self.timeout, &other.timeout)) / 2f64
+    }
+    fn differs(&self, other: &Self) -> bool {
+        ::comparable::derive_support::masked_or(
+            "name",
+            false,
+            || ::comparable::Comparable::differs(&self.name, &other.name),
+        )
+            || ::comparable::derive_support::masked_or(
+                "timeout",
+                false,
+                || ::comparable::Comparable::differs(&self.timeout, &other.timeout),
+            )
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        ::comparable::derive_support::masked_or(
+            "name",
+            0,
+            || ::comparable::Comparable::count_changes(&self.name, &other.name),
+        )
+            + ::comparable::derive_support::masked_or(
+                "timeout",
+                0,
+                || ::comparable::Comparable::count_changes(&self.timeout, &other.timeout),
+            )
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        ::comparable::derive_support::masked_or(
+                "name",
+                None,
+                || ::comparable::Comparable::first_difference(&self.name, &other.name),
+            )
+            .map(|path| {
+                path.within(::comparable::location::Segment::Field("name".to_string()))
+            })
+            .or_else(|| {
+                ::comparable::derive_support::masked_or(
+                        "timeout",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            &self.timeout,
+                            &other.timeout,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field(
+                                    "timeout".to_string(),
+                                ),
+                            )
+                    })
+            })
+    }
+}
+impl ::comparable::ComparableFrom<config_v1::Config> for Config {
+    type Change = Vec<ConfigFromConfigChange>;
+    fn comparison_from(
+        &self,
+        before: &config_v1::Config,
+    ) -> ::comparable::Changed<Self::Change> {
+        let mut changes = Vec::new();
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "name",
+                        || before.name.comparison(&self.name),
+                    )
+                    .map(ConfigFromConfigChange::Name),
+            );
+        changes
+            .push(
+                ConfigFromConfigChange::AddedTimeout(
+                    ::comparable::Comparable::describe(&self.timeout),
+                ),
+            );
+        changes.push(ConfigFromConfigChange::Removed("verbose".to_string()));
+        ::comparable::Changed::from_changes(changes)
+    }
+}
+#[derive(PartialEq, Debug)]
+enum ShapeDesc {
+    Circle(<u32 as ::comparable::Comparable>::Desc),
+    Square(<u32 as ::comparable::Comparable>::Desc),
+}
+#[derive(PartialEq, Debug)]
+enum ShapeChange {
+    BothCircle(<u32 as ::comparable::Comparable>::Change),
+    BothSquare(<u32 as ::comparable::Comparable>::Change),
+    Different(
+        <Shape as ::comparable::Comparable>::Desc,
+        <Shape as ::comparable::Comparable>::Desc,
+    ),
+}
+impl ShapeChange {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["0"];
+}
+impl ::comparable::Comparable for Shape {
+    type Desc = ShapeDesc;
+    fn describe(&self) -> Self::Desc {
+        match self {
+            Shape::Circle(var0) => {
+                ShapeDesc::Circle(::comparable::Comparable::describe(var0))
+            }
+            Shape::Square(var0) => {
+                ShapeDesc::Square(::comparable::Comparable::describe(var0))
+            }
+        }
+    }
+    type Change = ShapeChange;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        match (self, other) {
+            (Shape::Circle(self_var0), Shape::Circle(other_var0)) => {
+                let changes_var0 = ::comparable::derive_support::masked(
+                    "0",
+                    || self_var0.comparison(&other_var0),
+                );
+                changes_var0.map(|changes_var0| ShapeChange::BothCircle(changes_var0))
+            }
+            (Shape::Square(self_var0), Shape::Square(other_var0)) => {
+                let changes_var0 = ::comparable::derive_support::masked(
+                    "0",
+                    || self_var0.comparison(&other_var0),
+                );
+                changes_var0.map(|changes_var0| ShapeChange::BothSquare(changes_var0))
+            }
+            (_, _) => {
+                ::comparable::Changed::Changed(
+                    ShapeChange::Different(
+                        ::comparable::Comparable::describe(self),
+                        ::comparable::Comparable::describe(other),
+                    ),
+                )
+            }
+        }
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        match (self, other) {
+            (Self::Circle(self_var0), Self::Circle(other_var0)) => {
+                ::comparable::Comparable::similarity(self_var0, other_var0)
+            }
+            (Self::Square(self_var0), Self::Square(other_var0)) => {
+                ::comparable::Comparable::similarity(self_var0, other_var0)
+            }
+            _ => 0.0,
+        }
+    }
+    fn differs(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Circle(self_var0), Self::Circle(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    false,
+                    || ::comparable::Comparable::differs(self_var0, other_var0),
+                )
+            }
+            (Self::Square(self_var0), Self::Square(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    false,
+                    || ::comparable::Comparable::differs(self_var0, other_var0),
+                )
+            }
+            _ => true,
+        }
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        match (self, other) {
+            (Self::Circle(self_var0), Self::Circle(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    0,
+                    || ::comparable::Comparable::count_changes(self_var0, other_var0),
+                )
+            }
+            (Self::Square(self_var0), Self::Square(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    0,
+                    || ::comparable::Comparable::count_changes(self_var0, other_var0),
+                )
+            }
+            _ => 1,
+        }
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        match (self, other) {
+            (Self::Circle(self_var0), Self::Circle(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                        "0",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            self_var0,
+                            other_var0,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("0".to_string()),
+                            )
+                    })
+            }
+            (Self::Square(self_var0), Self::Square(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                        "0",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            self_var0,
+                            other_var0,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("0".to_string()),
+                            )
+                    })
+            }
+            _ => Some(::comparable::location::Path::default()),
+        }
+    }
+}
+#[derive(PartialEq, Debug)]
+enum JobDesc {
+    Queued,
+    Running { pid: <u32 as ::comparable::Comparable>::Desc },
+    Stopped(<i32 as ::comparable::Comparable>::Desc),
+}
+#[derive(PartialEq, Debug)]
+enum JobChange {
+    BothRunning { pid: <u32 as ::comparable::Comparable>::Change },
+    BothStopped(<i32 as ::comparable::Comparable>::Change),
+    Different(
+        <Job as ::comparable::Comparable>::Desc,
+        <Job as ::comparable::Comparable>::Desc,
+    ),
+}
+impl JobChange {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["pid", "0"];
+}
+impl ::comparable::display::DisplayChange for JobChange {
+    fn display_changes(
+        &self,
+        path: &::comparable::location::Path,
+        lines: &mut Vec<String>,
+    ) {
+        match self {
+            JobChange::BothRunning { pid: change0 } => {
+                ::comparable::display::DisplayChange::display_changes(
+                    change0,
+                    &path
+                        .child(
+                            ::comparable::location::Segment::Field("pid".to_string()),
+                        ),
+                    lines,
+                );
+            }
+            JobChange::BothStopped(change0) => {
+                ::comparable::display::DisplayChange::display_changes(
+                    change0,
+                    &path.child(::comparable::location::Segment::Field("0".to_string())),
+                    lines,
+                );
+            }
+            JobChange::Different(change0, change1) => {
+                ::comparable::display::push_replaced(lines, path, change0, change1)
+            }
+        }
+    }
+}
+impl std::fmt::Display for JobChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&::comparable::display::display(self), f)
+    }
+}
+impl ::comparable::tree::ToTree for JobChange {
+    fn to_tree(&self) -> ::comparable::tree::ChangeTree {
+        match self {
+            JobChange::BothRunning { pid: change0 } => {
+                let mut children = Vec::new();
+                ::comparable::tree::push_child(
+                    &mut children,
+                    "pid".to_string(),
+                    ::comparable::tree::ToTree::to_tree(change0),
+                );
+                ::comparable::tree::ChangeTree::Children(children)
+            }
+            JobChange::BothStopped(change0) => {
+                let mut children = Vec::new();
+                ::comparable::tree::push_child(
+                    &mut children,
+                    "0".to_string(),
+                    ::comparable::tree::ToTree::to_tree(change0),
+                );
+                ::comparable::tree::ChangeTree::Children(children)
+            }
+            JobChange::Different(change0, change1) => {
+                ::comparable::tree::ChangeTree::modified(change0, change1)
+            }
+        }
+    }
+}
+impl std::convert::From<&JobChange> for Vec<(String, String, String)> {
+    fn from(change: &JobChange) -> Self {
+        ::comparable::tree::ToTree::to_tree(change).rows()
+    }
+}
+#[derive(PartialEq, Debug)]
+enum JobTransition {
+    QueuedToRunning {
+        before: <Job as ::comparable::Comparable>::Desc,
+        after: <Job as ::comparable::Comparable>::Desc,
+    },
+    QueuedToStopped {
+        before: <Job as ::comparable::Comparable>::Desc,
+        after: <Job as ::comparable::Comparable>::Desc,
+    },
+    RunningToQueued {
+        before: <Job as ::comparable::Comparable>::Desc,
+        after: <Job as ::comparable::Comparable>::Desc,
+    },
+    RunningToStopped {
+        before: <Job as ::comparable::Comparable>::Desc,
+        after: <Job as ::comparable::Comparable>::Desc,
+    },
+    StoppedToQueued {
+        before: <Job as ::comparable::Comparable>::Desc,
+        after: <Job as ::comparable::Comparable>::Desc,
+    },
+    StoppedToRunning {
+        before: <Job as ::comparable::Comparable>::Desc,
+        after: <Job as ::comparable::Comparable>::Desc,
+    },
+}
+impl ::comparable::Comparable for Job {
+    type Desc = JobDesc;
+    fn describe(&self) -> Self::Desc {
+        match self {
+            Job::Queued => JobDesc::Queued,
+            Job::Running { pid: var0 } => {
+                JobDesc::Running {
+                    pid: ::comparable::Comparable::describe(var0),
+                }
+            }
+            Job::Stopped(var0) => {
+                JobDesc::Stopped(::comparable::Comparable::describe(var0))
+            }
+        }
+    }
+    type Change = JobChange;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        match (self, other) {
+            (Job::Queued, Job::Queued) => ::comparable::Changed::Unchanged,
+            (Job::Running { pid: self_var0 }, Job::Running { pid: other_var0 }) => {
+                let changes_var0 = ::comparable::derive_support::masked(
+                    "pid",
+                    || self_var0.comparison(&other_var0),
+                );
+                changes_var0
+                    .map(|changes_var0| JobChange::BothRunning {
+                        pid: changes_var0,
+                    })
+            }
+            (Job::Stopped(self_var0), Job::Stopped(other_var0)) => {
+                let changes_var0 = ::comparable::derive_support::masked(
+                    "0",
+                    || self_var0.comparison(&other_var0),
+                );
+                changes_var0.map(|changes_var0| JobChange::BothStopped(changes_var0))
+            }
+            (_, _) => {
+                ::comparable::Changed::Changed(
+                    JobChange::Different(
+                        ::comparable::Comparable::describe(self),
+                        ::comparable::Comparable::describe(other),
+                    ),
+                )
+            }
+        }
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        match (self, other) {
+            (Self::Queued, Self::Queued) => 1.0,
+            (Self::Running { pid: self_var0 }, Self::Running { pid: other_var0 }) => {
+                ::comparable::Comparable::similarity(self_var0, other_var0)
+            }
+            (Self::Stopped(self_var0), Self::Stopped(other_var0)) => {
+                ::comparable::Comparable::similarity(self_var0, other_var0)
+            }
+            _ => 0.0,
+        }
+    }
+    fn differs(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Queued, Self::Queued) => false,
+            (Self::Running { pid: self_var0 }, Self::Running { pid: other_var0 }) => {
+                ::comparable::derive_support::masked_or(
+                    "pid",
+                    false,
+                    || ::comparable::Comparable::differs(self_var0, other_var0),
+                )
+            }
+            (Self::Stopped(self_var0), Self::Stopped(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    false,
+                    || ::comparable::Comparable::differs(self_var0, other_var0),
+                )
+            }
+            _ => true,
+        }
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        match (self, other) {
+            (Self::Queued, Self::Queued) => 0,
+            (Self::Running { pid: self_var0 }, Self::Running { pid: other_var0 }) => {
+                ::comparable::derive_support::masked_or(
+                    "pid",
+                    0,
+                    || ::comparable::Comparable::count_changes(self_var0, other_var0),
+                )
+            }
+            (Self::Stopped(self_var0), Self::Stopped(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    0,
+                    || ::comparable::Comparable::count_changes(self_var0, other_var0),
+                )
+            }
+            _ => 1,
+        }
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        match (self, other) {
+            (Self::Queued, Self::Queued) => None,
+            (Self::Running { pid: self_var0 }, Self::Running { pid: other_var0 }) => {
+                ::comparable::derive_support::masked_or(
+                        "pid",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            self_var0,
+                            other_var0,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("pid".to_string()),
+                            )
+                    })
+            }
+            (Self::Stopped(self_var0), Self::Stopped(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                        "0",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            self_var0,
+                            other_var0,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("0".to_string()),
+                            )
+                    })
+            }
+            _ => Some(::comparable::location::Path::default()),
+        }
+    }
+}
+impl Job {
+    /// The transition from this value to `other`, if they are
+    /// different variants.
+    pub fn transition(&self, other: &Self) -> Option<JobTransition> {
+        match (self, other) {
+            (Self::Queued { .. }, Self::Running { .. }) => {
+                Some(JobTransition::QueuedToRunning {
+                    before: ::comparable::Comparable::describe(self),
+                    after: ::comparable::Comparable::describe(other),
+                })
+            }
+            (Self::Queued { .. }, Self::Stopped { .. }) => {
+                Some(JobTransition::QueuedToStopped {
+                    before: ::comparable::Comparable::describe(self),
+                    after: ::comparable::Comparable::describe(other),
+                })
+            }
+            (Self::Running { .. }, Self::Queued { .. }) => {
+                Some(JobTransition::RunningToQueued {
+                    before: ::comparable::Comparable::describe(self),
+                    after: ::comparable::Comparable::describe(other),
+                })
+            }
+            (Self::Running { .. }, Self::Stopped { .. }) => {
+                Some(JobTransition::RunningToStopped {
+                    before: ::comparable::Comparable::describe(self),
+                    after: ::comparable::Comparable::describe(other),
+                })
+            }
+            (Self::Stopped { .. }, Self::Queued { .. }) => {
+                Some(JobTransition::StoppedToQueued {
+                    before: ::comparable::Comparable::describe(self),
+                    after: ::comparable::Comparable::describe(other),
+                })
+            }
+            (Self::Stopped { .. }, Self::Running { .. }) => {
+                Some(JobTransition::StoppedToRunning {
+                    before: ::comparable::Comparable::describe(self),
+                    after: ::comparable::Comparable::describe(other),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+#[derive(PartialEq, Debug)]
+struct GuardedDesc<__T0> {
+    value: __T0,
+    label: <String as ::comparable::fallible::TryComparable>::Desc,
+}
+#[derive(PartialEq, Debug)]
+enum GuardedChange<__T0> {
+    Value(__T0),
+    Label(<String as ::comparable::fallible::TryComparable>::Change),
+}
+impl<T> ::comparable::fallible::TryComparable for Guarded<T>
+where
+    std::sync::Mutex<T>: ::comparable::fallible::TryComparable,
+{
+    type Desc = GuardedDesc<
+        <std::sync::Mutex<T> as ::comparable::fallible::TryComparable>::Desc,
+    >;
+    fn try_describe(&self) -> Result<Self::Desc, ::comparable::fallible::Error> {
+        Ok(GuardedDesc {
+            value: ::comparable::fallible::TryComparable::try_describe(&self.value)?,
+            label: ::comparable::fallible::TryComparable::try_describe(&self.label)?,
+        })
+    }
+    type Change = Vec<
+        GuardedChange<
+            <std::sync::Mutex<T> as ::comparable::fallible::TryComparable>::Change,
+        >,
+    >;
+    fn try_comparison(
+        &self,
+        other: &Self,
+    ) -> Result<::comparable::Changed<Self::Change>, ::comparable::fallible::Error> {
+        let mut changes = Vec::new();
+        changes
+            .extend(
+                ::comparable::fallible::TryComparable::try_comparison(
+                        &self.value,
+                        &other.value,
+                    )?
+                    .map(GuardedChange::Value),
+            );
+        changes
+            .extend(
+                ::comparable::fallible::TryComparable::try_comparison(
+                        &self.label,
+                        &other.label,
+                    )?
+                    .map(GuardedChange::Label),
+            );
+        Ok(::comparable::Changed::from_changes(changes))
+    }
+}
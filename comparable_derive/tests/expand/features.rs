@@ -0,0 +1,53 @@
+#[derive(Comparable)]
+#[comparable_desc_roundtrip]
+#[comparable_schema]
+#[comparable_paths]
+#[comparable_lens]
+#[comparable_display]
+#[comparable_tree]
+struct Account {
+	owner: String,
+	balance: i64,
+}
+
+#[derive(Comparable)]
+#[comparable_sql(table = "users", key = "id")]
+struct User {
+	id: u64,
+	name: String,
+}
+
+#[derive(Comparable)]
+#[comparable_from(config_v1::Config, removed(verbose))]
+pub struct Config {
+	name: String,
+	#[comparable_added]
+	timeout: u64,
+}
+
+#[derive(Comparable)]
+#[comparable_version = 2]
+#[comparable_migrate]
+enum Shape {
+	Circle(u32),
+	Square(u32),
+}
+
+#[derive(Comparable)]
+#[comparable_transitions]
+#[comparable_display]
+#[comparable_tree]
+enum Job {
+	Queued,
+	Running { pid: u32 },
+	Stopped(i32),
+}
+
+#[derive(Comparable)]
+#[comparable_try]
+struct Guarded<T> {
+	value: std::sync::Mutex<T>,
+	#[comparable_ignore]
+	hits: u64,
+	label: String,
+}
@@ -0,0 +1,29 @@
+#[derive(Comparable)]
+struct Fields {
+	#[comparable_synthetic {
+		let total = |x: &Self| -> u8 { x.parts.iter().sum() };
+	}]
+	#[comparable_ignore]
+	parts: Vec<u8>,
+	#[comparable_box]
+	boxed: Box<u8>,
+	#[comparable_redact]
+	password: String,
+	#[comparable_by(desc = "self.name()", eq = "self.id()")]
+	plugin: Box<dyn Plugin>,
+	#[comparable_rename = "Label"]
+	name: String,
+	#[comparable_flatten]
+	address: Address,
+	#[comparable_group("limits")]
+	min: u8,
+	#[comparable_group("limits")]
+	max: u8,
+}
+
+#[derive(Comparable)]
+struct Pinned {
+	name: String,
+	#[comparable_leaf]
+	version: Version,
+}
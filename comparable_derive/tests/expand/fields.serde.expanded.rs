@@ -0,0 +1,589 @@
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+struct FieldsDesc {
+    total: <u8 as ::comparable::Comparable>::Desc,
+    boxed: Box<<Box<u8> as ::comparable::Comparable>::Desc>,
+    password: ::comparable::Redacted,
+    plugin: String,
+    name: <String as ::comparable::Comparable>::Desc,
+    address: <Address as ::comparable::Comparable>::Desc,
+    min: <u8 as ::comparable::Comparable>::Desc,
+    max: <u8 as ::comparable::Comparable>::Desc,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+enum FieldsChange {
+    Total(<u8 as ::comparable::Comparable>::Change),
+    Boxed(Box<<Box<u8> as ::comparable::Comparable>::Change>),
+    Password(::comparable::Redacted),
+    Plugin(::comparable::StringChange),
+    Label(<String as ::comparable::Comparable>::Change),
+    Address(<<Address as ::comparable::Comparable>::Change as IntoIterator>::Item),
+    Limits(Vec<FieldsLimitsChange>),
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+enum FieldsLimitsChange {
+    Min(<u8 as ::comparable::Comparable>::Change),
+    Max(<u8 as ::comparable::Comparable>::Change),
+}
+impl ::comparable::merge::MergeKey for FieldsChange {
+    fn merge_key(&self) -> String {
+        match self {
+            FieldsChange::Total(_) => "total".to_string(),
+            FieldsChange::Boxed(_) => "boxed".to_string(),
+            FieldsChange::Password(_) => "password".to_string(),
+            FieldsChange::Plugin(_) => "plugin".to_string(),
+            FieldsChange::Label(_) => "name".to_string(),
+            FieldsChange::Address(change) => {
+                format!(
+                    "{}.{}", "address", ::comparable::merge::MergeKey::merge_key(change)
+                )
+            }
+            FieldsChange::Limits(_) => "limits".to_string(),
+        }
+    }
+}
+impl FieldsChange {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &[
+        "total",
+        "boxed",
+        "password",
+        "plugin",
+        "name",
+        "address",
+        "min",
+        "max",
+    ];
+}
+impl ::comparable::Comparable for Fields {
+    type Desc = FieldsDesc;
+    fn describe(&self) -> Self::Desc {
+        FieldsDesc {
+            total: ::comparable::Comparable::describe(
+                &(|x: &Self| -> u8 { x.parts.iter().sum() })(&self),
+            ),
+            boxed: Box::new(::comparable::Comparable::describe(&self.boxed)),
+            password: ::comparable::Redacted,
+            plugin: ::comparable::Comparable::describe(
+                &::comparable::derive_support::by(
+                    &self.plugin,
+                    |__value| ToString::to_string(&(__value.name())),
+                    |__value| __value.id(),
+                ),
+            ),
+            name: ::comparable::Comparable::describe(&self.name),
+            address: ::comparable::Comparable::describe(&self.address),
+            min: ::comparable::Comparable::describe(&self.min),
+            max: ::comparable::Comparable::describe(&self.max),
+        }
+    }
+    type Change = Vec<FieldsChange>;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        let mut changes: Vec<_> = Vec::new();
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "total",
+                        || {
+                            (|x: &Self| -> u8 { x.parts.iter().sum() })(&self)
+                                .comparison(
+                                    &(|x: &Self| -> u8 { x.parts.iter().sum() })(&other),
+                                )
+                        },
+                    )
+                    .map(FieldsChange::Total),
+            );
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "boxed",
+                        || self.boxed.comparison(&other.boxed).map(Box::new),
+                    )
+                    .map(FieldsChange::Boxed),
+            );
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "password",
+                        || {
+                            self
+                                .password
+                                .comparison(&other.password)
+                                .map(|_| ::comparable::Redacted)
+                        },
+                    )
+                    .map(FieldsChange::Password),
+            );
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "plugin",
+                        || {
+                            ::comparable::derive_support::by(
+                                    &self.plugin,
+                                    |__value| ToString::to_string(&(__value.name())),
+                                    |__value| __value.id(),
+                                )
+                                .comparison(
+                                    &::comparable::derive_support::by(
+                                        &other.plugin,
+                                        |__value| ToString::to_string(&(__value.name())),
+                                        |__value| __value.id(),
+                                    ),
+                                )
+                        },
+                    )
+                    .map(FieldsChange::Plugin),
+            );
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "name",
+                        || self.name.comparison(&other.name),
+                    )
+                    .map(FieldsChange::Label),
+            );
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "address",
+                        || self.address.comparison(&other.address),
+                    )
+                    .flatten()
+                    .map(FieldsChange::Address),
+            );
+        let mut group_changes: Vec<_> = Vec::new();
+        group_changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "min",
+                        || self.min.comparison(&other.min),
+                    )
+                    .map(FieldsLimitsChange::Min),
+            );
+        group_changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "max",
+                        || self.max.comparison(&other.max),
+                    )
+                    .map(FieldsLimitsChange::Max),
+            );
+        changes
+            .extend(
+                ::comparable::Changed::from_changes(group_changes)
+                    .map(FieldsChange::Limits),
+            );
+        ::comparable::Changed::from_changes(changes)
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        (::comparable::Comparable::similarity(
+            &(|x: &Self| -> u8 { x.parts.iter().sum() })(&self),
+            &(|x: &Self| -> u8 { x.parts.iter().sum() })(&other),
+        ) + ::comparable::Comparable::similarity(&self.boxed, &other.boxed)
+            + ::comparable::Comparable::similarity(&self.password, &other.password)
+            + ::comparable::Comparable::similarity(
+                &::comparable::derive_support::by(
+                    &self.plugin,
+                    |__value| ToString::to_string(&(__value.name())),
+                    |__value| __value.id(),
+                ),
+                &::comparable::derive_support::by(
+                    &other.plugin,
+                    |__value| ToString::to_string(&(__value.name())),
+                    |__value| __value.id(),
+                ),
+            ) + ::comparable::Comparable::similarity(&self.name, &other.name)
+            + ::comparable::Comparable::similarity(&self.address, &other.address)
+            + ::comparable::Comparable::similarity(&self.min, &other.min)
+            + ::comparable::Comparable::similarity(&self.max, &other.max)) / 8f64
+    }
+    fn differs(&self, other: &Self) -> bool {
+        ::comparable::derive_support::masked_or(
+            "total",
+            false,
+            || ::comparable::Comparable::differs(
+                &(|x: &Self| -> u8 { x.parts.iter().sum() })(&self),
+                &(|x: &Self| -> u8 { x.parts.iter().sum() })(&other),
+            ),
+        )
+            || ::comparable::derive_support::masked_or(
+                "boxed",
+                false,
+                || ::comparable::Comparable::differs(&self.boxed, &other.boxed),
+            )
+            || ::comparable::derive_support::masked_or(
+                "password",
+                false,
+                || ::comparable::Comparable::differs(&self.password, &other.password),
+            )
+            || ::comparable::derive_support::masked_or(
+                "plugin",
+                false,
+                || ::comparable::Comparable::differs(
+                    &::comparable::derive_support::by(
+                        &self.plugin,
+                        |__value| ToString::to_string(&(__value.name())),
+                        |__value| __value.id(),
+                    ),
+                    &::comparable::derive_support::by(
+                        &other.plugin,
+                        |__value| ToString::to_string(&(__value.name())),
+                        |__value| __value.id(),
+                    ),
+                ),
+            )
+            || ::comparable::derive_support::masked_or(
+                "name",
+                false,
+                || ::comparable::Comparable::differs(&self.name, &other.name),
+            )
+            || ::comparable::derive_support::masked_or(
+                "address",
+                false,
+                || ::comparable::Comparable::differs(&self.address, &other.address),
+            )
+            || ::comparable::derive_support::masked_or(
+                "min",
+                false,
+                || ::comparable::Comparable::differs(&self.min, &other.min),
+            )
+            || ::comparable::derive_support::masked_or(
+                "max",
+                false,
+                || ::comparable::Comparable::differs(&self.max, &other.max),
+            )
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        ::comparable::derive_support::masked_or(
+            "total",
+            0,
+            || ::comparable::Comparable::count_changes(
+                &(|x: &Self| -> u8 { x.parts.iter().sum() })(&self),
+                &(|x: &Self| -> u8 { x.parts.iter().sum() })(&other),
+            ),
+        )
+            + ::comparable::derive_support::masked_or(
+                "boxed",
+                0,
+                || ::comparable::Comparable::count_changes(&self.boxed, &other.boxed),
+            )
+            + ::comparable::derive_support::masked_or(
+                "password",
+                0,
+                || ::comparable::Comparable::count_changes(
+                    &self.password,
+                    &other.password,
+                ),
+            )
+            + ::comparable::derive_support::masked_or(
+                "plugin",
+                0,
+                || ::comparable::Comparable::count_changes(
+                    &::comparable::derive_support::by(
+                        &self.plugin,
+                        |__value| ToString::to_string(&(__value.name())),
+                        |__value| __value.id(),
+                    ),
+                    &::comparable::derive_support::by(
+                        &other.plugin,
+                        |__value| ToString::to_string(&(__value.name())),
+                        |__value| __value.id(),
+                    ),
+                ),
+            )
+            + ::comparable::derive_support::masked_or(
+                "name",
+                0,
+                || ::comparable::Comparable::count_changes(&self.name, &other.name),
+            )
+            + ::comparable::derive_support::masked_or(
+                "address",
+                0,
+                || ::comparable::Comparable::count_changes(&self.address, &other.address),
+            )
+            + ::comparable::derive_support::masked_or(
+                "min",
+                0,
+                || ::comparable::Comparable::count_changes(&self.min, &other.min),
+            )
+            + ::comparable::derive_support::masked_or(
+                "max",
+                0,
+                || ::comparable::Comparable::count_changes(&self.max, &other.max),
+            )
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        ::comparable::derive_support::masked_or(
+                "total",
+                None,
+                || ::comparable::Comparable::first_difference(
+                    &(|x: &Self| -> u8 { x.parts.iter().sum() })(&self),
+                    &(|x: &Self| -> u8 { x.parts.iter().sum() })(&other),
+                ),
+            )
+            .map(|path| {
+                path.within(::comparable::location::Segment::Field("total".to_string()))
+            })
+            .or_else(|| {
+                ::comparable::derive_support::masked_or(
+                        "boxed",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            &self.boxed,
+                            &other.boxed,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("boxed".to_string()),
+                            )
+                    })
+            })
+            .or_else(|| {
+                ::comparable::derive_support::masked_or(
+                        "password",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            &self.password,
+                            &other.password,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field(
+                                    "password".to_string(),
+                                ),
+                            )
+                    })
+            })
+            .or_else(|| {
+                ::comparable::derive_support::masked_or(
+                        "plugin",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            &::comparable::derive_support::by(
+                                &self.plugin,
+                                |__value| ToString::to_string(&(__value.name())),
+                                |__value| __value.id(),
+                            ),
+                            &::comparable::derive_support::by(
+                                &other.plugin,
+                                |__value| ToString::to_string(&(__value.name())),
+                                |__value| __value.id(),
+                            ),
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("plugin".to_string()),
+                            )
+                    })
+            })
+            .or_else(|| {
+                ::comparable::derive_support::masked_or(
+                        "name",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            &self.name,
+                            &other.name,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("name".to_string()),
+                            )
+                    })
+            })
+            .or_else(|| {
+                ::comparable::derive_support::masked_or(
+                        "address",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            &self.address,
+                            &other.address,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field(
+                                    "address".to_string(),
+                                ),
+                            )
+                    })
+            })
+            .or_else(|| {
+                ::comparable::derive_support::masked_or(
+                        "min",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            &self.min,
+                            &other.min,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("min".to_string()),
+                            )
+                    })
+            })
+            .or_else(|| {
+                ::comparable::derive_support::masked_or(
+                        "max",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            &self.max,
+                            &other.max,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("max".to_string()),
+                            )
+                    })
+            })
+    }
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+struct PinnedDesc {
+    name: <String as ::comparable::Comparable>::Desc,
+    version: <::comparable::Leaf<Version> as ::comparable::Comparable>::Desc,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+enum PinnedChange {
+    Name(<String as ::comparable::Comparable>::Change),
+    Version(<::comparable::Leaf<Version> as ::comparable::Comparable>::Change),
+}
+impl ::comparable::merge::MergeKey for PinnedChange {
+    fn merge_key(&self) -> String {
+        match self {
+            PinnedChange::Name(_) => "name".to_string(),
+            PinnedChange::Version(_) => "version".to_string(),
+        }
+    }
+}
+impl PinnedChange {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["name", "version"];
+}
+impl ::comparable::Comparable for Pinned {
+    type Desc = PinnedDesc;
+    fn describe(&self) -> Self::Desc {
+        PinnedDesc {
+            name: ::comparable::Comparable::describe(&self.name),
+            version: ::comparable::Comparable::describe(
+                &::comparable::derive_support::leaf::<Version>(&self.version),
+            ),
+        }
+    }
+    type Change = Vec<PinnedChange>;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        let mut changes: Vec<_> = Vec::new();
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "name",
+                        || self.name.comparison(&other.name),
+                    )
+                    .map(PinnedChange::Name),
+            );
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "version",
+                        || {
+                            ::comparable::derive_support::leaf::<Version>(&self.version)
+                                .comparison(
+                                    &::comparable::derive_support::leaf::<
+                                        Version,
+                                    >(&other.version),
+                                )
+                        },
+                    )
+                    .map(PinnedChange::Version),
+            );
+        ::comparable::Changed::from_changes(changes)
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        (::comparable::Comparable::similarity(&self.name, &other.name)
+            + ::comparable::Comparable::similarity(
+                &::comparable::derive_support::leaf::<Version>(&self.version),
+                &::comparable::derive_support::leaf::<Version>(&other.version),
+            )) / 2f64
+    }
+    fn differs(&self, other: &Self) -> bool {
+        ::comparable::derive_support::masked_or(
+            "name",
+            false,
+            || ::comparable::Comparable::differs(&self.name, &other.name),
+        )
+            || ::comparable::derive_support::masked_or(
+                "version",
+                false,
+                || ::comparable::Comparable::differs(
+                    &::comparable::derive_support::leaf::<Version>(&self.version),
+                    &::comparable::derive_support::leaf::<Version>(&other.version),
+                ),
+            )
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        ::comparable::derive_support::masked_or(
+            "name",
+            0,
+            || ::comparable::Comparable::count_changes(&self.name, &other.name),
+        )
+            + ::comparable::derive_support::masked_or(
+                "version",
+                0,
+                || ::comparable::Comparable::count_changes(
+                    &::comparable::derive_support::leaf::<Version>(&self.version),
+                    &::comparable::derive_support::leaf::<Version>(&other.version),
+                ),
+            )
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        ::comparable::derive_support::masked_or(
+                "name",
+                None,
+                || ::comparable::Comparable::first_difference(&self.name, &other.name),
+            )
+            .map(|path| {
+                path.within(::comparable::location::Segment::Field("name".to_string()))
+            })
+            .or_else(|| {
+                ::comparable::derive_support::masked_or(
+                        "version",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            &::comparable::derive_support::leaf::<
+                                Version,
+                            >(&self.version),
+                            &::comparable::derive_support::leaf::<
+                                Version,
+                            >(&other.version),
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field(
+                                    "version".to_string(),
+                                ),
+                            )
+                    })
+            })
+    }
+}
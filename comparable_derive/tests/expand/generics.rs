@@ -0,0 +1,37 @@
+#[derive(Comparable)]
+pub struct Pair<A, B> {
+	first: A,
+	second: B,
+	count: u8,
+}
+
+#[derive(Comparable)]
+struct Bounded<K: Ord + Clone + std::fmt::Debug, V>
+where
+	V: Clone,
+{
+	entries: std::collections::BTreeMap<K, V>,
+	#[comparable_include]
+	marker: std::marker::PhantomData<V>,
+}
+
+#[derive(Comparable)]
+enum Either<L, R> {
+	Left(L),
+	Right(R),
+}
+
+#[derive(Comparable)]
+#[comparable_bound(T: std::fmt::Display)]
+struct Tagged<T> {
+	#[comparable_by(desc = "self.to_string()")]
+	tag: T,
+	items: Vec<u8>,
+}
+
+#[derive(Comparable)]
+#[comparable_display]
+enum Polled<F: std::future::Future> {
+	Pending,
+	Ready(F::Output),
+}
@@ -0,0 +1,767 @@
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+pub struct PairDesc<__T0, __T1> {
+    first: __T0,
+    second: __T1,
+    count: <u8 as ::comparable::Comparable>::Desc,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+pub enum PairChange<__T0, __T1> {
+    First(__T0),
+    Second(__T1),
+    Count(<u8 as ::comparable::Comparable>::Change),
+}
+impl<__T0, __T1> ::comparable::merge::MergeKey for PairChange<__T0, __T1> {
+    fn merge_key(&self) -> String {
+        match self {
+            PairChange::First(_) => "first".to_string(),
+            PairChange::Second(_) => "second".to_string(),
+            PairChange::Count(_) => "count".to_string(),
+        }
+    }
+}
+impl<__T0, __T1> PairChange<__T0, __T1> {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["first", "second", "count"];
+}
+impl<A, B> ::comparable::Comparable for Pair<A, B>
+where
+    A: ::comparable::Comparable,
+    B: ::comparable::Comparable,
+{
+    type Desc = PairDesc<
+        <A as ::comparable::Comparable>::Desc,
+        <B as ::comparable::Comparable>::Desc,
+    >;
+    fn describe(&self) -> Self::Desc {
+        PairDesc {
+            first: ::comparable::Comparable::describe(&self.first),
+            second: ::comparable::Comparable::describe(&self.second),
+            count: ::comparable::Comparable::describe(&self.count),
+        }
+    }
+    type Change = Vec<
+        PairChange<
+            <A as ::comparable::Comparable>::Change,
+            <B as ::comparable::Comparable>::Change,
+        >,
+    >;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        let mut changes: Vec<_> = Vec::new();
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "first",
+                        || self.first.comparison(&other.first),
+                    )
+                    .map(PairChange::First),
+            );
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "second",
+                        || self.second.comparison(&other.second),
+                    )
+                    .map(PairChange::Second),
+            );
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "count",
+                        || self.count.comparison(&other.count),
+                    )
+                    .map(PairChange::Count),
+            );
+        ::comparable::Changed::from_changes(changes)
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        (::comparable::Comparable::similarity(&self.first, &other.first)
+            + ::comparable::Comparable::similarity(&self.second, &other.second)
+            + ::comparable::Comparable::similarity(&self.count, &other.count)) / 3f64
+    }
+    fn differs(&self, other: &Self) -> bool {
+        ::comparable::derive_support::masked_or(
+            "first",
+            false,
+            || ::comparable::Comparable::differs(&self.first, &other.first),
+        )
+            || ::comparable::derive_support::masked_or(
+                "second",
+                false,
+                || ::comparable::Comparable::differs(&self.second, &other.second),
+            )
+            || ::comparable::derive_support::masked_or(
+                "count",
+                false,
+                || ::comparable::Comparable::differs(&self.count, &other.count),
+            )
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        ::comparable::derive_support::masked_or(
+            "first",
+            0,
+            || ::comparable::Comparable::count_changes(&self.first, &other.first),
+        )
+            + ::comparable::derive_support::masked_or(
+                "second",
+                0,
+                || ::comparable::Comparable::count_changes(&self.second, &other.second),
+            )
+            + ::comparable::derive_support::masked_or(
+                "count",
+                0,
+                || ::comparable::Comparable::count_changes(&self.count, &other.count),
+            )
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        ::comparable::derive_support::masked_or(
+                "first",
+                None,
+                || ::comparable::Comparable::first_difference(&self.first, &other.first),
+            )
+            .map(|path| {
+                path.within(::comparable::location::Segment::Field("first".to_string()))
+            })
+            .or_else(|| {
+                ::comparable::derive_support::masked_or(
+                        "second",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            &self.second,
+                            &other.second,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("second".to_string()),
+                            )
+                    })
+            })
+            .or_else(|| {
+                ::comparable::derive_support::masked_or(
+                        "count",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            &self.count,
+                            &other.count,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("count".to_string()),
+                            )
+                    })
+            })
+    }
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+struct BoundedDesc<__T0, __T1> {
+    entries: __T0,
+    marker: __T1,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+enum BoundedChange<__T0, __T1> {
+    Entries(__T0),
+    Marker(__T1),
+}
+impl<__T0, __T1> ::comparable::merge::MergeKey for BoundedChange<__T0, __T1> {
+    fn merge_key(&self) -> String {
+        match self {
+            BoundedChange::Entries(_) => "entries".to_string(),
+            BoundedChange::Marker(_) => "marker".to_string(),
+        }
+    }
+}
+impl<__T0, __T1> BoundedChange<__T0, __T1> {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["entries", "marker"];
+}
+impl<K: Ord + Clone + std::fmt::Debug, V> ::comparable::Comparable for Bounded<K, V>
+where
+    V: Clone,
+    std::collections::BTreeMap<K, V>: ::comparable::Comparable,
+    std::marker::PhantomData<V>: ::comparable::Comparable,
+{
+    type Desc = BoundedDesc<
+        <std::collections::BTreeMap<K, V> as ::comparable::Comparable>::Desc,
+        <std::marker::PhantomData<V> as ::comparable::Comparable>::Desc,
+    >;
+    fn describe(&self) -> Self::Desc {
+        BoundedDesc {
+            entries: ::comparable::Comparable::describe(&self.entries),
+            marker: ::comparable::Comparable::describe(&self.marker),
+        }
+    }
+    type Change = Vec<
+        BoundedChange<
+            <std::collections::BTreeMap<K, V> as ::comparable::Comparable>::Change,
+            <std::marker::PhantomData<V> as ::comparable::Comparable>::Change,
+        >,
+    >;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        let mut changes: Vec<_> = Vec::new();
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "entries",
+                        || self.entries.comparison(&other.entries),
+                    )
+                    .map(BoundedChange::Entries),
+            );
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "marker",
+                        || self.marker.comparison(&other.marker),
+                    )
+                    .map(BoundedChange::Marker),
+            );
+        ::comparable::Changed::from_changes(changes)
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        (::comparable::Comparable::similarity(&self.entries, &other.entries)
+            + ::comparable::Comparable::similarity(&self.marker, &other.marker)) / 2f64
+    }
+    fn differs(&self, other: &Self) -> bool {
+        ::comparable::derive_support::masked_or(
+            "entries",
+            false,
+            || ::comparable::Comparable::differs(&self.entries, &other.entries),
+        )
+            || ::comparable::derive_support::masked_or(
+                "marker",
+                false,
+                || ::comparable::Comparable::differs(&self.marker, &other.marker),
+            )
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        ::comparable::derive_support::masked_or(
+            "entries",
+            0,
+            || ::comparable::Comparable::count_changes(&self.entries, &other.entries),
+        )
+            + ::comparable::derive_support::masked_or(
+                "marker",
+                0,
+                || ::comparable::Comparable::count_changes(&self.marker, &other.marker),
+            )
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        ::comparable::derive_support::masked_or(
+                "entries",
+                None,
+                || ::comparable::Comparable::first_difference(
+                    &self.entries,
+                    &other.entries,
+                ),
+            )
+            .map(|path| {
+                path
+                    .within(
+                        ::comparable::location::Segment::Field("entries".to_string()),
+                    )
+            })
+            .or_else(|| {
+                ::comparable::derive_support::masked_or(
+                        "marker",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            &self.marker,
+                            &other.marker,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("marker".to_string()),
+                            )
+                    })
+            })
+    }
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+enum EitherDesc<__T0, __T1> {
+    Left(__T0),
+    Right(__T1),
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+enum EitherChange<__T0, __T1, __T2, __T3> {
+    BothLeft(__T0),
+    BothRight(__T1),
+    Different(__T2, __T3),
+}
+impl<__T0, __T1, __T2, __T3> EitherChange<__T0, __T1, __T2, __T3> {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["0"];
+}
+impl<L, R> ::comparable::Comparable for Either<L, R>
+where
+    L: ::comparable::Comparable,
+    R: ::comparable::Comparable,
+{
+    type Desc = EitherDesc<
+        <L as ::comparable::Comparable>::Desc,
+        <R as ::comparable::Comparable>::Desc,
+    >;
+    fn describe(&self) -> Self::Desc {
+        match self {
+            Either::Left(var0) => {
+                EitherDesc::Left(::comparable::Comparable::describe(var0))
+            }
+            Either::Right(var0) => {
+                EitherDesc::Right(::comparable::Comparable::describe(var0))
+            }
+        }
+    }
+    type Change = EitherChange<
+        <L as ::comparable::Comparable>::Change,
+        <R as ::comparable::Comparable>::Change,
+        <Either<L, R> as ::comparable::Comparable>::Desc,
+        <Either<L, R> as ::comparable::Comparable>::Desc,
+    >;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        match (self, other) {
+            (Either::Left(self_var0), Either::Left(other_var0)) => {
+                let changes_var0 = ::comparable::derive_support::masked(
+                    "0",
+                    || self_var0.comparison(&other_var0),
+                );
+                changes_var0.map(|changes_var0| EitherChange::BothLeft(changes_var0))
+            }
+            (Either::Right(self_var0), Either::Right(other_var0)) => {
+                let changes_var0 = ::comparable::derive_support::masked(
+                    "0",
+                    || self_var0.comparison(&other_var0),
+                );
+                changes_var0.map(|changes_var0| EitherChange::BothRight(changes_var0))
+            }
+            (_, _) => {
+                ::comparable::Changed::Changed(
+                    EitherChange::Different(
+                        ::comparable::Comparable::describe(self),
+                        ::comparable::Comparable::describe(other),
+                    ),
+                )
+            }
+        }
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        match (self, other) {
+            (Self::Left(self_var0), Self::Left(other_var0)) => {
+                ::comparable::Comparable::similarity(self_var0, other_var0)
+            }
+            (Self::Right(self_var0), Self::Right(other_var0)) => {
+                ::comparable::Comparable::similarity(self_var0, other_var0)
+            }
+            _ => 0.0,
+        }
+    }
+    fn differs(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Left(self_var0), Self::Left(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    false,
+                    || ::comparable::Comparable::differs(self_var0, other_var0),
+                )
+            }
+            (Self::Right(self_var0), Self::Right(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    false,
+                    || ::comparable::Comparable::differs(self_var0, other_var0),
+                )
+            }
+            _ => true,
+        }
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        match (self, other) {
+            (Self::Left(self_var0), Self::Left(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    0,
+                    || ::comparable::Comparable::count_changes(self_var0, other_var0),
+                )
+            }
+            (Self::Right(self_var0), Self::Right(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    0,
+                    || ::comparable::Comparable::count_changes(self_var0, other_var0),
+                )
+            }
+            _ => 1,
+        }
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        match (self, other) {
+            (Self::Left(self_var0), Self::Left(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                        "0",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            self_var0,
+                            other_var0,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("0".to_string()),
+                            )
+                    })
+            }
+            (Self::Right(self_var0), Self::Right(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                        "0",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            self_var0,
+                            other_var0,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("0".to_string()),
+                            )
+                    })
+            }
+            _ => Some(::comparable::location::Path::default()),
+        }
+    }
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+struct TaggedDesc {
+    tag: String,
+    items: <Vec<u8> as ::comparable::Comparable>::Desc,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+enum TaggedChange {
+    Tag(::comparable::StringChange),
+    Items(<Vec<u8> as ::comparable::Comparable>::Change),
+}
+impl ::comparable::merge::MergeKey for TaggedChange {
+    fn merge_key(&self) -> String {
+        match self {
+            TaggedChange::Tag(_) => "tag".to_string(),
+            TaggedChange::Items(_) => "items".to_string(),
+        }
+    }
+}
+impl TaggedChange {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["tag", "items"];
+}
+impl<T> ::comparable::Comparable for Tagged<T>
+where
+    T: std::fmt::Display,
+{
+    type Desc = TaggedDesc;
+    fn describe(&self) -> Self::Desc {
+        TaggedDesc {
+            tag: ::comparable::Comparable::describe(
+                &::comparable::derive_support::by(
+                    &self.tag,
+                    |__value| ToString::to_string(&(__value.to_string())),
+                    |__value| ToString::to_string(&(__value.to_string())),
+                ),
+            ),
+            items: ::comparable::Comparable::describe(&self.items),
+        }
+    }
+    type Change = Vec<TaggedChange>;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        let mut changes: Vec<_> = Vec::new();
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "tag",
+                        || {
+                            ::comparable::derive_support::by(
+                                    &self.tag,
+                                    |__value| ToString::to_string(&(__value.to_string())),
+                                    |__value| ToString::to_string(&(__value.to_string())),
+                                )
+                                .comparison(
+                                    &::comparable::derive_support::by(
+                                        &other.tag,
+                                        |__value| ToString::to_string(&(__value.to_string())),
+                                        |__value| ToString::to_string(&(__value.to_string())),
+                                    ),
+                                )
+                        },
+                    )
+                    .map(TaggedChange::Tag),
+            );
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "items",
+                        || self.items.comparison(&other.items),
+                    )
+                    .map(TaggedChange::Items),
+            );
+        ::comparable::Changed::from_changes(changes)
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        (::comparable::Comparable::similarity(
+            &::comparable::derive_support::by(
+                &self.tag,
+                |__value| ToString::to_string(&(__value.to_string())),
+                |__value| ToString::to_string(&(__value.to_string())),
+            ),
+            &::comparable::derive_support::by(
+                &other.tag,
+                |__value| ToString::to_string(&(__value.to_string())),
+                |__value| ToString::to_string(&(__value.to_string())),
+            ),
+        ) + ::comparable::Comparable::similarity(&self.items, &other.items)) / 2f64
+    }
+    fn differs(&self, other: &Self) -> bool {
+        ::comparable::derive_support::masked_or(
+            "tag",
+            false,
+            || ::comparable::Comparable::differs(
+                &::comparable::derive_support::by(
+                    &self.tag,
+                    |__value| ToString::to_string(&(__value.to_string())),
+                    |__value| ToString::to_string(&(__value.to_string())),
+                ),
+                &::comparable::derive_support::by(
+                    &other.tag,
+                    |__value| ToString::to_string(&(__value.to_string())),
+                    |__value| ToString::to_string(&(__value.to_string())),
+                ),
+            ),
+        )
+            || ::comparable::derive_support::masked_or(
+                "items",
+                false,
+                || ::comparable::Comparable::differs(&self.items, &other.items),
+            )
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        ::comparable::derive_support::masked_or(
+            "tag",
+            0,
+            || ::comparable::Comparable::count_changes(
+                &::comparable::derive_support::by(
+                    &self.tag,
+                    |__value| ToString::to_string(&(__value.to_string())),
+                    |__value| ToString::to_string(&(__value.to_string())),
+                ),
+                &::comparable::derive_support::by(
+                    &other.tag,
+                    |__value| ToString::to_string(&(__value.to_string())),
+                    |__value| ToString::to_string(&(__value.to_string())),
+                ),
+            ),
+        )
+            + ::comparable::derive_support::masked_or(
+                "items",
+                0,
+                || ::comparable::Comparable::count_changes(&self.items, &other.items),
+            )
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        ::comparable::derive_support::masked_or(
+                "tag",
+                None,
+                || ::comparable::Comparable::first_difference(
+                    &::comparable::derive_support::by(
+                        &self.tag,
+                        |__value| ToString::to_string(&(__value.to_string())),
+                        |__value| ToString::to_string(&(__value.to_string())),
+                    ),
+                    &::comparable::derive_support::by(
+                        &other.tag,
+                        |__value| ToString::to_string(&(__value.to_string())),
+                        |__value| ToString::to_string(&(__value.to_string())),
+                    ),
+                ),
+            )
+            .map(|path| {
+                path.within(::comparable::location::Segment::Field("tag".to_string()))
+            })
+            .or_else(|| {
+                ::comparable::derive_support::masked_or(
+                        "items",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            &self.items,
+                            &other.items,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("items".to_string()),
+                            )
+                    })
+            })
+    }
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+enum PolledDesc<__T0> {
+    Pending,
+    Ready(__T0),
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+enum PolledChange<__T0, __T1, __T2> {
+    BothReady(__T0),
+    Different(__T1, __T2),
+}
+impl<__T0, __T1, __T2> PolledChange<__T0, __T1, __T2> {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["0"];
+}
+impl<__T0, __T1, __T2> ::comparable::display::DisplayChange
+for PolledChange<__T0, __T1, __T2>
+where
+    __T0: ::comparable::display::DisplayChange,
+    __T1: std::fmt::Debug,
+    __T2: std::fmt::Debug,
+{
+    fn display_changes(
+        &self,
+        path: &::comparable::location::Path,
+        lines: &mut Vec<String>,
+    ) {
+        match self {
+            PolledChange::BothReady(change0) => {
+                ::comparable::display::DisplayChange::display_changes(
+                    change0,
+                    &path.child(::comparable::location::Segment::Field("0".to_string())),
+                    lines,
+                );
+            }
+            PolledChange::Different(change0, change1) => {
+                ::comparable::display::push_replaced(lines, path, change0, change1)
+            }
+        }
+    }
+}
+impl<__T0, __T1, __T2> std::fmt::Display for PolledChange<__T0, __T1, __T2>
+where
+    __T0: ::comparable::display::DisplayChange,
+    __T1: std::fmt::Debug,
+    __T2: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&::comparable::display::display(self), f)
+    }
+}
+impl<F: std::future::Future> ::comparable::Comparable for Polled<F>
+where
+    F::Output: ::comparable::Comparable,
+{
+    type Desc = PolledDesc<<F::Output as ::comparable::Comparable>::Desc>;
+    fn describe(&self) -> Self::Desc {
+        match self {
+            Polled::Pending => PolledDesc::Pending,
+            Polled::Ready(var0) => {
+                PolledDesc::Ready(::comparable::Comparable::describe(var0))
+            }
+        }
+    }
+    type Change = PolledChange<
+        <F::Output as ::comparable::Comparable>::Change,
+        <Polled<F> as ::comparable::Comparable>::Desc,
+        <Polled<F> as ::comparable::Comparable>::Desc,
+    >;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        match (self, other) {
+            (Polled::Pending, Polled::Pending) => ::comparable::Changed::Unchanged,
+            (Polled::Ready(self_var0), Polled::Ready(other_var0)) => {
+                let changes_var0 = ::comparable::derive_support::masked(
+                    "0",
+                    || self_var0.comparison(&other_var0),
+                );
+                changes_var0.map(|changes_var0| PolledChange::BothReady(changes_var0))
+            }
+            (_, _) => {
+                ::comparable::Changed::Changed(
+                    PolledChange::Different(
+                        ::comparable::Comparable::describe(self),
+                        ::comparable::Comparable::describe(other),
+                    ),
+                )
+            }
+        }
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        match (self, other) {
+            (Self::Pending, Self::Pending) => 1.0,
+            (Self::Ready(self_var0), Self::Ready(other_var0)) => {
+                ::comparable::Comparable::similarity(self_var0, other_var0)
+            }
+            _ => 0.0,
+        }
+    }
+    fn differs(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Pending, Self::Pending) => false,
+            (Self::Ready(self_var0), Self::Ready(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    false,
+                    || ::comparable::Comparable::differs(self_var0, other_var0),
+                )
+            }
+            _ => true,
+        }
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        match (self, other) {
+            (Self::Pending, Self::Pending) => 0,
+            (Self::Ready(self_var0), Self::Ready(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                    "0",
+                    0,
+                    || ::comparable::Comparable::count_changes(self_var0, other_var0),
+                )
+            }
+            _ => 1,
+        }
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        match (self, other) {
+            (Self::Pending, Self::Pending) => None,
+            (Self::Ready(self_var0), Self::Ready(other_var0)) => {
+                ::comparable::derive_support::masked_or(
+                        "0",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            self_var0,
+                            other_var0,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("0".to_string()),
+                            )
+                    })
+            }
+            _ => Some(::comparable::location::Path::default()),
+        }
+    }
+}
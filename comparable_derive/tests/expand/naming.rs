@@ -0,0 +1,33 @@
+#[derive(Comparable)]
+#[comparable_public]
+#[comparable_desc_suffix(Before)]
+#[comparable_change_suffix(Mutated)]
+struct Public {
+	value: u8,
+	other: u8,
+}
+
+#[derive(Comparable)]
+#[comparable_private]
+pub struct Private {
+	value: u8,
+	other: u8,
+}
+
+#[derive(Comparable)]
+#[comparable_module = "point_changes"]
+pub struct Point {
+	x: i32,
+	y: i32,
+}
+
+#[derive(Comparable)]
+#[comparable_crate = "crate::facade::inner"]
+struct Reexported {
+	x: i32,
+	y: i32,
+}
+
+#[derive(Comparable)]
+#[comparable_transparent]
+pub struct UserId(u64);
@@ -0,0 +1,472 @@
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+pub struct PublicBefore {
+    value: <u8 as ::comparable::Comparable>::Desc,
+    other: <u8 as ::comparable::Comparable>::Desc,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+pub enum PublicMutated {
+    Value(<u8 as ::comparable::Comparable>::Change),
+    Other(<u8 as ::comparable::Comparable>::Change),
+}
+impl ::comparable::merge::MergeKey for PublicMutated {
+    fn merge_key(&self) -> String {
+        match self {
+            PublicMutated::Value(_) => "value".to_string(),
+            PublicMutated::Other(_) => "other".to_string(),
+        }
+    }
+}
+impl PublicMutated {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["value", "other"];
+}
+impl ::comparable::Comparable for Public {
+    type Desc = PublicBefore;
+    fn describe(&self) -> Self::Desc {
+        PublicBefore {
+            value: ::comparable::Comparable::describe(&self.value),
+            other: ::comparable::Comparable::describe(&self.other),
+        }
+    }
+    type Change = Vec<PublicMutated>;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        let mut changes: Vec<_> = Vec::new();
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "value",
+                        || self.value.comparison(&other.value),
+                    )
+                    .map(PublicMutated::Value),
+            );
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "other",
+                        || self.other.comparison(&other.other),
+                    )
+                    .map(PublicMutated::Other),
+            );
+        ::comparable::Changed::from_changes(changes)
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        (::comparable::Comparable::similarity(&self.value, &other.value)
+            + ::comparable::Comparable::similarity(&self.other, &other.other)) / 2f64
+    }
+    fn differs(&self, other: &Self) -> bool {
+        ::comparable::derive_support::masked_or(
+            "value",
+            false,
+            || ::comparable::Comparable::differs(&self.value, &other.value),
+        )
+            || ::comparable::derive_support::masked_or(
+                "other",
+                false,
+                || ::comparable::Comparable::differs(&self.other, &other.other),
+            )
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        ::comparable::derive_support::masked_or(
+            "value",
+            0,
+            || ::comparable::Comparable::count_changes(&self.value, &other.value),
+        )
+            + ::comparable::derive_support::masked_or(
+                "other",
+                0,
+                || ::comparable::Comparable::count_changes(&self.other, &other.other),
+            )
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        ::comparable::derive_support::masked_or(
+                "value",
+                None,
+                || ::comparable::Comparable::first_difference(&self.value, &other.value),
+            )
+            .map(|path| {
+                path.within(::comparable::location::Segment::Field("value".to_string()))
+            })
+            .or_else(|| {
+                ::comparable::derive_support::masked_or(
+                        "other",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            &self.other,
+                            &other.other,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("other".to_string()),
+                            )
+                    })
+            })
+    }
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+struct PrivateDesc {
+    value: <u8 as ::comparable::Comparable>::Desc,
+    other: <u8 as ::comparable::Comparable>::Desc,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+enum PrivateChange {
+    Value(<u8 as ::comparable::Comparable>::Change),
+    Other(<u8 as ::comparable::Comparable>::Change),
+}
+impl ::comparable::merge::MergeKey for PrivateChange {
+    fn merge_key(&self) -> String {
+        match self {
+            PrivateChange::Value(_) => "value".to_string(),
+            PrivateChange::Other(_) => "other".to_string(),
+        }
+    }
+}
+impl PrivateChange {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["value", "other"];
+}
+impl ::comparable::Comparable for Private {
+    type Desc = PrivateDesc;
+    fn describe(&self) -> Self::Desc {
+        PrivateDesc {
+            value: ::comparable::Comparable::describe(&self.value),
+            other: ::comparable::Comparable::describe(&self.other),
+        }
+    }
+    type Change = Vec<PrivateChange>;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        let mut changes: Vec<_> = Vec::new();
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "value",
+                        || self.value.comparison(&other.value),
+                    )
+                    .map(PrivateChange::Value),
+            );
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "other",
+                        || self.other.comparison(&other.other),
+                    )
+                    .map(PrivateChange::Other),
+            );
+        ::comparable::Changed::from_changes(changes)
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        (::comparable::Comparable::similarity(&self.value, &other.value)
+            + ::comparable::Comparable::similarity(&self.other, &other.other)) / 2f64
+    }
+    fn differs(&self, other: &Self) -> bool {
+        ::comparable::derive_support::masked_or(
+            "value",
+            false,
+            || ::comparable::Comparable::differs(&self.value, &other.value),
+        )
+            || ::comparable::derive_support::masked_or(
+                "other",
+                false,
+                || ::comparable::Comparable::differs(&self.other, &other.other),
+            )
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        ::comparable::derive_support::masked_or(
+            "value",
+            0,
+            || ::comparable::Comparable::count_changes(&self.value, &other.value),
+        )
+            + ::comparable::derive_support::masked_or(
+                "other",
+                0,
+                || ::comparable::Comparable::count_changes(&self.other, &other.other),
+            )
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        ::comparable::derive_support::masked_or(
+                "value",
+                None,
+                || ::comparable::Comparable::first_difference(&self.value, &other.value),
+            )
+            .map(|path| {
+                path.within(::comparable::location::Segment::Field("value".to_string()))
+            })
+            .or_else(|| {
+                ::comparable::derive_support::masked_or(
+                        "other",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            &self.other,
+                            &other.other,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("other".to_string()),
+                            )
+                    })
+            })
+    }
+}
+pub mod point_changes {
+    use super::*;
+    #[derive(serde::Serialize, serde::Deserialize)]
+    #[derive(PartialEq, Debug)]
+    pub struct PointDesc {
+        pub(super) x: <i32 as ::comparable::Comparable>::Desc,
+        pub(super) y: <i32 as ::comparable::Comparable>::Desc,
+    }
+    #[derive(serde::Serialize, serde::Deserialize)]
+    #[derive(PartialEq, Debug)]
+    pub enum PointChange {
+        X(<i32 as ::comparable::Comparable>::Change),
+        Y(<i32 as ::comparable::Comparable>::Change),
+    }
+    impl ::comparable::merge::MergeKey for PointChange {
+        fn merge_key(&self) -> String {
+            match self {
+                PointChange::X(_) => "x".to_string(),
+                PointChange::Y(_) => "y".to_string(),
+            }
+        }
+    }
+    impl PointChange {
+        /// The names of the fields compared, as written in a
+        /// [`FieldMask`](comparable::mask::FieldMask).
+        pub const FIELDS: &'static [&'static str] = &["x", "y"];
+    }
+    impl ::comparable::Comparable for Point {
+        type Desc = PointDesc;
+        fn describe(&self) -> Self::Desc {
+            PointDesc {
+                x: ::comparable::Comparable::describe(&self.x),
+                y: ::comparable::Comparable::describe(&self.y),
+            }
+        }
+        type Change = Vec<PointChange>;
+        fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+            let mut changes: Vec<_> = Vec::new();
+            changes
+                .extend(
+                    ::comparable::derive_support::masked(
+                            "x",
+                            || self.x.comparison(&other.x),
+                        )
+                        .map(PointChange::X),
+                );
+            changes
+                .extend(
+                    ::comparable::derive_support::masked(
+                            "y",
+                            || self.y.comparison(&other.y),
+                        )
+                        .map(PointChange::Y),
+                );
+            ::comparable::Changed::from_changes(changes)
+        }
+        fn similarity(&self, other: &Self) -> f64 {
+            (::comparable::Comparable::similarity(&self.x, &other.x)
+                + ::comparable::Comparable::similarity(&self.y, &other.y)) / 2f64
+        }
+        fn differs(&self, other: &Self) -> bool {
+            ::comparable::derive_support::masked_or(
+                "x",
+                false,
+                || ::comparable::Comparable::differs(&self.x, &other.x),
+            )
+                || ::comparable::derive_support::masked_or(
+                    "y",
+                    false,
+                    || ::comparable::Comparable::differs(&self.y, &other.y),
+                )
+        }
+        fn count_changes(&self, other: &Self) -> usize {
+            ::comparable::derive_support::masked_or(
+                "x",
+                0,
+                || ::comparable::Comparable::count_changes(&self.x, &other.x),
+            )
+                + ::comparable::derive_support::masked_or(
+                    "y",
+                    0,
+                    || ::comparable::Comparable::count_changes(&self.y, &other.y),
+                )
+        }
+        fn first_difference(
+            &self,
+            other: &Self,
+        ) -> Option<::comparable::location::Path> {
+            ::comparable::derive_support::masked_or(
+                    "x",
+                    None,
+                    || ::comparable::Comparable::first_difference(&self.x, &other.x),
+                )
+                .map(|path| {
+                    path.within(::comparable::location::Segment::Field("x".to_string()))
+                })
+                .or_else(|| {
+                    ::comparable::derive_support::masked_or(
+                            "y",
+                            None,
+                            || ::comparable::Comparable::first_difference(
+                                &self.y,
+                                &other.y,
+                            ),
+                        )
+                        .map(|path| {
+                            path
+                                .within(
+                                    ::comparable::location::Segment::Field("y".to_string()),
+                                )
+                        })
+                })
+        }
+    }
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+struct ReexportedDesc {
+    x: <i32 as crate::facade::inner::Comparable>::Desc,
+    y: <i32 as crate::facade::inner::Comparable>::Desc,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+enum ReexportedChange {
+    X(<i32 as crate::facade::inner::Comparable>::Change),
+    Y(<i32 as crate::facade::inner::Comparable>::Change),
+}
+impl crate::facade::inner::merge::MergeKey for ReexportedChange {
+    fn merge_key(&self) -> String {
+        match self {
+            ReexportedChange::X(_) => "x".to_string(),
+            ReexportedChange::Y(_) => "y".to_string(),
+        }
+    }
+}
+impl ReexportedChange {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["x", "y"];
+}
+impl crate::facade::inner::Comparable for Reexported {
+    type Desc = ReexportedDesc;
+    fn describe(&self) -> Self::Desc {
+        ReexportedDesc {
+            x: crate::facade::inner::Comparable::describe(&self.x),
+            y: crate::facade::inner::Comparable::describe(&self.y),
+        }
+    }
+    type Change = Vec<ReexportedChange>;
+    fn comparison(&self, other: &Self) -> crate::facade::inner::Changed<Self::Change> {
+        let mut changes: Vec<_> = Vec::new();
+        changes
+            .extend(
+                crate::facade::inner::derive_support::masked(
+                        "x",
+                        || self.x.comparison(&other.x),
+                    )
+                    .map(ReexportedChange::X),
+            );
+        changes
+            .extend(
+                crate::facade::inner::derive_support::masked(
+                        "y",
+                        || self.y.comparison(&other.y),
+                    )
+                    .map(ReexportedChange::Y),
+            );
+        crate::facade::inner::Changed::from_changes(changes)
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        (crate::facade::inner::Comparable::similarity(&self.x, &other.x)
+            + crate::facade::inner::Comparable::similarity(&self.y, &other.y)) / 2f64
+    }
+    fn differs(&self, other: &Self) -> bool {
+        crate::facade::inner::derive_support::masked_or(
+            "x",
+            false,
+            || crate::facade::inner::Comparable::differs(&self.x, &other.x),
+        )
+            || crate::facade::inner::derive_support::masked_or(
+                "y",
+                false,
+                || crate::facade::inner::Comparable::differs(&self.y, &other.y),
+            )
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        crate::facade::inner::derive_support::masked_or(
+            "x",
+            0,
+            || crate::facade::inner::Comparable::count_changes(&self.x, &other.x),
+        )
+            + crate::facade::inner::derive_support::masked_or(
+                "y",
+                0,
+                || crate::facade::inner::Comparable::count_changes(&self.y, &other.y),
+            )
+    }
+    fn first_difference(
+        &self,
+        other: &Self,
+    ) -> Option<crate::facade::inner::location::Path> {
+        crate::facade::inner::derive_support::masked_or(
+                "x",
+                None,
+                || crate::facade::inner::Comparable::first_difference(&self.x, &other.x),
+            )
+            .map(|path| {
+                path
+                    .within(
+                        crate::facade::inner::location::Segment::Field("x".to_string()),
+                    )
+            })
+            .or_else(|| {
+                crate::facade::inner::derive_support::masked_or(
+                        "y",
+                        None,
+                        || crate::facade::inner::Comparable::first_difference(
+                            &self.y,
+                            &other.y,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                crate::facade::inner::location::Segment::Field(
+                                    "y".to_string(),
+                                ),
+                            )
+                    })
+            })
+    }
+}
+impl ::comparable::Comparable for UserId {
+    type Desc = <u64 as ::comparable::Comparable>::Desc;
+    fn describe(&self) -> Self::Desc {
+        ::comparable::Comparable::describe(&self.0)
+    }
+    type Change = <u64 as ::comparable::Comparable>::Change;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        self.0.comparison(&other.0)
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        ::comparable::Comparable::similarity(&self.0, &other.0)
+    }
+    fn differs(&self, other: &Self) -> bool {
+        ::comparable::Comparable::differs(&self.0, &other.0)
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        ::comparable::Comparable::count_changes(&self.0, &other.0)
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        ::comparable::Comparable::first_difference(&self.0, &other.0)
+    }
+}
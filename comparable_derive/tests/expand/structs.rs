@@ -0,0 +1,14 @@
+#[derive(Comparable)]
+struct Unit;
+
+#[derive(Comparable)]
+struct Single {
+	value: u8,
+}
+
+#[derive(Comparable)]
+pub struct Account {
+	pub owner: String,
+	pub balance: i64,
+	tags: Vec<String>,
+}
@@ -0,0 +1,243 @@
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+struct UnitDesc;
+impl ::comparable::Comparable for Unit {
+    type Desc = UnitDesc;
+    fn describe(&self) -> Self::Desc {
+        UnitDesc
+    }
+    type Change = ();
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        ::comparable::Changed::Unchanged
+    }
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+struct SingleDesc {
+    value: <u8 as ::comparable::Comparable>::Desc,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+struct SingleChange {
+    value: <u8 as ::comparable::Comparable>::Change,
+}
+impl SingleChange {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["value"];
+}
+impl SingleChange {
+    /// The change type holding the changes of the only field
+    /// compared.
+    fn new(change: <u8 as ::comparable::Comparable>::Change) -> Self {
+        SingleChange { value: change }
+    }
+    /// The changes of the only field compared.
+    fn into_inner(self) -> <u8 as ::comparable::Comparable>::Change {
+        self.value
+    }
+}
+impl std::ops::Deref for SingleChange {
+    type Target = <u8 as ::comparable::Comparable>::Change;
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+impl std::ops::DerefMut for SingleChange {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+impl ::comparable::Comparable for Single {
+    type Desc = SingleDesc;
+    fn describe(&self) -> Self::Desc {
+        SingleDesc {
+            value: ::comparable::Comparable::describe(&self.value),
+        }
+    }
+    type Change = SingleChange;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        ::comparable::derive_support::masked(
+                "value",
+                || self.value.comparison(&other.value),
+            )
+            .map(|x| SingleChange { value: x })
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        ::comparable::Comparable::similarity(&self.value, &other.value)
+    }
+    fn differs(&self, other: &Self) -> bool {
+        ::comparable::derive_support::masked_or(
+            "value",
+            false,
+            || ::comparable::Comparable::differs(&self.value, &other.value),
+        )
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        ::comparable::derive_support::masked_or(
+            "value",
+            0,
+            || ::comparable::Comparable::count_changes(&self.value, &other.value),
+        )
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        ::comparable::derive_support::masked_or(
+                "value",
+                None,
+                || ::comparable::Comparable::first_difference(&self.value, &other.value),
+            )
+            .map(|path| {
+                path.within(::comparable::location::Segment::Field("value".to_string()))
+            })
+    }
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+pub struct AccountDesc {
+    pub owner: <String as ::comparable::Comparable>::Desc,
+    pub balance: <i64 as ::comparable::Comparable>::Desc,
+    tags: <Vec<String> as ::comparable::Comparable>::Desc,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Debug)]
+pub enum AccountChange {
+    Owner(<String as ::comparable::Comparable>::Change),
+    Balance(<i64 as ::comparable::Comparable>::Change),
+    Tags(<Vec<String> as ::comparable::Comparable>::Change),
+}
+impl ::comparable::merge::MergeKey for AccountChange {
+    fn merge_key(&self) -> String {
+        match self {
+            AccountChange::Owner(_) => "owner".to_string(),
+            AccountChange::Balance(_) => "balance".to_string(),
+            AccountChange::Tags(_) => "tags".to_string(),
+        }
+    }
+}
+impl AccountChange {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["owner", "balance", "tags"];
+}
+impl ::comparable::Comparable for Account {
+    type Desc = AccountDesc;
+    fn describe(&self) -> Self::Desc {
+        AccountDesc {
+            owner: ::comparable::Comparable::describe(&self.owner),
+            balance: ::comparable::Comparable::describe(&self.balance),
+            tags: ::comparable::Comparable::describe(&self.tags),
+        }
+    }
+    type Change = Vec<AccountChange>;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        let mut changes: Vec<_> = Vec::new();
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "owner",
+                        || self.owner.comparison(&other.owner),
+                    )
+                    .map(AccountChange::Owner),
+            );
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "balance",
+                        || self.balance.comparison(&other.balance),
+                    )
+                    .map(AccountChange::Balance),
+            );
+        changes
+            .extend(
+                ::comparable::derive_support::masked(
+                        "tags",
+                        || self.tags.comparison(&other.tags),
+                    )
+                    .map(AccountChange::Tags),
+            );
+        ::comparable::Changed::from_changes(changes)
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        (::comparable::Comparable::similarity(&self.owner, &other.owner)
+            + ::comparable::Comparable::similarity(&self.balance, &other.balance)
+            + ::comparable::Comparable::similarity(&self.tags, &other.tags)) / 3f64
+    }
+    fn differs(&self, other: &Self) -> bool {
+        ::comparable::derive_support::masked_or(
+            "owner",
+            false,
+            || ::comparable::Comparable::differs(&self.owner, &other.owner),
+        )
+            || ::comparable::derive_support::masked_or(
+                "balance",
+                false,
+                || ::comparable::Comparable::differs(&self.balance, &other.balance),
+            )
+            || ::comparable::derive_support::masked_or(
+                "tags",
+                false,
+                || ::comparable::Comparable::differs(&self.tags, &other.tags),
+            )
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        ::comparable::derive_support::masked_or(
+            "owner",
+            0,
+            || ::comparable::Comparable::count_changes(&self.owner, &other.owner),
+        )
+            + ::comparable::derive_support::masked_or(
+                "balance",
+                0,
+                || ::comparable::Comparable::count_changes(&self.balance, &other.balance),
+            )
+            + ::comparable::derive_support::masked_or(
+                "tags",
+                0,
+                || ::comparable::Comparable::count_changes(&self.tags, &other.tags),
+            )
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        ::comparable::derive_support::masked_or(
+                "owner",
+                None,
+                || ::comparable::Comparable::first_difference(&self.owner, &other.owner),
+            )
+            .map(|path| {
+                path.within(::comparable::location::Segment::Field("owner".to_string()))
+            })
+            .or_else(|| {
+                ::comparable::derive_support::masked_or(
+                        "balance",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            &self.balance,
+                            &other.balance,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field(
+                                    "balance".to_string(),
+                                ),
+                            )
+                    })
+            })
+            .or_else(|| {
+                ::comparable::derive_support::masked_or(
+                        "tags",
+                        None,
+                        || ::comparable::Comparable::first_difference(
+                            &self.tags,
+                            &other.tags,
+                        ),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("tags".to_string()),
+                            )
+                    })
+            })
+    }
+}
@@ -0,0 +1,265 @@
+#[derive(PartialEq, Debug)]
+struct MetersDesc(<f64 as ::comparable::Comparable>::Desc);
+#[derive(PartialEq, Debug)]
+struct MetersChange(<f64 as ::comparable::Comparable>::Change);
+impl MetersChange {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["0"];
+}
+impl MetersChange {
+    /// The change type holding the changes of the only field
+    /// compared.
+    fn new(change: <f64 as ::comparable::Comparable>::Change) -> Self {
+        MetersChange { 0: change }
+    }
+    /// The changes of the only field compared.
+    fn into_inner(self) -> <f64 as ::comparable::Comparable>::Change {
+        self.0
+    }
+}
+impl std::ops::Deref for MetersChange {
+    type Target = <f64 as ::comparable::Comparable>::Change;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl std::ops::DerefMut for MetersChange {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl ::comparable::Comparable for Meters {
+    type Desc = MetersDesc;
+    fn describe(&self) -> Self::Desc {
+        MetersDesc(::comparable::Comparable::describe(&self.0))
+    }
+    type Change = MetersChange;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        ::comparable::derive_support::masked("0", || self.0.comparison(&other.0))
+            .map(MetersChange)
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        ::comparable::Comparable::similarity(&self.0, &other.0)
+    }
+    fn differs(&self, other: &Self) -> bool {
+        ::comparable::derive_support::masked_or(
+            "0",
+            false,
+            || ::comparable::Comparable::differs(&self.0, &other.0),
+        )
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        ::comparable::derive_support::masked_or(
+            "0",
+            0,
+            || ::comparable::Comparable::count_changes(&self.0, &other.0),
+        )
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        ::comparable::derive_support::masked_or(
+                "0",
+                None,
+                || ::comparable::Comparable::first_difference(&self.0, &other.0),
+            )
+            .map(|path| {
+                path.within(::comparable::location::Segment::Field("0".to_string()))
+            })
+    }
+}
+#[derive(PartialEq, Debug)]
+pub struct PointDesc(
+    pub <i32 as ::comparable::Comparable>::Desc,
+    pub <i32 as ::comparable::Comparable>::Desc,
+);
+#[derive(PartialEq, Debug)]
+pub enum PointChange {
+    Field0(<i32 as ::comparable::Comparable>::Change),
+    Field1(<i32 as ::comparable::Comparable>::Change),
+}
+impl ::comparable::merge::MergeKey for PointChange {
+    fn merge_key(&self) -> String {
+        match self {
+            PointChange::Field0(_) => "0".to_string(),
+            PointChange::Field1(_) => "1".to_string(),
+        }
+    }
+}
+impl PointChange {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["0", "1"];
+}
+impl ::comparable::Comparable for Point {
+    type Desc = PointDesc;
+    fn describe(&self) -> Self::Desc {
+        PointDesc(
+            ::comparable::Comparable::describe(&self.0),
+            ::comparable::Comparable::describe(&self.1),
+        )
+    }
+    type Change = Vec<PointChange>;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        let mut changes: Vec<_> = Vec::new();
+        changes
+            .extend(
+                ::comparable::derive_support::masked("0", || self.0.comparison(&other.0))
+                    .map(PointChange::Field0),
+            );
+        changes
+            .extend(
+                ::comparable::derive_support::masked("1", || self.1.comparison(&other.1))
+                    .map(PointChange::Field1),
+            );
+        ::comparable::Changed::from_changes(changes)
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        (::comparable::Comparable::similarity(&self.0, &other.0)
+            + ::comparable::Comparable::similarity(&self.1, &other.1)) / 2f64
+    }
+    fn differs(&self, other: &Self) -> bool {
+        ::comparable::derive_support::masked_or(
+            "0",
+            false,
+            || ::comparable::Comparable::differs(&self.0, &other.0),
+        )
+            || ::comparable::derive_support::masked_or(
+                "1",
+                false,
+                || ::comparable::Comparable::differs(&self.1, &other.1),
+            )
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        ::comparable::derive_support::masked_or(
+            "0",
+            0,
+            || ::comparable::Comparable::count_changes(&self.0, &other.0),
+        )
+            + ::comparable::derive_support::masked_or(
+                "1",
+                0,
+                || ::comparable::Comparable::count_changes(&self.1, &other.1),
+            )
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        ::comparable::derive_support::masked_or(
+                "0",
+                None,
+                || ::comparable::Comparable::first_difference(&self.0, &other.0),
+            )
+            .map(|path| {
+                path.within(::comparable::location::Segment::Field("0".to_string()))
+            })
+            .or_else(|| {
+                ::comparable::derive_support::masked_or(
+                        "1",
+                        None,
+                        || ::comparable::Comparable::first_difference(&self.1, &other.1),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("1".to_string()),
+                            )
+                    })
+            })
+    }
+}
+#[derive(PartialEq, Debug)]
+struct LabelledDesc(
+    <u8 as ::comparable::Comparable>::Desc,
+    <String as ::comparable::Comparable>::Desc,
+);
+#[derive(PartialEq, Debug)]
+enum LabelledChange {
+    Field0(<u8 as ::comparable::Comparable>::Change),
+    Field2(<String as ::comparable::Comparable>::Change),
+}
+impl ::comparable::merge::MergeKey for LabelledChange {
+    fn merge_key(&self) -> String {
+        match self {
+            LabelledChange::Field0(_) => "0".to_string(),
+            LabelledChange::Field2(_) => "2".to_string(),
+        }
+    }
+}
+impl LabelledChange {
+    /// The names of the fields compared, as written in a
+    /// [`FieldMask`](comparable::mask::FieldMask).
+    pub const FIELDS: &'static [&'static str] = &["0", "2"];
+}
+impl ::comparable::Comparable for Labelled {
+    type Desc = LabelledDesc;
+    fn describe(&self) -> Self::Desc {
+        LabelledDesc(
+            ::comparable::Comparable::describe(&self.0),
+            ::comparable::Comparable::describe(&self.2),
+        )
+    }
+    type Change = Vec<LabelledChange>;
+    fn comparison(&self, other: &Self) -> ::comparable::Changed<Self::Change> {
+        let mut changes: Vec<_> = Vec::new();
+        changes
+            .extend(
+                ::comparable::derive_support::masked("0", || self.0.comparison(&other.0))
+                    .map(LabelledChange::Field0),
+            );
+        changes
+            .extend(
+                ::comparable::derive_support::masked("2", || self.2.comparison(&other.2))
+                    .map(LabelledChange::Field2),
+            );
+        ::comparable::Changed::from_changes(changes)
+    }
+    fn similarity(&self, other: &Self) -> f64 {
+        (::comparable::Comparable::similarity(&self.0, &other.0)
+            + ::comparable::Comparable::similarity(&self.2, &other.2)) / 2f64
+    }
+    fn differs(&self, other: &Self) -> bool {
+        ::comparable::derive_support::masked_or(
+            "0",
+            false,
+            || ::comparable::Comparable::differs(&self.0, &other.0),
+        )
+            || ::comparable::derive_support::masked_or(
+                "2",
+                false,
+                || ::comparable::Comparable::differs(&self.2, &other.2),
+            )
+    }
+    fn count_changes(&self, other: &Self) -> usize {
+        ::comparable::derive_support::masked_or(
+            "0",
+            0,
+            || ::comparable::Comparable::count_changes(&self.0, &other.0),
+        )
+            + ::comparable::derive_support::masked_or(
+                "2",
+                0,
+                || ::comparable::Comparable::count_changes(&self.2, &other.2),
+            )
+    }
+    fn first_difference(&self, other: &Self) -> Option<::comparable::location::Path> {
+        ::comparable::derive_support::masked_or(
+                "0",
+                None,
+                || ::comparable::Comparable::first_difference(&self.0, &other.0),
+            )
+            .map(|path| {
+                path.within(::comparable::location::Segment::Field("0".to_string()))
+            })
+            .or_else(|| {
+                ::comparable::derive_support::masked_or(
+                        "2",
+                        None,
+                        || ::comparable::Comparable::first_difference(&self.2, &other.2),
+                    )
+                    .map(|path| {
+                        path
+                            .within(
+                                ::comparable::location::Segment::Field("2".to_string()),
+                            )
+                    })
+            })
+    }
+}
@@ -0,0 +1,8 @@
+#[derive(Comparable)]
+struct Meters(f64);
+
+#[derive(Comparable)]
+pub struct Point(pub i32, pub i32);
+
+#[derive(Comparable)]
+struct Labelled(u8, #[comparable_ignore] Vec<u8>, String);
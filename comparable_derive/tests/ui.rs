@@ -0,0 +1,7 @@
+// The errors reported for attributes written wrongly, or for types the
+// derives do not support. Running with `TRYBUILD=overwrite` writes the
+// expected errors of new cases, or of those whose errors have changed.
+#[test]
+fn test_attribute_errors() {
+	trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,9 @@
+use comparable::Comparable;
+
+#[derive(Comparable)]
+struct Handler {
+	#[comparable_by(eq = "self.len()")]
+	name: String,
+}
+
+fn main() {}
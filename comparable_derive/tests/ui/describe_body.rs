@@ -0,0 +1,9 @@
+use comparable::Comparable;
+
+#[derive(Comparable)]
+struct Account {
+	#[describe_type(String)]
+	name: String,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use comparable::Comparable;
+
+struct Before {
+	name: String,
+}
+
+#[derive(Comparable)]
+#[comparable_from(Before, kept(name))]
+struct After {
+	name: String,
+}
+
+fn main() {}
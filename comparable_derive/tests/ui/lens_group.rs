@@ -0,0 +1,12 @@
+use comparable::Comparable;
+
+#[derive(Comparable)]
+#[comparable_lens]
+struct Address {
+	#[comparable_group("place")]
+	city: String,
+	#[comparable_group("place")]
+	country: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use comparable::Comparable;
+
+#[derive(Comparable)]
+#[comparable_sql(table = "users")]
+struct User {
+	id: i64,
+	name: String,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use comparable::Comparable;
+
+#[derive(Comparable)]
+#[comparable_transparent]
+struct Pair(u8, u8);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use comparable::Comparable;
+
+#[derive(Comparable)]
+union Bits {
+	int: u32,
+	float: f32,
+}
+
+fn main() {}
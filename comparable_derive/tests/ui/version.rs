@@ -0,0 +1,9 @@
+use comparable::Comparable;
+
+#[derive(Comparable)]
+#[comparable_version = "one"]
+struct Config {
+	port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,124 @@
+//! Python bindings for comparing JSON values with `comparable`.
+//!
+//! This crate builds the `comparable` Python module, using maturin. It
+//! compares values made of dicts, lists, strings, numbers, booleans and
+//! `None` as `comparable` compares `serde_json::Value`, and exchanges their
+//! changes in the form that [`ValueChange`] is serialized as JSON, so that
+//! changes written by Rust services can be read, applied and merged from
+//! Python, and the other way around.
+//!
+//! ```python
+//! import comparable
+//!
+//! change = comparable.diff({"port": 80}, {"port": 8080})
+//! assert change == {"Object": [{"Changed": ["port", {"Replaced": [80, 8080]}]}]}
+//! assert comparable.apply({"port": 80}, change) == {"port": 8080}
+//! ```
+//!
+//! Values are read and written through Python's `json` module, so that
+//! anything `json.dumps` accepts can be compared.
+
+use comparable::json::ValueChange;
+use comparable::lens::Apply;
+use comparable::merge::{merge_changes, Conflict};
+use comparable::{Changed, Comparable};
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+create_exception!(
+	comparable,
+	ConflictError,
+	PyValueError,
+	"A change that does not fit the value it is applied to, or was not made from it."
+);
+create_exception!(comparable, MergeConflict, PyValueError, "Two changes made to the same part of a value differently.");
+
+/// The changes from `before` to `after`, or `None` if they are the same.
+#[pyfunction]
+fn diff(py: Python<'_>, before: &Bound<'_, PyAny>, after: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+	let (before, after): (Value, Value) = (read(before)?, read(after)?);
+	match before.comparison(&after) {
+		Changed::Unchanged => Ok(py.None()),
+		Changed::Changed(change) => write(py, &change),
+	}
+}
+
+/// The value that `change` makes of `value`. Raises `ConflictError` if
+/// `value` is not the one the change was made from.
+#[pyfunction]
+fn apply(py: Python<'_>, value: &Bound<'_, PyAny>, change: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+	let mut value: Value = read(value)?;
+	if !change.is_none() {
+		let change: ValueChange = read(change)?;
+		value.try_apply(change).map_err(|e| ConflictError::new_err(e.to_string()))?;
+	}
+	write(py, &value)
+}
+
+/// The changes made by both `left` and `right`, which were made from the
+/// same value. Raises `MergeConflict`, naming the members or indices
+/// involved, if they change the same part of the value differently.
+#[pyfunction]
+fn merge(py: Python<'_>, left: &Bound<'_, PyAny>, right: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+	let left: Option<ValueChange> = read(left)?;
+	let right: Option<ValueChange> = read(right)?;
+	let merged = match (left, right) {
+		(Some(left), Some(right)) => Some(
+			merge_value_changes(left, right)
+				.map_err(|paths| MergeConflict::new_err(format!("conflicting changes at {}", paths.join(", "))))?,
+		),
+		(left, right) => left.or(right),
+	};
+	write(py, &merged)
+}
+
+/// Merge the changes made to the members of an object, or to the elements
+/// of an array, from both sides, as `comparable::merge::merge_changes` does,
+/// returning the members or indices changed differently on each side if any
+/// were. A value replaced on either side can only be merged with the same
+/// replacement, and otherwise conflicts as a whole, as `.`.
+pub fn merge_value_changes(left: ValueChange, right: ValueChange) -> Result<ValueChange, Vec<String>> {
+	match (left, right) {
+		(ValueChange::Object(left), ValueChange::Object(right)) => {
+			merge_changes(left, right).map(ValueChange::Object).map_err(paths)
+		}
+		(ValueChange::Array(left), ValueChange::Array(right)) => {
+			merge_changes(left, right).map(ValueChange::Array).map_err(paths)
+		}
+		(left, right) if left == right => Ok(left),
+		_ => Err(vec![".".to_string()]),
+	}
+}
+
+// The merge keys of the parts of a value changed differently on each side.
+fn paths<C>(conflicts: Vec<Conflict<C>>) -> Vec<String> {
+	conflicts.into_iter().map(|conflict| conflict.path).collect()
+}
+
+// A Python value, read as JSON.
+fn read<T: DeserializeOwned>(value: &Bound<'_, PyAny>) -> PyResult<T> {
+	let text: String = value.py().import("json")?.call_method1("dumps", (value,))?.extract()?;
+	serde_json::from_str(&text).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+// A value written as JSON, read back as a Python value.
+fn write<T: Serialize>(py: Python<'_>, value: &T) -> PyResult<Py<PyAny>> {
+	let text = serde_json::to_string(value).map_err(|e| PyValueError::new_err(e.to_string()))?;
+	Ok(py.import("json")?.call_method1("loads", (text,))?.unbind())
+}
+
+/// The `comparable` Python module.
+#[pymodule]
+#[pyo3(name = "comparable")]
+pub fn comparable_python(m: &Bound<'_, PyModule>) -> PyResult<()> {
+	m.add_function(wrap_pyfunction!(diff, m)?)?;
+	m.add_function(wrap_pyfunction!(apply, m)?)?;
+	m.add_function(wrap_pyfunction!(merge, m)?)?;
+	m.add("ConflictError", m.py().get_type::<ConflictError>())?;
+	m.add("MergeConflict", m.py().get_type::<MergeConflict>())?;
+	Ok(())
+}
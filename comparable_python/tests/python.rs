@@ -0,0 +1,109 @@
+use std::ffi::CString;
+
+use comparable::json::ValueChange;
+use comparable::{MapChange, VecChange};
+use comparable_python::merge_value_changes;
+use pyo3::prelude::*;
+use pyo3::wrap_pymodule;
+use serde_json::json;
+
+// Run Python code with the module imported as `comparable`.
+fn run(code: &str) {
+	Python::attach(|py| {
+		let module = wrap_pymodule!(comparable_python::comparable_python)(py);
+		py.import("sys").unwrap().getattr("modules").unwrap().set_item("comparable", module).unwrap();
+		let code = CString::new(format!("import comparable\n{}", code)).unwrap();
+		if let Err(e) = py.run(&code, None, None) {
+			panic!("{}", e)
+		}
+	})
+}
+
+#[test]
+fn test_diff_and_apply() {
+	run(r#"
+before = {"name": "web", "port": 80, "hosts": ["a", "b"], "debug": True}
+after = {"name": "web", "port": 8080, "hosts": ["a", "c", "d"]}
+assert comparable.diff(before, before) is None
+change = comparable.diff(before, after)
+assert change == {"Object": [
+    {"Changed": ["hosts", {"Array": [
+        {"Changed": [1, {"Replaced": ["b", "c"]}]},
+        {"Added": [2, "d"]},
+    ]}]},
+    {"Changed": ["port", {"Replaced": [80, 8080]}]},
+    {"Removed": "debug"},
+]}, change
+assert comparable.apply(before, change) == after
+assert comparable.apply(before, None) == before
+assert comparable.diff(1, "1") == {"Replaced": [1, "1"]}
+
+try:
+    comparable.apply(after, change)
+    assert False
+except comparable.ConflictError as e:
+    assert str(e) == '["hosts"][1] was expected to be String("b"), but is String("c")', str(e)
+
+try:
+    comparable.diff(before, {1, 2})
+    assert False
+except TypeError:
+    pass
+
+try:
+    comparable.apply(before, {"Changed": 1})
+    assert False
+except ValueError:
+    pass
+"#);
+}
+
+#[test]
+fn test_merge() {
+	run(r#"
+base = {"name": "web", "port": 80, "hosts": ["a", "b"]}
+left = comparable.diff(base, {"name": "api", "port": 80, "hosts": ["a", "b"]})
+right = comparable.diff(base, {"name": "web", "port": 8080, "hosts": ["a", "b"]})
+merged = comparable.merge(left, right)
+assert comparable.apply(base, merged) == {"name": "api", "port": 8080, "hosts": ["a", "b"]}
+assert comparable.merge(left, None) == left
+assert comparable.merge(None, None) is None
+assert comparable.merge(left, left) == left
+
+try:
+    comparable.merge(left, comparable.diff(base, {"name": "www", "port": 81, "hosts": ["a", "b"]}))
+    assert False
+except comparable.MergeConflict as e:
+    assert str(e) == 'conflicting changes at "name"', str(e)
+"#);
+}
+
+#[test]
+fn test_merge_value_changes() {
+	let replaced = |before, after| ValueChange::Replaced(before, after);
+	assert_eq!(
+		merge_value_changes(replaced(json!(1), json!(2)), replaced(json!(1), json!(2))),
+		Ok(replaced(json!(1), json!(2)))
+	);
+	assert_eq!(
+		merge_value_changes(replaced(json!(1), json!(2)), replaced(json!(1), json!(3))),
+		Err(vec![".".to_string()])
+	);
+	assert_eq!(
+		merge_value_changes(
+			ValueChange::Array(vec![VecChange::Changed(0, replaced(json!(1), json!(2)))]),
+			ValueChange::Array(vec![
+				VecChange::Changed(0, replaced(json!(1), json!(3))),
+				VecChange::Added(1, json!(4))
+			])
+		),
+		Err(vec!["0".to_string()])
+	);
+	assert_eq!(
+		merge_value_changes(
+			ValueChange::Object(vec![MapChange::Added("a".to_string(), json!(1))]),
+			ValueChange::Object(vec![MapChange::Removed("b".to_string())])
+		),
+		Ok(ValueChange::Object(vec![MapChange::Added("a".to_string(), json!(1)), MapChange::Removed("b".to_string())]))
+	);
+}
@@ -0,0 +1,53 @@
+use std::collections::{BTreeMap, HashMap};
+
+use bumpalo::Bump;
+use comparable::arena::ComparableIn;
+use comparable::*;
+
+#[derive(Comparable, Clone, PartialEq)]
+struct Unit {
+	hp: u32,
+	name: String,
+}
+
+fn unit(hp: u32, name: &str) -> Unit {
+	Unit { hp, name: name.to_string() }
+}
+
+#[test]
+fn test_arena_vec() {
+	let bump = Bump::new();
+	let before = vec![unit(10, "a"), unit(5, "b"), unit(1, "c")];
+	let after = vec![unit(10, "a"), unit(4, "b")];
+	let changes = before.comparison_in(&after, &bump);
+	assert_eq!(changes.unwrap_changed()[..], before.comparison(&after).unwrap_changed()[..]);
+	let changes = after.comparison_in(&before, &bump);
+	assert_eq!(changes.unwrap_changed()[..], after.comparison(&before).unwrap_changed()[..]);
+	assert!(before.comparison_in(&before.clone(), &bump).is_unchanged());
+	assert!(bump.allocated_bytes() > 0);
+}
+
+#[test]
+fn test_arena_maps() {
+	let mut bump = Bump::new();
+	let before: BTreeMap<u8, u32> = vec![(1, 10), (2, 20), (3, 30)].into_iter().collect();
+	let after: BTreeMap<u8, u32> = vec![(0, 5), (2, 21), (3, 30)].into_iter().collect();
+	let changes = before.comparison_in(&after, &bump);
+	assert_eq!(
+		changes.unwrap_changed()[..],
+		[MapChange::Added(0, 5), MapChange::Changed(2, U32Change(20, 21)), MapChange::Removed(1)]
+	);
+	assert_eq!(
+		before.comparison_in(&after, &bump).unwrap_changed()[..],
+		before.comparison(&after).unwrap_changed()[..]
+	);
+	bump.reset();
+
+	let before: HashMap<u8, u32> = before.into_iter().collect();
+	let after: HashMap<u8, u32> = after.into_iter().collect();
+	assert_eq!(
+		before.comparison_in(&after, &bump).unwrap_changed()[..],
+		before.comparison(&after).unwrap_changed()[..]
+	);
+	assert!(before.comparison_in(&before.clone(), &bump).is_unchanged());
+}
@@ -0,0 +1,49 @@
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use comparable::roundtrip::{FromDesc, IntoDesc};
+use comparable::*;
+
+#[derive(Comparable)]
+struct Counters {
+	hits: AtomicUsize,
+	balance: AtomicI32,
+	open: AtomicBool,
+}
+
+#[test]
+fn test_atomic_scalars() {
+	assert_eq!(AtomicUsize::new(3).describe(), 3);
+	assert_eq!(AtomicUsize::new(3).comparison(&AtomicUsize::new(3)), Changed::Unchanged);
+	assert_eq!(AtomicBool::new(false).comparison(&AtomicBool::new(true)), Changed::Changed(BoolChange(false, true)));
+	assert_eq!(
+		with_atomic_ordering(Ordering::Relaxed, || AtomicI32::new(-1).comparison(&AtomicI32::new(1))),
+		Changed::Changed(I32Change(-1, 1))
+	);
+}
+
+#[test]
+fn test_atomic_struct() {
+	let before = Counters { hits: AtomicUsize::new(0), balance: AtomicI32::new(5), open: AtomicBool::new(true) };
+	let after =
+		Arc::new(Counters { hits: AtomicUsize::new(0), balance: AtomicI32::new(5), open: AtomicBool::new(true) });
+	let writer = {
+		let after = after.clone();
+		thread::spawn(move || {
+			after.hits.fetch_add(2, Ordering::SeqCst);
+			after.open.store(false, Ordering::SeqCst);
+		})
+	};
+	writer.join().unwrap();
+	assert_eq!(
+		before.comparison(&after),
+		Changed::Changed(vec![CountersChange::Hits(UsizeChange(0, 2)), CountersChange::Open(BoolChange(true, false))])
+	);
+}
+
+#[test]
+fn test_atomic_roundtrip() {
+	assert_eq!(AtomicI32::from_desc(7).load(Ordering::SeqCst), 7);
+	assert!(AtomicBool::new(true).into_desc());
+}
@@ -0,0 +1,36 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use comparable::audit::AuditEntry;
+use comparable::codec::{decode, encode};
+use comparable::*;
+
+#[derive(Comparable, Clone)]
+struct Account {
+	owner: String,
+	balance: i32,
+}
+
+#[test]
+fn test_audit_entry() {
+	let before = Account { owner: "alice".to_string(), balance: 10 };
+	let after = Account { owner: "alice".to_string(), balance: 25 };
+	assert!(AuditEntry::of("bob", &before, &before).is_none());
+
+	let entry = AuditEntry::of("bob", &before, &after).unwrap().at(UNIX_EPOCH + Duration::from_secs(1_709_210_096));
+	assert_eq!(entry.reason, None);
+	assert_eq!(entry.change, vec![AccountChange::Balance(I32Change(10, 25))]);
+	assert_eq!(entry.to_string(), "2024-02-29T12:34:56Z bob: [Balance(I32Change(10, 25))]");
+	assert_eq!(
+		format!("{:#}", entry.with_reason("deposit")),
+		"2024-02-29T12:34:56Z bob (deposit):\n[\n    Balance(\n        I32Change(\n            10,\n            25,\n        ),\n    ),\n]"
+	);
+
+	let entry = AuditEntry::new("system", U8Change(1, 2)).at(UNIX_EPOCH - Duration::from_secs(1));
+	assert_eq!(entry.to_string(), "1969-12-31T23:59:59Z system: U8Change(1, 2)");
+}
+
+#[test]
+fn test_audit_entry_serde() {
+	let entry = AuditEntry::new("carol", StringChange("a".to_string(), "b".to_string())).with_reason("rename");
+	assert_eq!(decode::<AuditEntry<StringChange>>(&encode(&entry).unwrap()).unwrap(), entry);
+}
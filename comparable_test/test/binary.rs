@@ -0,0 +1,88 @@
+use comparable::{assert_changes, BytesChange, Changed::*, Comparable, U8Change, VecChange};
+
+#[test]
+fn test_byte_slices() {
+	let old: &[u8] = b"hello world";
+	assert_changes!(&old, &old, Unchanged);
+	assert_changes!(
+		&old,
+		&&b"jello wOrld"[..],
+		Changed(vec![
+			BytesChange { offset: 0, old: b"h".to_vec(), new: b"j".to_vec() },
+			BytesChange { offset: 7, old: b"o".to_vec(), new: b"O".to_vec() },
+		]),
+	);
+	assert_changes!(
+		&old,
+		&&b"hello, brave world"[..],
+		Changed(vec![BytesChange { offset: 5, old: Vec::new(), new: b", brave".to_vec() }]),
+	);
+	assert_changes!(
+		&old,
+		&&b"hello"[..],
+		Changed(vec![BytesChange { offset: 5, old: b" world".to_vec(), new: Vec::new() }]),
+	);
+	assert_eq!(old.describe(), b"hello world".to_vec());
+}
+
+#[test]
+fn test_byte_slices_insertion_and_edit() {
+	let old: Vec<u8> = (0..64).collect();
+	let mut new = old.clone();
+	new.insert(5, 200);
+	new[51] = 201;
+	// The inserted byte shifts everything after it, but only the insertion
+	// and the distant edit are reported, not the bytes in between.
+	assert_changes!(
+		&old.as_slice(),
+		&new.as_slice(),
+		Changed(vec![
+			BytesChange { offset: 5, old: Vec::new(), new: vec![200] },
+			BytesChange { offset: 50, old: vec![50], new: vec![201] },
+		]),
+	);
+
+	let mut new = old.clone();
+	new.drain(10..13);
+	new[40] = 202;
+	new.extend_from_slice(b"tail");
+	assert_changes!(
+		&old.as_slice(),
+		&new.as_slice(),
+		Changed(vec![
+			BytesChange { offset: 10, old: vec![10, 11, 12], new: Vec::new() },
+			BytesChange { offset: 43, old: vec![43], new: vec![202] },
+			BytesChange { offset: 64, old: Vec::new(), new: b"tail".to_vec() },
+		]),
+	);
+}
+
+#[test]
+fn test_byte_vectors() {
+	let old = vec![1u8, 2, 3];
+	let new = vec![1u8, 5, 3];
+	// Vectors of bytes are compared element-wise by default, but may be
+	// compared as binary data through their slices.
+	assert_changes!(&old, &new, Changed(vec![VecChange::Changed(1, U8Change(2, 5))]));
+	assert_changes!(
+		&old.as_slice(),
+		&new.as_slice(),
+		Changed(vec![BytesChange { offset: 1, old: vec![2], new: vec![5] }]),
+	);
+}
+
+#[test]
+fn test_bytes() {
+	let old = bytes::Bytes::from_static(b"abcdef");
+	assert_changes!(&old, &old.clone(), Unchanged);
+	assert_changes!(
+		&old,
+		&bytes::Bytes::from_static(b"abXYef"),
+		Changed(vec![BytesChange { offset: 2, old: b"cd".to_vec(), new: b"XY".to_vec() }]),
+	);
+	assert_changes!(
+		&bytes::BytesMut::from(&b"abc"[..]),
+		&bytes::BytesMut::from(&b"abcd"[..]),
+		Changed(vec![BytesChange { offset: 3, old: Vec::new(), new: b"d".to_vec() }]),
+	);
+}
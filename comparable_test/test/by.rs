@@ -0,0 +1,81 @@
+use comparable::mask::FieldMask;
+use comparable::*;
+
+trait Plugin {
+	fn name(&self) -> String;
+	fn id(&self) -> u32;
+}
+
+struct Echo {
+	id: u32,
+	label: &'static str,
+}
+
+impl Plugin for Echo {
+	fn name(&self) -> String {
+		format!("echo:{}", self.label)
+	}
+
+	fn id(&self) -> u32 {
+		self.id
+	}
+}
+
+#[derive(Comparable)]
+struct Host {
+	port: u16,
+	#[comparable_by(desc = "self.name()", eq = "self.id()")]
+	plugin: Box<dyn Plugin>,
+	#[comparable_by(desc = "self.iter().map(|p| p.name()).collect::<Vec<_>>().join(\",\")")]
+	extras: Vec<Box<dyn Plugin>>,
+}
+
+#[derive(Comparable)]
+enum Slot {
+	Empty,
+	Loaded(#[comparable_by(desc = "self.name()", eq = "self.id()")] Box<dyn Plugin>),
+}
+
+fn echo(id: u32, label: &'static str) -> Box<dyn Plugin> {
+	Box::new(Echo { id, label })
+}
+
+fn host(plugin: Box<dyn Plugin>, extras: Vec<Box<dyn Plugin>>) -> Host {
+	Host { port: 80, plugin, extras }
+}
+
+#[test]
+fn test_compared_by_projection() {
+	let before = host(echo(1, "a"), vec![]);
+	assert_eq!(before.describe(), HostDesc { port: 80, plugin: "echo:a".to_string(), extras: String::new() });
+
+	// Values with the same key are the same, however they are described.
+	assert_eq!(before.comparison(&host(echo(1, "b"), vec![])), Changed::Unchanged);
+	assert_eq!(
+		before.comparison(&host(echo(2, "b"), vec![echo(3, "c")])),
+		Changed::Changed(vec![
+			HostChange::Plugin(StringChange("echo:a".to_string(), "echo:b".to_string())),
+			HostChange::Extras(StringChange(String::new(), "echo:c".to_string())),
+		])
+	);
+	assert_eq!(
+		before.comparison_masked(&host(echo(2, "b"), vec![echo(3, "c")]), &FieldMask::include(["extras"])),
+		Changed::Changed(vec![HostChange::Extras(StringChange(String::new(), "echo:c".to_string()))])
+	);
+	assert!(before.differs(&host(echo(2, "a"), vec![])));
+	assert_eq!(before.count_changes(&host(echo(2, "b"), vec![echo(3, "c")])), 2);
+}
+
+#[test]
+fn test_compared_by_in_enum() {
+	let loaded = Slot::Loaded(echo(1, "a"));
+	assert_eq!(loaded.comparison(&Slot::Loaded(echo(1, "z"))), Changed::Unchanged);
+	assert_eq!(
+		loaded.comparison(&Slot::Loaded(echo(2, "b"))),
+		Changed::Changed(SlotChange::BothLoaded(StringChange("echo:a".to_string(), "echo:b".to_string())))
+	);
+	assert_eq!(
+		loaded.comparison(&Slot::Empty),
+		Changed::Changed(SlotChange::Different(SlotDesc::Loaded("echo:a".to_string()), SlotDesc::Empty))
+	);
+}
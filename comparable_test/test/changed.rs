@@ -0,0 +1,150 @@
+use serde::de::value::{Error, MapDeserializer, StrDeserializer, UnitDeserializer};
+use serde::de::IntoDeserializer;
+use serde::{Deserialize, Serialize};
+
+use comparable::codec::{decode, encode};
+use comparable::*;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Report {
+	#[serde(default, skip_serializing_if = "Changed::is_unchanged")]
+	total: Changed<I32Change>,
+	#[serde(with = "comparable::changed::tagged")]
+	secret: Changed<Redacted>,
+	#[serde(with = "comparable::changed::flat")]
+	count: Changed<u8>,
+}
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct Totals {
+	#[serde(default)]
+	total: Changed<I32Change>,
+	#[serde(with = "comparable::changed::tagged")]
+	secret: Changed<Redacted>,
+}
+
+#[test]
+fn test_changed_flat() {
+	#[derive(Serialize, Deserialize, PartialEq, Debug)]
+	struct Count {
+		#[serde(with = "comparable::changed::flat")]
+		count: Changed<u8>,
+	}
+
+	let count = Count { count: Changed::Changed(5) };
+	assert_eq!(serde_json::to_string(&count).unwrap(), r#"{"count":5}"#);
+	assert_eq!(serde_json::from_str::<Count>(r#"{"count":5}"#).unwrap(), count);
+	let count = Count { count: Changed::Unchanged };
+	assert_eq!(serde_json::to_string(&count).unwrap(), r#"{"count":null}"#);
+	assert_eq!(serde_json::from_str::<Count>(r#"{"count":null}"#).unwrap(), count);
+	assert_eq!(
+		comparable::changed::flat::deserialize::<u32, _>(UnitDeserializer::<Error>::new()),
+		Ok(Changed::Unchanged)
+	);
+}
+
+#[test]
+fn test_changed_json() {
+	let changed = Changed::Changed(Redacted);
+	let json = serde_json::to_string(&changed).unwrap();
+	assert_eq!(json, r#"{"Changed":null}"#);
+	assert_eq!(serde_json::from_str::<Changed<Redacted>>(&json).unwrap(), changed);
+
+	let json = serde_json::to_string(&Changed::<Redacted>::Unchanged).unwrap();
+	assert_eq!(json, r#""Unchanged""#);
+	assert_eq!(serde_json::from_str::<Changed<Redacted>>(&json).unwrap(), Changed::Unchanged);
+
+	let changes = 1i32.comparison(&2);
+	assert_eq!(serde_json::from_str::<Changed<I32Change>>(&serde_json::to_string(&changes).unwrap()).unwrap(), changes);
+}
+
+#[test]
+fn test_changed_tagged() {
+	let unchanged: Result<Changed<Redacted>, Error> =
+		comparable::changed::tagged::deserialize(StrDeserializer::<Error>::new("Unchanged"));
+	assert_eq!(unchanged, Ok(Changed::Unchanged));
+	let changed: Result<Changed<Redacted>, Error> =
+		comparable::changed::tagged::deserialize(StrDeserializer::<Error>::new("Changed"));
+	assert!(changed.is_err());
+}
+
+#[test]
+fn test_changed_fields() {
+	let report = Report {
+		total: Changed::Changed(I32Change(1, 2)),
+		secret: Changed::Changed(Redacted),
+		count: Changed::Unchanged,
+	};
+	assert_eq!(decode::<Report>(&encode(&report).unwrap()).unwrap(), report);
+
+	// Absent fields are unchanged, when the field allows it.
+	let fields: Vec<(&str, &str)> = vec![("secret", "Unchanged")];
+	let deserializer = MapDeserializer::<_, Error>::new(
+		fields.into_iter().map(|(k, v)| (k.into_deserializer(), v.into_deserializer())),
+	);
+	assert_eq!(Totals::deserialize(deserializer), Ok(Totals { total: Changed::Unchanged, secret: Changed::Unchanged }));
+}
+
+#[test]
+fn test_changed_traits() {
+	use std::collections::HashSet;
+
+	let change = Changed::Changed((1u8, 2u8));
+	let copy = change;
+	assert_eq!(change.clone(), copy);
+	let set: HashSet<Changed<u8>> =
+		vec![Changed::Unchanged, Changed::Changed(1), Changed::Unchanged].into_iter().collect();
+	assert_eq!(set.len(), 2);
+	assert_eq!(Changed::<u8>::default(), Changed::Unchanged);
+	assert_eq!((&Changed::Changed(3)).into_iter().collect::<Vec<_>>(), vec![&3]);
+	assert_eq!(Changed::Changed(3).into_iter().collect::<Vec<_>>(), vec![3]);
+
+	let mut changes = Changed::Unchanged;
+	changes.extend(Vec::<U8Change>::new());
+	assert_eq!(changes, Changed::Unchanged);
+	changes.extend(vec![U8Change(1, 2)]);
+	changes.extend(vec![U8Change(3, 4)]);
+	assert_eq!(changes, Changed::Changed(vec![U8Change(1, 2), U8Change(3, 4)]));
+}
+
+#[test]
+fn test_changed_adapters() {
+	let change = Changed::Changed(U8Change(1, 2));
+	assert_eq!(change.iter().collect::<Vec<_>>(), vec![&U8Change(1, 2)]);
+	assert_eq!(Changed::<U8Change>::Unchanged.iter().next(), None);
+
+	let gathered: Changed<Vec<U8Change>> = vec![1u8.comparison(&1), 2u8.comparison(&3)].into_iter().collect();
+	assert_eq!(gathered, Changed::Changed(vec![U8Change(2, 3)]));
+	let gathered: Changed<Vec<U8Change>> = vec![1u8.comparison(&1), 2u8.comparison(&2)].into_iter().collect();
+	assert_eq!(gathered, Changed::Unchanged);
+
+	assert_eq!(Changed::from_changes(Vec::<U8Change>::new()), Changed::Unchanged);
+	let mut change = Changed::Changed(U8Change(1, 2));
+	assert_eq!(change.to_changes(), vec![U8Change(1, 2)]);
+	assert_eq!(change, Changed::Unchanged);
+}
+
+#[test]
+fn test_changed_unwrap() {
+	assert_eq!(1i32.comparison(&2).unwrap_changed(), I32Change(1, 2));
+	assert_eq!(1i32.comparison(&2).expect_changed("values differ"), I32Change(1, 2));
+	1i32.comparison(&1).unwrap_unchanged();
+}
+
+#[test]
+#[should_panic(expected = "called `Changed::unwrap_changed()` on an `Unchanged` value")]
+fn test_changed_unwrap_unchanged_value() {
+	1i32.comparison(&1).unwrap_changed();
+}
+
+#[test]
+#[should_panic(expected = "values differ")]
+fn test_changed_expect_unchanged_value() {
+	1i32.comparison(&1).expect_changed("values differ");
+}
+
+#[test]
+#[should_panic(expected = "on a `Changed` value: I32Change(1, 2)")]
+fn test_changed_unwrap_unchanged_changed_value() {
+	1i32.comparison(&2).unwrap_unchanged();
+}
@@ -0,0 +1,95 @@
+use std::collections::BTreeMap;
+
+use comparable::codec::{decode, decode_changes, encode, encode_changes, Error};
+use comparable::*;
+
+#[derive(Comparable, Clone)]
+struct Player {
+	name: String,
+	score: i64,
+	position: (f32, f32),
+	inventory: BTreeMap<String, u32>,
+	status: Status,
+}
+
+#[derive(Comparable, Clone)]
+enum Status {
+	Idle,
+	Moving { speed: u16 },
+	Chatting(Option<String>),
+}
+
+fn assert_changes_roundtrip<T: Comparable>(a: &T, b: &T)
+where
+	T::Change: serde::Serialize + serde::de::DeserializeOwned,
+{
+	let changes = a.comparison(b);
+	let bytes = encode_changes(&changes).unwrap();
+	assert_eq!(decode_changes::<T::Change>(&bytes).unwrap(), changes);
+}
+
+#[test]
+fn test_codec_roundtrip() {
+	let player = Player {
+		name: "ann".to_string(),
+		score: 10,
+		position: (1.0, 2.0),
+		inventory: vec![("gold".to_string(), 5)].into_iter().collect(),
+		status: Status::Idle,
+	};
+	let mut moved = player.clone();
+	moved.score = -300;
+	moved.position.1 = 2.5;
+	moved.inventory.insert("gem".to_string(), 1);
+	moved.inventory.remove("gold");
+	moved.status = Status::Moving { speed: 1000 };
+	assert_changes_roundtrip(&player, &player);
+	assert_changes_roundtrip(&player, &moved);
+	assert_changes_roundtrip(&moved, &player);
+	assert_changes_roundtrip(&Status::Chatting(None), &Status::Chatting(Some("hi".to_string())));
+	assert_changes_roundtrip(&'a', &'\u{1F600}');
+	assert_changes_roundtrip(&u64::MAX, &0);
+	assert_changes_roundtrip(&i64::MIN, &i64::MAX);
+}
+
+#[test]
+fn test_codec_compact() {
+	let player = Player {
+		name: "ann".to_string(),
+		score: 10,
+		position: (1.0, 2.0),
+		inventory: BTreeMap::new(),
+		status: Status::Idle,
+	};
+	assert_eq!(encode_changes(&player.comparison(&player)).unwrap(), Vec::<u8>::new());
+	let mut scored = player.clone();
+	scored.score = 11;
+	// One change, to the second field, from 10 to 11.
+	assert_eq!(encode_changes(&player.comparison(&scored)).unwrap(), vec![1, 1, 1, 20, 22]);
+	assert_eq!(encode(&I32Change(-1, 300)).unwrap(), vec![1, 251, 0x58, 0x02]);
+}
+
+#[test]
+fn test_codec_errors() {
+	let bytes = encode(&I32Change(1, 300)).unwrap();
+	assert_eq!(decode::<I32Change>(&bytes[..bytes.len() - 1]), Err(Error::UnexpectedEnd));
+	assert_eq!(decode::<I8Change>(&bytes), Err(Error::TrailingBytes));
+	assert!(matches!(decode::<U8Change>(&[0xff, 0xff, 0xff]), Err(Error::TrailingBytes)));
+	assert!(matches!(decode::<bool>(&[2]), Err(Error::Invalid(_))));
+	assert!(matches!(decode::<U16Change>(&[0xff, 0xff, 0x7f, 0]), Err(Error::Message(_))));
+	assert!(matches!(decode_changes::<I32Change>(&[2, 0, 0]), Err(Error::Invalid(_))));
+}
+
+#[test]
+fn test_codec_unit_changes() {
+	#[derive(Comparable)]
+	struct Secret {
+		#[comparable_redact]
+		key: String,
+	}
+
+	assert_changes_roundtrip(&Secret { key: "a".to_string() }, &Secret { key: "b".to_string() });
+	let changes = Changed::Changed(Redacted);
+	assert_eq!(encode_changes(&changes).unwrap(), vec![1]);
+	assert_eq!(decode_changes::<Redacted>(&[1]).unwrap(), changes);
+}
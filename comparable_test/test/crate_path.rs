@@ -0,0 +1,56 @@
+use comparable::{Changed::*, *};
+
+// Re-exports of the crate, as a facade crate would make them.
+mod facade {
+	pub use comparable as inner;
+}
+
+#[test]
+fn test_crate_path_struct() {
+	#[derive(Comparable)]
+	#[comparable_crate = "crate::crate_path::facade::inner"]
+	struct Point {
+		x: i32,
+		label: Option<String>,
+	}
+
+	assert_eq!(Point { x: 1, label: None }.describe(), PointDesc { x: 1, label: None });
+	assert_changes!(
+		&Point { x: 1, label: None },
+		&Point { x: 2, label: None },
+		Changed(vec![PointChange::X(I32Change(1, 2))])
+	);
+}
+
+#[test]
+fn test_crate_path_enum() {
+	#[derive(Comparable)]
+	#[comparable_crate = "crate::crate_path::facade::inner"]
+	enum Shape {
+		Circle(u32),
+		Square { side: u32 },
+	}
+
+	assert_changes!(&Shape::Circle(1), &Shape::Circle(2), Changed(ShapeChange::BothCircle(U32Change(1, 2))));
+	assert_changes!(
+		&Shape::Square { side: 1 },
+		&Shape::Circle(1),
+		Changed(ShapeChange::Different(ShapeDesc::Square { side: 1 }, ShapeDesc::Circle(1)))
+	);
+}
+
+#[test]
+fn test_crate_path_field_named_comparable() {
+	#[derive(Comparable)]
+	#[comparable_crate = "crate::crate_path::facade::inner"]
+	struct Setting {
+		comparable: u8,
+		name: String,
+	}
+
+	assert_changes!(
+		&Setting { comparable: 1, name: "a".to_string() },
+		&Setting { comparable: 3, name: "a".to_string() },
+		Changed(vec![SettingChange::Comparable(U8Change(1, 3))])
+	);
+}
@@ -0,0 +1,89 @@
+use comparable::describe::Describe;
+
+// Neither of these types implements `Comparable`.
+#[derive(Debug, PartialEq)]
+struct Handle(u32);
+
+impl Describe for Handle {
+	type Desc = String;
+
+	fn describe(&self) -> Self::Desc {
+		format!("handle #{}", self.0)
+	}
+}
+
+#[derive(Describe)]
+struct Window {
+	title: String,
+	handle: Handle,
+	#[comparable_ignore]
+	#[allow(dead_code)]
+	cache: Vec<u8>,
+}
+
+#[derive(Describe)]
+enum Shape {
+	Circle(f64),
+	Rect {
+		width: f64,
+		height: f64,
+	},
+	Empty,
+	#[comparable_ignore]
+	#[allow(dead_code)]
+	Mesh(Vec<u8>),
+}
+
+#[derive(Describe)]
+#[describe_type(String)]
+#[describe_body(format!("{} bytes", self.0.len()))]
+struct Blob(Vec<u8>);
+
+#[derive(Describe, Debug)]
+#[describe_type(String)]
+#[describe_body(format!("{:?}", self))]
+enum Level {
+	Low,
+	#[allow(dead_code)]
+	High(u8),
+}
+
+#[test]
+fn test_derive_describe_struct() {
+	let window = Window { title: "main".to_string(), handle: Handle(7), cache: vec![1, 2, 3] };
+	assert_eq!(window.describe(), WindowDesc { title: "main".to_string(), handle: "handle #7".to_string() });
+}
+
+#[test]
+fn test_derive_describe_enum() {
+	assert_eq!(Shape::Circle(1.0).describe(), ShapeDesc::Circle(1.0));
+	assert_eq!(Shape::Rect { width: 2.0, height: 3.0 }.describe(), ShapeDesc::Rect { width: 2.0, height: 3.0 });
+	assert_eq!(Shape::Empty.describe(), ShapeDesc::Empty);
+	assert_eq!(Shape::Mesh(vec![1]).describe(), ShapeDesc::Mesh);
+}
+
+#[test]
+fn test_derive_describe_attributes() {
+	assert_eq!(Blob(vec![0; 4]).describe(), "4 bytes");
+	assert_eq!(Level::Low.describe(), "Low");
+}
+
+#[test]
+fn test_comparable_types_describe() {
+	assert_eq!(Describe::describe(&vec![1, 2]), vec![1, 2]);
+	assert_eq!(Describe::describe(&Some("a".to_string())), Some("a".to_string()));
+}
+
+#[test]
+fn test_describe_generic() {
+	#[derive(Describe)]
+	struct Labeled<T> {
+		label: String,
+		value: T,
+	}
+
+	assert_eq!(
+		Labeled { label: "h".to_string(), value: Handle(7) }.describe(),
+		LabeledDesc { label: "h".to_string(), value: "handle #7".to_string() }
+	);
+}
@@ -0,0 +1,101 @@
+use std::collections::BTreeMap;
+
+use comparable::display::display;
+use comparable::*;
+
+#[derive(Comparable)]
+#[comparable_display]
+struct Person {
+	name: String,
+	age: u32,
+	nickname: Option<String>,
+	tags: Vec<String>,
+	scores: BTreeMap<String, u8>,
+	#[comparable_redact]
+	password: String,
+}
+
+#[derive(Comparable)]
+#[comparable_display]
+struct Team {
+	lead: Person,
+	#[comparable_ignore]
+	#[allow(dead_code)]
+	size: usize,
+}
+
+#[derive(Comparable)]
+#[comparable_display]
+enum Shape {
+	Circle(f64),
+	Rectangle { width: u32, height: u32 },
+	Empty,
+}
+
+#[derive(Comparable)]
+#[comparable_display]
+struct Pair<T>(T, T);
+
+fn person(age: u32, nickname: Option<&str>, tags: &[&str], password: &str) -> Person {
+	Person {
+		name: "Alice".to_string(),
+		age,
+		nickname: nickname.map(ToString::to_string),
+		tags: tags.iter().map(ToString::to_string).collect(),
+		scores: vec![("chess".to_string(), 3)].into_iter().collect(),
+		password: password.to_string(),
+	}
+}
+
+#[test]
+fn test_display_struct() {
+	let before = person(41, None, &["a"], "secret");
+	let after = Person {
+		scores: vec![("chess".to_string(), 4), ("go".to_string(), 1)].into_iter().collect(),
+		..person(42, Some("Al"), &["a", "b"], "hunter2")
+	};
+	let changes = before.comparison(&after).unwrap_or_default();
+	assert_eq!(
+		changes.iter().map(ToString::to_string).collect::<Vec<_>>(),
+		vec![
+			"age: 41 → 42",
+			"nickname: None → Some(\"Al\")",
+			"tags[1]: added \"b\"",
+			"scores[\"chess\"]: 3 → 4; scores[\"go\"]: added 1",
+			"password: changed",
+		]
+	);
+	assert_eq!(
+		display(&changes).to_string(),
+		"age: 41 → 42; nickname: None → Some(\"Al\"); tags[1]: added \"b\"; \
+		 scores[\"chess\"]: 3 → 4; scores[\"go\"]: added 1; password: changed"
+	);
+}
+
+#[test]
+fn test_display_nested() {
+	let before = Team { lead: person(41, Some("Al"), &[], "secret"), size: 1 };
+	let after = Team { lead: person(42, None, &[], "secret"), size: 2 };
+	let change = before.comparison(&after).next().unwrap();
+	assert_eq!(change.to_string(), "lead.age: 41 → 42; lead.nickname: Some(\"Al\") → None");
+}
+
+#[test]
+fn test_display_enum() {
+	let circle = Shape::Circle(1.0).comparison(&Shape::Circle(2.5)).next().unwrap();
+	assert_eq!(circle.to_string(), "0: 1.0 → 2.5");
+
+	let rectangle =
+		Shape::Rectangle { width: 1, height: 2 }.comparison(&Shape::Rectangle { width: 3, height: 4 }).next().unwrap();
+	assert_eq!(rectangle.to_string(), "width: 1 → 3; height: 2 → 4");
+
+	let different = Shape::Circle(1.0).comparison(&Shape::Empty).next().unwrap();
+	assert_eq!(different.to_string(), "Circle(1.0) → Empty");
+}
+
+#[test]
+fn test_display_generic() {
+	let changes = Pair(1u8, 2u8).comparison(&Pair(1, 3)).unwrap_or_default();
+	assert_eq!(display(&changes).to_string(), "1: 2 → 3");
+	assert_eq!(display(&U8Change(1, 2)).to_string(), "1 → 2");
+}
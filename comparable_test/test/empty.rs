@@ -1,6 +1,70 @@
-use comparable::{assert_changes, Changed::*};
+use comparable::{assert_changes, Changed::*, Comparable};
 
 #[test]
 fn test_empty() {
 	assert_changes!(&std::iter::empty::<()>(), &std::iter::empty::<()>(), Unchanged,);
 }
+
+#[test]
+fn test_all_ignored() {
+	struct Opaque;
+
+	#[derive(Comparable)]
+	#[comparable_display]
+	#[comparable_tree]
+	#[allow(dead_code)]
+	struct Fields {
+		#[comparable_ignore]
+		first: Opaque,
+		#[comparable_ignore]
+		second: Opaque,
+	}
+
+	#[derive(Comparable)]
+	#[comparable_display]
+	#[comparable_tree]
+	#[comparable_transitions]
+	enum Variant {
+		#[comparable_ignore]
+		Only(Opaque),
+	}
+
+	#[derive(Comparable)]
+	enum VariantFields {
+		Only(#[comparable_ignore] Opaque, #[comparable_ignore] Opaque),
+	}
+
+	#[derive(Comparable)]
+	enum Unit {
+		Only,
+	}
+
+	// Each of these is never changed, and has no change type of its own.
+	let _: [(); 4] = [
+		Fields { first: Opaque, second: Opaque }
+			.comparison(&Fields { first: Opaque, second: Opaque })
+			.unwrap_or_default(),
+		Variant::Only(Opaque).comparison(&Variant::Only(Opaque)).unwrap_or_default(),
+		VariantFields::Only(Opaque, Opaque).comparison(&VariantFields::Only(Opaque, Opaque)).unwrap_or_default(),
+		Unit::Only.comparison(&Unit::Only).unwrap_or_default(),
+	];
+	assert_changes!(&Variant::Only(Opaque), &Variant::Only(Opaque), Unchanged);
+	assert_changes!(&VariantFields::Only(Opaque, Opaque), &VariantFields::Only(Opaque, Opaque), Unchanged);
+	assert_changes!(&Unit::Only, &Unit::Only, Unchanged);
+	assert_eq!(Variant::Only(Opaque).transition(&Variant::Only(Opaque)), None);
+
+	// Which of several variants a value holds is still compared.
+	#[derive(Comparable)]
+	#[allow(dead_code)]
+	enum Variants {
+		First(#[comparable_ignore] Opaque),
+		Second(#[comparable_ignore] Opaque),
+	}
+
+	assert_changes!(&Variants::First(Opaque), &Variants::First(Opaque), Unchanged);
+	assert_changes!(
+		&Variants::First(Opaque),
+		&Variants::Second(Opaque),
+		Changed(VariantsChange::Different(VariantsDesc::First(), VariantsDesc::Second()))
+	);
+}
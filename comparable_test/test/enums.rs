@@ -547,3 +547,317 @@ fn test_enum_2_variant_2_named_field_1_ignored() {
 		Changed(TwoVariantEnumChange::BothField1 { some_u16: Changed(U16Change(0, 1)) })
 	);
 }
+
+#[test]
+fn test_enum_variant_no_description() {
+	#[derive(Comparable)]
+	enum Payload {
+		Small(u8),
+		#[no_description]
+		Large(Vec<u8>),
+		#[describe_type(usize)]
+		#[describe_body(match self { Payload::Sized { data } => data.len(), _ => unreachable!() })]
+		Sized {
+			data: Vec<u8>,
+		},
+	}
+
+	assert_eq!(Payload::Small(1).describe(), PayloadDesc::Small(1));
+	assert_eq!(Payload::Large(vec![1, 2, 3]).describe(), PayloadDesc::Large);
+	assert_eq!(Payload::Sized { data: vec![1, 2, 3] }.describe(), PayloadDesc::Sized(3));
+
+	// Payloads are still compared in full, even if they aren't described.
+	assert_changes!(
+		&Payload::Large(vec![1]),
+		&Payload::Large(vec![2]),
+		Changed(PayloadChange::BothLarge(vec![VecChange::Changed(0, U8Change(1, 2))]))
+	);
+	assert_changes!(
+		&Payload::Large(vec![1]),
+		&Payload::Sized { data: vec![1, 2] },
+		Changed(PayloadChange::Different(PayloadDesc::Large, PayloadDesc::Sized(2)))
+	);
+}
+
+#[test]
+fn test_enum_field_describe_attrs() {
+	#[derive(Comparable)]
+	enum Message {
+		Text(String),
+		Binary(
+			#[describe_type(usize)]
+			#[describe_body(match self { Message::Binary(data) => data.len(), _ => unreachable!() })]
+			Vec<u8>,
+		),
+	}
+
+	assert_eq!(Message::Text("hi".to_string()).describe(), MessageDesc::Text("hi".to_string()));
+	assert_eq!(Message::Binary(vec![0; 10]).describe(), MessageDesc::Binary(10));
+}
+
+#[test]
+fn test_enum_field_redacted() {
+	#[derive(Comparable)]
+	enum Auth {
+		Anonymous,
+		Token(#[comparable_redact] String),
+	}
+
+	assert_eq!(Auth::Token("abc".to_string()).describe(), AuthDesc::Token(Redacted));
+	assert_changes!(&Auth::Token("abc".to_string()), &Auth::Token("abc".to_string()), Unchanged);
+	assert_changes!(
+		&Auth::Token("abc".to_string()),
+		&Auth::Token("xyz".to_string()),
+		Changed(AuthChange::BothToken(Redacted))
+	);
+	assert_changes!(
+		&Auth::Anonymous,
+		&Auth::Token("xyz".to_string()),
+		Changed(AuthChange::Different(AuthDesc::Anonymous, AuthDesc::Token(Redacted)))
+	);
+}
+
+#[test]
+fn test_enum_variant_renamed() {
+	#[derive(Comparable)]
+	#[variant_struct_fields]
+	enum Shape {
+		#[comparable_rename = "Radius"]
+		Circle(u32),
+		Rect {
+			#[comparable_rename = "W"]
+			width: u32,
+			height: u32,
+		},
+	}
+
+	assert_changes!(&Shape::Circle(1), &Shape::Circle(2), Changed(ShapeChange::Radius(U32Change(1, 2))));
+	assert_changes!(
+		&Shape::Rect { width: 1, height: 2 },
+		&Shape::Rect { width: 3, height: 2 },
+		Changed(ShapeChange::BothRect(vec![ShapeRectChange::W(U32Change(1, 3))]))
+	);
+	assert_changes!(
+		&Shape::Circle(1),
+		&Shape::Rect { width: 3, height: 2 },
+		Changed(ShapeChange::Different(ShapeDesc::Circle(1), ShapeDesc::Rect { width: 3, height: 2 }))
+	);
+}
+
+#[test]
+fn test_enum_discriminants() {
+	#[derive(Comparable)]
+	enum Level {
+		Low = 1,
+		Mid = 5,
+		High,
+	}
+
+	#[derive(Comparable)]
+	#[repr(u8)]
+	enum Packet {
+		Ping = 1,
+		Data(u8) = 7,
+	}
+
+	assert_eq!(Level::High as u8, 6);
+	assert_changes!(&Level::Mid, &Level::Mid, Unchanged);
+	assert_changes!(&Level::Low, &Level::High, Changed(LevelChange::Different(LevelDesc::Low, LevelDesc::High)));
+	assert_changes!(&Packet::Data(1), &Packet::Data(2), Changed(PacketChange::BothData(U8Change(1, 2))));
+	assert_changes!(
+		&Packet::Ping,
+		&Packet::Data(2),
+		Changed(PacketChange::Different(PacketDesc::Ping, PacketDesc::Data(2)))
+	);
+}
+
+#[test]
+fn test_enum_non_exhaustive() {
+	#[derive(Comparable)]
+	#[non_exhaustive]
+	enum Event {
+		Started { at: u64 },
+	}
+
+	#[derive(Comparable)]
+	#[non_exhaustive]
+	enum Status {
+		Up,
+		Down(String),
+	}
+
+	// The change type of a `#[non_exhaustive]` enum always has a `Different`
+	// variant, which remains meaningful once other variants are added.
+	let _ = EventChange::Different(EventDesc::Started { at: 1 }, EventDesc::Started { at: 2 });
+	assert_changes!(&Event::Started { at: 1 }, &Event::Started { at: 1 }, Unchanged);
+	assert_changes!(
+		&Event::Started { at: 1 },
+		&Event::Started { at: 2 },
+		Changed(EventChange::BothStarted { at: U64Change(1, 2) })
+	);
+	assert!(Event::Started { at: 1 }.differs(&Event::Started { at: 2 }));
+	assert_changes!(
+		&Status::Up,
+		&Status::Down("maintenance".to_string()),
+		Changed(StatusChange::Different(StatusDesc::Up, StatusDesc::Down("maintenance".to_string())))
+	);
+}
+
+#[test]
+fn test_enum_variant_only() {
+	#[derive(Comparable)]
+	#[comparable_variant_only]
+	#[comparable_display]
+	#[comparable_tree]
+	enum Connection {
+		Idle,
+		Open { peer: String, bytes: u64 },
+		Failed(String),
+	}
+
+	#[derive(Comparable)]
+	#[comparable_variant_only]
+	enum Single {
+		Only(u8),
+	}
+
+	let open = |bytes| Connection::Open { peer: "a".to_string(), bytes };
+	// Payloads are described, but changes to them are not reported.
+	assert_changes!(&open(1), &open(2), Unchanged);
+	assert_changes!(&Connection::Failed("x".to_string()), &Connection::Failed("y".to_string()), Unchanged);
+	assert_changes!(
+		&Connection::Idle,
+		&open(1),
+		Changed(ConnectionChange::Different(
+			ConnectionDesc::Idle,
+			ConnectionDesc::Open { peer: "a".to_string(), bytes: 1 }
+		))
+	);
+	assert!(!open(1).differs(&open(2)));
+	assert!(open(1).differs(&Connection::Idle));
+	assert_eq!(open(1).count_changes(&open(2)), 0);
+	assert_eq!(open(1).count_changes(&Connection::Idle), 1);
+	assert_eq!(open(1).similarity(&open(2)), 1.0);
+	assert_eq!(open(1).first_difference(&Connection::Idle), Some(location::Path::default()));
+	assert!(ConnectionChange::FIELDS.is_empty());
+
+	assert_changes!(&Single::Only(1), &Single::Only(2), Unchanged);
+}
+
+#[test]
+fn test_enum_describe_type() {
+	#[derive(Comparable, Debug)]
+	#[describe_type(String)]
+	#[describe_body(format!("{:?}", self))]
+	#[comparable_display]
+	#[comparable_tree]
+	enum Level {
+		Low,
+		High { limit: u8 },
+	}
+
+	assert_eq!(Level::High { limit: 3 }.describe(), "High { limit: 3 }".to_string());
+	assert_changes!(
+		&Level::High { limit: 3 },
+		&Level::High { limit: 4 },
+		Changed(LevelChange::BothHigh { limit: U8Change(3, 4) })
+	);
+	assert_changes!(
+		&Level::Low,
+		&Level::High { limit: 4 },
+		Changed(LevelChange::Different("Low".to_string(), "High { limit: 4 }".to_string()))
+	);
+}
+
+#[test]
+fn test_enum_compare_default() {
+	#[derive(Comparable, Default)]
+	#[compare_default]
+	#[comparable_display]
+	enum Mode {
+		#[default]
+		Off,
+		On {
+			level: u8,
+			label: String,
+		},
+	}
+
+	#[derive(Comparable)]
+	#[compare_default]
+	enum Limit<T: Default> {
+		Unlimited,
+		Max(T),
+	}
+
+	impl<T: Default> Default for Limit<T> {
+		fn default() -> Self {
+			Limit::Max(T::default())
+		}
+	}
+
+	let on = |level| Mode::On { level, label: "a".to_string() };
+	// Values are described by their change from the default value.
+	assert_eq!(Mode::Off.describe(), Unchanged);
+	assert_eq!(
+		on(1).describe(),
+		Changed(ModeChange::Different(ModeDesc::Off, ModeDesc::On { level: 1, label: "a".to_string() }))
+	);
+	assert_changes!(&on(1), &on(2), Changed(ModeChange::BothOn { level: Changed(U8Change(1, 2)), label: Unchanged }));
+	assert_changes!(
+		&on(1),
+		&Mode::Off,
+		Changed(ModeChange::Different(ModeDesc::On { level: 1, label: "a".to_string() }, ModeDesc::Off))
+	);
+
+	assert_eq!(Limit::Max(3u32).describe(), Changed(LimitChange::BothMax(U32Change(0, 3))));
+	assert_eq!(Limit::<u32>::Max(0).describe(), Unchanged);
+	assert_eq!(
+		Limit::<u32>::Unlimited.describe(),
+		Changed(LimitChange::Different(LimitDesc::Max(0), LimitDesc::Unlimited))
+	);
+}
+
+#[test]
+fn test_enum_ignored_variant() {
+	struct Handle(#[allow(dead_code)] u32);
+
+	#[derive(Comparable)]
+	#[comparable_display]
+	#[comparable_tree]
+	enum Source {
+		File(String),
+		#[comparable_ignore]
+		Open(Handle, u64),
+		#[comparable_ignore]
+		Pipe {
+			handle: Handle,
+		},
+		#[comparable_ignore]
+		#[describe_type(String)]
+		#[describe_body("stdin".to_string())]
+		Stdin(Handle),
+	}
+
+	let file = |name: &str| Source::File(name.to_string());
+	assert_eq!(Source::Open(Handle(1), 0).describe(), SourceDesc::Open);
+	assert_eq!(Source::Stdin(Handle(1)).describe(), SourceDesc::Stdin("stdin".to_string()));
+	assert_changes!(&Source::Open(Handle(1), 0), &Source::Open(Handle(2), 5), Unchanged);
+	assert_changes!(&Source::Pipe { handle: Handle(1) }, &Source::Pipe { handle: Handle(2) }, Unchanged);
+	assert_changes!(&Source::Stdin(Handle(1)), &Source::Stdin(Handle(2)), Unchanged);
+	assert_changes!(
+		&file("a"),
+		&Source::Open(Handle(1), 0),
+		Changed(SourceChange::Different(SourceDesc::File("a".to_string()), SourceDesc::Open))
+	);
+	assert_changes!(
+		&Source::Open(Handle(1), 0),
+		&Source::Pipe { handle: Handle(1) },
+		Changed(SourceChange::Different(SourceDesc::Open, SourceDesc::Pipe))
+	);
+	assert!(!Source::Open(Handle(1), 0).differs(&Source::Open(Handle(2), 5)));
+	assert_eq!(Source::Open(Handle(1), 0).count_changes(&Source::Open(Handle(2), 5)), 0);
+	assert_eq!(Source::Open(Handle(1), 0).similarity(&Source::Open(Handle(2), 5)), 1.0);
+	assert_eq!(Source::Open(Handle(1), 0).first_difference(&Source::Open(Handle(2), 5)), None);
+	assert_eq!(SourceChange::FIELDS, &["0"]);
+}
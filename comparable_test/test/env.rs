@@ -0,0 +1,38 @@
+use std::collections::BTreeMap;
+
+use comparable::env::{self, EnvChange};
+use comparable::*;
+
+fn vars(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+	pairs.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect()
+}
+
+#[test]
+fn test_env_render() {
+	let before = vars(&[("HOME", "/home/a"), ("LANG", "C"), ("OLD", "1")]);
+	let after = vars(&[("HOME", "/home/a"), ("LANG", "en_US.UTF-8"), ("NEW", "2")]);
+	let changes: Vec<EnvChange> = before.comparison(&after).unwrap_or_default();
+	assert_eq!(env::render(&changes), "~ LANG: C → en_US.UTF-8\n+ NEW=2\n- OLD");
+	assert_eq!(env::render(&[]), "");
+
+	let renamed = vars(&[("HOME", "/home/a"), ("LANG", "C"), ("RENAMED", "1")]);
+	let changes = with_diff_options(DiffOptions { map_moves: Some(1.0), ..DiffOptions::default() }, || {
+		before.comparison(&renamed).unwrap_or_default()
+	});
+	assert_eq!(env::render(&changes), "> OLD → RENAMED");
+}
+
+#[test]
+fn test_env_report() {
+	let before = env::snapshot();
+	assert_eq!(before.get("PATH").cloned(), std::env::var("PATH").ok());
+
+	std::env::set_var("COMPARABLE_ENV_TEST_REPORT", "on");
+	let report = env::report(&before).unwrap();
+	assert!(report.lines().any(|line| line == "+ COMPARABLE_ENV_TEST_REPORT=on"));
+
+	let during = env::snapshot();
+	std::env::remove_var("COMPARABLE_ENV_TEST_REPORT");
+	let report = env::report(&during).unwrap();
+	assert!(report.lines().any(|line| line == "- COMPARABLE_ENV_TEST_REPORT"));
+}
@@ -0,0 +1,77 @@
+use std::sync::{Arc, Mutex, RwLock};
+
+use comparable::fallible::{Error, TryComparable};
+use comparable::{Changed::*, *};
+
+#[derive(Comparable)]
+#[comparable_try]
+struct Counter {
+	name: String,
+	count: Mutex<u32>,
+}
+
+#[test]
+fn test_try_comparable_blanket() {
+	assert_eq!(1u8.try_describe(), Ok(1));
+	assert_eq!(1u8.try_comparison(&2), Ok(Changed(U8Change(1, 2))));
+	assert_eq!(vec![1u8].try_comparison(&vec![1u8]), Ok(Unchanged));
+}
+
+#[test]
+fn test_try_comparable_locks() {
+	let value = Mutex::new(1u8);
+	assert_eq!(value.try_describe(), Ok(1));
+	assert_eq!(value.try_comparison(&value), Ok(Unchanged));
+	assert_eq!(value.try_comparison(&Mutex::new(2)), Ok(Changed(U8Change(1, 2))));
+	assert_eq!(RwLock::new(1u8).try_comparison(&RwLock::new(2)), Ok(Changed(U8Change(1, 2))));
+
+	let poisoned = Arc::new(Mutex::new(1u8));
+	let held = Arc::clone(&poisoned);
+	let _ = std::thread::spawn(move || {
+		let _guard = held.lock().unwrap();
+		panic!("poisoning the lock");
+	})
+	.join();
+	assert_eq!(poisoned.try_describe(), Err(Error::Poisoned));
+	assert_eq!(Mutex::new(1u8).try_comparison(&poisoned), Err(Error::Poisoned));
+	assert_eq!(Error::Poisoned.to_string(), "a lock holding the value was poisoned");
+}
+
+#[test]
+fn test_try_comparable_derive() {
+	let counter = |count| Counter { name: "a".to_string(), count: Mutex::new(count) };
+	assert_eq!(counter(1).try_describe(), Ok(CounterDesc { name: "a".to_string(), count: 1 }));
+	assert_eq!(counter(1).try_comparison(&counter(1)), Ok(Unchanged));
+	assert_eq!(counter(1).try_comparison(&counter(2)), Ok(Changed(vec![CounterChange::Count(U32Change(1, 2))])));
+
+	#[derive(Comparable)]
+	#[comparable_try]
+	#[allow(dead_code)]
+	struct Shared<T>(RwLock<T>, #[comparable_ignore] u8, Mutex<Counter>);
+
+	let shared = |value, count| Shared(RwLock::new(value), 0, Mutex::new(counter(count)));
+	assert_eq!(shared(true, 1).try_describe(), Ok(SharedDesc(true, CounterDesc { name: "a".to_string(), count: 1 })));
+	assert_eq!(
+		shared(true, 1).try_comparison(&shared(false, 2)),
+		Ok(Changed(vec![
+			SharedChange::Field0(BoolChange(true, false)),
+			SharedChange::Field2(vec![CounterChange::Count(U32Change(1, 2))]),
+		]))
+	);
+}
+
+#[test]
+fn test_try_comparable_all_ignored() {
+	#[derive(Comparable)]
+	#[comparable_try]
+	struct Opaque {
+		#[comparable_ignore]
+		#[allow(dead_code)]
+		lock: Mutex<Vec<u8>>,
+	}
+
+	let opaque = || Opaque { lock: Mutex::new(vec![1]) };
+	assert_eq!(opaque().try_describe(), Ok(OpaqueDesc {}));
+	let unchanged: Result<comparable::Changed<()>, Error> = opaque().try_comparison(&opaque());
+	assert_eq!(unchanged, Ok(Unchanged));
+}
@@ -0,0 +1,59 @@
+use comparable::display::display;
+use comparable::tree::{ChangeTree, ToTree};
+use comparable::*;
+
+bitflags::bitflags! {
+	#[derive(Clone, Copy, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+	pub struct Permissions: u8 {
+		const READ = 1;
+		const WRITE = 2;
+		const EXECUTE = 4;
+	}
+}
+
+impl_comparable_for_bitflags!(Permissions);
+
+#[derive(Comparable)]
+#[comparable_display]
+#[comparable_tree]
+struct File {
+	name: String,
+	permissions: Permissions,
+}
+
+#[test]
+fn test_flags() {
+	let rw = Permissions::READ | Permissions::WRITE;
+	assert_eq!(rw.describe(), rw);
+	assert_eq!(rw.comparison(&rw), Changed::Unchanged);
+	assert_eq!(
+		rw.comparison(&Permissions::all()),
+		Changed::Changed(FlagsChange { added: Permissions::EXECUTE, removed: Permissions::empty() })
+	);
+	assert_eq!(
+		rw.comparison(&Permissions::EXECUTE),
+		Changed::Changed(FlagsChange { added: Permissions::EXECUTE, removed: rw })
+	);
+}
+
+#[test]
+fn test_flags_field() {
+	let before = File { name: "a".to_string(), permissions: Permissions::READ | Permissions::WRITE };
+	let after = File { name: "a".to_string(), permissions: Permissions::READ | Permissions::EXECUTE };
+	let changes = before.comparison(&after).unwrap_or_default();
+	assert_eq!(
+		changes,
+		vec![FileChange::Permissions(FlagsChange { added: Permissions::EXECUTE, removed: Permissions::WRITE })]
+	);
+	assert_eq!(display(&changes).to_string(), "permissions.EXECUTE: false → true; permissions.WRITE: true → false");
+	assert_eq!(
+		changes.to_tree(),
+		ChangeTree::Children(vec![(
+			"permissions".to_string(),
+			ChangeTree::Children(vec![
+				("EXECUTE".to_string(), ChangeTree::modified(&false, &true)),
+				("WRITE".to_string(), ChangeTree::modified(&true, &false)),
+			])
+		)])
+	);
+}
@@ -0,0 +1,248 @@
+use comparable::format::csv;
+use comparable::format::markdown::{render, MarkdownStyle};
+use comparable::*;
+
+#[derive(Comparable)]
+#[comparable_tree]
+struct Item {
+	name: String,
+	tags: Vec<String>,
+}
+
+#[derive(Comparable)]
+#[comparable_tree]
+enum Status {
+	Active { since: u32 },
+	Closed,
+}
+
+fn csv_of(rows: &[(String, String, String)]) -> String {
+	let mut out = Vec::new();
+	csv::write_rows(rows, &mut out).unwrap();
+	String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn test_change_rows() {
+	let before = Item { name: "pen".to_string(), tags: vec!["a".to_string()] };
+	let after = Item { name: "pen".to_string(), tags: vec!["a".to_string(), "b,c".to_string()] };
+	let changes = before.comparison(&after).unwrap_or_default();
+	let rows: Vec<(String, String, String)> = (&changes[0]).into();
+	assert_eq!(rows, vec![("tags.1".to_string(), String::new(), "\"b,c\"".to_string())]);
+	assert_eq!(csv_of(&rows), "path,old,new\ntags.1,,\"\"\"b,c\"\"\"\n");
+
+	let changes = Status::Active { since: 1 }.comparison(&Status::Active { since: 2 }).next().unwrap();
+	let rows: Vec<(String, String, String)> = (&changes).into();
+	assert_eq!(csv_of(&rows), "path,old,new\nsince,1,2\n");
+	let changes = Status::Active { since: 1 }.comparison(&Status::Closed).next().unwrap();
+	let rows: Vec<(String, String, String)> = (&changes).into();
+	assert_eq!(csv_of(&rows), "path,old,new\n,Active { since: 1 },Closed\n");
+}
+
+#[test]
+fn test_csv_quoting() {
+	let row = |path: &str, old: &str, new: &str| (path.to_string(), old.to_string(), new.to_string());
+	assert_eq!(csv_of(&[]), "path,old,new\n");
+	assert_eq!(
+		csv_of(&[row("a", "plain", "two\nlines"), row("b", "say \"hi\"", "cr\r")]),
+		"path,old,new\na,plain,\"two\nlines\"\nb,\"say \"\"hi\"\"\",\"cr\r\"\n"
+	);
+}
+
+#[test]
+fn test_csv_write() {
+	let before = Item { name: "pen".to_string(), tags: vec!["a".to_string(), "b".to_string()] };
+	let after = Item { name: "ink, blue".to_string(), tags: vec!["a".to_string()] };
+	let changes = before.comparison(&after).unwrap_or_default();
+	let mut out = Vec::new();
+	csv::write(&changes, &mut out).unwrap();
+	assert_eq!(
+		String::from_utf8(out).unwrap(),
+		"path,kind,old,new\nname,modified,\"\"\"pen\"\"\",\"\"\"ink, blue\"\"\"\ntags.1,removed,\"\"\"b\"\"\",\n"
+	);
+
+	let mut out = Vec::new();
+	csv::write(&Vec::<ItemChange>::new(), &mut out).unwrap();
+	assert_eq!(String::from_utf8(out).unwrap(), "path,kind,old,new\n");
+
+	let moved = with_diff_options(DiffOptions { sequence_moves: true, ..DiffOptions::default() }, || {
+		vec![1, 2].comparison(&vec![2, 1]).unwrap_or_default()
+	});
+	let mut out = Vec::new();
+	csv::write(&moved, &mut out).unwrap();
+	assert!(String::from_utf8(out).unwrap().contains(",moved,,"));
+}
+
+#[derive(Comparable)]
+#[comparable_tree]
+struct Order {
+	item: Item,
+	note: String,
+}
+
+#[test]
+fn test_markdown() {
+	let before = Order { item: Item { name: "pen".to_string(), tags: vec!["a".to_string()] }, note: "a|b".to_string() };
+	let after = Order { item: Item { name: "pencil".to_string(), tags: vec![] }, note: "`quoted`".to_string() };
+	let changes = before.comparison(&after).unwrap_or_default();
+	assert_eq!(
+		render(&changes, MarkdownStyle::Table),
+		"| Path | Change | Before | After |\n\
+		 | --- | --- | --- | --- |\n\
+		 | `item.name` | modified | `\"pen\"` | `\"pencil\"` |\n\
+		 | `item.tags.0` | removed | `\"a\"` |  |\n\
+		 | `note` | modified | `\"a\\|b\"` | ``\"`quoted`\"`` |\n"
+	);
+	assert_eq!(
+		render(&changes, MarkdownStyle::List),
+		"- `item`\n\
+		 \x20 - `name`: `\"pen\"` → `\"pencil\"`\n\
+		 \x20 - `tags`\n\
+		 \x20   - `0`: removed `\"a\"`\n\
+		 - `note`: `\"a|b\"` → ``\"`quoted`\"``\n"
+	);
+
+	assert_eq!(render(&Vec::<OrderChange>::new(), MarkdownStyle::List), "");
+	assert_eq!(render(&1u8.comparison(&2).next().unwrap(), MarkdownStyle::List), "- `1` → `2`\n");
+	let removed = vec![(1, 'x')].into_iter().collect::<std::collections::BTreeMap<_, _>>();
+	let changes = removed.comparison(&Default::default()).unwrap_or_default();
+	assert_eq!(render(&changes, MarkdownStyle::List), "- `1`: removed\n");
+}
+
+#[test]
+fn test_dot() {
+	use comparable::format::dot;
+
+	let before =
+		Order { item: Item { name: "pen".to_string(), tags: vec!["a".to_string()] }, note: "say \"hi\"".to_string() };
+	let after = Order {
+		item: Item { name: "pen".to_string(), tags: vec!["a".to_string(), "b".to_string()] },
+		note: "bye".to_string(),
+	};
+	let changes = before.comparison(&after).unwrap_or_default();
+	assert_eq!(
+		dot::render(&changes),
+		r#"digraph changes {
+  node [shape=box, style=filled, fillcolor=white];
+  n0 [label="(root)", fillcolor=lightgray];
+  n1 [label="item", fillcolor=lightgray];
+  n0 -> n1;
+  n2 [label="tags", fillcolor=lightgray];
+  n1 -> n2;
+  n3 [label="1\nadded \"b\"", fillcolor=palegreen];
+  n2 -> n3;
+  n4 [label="note\n\"say \\\"hi\\\"\" → \"bye\"", fillcolor=orange];
+  n0 -> n4;
+}
+"#
+	);
+	assert_eq!(
+		dot::render(&Vec::<OrderChange>::new()),
+		"digraph changes {\n  node [shape=box, style=filled, fillcolor=white];\n  n0 [label=\"(root)\", fillcolor=lightgray];\n}\n"
+	);
+}
+
+#[test]
+fn test_merge_patch() {
+	use comparable::format::merge_patch::{apply, merge_patch};
+	use serde_json::json;
+
+	let before = Order { item: Item { name: "pen".to_string(), tags: vec!["a".to_string()] }, note: "hi".to_string() };
+	let after = Order {
+		item: Item { name: "ink".to_string(), tags: vec!["a".to_string(), "b".to_string()] },
+		note: "hi".to_string(),
+	};
+	let patch = merge_patch(&before, &after).unwrap();
+	assert_eq!(patch, json!({ "item": { "name": "ink", "tags": ["a", "b"] } }));
+	let mut target = serde_json::to_value(before.describe()).unwrap();
+	apply(&mut target, &patch);
+	assert_eq!(target, serde_json::to_value(after.describe()).unwrap());
+	assert_eq!(merge_patch(&before, &before).unwrap(), json!({}));
+
+	// Enum variants are replaced whole when they differ, and merged when the
+	// same variant's fields change.
+	let active = |since| Status::Active { since };
+	assert_eq!(merge_patch(&active(1), &active(2)).unwrap(), json!({ "Active": { "since": 2 } }));
+	assert_eq!(merge_patch(&active(1), &Status::Closed).unwrap(), json!("Closed"));
+
+	// Removed map entries are null, as RFC 7386 requires.
+	let map = |entries: &[(&str, u8)]| {
+		entries.iter().map(|(k, v)| (k.to_string(), *v)).collect::<std::collections::BTreeMap<_, _>>()
+	};
+	let patch = merge_patch(&map(&[("a", 1), ("b", 2)]), &map(&[("a", 1), ("c", 3)])).unwrap();
+	assert_eq!(patch, json!({ "b": null, "c": 3 }));
+	let mut target = json!({ "a": 1, "b": 2 });
+	apply(&mut target, &patch);
+	assert_eq!(target, json!({ "a": 1, "c": 3 }));
+}
+
+#[derive(Comparable, Clone, PartialEq, Debug)]
+#[comparable_desc_roundtrip]
+struct Profile {
+	name: String,
+	scores: Vec<u32>,
+	links: std::collections::BTreeMap<String, String>,
+}
+
+fn operations(patch: serde_json::Value) -> Vec<comparable::format::json_patch::Operation> {
+	serde_json::from_value(patch).unwrap()
+}
+
+#[test]
+fn test_json_patch() {
+	use comparable::format::json_patch::{apply, to_change, Error};
+	use serde_json::json;
+
+	let profile = Profile {
+		name: "ann".to_string(),
+		scores: vec![1, 2],
+		links: vec![("a/b".to_string(), "x".to_string())].into_iter().collect(),
+	};
+	let patch = operations(json!([
+		{ "op": "test", "path": "/name", "value": "ann" },
+		{ "op": "add", "path": "/scores/-", "value": 3 },
+		{ "op": "add", "path": "/scores/0", "value": 0 },
+		{ "op": "remove", "path": "/scores/1" },
+		{ "op": "copy", "from": "/links/a~1b", "path": "/links/c~0d" },
+		{ "op": "move", "from": "/links/a~1b", "path": "/links/e" },
+		{ "op": "replace", "path": "/name", "value": "bo" },
+	]));
+	let mut expected = profile.clone();
+	expected.name = "bo".to_string();
+	expected.scores = vec![0, 2, 3];
+	expected.links =
+		vec![("c~d".to_string(), "x".to_string()), ("e".to_string(), "x".to_string())].into_iter().collect();
+	assert_eq!(to_change(&profile, &patch), Ok(profile.comparison(&expected)));
+
+	// Patches that do not fit the value are refused.
+	let refused = |patch| to_change(&profile, &operations(patch));
+	assert_eq!(refused(json!([{ "op": "remove", "path": "/age" }])), Err(Error::NoSuchPath("/age".to_string())));
+	assert_eq!(
+		refused(json!([{ "op": "replace", "path": "/scores/2", "value": 1 }])),
+		Err(Error::NoSuchPath("/scores/2".to_string()))
+	);
+	assert_eq!(
+		refused(json!([{ "op": "add", "path": "/scores/01", "value": 1 }])),
+		Err(Error::NoSuchPath("/scores/01".to_string()))
+	);
+	assert_eq!(
+		refused(json!([{ "op": "test", "path": "/name", "value": "bo" }])),
+		Err(Error::TestFailed("/name".to_string()))
+	);
+	assert_eq!(refused(json!([{ "op": "remove", "path": "name" }])), Err(Error::InvalidPath("name".to_string())));
+	assert_eq!(
+		refused(json!([{ "op": "move", "from": "/links", "path": "/links/x" }])),
+		Err(Error::InvalidPath("/links/x".to_string()))
+	);
+	assert_eq!(
+		refused(json!([{ "op": "add", "path": "/age", "value": 1 }])),
+		Err(Error::NoSuchPath("/age".to_string()))
+	);
+	assert!(matches!(refused(json!([{ "op": "replace", "path": "/name", "value": 1 }])), Err(Error::Invalid(_))));
+	assert_eq!(to_change(&profile, &[]), Ok(Changed::Unchanged));
+
+	// The whole document can be replaced.
+	let mut doc = json!({ "a": 1 });
+	apply(&mut doc, &operations(json!([{ "op": "replace", "path": "", "value": [1] }]))).unwrap();
+	assert_eq!(doc, json!([1]));
+}
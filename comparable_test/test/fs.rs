@@ -0,0 +1,137 @@
+#![allow(clippy::single_range_in_vec_init)]
+
+use std::path::{Path, PathBuf};
+
+use comparable::display::display;
+use comparable::fs::{diff_dirs, diff_files, DirTree, FileHashes, FileMetadata, FileMetadataChange};
+use comparable::*;
+
+// A fresh directory for the named test, removed when dropped.
+struct Scratch(PathBuf);
+
+impl Scratch {
+	fn new(name: &str) -> Self {
+		let root = std::env::temp_dir().join(format!("comparable-{}-{}", name, std::process::id()));
+		let _ = std::fs::remove_dir_all(&root);
+		std::fs::create_dir_all(&root).unwrap();
+		Scratch(root)
+	}
+
+	fn write(&self, path: &str, contents: &[u8]) -> PathBuf {
+		let path = self.0.join(path);
+		std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+		std::fs::write(&path, contents).unwrap();
+		path
+	}
+
+	fn path(&self, path: &str) -> PathBuf {
+		self.0.join(path)
+	}
+}
+
+impl Drop for Scratch {
+	fn drop(&mut self) {
+		let _ = std::fs::remove_dir_all(&self.0);
+	}
+}
+
+#[test]
+fn test_file_hashes() {
+	let hashes = FileHashes::read(&b"aaaabbbbcc"[..], 4).unwrap();
+	assert_eq!(hashes.len, 10);
+	assert_eq!(hashes.chunks.len(), 3);
+	assert_eq!(FileHashes::read(&b""[..], 4).unwrap().chunks, Vec::<u64>::new());
+	assert_eq!(FileHashes::read(&b"aaaabbbb"[..], 4).unwrap().chunks.len(), 2);
+
+	let same = FileHashes::read(&b"aaaabbbbcc"[..], 4).unwrap();
+	assert_eq!(hashes.changed_ranges(&same), Changed::Unchanged);
+
+	// Adjacent changed chunks form one range, and the ranges end with the
+	// longer of the two files.
+	let other = FileHashes::read(&b"aaaaXbbbYcccccc"[..], 4).unwrap();
+	assert_eq!(hashes.changed_ranges(&other), Changed::Changed(vec![4..15]));
+	let other = FileHashes::read(&b"Xaaabbbbcc"[..], 4).unwrap();
+	assert_eq!(hashes.changed_ranges(&other), Changed::Changed(vec![0..4]));
+	let other = FileHashes::read(&b"Xaaabbbbc"[..], 4).unwrap();
+	assert_eq!(hashes.changed_ranges(&other), Changed::Changed(vec![0..4, 8..10]));
+}
+
+#[test]
+fn test_diff_files() {
+	let scratch = Scratch::new("fs-files");
+	let before = scratch.write("before", &[1; 100]);
+	let mut contents = vec![1; 120];
+	contents[50] = 2;
+	let after = scratch.write("after", &contents);
+	assert_eq!(diff_files(&before, &before, 16).unwrap(), Changed::Unchanged);
+	assert_eq!(diff_files(&before, &after, 16).unwrap(), Changed::Changed(vec![48..64, 96..120]));
+	assert!(diff_files(&before, scratch.path("missing"), 16).is_err());
+}
+
+#[test]
+fn test_diff_dirs() {
+	let scratch = Scratch::new("fs-dirs");
+	scratch.write("before/same", b"same");
+	scratch.write("before/changed", b"aaaabbbb");
+	scratch.write("before/sub/removed", b"gone");
+	scratch.write("after/same", b"same");
+	scratch.write("after/changed", b"aaaabbbc");
+	scratch.write("after/sub/deeper/added", b"new!!");
+	std::fs::create_dir_all(scratch.path("after/empty")).unwrap();
+
+	let root = |name: &str| -> PathBuf { scratch.path(name) };
+	assert_eq!(diff_dirs(root("before"), root("before"), 4).unwrap(), Changed::Unchanged);
+	assert_eq!(
+		diff_dirs(root("before"), root("after"), 4).unwrap(),
+		Changed::Changed(vec![
+			MapChange::Changed(PathBuf::from("changed"), vec![4..8]),
+			MapChange::Added(Path::new("sub").join("deeper").join("added"), 5),
+			MapChange::Removed(Path::new("sub").join("removed")),
+		])
+	);
+}
+
+#[test]
+fn test_dir_tree() {
+	let scratch = Scratch::new("fs-tree");
+	scratch.write("same", b"same");
+	scratch.write("changed", b"before");
+	scratch.write("sub/removed", b"gone");
+	let before = DirTree::snapshot(scratch.path("")).unwrap();
+	assert_eq!(before.files.len(), 3);
+	assert_eq!(before.comparison(&DirTree::snapshot(scratch.path("")).unwrap()), Changed::Unchanged);
+
+	scratch.write("changed", b"after!!");
+	std::fs::remove_file(scratch.path("sub/removed")).unwrap();
+	scratch.write("added", b"new");
+	let after = DirTree::snapshot(scratch.path("")).unwrap();
+	let changes = before.comparison(&after).unwrap_or_default();
+	assert_eq!(changes.len(), 3);
+	assert_eq!(changes[0], MapChange::Added(PathBuf::from("added"), after.files[Path::new("added")].clone()));
+	assert_eq!(changes[2], MapChange::Removed(Path::new("sub").join("removed")));
+	match &changes[1] {
+		MapChange::Changed(path, changes) => {
+			assert_eq!(path, Path::new("changed"));
+			assert_eq!(changes[0], FileMetadataChange::Size(U64Change(6, 7)));
+			assert!(matches!(changes.last(), Some(FileMetadataChange::Hash(_))));
+		}
+		change => panic!("unexpected change {:?}", change),
+	}
+}
+
+#[test]
+fn test_file_metadata() {
+	let metadata = FileMetadata { size: 4, modified: None, hash: 1 };
+	assert_eq!(metadata.comparison(&metadata.clone()), Changed::Unchanged);
+
+	// A file rewritten with the same contents changes only in the time it was
+	// modified.
+	let touched = FileMetadata { modified: Some(std::time::UNIX_EPOCH), ..metadata.clone() };
+	assert_eq!(
+		metadata.comparison(&touched),
+		Changed::Changed(vec![FileMetadataChange::Modified(None, Some(std::time::UNIX_EPOCH))])
+	);
+
+	let grown = FileMetadata { size: 5, hash: 2, ..metadata.clone() };
+	assert_eq!(display(&metadata.comparison(&grown)).to_string(), "size: 4 → 5; hash: 1 → 2");
+}
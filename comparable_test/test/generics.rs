@@ -0,0 +1,234 @@
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use comparable::{Changed::*, *};
+
+#[test]
+fn test_generic_struct() {
+	#[derive(Comparable)]
+	struct Pair<A, B> {
+		first: A,
+		second: B,
+		count: u8,
+	}
+
+	assert_eq!(
+		Pair { first: 1u32, second: "a".to_string(), count: 0 }.describe(),
+		PairDesc { first: 1, second: "a".to_string(), count: 0 }
+	);
+	assert_changes!(
+		&Pair { first: 1u32, second: "a".to_string(), count: 0 },
+		&Pair { first: 2u32, second: "a".to_string(), count: 1 },
+		Changed(vec![PairChange::First(U32Change(1, 2)), PairChange::Count(U8Change(0, 1))])
+	);
+}
+
+#[test]
+fn test_generic_default_parameter() {
+	#[derive(Comparable)]
+	struct Cache<K: Ord + Clone + std::fmt::Debug + std::hash::Hash, V, S = RandomState> {
+		entries: HashMap<K, V, S>,
+		hits: u32,
+	}
+
+	let mut cache: Cache<String, i32> = Cache { entries: HashMap::new(), hits: 0 };
+	let before = Cache { entries: cache.entries.clone(), hits: cache.hits };
+	cache.entries.insert("a".to_string(), 1);
+	assert_changes!(&before, &cache, Changed(vec![CacheChange::Entries(vec![MapChange::Added("a".to_string(), 1)])]));
+}
+
+#[test]
+fn test_generic_phantom_data() {
+	struct NotComparable;
+
+	#[derive(Comparable)]
+	struct Id<T> {
+		value: u64,
+		marker: PhantomData<T>,
+	}
+
+	assert_eq!(Id::<NotComparable> { value: 1, marker: PhantomData }.describe(), IdDesc { value: 1 });
+	assert_changes!(
+		&Id::<NotComparable> { value: 1, marker: PhantomData },
+		&Id::<NotComparable> { value: 2, marker: PhantomData },
+		Changed(IdChange { value: U64Change(1, 2) })
+	);
+}
+
+#[test]
+fn test_generic_enum() {
+	#[derive(Comparable)]
+	enum Either<L, R> {
+		Left(L),
+		Right(R),
+	}
+
+	assert_changes!(&Either::<u8, String>::Left(1), &Either::Left(2), Changed(EitherChange::BothLeft(U8Change(1, 2))));
+	assert_changes!(
+		&Either::<u8, String>::Left(1),
+		&Either::Right("a".to_string()),
+		Changed(EitherChange::Different(EitherDesc::Left(1), EitherDesc::Right("a".to_string())))
+	);
+}
+
+#[test]
+fn test_generic_lifetime_and_const() {
+	#[derive(Comparable)]
+	struct Borrowed<'a, const N: usize> {
+		name: &'a str,
+		data: [u8; N],
+	}
+
+	assert_changes!(
+		&Borrowed { name: "a", data: [1, 2] },
+		&Borrowed { name: "b", data: [1, 3] },
+		Changed(vec![
+			BorrowedChange::Name(StringChange("a".to_string(), "b".to_string())),
+			BorrowedChange::Data([Unchanged, Changed(U8Change(2, 3))]),
+		])
+	);
+}
+
+#[derive(Comparable)]
+struct Framed {
+	header: [u8; Self::HEADER_LEN],
+	length: u16,
+}
+
+impl Framed {
+	const HEADER_LEN: usize = 2;
+}
+
+#[test]
+fn test_associated_const_in_field_type() {
+	assert_changes!(
+		&Framed { header: [1, 2], length: 3 },
+		&Framed { header: [1, 4], length: 3 },
+		Changed(vec![FramedChange::Header([Unchanged, Changed(U8Change(2, 4))])])
+	);
+}
+
+#[test]
+fn test_marker_fields() {
+	#[derive(Comparable)]
+	#[allow(dead_code)]
+	struct Pinned {
+		value: u8,
+		pin: std::marker::PhantomPinned,
+	}
+
+	#[derive(Comparable)]
+	struct Included<T> {
+		value: u8,
+		#[comparable_include]
+		marker: PhantomData<T>,
+	}
+
+	assert_eq!(Pinned { value: 1, pin: std::marker::PhantomPinned }.describe(), PinnedDesc { value: 1 });
+	assert_eq!(Included::<String> { value: 1, marker: PhantomData }.describe(), IncludedDesc { value: 1, marker: () });
+	assert_changes!(
+		&Included::<String> { value: 1, marker: PhantomData },
+		&Included::<String> { value: 2, marker: PhantomData },
+		Changed(vec![IncludedChange::Value(U8Change(1, 2))])
+	);
+}
+
+#[test]
+fn test_generic_bound() {
+	struct Tag(u8);
+
+	impl std::fmt::Display for Tag {
+		fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+			write!(f, "#{}", self.0)
+		}
+	}
+
+	// `T` is only compared by its string, so `T: Comparable` is not required.
+	#[derive(Comparable)]
+	#[comparable_bound(T: std::fmt::Display)]
+	struct Tagged<T> {
+		#[comparable_by(desc = "self.to_string()")]
+		tag: T,
+		count: u8,
+	}
+
+	// Bounds may also be stricter than those inferred.
+	#[derive(Comparable)]
+	#[comparable_bound(Vec<T>: Comparable)]
+	#[comparable_bound(T: Clone)]
+	struct Items<T> {
+		items: Vec<T>,
+	}
+
+	assert_eq!(Tagged { tag: Tag(1), count: 0 }.describe(), TaggedDesc { tag: "#1".to_string(), count: 0 });
+	assert_changes!(
+		&Tagged { tag: Tag(1), count: 0 },
+		&Tagged { tag: Tag(2), count: 0 },
+		Changed(vec![TaggedChange::Tag(StringChange("#1".to_string(), "#2".to_string()))])
+	);
+	assert_changes!(
+		&Items { items: vec![1u8] },
+		&Items { items: vec![2u8] },
+		Changed(ItemsChange { items: vec![VecChange::Changed(0, U8Change(1, 2))] })
+	);
+}
+
+#[test]
+fn test_generic_associated_types() {
+	// Fields of associated types are required to be comparable themselves.
+	#[derive(Comparable)]
+	#[comparable_display]
+	#[comparable_tree]
+	#[comparable_from_default]
+	struct Peeked<I: Iterator> {
+		peeked: Option<<I as Iterator>::Item>,
+		last: I::Item,
+		position: usize,
+	}
+
+	impl<I: Iterator> Default for Peeked<I>
+	where
+		I::Item: Default,
+	{
+		fn default() -> Self {
+			Peeked { peeked: None, last: Default::default(), position: 0 }
+		}
+	}
+
+	#[derive(Comparable)]
+	#[comparable_display]
+	#[comparable_tree]
+	#[comparable_transitions]
+	enum Polled<F: std::future::Future> {
+		Pending,
+		Ready(F::Output),
+	}
+
+	type Chars = std::vec::IntoIter<char>;
+	type Ready = std::future::Ready<u8>;
+
+	assert_eq!(
+		Peeked::<Chars> { peeked: Some('a'), last: 'b', position: 1 }.describe(),
+		PeekedDesc { peeked: Some('a'), last: 'b', position: 1 }
+	);
+	assert_changes!(
+		&Peeked::<Chars> { peeked: Some('a'), last: 'b', position: 1 },
+		&Peeked::<Chars> { peeked: None, last: 'b', position: 2 },
+		Changed(vec![
+			PeekedChange::Peeked(OptionChange::Different(Some('a'), None)),
+			PeekedChange::Position(UsizeChange(1, 2)),
+		])
+	);
+	assert_eq!(
+		Peeked::<Chars> { peeked: None, last: '\0', position: 3 }.comparison_from_default(),
+		Changed(vec![PeekedChange::Position(UsizeChange(0, 3))])
+	);
+
+	assert_changes!(&Polled::<Ready>::Ready(1), &Polled::Ready(2), Changed(PolledChange::BothReady(U8Change(1, 2))));
+	let change = Polled::<Ready>::Pending.comparison(&Polled::Ready(2));
+	assert_eq!(change, Changed(PolledChange::Different(PolledDesc::Pending, PolledDesc::Ready(2))));
+	if let Changed(change) = change {
+		assert!(!change.to_string().is_empty());
+	}
+}
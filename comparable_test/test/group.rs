@@ -0,0 +1,96 @@
+use comparable::display::display;
+use comparable::mask::FieldMask;
+use comparable::merge::MergeKey;
+use comparable::tree::{ChangeTree, ToTree};
+use comparable::*;
+
+#[derive(Comparable, Clone)]
+#[comparable_display]
+#[comparable_tree]
+struct Server {
+	name: String,
+	#[comparable_group("network")]
+	host: String,
+	#[comparable_group("network")]
+	port: u16,
+	#[comparable_group("limits")]
+	max_connections: u32,
+	enabled: bool,
+}
+
+#[derive(Comparable)]
+struct Labelled<T> {
+	#[comparable_group("value")]
+	value: T,
+	label: String,
+}
+
+fn server() -> Server {
+	Server { name: "web".to_string(), host: "localhost".to_string(), port: 80, max_connections: 100, enabled: true }
+}
+
+#[test]
+fn test_group_change_type() {
+	let before = server();
+	let after = Server { port: 81, host: "example.com".to_string(), enabled: false, ..server() };
+	assert_eq!(
+		before.comparison(&after),
+		Changed::Changed(vec![
+			ServerChange::Network(vec![
+				ServerNetworkChange::Host(StringChange("localhost".to_string(), "example.com".to_string())),
+				ServerNetworkChange::Port(U16Change(80, 81)),
+			]),
+			ServerChange::Enabled(BoolChange(true, false)),
+		])
+	);
+	assert_eq!(before.comparison(&server()), Changed::Unchanged);
+	assert_eq!(
+		before.comparison(&Server { max_connections: 10, ..server() }),
+		Changed::Changed(vec![ServerChange::Limits(vec![ServerLimitsChange::MaxConnections(U32Change(100, 10))])])
+	);
+	assert_eq!(ServerChange::FIELDS, &["name", "host", "port", "max_connections", "enabled"]);
+}
+
+#[test]
+fn test_group_generics() {
+	let before = Labelled { value: 1u8, label: "a".to_string() };
+	let after = Labelled { value: 2u8, label: "a".to_string() };
+	assert_eq!(
+		before.comparison(&after),
+		Changed::Changed(vec![LabelledChange::Value(vec![LabelledValueChange::Value(U8Change(1, 2))])])
+	);
+}
+
+#[test]
+fn test_group_mask() {
+	let before = server();
+	let after = Server { port: 81, host: "example.com".to_string(), ..server() };
+	assert_eq!(
+		before.comparison_masked(&after, &FieldMask::include(["port"])),
+		Changed::Changed(vec![ServerChange::Network(vec![ServerNetworkChange::Port(U16Change(80, 81))])])
+	);
+	assert_eq!(before.comparison_masked(&after, &FieldMask::exclude(["host", "port"])), Changed::Unchanged);
+}
+
+#[test]
+fn test_group_display_and_tree() {
+	let before = server();
+	let after = Server { port: 81, enabled: false, ..server() };
+	let changes = before.comparison(&after).unwrap_or_default();
+	assert_eq!(display(&changes).to_string(), "network.port: 80 → 81; enabled: true → false");
+	assert_eq!(
+		changes.to_tree(),
+		ChangeTree::Children(vec![
+			("network".to_string(), ChangeTree::Children(vec![("port".to_string(), ChangeTree::modified(&80, &81))])),
+			("enabled".to_string(), ChangeTree::modified(&true, &false)),
+		])
+	);
+}
+
+#[test]
+fn test_group_merge_key() {
+	let before = server();
+	let after = Server { port: 81, enabled: false, ..server() };
+	let changes = before.comparison(&after).unwrap_or_default();
+	assert_eq!(changes.iter().map(MergeKey::merge_key).collect::<Vec<_>>(), vec!["network", "enabled"]);
+}
@@ -0,0 +1,64 @@
+use comparable::{History, I32Change, StringChange, VecChange};
+
+#[test]
+fn test_history_undo_redo() {
+	let mut history = History::new(vec!["a".to_string()]);
+	assert_eq!(history.undo(), None);
+	assert_eq!(history.redo(), None);
+
+	let mut value = history.current().clone();
+	value.push("b".to_string());
+	assert_eq!(history.record(value.clone()), Some(&vec![VecChange::Added(1, "b".to_string())]));
+	// Recording an identical state does not add a step.
+	assert_eq!(history.record(value.clone()), None);
+	assert_eq!(history.undo_len(), 1);
+
+	value[0] = "c".to_string();
+	history.record(value);
+	assert_eq!(history.undo_len(), 2);
+
+	assert_eq!(history.undo(), Some(&vec![VecChange::Changed(0, StringChange("c".to_string(), "a".to_string()))]));
+	assert_eq!(history.undo(), Some(&vec![VecChange::Removed(1, "b".to_string())]));
+	assert!(!history.can_undo());
+	assert_eq!(history.redo_len(), 2);
+
+	assert_eq!(history.redo(), Some(&vec![VecChange::Added(1, "b".to_string())]));
+	assert_eq!(history.current(), &vec!["a".to_string(), "b".to_string()]);
+
+	// Recording after an undo discards the redo steps.
+	history.record(vec![]);
+	assert!(!history.can_redo());
+	assert_eq!(history.undo_len(), 2);
+}
+
+#[test]
+fn test_history_compact() {
+	let mut history = History::new(0);
+	for i in 1..=10 {
+		history.record(i);
+	}
+	assert_eq!(history.undo_len(), 10);
+
+	history.compact(3);
+	assert_eq!(history.undo_len(), 3);
+	assert_eq!(*history.current(), 10);
+	history.undo();
+	history.undo();
+	assert_eq!(*history.current(), 8);
+	// The oldest remaining step goes straight back to the initial state.
+	assert_eq!(history.undo(), Some(&I32Change(8, 0)));
+	assert_eq!(*history.current(), 0);
+	assert_eq!(history.redo(), Some(&I32Change(0, 8)));
+
+	history.compact(0);
+	assert_eq!(history.redo_len(), 2);
+
+	// Steps composing to no change at all are dropped.
+	let mut history = History::new(0);
+	history.record(1);
+	history.record(0);
+	history.record(2);
+	history.compact(2);
+	assert_eq!(history.undo_len(), 1);
+	assert_eq!(history.undo(), Some(&I32Change(2, 0)));
+}
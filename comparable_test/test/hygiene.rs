@@ -0,0 +1,184 @@
+use comparable::roundtrip::FromDesc;
+use comparable::{Changed::*, *};
+
+// Fields named as the locals used in generated code, which must not capture
+// or shadow them.
+
+#[test]
+fn test_hygiene_struct() {
+	#[derive(Comparable)]
+	#[comparable_display]
+	#[comparable_tree]
+	#[comparable_schema]
+	#[comparable_paths]
+	#[comparable_lens]
+	struct Locals {
+		self_var0: u8,
+		other_var0: u8,
+		other: u8,
+		changes: u8,
+		changes_var0: u8,
+		var0: u8,
+		change0: u8,
+		__value: u8,
+		path: u8,
+		lines: u8,
+		f: u8,
+	}
+
+	let before = Locals {
+		self_var0: 0,
+		other_var0: 0,
+		other: 0,
+		changes: 0,
+		changes_var0: 0,
+		var0: 0,
+		change0: 0,
+		__value: 0,
+		path: 0,
+		lines: 0,
+		f: 0,
+	};
+	let after = Locals { other: 1, changes: 2, self_var0: 3, ..before };
+	assert_changes!(
+		&before,
+		&after,
+		Changed(vec![
+			LocalsChange::SelfVar0(U8Change(0, 3)),
+			LocalsChange::Other(U8Change(0, 1)),
+			LocalsChange::Changes(U8Change(0, 2)),
+		])
+	);
+}
+
+#[test]
+fn test_hygiene_enum() {
+	#[derive(Comparable)]
+	#[comparable_display]
+	#[comparable_tree]
+	#[comparable_transitions]
+	#[allow(dead_code)]
+	enum Locals {
+		Swapped { other_var0: u8, self_var0: u8, other: u8 },
+		Named { changes: u8, changes_var0: u8, var0: u8, change0: u8, __value: u8 },
+		Unnamed(u8, u8),
+	}
+
+	assert_changes!(
+		&Locals::Swapped { other_var0: 0, self_var0: 1, other: 2 },
+		&Locals::Swapped { other_var0: 0, self_var0: 3, other: 2 },
+		Changed(LocalsChange::BothSwapped {
+			other_var0: Unchanged,
+			self_var0: Changed(U8Change(1, 3)),
+			other: Unchanged
+		})
+	);
+	assert_changes!(
+		&Locals::Named { changes: 0, changes_var0: 1, var0: 2, change0: 3, __value: 4 },
+		&Locals::Named { changes: 5, changes_var0: 1, var0: 2, change0: 3, __value: 6 },
+		Changed(LocalsChange::BothNamed {
+			changes: Changed(U8Change(0, 5)),
+			changes_var0: Unchanged,
+			var0: Unchanged,
+			change0: Unchanged,
+			__value: Changed(U8Change(4, 6)),
+		})
+	);
+}
+
+#[test]
+fn test_hygiene_attributes() {
+	#[derive(Comparable, Clone, PartialEq, Debug, Default)]
+	#[comparable_desc_roundtrip]
+	struct Locals {
+		other: String,
+		#[comparable_group("changes")]
+		changes: u8,
+		#[comparable_group("changes")]
+		group_changes: u8,
+		__value: u8,
+	}
+
+	#[derive(Comparable)]
+	#[comparable_sql(table = "locals", key = "other")]
+	struct Sql {
+		other: String,
+		changes: u8,
+	}
+
+	#[derive(Comparable)]
+	#[variant_struct_fields]
+	enum Helpers {
+		Named { self_var0: u8, other_var0: u8, changes_var0: u8 },
+	}
+
+	let before = Locals { other: "a".to_string(), ..Default::default() };
+	let after = Locals { changes: 1, group_changes: 2, __value: 3, ..before.clone() };
+	assert_eq!(before.comparison(&after).unwrap_or_default().len(), 2);
+	assert_eq!(Locals::from_desc(after.describe()), after);
+	assert_eq!(
+		Sql { other: "a".to_string(), changes: 0 }
+			.comparison(&Sql { other: "a".to_string(), changes: 1 })
+			.map(|changes| changes.len()),
+		Changed(1)
+	);
+	assert_changes!(
+		&Helpers::Named { self_var0: 0, other_var0: 1, changes_var0: 2 },
+		&Helpers::Named { self_var0: 0, other_var0: 3, changes_var0: 2 },
+		Changed(HelpersChange::BothNamed(vec![HelpersNamedChange::OtherVar0(U8Change(1, 3))]))
+	);
+}
+
+// Functions named as the locals used in generated code, called from
+// expressions given to `comparable_by`.
+fn var0(value: &u8) -> String {
+	format!("#{}", value)
+}
+
+fn self_var0(value: &u8) -> String {
+	format!("#{}", value)
+}
+
+fn other_var0(value: &u8) -> String {
+	format!("#{}", value)
+}
+
+fn __value(value: &u8) -> String {
+	format!("#{}", value)
+}
+
+fn changes(value: &u8) -> String {
+	format!("#{}", value)
+}
+
+#[test]
+fn test_hygiene_attribute_expressions() {
+	#[derive(Comparable)]
+	enum Tagged {
+		Tag(
+			#[comparable_by(desc = "var0(self)")] u8,
+			#[comparable_by(desc = "self_var0(self)", eq = "other_var0(self)")] u8,
+			#[comparable_by(desc = "__value(self)")] u8,
+		),
+	}
+
+	#[derive(Comparable)]
+	struct Tags {
+		#[comparable_by(desc = "changes(self)")]
+		first: u8,
+		#[comparable_by(desc = "changes(self)")]
+		second: u8,
+	}
+
+	assert_changes!(
+		&Tags { first: 1, second: 2 },
+		&Tags { first: 1, second: 3 },
+		Changed(vec![TagsChange::Second(StringChange("#2".to_string(), "#3".to_string()))])
+	);
+	assert_eq!(Tagged::Tag(1, 2, 3).describe(), TaggedDesc::Tag("#1".to_string(), "#2".to_string(), "#3".to_string()));
+	assert_changes!(
+		&Tagged::Tag(1, 2, 3),
+		&Tagged::Tag(1, 4, 3),
+		Changed(TaggedChange::BothTag(Unchanged, Changed(StringChange("#2".to_string(), "#4".to_string())), Unchanged))
+	);
+}
@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+
+use comparable::ignore::{with_ignore_set, PatternError};
+use comparable::mask::{with_field_mask, FieldMask};
+use comparable::tree::{ChangeTree, ToTree};
+use comparable::*;
+
+#[derive(Comparable, Clone, PartialEq)]
+#[comparable_tree]
+struct Metrics {
+	requests: u64,
+	errors: u64,
+}
+
+#[derive(Comparable, Clone, PartialEq)]
+#[comparable_tree]
+struct Item {
+	name: String,
+	updated_at: u64,
+}
+
+#[derive(Comparable, Clone, PartialEq)]
+#[comparable_tree]
+struct Service {
+	name: String,
+	updated_at: u64,
+	metrics: Metrics,
+	items: Vec<Item>,
+	labels: BTreeMap<String, String>,
+}
+
+fn service() -> Service {
+	Service {
+		name: "api".to_string(),
+		updated_at: 1,
+		metrics: Metrics { requests: 10, errors: 0 },
+		items: vec![Item { name: "a".to_string(), updated_at: 1 }],
+		labels: BTreeMap::new(),
+	}
+}
+
+fn changed() -> Service {
+	Service {
+		name: "api".to_string(),
+		updated_at: 2,
+		metrics: Metrics { requests: 20, errors: 1 },
+		items: vec![Item { name: "b".to_string(), updated_at: 2 }],
+		labels: vec![("tier".to_string(), "web".to_string())].into_iter().collect(),
+	}
+}
+
+#[test]
+fn test_ignore_patterns() {
+	let ignore = IgnoreSet::new(["*.updated_at", "metrics.**", "lab?ls"]).unwrap();
+	assert_eq!(ignore.patterns().collect::<Vec<_>>(), vec!["*.updated_at", "metrics.**", "lab?ls"]);
+	assert!(ignore.is_ignored("items.updated_at"));
+	assert!(!ignore.is_ignored("updated_at"));
+	assert!(!ignore.is_ignored("a.b.updated_at"));
+	assert!(ignore.is_ignored("metrics"));
+	assert!(ignore.is_ignored("metrics.requests.total"));
+	assert!(ignore.is_ignored("labels.tier"));
+	assert!(!ignore.is_ignored("name"));
+
+	let ignore = IgnoreSet::new(["**.updated_at", "*_count"]).unwrap();
+	assert!(ignore.is_ignored("updated_at"));
+	assert!(ignore.is_ignored("a.b.updated_at"));
+	assert!(ignore.is_ignored("retry_count"));
+	assert!(!ignore.is_ignored("count"));
+
+	assert_eq!(IgnoreSet::parse("\n# comment\n  name  \n").unwrap(), IgnoreSet::new(["name"]).unwrap());
+	assert_eq!(
+		IgnoreSet::new(["a..b"]),
+		Err(PatternError { pattern: "a..b".to_string(), reason: "a field name is empty" })
+	);
+	assert!(IgnoreSet::new([""]).is_err());
+	assert_eq!(
+		IgnoreSet::parse("a**").unwrap_err().to_string(),
+		"invalid ignore pattern `a**`: `**` must stand for a whole field name"
+	);
+}
+
+#[test]
+fn test_ignore_comparison() {
+	let (before, after) = (service(), changed());
+	let ignore = IgnoreSet::parse("updated_at\nitems.updated_at\nmetrics.**\nlabels").unwrap();
+	assert_eq!(
+		with_ignore_set(&ignore, || before.comparison(&after)),
+		Changed::Changed(vec![ServiceChange::Items(vec![VecChange::Changed(
+			0,
+			vec![ItemChange::Name(StringChange("a".to_string(), "b".to_string()))]
+		)])])
+	);
+
+	let ignore = IgnoreSet::new(["**.updated_at", "metrics.req*", "items", "labels"]).unwrap();
+	assert_eq!(
+		with_ignore_set(&ignore, || before.comparison(&after)),
+		Changed::Changed(vec![ServiceChange::Metrics(vec![MetricsChange::Errors(U64Change(0, 1))])])
+	);
+
+	// An empty set ignores nothing, and the set replaces any mask in effect.
+	assert_eq!(with_ignore_set(&IgnoreSet::default(), || before.comparison(&after)), before.comparison(&after));
+	assert_eq!(
+		with_field_mask(&FieldMask::include(["name"]), || {
+			with_ignore_set(&IgnoreSet::new(["**"]).unwrap(), || before.comparison(&after))
+		}),
+		Changed::Unchanged
+	);
+}
+
+#[test]
+fn test_ignore_filter_tree() {
+	let tree = service().comparison(&changed()).unwrap_or_default().to_tree();
+	let ignore = IgnoreSet::new(["updated_at", "items.*.updated_at", "metrics.**", "labels"]).unwrap();
+	assert_eq!(
+		ignore.filter_tree(tree.clone()).rows(),
+		vec![("items.0.name".to_string(), "\"a\"".to_string(), "\"b\"".to_string())]
+	);
+	assert_eq!(IgnoreSet::default().filter_tree(tree.clone()), tree);
+	assert_eq!(IgnoreSet::new(["**"]).unwrap().filter_tree(tree.clone()), ChangeTree::Children(vec![]));
+	assert!(IgnoreSet::new(["*"]).unwrap().filter_tree(tree).is_empty());
+}
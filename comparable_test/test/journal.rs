@@ -0,0 +1,53 @@
+use comparable::{Changed::*, Comparable, I32Change, Journal, StringChange};
+
+#[derive(Comparable, Clone, Debug, PartialEq)]
+struct Account {
+	owner: String,
+	balance: i32,
+}
+
+fn apply(account: &mut Account, changes: &[AccountChange]) {
+	for change in changes {
+		match change {
+			AccountChange::Owner(StringChange(_, owner)) => account.owner = owner.clone(),
+			AccountChange::Balance(I32Change(_, balance)) => account.balance = *balance,
+		}
+	}
+}
+
+#[test]
+fn test_journal_replay() {
+	let mut account = Account { owner: "alice".to_string(), balance: 0 };
+	let mut journal = Journal::new(account.clone());
+	assert!(journal.is_empty());
+
+	account.balance = 100;
+	assert!(journal.record(&account).is_some());
+	assert!(journal.record(&account).is_none());
+	account.owner = "bob".to_string();
+	account.balance = 50;
+	journal.record(&account);
+
+	assert_eq!(journal.len(), 2);
+	assert_eq!(journal.entries()[0].change, vec![AccountChange::Balance(I32Change(0, 100))]);
+	assert!(journal.entries()[0].timestamp <= journal.entries()[1].timestamp);
+	assert_eq!(journal.replay(|account, changes| apply(account, changes)), account);
+	assert!(!journal.snapshot().comparison(&account).is_unchanged());
+
+	journal.compact();
+	assert!(journal.is_empty());
+	assert_eq!(journal.snapshot().comparison(&account), Unchanged);
+	assert_eq!(journal.replay(|account, changes| apply(account, changes)), account);
+}
+
+#[test]
+fn test_journal_periodic_compaction() {
+	let mut journal = Journal::new(0).with_compaction(3);
+	for i in 1..=7 {
+		journal.record(&i);
+	}
+	// Compaction happened when the fourth and seventh values were recorded.
+	assert_eq!(journal.len(), 1);
+	assert_eq!(*journal.snapshot(), 6);
+	assert_eq!(journal.replay(|value, change: &I32Change| *value = change.1), 7);
+}
@@ -0,0 +1,181 @@
+use comparable::format::json_patch::{apply, diff, Operation};
+use comparable::json::ValueChange;
+use comparable::lens::{Apply, ApplyError, ConflictError};
+use comparable::location::{Path, Segment};
+use comparable::tree::{ChangeTree, ToTree};
+use comparable::*;
+use serde_json::{json, Value};
+
+fn pairs() -> Vec<(Value, Value)> {
+	vec![
+		(json!(1), json!(2)),
+		(json!("a"), json!({ "a": 1 })),
+		(json!([1, 2, 3]), json!([1, 5, 3])),
+		(json!([1, 2, 3, 4]), json!([4, 2, 9, 1, 3])),
+		(json!([{ "id": 1 }, { "id": 2 }]), json!([{ "id": 2 }, { "id": 1, "x": true }])),
+		(json!({ "a": 1, "b": [1, 2] }), json!({ "b": [2], "c": null })),
+		(json!({ "a/b": { "c~d": 1 }, "e": 2 }), json!({ "a/b": { "c~d": 2 }, "f": 2 })),
+		(json!({ "a": { "b": { "c": [1, { "d": 1 }] } } }), json!({ "a": { "b": { "c": [{ "d": 2 }] } } })),
+	]
+}
+
+fn options() -> Vec<DiffOptions> {
+	vec![
+		DiffOptions::default(),
+		DiffOptions { sequence_moves: true, map_moves: Some(1.0), ..DiffOptions::default() },
+		DiffOptions { sequence_algorithm: Some(DiffAlgorithm::Myers), ..DiffOptions::default() },
+		DiffOptions { sequence_similarity: Some(0.5), map_moves: Some(0.5), ..DiffOptions::default() },
+	]
+}
+
+#[test]
+fn test_value_comparison() {
+	assert_eq!(json!({ "a": [1, 2] }).comparison(&json!({ "a": [1, 2] })), Changed::Unchanged);
+	assert_eq!(json!(1).comparison(&json!(1.0)), Changed::Changed(ValueChange::Replaced(json!(1), json!(1.0))));
+	assert_eq!(
+		json!([1, 2]).comparison(&json!([1, 3, 4])),
+		Changed::Changed(ValueChange::Array(vec![
+			VecChange::Changed(1, ValueChange::Replaced(json!(2), json!(3))),
+			VecChange::Added(2, json!(4)),
+		]))
+	);
+	assert_eq!(
+		json!({ "b": 1, "a": { "x": true } }).comparison(&json!({ "a": { "x": false }, "c": 1 })),
+		Changed::Changed(ValueChange::Object(vec![
+			MapChange::Changed(
+				"a".to_string(),
+				ValueChange::Object(vec![MapChange::Changed(
+					"x".to_string(),
+					ValueChange::Replaced(json!(true), json!(false))
+				)])
+			),
+			MapChange::Added("c".to_string(), json!(1)),
+			MapChange::Removed("b".to_string()),
+		]))
+	);
+	assert_eq!(
+		with_diff_options(DiffOptions { map_moves: Some(1.0), ..DiffOptions::default() }, || {
+			json!({ "a": [1] }).comparison(&json!({ "b": [1] }))
+		}),
+		Changed::Changed(ValueChange::Object(vec![MapChange::Moved {
+			from: "a".to_string(),
+			to: "b".to_string(),
+			change: Changed::Unchanged
+		}]))
+	);
+
+	let (before, after) = (json!({ "a": [1, 2], "b": 1 }), json!({ "a": [1, 3, 4], "b": 1, "c": 1 }));
+	assert!(before.differs(&after));
+	assert!(!before.differs(&before));
+	assert_eq!(before.count_changes(&after), 3);
+	assert_eq!(
+		before.first_difference(&after),
+		Some(Path::from(vec![Segment::Key("\"a\"".to_string()), Segment::Index(1)]))
+	);
+	assert_eq!(json!(1).first_difference(&json!([1])), Some(Path::default()));
+}
+
+#[test]
+fn test_apply_value() {
+	for options in options() {
+		for (before, after) in pairs() {
+			let changes = with_diff_options(options, || before.comparison(&after)).unwrap_changed();
+			assert_eq!(before.check(&changes), Ok(()));
+			let mut value = before.clone();
+			assert_eq!(value.try_apply(changes), Ok(()));
+			assert_eq!(value, after, "{} with {:?}", before, options);
+		}
+	}
+
+	let object = json!({ "a": 1 });
+	assert_eq!(json!([1]).check(&ValueChange::Object(vec![])), Err(ApplyError::VariantMismatch(Path::default())));
+	assert_eq!(
+		object.check(&ValueChange::Object(vec![MapChange::Removed("b".to_string())])),
+		Err(ApplyError::MissingKey(Path::from(vec![Segment::Key("\"b\"".to_string())])))
+	);
+	assert_eq!(
+		object.verify(&ValueChange::Object(vec![MapChange::Changed(
+			"a".to_string(),
+			ValueChange::Replaced(json!(2), json!(3))
+		)])),
+		Err(ConflictError::Stale {
+			path: Path::from(vec![Segment::Key("\"a\"".to_string())]),
+			expected: "Number(2)".to_string(),
+			found: "Number(1)".to_string(),
+		})
+	);
+
+	// Members removed must hold the values they held in the value the
+	// changes were made from.
+	let base = json!({ "a": [{ "b": 1, "c": 2 }] });
+	let changes = base.comparison(&json!({ "a": [{ "b": 1 }] })).unwrap_changed();
+	let edited = json!({ "a": [{ "b": 1, "c": 3 }] });
+	assert_eq!(edited.verify(&changes), Ok(()));
+	assert_eq!(
+		edited.verify_from(&base, &changes),
+		Err(ConflictError::Stale {
+			path: Path::from(vec![
+				Segment::Key("\"a\"".to_string()),
+				Segment::Index(0),
+				Segment::Key("\"c\"".to_string())
+			]),
+			expected: "Number(2)".to_string(),
+			found: "Number(3)".to_string(),
+		})
+	);
+	assert_eq!(json!({ "a": [{ "b": 5, "c": 2 }] }).verify_from(&base, &changes), Ok(()));
+}
+
+#[test]
+fn test_json_patch_diff() {
+	for options in options() {
+		for (before, after) in pairs() {
+			let patch = with_diff_options(options, || diff(&before, &after));
+			let mut doc = before.clone();
+			assert_eq!(apply(&mut doc, &patch), Ok(()), "{:?}", patch);
+			assert_eq!(doc, after, "{} with {:?}: {:?}", before, options, patch);
+		}
+	}
+
+	assert_eq!(diff(&json!({ "a": 1 }), &json!({ "a": 1 })), vec![]);
+	assert_eq!(diff(&json!(1), &json!("1")), vec![Operation::Replace { path: String::new(), value: json!("1") }]);
+	assert_eq!(
+		diff(&json!({ "a/b": [1, 2, 3], "c~d": 1 }), &json!({ "a/b": [0, 1], "e": 1 })),
+		vec![
+			Operation::Remove { path: "/a~1b/2".to_string() },
+			Operation::Replace { path: "/a~1b/0".to_string(), value: json!(0) },
+			Operation::Replace { path: "/a~1b/1".to_string(), value: json!(1) },
+			Operation::Add { path: "/e".to_string(), value: json!(1) },
+			Operation::Remove { path: "/c~0d".to_string() },
+		]
+	);
+	assert_eq!(
+		with_diff_options(DiffOptions { sequence_moves: true, map_moves: Some(1.0), ..DiffOptions::default() }, || {
+			diff(&json!({ "a": { "x": 1 }, "l": [1, 2] }), &json!({ "b": { "x": 1 }, "l": [2, 1] }))
+		}),
+		vec![
+			Operation::Move { from: "/a".to_string(), path: "/b".to_string() },
+			Operation::Remove { path: "/l/1".to_string() },
+			Operation::Remove { path: "/l/0".to_string() },
+			Operation::Add { path: "/l/0".to_string(), value: json!(2) },
+			Operation::Add { path: "/l/1".to_string(), value: json!(1) },
+		]
+	);
+}
+
+#[test]
+fn test_value_rendering() {
+	let changes = json!({ "a": [1, 2], "b": "x" }).comparison(&json!({ "a": [1], "b": "y", "c": null }));
+	assert_eq!(
+		comparable::display::display(&changes).to_string(),
+		"[\"a\"][1]: removed 2; [\"b\"]: \"x\" → \"y\"; [\"c\"]: added null"
+	);
+	assert_eq!(
+		changes.to_tree(),
+		ChangeTree::Children(vec![
+			("\"a\"".to_string(), ChangeTree::Children(vec![("1".to_string(), ChangeTree::Removed("2".to_string()))])),
+			("\"b\"".to_string(), ChangeTree::Modified { before: "\"x\"".to_string(), after: "\"y\"".to_string() }),
+			("\"c\"".to_string(), ChangeTree::Added("null".to_string())),
+		])
+	);
+}
@@ -0,0 +1,98 @@
+use comparable::display::display;
+use comparable::lens::Apply;
+use comparable::roundtrip::{FromDesc, IntoDesc};
+use comparable::*;
+
+// A type from elsewhere that does not implement `Comparable`.
+#[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+struct Version {
+	major: u32,
+	minor: u32,
+}
+
+fn version(major: u32, minor: u32) -> Version {
+	Version { major, minor }
+}
+
+#[derive(Clone, Comparable)]
+#[comparable_display]
+#[comparable_lens]
+#[comparable_desc_roundtrip]
+struct Package {
+	name: String,
+	#[comparable_leaf]
+	version: Version,
+}
+
+#[derive(Comparable)]
+enum Dependency {
+	Local(String),
+	Registry(#[comparable_leaf] Version),
+}
+
+#[derive(Comparable)]
+struct Pinned<T> {
+	#[comparable_leaf]
+	pin: T,
+}
+
+#[test]
+fn test_leaf() {
+	assert_changes!(&Leaf(version(1, 0)), &Leaf(version(1, 0)), Changed::Unchanged);
+	assert_changes!(&Leaf(version(1, 0)), &Leaf(version(1, 1)), Changed::Changed((version(1, 0), version(1, 1))));
+
+	let mut leaf = Leaf::from(version(1, 0));
+	leaf.minor = 2;
+	assert_eq!(leaf.describe(), version(1, 2));
+	leaf.apply((version(1, 2), version(2, 0)));
+	assert_eq!(leaf.into_inner(), version(2, 0));
+}
+
+#[test]
+fn test_leaf_field() {
+	let before = Package { name: "a".to_string(), version: version(1, 0) };
+	let after = Package { name: "a".to_string(), version: version(1, 1) };
+	assert_eq!(before.describe(), PackageDesc { name: "a".to_string(), version: version(1, 0) });
+	assert_eq!(before.comparison(&before.clone()), Changed::Unchanged);
+	let changes = before.comparison(&after);
+	assert_eq!(changes, Changed::Changed(vec![PackageChange::Version((version(1, 0), version(1, 1)))]));
+	assert_eq!(
+		display(&changes).to_string(),
+		"version: Version { major: 1, minor: 0 } → Version { major: 1, minor: 1 }"
+	);
+
+	let mut updated = before.clone();
+	updated.apply(changes.unwrap_changed());
+	assert!(updated.version == after.version);
+
+	let restored = Package::from_desc(after.clone().into_desc());
+	assert!(restored.version == after.version);
+}
+
+#[test]
+fn test_leaf_in_enum() {
+	let registry = Dependency::Registry(version(1, 0));
+	assert_changes!(&registry, &Dependency::Registry(version(1, 0)), Changed::Unchanged);
+	assert_changes!(
+		&registry,
+		&Dependency::Registry(version(2, 0)),
+		Changed::Changed(DependencyChange::BothRegistry((version(1, 0), version(2, 0))))
+	);
+	assert_changes!(
+		&registry,
+		&Dependency::Local("..".to_string()),
+		Changed::Changed(DependencyChange::Different(
+			DependencyDesc::Registry(version(1, 0)),
+			DependencyDesc::Local("..".to_string())
+		))
+	);
+}
+
+#[test]
+fn test_generic_leaf_field() {
+	assert_changes!(
+		&Pinned { pin: version(1, 0) },
+		&Pinned { pin: version(1, 1) },
+		Changed::Changed(PinnedChange { pin: (version(1, 0), version(1, 1)) })
+	);
+}
@@ -0,0 +1,466 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use comparable::diff::DiffAlgorithm;
+use comparable::lens::{Apply, ApplyError, ConflictError};
+use comparable::location::{Path, Segment};
+use comparable::*;
+
+#[derive(Comparable, Clone, PartialEq, Debug)]
+#[comparable_lens]
+#[comparable_desc_roundtrip]
+struct Address {
+	street: String,
+	city: String,
+}
+
+#[derive(Comparable, Clone, PartialEq, Debug)]
+#[comparable_lens]
+struct Customer {
+	name: String,
+	address: Address,
+	nickname: Option<String>,
+	orders: BTreeMap<u32, Address>,
+	#[comparable_redact]
+	password: String,
+	#[comparable_ignore]
+	visits: u64,
+}
+
+#[derive(Comparable, Clone, PartialEq, Debug)]
+#[comparable_lens]
+struct Wrapper(u32);
+
+#[derive(Comparable, Clone, PartialEq, Debug)]
+#[comparable_lens]
+struct Pair<T>(T, T);
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Debug)]
+struct Version(u32, u32);
+
+#[derive(Comparable, Clone, PartialEq, Debug)]
+#[comparable_lens]
+struct Document {
+	title: String,
+	#[comparable_leaf]
+	version: Version,
+	scores: BTreeMap<String, u32>,
+}
+
+#[derive(Comparable, Clone, PartialEq, Debug)]
+#[comparable_lens]
+#[comparable_desc_roundtrip]
+enum Shape {
+	Point,
+	Circle(u32),
+	Segment(i32, i32),
+	Labelled { label: Option<String>, sides: u8 },
+}
+
+#[derive(Comparable, Clone, PartialEq, Debug)]
+#[comparable_lens]
+enum Only {
+	Value(u8),
+}
+
+fn address(street: &str, city: &str) -> Address {
+	Address { street: street.to_string(), city: city.to_string() }
+}
+
+fn customers() -> (Customer, Customer) {
+	let before = Customer {
+		name: "Alice".to_string(),
+		address: address("Main St", "Springfield"),
+		nickname: None,
+		orders: vec![(1, address("Main St", "Springfield")), (2, address("Elm St", "Springfield"))]
+			.into_iter()
+			.collect(),
+		password: "secret".to_string(),
+		visits: 1,
+	};
+	let after = Customer {
+		name: "Alice".to_string(),
+		address: address("Main St", "Shelbyville"),
+		nickname: Some("Al".to_string()),
+		orders: vec![(1, address("Oak St", "Springfield")), (3, address("Elm St", "Ogdenville"))].into_iter().collect(),
+		password: "secret".to_string(),
+		visits: 2,
+	};
+	(before, after)
+}
+
+#[test]
+fn test_lens_change() {
+	let (before, after) = customers();
+	let changes = before.comparison(&after).unwrap_or_default();
+	assert_eq!(Customer::name_lens().change(&changes), None);
+	assert_eq!(
+		Customer::address_lens().change(&changes),
+		Some(&vec![AddressChange::City(StringChange("Springfield".to_string(), "Shelbyville".to_string()))])
+	);
+	assert_eq!(
+		Customer::nickname_lens().change(&changes),
+		Some(&OptionChange::Different(None, Some("Al".to_string())))
+	);
+
+	let lens = Customer::address_lens();
+	assert_eq!(lens.name(), "address");
+	assert_eq!(lens.get(&after), &after.address);
+	let mut value = before.clone();
+	lens.get_mut(&mut value).city = "Shelbyville".to_string();
+	assert_eq!(value.address, after.address);
+}
+
+#[test]
+fn test_lens_apply() {
+	let (before, after) = customers();
+	let mut value = before.clone();
+	Customer::address_lens().apply(&mut value, before.address.comparison(&after.address).unwrap_or_default());
+	assert_eq!(value.address, after.address);
+
+	value.apply(before.comparison(&after).unwrap_or_default());
+	// Ignored fields are not compared, and so are left as they were.
+	assert_eq!(value, Customer { visits: 1, ..after });
+
+	let mut wrapper = Wrapper(1);
+	let change = Wrapper(1).comparison(&Wrapper(2)).next().unwrap();
+	assert_eq!(Wrapper::field_0_lens().change(&change), Some(&U32Change(1, 2)));
+	wrapper.apply(change);
+	assert_eq!(wrapper, Wrapper(2));
+
+	let mut pair = Pair(1u8, 2u8);
+	Pair::field_1_lens().apply(&mut pair, U8Change(2, 3));
+	assert_eq!(pair, Pair(1, 3));
+}
+
+#[test]
+fn test_lens_check() {
+	let (before, after) = customers();
+	let changes = before.comparison(&after).unwrap_or_default();
+	assert_eq!(before.check(&changes), Ok(()));
+	// Checking does not change the value.
+	assert_eq!(before, customers().0);
+
+	// Changes from another value are refused where they do not fit.
+	let at = |segments: Vec<Segment>| Path::from(segments);
+	let field = |name: &str| Segment::Field(name.to_string());
+	let mut other = before.clone();
+	other.orders.remove(&1);
+	assert_eq!(
+		other.check(&changes),
+		Err(ApplyError::MissingKey(at(vec![field("orders"), Segment::Key("1".to_string())])))
+	);
+	let mut other = before.clone();
+	other.orders.insert(3, address("Elm St", "Springfield"));
+	assert_eq!(
+		other.check(&changes),
+		Err(ApplyError::KeyExists(at(vec![field("orders"), Segment::Key("3".to_string())])))
+	);
+	let with_nickname = Customer { nickname: Some("Ally".to_string()), ..before.clone() };
+	let changes =
+		with_nickname.comparison(&Customer { nickname: Some("Al".to_string()), ..before.clone() }).unwrap_or_default();
+	assert_eq!(with_nickname.check(&changes), Ok(()));
+	assert_eq!(before.check(&changes), Err(ApplyError::VariantMismatch(at(vec![field("nickname")]))));
+	assert_eq!(before.check(&changes).unwrap_err().to_string(), "nickname is a different variant than the change");
+
+	// Entries may be removed and added again, in order.
+	let orders = &before.orders;
+	let readded = vec![MapChange::Removed(1), MapChange::Added(1, address("Oak St", "Springfield").describe())];
+	assert_eq!(orders.check(&readded), Ok(()));
+	let moved = vec![MapChange::Moved { from: 2, to: 1, change: Changed::Unchanged }];
+	assert_eq!(orders.check(&moved), Err(ApplyError::KeyExists(at(vec![Segment::Key("1".to_string())]))));
+	let moved = vec![MapChange::Moved { from: 2, to: 4, change: Changed::Unchanged }, MapChange::Removed(4)];
+	assert_eq!(orders.check(&moved), Ok(()));
+
+	assert_eq!(Wrapper(1).check(&Wrapper(1).comparison(&Wrapper(2)).next().unwrap()), Ok(()));
+}
+
+#[test]
+fn test_lens_try_apply() {
+	let (before, after) = customers();
+	let changes = || before.comparison(&after).unwrap_or_default();
+	assert_eq!(before.verify(&changes()), Ok(()));
+	let mut value = before.clone();
+	assert_eq!(value.try_apply(changes()), Ok(()));
+	assert_eq!(value, Customer { visits: 1, ..after.clone() });
+
+	// Changes made from a value that has since changed are refused, leaving
+	// the value as it was.
+	let stale = |path: Vec<Segment>, expected: &str, found: &str| ConflictError::Stale {
+		path: Path::from(path),
+		expected: expected.to_string(),
+		found: found.to_string(),
+	};
+	let field = |name: &str| Segment::Field(name.to_string());
+	let mut moved = before.clone();
+	moved.address.city = "Capital City".to_string();
+	assert_eq!(
+		moved.try_apply(changes()),
+		Err(stale(vec![field("address"), field("city")], "\"Springfield\"", "\"Capital City\""))
+	);
+	assert_eq!(moved.address.city, "Capital City");
+	let mut named = Customer { nickname: Some("Ally".to_string()), ..before.clone() };
+	assert_eq!(named.try_apply(changes()), Err(stale(vec![field("nickname")], "None", "Some(\"Ally\")")));
+	let mut reordered = before.clone();
+	reordered.orders.get_mut(&1).unwrap().street = "Pine St".to_string();
+	assert_eq!(
+		reordered.verify(&changes()),
+		Err(stale(vec![field("orders"), Segment::Key("1".to_string()), field("street")], "\"Main St\"", "\"Pine St\""))
+	);
+	assert_eq!(
+		reordered.verify(&changes()).unwrap_err().to_string(),
+		"orders[1].street was expected to be \"Main St\", but is \"Pine St\""
+	);
+
+	// Changes that do not fit the value are refused as they are by `check`.
+	let mut removed = before.clone();
+	removed.orders.remove(&2);
+	assert_eq!(
+		removed.try_apply(changes()),
+		Err(ConflictError::Invalid(ApplyError::MissingKey(Path::from(vec![
+			field("orders"),
+			Segment::Key("2".to_string())
+		]))))
+	);
+
+	// Fields compared as a whole are verified, though they can always be
+	// replaced.
+	let document = Document { title: "a".to_string(), version: Version(1, 0), scores: BTreeMap::new() };
+	let updated = Document { version: Version(1, 1), ..document.clone() };
+	let changes = document.comparison(&updated).unwrap_or_default();
+	let newer = Document { version: Version(2, 0), ..document.clone() };
+	assert_eq!(newer.check(&changes), Ok(()));
+	assert_eq!(newer.verify(&changes), Err(stale(vec![field("version")], "Version(1, 0)", "Version(2, 0)")));
+	let mut value = document.clone();
+	assert_eq!(value.try_apply(changes), Ok(()));
+	assert_eq!(value, updated);
+}
+
+#[test]
+fn test_apply_vec() {
+	let pairs: Vec<(Vec<u32>, Vec<u32>)> = vec![
+		(vec![1, 2, 3], vec![1, 5, 3]),
+		(vec![1, 2, 3], vec![1]),
+		(vec![1], vec![1, 2, 3]),
+		(vec![1, 2, 3, 4], vec![4, 2, 9, 1, 3]),
+		(vec![5, 1, 2, 3], vec![1, 2, 7, 3, 8]),
+		(vec![], vec![1, 2]),
+	];
+	let options = vec![
+		DiffOptions::default(),
+		DiffOptions { sequence_moves: true, ..DiffOptions::default() },
+		DiffOptions { sequence_algorithm: Some(DiffAlgorithm::Myers), ..DiffOptions::default() },
+		DiffOptions { sequence_algorithm: Some(DiffAlgorithm::Patience), ..DiffOptions::default() },
+		DiffOptions { sequence_similarity: Some(0.5), ..DiffOptions::default() },
+	];
+	for options in options {
+		for (before, after) in &pairs {
+			let changes = with_diff_options(options, || before.comparison(after)).unwrap_or_default();
+			assert_eq!(before.check(&changes), Ok(()));
+			let mut value = before.clone();
+			value.apply(changes);
+			assert_eq!(&value, after, "{:?} with {:?}", before, options);
+		}
+	}
+
+	let mut names = vec![Some("a".to_string()), None];
+	names.apply(vec![VecChange::Changed(0, OptionChange::Different(Some("a".to_string()), None))]);
+	assert_eq!(names, vec![None, None]);
+}
+
+#[test]
+fn test_check_vec() {
+	let at = |segments: Vec<Segment>| Path::from(segments);
+	let values = vec![1u8, 2, 3];
+	assert_eq!(
+		values.check(&vec![VecChange::Removed(3, 4)]),
+		Err(ApplyError::IndexOutOfRange(at(vec![Segment::Index(3)])))
+	);
+	assert_eq!(
+		values.check(&vec![VecChange::Removed(2, 3), VecChange::Changed(2, U8Change(3, 4))]),
+		Err(ApplyError::IndexOutOfRange(at(vec![Segment::Index(2)])))
+	);
+	assert_eq!(
+		values.check(&vec![VecChange::Added(5, 4)]).unwrap_err().to_string(),
+		"[5] is beyond the end of the vector"
+	);
+	assert_eq!(
+		values.check(&vec![VecChange::Removed(1, 2), VecChange::Moved { from: 1, to: 0 }]),
+		Err(ApplyError::MissingKey(at(vec![Segment::Index(1)])))
+	);
+	assert_eq!(
+		values.check(&vec![VecChange::Added(1, 4), VecChange::Added(1, 5)]),
+		Err(ApplyError::KeyExists(at(vec![Segment::Index(1)])))
+	);
+
+	// Changes to elements are checked against the elements they were made
+	// from, even when elements before them are removed.
+	let names = vec![None, Some("b".to_string())];
+	let change = OptionChange::BothSome(StringChange("b".to_string(), "c".to_string()));
+	assert_eq!(names.check(&vec![VecChange::Removed(0, None), VecChange::Changed(0, change)]), Ok(()));
+	let change = OptionChange::BothSome(StringChange("b".to_string(), "c".to_string()));
+	assert_eq!(
+		names.check(&vec![VecChange::Changed(0, change)]),
+		Err(ApplyError::VariantMismatch(at(vec![Segment::Index(0)])))
+	);
+	let mut value = names.clone();
+	let change = OptionChange::BothSome(StringChange("b".to_string(), "c".to_string()));
+	value.apply(vec![VecChange::Removed(0, None), VecChange::Changed(0, change)]);
+	assert_eq!(value, vec![Some("c".to_string())]);
+}
+
+#[test]
+fn test_apply_set() {
+	let key = |desc: &str| Path::from(vec![Segment::Key(desc.to_string())]);
+	let before: BTreeSet<u8> = vec![1, 2, 3].into_iter().collect();
+	let after: BTreeSet<u8> = vec![2, 3, 4].into_iter().collect();
+	let changes = before.comparison(&after).unwrap_or_default();
+	assert_eq!(before.check(&changes), Ok(()));
+	let mut value = before.clone();
+	value.apply(changes);
+	assert_eq!(value, after);
+	assert_eq!(before.check(&vec![SetChange::Added(2)]), Err(ApplyError::KeyExists(key("2"))));
+	assert_eq!(before.check(&vec![SetChange::Removed(4)]), Err(ApplyError::MissingKey(key("4"))));
+	assert_eq!(before.check(&vec![SetChange::Removed(2), SetChange::Added(2)]), Ok(()));
+
+	let before: HashSet<String> = vec!["a".to_string(), "b".to_string()].into_iter().collect();
+	let after: HashSet<String> = vec!["b".to_string(), "c".to_string()].into_iter().collect();
+	let changes = before.comparison(&after).unwrap_or_default();
+	assert_eq!(before.check(&changes), Ok(()));
+	let mut value = before.clone();
+	value.apply(changes);
+	assert_eq!(value, after);
+	assert_eq!(after.check(&vec![SetChange::Added("c".to_string())]), Err(ApplyError::KeyExists(key("\"c\""))));
+}
+
+#[test]
+fn test_apply_enum() {
+	let shapes = vec![
+		Shape::Point,
+		Shape::Circle(1),
+		Shape::Circle(2),
+		Shape::Segment(1, 2),
+		Shape::Segment(1, 5),
+		Shape::Labelled { label: None, sides: 3 },
+		Shape::Labelled { label: Some("tri".to_string()), sides: 4 },
+	];
+	for before in &shapes {
+		for after in &shapes {
+			let mut value = before.clone();
+			if let Changed::Changed(changes) = before.comparison(after) {
+				assert_eq!(before.check(&changes), Ok(()));
+				value.apply(changes);
+			}
+			assert_eq!(&value, after);
+		}
+	}
+
+	let mut only = Only::Value(1);
+	only.apply(OnlyChange::BothValue(U8Change(1, 2)));
+	assert_eq!(only, Only::Value(2));
+}
+
+#[test]
+fn test_check_enum() {
+	let at = |segments: Vec<Segment>| Path::from(segments);
+	let circle = Shape::Circle(1).comparison(&Shape::Circle(2)).unwrap_changed();
+	assert_eq!(Shape::Point.check(&circle), Err(ApplyError::VariantMismatch(Path::default())));
+	assert_eq!(
+		Shape::Segment(1, 2).check(&circle).unwrap_err().to_string(),
+		". is a different variant than the change"
+	);
+	// Changes within a value of another variant leave it as it was.
+	let mut point = Shape::Point;
+	point.apply(circle);
+	assert_eq!(point, Shape::Point);
+
+	let labelled = Shape::Labelled { label: Some("tri".to_string()), sides: 3 };
+	let changes =
+		labelled.comparison(&Shape::Labelled { label: Some("triangle".to_string()), sides: 3 }).unwrap_changed();
+	assert_eq!(labelled.check(&changes), Ok(()));
+	assert_eq!(
+		Shape::Labelled { label: None, sides: 3 }.check(&changes),
+		Err(ApplyError::VariantMismatch(at(vec![Segment::Field("label".to_string())])))
+	);
+}
+
+#[test]
+fn test_try_apply_collections() {
+	let stale = |path: Vec<Segment>, expected: &str, found: &str| ConflictError::Stale {
+		path: Path::from(path),
+		expected: expected.to_string(),
+		found: found.to_string(),
+	};
+
+	// Entries added must not have been inserted meanwhile, and given the
+	// value the changes were made from, entries removed must hold the values
+	// they held there.
+	let before: BTreeMap<&str, u32> = vec![("a", 1), ("b", 2)].into_iter().collect();
+	let after: BTreeMap<&str, u32> = vec![("b", 2), ("c", 3)].into_iter().collect();
+	let changes = || before.comparison(&after).unwrap_or_default();
+	assert_eq!(before.verify(&changes()), Ok(()));
+	assert_eq!(before.verify_from(&before, &changes()), Ok(()));
+	let mut updated = before.clone();
+	updated.insert("a", 5);
+	assert_eq!(updated.verify(&changes()), Ok(()));
+	assert_eq!(
+		updated.try_apply_from(&before, changes()),
+		Err(stale(vec![Segment::Key("\"a\"".to_string())], "1", "5"))
+	);
+	let mut inserted = before.clone();
+	inserted.insert("c", 4);
+	assert_eq!(
+		inserted.try_apply(changes()),
+		Err(ConflictError::Invalid(ApplyError::KeyExists(Path::from(vec![Segment::Key("\"c\"".to_string())]))))
+	);
+	assert_eq!(inserted["c"], 4);
+	let mut value = before.clone();
+	assert_eq!(value.try_apply(changes()), Ok(()));
+	assert_eq!(value, after);
+
+	// So are those of maps within other values.
+	let document = |scores: Vec<(&str, u32)>| Document {
+		title: "Notes".to_string(),
+		version: Version(1, 0),
+		scores: scores.into_iter().map(|(name, score)| (name.to_string(), score)).collect(),
+	};
+	let base = document(vec![("ann", 1), ("bo", 2)]);
+	let changes = || base.comparison(&document(vec![("ann", 1)])).unwrap_changed();
+	let mut edited = document(vec![("ann", 1), ("bo", 3)]);
+	assert_eq!(edited.verify(&changes()), Ok(()));
+	assert_eq!(
+		edited.try_apply_from(&base, changes()),
+		Err(stale(vec![Segment::Field("scores".to_string()), Segment::Key("\"bo\"".to_string())], "2", "3"))
+	);
+	let mut edited = document(vec![("ann", 4), ("bo", 2)]);
+	assert_eq!(edited.try_apply_from(&base, changes()), Ok(()));
+	assert_eq!(edited, document(vec![("ann", 4)]));
+
+	// Elements removed from vectors must hold the values they were removed
+	// with, and those changed the values they were changed from.
+	let before = vec![1u8, 2, 3];
+	let changes = || before.comparison(&vec![1, 4]).unwrap_or_default();
+	assert_eq!(vec![1u8, 2, 5].try_apply(changes()), Err(stale(vec![Segment::Index(2)], "3", "5")));
+	assert_eq!(vec![1u8, 6, 3].try_apply(changes()), Err(stale(vec![Segment::Index(1)], "2", "6")));
+	let mut value = before.clone();
+	assert_eq!(value.try_apply(changes()), Ok(()));
+	assert_eq!(value, vec![1, 4]);
+
+	// Changes to another variant must be made from the value's description,
+	// and changes within a variant from its fields.
+	let changes = Shape::Circle(1).comparison(&Shape::Point).unwrap_changed();
+	assert_eq!(Shape::Circle(1).verify(&changes), Ok(()));
+	assert_eq!(Shape::Circle(2).verify(&changes), Err(stale(vec![], "Circle(1)", "Circle(2)")));
+	let changes = Shape::Segment(1, 2).comparison(&Shape::Segment(1, 3)).unwrap_changed();
+	assert_eq!(Shape::Segment(1, 4).try_apply(changes), Err(stale(vec![Segment::Field("1".to_string())], "2", "4")));
+	let changes = Shape::Segment(1, 2).comparison(&Shape::Segment(1, 3)).unwrap_changed();
+	assert_eq!(
+		Shape::Segment(1, 4).verify_from(&Shape::Segment(1, 2), &changes),
+		Err(stale(vec![Segment::Field("1".to_string())], "2", "4"))
+	);
+	assert_eq!(Shape::Segment(1, 2).verify_from(&Shape::Point, &changes), Ok(()));
+	let changes = Shape::Segment(1, 2).comparison(&Shape::Segment(1, 3)).unwrap_changed();
+	assert_eq!(
+		Shape::Point.try_apply(changes),
+		Err(ConflictError::Invalid(ApplyError::VariantMismatch(Path::default())))
+	);
+}
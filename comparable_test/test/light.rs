@@ -0,0 +1,55 @@
+use std::collections::{BTreeMap, HashMap};
+
+use comparable::light::{LightChange, LightComparable};
+use comparable::*;
+
+#[derive(Comparable, Clone, PartialEq, Hash)]
+struct Reading {
+	sensor: String,
+	value: i64,
+}
+
+fn reading(sensor: &str, value: i64) -> Reading {
+	Reading { sensor: sensor.to_string(), value }
+}
+
+#[test]
+fn test_light_vec() {
+	let before = vec![reading("a", 1), reading("b", 2), reading("c", 3)];
+	let after = vec![reading("a", 1), reading("b", 5)];
+	assert_eq!(before.light_comparison(&before), Changed::Unchanged);
+
+	let changes = before.light_comparison(&after).unwrap_or_default();
+	assert!(matches!(changes[..], [LightChange::Changed(1, _, _), LightChange::Removed(2, _)]));
+	assert_eq!(before.hydrate(&after, &changes), before.comparison(&after).next());
+	assert_eq!(
+		after.hydrate(&before, &after.light_comparison(&before).unwrap_or_default()),
+		after.comparison(&before).next()
+	);
+
+	// Changes can no longer be hydrated once the elements they refer to have
+	// changed again.
+	let mut later = after.clone();
+	later[1].value = 6;
+	assert_eq!(before.hydrate(&later, &changes), None);
+	later.truncate(1);
+	assert_eq!(before.hydrate(&later, &changes), None);
+}
+
+#[test]
+fn test_light_maps() {
+	let before: BTreeMap<u32, Reading> = vec![(1, reading("a", 1)), (2, reading("b", 2))].into_iter().collect();
+	let after: BTreeMap<u32, Reading> = vec![(2, reading("b", 3)), (3, reading("c", 3))].into_iter().collect();
+	let changes = before.light_comparison(&after).unwrap_or_default();
+	assert!(matches!(
+		changes[..],
+		[LightChange::Changed(2, _, _), LightChange::Added(3, _), LightChange::Removed(1, _)]
+	));
+	assert_eq!(before.hydrate(&after, &changes), before.comparison(&after).next());
+
+	let before: HashMap<u32, Reading> = before.into_iter().collect();
+	let after: HashMap<u32, Reading> = after.into_iter().collect();
+	assert_eq!(before.light_comparison(&after).unwrap_or_default(), changes);
+	assert_eq!(before.hydrate(&after, &changes), before.comparison(&after).next());
+	assert_eq!(before.hydrate(&before, &changes), None);
+}
@@ -3,7 +3,10 @@
 
 use std::collections::{BTreeMap, HashMap};
 
-use comparable::{assert_changes, Changed::*, I32Change, MapChange};
+use comparable::{
+	assert_changes, with_diff_options, Changed, Changed::*, Comparable, DiffOptions, I32Change, MapChange,
+	StringChange, U8Change,
+};
 
 pub struct HashMapBuilder {
 	elements: Vec<(i32, i32)>,
@@ -126,3 +129,94 @@ fn test_btreemap() {
 		Changed(vec![MapChange::Changed(1, I32Change(2, 4))]),
 	);
 }
+
+#[derive(Comparable, Clone)]
+struct User {
+	name: String,
+	email: String,
+	age: u8,
+}
+
+#[derive(Comparable, Clone)]
+enum Role {
+	Admin,
+	Member { user: User, since: u16 },
+}
+
+fn user(name: &str, email: &str, age: u8) -> User {
+	User { name: name.to_string(), email: email.to_string(), age }
+}
+
+fn moves(threshold: f64) -> DiffOptions {
+	DiffOptions { map_moves: Some(threshold), ..DiffOptions::default() }
+}
+
+#[test]
+fn test_similarity() {
+	let ann = user("ann", "ann@example.com", 30);
+	assert_eq!(ann.similarity(&ann), 1.0);
+	assert_eq!(ann.similarity(&user("anne", "ann@example.com", 30)), 2.0 / 3.0);
+	assert_eq!(ann.similarity(&user("bob", "bob@example.com", 40)), 0.0);
+	assert_eq!(Some(ann.clone()).similarity(&None), 0.0);
+
+	let member = Role::Member { user: ann.clone(), since: 2020 };
+	assert_eq!(member.similarity(&Role::Admin), 0.0);
+	assert_eq!(Role::Admin.similarity(&Role::Admin), 1.0);
+	assert_eq!(
+		member.similarity(&Role::Member { user: user("anne", "ann@example.com", 30), since: 2020 }),
+		(2.0 / 3.0 + 1.0) / 2.0
+	);
+}
+
+#[test]
+fn test_map_moves() {
+	let before: BTreeMap<String, User> = vec![
+		("ann".to_string(), user("ann", "ann@example.com", 30)),
+		("bob".to_string(), user("bob", "bob@example.com", 40)),
+	]
+	.into_iter()
+	.collect();
+	let after: BTreeMap<String, User> = vec![
+		("anne".to_string(), user("anne", "ann@example.com", 30)),
+		("bob".to_string(), user("bob", "bob@example.com", 41)),
+		("cy".to_string(), user("cy", "cy@example.com", 20)),
+	]
+	.into_iter()
+	.collect();
+
+	// Without the option, a renamed user is removed and added again.
+	let changes = before.comparison(&after);
+	assert!(matches!(&changes, Changed::Changed(changes) if changes.len() == 4));
+
+	assert_eq!(
+		with_diff_options(moves(0.5), || before.comparison(&after)),
+		Changed::Changed(vec![
+			MapChange::Moved {
+				from: "ann".to_string(),
+				to: "anne".to_string(),
+				change: Changed::Changed(vec![UserChange::Name(StringChange("ann".to_string(), "anne".to_string()))]),
+			},
+			MapChange::Changed("bob".to_string(), vec![UserChange::Age(U8Change(40, 41))]),
+			MapChange::Added(
+				"cy".to_string(),
+				UserDesc { name: "cy".to_string(), email: "cy@example.com".to_string(), age: 20 }
+			),
+		])
+	);
+
+	// A value that is not similar enough is not considered moved.
+	assert_eq!(with_diff_options(moves(0.9), || before.comparison(&after)), changes);
+}
+
+#[test]
+fn test_hashmap_moves() {
+	let before: HashMap<i32, i32> = vec![(1, 100), (2, 200)].into_iter().collect();
+	let after: HashMap<i32, i32> = vec![(1, 100), (3, 200), (4, 300)].into_iter().collect();
+	assert_eq!(
+		with_diff_options(moves(1.0), || before.comparison(&after)),
+		Changed::Changed(vec![
+			MapChange::Moved { from: 2, to: 3, change: Changed::Unchanged },
+			MapChange::Added(4, 300),
+		])
+	);
+}
@@ -0,0 +1,243 @@
+use comparable::mask::{with_field_mask, FieldMask};
+use comparable::*;
+
+#[derive(Comparable, Clone, PartialEq)]
+#[comparable_paths]
+struct Address {
+	street: String,
+	city: String,
+}
+
+#[derive(Comparable, Clone, PartialEq)]
+struct Item {
+	name: String,
+	price: u32,
+}
+
+#[derive(Comparable, Clone)]
+#[comparable_paths]
+struct Order {
+	id: u64,
+	address: Address,
+	items: Vec<Item>,
+	note: Option<String>,
+}
+
+#[derive(Comparable, Clone)]
+#[comparable_paths]
+enum Shape {
+	Circle { radius: u32, label: String },
+	Rect(u32, u32),
+}
+
+fn orders() -> (Order, Order) {
+	let before = Order {
+		id: 1,
+		address: Address { street: "Main St".to_string(), city: "Springfield".to_string() },
+		items: vec![Item { name: "Pen".to_string(), price: 2 }],
+		note: None,
+	};
+	let after = Order {
+		id: 2,
+		address: Address { street: "Elm St".to_string(), city: "Shelbyville".to_string() },
+		items: vec![Item { name: "Pencil".to_string(), price: 3 }],
+		note: Some("Fragile".to_string()),
+	};
+	(before, after)
+}
+
+#[test]
+fn test_mask_include() {
+	let (before, after) = orders();
+	assert_eq!(
+		before.comparison_masked(&after, &FieldMask::include(["id", "note"])),
+		Changed::Changed(vec![
+			OrderChange::Id(U64Change(1, 2)),
+			OrderChange::Note(OptionChange::Different(None, Some("Fragile".to_string()))),
+		])
+	);
+	assert_eq!(before.comparison_masked(&after, &FieldMask::include(Vec::<String>::new())), Changed::Unchanged);
+	assert_eq!(before.comparison_masked(&before, &FieldMask::include(["id"])), Changed::Unchanged);
+}
+
+#[test]
+fn test_mask_own_method() {
+	#[derive(Comparable)]
+	struct Reading {
+		value: u32,
+	}
+
+	// A method of the same name on the type itself takes precedence over the
+	// one from `MaskedComparison`, which remains available through the trait.
+	impl Reading {
+		fn comparison_masked(&self, other: &Self, _mask: &str) -> bool {
+			self.value != other.value
+		}
+	}
+
+	let (before, after) = (Reading { value: 1 }, Reading { value: 2 });
+	assert!(before.comparison_masked(&after, "value"));
+	assert_eq!(
+		MaskedComparison::comparison_masked(&before, &after, &FieldMask::exclude(["value"])),
+		Changed::Unchanged
+	);
+
+	// Values of types that are not derived can be masked too.
+	let (before, after) = orders();
+	assert_eq!(
+		vec![before.address].comparison_masked(&vec![after.address], &FieldMask::include(["city"])),
+		Changed::Changed(vec![VecChange::Changed(
+			0,
+			vec![AddressChange::City(StringChange("Springfield".to_string(), "Shelbyville".to_string()))]
+		)])
+	);
+}
+
+#[test]
+fn test_mask_exclude() {
+	let (before, after) = orders();
+	assert_eq!(
+		before.comparison_masked(&after, &FieldMask::exclude(["address", "items", "note"])),
+		Changed::Changed(vec![OrderChange::Id(U64Change(1, 2))])
+	);
+	assert_eq!(before.comparison_masked(&after, &FieldMask::exclude(Vec::<String>::new())), before.comparison(&after));
+}
+
+#[test]
+fn test_mask_nested_paths() {
+	let (before, after) = orders();
+	assert_eq!(
+		before.comparison_masked(&after, &FieldMask::include(["address.city"])),
+		Changed::Changed(vec![OrderChange::Address(vec![AddressChange::City(StringChange(
+			"Springfield".to_string(),
+			"Shelbyville".to_string()
+		))])])
+	);
+	assert_eq!(
+		before.comparison_masked(&after, &FieldMask::exclude(["id", "address.street", "items", "note"])),
+		Changed::Changed(vec![OrderChange::Address(vec![AddressChange::City(StringChange(
+			"Springfield".to_string(),
+			"Shelbyville".to_string()
+		))])])
+	);
+	// A path naming a whole field takes precedence over paths within it.
+	assert_eq!(
+		before.comparison_masked(&after, &FieldMask::include(["address", "address.city"])),
+		Changed::Changed(vec![OrderChange::Address(vec![
+			AddressChange::Street(StringChange("Main St".to_string(), "Elm St".to_string())),
+			AddressChange::City(StringChange("Springfield".to_string(), "Shelbyville".to_string())),
+		])])
+	);
+}
+
+#[test]
+fn test_mask_through_collections() {
+	let (before, after) = orders();
+	assert_eq!(
+		before.comparison_masked(&after, &FieldMask::include(["items.price"])),
+		Changed::Changed(vec![OrderChange::Items(vec![VecChange::Changed(
+			0,
+			vec![ItemChange::Price(U32Change(2, 3))]
+		)])])
+	);
+	assert_eq!(
+		with_field_mask(&FieldMask::include(["name"]), || before.items.comparison(&after.items)),
+		Changed::Changed(vec![VecChange::Changed(
+			0,
+			vec![ItemChange::Name(StringChange("Pen".to_string(), "Pencil".to_string()))]
+		)])
+	);
+}
+
+#[test]
+fn test_mask_enums() {
+	let before = Shape::Circle { radius: 1, label: "a".to_string() };
+	let after = Shape::Circle { radius: 2, label: "b".to_string() };
+	assert_eq!(
+		before.comparison_masked(&after, &FieldMask::include(["radius"])),
+		Changed::Changed(ShapeChange::BothCircle {
+			radius: Changed::Changed(U32Change(1, 2)),
+			label: Changed::Unchanged
+		})
+	);
+	assert_eq!(
+		Shape::Rect(1, 2).comparison_masked(&Shape::Rect(3, 4), &FieldMask::exclude(["0"])),
+		Changed::Changed(ShapeChange::BothRect(Changed::Unchanged, Changed::Changed(U32Change(2, 4))))
+	);
+	// Changes of variant are always reported.
+	assert!(before.comparison_masked(&Shape::Rect(1, 2), &FieldMask::include(["radius"])) != Changed::Unchanged);
+}
+
+#[test]
+fn test_mask_is_scoped() {
+	let (before, after) = orders();
+	assert_eq!(before.comparison_masked(&after, &FieldMask::include(["id"])).map(|c| c.len()), Changed::Changed(1));
+	assert_eq!(before.comparison(&after).map(|c| c.len()), Changed::Changed(4));
+}
+
+#[test]
+fn test_mask_paths() {
+	let mask = FieldMask::include(["items.price", "address", "address.city", "id"]);
+	assert_eq!(mask.paths(), vec!["address", "id", "items.price"]);
+	assert!(!mask.is_exclude());
+	assert_eq!(FieldMask::include(mask.paths()), FieldMask::include(["address", "id", "items.price"]));
+	assert!(FieldMask::exclude(["id"]).is_exclude());
+}
+
+#[test]
+fn test_mask_protobuf() {
+	let mask = FieldMask::include(["user.display_name", "photo_url", "items.0"]);
+	assert_eq!(mask.to_protobuf(), Some("items.0,photoUrl,user.displayName".to_string()));
+	assert_eq!(FieldMask::from_protobuf("items.0,photoUrl,user.displayName"), mask);
+	assert_eq!(FieldMask::from_protobuf(""), FieldMask::include(Vec::<String>::new()));
+	assert_eq!(FieldMask::exclude(["id"]).to_protobuf(), None);
+
+	let (before, after) = orders();
+	assert_eq!(
+		before.comparison_masked(&after, &FieldMask::from_protobuf("address.city")),
+		before.comparison_masked(&after, &FieldMask::include(["address.city"]))
+	);
+}
+
+#[test]
+fn test_mask_differs() {
+	let (before, after) = orders();
+	let mut same_id = after.clone();
+	same_id.id = before.id;
+	assert!(before.differs(&same_id));
+	assert!(!with_field_mask(&FieldMask::include(["id"]), || before.differs(&same_id)));
+	assert!(with_field_mask(&FieldMask::include(["id"]), || before.differs(&after)));
+	assert!(!with_field_mask(&FieldMask::exclude(["id", "address", "items", "note"]), || before.differs(&after)));
+}
+
+#[test]
+fn test_mask_count_changes() {
+	let (before, after) = orders();
+	assert_eq!(before.count_changes(&after), 6);
+	assert_eq!(with_field_mask(&FieldMask::include(["address.city", "items"]), || before.count_changes(&after)), 3);
+	assert_eq!(with_field_mask(&FieldMask::exclude(["items.name"]), || before.count_changes(&after)), 5);
+}
+
+#[test]
+fn test_mask_first_difference() {
+	let (before, after) = orders();
+	let first_difference = |mask| with_field_mask(&mask, || before.first_difference(&after)).map(|p| p.to_string());
+	assert_eq!(before.first_difference(&after).map(|p| p.to_string()), Some("id".to_string()));
+	assert_eq!(first_difference(FieldMask::include(["items.price", "note"])), Some("items[0].price".to_string()));
+	assert_eq!(first_difference(FieldMask::exclude(["id", "address.street"])), Some("address.city".to_string()));
+	assert_eq!(first_difference(FieldMask::include(["address.zip"])), None);
+}
+
+#[test]
+fn test_mask_field_constants() {
+	assert_eq!(OrderChange::FIELDS, ["id", "address", "items", "note"]);
+	assert_eq!(ShapeChange::FIELDS, ["radius", "label", "0", "1"]);
+	assert_eq!((order::path::ID, shape::path::LABEL, shape::path::FIELD_1), ("id", "label", "1"));
+
+	let (before, after) = orders();
+	let city = [order::path::ADDRESS, address::path::CITY].join(".");
+	assert_eq!(
+		before.comparison_masked(&after, &FieldMask::include([city])),
+		before.comparison_masked(&after, &FieldMask::include(["address.city"]))
+	);
+}
@@ -0,0 +1,134 @@
+use std::collections::BTreeMap;
+
+use comparable::merge::{dedup_changes, merge_changes, sort_changes, Conflict, MergeKey};
+use comparable::{Comparable, I32Change, MapChange, StringChange};
+
+#[derive(Comparable, Clone)]
+struct Settings {
+	name: String,
+	width: i32,
+	height: i32,
+}
+
+#[test]
+fn test_merge_struct_changes() {
+	let base = Settings { name: "base".to_string(), width: 10, height: 20 };
+	let left = Settings { width: 15, ..base.clone() };
+	let right = Settings { name: "right".to_string(), width: 15, ..base.clone() };
+
+	let left_changes = base.comparison(&left).unwrap_or_default();
+	let right_changes = base.comparison(&right).unwrap_or_default();
+	assert_eq!(left_changes[0].merge_key(), "width");
+	assert_eq!(
+		merge_changes(left_changes, right_changes),
+		Ok(vec![
+			SettingsChange::Width(I32Change(10, 15)),
+			SettingsChange::Name(StringChange("base".to_string(), "right".to_string())),
+		])
+	);
+
+	let conflicting = Settings { width: 5, height: 25, ..base.clone() };
+	assert_eq!(
+		merge_changes(base.comparison(&left).unwrap_or_default(), base.comparison(&conflicting).unwrap_or_default()),
+		Err(vec![Conflict {
+			path: "width".to_string(),
+			left: SettingsChange::Width(I32Change(10, 15)),
+			right: SettingsChange::Width(I32Change(10, 5)),
+		}])
+	);
+}
+
+#[test]
+fn test_merge_map_changes() {
+	let base: BTreeMap<String, i32> = vec![("a".to_string(), 1), ("b".to_string(), 2)].into_iter().collect();
+	let mut left = base.clone();
+	left.insert("c".to_string(), 3);
+	let mut right = base.clone();
+	right.remove("a");
+	right.insert("c".to_string(), 4);
+
+	assert_eq!(
+		merge_changes(base.comparison(&left).unwrap_or_default(), base.comparison(&right).unwrap_or_default()),
+		Err(vec![Conflict {
+			path: "\"c\"".to_string(),
+			left: MapChange::Added("c".to_string(), 3),
+			right: MapChange::Added("c".to_string(), 4),
+		}])
+	);
+
+	right.insert("c".to_string(), 3);
+	assert_eq!(
+		merge_changes(base.comparison(&left).unwrap_or_default(), base.comparison(&right).unwrap_or_default()),
+		Ok(vec![MapChange::Added("c".to_string(), 3), MapChange::Removed("a".to_string())])
+	);
+}
+
+#[test]
+fn test_merge_flattened_fields() {
+	#[derive(Clone, Comparable)]
+	struct Inner {
+		a: i32,
+		b: i32,
+	}
+
+	#[derive(Clone, Comparable)]
+	struct Outer {
+		#[comparable_flatten]
+		inner: Inner,
+		c: i32,
+	}
+
+	let base = Outer { inner: Inner { a: 1, b: 2 }, c: 3 };
+	let left = base.comparison(&Outer { inner: Inner { a: 10, b: 2 }, c: 3 }).unwrap_or_default();
+	let right = base.comparison(&Outer { inner: Inner { a: 1, b: 20 }, c: 3 }).unwrap_or_default();
+	assert_eq!(
+		merge_changes(left, right),
+		Ok(vec![
+			OuterChange::Inner(InnerChange::A(I32Change(1, 10))),
+			OuterChange::Inner(InnerChange::B(I32Change(2, 20)))
+		])
+	);
+}
+
+#[test]
+fn test_declaration_order() {
+	let base = Settings { name: "base".to_string(), width: 10, height: 20 };
+	let changed = Settings { name: "changed".to_string(), width: 15, height: 25 };
+	for _ in 0..3 {
+		assert_eq!(
+			base.comparison(&changed),
+			comparable::Changed::Changed(vec![
+				SettingsChange::Name(StringChange("base".to_string(), "changed".to_string())),
+				SettingsChange::Width(I32Change(10, 15)),
+				SettingsChange::Height(I32Change(20, 25)),
+			])
+		);
+	}
+}
+
+#[test]
+fn test_sort_and_dedup_changes() {
+	let base = Settings { name: "base".to_string(), width: 10, height: 20 };
+	let mut changes = base.comparison(&Settings { width: 15, height: 25, ..base.clone() }).unwrap_or_default();
+	changes.extend(
+		base.comparison(&Settings { name: "other".to_string(), width: 15, ..base.clone() }).unwrap_or_default(),
+	);
+	sort_changes(&mut changes);
+	dedup_changes(&mut changes);
+	assert_eq!(
+		changes,
+		vec![
+			SettingsChange::Height(I32Change(20, 25)),
+			SettingsChange::Name(StringChange("base".to_string(), "other".to_string())),
+			SettingsChange::Width(I32Change(10, 15)),
+		]
+	);
+
+	let mut changes = vec![
+		SettingsChange::Width(I32Change(10, 15)),
+		SettingsChange::Width(I32Change(10, 5)),
+		SettingsChange::Width(I32Change(10, 15)),
+	];
+	dedup_changes(&mut changes);
+	assert_eq!(changes, vec![SettingsChange::Width(I32Change(10, 15)), SettingsChange::Width(I32Change(10, 5))]);
+}
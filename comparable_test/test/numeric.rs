@@ -0,0 +1,54 @@
+use comparable::display::display;
+use comparable::tree::{ChangeTree, ToTree};
+use comparable::*;
+use ordered_float::{NotNan, OrderedFloat};
+use rust_decimal::Decimal;
+
+#[derive(Comparable)]
+#[comparable_display]
+#[comparable_tree]
+struct Account {
+	balance: Decimal,
+	rate: OrderedFloat<f64>,
+	weight: NotNan<f64>,
+}
+
+#[test]
+fn test_ordered_float() {
+	assert_eq!(OrderedFloat(1.5).describe(), 1.5);
+	assert_eq!(OrderedFloat(f64::NAN).comparison(&OrderedFloat(f64::NAN)), Changed::Unchanged);
+	assert_eq!(OrderedFloat(1.5).comparison(&OrderedFloat(2.0)), Changed::Changed(OrderedFloatChange(1.5, 2.0, 0.5)));
+	assert_eq!(
+		NotNan::new(3.0f32).unwrap().comparison(&NotNan::new(1.0).unwrap()),
+		Changed::Changed(OrderedFloatChange(3.0, 1.0, -2.0))
+	);
+}
+
+#[test]
+fn test_decimal() {
+	assert_eq!(Decimal::new(100, 2).comparison(&Decimal::new(1000, 3)), Changed::Unchanged);
+	assert_eq!(
+		Decimal::new(1050, 2).comparison(&Decimal::new(1200, 2)),
+		Changed::Changed(DecimalChange(Decimal::new(1050, 2), Decimal::new(1200, 2), Decimal::new(150, 2)))
+	);
+}
+
+#[test]
+fn test_numeric_fields() {
+	let before =
+		Account { balance: Decimal::new(1000, 2), rate: OrderedFloat(0.25), weight: NotNan::new(1.0).unwrap() };
+	let after = Account { balance: Decimal::new(950, 2), rate: OrderedFloat(0.25), weight: NotNan::new(2.0).unwrap() };
+	let changes = before.comparison(&after).unwrap_or_default();
+	assert_eq!(
+		changes,
+		vec![
+			AccountChange::Balance(DecimalChange(Decimal::new(1000, 2), Decimal::new(950, 2), Decimal::new(-50, 2))),
+			AccountChange::Weight(OrderedFloatChange(1.0, 2.0, 1.0)),
+		]
+	);
+	assert_eq!(display(&changes[0]).to_string(), "balance: 10.00 → 9.50");
+	assert_eq!(
+		changes[1].to_tree(),
+		ChangeTree::Children(vec![("weight".to_string(), ChangeTree::modified(&1.0, &2.0))])
+	);
+}
@@ -0,0 +1,70 @@
+use std::collections::BTreeMap;
+
+use comparable::{assert_changes, Changed::*, Comparable, I32Change, MapChange, VecChange};
+
+#[test]
+fn test_im_vector() {
+	let v1: im::Vector<i32> = im::vector![1, 2, 3];
+	let v2 = v1.clone();
+	assert_changes!(&v1, &v2, Unchanged);
+
+	let mut v3 = v1.clone();
+	v3.set(1, 20);
+	v3.push_back(4);
+	assert_changes!(&v1, &v3, Changed(vec![VecChange::Changed(1, I32Change(2, 20)), VecChange::Added(3, 4)]));
+	assert_changes!(&v3, &v1, Changed(vec![VecChange::Changed(1, I32Change(20, 2)), VecChange::Removed(3, 4)]));
+	assert_eq!(v1.describe(), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_im_hashmap() {
+	let m1: im::HashMap<i32, i32> = im::hashmap! {1 => 100, 2 => 200, 3 => 300};
+	let m2 = m1.clone();
+	assert_changes!(&m1, &m2, Unchanged);
+
+	let m3 = m1.update(2, 250).without(&3).update(4, 400);
+	assert_changes!(
+		&m1,
+		&m3,
+		Changed(vec![MapChange::Changed(2, I32Change(200, 250)), MapChange::Added(4, 400), MapChange::Removed(3)])
+	);
+	assert_eq!(m1.describe(), vec![(1, 100), (2, 200), (3, 300)].into_iter().collect::<BTreeMap<_, _>>());
+}
+
+#[test]
+fn test_im_ordmap() {
+	let m1: im::OrdMap<i32, i32> = (0..1000).map(|i| (i, i * 10)).collect();
+	let m2 = m1.clone();
+	assert_changes!(&m1, &m2, Unchanged);
+
+	let m3 = m1.update(500, 5).without(&3).update(1000, 10000);
+	assert_changes!(
+		&m1,
+		&m3,
+		Changed(vec![
+			MapChange::Changed(500, I32Change(5000, 5)),
+			MapChange::Added(1000, 10000),
+			MapChange::Removed(3)
+		])
+	);
+
+	// The result must match that of the equivalent `BTreeMap`s.
+	let b1: BTreeMap<i32, i32> = m1.clone().into_iter().collect();
+	let b3: BTreeMap<i32, i32> = m3.clone().into_iter().collect();
+	assert_eq!(m1.comparison(&m3), b1.comparison(&b3));
+}
+
+#[test]
+fn test_im_rc_collections() {
+	let v1: im_rc::Vector<i32> = im_rc::vector![1, 2, 3];
+	let mut v2 = v1.clone();
+	assert_changes!(&v1, &v2, Unchanged);
+	v2.pop_back();
+	assert_changes!(&v1, &v2, Changed(vec![VecChange::Removed(2, 3)]));
+
+	let m1: im_rc::HashMap<i32, i32> = im_rc::hashmap! {1 => 100};
+	assert_changes!(&m1, &m1.update(1, 101), Changed(vec![MapChange::Changed(1, I32Change(100, 101))]));
+
+	let o1: im_rc::OrdMap<i32, i32> = im_rc::ordmap! {1 => 100};
+	assert_changes!(&o1, &o1.update(2, 200), Changed(vec![MapChange::Added(2, 200)]));
+}
@@ -0,0 +1,30 @@
+use comparable::prelude::*;
+
+#[derive(Clone, Comparable)]
+#[comparable_lens]
+struct Account {
+	owner: String,
+	balance: i64,
+}
+
+fn account(owner: &str, balance: i64) -> Account {
+	Account { owner: owner.to_string(), balance }
+}
+
+#[test]
+fn test_prelude() {
+	let before = account("Ann", 10);
+	assert!(changed(&before, &account("ANN", 10)));
+	assert!(!changed(&CaseInsensitive("Ann"), &CaseInsensitive("ANN")));
+	assert_changes!(&before, &account("Ann", 10), Changed::Unchanged);
+	assert_eq!(describe(&before).balance, 10);
+
+	let after = account("Ann", 20);
+	let mut updated = before.clone();
+	updated.apply(diff(&before, &after).unwrap_changed());
+	assert!(!changed(&updated, &after));
+
+	let mut tracked = Tracked::new(Leaf(1.5));
+	tracked.0 = 2.5;
+	assert_eq!(tracked.take_changes(), Changed::Changed((1.5, 2.5)));
+}
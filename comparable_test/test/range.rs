@@ -0,0 +1,59 @@
+use std::ops::{Range, RangeInclusive};
+
+use comparable::display::display;
+use comparable::merge::MergeKey;
+use comparable::roundtrip::{FromDesc, IntoDesc};
+use comparable::tree::{ChangeTree, ToTree};
+use comparable::*;
+
+#[derive(Comparable)]
+struct Shift {
+	hours: Range<u8>,
+	days: RangeInclusive<u8>,
+}
+
+#[test]
+fn test_range() {
+	assert_eq!((1..5).describe(), 1..5);
+	assert_eq!((1..5).comparison(&(1..5)), Changed::Unchanged);
+	assert_eq!((1..5).comparison(&(2..5)), Changed::Changed(vec![RangeChange::Start(I32Change(1, 2))]));
+	assert_eq!(
+		(1..5).comparison(&(2..6)),
+		Changed::Changed(vec![RangeChange::Start(I32Change(1, 2)), RangeChange::End(I32Change(5, 6))])
+	);
+	assert_eq!((1..5).count_changes(&(2..6)), 2);
+	assert_eq!((1..5).first_difference(&(1..6)).unwrap().to_string(), "end");
+}
+
+#[test]
+fn test_range_inclusive() {
+	assert_eq!((1..=5).describe(), 1..=5);
+	assert_eq!((1..=5).comparison(&(1..=7)), Changed::Changed(vec![RangeChange::End(I32Change(5, 7))]));
+	assert!(!(1..=5).differs(&(1..=5)));
+}
+
+#[test]
+fn test_range_fields() {
+	let before = Shift { hours: 9..17, days: 1..=5 };
+	let after = Shift { hours: 8..17, days: 1..=6 };
+	let changes = before.comparison(&after).unwrap_or_default();
+	assert_eq!(
+		changes,
+		vec![
+			ShiftChange::Hours(vec![RangeChange::Start(U8Change(9, 8))]),
+			ShiftChange::Days(vec![RangeChange::End(U8Change(5, 6))]),
+		]
+	);
+	assert_eq!(before.first_difference(&after).unwrap().to_string(), "hours.start");
+
+	let hours = before.hours.comparison(&after.hours).unwrap_or_default();
+	assert_eq!(display(&hours).to_string(), "start: 9 → 8");
+	assert_eq!(hours.to_tree(), ChangeTree::Children(vec![("start".to_string(), ChangeTree::modified(&9, &8))]));
+	assert_eq!(hours[0].merge_key(), "start");
+}
+
+#[test]
+fn test_range_roundtrip() {
+	assert_eq!(Range::<u8>::from_desc(1..3), 1..3);
+	assert_eq!((1u8..=3).into_desc(), 1..=3);
+}
@@ -0,0 +1,91 @@
+use comparable::{Changed::*, *};
+
+#[derive(Comparable)]
+enum Expr {
+	Add(Box<Expr>, Box<Expr>),
+	Neg(Box<Self>),
+	Lit(i64),
+}
+
+#[derive(Comparable)]
+struct Node {
+	value: i32,
+	next: Option<Box<Node>>,
+}
+
+// Mutually recursive types must mark one of the recursive fields explicitly.
+#[derive(Comparable)]
+enum Statement {
+	Print(String),
+	If(#[comparable_box] Box<Condition>, Box<Statement>),
+}
+
+#[derive(Comparable)]
+enum Condition {
+	True,
+	Not(Box<Condition>),
+	Ran(Box<Statement>),
+}
+
+fn lit(n: i64) -> Box<Expr> {
+	Box::new(Expr::Lit(n))
+}
+
+#[test]
+fn test_recursive_enum() {
+	assert_eq!(
+		Expr::Add(lit(1), lit(2)).describe(),
+		ExprDesc::Add(Box::new(ExprDesc::Lit(1)), Box::new(ExprDesc::Lit(2)))
+	);
+	assert_changes!(&Expr::Add(lit(1), lit(2)), &Expr::Add(lit(1), lit(2)), Unchanged);
+	assert_changes!(
+		&Expr::Add(lit(1), lit(2)),
+		&Expr::Add(lit(1), lit(3)),
+		Changed(ExprChange::BothAdd(Unchanged, Changed(Box::new(ExprChange::BothLit(I64Change(2, 3))))))
+	);
+	assert_changes!(
+		&Expr::Neg(lit(1)),
+		&Expr::Neg(Box::new(Expr::Neg(lit(1)))),
+		Changed(ExprChange::BothNeg(Box::new(ExprChange::Different(
+			ExprDesc::Lit(1),
+			ExprDesc::Neg(Box::new(ExprDesc::Lit(1)))
+		))))
+	);
+}
+
+#[test]
+fn test_linked_list() {
+	let list = |values: &[i32]| {
+		values.iter().rev().fold(None, |next, &value| Some(Box::new(Node { value, next }))).map(|node| *node)
+	};
+	let (a, b) = (list(&[1, 2, 3]).unwrap(), list(&[1, 2, 4]).unwrap());
+	assert_changes!(&a, &list(&[1, 2, 3]).unwrap(), Unchanged);
+	assert_changes!(
+		&a,
+		&b,
+		Changed(vec![NodeChange::Next(Box::new(OptionChange::BothSome(vec![NodeChange::Next(Box::new(
+			OptionChange::BothSome(vec![NodeChange::Value(I32Change(3, 4))])
+		))])))])
+	);
+}
+
+#[test]
+fn test_mutually_recursive() {
+	let stmt =
+		Statement::If(Box::new(Condition::Not(Box::new(Condition::True))), Box::new(Statement::Print("a".into())));
+	let other = Statement::If(
+		Box::new(Condition::Ran(Box::new(Statement::Print("b".into())))),
+		Box::new(Statement::Print("a".into())),
+	);
+	assert_changes!(
+		&stmt,
+		&other,
+		Changed(StatementChange::BothIf(
+			Changed(Box::new(ConditionChange::Different(
+				ConditionDesc::Not(Box::new(ConditionDesc::True)),
+				ConditionDesc::Ran(StatementDesc::Print("b".to_string()))
+			))),
+			Unchanged
+		))
+	);
+}
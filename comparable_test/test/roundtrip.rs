@@ -0,0 +1,72 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use comparable::roundtrip::{FromDesc, IntoDesc};
+use comparable::*;
+
+fn assert_roundtrip<T: FromDesc + IntoDesc + Clone + PartialEq + Debug>(value: T) {
+	let desc = value.describe();
+	assert_eq!(value.clone().into_desc(), desc);
+	assert_eq!(T::from_desc(desc), value);
+}
+
+#[test]
+fn test_roundtrip_std_types() {
+	assert_roundtrip(10u8);
+	assert_roundtrip("hello".to_string());
+	assert_roundtrip(Some(vec![1, 2, 3]));
+	assert_roundtrip(Box::new(1.5f64));
+	assert_roundtrip([true, false]);
+	assert_roundtrip((1, 'a', "b".to_string()));
+	assert_roundtrip((7,));
+	assert_roundtrip(vec![1, 2].into_iter().collect::<HashSet<i32>>());
+	assert_roundtrip(vec![("a".to_string(), vec![1])].into_iter().collect::<BTreeMap<_, _>>());
+}
+
+#[derive(Comparable, Clone, PartialEq, Debug)]
+#[comparable_desc_roundtrip]
+struct Settings {
+	name: String,
+	retries: Option<u8>,
+	tags: Vec<String>,
+}
+
+#[derive(Comparable, Clone, PartialEq, Debug)]
+#[comparable_desc_roundtrip]
+struct Pair<T>(T, #[comparable_flatten] Settings, PhantomData<T>);
+
+#[derive(Comparable, Clone, PartialEq, Debug)]
+#[comparable_desc_roundtrip]
+enum Shape {
+	Empty,
+	Circle(f64),
+	Rect { width: u32, height: u32 },
+	Group(Vec<Shape>),
+}
+
+#[derive(Comparable, Clone, PartialEq, Debug)]
+#[comparable_desc_roundtrip]
+#[comparable_transparent]
+struct Name(String);
+
+#[test]
+fn test_roundtrip_derived() {
+	let settings = Settings { name: "a".to_string(), retries: Some(3), tags: vec!["x".to_string()] };
+	assert_roundtrip(settings.clone());
+	assert_roundtrip(Pair(1u8, settings, PhantomData));
+	assert_roundtrip(Shape::Group(vec![
+		Shape::Empty,
+		Shape::Circle(1.0),
+		Shape::Rect { width: 2, height: 3 },
+		Shape::Group(vec![]),
+	]));
+	assert_roundtrip(Name("b".to_string()));
+}
+
+#[test]
+fn test_roundtrip_normalized_string() {
+	let name = CaseInsensitive("Hello".to_string());
+	assert_eq!(name.clone().into_desc(), "Hello");
+	assert_eq!(CaseInsensitive::<String>::from_desc(name.describe()).0, "Hello");
+}
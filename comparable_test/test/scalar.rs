@@ -90,3 +90,79 @@ fn test_char() {
 	assert_changes!(&'a', &'a', Unchanged);
 	assert_changes!(&'a', &'b', Changed(CharChange('a', 'b')));
 }
+
+#[test]
+fn test_float_tolerance() {
+	#[derive(Comparable)]
+	struct Point {
+		x: f64,
+		y: f32,
+	}
+
+	let a = Point { x: 0.1 + 0.2, y: 1.0 };
+	let b = Point { x: 0.3, y: 1.0 + f32::EPSILON };
+	assert_changes!(
+		&a,
+		&b,
+		Changed(vec![PointChange::X(F64Change(0.1 + 0.2, 0.3)), PointChange::Y(F32Change(1.0, 1.0 + f32::EPSILON))])
+	);
+	with_float_tolerance(FloatTolerance::Absolute(1e-6), || assert_changes!(&a, &b, Unchanged));
+	with_float_tolerance(FloatTolerance::Ulps(1), || assert_changes!(&a, &b, Unchanged));
+	with_float_tolerance(FloatTolerance::Ulps(1), || {
+		assert_changes!(&1.0, &1.1, Changed(F64Change(1.0, 1.1)));
+		assert_changes!(&0.0, &-0.0, Unchanged);
+	});
+	// The tolerance only applies within the closure.
+	assert_changes!(
+		&a,
+		&b,
+		Changed(vec![PointChange::X(F64Change(0.1 + 0.2, 0.3)), PointChange::Y(F32Change(1.0, 1.0 + f32::EPSILON))])
+	);
+}
+
+#[test]
+fn test_std_leaves() {
+	use std::net::{IpAddr, Ipv4Addr};
+	use std::num::NonZeroU32;
+	use std::time::Duration;
+
+	assert_changes!(&(1 as u128), &(2 as u128), Changed(U128Change(1, 2)));
+	assert_changes!(&(-1 as i128), &(-1 as i128), Unchanged);
+	let (one, two) = (NonZeroU32::new(1).unwrap(), NonZeroU32::new(2).unwrap());
+	assert_changes!(&one, &two, Changed(NonZeroU32Change(one, two)));
+	assert_changes!(
+		&Duration::from_secs(1),
+		&Duration::from_millis(1500),
+		Changed(DurationChange(Duration::from_secs(1), Duration::from_millis(1500)))
+	);
+	let (local, other) = (IpAddr::V4(Ipv4Addr::LOCALHOST), IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)));
+	assert_changes!(&local, &local, Unchanged);
+	assert_changes!(&local, &other, Changed(IpAddrChange(local, other)));
+}
+
+#[test]
+fn test_leaf_macro() {
+	#[derive(Clone, Copy, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+	struct Celsius(i16);
+
+	impl_comparable_for_leaf! {
+		#[derive(serde::Serialize, serde::Deserialize)]
+		Celsius => CelsiusChange,
+	}
+
+	#[derive(Comparable)]
+	#[comparable_display]
+	struct Reading {
+		place: String,
+		temperature: Celsius,
+	}
+
+	let before = Reading { place: "roof".to_string(), temperature: Celsius(20) };
+	let after = Reading { place: "roof".to_string(), temperature: Celsius(23) };
+	let change = before.comparison(&after).unwrap_changed();
+	assert_eq!(change, vec![ReadingChange::Temperature(CelsiusChange(Celsius(20), Celsius(23)))]);
+	assert_eq!(
+		comparable::display::display(&before.comparison(&after)).to_string(),
+		"temperature: Celsius(20) → Celsius(23)"
+	);
+}
@@ -0,0 +1,141 @@
+use std::collections::BTreeMap;
+
+use comparable::location::{Path, Segment};
+use comparable::schema::{Field, HasSchema, Schema, SchemaChange, Variant};
+use comparable::*;
+
+fn path(fields: &[&str]) -> Path {
+	Path::from(fields.iter().map(|field| Segment::Field(field.to_string())).collect::<Vec<_>>())
+}
+
+fn scalar(name: &str) -> Schema {
+	Schema::Scalar(name.to_string())
+}
+
+#[derive(Comparable)]
+#[comparable_schema]
+#[allow(dead_code)]
+struct Address {
+	city: String,
+	#[comparable_redact]
+	street: String,
+	#[comparable_ignore]
+	cache: Vec<u8>,
+}
+
+#[derive(Comparable)]
+#[comparable_schema]
+struct Point(i32, i32);
+
+#[derive(Comparable, PartialEq)]
+#[comparable_schema]
+#[allow(dead_code)]
+enum Tree {
+	Leaf,
+	Node(Box<Tree>, Box<Tree>),
+	Labeled { label: String, children: Vec<Tree> },
+}
+
+#[test]
+fn test_schema_derived() {
+	assert_eq!(
+		Address::schema(),
+		Schema::Struct {
+			name: "Address".to_string(),
+			fields: vec![Field::new("city", scalar("String")), Field::new("street", scalar("Redacted"))],
+		}
+	);
+	assert_eq!(
+		Point::schema(),
+		Schema::Struct {
+			name: "Point".to_string(),
+			fields: vec![Field::new("0", scalar("i32")), Field::new("1", scalar("i32"))],
+		}
+	);
+	assert_eq!(
+		<BTreeMap<String, Option<(u8, bool)>>>::schema(),
+		Schema::Map(
+			Box::new(scalar("String")),
+			Box::new(Schema::Option(Box::new(Schema::Tuple(vec![scalar("u8"), scalar("bool")]))))
+		)
+	);
+
+	let tree = || Schema::Recursive("Tree".to_string());
+	assert_eq!(
+		Tree::schema(),
+		Schema::Enum {
+			name: "Tree".to_string(),
+			variants: vec![
+				Variant::new("Leaf", vec![]),
+				Variant::new("Node", vec![Field::new("0", tree()), Field::new("1", tree())]),
+				Variant::new(
+					"Labeled",
+					vec![
+						Field::new("label", scalar("String")),
+						Field::new("children", Schema::Sequence(Box::new(tree())))
+					]
+				),
+			],
+		}
+	);
+	// The type is described in full again outside of its own schema.
+	assert_eq!(<Vec<Tree>>::schema(), Schema::Sequence(Box::new(Tree::schema())));
+}
+
+mod v1 {
+	use comparable::*;
+
+	#[derive(Comparable)]
+	#[comparable_schema]
+	pub struct Customer {
+		pub name: String,
+		pub address: super::Address,
+		pub status: Status,
+		pub tags: Vec<u32>,
+	}
+
+	#[derive(Comparable)]
+	#[comparable_schema]
+	#[allow(dead_code)]
+	pub enum Status {
+		Active { since: u64 },
+		Suspended,
+	}
+}
+
+#[derive(Comparable)]
+#[comparable_schema]
+struct Customer {
+	tags: Vec<u64>,
+	address: Address,
+	status: Status,
+	email: String,
+}
+
+#[derive(Comparable)]
+#[comparable_schema]
+#[allow(dead_code)]
+enum Status {
+	Active { since: u64, until: Option<u64> },
+	Closed,
+}
+
+#[test]
+fn test_schema_comparison() {
+	assert_eq!(Customer::schema().comparison(&Customer::schema()), Changed::Unchanged);
+	assert_eq!(
+		v1::Customer::schema().comparison(&Customer::schema()),
+		Changed::Changed(vec![
+			SchemaChange::Changed(path(&["tags"]), scalar("u32"), scalar("u64")),
+			SchemaChange::FieldAdded(path(&["status", "Active", "until"]), <Option<u64>>::schema()),
+			SchemaChange::VariantAdded(path(&["status", "Closed"])),
+			SchemaChange::VariantRemoved(path(&["status", "Suspended"])),
+			SchemaChange::FieldAdded(path(&["email"]), scalar("String")),
+			SchemaChange::FieldRemoved(path(&["name"]), scalar("String")),
+		])
+	);
+	assert_eq!(
+		Address::schema().comparison(&Point::schema()),
+		Changed::Changed(vec![SchemaChange::Changed(Path::default(), Address::schema(), Point::schema())])
+	);
+}
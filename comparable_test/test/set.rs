@@ -3,7 +3,11 @@
 
 use std::collections::{BTreeSet, HashSet};
 
-use comparable::{assert_changes, Changed::*, I32Change, SetChange, VecChange};
+use comparable::{
+	assert_changes, with_diff_options, Changed::*, CharChange, Comparable, DiffAlgorithm, DiffOptions, I32Change,
+	SetChange, StringChange, U32Change, VecChange,
+};
+use proptest::prelude::*;
 
 #[test]
 fn test_vec() {
@@ -37,6 +41,214 @@ fn test_vec() {
 	);
 }
 
+#[test]
+fn test_vec_moves() {
+	let moves = DiffOptions { sequence_moves: true, ..DiffOptions::default() };
+	let compare = |a: Vec<i32>, b: Vec<i32>| with_diff_options(moves, || a.comparison(&b));
+
+	assert_eq!(compare(vec![1, 2, 3], vec![1, 2, 3]), Unchanged);
+	assert_eq!(
+		compare(vec![1, 2, 3], vec![2, 3, 1]),
+		Changed(vec![
+			VecChange::Moved { from: 1, to: 0 },
+			VecChange::Moved { from: 2, to: 1 },
+			VecChange::Moved { from: 0, to: 2 },
+		])
+	);
+	assert_eq!(
+		compare(vec![1, 2, 3, 4], vec![1, 4, 3, 5, 6]),
+		Changed(vec![
+			VecChange::Moved { from: 3, to: 1 },
+			VecChange::Added(3, 5),
+			VecChange::Added(4, 6),
+			VecChange::Removed(1, 2),
+		])
+	);
+	assert_eq!(
+		compare(vec![1, 2, 2], vec![2, 5, 2]),
+		Changed(vec![VecChange::Moved { from: 1, to: 0 }, VecChange::Added(1, 5), VecChange::Removed(0, 1)])
+	);
+	assert_eq!(
+		compare(vec![1, 2], vec![3]),
+		Changed(vec![VecChange::Changed(0, I32Change(1, 3)), VecChange::Removed(1, 2)])
+	);
+
+	// Without the option, moved elements are changes at each index.
+	assert_changes!(
+		&vec![1, 2],
+		&vec![2, 1],
+		Changed(vec![VecChange::Changed(0, I32Change(1, 2)), VecChange::Changed(1, I32Change(2, 1))])
+	);
+}
+
+fn aligned<T: Comparable>(algorithm: DiffAlgorithm, a: &T, b: &T) -> comparable::Changed<T::Change> {
+	let options = DiffOptions { sequence_algorithm: Some(algorithm), ..DiffOptions::default() };
+	with_diff_options(options, || a.comparison(b))
+}
+
+const ALGORITHMS: [DiffAlgorithm; 3] = [DiffAlgorithm::Myers, DiffAlgorithm::Patience, DiffAlgorithm::Histogram];
+
+#[test]
+fn test_vec_algorithms() {
+	for algorithm in ALGORITHMS {
+		assert_eq!(aligned(algorithm, &vec![1, 2, 3], &vec![1, 2, 3]), Unchanged);
+		assert_eq!(aligned(algorithm, &vec![1, 2, 3, 4], &vec![1, 9, 2, 3, 4]), Changed(vec![VecChange::Added(1, 9)]));
+		assert_eq!(aligned(algorithm, &vec![1, 2, 3, 4], &vec![1, 3, 4]), Changed(vec![VecChange::Removed(1, 2)]));
+		assert_eq!(
+			aligned(algorithm, &vec![1, 2, 3, 4], &vec![1, 5, 3, 6, 7]),
+			Changed(vec![
+				VecChange::Changed(1, I32Change(2, 5)),
+				VecChange::Changed(3, I32Change(4, 6)),
+				VecChange::Added(4, 7),
+			])
+		);
+		assert_eq!(
+			aligned(algorithm, &vec![], &vec![1, 2]),
+			Changed(vec![VecChange::Added(0, 1), VecChange::Added(1, 2)])
+		);
+		assert_eq!(
+			aligned(algorithm, &vec![1, 2], &vec![]),
+			Changed(vec![VecChange::Removed(0, 1), VecChange::Removed(1, 2)])
+		);
+		// A function inserted before another shares its braces, which should
+		// not be matched with those of the function before.
+		let before = vec!["fn a", "{", "x", "}", "fn b", "{", "y", "}"];
+		let after = vec!["fn a", "{", "x", "}", "fn c", "{", "z", "}", "fn b", "{", "y", "}"];
+		assert_eq!(
+			aligned(algorithm, &before, &after),
+			Changed(vec![
+				VecChange::Added(4, "fn c".to_string()),
+				VecChange::Added(5, "{".to_string()),
+				VecChange::Added(6, "z".to_string()),
+				VecChange::Added(7, "}".to_string()),
+			])
+		);
+	}
+
+	// Patience diff aligns on the unique element 'c', where Myers' algorithm
+	// finds a longer alignment.
+	let before: Vec<char> = "bdabc".chars().collect();
+	let after: Vec<char> = "cdbbbdd".chars().collect();
+	assert_eq!(
+		aligned(DiffAlgorithm::Myers, &before, &after),
+		Changed(vec![
+			VecChange::Added(0, 'c'),
+			VecChange::Added(1, 'd'),
+			VecChange::Added(2, 'b'),
+			VecChange::Added(3, 'b'),
+			VecChange::Changed(6, CharChange('a', 'd')),
+			VecChange::Removed(3, 'b'),
+			VecChange::Removed(4, 'c'),
+		])
+	);
+	assert!(matches!(aligned(DiffAlgorithm::Patience, &before, &after), Changed(changes) if changes.len() == 10));
+}
+
+#[test]
+fn test_vec_algorithms_large() {
+	// Aligning long sequences with nothing in common takes space linear in
+	// their lengths, and their elements are classified by fingerprint.
+	let before: Vec<i64> = (0..4000).collect();
+	let after: Vec<i64> = (4000..8000).collect();
+	assert!(matches!(
+		aligned(DiffAlgorithm::Myers, &before, &after),
+		Changed(changes) if changes.len() == 4000
+			&& changes.iter().all(|change| matches!(change, VecChange::Changed(..)))
+	));
+
+	let before: Vec<String> = (0..2000).map(|i| format!("line {}", i)).collect();
+	let mut after = before.clone();
+	after.insert(1000, "inserted".to_string());
+	after.remove(10);
+	assert_eq!(
+		aligned(DiffAlgorithm::Myers, &before, &after),
+		Changed(vec![VecChange::Removed(10, "line 10".to_string()), VecChange::Added(999, "inserted".to_string())])
+	);
+}
+
+#[derive(Comparable, Clone, PartialEq)]
+struct Entry {
+	id: u32,
+	label: String,
+}
+
+fn entry(id: u32, label: &str) -> Entry {
+	Entry { id, label: label.to_string() }
+}
+
+#[test]
+fn test_vec_similarity() {
+	let before = vec![entry(1, "x"), entry(2, "a")];
+	let after = vec![entry(2, "b"), entry(3, "y"), entry(4, "z")];
+	let similar = DiffOptions { sequence_similarity: Some(0.5), ..DiffOptions::default() };
+	assert_eq!(
+		with_diff_options(similar, || before.comparison(&after)),
+		Changed(vec![
+			VecChange::Changed(0, vec![EntryChange::Label(StringChange("a".to_string(), "b".to_string()))]),
+			VecChange::Removed(0, EntryDesc { id: 1, label: "x".to_string() }),
+			VecChange::Added(1, EntryDesc { id: 3, label: "y".to_string() }),
+			VecChange::Added(2, EntryDesc { id: 4, label: "z".to_string() }),
+		])
+	);
+
+	// Without it, elements between aligned ones are paired by position.
+	assert_eq!(
+		aligned(DiffAlgorithm::Myers, &before, &after),
+		Changed(vec![
+			VecChange::Changed(
+				0,
+				vec![
+					EntryChange::Id(U32Change(1, 2)),
+					EntryChange::Label(StringChange("x".to_string(), "b".to_string())),
+				]
+			),
+			VecChange::Changed(
+				1,
+				vec![
+					EntryChange::Id(U32Change(2, 3)),
+					EntryChange::Label(StringChange("a".to_string(), "y".to_string())),
+				]
+			),
+			VecChange::Added(2, EntryDesc { id: 4, label: "z".to_string() }),
+		])
+	);
+
+	// Elements that are not similar enough are removed and added.
+	let strict = DiffOptions { sequence_similarity: Some(0.9), ..similar };
+	assert!(matches!(with_diff_options(strict, || before.comparison(&after)), Changed(changes) if changes.len() == 5));
+}
+
+// The length of the longest common subsequence of `a` and `b`.
+fn lcs_len(a: &[u8], b: &[u8]) -> usize {
+	let mut lengths = vec![vec![0; b.len() + 1]; a.len() + 1];
+	for i in 0..a.len() {
+		for j in 0..b.len() {
+			lengths[i + 1][j + 1] =
+				if a[i] == b[j] { lengths[i][j] + 1 } else { lengths[i][j + 1].max(lengths[i + 1][j]) };
+		}
+	}
+	lengths[a.len()][b.len()]
+}
+
+proptest! {
+
+#[test]
+fn test_vec_algorithm_alignments(a in prop::collection::vec(0u8..4, 0..40), b in prop::collection::vec(0u8..4, 0..40)) {
+	for algorithm in ALGORITHMS {
+		let changes = aligned(algorithm, &a, &b).unwrap_or_default();
+		let added = changes.iter().filter(|c| matches!(c, VecChange::Added(..))).count();
+		let removed = changes.iter().filter(|c| matches!(c, VecChange::Removed(..))).count();
+		let changed = changes.iter().filter(|c| matches!(c, VecChange::Changed(..))).count();
+		// Every element is either aligned, changed, added or removed.
+		prop_assert_eq!(a.len() - removed - changed, b.len() - added - changed);
+		if algorithm == DiffAlgorithm::Myers {
+			prop_assert_eq!(a.len() - removed - changed, lcs_len(&a, &b));
+		}
+	}
+}
+
+}
+
 #[test]
 fn test_hashset() {
 	assert_changes!(&(vec![] as Vec<i32>), &vec![], Unchanged);
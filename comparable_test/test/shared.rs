@@ -0,0 +1,58 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use comparable::{Changed::*, *};
+
+#[derive(Comparable)]
+struct Node {
+	value: i32,
+	next: Option<Rc<RefCell<Node>>>,
+}
+
+// Build a cycle of nodes with the given values, the last pointing back to
+// the first.
+fn cycle(values: &[i32]) -> Rc<RefCell<Node>> {
+	let first = Rc::new(RefCell::new(Node { value: values[0], next: None }));
+	let last = values[1..].iter().fold(first.clone(), |prev, &value| {
+		let node = Rc::new(RefCell::new(Node { value, next: None }));
+		prev.borrow_mut().next = Some(node.clone());
+		node
+	});
+	last.borrow_mut().next = Some(first.clone());
+	first
+}
+
+#[test]
+fn test_shared_pointers() {
+	assert_changes!(&Rc::new(1), &Rc::new(2), Changed(I32Change(1, 2)));
+	assert_changes!(&Arc::new(1), &Arc::new(1), Unchanged);
+	assert_changes!(&RefCell::new(1), &RefCell::new(2), Changed(I32Change(1, 2)));
+	assert_eq!(Rc::new(1).describe(), SharedDesc::Value(1));
+
+	let shared = Rc::new(5);
+	assert_eq!(vec![shared.clone(), shared.clone()].describe(), vec![SharedDesc::Value(5), SharedDesc::Value(5)]);
+	assert_eq!(
+		Rc::new((shared.clone(), shared)).describe(),
+		SharedDesc::Value((SharedDesc::Value(5), SharedDesc::Seen(1)))
+	);
+}
+
+#[test]
+fn test_shared_cycles() {
+	let a = cycle(&[1, 2]);
+	assert_eq!(
+		a.describe(),
+		SharedDesc::Value(NodeDesc {
+			value: 1,
+			next: Box::new(Some(SharedDesc::Value(NodeDesc { value: 2, next: Box::new(Some(SharedDesc::Seen(0))) })))
+		})
+	);
+	assert_changes!(&a, &a, Unchanged);
+	assert_changes!(&a, &cycle(&[1, 2]), Unchanged);
+	assert_changes!(
+		&a,
+		&cycle(&[1, 3]),
+		Changed(vec![NodeChange::Next(Box::new(OptionChange::BothSome(vec![NodeChange::Value(I32Change(2, 3))])))])
+	);
+}
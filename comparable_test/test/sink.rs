@@ -0,0 +1,67 @@
+use std::sync::mpsc;
+
+use comparable::sink::ChangeSink;
+use comparable::tree::ChangeTree;
+use comparable::watch::Store;
+use comparable::*;
+
+#[derive(Comparable, Clone)]
+struct Settings {
+	theme: String,
+	volume: u8,
+}
+
+fn settings(theme: &str, volume: u8) -> Settings {
+	Settings { theme: theme.to_string(), volume }
+}
+
+#[test]
+fn test_tracked_sink() {
+	let mut changes = Vec::new();
+	let mut tracked = Tracked::new(settings("dark", 5));
+	assert_eq!(tracked.send_changes(&mut changes), Ok(false));
+	tracked.volume = 7;
+	assert_eq!(tracked.send_changes(&mut changes), Ok(true));
+	assert_eq!(changes, vec![vec![SettingsChange::Volume(U8Change(5, 7))]]);
+
+	let (mut sender, receiver) = mpsc::sync_channel(1);
+	tracked.theme = "light".to_string();
+	assert_eq!(tracked.send_changes(&mut sender), Ok(true));
+	assert_eq!(
+		receiver.try_recv(),
+		Ok(vec![SettingsChange::Theme(StringChange("dark".to_string(), "light".to_string()))])
+	);
+	drop(receiver);
+	tracked.volume = 8;
+	assert!(tracked.send_changes(&mut sender).is_err());
+}
+
+#[test]
+fn test_store_sink() {
+	let (sender, receiver) = mpsc::channel();
+	let mut store = Store::new(settings("dark", 5));
+	store.subscribe_sink("volume", sender);
+	store.update(settings("light", 5));
+	store.update(settings("light", 7));
+	assert_eq!(receiver.try_iter().collect::<Vec<_>>(), vec![vec![SettingsChange::Volume(U8Change(5, 7))]]);
+}
+
+#[test]
+fn test_crossbeam_sink() {
+	let (mut sender, receiver) = crossbeam_channel::unbounded();
+	sender.send_change(U8Change(1, 2)).unwrap();
+	assert_eq!(receiver.try_recv(), Ok(U8Change(1, 2)));
+}
+
+#[test]
+fn test_broadcast_sink() {
+	// Broadcast channels require their values to be `Clone`, as change trees
+	// are.
+	let (mut sender, mut first) = tokio::sync::broadcast::channel(4);
+	let mut second = sender.subscribe();
+	sender.send_change(ChangeTree::modified(&5, &7)).unwrap();
+	assert_eq!(first.try_recv(), Ok(ChangeTree::modified(&5, &7)));
+	assert_eq!(second.try_recv(), Ok(ChangeTree::modified(&5, &7)));
+	drop((first, second));
+	assert!(sender.send_change(ChangeTree::modified(&7, &8)).is_err());
+}
@@ -0,0 +1,180 @@
+use comparable::sql::{Error, SqlChangeset, SqlUpdate, SqlValue};
+use comparable::*;
+
+#[derive(Comparable, Clone)]
+#[comparable_sql(table = "users", key = "id")]
+struct User {
+	id: u64,
+	name: String,
+	age: Option<u8>,
+	score: f64,
+	#[comparable_synthetic { let initial = |x: &Self| -> char { x.name.chars().next().unwrap_or(' ') }; }]
+	active: bool,
+}
+
+#[derive(Comparable)]
+#[comparable_sql(table = "counters", key = "name")]
+struct Counter {
+	#[comparable_ignore]
+	name: String,
+	count: i32,
+}
+
+#[derive(Comparable)]
+#[comparable_sql(table = "settings", key = "key")]
+struct Setting<T> {
+	key: String,
+	value: T,
+}
+
+fn update(table: &str, assignments: Vec<(&str, SqlValue)>, key: (&str, SqlValue)) -> SqlUpdate {
+	SqlUpdate {
+		table: table.to_string(),
+		assignments: assignments.into_iter().map(|(column, value)| (column.to_string(), value)).collect(),
+		key: (key.0.to_string(), key.1),
+	}
+}
+
+#[test]
+fn test_sql_update() {
+	let before = User { id: 1, name: "Alice".to_string(), age: Some(30), score: 1.5, active: true };
+	let after = User { id: 2, name: "Bob".to_string(), age: Some(31), score: 1.5, active: false };
+	let sql = before.sql_update(&before.comparison(&after).unwrap_or_default()).unwrap();
+	assert_eq!(sql.statement(), "UPDATE users SET id = ?, name = ?, age = ?, active = ? WHERE id = ?");
+	assert_eq!(
+		sql,
+		update(
+			"users",
+			vec![
+				("id", SqlValue::Unsigned(2)),
+				("name", SqlValue::Text("Bob".to_string())),
+				("age", SqlValue::Unsigned(31)),
+				("active", SqlValue::Boolean(false)),
+			],
+			("id", SqlValue::Unsigned(1))
+		)
+	);
+
+	let after = User { age: None, score: 2.5, ..before.clone() };
+	assert_eq!(
+		before.sql_update(&before.comparison(&after).unwrap_or_default()).unwrap().parameters(),
+		vec![&SqlValue::Null, &SqlValue::Real(2.5), &SqlValue::Unsigned(1)]
+	);
+
+	// The key need not be compared itself.
+	let before = Counter { name: "visits".to_string(), count: 1 };
+	let change = before.comparison(&Counter { name: "visits".to_string(), count: 2 }).next().unwrap();
+	assert_eq!(
+		before.sql_update(&change),
+		Some(update("counters", vec![("count", SqlValue::Integer(2))], ("name", SqlValue::Text("visits".to_string()))))
+	);
+
+	let before = Setting { key: "theme".to_string(), value: 'a' };
+	let after = Setting { key: "theme".to_string(), value: 'b' };
+	assert_eq!(
+		before.sql_update(&before.comparison(&after).unwrap_or_default()).unwrap().statement(),
+		"UPDATE settings SET value = ? WHERE key = ?"
+	);
+
+	// There is no statement setting no columns.
+	let before = User { id: 1, name: "Alice".to_string(), age: Some(30), score: 1.5, active: true };
+	assert_eq!(before.sql_update(&before.comparison(&before.clone()).unwrap_or_default()), None);
+}
+
+#[test]
+fn test_signed() {
+	let sql = update("counters", vec![("count", SqlValue::Unsigned(2))], ("id", SqlValue::Unsigned(7)));
+	assert_eq!(
+		sql.signed(),
+		Ok(update("counters", vec![("count", SqlValue::Integer(2))], ("id", SqlValue::Integer(7))))
+	);
+	let sql = update("counters", vec![("count", SqlValue::Unsigned(u64::MAX))], ("id", SqlValue::Unsigned(7)));
+	assert!(sql.signed().is_err());
+}
+
+const CREATE_USERS: &str =
+	"CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT, age INTEGER, score REAL, active BOOLEAN)";
+
+fn changed_user() -> (User, User) {
+	let before = User { id: 1, name: "Alice".to_string(), age: Some(30), score: 1.5, active: true };
+	let after = User { name: "Alicia".to_string(), age: None, score: 2.5, active: false, ..before.clone() };
+	(before, after)
+}
+
+#[test]
+fn test_diesel_update() {
+	use comparable::sql::diesel::DieselUpdate;
+	use diesel::sql_types::{BigInt, Bool, Double, Nullable, Text};
+	use diesel::{Connection, RunQueryDsl, SqliteConnection};
+
+	let empty = update("users", vec![], ("id", SqlValue::Integer(1)));
+	assert_eq!(DieselUpdate::new(empty), Err(Error::NoAssignments));
+
+	let (before, after) = changed_user();
+	let update = DieselUpdate::new(before.sql_update(&before.comparison(&after).unwrap_or_default()).unwrap()).unwrap();
+	assert_eq!(
+		diesel::debug_query::<diesel::sqlite::Sqlite, _>(&update).to_string(),
+		"UPDATE `users` SET `name` = ?, `age` = NULL, `score` = ?, `active` = ? WHERE `id` = ? \
+		 -- binds: [\"Alicia\", 2.5, false, 1]"
+	);
+
+	let mut conn = SqliteConnection::establish(":memory:").unwrap();
+	diesel::sql_query(CREATE_USERS).execute(&mut conn).unwrap();
+	diesel::sql_query("INSERT INTO users VALUES (1, 'Alice', 30, 1.5, TRUE), (2, 'Bob', 40, 0.5, TRUE)")
+		.execute(&mut conn)
+		.unwrap();
+	assert_eq!(update.execute(&mut conn), Ok(1));
+	#[derive(diesel::QueryableByName, PartialEq, Debug)]
+	struct Row {
+		#[diesel(sql_type = Text)]
+		name: String,
+		#[diesel(sql_type = Nullable<BigInt>)]
+		age: Option<i64>,
+		#[diesel(sql_type = Double)]
+		score: f64,
+		#[diesel(sql_type = Bool)]
+		active: bool,
+	}
+	assert_eq!(
+		diesel::sql_query("SELECT name, age, score, active FROM users WHERE id = 1").get_result::<Row>(&mut conn),
+		Ok(Row { name: "Alicia".to_string(), age: None, score: 2.5, active: false })
+	);
+}
+
+#[tokio::test]
+async fn test_sqlx_update() {
+	use comparable::sql::sqlx::query_builder;
+	use sqlx::{Connection, SqliteConnection};
+
+	let (before, after) = changed_user();
+	let sql = before.sql_update(&before.comparison(&after).unwrap_or_default()).unwrap();
+	let mut query = query_builder::<sqlx::Sqlite>(&sql).unwrap();
+	assert_eq!(query.sql(), r#"UPDATE "users" SET "name" = ?, "age" = NULL, "score" = ?, "active" = ? WHERE "id" = ?"#);
+
+	let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+	sqlx::query(CREATE_USERS).execute(&mut conn).await.unwrap();
+	sqlx::query("INSERT INTO users VALUES (1, 'Alice', 30, 1.5, TRUE), (2, 'Bob', 40, 0.5, TRUE)")
+		.execute(&mut conn)
+		.await
+		.unwrap();
+	assert_eq!(query.build().execute(&mut conn).await.unwrap().rows_affected(), 1);
+	let row: (String, Option<i64>, f64, bool) =
+		sqlx::query_as("SELECT name, age, score, active FROM users WHERE id = 1").fetch_one(&mut conn).await.unwrap();
+	assert_eq!(row, ("Alicia".to_string(), None, 2.5, false));
+
+	let too_large = SqlUpdate {
+		table: "users".to_string(),
+		assignments: vec![("age".to_string(), SqlValue::Unsigned(u64::MAX))],
+		key: ("id".to_string(), SqlValue::Unsigned(1)),
+	};
+	assert!(matches!(query_builder::<sqlx::Sqlite>(&too_large), Err(Error::TooLarge(_))));
+
+	// Names are quoted, and a statement must set a column.
+	let quoted = update("my \"users\"", vec![("first name", SqlValue::Null)], ("id", SqlValue::Integer(1)));
+	assert_eq!(
+		query_builder::<sqlx::Sqlite>(&quoted).unwrap().sql(),
+		r#"UPDATE "my ""users""" SET "first name" = NULL WHERE "id" = ?"#
+	);
+	let empty = update("users", vec![], ("id", SqlValue::Integer(1)));
+	assert!(matches!(query_builder::<sqlx::Sqlite>(&empty), Err(Error::NoAssignments)));
+}
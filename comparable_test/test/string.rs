@@ -9,3 +9,19 @@ fn test_string() {
 		Changed(StringChange("hello".to_string(), "goodbye".to_string())),
 	);
 }
+
+#[test]
+fn test_normalized_strings() {
+	use comparable::{CaseInsensitive, Comparable, Trimmed};
+
+	assert_changes!(&CaseInsensitive("Hello"), &CaseInsensitive("HELLO"), Unchanged);
+	assert_changes!(
+		&CaseInsensitive("Hello".to_string()),
+		&CaseInsensitive("Goodbye".to_string()),
+		Changed(StringChange("Hello".to_string(), "Goodbye".to_string())),
+	);
+	assert_eq!(CaseInsensitive("Hello").describe(), "Hello".to_string());
+
+	assert_changes!(&Trimmed(" name "), &Trimmed("name\n"), Unchanged);
+	assert_changes!(&Trimmed(" a"), &Trimmed("b "), Changed(StringChange(" a".to_string(), "b ".to_string())));
+}
@@ -30,6 +30,28 @@ fn test_struct_1_unnamed_field_scalar() {
 	assert_changes!(&ScalarField(100), &ScalarField(200), Changed(ScalarFieldChange(I32Change(100, 200))),);
 }
 
+#[test]
+fn test_struct_1_field_change_newtype() {
+	#[derive(Comparable)]
+	struct ScalarField(i32);
+
+	#[derive(Comparable)]
+	struct NamedField<T> {
+		value: Vec<T>,
+	}
+
+	let mut change = ScalarField(100).comparison(&ScalarField(200)).unwrap_changed();
+	assert_eq!(*change, I32Change(100, 200));
+	change.1 = 300;
+	assert_eq!(change.into_inner(), I32Change(100, 300));
+	assert_eq!(ScalarFieldChange::new(I32Change(1, 2)), ScalarFieldChange(I32Change(1, 2)));
+
+	let change = NamedField { value: vec![1u8] }.comparison(&NamedField { value: vec![1, 2] }).unwrap_changed();
+	assert_eq!(change.len(), 1);
+	assert_eq!(change.into_inner(), vec![VecChange::Added(1, 2)]);
+	assert_eq!(NamedFieldChange::new(vec![VecChange::<u8, U8Change>::Removed(0, 1)]).len(), 1);
+}
+
 #[test]
 fn test_struct_2_unnamed_fields_scalar() {
 	#[derive(Comparable)]
@@ -209,3 +231,405 @@ fn test_struct_3_named_fields_varying_visibility() {
 		]),
 	);
 }
+
+#[test]
+fn test_struct_field_describe_attrs() {
+	fn redacted(token: &str) -> String {
+		format!("{}...", &token[..2])
+	}
+
+	#[derive(Comparable)]
+	pub struct Credentials {
+		user: String,
+		#[describe_type(String)]
+		#[describe_body(redacted(&self.token))]
+		token: String,
+	}
+
+	#[derive(Comparable)]
+	pub struct Sizes(
+		#[describe_type(usize)]
+		#[describe_body(self.0.len())]
+		Vec<u8>,
+		u8,
+	);
+
+	assert_eq!(
+		Credentials { user: "me".to_string(), token: "secret".to_string() }.describe(),
+		CredentialsDesc { user: "me".to_string(), token: "se...".to_string() }
+	);
+	assert_eq!(Sizes(vec![1, 2, 3], 4).describe(), SizesDesc(3, 4));
+	assert_changes!(
+		&Sizes(vec![1, 2, 3], 4),
+		&Sizes(vec![1, 2], 4),
+		Changed(vec![SizesChange::Field0(vec![VecChange::Removed(2, 3)])])
+	);
+}
+
+#[test]
+fn test_struct_field_redacted() {
+	#[derive(Comparable)]
+	pub struct Login {
+		user: String,
+		#[comparable_redact]
+		password: String,
+	}
+
+	assert_eq!(
+		Login { user: "me".to_string(), password: "hunter2".to_string() }.describe(),
+		LoginDesc { user: "me".to_string(), password: Redacted }
+	);
+	assert_changes!(
+		&Login { user: "me".to_string(), password: "hunter2".to_string() },
+		&Login { user: "me".to_string(), password: "hunter2".to_string() },
+		Unchanged
+	);
+	assert_changes!(
+		&Login { user: "me".to_string(), password: "hunter2".to_string() },
+		&Login { user: "you".to_string(), password: "hunter3".to_string() },
+		Changed(vec![
+			LoginChange::User(StringChange("me".to_string(), "you".to_string())),
+			LoginChange::Password(Redacted)
+		])
+	);
+}
+
+#[test]
+fn test_struct_field_renamed() {
+	#[derive(Comparable)]
+	pub struct Point(#[comparable_rename = "X"] i32, #[comparable_rename = "Y"] i32);
+
+	#[derive(Comparable)]
+	pub struct Account {
+		#[comparable_rename = "Name"]
+		user_name: String,
+		balance: u64,
+	}
+
+	assert_changes!(&Point(1, 2), &Point(1, 3), Changed(vec![PointChange::Y(I32Change(2, 3))]));
+	assert_changes!(
+		&Account { user_name: "a".to_string(), balance: 1 },
+		&Account { user_name: "b".to_string(), balance: 2 },
+		Changed(vec![
+			AccountChange::Name(StringChange("a".to_string(), "b".to_string())),
+			AccountChange::Balance(U64Change(1, 2))
+		])
+	);
+}
+
+#[test]
+fn test_struct_field_flattened() {
+	#[derive(Comparable)]
+	pub struct Address {
+		street: String,
+		city: String,
+	}
+
+	#[derive(Comparable)]
+	pub struct Person {
+		name: String,
+		#[comparable_flatten]
+		address: Address,
+	}
+
+	let person = Person {
+		name: "Alice".to_string(),
+		address: Address { street: "Main".to_string(), city: "Springfield".to_string() },
+	};
+	assert_changes!(&person, &person, Unchanged);
+	assert_changes!(
+		&person,
+		&Person {
+			name: "Bob".to_string(),
+			address: Address { street: "Elm".to_string(), city: "Shelbyville".to_string() },
+		},
+		Changed(vec![
+			PersonChange::Name(StringChange("Alice".to_string(), "Bob".to_string())),
+			PersonChange::Address(AddressChange::Street(StringChange("Main".to_string(), "Elm".to_string()))),
+			PersonChange::Address(AddressChange::City(StringChange(
+				"Springfield".to_string(),
+				"Shelbyville".to_string()
+			))),
+		])
+	);
+}
+
+#[test]
+fn test_struct_transparent() {
+	#[derive(Comparable)]
+	#[comparable_transparent]
+	pub struct UserId(u64);
+
+	#[derive(Comparable)]
+	#[comparable_transparent]
+	pub struct Tags {
+		tags: Vec<String>,
+	}
+
+	#[derive(Comparable)]
+	pub struct User {
+		id: UserId,
+		tags: Tags,
+	}
+
+	assert_eq!(UserId(1).describe(), 1);
+	assert_changes!(&UserId(1), &UserId(1), Unchanged);
+	assert_changes!(&UserId(1), &UserId(2), Changed(U64Change(1, 2)));
+	assert_changes!(
+		&User { id: UserId(1), tags: Tags { tags: vec![] } },
+		&User { id: UserId(2), tags: Tags { tags: vec!["new".to_string()] } },
+		Changed(vec![UserChange::Id(U64Change(1, 2)), UserChange::Tags(vec![VecChange::Added(0, "new".to_string())])])
+	);
+}
+
+#[derive(Comparable)]
+#[comparable_module = "config_changes"]
+pub struct Config {
+	name: String,
+	retries: u8,
+}
+
+#[derive(Comparable)]
+#[comparable_module = "mode_changes"]
+enum Mode {
+	Fast,
+	Slow(u8),
+}
+
+#[test]
+fn test_struct_module() {
+	assert_eq!(
+		Config { name: "a".to_string(), retries: 1 }.describe(),
+		config_changes::ConfigDesc { name: "a".to_string(), retries: 1 }
+	);
+	assert_changes!(
+		&Config { name: "a".to_string(), retries: 1 },
+		&Config { name: "a".to_string(), retries: 2 },
+		Changed(vec![config_changes::ConfigChange::Retries(U8Change(1, 2))])
+	);
+	assert_changes!(&Mode::Slow(1), &Mode::Slow(2), Changed(mode_changes::ModeChange::BothSlow(U8Change(1, 2))));
+	assert_changes!(
+		&Mode::Fast,
+		&Mode::Slow(2),
+		Changed(mode_changes::ModeChange::Different(mode_changes::ModeDesc::Fast, mode_changes::ModeDesc::Slow(2)))
+	);
+}
+
+#[test]
+fn test_struct_from_default() {
+	#[derive(Comparable, Default)]
+	#[comparable_from_default]
+	struct Limits {
+		min: u8,
+		max: u8,
+	}
+
+	assert_eq!(Limits::default().comparison_from_default(), Unchanged);
+	assert_eq!(Limits { min: 0, max: 10 }.comparison_from_default(), Changed(vec![LimitsChange::Max(U8Change(0, 10))]));
+	assert_eq!(from_default(&Limits { min: 1, max: 0 }), Changed(vec![LimitsChange::Min(U8Change(0, 1))]));
+}
+
+#[test]
+fn test_struct_differs() {
+	// A value whose differences are never looked at, as they follow one
+	// already found.
+	#[derive(Debug)]
+	struct Unreachable;
+
+	impl Comparable for Unreachable {
+		type Desc = ();
+		fn describe(&self) -> Self::Desc {}
+		type Change = ();
+		fn comparison(&self, _other: &Self) -> comparable::Changed<Self::Change> {
+			panic!("compared after a difference was found")
+		}
+	}
+
+	#[derive(Comparable)]
+	struct Record {
+		id: u64,
+		#[comparable_ignore]
+		_seen: u64,
+		tags: std::collections::BTreeSet<String>,
+		scores: std::collections::BTreeMap<String, Vec<u8>>,
+		rest: Unreachable,
+	}
+
+	let record = |id, tag: &str, score| Record {
+		id,
+		_seen: 0,
+		tags: vec![tag.to_string()].into_iter().collect(),
+		scores: vec![("a".to_string(), vec![1, score])].into_iter().collect(),
+		rest: Unreachable,
+	};
+	assert!(record(1, "x", 2).differs(&record(2, "x", 2)));
+	assert!(record(1, "x", 2).differs(&record(1, "y", 2)));
+	assert!(record(1, "x", 2).differs(&record(1, "x", 3)));
+
+	assert!(Mode::Fast.differs(&Mode::Slow(1)));
+	assert!(Mode::Slow(1).differs(&Mode::Slow(2)));
+	assert!(!Mode::Slow(1).differs(&Mode::Slow(1)));
+	assert!(!Config { name: "a".to_string(), retries: 1 }.differs(&Config { name: "a".to_string(), retries: 1 }));
+	assert!(Config { name: "a".to_string(), retries: 1 }.differs(&Config { name: "a".to_string(), retries: 2 }));
+	assert!(!vec![1u8].differs(&vec![1]));
+	assert!(vec![1u8].differs(&vec![1, 1]));
+}
+
+#[test]
+fn test_struct_count_changes() {
+	#[derive(Comparable)]
+	struct Record {
+		id: u64,
+		#[comparable_ignore]
+		_seen: u64,
+		tags: std::collections::BTreeSet<String>,
+		scores: std::collections::HashMap<String, Vec<u8>>,
+		parent: Option<Box<Record>>,
+	}
+
+	let record = |id, tags: &[&str], scores: &[(&str, &[u8])]| Record {
+		id,
+		_seen: id,
+		tags: tags.iter().map(|t| t.to_string()).collect(),
+		scores: scores.iter().map(|(k, v)| (k.to_string(), v.to_vec())).collect(),
+		parent: None,
+	};
+	let before = record(1, &["a", "b"], &[("x", &[1, 2]), ("y", &[3])]);
+	assert_eq!(before.count_changes(&record(1, &["a", "b"], &[("x", &[1, 2]), ("y", &[3])])), 0);
+	assert_eq!(before.count_changes(&record(2, &["a", "b"], &[("x", &[1, 2]), ("y", &[3])])), 1);
+	assert_eq!(before.count_changes(&record(1, &["b", "c"], &[("x", &[1, 2]), ("y", &[3])])), 2);
+	assert_eq!(before.count_changes(&record(1, &["a", "b"], &[("x", &[0, 0, 0]), ("z", &[3])])), 5);
+
+	let mut child = record(3, &[], &[]);
+	child.parent = Some(Box::new(record(1, &[], &[])));
+	let mut other = record(3, &[], &[]);
+	assert_eq!(child.count_changes(&other), 1);
+	other.parent = Some(Box::new(record(2, &["a"], &[])));
+	assert_eq!(child.count_changes(&other), 2);
+
+	assert_eq!(Mode::Fast.count_changes(&Mode::Slow(1)), 1);
+	assert_eq!(Mode::Slow(1).count_changes(&Mode::Slow(2)), 1);
+	assert_eq!(Mode::Fast.count_changes(&Mode::Fast), 0);
+	assert_eq!(
+		Config { name: "a".to_string(), retries: 1 }.count_changes(&Config { name: "b".to_string(), retries: 2 }),
+		2
+	);
+}
+
+#[test]
+fn test_struct_first_difference() {
+	use comparable::location::{Path, Segment};
+	use std::collections::HashMap;
+
+	#[derive(Comparable, Clone, PartialEq)]
+	struct Point(i32, i32);
+
+	#[derive(Comparable, Clone)]
+	struct Shape {
+		name: String,
+		#[comparable_ignore]
+		_seen: u64,
+		points: Vec<Point>,
+		labels: HashMap<String, Option<Point>>,
+	}
+
+	let before = Shape {
+		name: "line".to_string(),
+		_seen: 0,
+		points: vec![Point(0, 0), Point(1, 1)],
+		labels: vec![("start".to_string(), Some(Point(0, 1))), ("end".to_string(), None)].into_iter().collect(),
+	};
+	let first_difference = |after: &Shape| before.first_difference(after).map(|path| path.to_string());
+	assert_eq!(first_difference(&Shape { _seen: 1, ..before.clone() }), None);
+	assert_eq!(
+		first_difference(&Shape { name: "curve".to_string(), points: vec![], ..before.clone() }),
+		Some("name".to_string())
+	);
+	assert_eq!(
+		first_difference(&Shape { points: vec![Point(0, 0), Point(1, 2)], ..before.clone() }),
+		Some("points[1].1".to_string())
+	);
+	assert_eq!(first_difference(&Shape { points: vec![Point(0, 0)], ..before.clone() }), Some("points[1]".to_string()));
+
+	let mut after = before.clone();
+	after.labels.insert("start".to_string(), Some(Point(0, 2)));
+	after.labels.insert("end".to_string(), Some(Point(9, 9)));
+	assert_eq!(
+		before.first_difference(&after),
+		Some(Path::from(vec![Segment::Field("labels".to_string()), Segment::Key("\"end\"".to_string())]))
+	);
+	after.labels.remove("end");
+	assert_eq!(first_difference(&after), Some("labels[\"end\"]".to_string()));
+	after.labels.insert("end".to_string(), None);
+	assert_eq!(first_difference(&after), Some("labels[\"start\"].1".to_string()));
+
+	assert_eq!(Mode::Fast.first_difference(&Mode::Slow(1)), Some(Path::default()));
+	assert_eq!(Path::default().to_string(), ".");
+}
+
+mod config_v1 {
+	#[allow(dead_code)]
+	pub struct Config {
+		pub name: String,
+		pub retries: u8,
+		pub verbose: bool,
+	}
+}
+
+#[derive(Comparable)]
+#[comparable_from(config_v1::Config, removed(verbose))]
+pub struct ConfigV2 {
+	name: String,
+	retries: u8,
+	#[comparable_added]
+	timeout: u64,
+	#[comparable_ignore]
+	_cache: Vec<u8>,
+}
+
+#[derive(Comparable)]
+#[comparable_from(Config)]
+pub struct RenamedConfig {
+	name: String,
+	#[comparable_rename = "Attempts"]
+	retries: u8,
+}
+
+#[test]
+fn test_struct_comparable_from() {
+	let before = config_v1::Config { name: "a".to_string(), retries: 1, verbose: true };
+	assert_eq!(
+		ConfigV2 { name: "a".to_string(), retries: 3, timeout: 30, _cache: vec![] }.comparison_from(&before),
+		Changed(vec![
+			ConfigV2FromConfigChange::Retries(U8Change(1, 3)),
+			ConfigV2FromConfigChange::AddedTimeout(30),
+			ConfigV2FromConfigChange::Removed("verbose".to_string()),
+		])
+	);
+
+	let before = Config { name: "a".to_string(), retries: 1 };
+	assert_eq!(RenamedConfig { name: "a".to_string(), retries: 1 }.comparison_from(&before), Unchanged);
+	assert_eq!(
+		RenamedConfig { name: "b".to_string(), retries: 2 }.comparison_from(&before),
+		Changed(vec![
+			RenamedConfigFromConfigChange::Name(StringChange("a".to_string(), "b".to_string())),
+			RenamedConfigFromConfigChange::Attempts(U8Change(1, 2)),
+		])
+	);
+}
+
+#[test]
+fn test_struct_free_functions() {
+	#[derive(comparable::Comparable)]
+	struct Limits {
+		min: u8,
+		max: u8,
+	}
+
+	let (before, after) = (Limits { min: 0, max: 10 }, Limits { min: 1, max: 10 });
+	assert!(comparable::changed(&before, &after));
+	assert!(!comparable::changed(&before, &before));
+	assert_eq!(comparable::diff(&before, &after), Changed(vec![LimitsChange::Min(U8Change(0, 1))]));
+	assert_eq!(comparable::diff(&after, &after), Unchanged);
+	assert_eq!(comparable::describe(&before), LimitsDesc { min: 0, max: 10 });
+}
@@ -0,0 +1,70 @@
+use comparable::codec;
+use comparable::sync::{Error, Message, Publisher, Subscriber};
+use comparable::*;
+
+#[derive(Comparable, Clone, PartialEq, Debug)]
+#[comparable_desc_roundtrip]
+struct Scoreboard {
+	home: u32,
+	away: u32,
+	period: String,
+}
+
+fn apply(board: &mut Scoreboard, changes: Vec<ScoreboardChange>) {
+	for change in changes {
+		match change {
+			ScoreboardChange::Home(U32Change(_, home)) => board.home = home,
+			ScoreboardChange::Away(U32Change(_, away)) => board.away = away,
+			ScoreboardChange::Period(StringChange(_, period)) => board.period = period,
+		}
+	}
+}
+
+fn board(home: u32, away: u32, period: &str) -> Scoreboard {
+	Scoreboard { home, away, period: period.to_string() }
+}
+
+#[test]
+fn test_sync_changes() {
+	let mut publisher = Publisher::new(board(0, 0, "first"));
+	let mut subscriber = Subscriber::new();
+	assert_eq!(subscriber.receive(&publisher.snapshot().unwrap(), apply), Ok(0));
+	publisher.acknowledge(0);
+
+	assert_eq!(publisher.publish(&board(0, 0, "first")), Ok(None));
+	let message = publisher.publish(&board(1, 0, "first")).unwrap().unwrap();
+	let bytes = codec::encode(&message).unwrap();
+	let version = subscriber.receive(&codec::decode::<Message>(&bytes).unwrap(), apply).unwrap();
+	assert_eq!(version, 1);
+	publisher.acknowledge(version);
+	assert_eq!(publisher.acknowledged(), 1);
+	assert_eq!(subscriber.value(), Some(&board(1, 0, "first")));
+
+	// Receiving the same changes again has no effect.
+	assert_eq!(subscriber.receive(&message, apply), Ok(1));
+	assert_eq!(subscriber.value(), Some(&board(1, 0, "first")));
+}
+
+#[test]
+fn test_sync_resync() {
+	let mut publisher = Publisher::new(board(0, 0, "first"));
+	let mut subscriber = Subscriber::new();
+	let message = publisher.publish(&board(1, 0, "first")).unwrap().unwrap();
+	assert_eq!(subscriber.receive(&message, apply), Err(Error::Desync { version: None, base: 0 }));
+	assert!(matches!(publisher.resync(subscriber.version()), Ok(Message::Snapshot { version: 1, .. })));
+	publisher.acknowledge(subscriber.receive(&publisher.resync(None).unwrap(), apply).unwrap());
+
+	// A lost message is recovered by sending the changes since the version
+	// the subscriber has, which is still known to the publisher.
+	let _lost = publisher.publish(&board(1, 1, "first")).unwrap();
+	let message = publisher.publish(&board(1, 1, "second")).unwrap().unwrap();
+	assert_eq!(subscriber.receive(&message, apply), Err(Error::Desync { version: Some(1), base: 2 }));
+	let resync = publisher.resync(subscriber.version()).unwrap();
+	assert!(matches!(resync, Message::Changes { base: 1, version: 3, .. }));
+	publisher.acknowledge(subscriber.receive(&resync, apply).unwrap());
+	assert_eq!(publisher.acknowledged(), 3);
+	assert_eq!(subscriber.value(), Some(publisher.value()));
+
+	// Versions before the last acknowledged one are forgotten.
+	assert!(matches!(publisher.resync(Some(1)), Ok(Message::Snapshot { version: 3, .. })));
+}
@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use comparable::mask::FieldMask;
+use comparable::task::{diff_blocking, diff_slices_yielding};
+use comparable::*;
+
+#[derive(Comparable, PartialEq)]
+struct Point {
+	x: i32,
+	y: i32,
+}
+
+fn points(n: i32, offset: i32) -> Vec<Point> {
+	(0..n).map(|i| Point { x: i, y: if i % 100 == 0 { i + offset } else { i } }).collect()
+}
+
+#[tokio::test]
+async fn test_diff_blocking() {
+	let before = Arc::new(points(1000, 0));
+	let after = Arc::new(points(1000, 1));
+	assert_eq!(diff_blocking(before.clone(), after.clone()).await, before.comparison(&after));
+	assert_eq!(diff_blocking(before.clone(), before.clone()).await, Changed::Unchanged);
+}
+
+#[tokio::test]
+async fn test_diff_blocking_settings() {
+	let before = Arc::new(vec![1.0, 2.0]);
+	let after = Arc::new(vec![1.0, 2.0 + 1e-12]);
+	let tolerance = FloatTolerance::Absolute(1e-9);
+	assert_eq!(with_float_tolerance(tolerance, || diff_blocking(before, after)).await, Changed::Unchanged);
+
+	let before = Arc::new(Point { x: 1, y: 1 });
+	let after = Arc::new(Point { x: 2, y: 2 });
+	let mask = FieldMask::include(["y"]);
+	assert_eq!(
+		comparable::mask::with_field_mask(&mask, || diff_blocking(before, after)).await,
+		Changed::Changed(vec![PointChange::Y(I32Change(1, 2))])
+	);
+}
+
+#[tokio::test]
+async fn test_diff_slices_yielding() {
+	let before = points(1000, 0);
+	let after = points(1003, 1);
+	assert_eq!(diff_slices_yielding(&before, &after, 64).await, before.comparison(&after));
+	assert_eq!(diff_slices_yielding(&after, &before, 7).await, after.comparison(&before));
+	assert_eq!(diff_slices_yielding(&before, &before, 0).await, Changed::Unchanged);
+}
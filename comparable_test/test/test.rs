@@ -1,12 +1,61 @@
 #![allow(clippy::derive_partial_eq_without_eq)]
 
+mod arena;
+mod atomic;
+mod audit;
+mod binary;
 mod boxes;
+mod by;
+mod changed;
+mod codec;
+mod crate_path;
+mod describe;
+mod display;
 mod empty;
+#[allow(dead_code)]
 mod enums;
+mod env;
+mod fallible;
+mod flags;
+mod format;
+mod fs;
+mod generics;
+mod group;
+mod history;
+mod hygiene;
+mod ignore;
+mod journal;
+mod json;
+mod leaf;
+mod lens;
+mod light;
 mod map;
+mod mask;
+mod merge;
+mod numeric;
 mod opt;
+mod persistent;
+mod prelude;
+mod range;
+mod recursive;
+mod roundtrip;
 mod scalar;
+mod schema;
 mod set;
+mod shared;
+mod sink;
+mod sql;
 mod string;
+#[allow(dead_code, clippy::to_string_trait_impl)]
 mod structs;
+mod sync;
+mod task;
+mod testing;
+mod trace;
+mod tracked;
+mod transitions;
+mod tree;
 mod unions;
+mod versioned;
+mod watch;
+mod wire;
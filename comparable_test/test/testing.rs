@@ -0,0 +1,60 @@
+use std::collections::BTreeMap;
+
+use comparable::testing::{assert_apply_law, assert_comparable_laws};
+use comparable::{Changed, Comparable, I32Change, StringChange};
+use proptest::prelude::*;
+
+#[derive(Comparable, Clone, Debug)]
+struct Point {
+	x: i32,
+	label: String,
+}
+
+fn apply_point(point: &mut Point, changes: Vec<PointChange>) {
+	for change in changes {
+		match change {
+			PointChange::X(I32Change(_, x)) => point.x = x,
+			PointChange::Label(StringChange(_, label)) => point.label = label,
+		}
+	}
+}
+
+#[test]
+#[should_panic(expected = "applying the changes did not produce the expected value")]
+fn test_apply_law_violation() {
+	assert_apply_law(&1, &2, |_, _| ());
+}
+
+proptest! {
+
+#[test]
+fn test_scalar_laws(a in any::<i64>(), b in any::<i64>()) {
+	assert_comparable_laws(&a, &b);
+	assert_apply_law(&a, &b, |value, change| *value = change.1);
+}
+
+#[test]
+fn test_collection_laws(a in any::<BTreeMap<u8, i32>>(), b in any::<BTreeMap<u8, i32>>()) {
+	assert_comparable_laws(&a, &b);
+}
+
+#[test]
+fn test_derived_laws(x1 in any::<i32>(), x2 in any::<i32>(), l1 in "[a-c]{0,2}", l2 in "[a-c]{0,2}") {
+	let a = Point { x: x1, label: l1 };
+	let b = Point { x: x2, label: l2 };
+	assert_comparable_laws(&a, &b);
+	assert_apply_law(&a, &b, apply_point);
+}
+
+#[test]
+fn test_arbitrary_changed(a in any::<i32>(), offset in any::<Changed<i32>>()) {
+	let b = match offset {
+		Changed::Changed(offset) => a.wrapping_add(offset),
+		Changed::Unchanged => a,
+	};
+	let expected = if a == b { Changed::Unchanged } else { Changed::Changed(I32Change(a, b)) };
+	prop_assert_eq!(a.comparison(&b), expected);
+	assert_comparable_laws(&a, &b);
+}
+
+}
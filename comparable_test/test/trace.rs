@@ -0,0 +1,87 @@
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+use comparable::*;
+
+// The name of the span an event was emitted in, and its fields.
+type Recorded = (String, Vec<(String, String)>);
+
+// Records the fields of each event, and the span it was emitted in.
+#[derive(Default)]
+struct Recorder {
+	spans: Mutex<Vec<&'static str>>,
+	events: Arc<Mutex<Vec<Recorded>>>,
+}
+
+struct Fields(Vec<(String, String)>);
+
+impl Visit for Fields {
+	fn record_str(&mut self, field: &Field, value: &str) {
+		self.0.push((field.name().to_string(), value.to_string()));
+	}
+
+	fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+		self.0.push((field.name().to_string(), format!("{:?}", value)));
+	}
+}
+
+impl Subscriber for Recorder {
+	fn enabled(&self, _: &Metadata<'_>) -> bool {
+		true
+	}
+
+	fn new_span(&self, span: &Attributes<'_>) -> Id {
+		let mut spans = self.spans.lock().unwrap();
+		spans.push(span.metadata().name());
+		Id::from_u64(spans.len() as u64)
+	}
+
+	fn record(&self, _: &Id, _: &Record<'_>) {}
+
+	fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+	fn event(&self, event: &Event<'_>) {
+		let span = event.parent().map_or("", |id| self.spans.lock().unwrap()[id.into_u64() as usize - 1]);
+		let mut fields = Fields(Vec::new());
+		event.record(&mut fields);
+		self.events.lock().unwrap().push((span.to_string(), fields.0));
+	}
+
+	fn enter(&self, _: &Id) {}
+
+	fn exit(&self, _: &Id) {}
+}
+
+#[derive(Comparable)]
+#[comparable_tree]
+struct Service {
+	name: String,
+	replicas: u32,
+	hosts: Vec<String>,
+}
+
+#[test]
+fn test_log_changes() {
+	let recorder = Recorder::default();
+	let events = recorder.events.clone();
+	tracing::subscriber::with_default(recorder, || {
+		let span = tracing::info_span!("reconcile");
+		let before = Service { name: "billing".to_string(), replicas: 2, hosts: vec!["a".to_string()] };
+		let after = Service { name: "billing".to_string(), replicas: 3, hosts: vec![] };
+		trace::log_changes(&span, &before.comparison(&after).unwrap_or_default());
+		trace::log_changes(&span, &before.comparison(&before).unwrap_or_default());
+	});
+
+	let event = |path: &str, kind: &str, old: &str, new: &str| {
+		let fields = [("message", "changed"), ("path", path), ("kind", kind), ("old", old), ("new", new)];
+		("reconcile".to_string(), fields.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect::<Vec<_>>())
+	};
+	assert_eq!(
+		*events.lock().unwrap(),
+		vec![event("replicas", "modified", "2", "3"), event("hosts.0", "removed", "\"a\"", "")]
+	);
+}
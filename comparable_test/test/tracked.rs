@@ -0,0 +1,35 @@
+use comparable::{Changed::*, Comparable, I32Change, Tracked};
+
+#[derive(Comparable, Clone, Default)]
+struct Counter {
+	count: i32,
+	total: i32,
+}
+
+#[test]
+fn test_tracked() {
+	let mut counter = Tracked::new(Counter::default());
+	assert_eq!(counter.take_changes(), Unchanged);
+
+	counter.count += 1;
+	counter.total += 10;
+	assert_eq!(
+		counter.changes(),
+		Changed(vec![CounterChange::Count(I32Change(0, 1)), CounterChange::Total(I32Change(0, 10))])
+	);
+	assert_eq!(
+		counter.take_changes(),
+		Changed(vec![CounterChange::Count(I32Change(0, 1)), CounterChange::Total(I32Change(0, 10))])
+	);
+	assert_eq!(counter.snapshot().count, 1);
+
+	counter.count += 1;
+	assert_eq!(counter.take_changes(), Changed(vec![CounterChange::Count(I32Change(1, 2))]));
+	assert_eq!(counter.take_changes(), Unchanged);
+
+	// Changes which are undone before being taken are not reported.
+	counter.total += 5;
+	counter.total -= 5;
+	assert_eq!(counter.take_changes(), Unchanged);
+	assert_eq!(counter.into_inner().count, 2);
+}
@@ -0,0 +1,51 @@
+use comparable::*;
+
+#[derive(Comparable)]
+#[comparable_transitions]
+enum Job {
+	Queued,
+	Running { pid: u32 },
+	Stopped(i32),
+}
+
+#[derive(Comparable)]
+#[comparable_transitions]
+enum Slot<T> {
+	Empty,
+	Full(T),
+}
+
+// Every transition is handled, so that adding a variant to `Job` makes this
+// fail to compile until its transitions are handled too.
+fn audit(transition: JobTransition) -> String {
+	match transition {
+		JobTransition::QueuedToRunning { after, .. } => format!("started {:?}", after),
+		JobTransition::QueuedToStopped { .. } => "cancelled".to_string(),
+		JobTransition::RunningToQueued { .. } => "requeued".to_string(),
+		JobTransition::RunningToStopped { after, .. } => format!("finished {:?}", after),
+		JobTransition::StoppedToQueued { .. } | JobTransition::StoppedToRunning { .. } => "restarted".to_string(),
+	}
+}
+
+#[test]
+fn test_transitions() {
+	let queued = Job::Queued;
+	let running = Job::Running { pid: 7 };
+	assert_eq!(
+		queued.transition(&running),
+		Some(JobTransition::QueuedToRunning { before: JobDesc::Queued, after: JobDesc::Running { pid: 7 } })
+	);
+	assert_eq!(running.transition(&Job::Running { pid: 8 }), None);
+	assert_eq!(queued.transition(&Job::Queued), None);
+	assert_eq!(audit(running.transition(&Job::Stopped(0)).unwrap()), "finished Stopped(0)");
+	assert_eq!(audit(Job::Stopped(1).transition(&running).unwrap()), "restarted");
+}
+
+#[test]
+fn test_transitions_generic() {
+	assert_eq!(
+		Slot::Empty.transition(&Slot::Full(3u8)),
+		Some(SlotTransition::EmptyToFull { before: SlotDesc::Empty, after: SlotDesc::Full(3) })
+	);
+	assert_eq!(Slot::Full(1u8).transition(&Slot::Full(2)), None);
+}
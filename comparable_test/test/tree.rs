@@ -0,0 +1,146 @@
+use std::collections::BTreeMap;
+
+use comparable::codec::{decode, encode};
+use comparable::tree::{ChangeTree, ToTree};
+use comparable::*;
+
+#[derive(Comparable)]
+#[comparable_tree]
+struct Address {
+	street: String,
+	city: String,
+}
+
+#[derive(Comparable)]
+#[comparable_tree]
+struct Customer {
+	name: String,
+	address: Address,
+	orders: BTreeMap<u32, String>,
+	#[comparable_redact]
+	password: String,
+}
+
+#[derive(Comparable)]
+#[comparable_tree]
+enum Shape {
+	Circle(f64),
+	Rectangle { width: u32, height: u32 },
+	Empty,
+}
+
+fn key(key: &str, tree: ChangeTree) -> (String, ChangeTree) {
+	(key.to_string(), tree)
+}
+
+fn added(value: &str) -> ChangeTree {
+	ChangeTree::Added(value.to_string())
+}
+
+fn removed(value: &str) -> ChangeTree {
+	ChangeTree::Removed(value.to_string())
+}
+
+#[test]
+fn test_tree_struct() {
+	let before = Customer {
+		name: "Alice".to_string(),
+		address: Address { street: "Main St".to_string(), city: "Springfield".to_string() },
+		orders: vec![(1, "pen".to_string()), (2, "ink".to_string())].into_iter().collect(),
+		password: "secret".to_string(),
+	};
+	let after = Customer {
+		name: "Alice".to_string(),
+		address: Address { street: "Main St".to_string(), city: "Shelbyville".to_string() },
+		orders: vec![(1, "pencil".to_string()), (3, "paper".to_string())].into_iter().collect(),
+		password: "hunter2".to_string(),
+	};
+	let tree = before.comparison(&after).unwrap_or_default().to_tree();
+	assert_eq!(
+		tree,
+		ChangeTree::Children(vec![
+			key(
+				"address",
+				ChangeTree::Children(vec![key("city", ChangeTree::modified(&"Springfield", &"Shelbyville"))])
+			),
+			key(
+				"orders",
+				ChangeTree::Children(vec![
+					key("1", ChangeTree::modified(&"pen", &"pencil")),
+					key("3", added("\"paper\"")),
+					key("2", removed("")),
+				])
+			),
+			key("password", ChangeTree::modified(&Redacted, &Redacted)),
+		])
+	);
+	assert!(!tree.is_empty());
+	assert_eq!(decode::<ChangeTree>(&encode(&tree).unwrap()).unwrap(), tree);
+}
+
+#[test]
+fn test_tree_enum() {
+	let circle = Shape::Circle(1.0).comparison(&Shape::Circle(2.5)).next().unwrap();
+	assert_eq!(circle.to_tree(), ChangeTree::Children(vec![key("0", ChangeTree::modified(&1.0, &2.5))]));
+
+	let rectangle =
+		Shape::Rectangle { width: 1, height: 2 }.comparison(&Shape::Rectangle { width: 1, height: 4 }).next().unwrap();
+	assert_eq!(rectangle.to_tree(), ChangeTree::Children(vec![key("height", ChangeTree::modified(&2, &4))]));
+
+	let different = Shape::Circle(1.0).comparison(&Shape::Empty).next().unwrap();
+	assert_eq!(
+		different.to_tree(),
+		ChangeTree::Modified { before: "Circle(1.0)".to_string(), after: "Empty".to_string() }
+	);
+}
+
+#[test]
+fn test_tree_collections() {
+	let changes = vec![1, 2, 3].comparison(&vec![1, 4]).unwrap_or_default();
+	assert_eq!(
+		changes.to_tree(),
+		ChangeTree::Children(vec![key("1", ChangeTree::modified(&2, &4)), key("2", removed("3"))])
+	);
+
+	let set: std::collections::BTreeSet<u8> = vec![1, 2].into_iter().collect();
+	let other = vec![2, 3].into_iter().collect();
+	assert_eq!(
+		set.comparison(&other).unwrap_or_default().to_tree(),
+		ChangeTree::Children(vec![key("3", added("3")), key("1", removed("1"))])
+	);
+	assert!(Vec::<VecChange<u8, U8Change>>::new().to_tree().is_empty());
+}
+
+#[test]
+fn test_tree_rows() {
+	let before = Customer {
+		name: "Alice".to_string(),
+		address: Address { street: "Main St".to_string(), city: "Springfield".to_string() },
+		orders: vec![(1, "book".to_string())].into_iter().collect(),
+		password: "secret".to_string(),
+	};
+	let after = Customer {
+		name: "Alice".to_string(),
+		address: Address { street: "Elm St".to_string(), city: "Springfield".to_string() },
+		orders: vec![(2, "pen".to_string())].into_iter().collect(),
+		password: "secret".to_string(),
+	};
+	let tree = before.comparison(&after).unwrap_or_default().to_tree();
+	assert_eq!(
+		tree.leaves().into_iter().map(|(path, _)| path).collect::<Vec<_>>(),
+		vec!["address.street", "orders.2", "orders.1"]
+	);
+	let row = |path: &str, old: &str, new: &str| (path.to_string(), old.to_string(), new.to_string());
+	assert_eq!(
+		tree.rows(),
+		vec![
+			row("address.street", "\"Main St\"", "\"Elm St\""),
+			row("orders.2", "", "\"pen\""),
+			row("orders.1", "", ""),
+		]
+	);
+
+	// A change to the value itself has the empty path.
+	assert_eq!(5i32.comparison(&6).next().unwrap().to_tree().rows(), vec![row("", "5", "6")]);
+	assert_eq!(ChangeTree::Children(vec![]).rows(), vec![]);
+}
@@ -0,0 +1,58 @@
+use comparable::codec::{decode, encode};
+use comparable::versioned::{Migrate, Versioned};
+use comparable::*;
+
+#[derive(Comparable)]
+#[comparable_version = 3]
+struct Settings<T> {
+	name: String,
+	value: T,
+}
+
+#[derive(Comparable)]
+#[comparable_version = 2]
+#[comparable_migrate]
+#[allow(dead_code)]
+enum Shape {
+	Circle(u32),
+	Square(u32),
+}
+
+// Version 1 only had circles, described by their radius alone.
+impl Migrate for ShapeDesc {
+	fn from_versioned<'de, D: serde::Deserializer<'de>>(version: u32, deserializer: D) -> Result<Self, D::Error> {
+		match version {
+			1 => Ok(ShapeDesc::Circle(serde::Deserialize::deserialize(deserializer)?)),
+			_ => Err(serde::de::Error::custom("unknown version")),
+		}
+	}
+}
+
+impl Migrate for ShapeChange {}
+
+#[test]
+fn test_versioned_roundtrip() {
+	assert_eq!(<SettingsDesc<u8> as Versioned>::VERSION, 3);
+	let a = Settings { name: "a".to_string(), value: 1u8 };
+	let b = Settings { name: "b".to_string(), value: 1u8 };
+	let desc = a.describe();
+	let bytes = encode(&desc).unwrap();
+	assert_eq!(bytes[0], 3);
+	assert_eq!(decode::<SettingsDesc<u8>>(&bytes).unwrap(), desc);
+
+	let changes = a.comparison(&b).unwrap_or_default();
+	let bytes = encode(&changes).unwrap();
+	assert_eq!(bytes[..2], [1, 3]);
+	assert_eq!(decode::<Vec<SettingsChange<U8Change>>>(&bytes).unwrap(), changes);
+}
+
+#[test]
+fn test_versioned_migration() {
+	let current = encode(&Shape::Square(2).describe()).unwrap();
+	assert_eq!(decode::<ShapeDesc>(&current).unwrap(), ShapeDesc::Square(2));
+	let old = encode(&(1u32, 5u32)).unwrap();
+	assert_eq!(decode::<ShapeDesc>(&old).unwrap(), ShapeDesc::Circle(5));
+	assert!(decode::<ShapeDesc>(&encode(&(7u32, 5u32)).unwrap()).is_err());
+	let old_change = encode(&(1u32, 0u32, 5u32, 6u32)).unwrap();
+	assert!(decode::<ShapeChange>(&old_change).is_err());
+}
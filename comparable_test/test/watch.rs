@@ -0,0 +1,57 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use comparable::watch::Store;
+use comparable::*;
+
+#[derive(Comparable, Clone, PartialEq, Debug)]
+struct Address {
+	street: String,
+	city: String,
+}
+
+#[derive(Comparable, Clone, PartialEq, Debug)]
+struct Profile {
+	name: String,
+	address: Address,
+}
+
+fn profile(name: &str, city: &str) -> Profile {
+	Profile { name: name.to_string(), address: Address { street: "Main St".to_string(), city: city.to_string() } }
+}
+
+#[test]
+fn test_watch_store() {
+	let mut store = Store::new(profile("Alice", "Springfield"));
+	let calls = Rc::new(RefCell::new(Vec::new()));
+	let subscribe = |store: &mut Store<Profile>, path: &'static str| {
+		let calls = calls.clone();
+		store.subscribe(path, move |old: &Profile, new: &Profile| {
+			calls.borrow_mut().push((path, old.address.city.clone(), new.address.city.clone()))
+		})
+	};
+	subscribe(&mut store, "");
+	subscribe(&mut store, "name");
+	let address = subscribe(&mut store, "address");
+	subscribe(&mut store, "address.city");
+	subscribe(&mut store, "address.street");
+
+	assert_eq!(store.update(profile("Alice", "Springfield")), profile("Alice", "Springfield"));
+	assert!(calls.borrow().is_empty());
+
+	store.update(profile("Alice", "Shelbyville"));
+	assert_eq!(
+		calls.borrow_mut().drain(..).collect::<Vec<_>>(),
+		vec![
+			("", "Springfield".to_string(), "Shelbyville".to_string()),
+			("address", "Springfield".to_string(), "Shelbyville".to_string()),
+			("address.city", "Springfield".to_string(), "Shelbyville".to_string()),
+		]
+	);
+	assert_eq!(store.get(), &profile("Alice", "Shelbyville"));
+
+	assert!(store.unsubscribe(address));
+	assert!(!store.unsubscribe(address));
+	store.update(profile("Alicia", "Ogdenville"));
+	assert_eq!(calls.borrow().iter().map(|(path, _, _)| *path).collect::<Vec<_>>(), vec!["", "name", "address.city"]);
+}
@@ -0,0 +1,110 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Debug;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use comparable::wire::{Envelope, VERSION};
+use comparable::*;
+
+#[derive(Comparable, Clone)]
+struct Point {
+	x: i32,
+	y: i32,
+}
+
+#[derive(Comparable, Clone)]
+struct Label {
+	text: String,
+}
+
+#[derive(Comparable, Clone)]
+struct Id(u8);
+
+#[derive(Comparable, Clone)]
+struct Pair(u8, u8);
+
+#[derive(Comparable, Clone)]
+enum Shape {
+	Empty,
+	Circle(u32),
+	Rect { width: u32, height: u32 },
+}
+
+// Each encoding is checked in both directions, so that a decoder written
+// from the specification in `comparable::wire` reads what is written here.
+fn assert_wire<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: T, json: &str) {
+	assert_eq!(serde_json::to_string(&value).unwrap(), json);
+	assert_eq!(serde_json::from_str::<T>(json).unwrap(), value);
+}
+
+// The changes compared always differ, so `json` leaves out the `Changed` tag
+// around them.
+fn assert_changes_wire<T: Comparable>(before: &T, after: &T, json: &str)
+where
+	T::Change: Serialize + DeserializeOwned,
+{
+	assert_wire(before.comparison(after), &format!(r#"{{"Changed":{}}}"#, json));
+}
+
+#[test]
+fn test_wire_changed() {
+	assert_wire(Changed::<U8Change>::Unchanged, r#""Unchanged""#);
+	assert_wire(Changed::Changed(U8Change(1, 2)), r#"{"Changed":[1,2]}"#);
+	assert_wire(Changed::Changed(Redacted), r#"{"Changed":null}"#);
+	assert_wire(Envelope::<U8Change>::new(Changed::Unchanged), r#"{"version":1,"changes":"Unchanged"}"#);
+	assert_wire(Envelope::new(Changed::Changed(Redacted)), r#"{"version":1,"changes":{"Changed":null}}"#);
+}
+
+#[test]
+fn test_wire_library_types() {
+	assert_changes_wire(&true, &false, "[true,false]");
+	assert_changes_wire(&-1i64, &2i64, "[-1,2]");
+	assert_changes_wire(&'a', &'b', r#"["a","b"]"#);
+	assert_changes_wire(&"a".to_string(), &"b".to_string(), r#"["a","b"]"#);
+	assert_changes_wire(&Some(1u8), &Some(2u8), r#"{"BothSome":[1,2]}"#);
+	assert_changes_wire(&Some(1u8), &None, r#"{"Different":[1,null]}"#);
+	assert_changes_wire(&vec![1u8, 2], &vec![3u8], r#"[{"Changed":[0,[1,3]]},{"Removed":[1,2]}]"#);
+	assert_changes_wire(&vec![1u8], &vec![1u8, 2], r#"[{"Added":[1,2]}]"#);
+	assert_changes_wire(
+		&vec![1u8].into_iter().collect::<BTreeSet<_>>(),
+		&vec![2u8].into_iter().collect::<BTreeSet<_>>(),
+		r#"[{"Added":2},{"Removed":1}]"#,
+	);
+	let map = |entries: &[(&str, u8)]| entries.iter().map(|(k, v)| (k.to_string(), *v)).collect::<BTreeMap<_, _>>();
+	assert_changes_wire(
+		&map(&[("a", 1), ("b", 2)]),
+		&map(&[("a", 3), ("c", 4)]),
+		r#"[{"Changed":["a",[1,3]]},{"Added":["c",4]},{"Removed":"b"}]"#,
+	);
+	assert_changes_wire(&(1u8..2), &(1u8..3), r#"[{"End":[2,3]}]"#);
+}
+
+#[test]
+fn test_wire_derived_types() {
+	let (before, after) = (Point { x: 1, y: 2 }, Point { x: 3, y: 4 });
+	assert_wire(before.describe(), r#"{"x":1,"y":2}"#);
+	assert_changes_wire(&before, &after, r#"[{"X":[1,3]},{"Y":[2,4]}]"#);
+	assert_changes_wire(&before, &Point { y: 5, ..before.clone() }, r#"[{"Y":[2,5]}]"#);
+	assert_changes_wire(&Label { text: "a".to_string() }, &Label { text: "b".to_string() }, r#"{"text":["a","b"]}"#);
+	assert_changes_wire(&Id(1), &Id(2), "[1,2]");
+	assert_changes_wire(&Pair(1, 2), &Pair(1, 3), r#"[{"Field1":[2,3]}]"#);
+
+	assert_wire(Shape::Rect { width: 1, height: 2 }.describe(), r#"{"Rect":{"width":1,"height":2}}"#);
+	assert_changes_wire(&Shape::Circle(1), &Shape::Circle(2), r#"{"BothCircle":[1,2]}"#);
+	assert_changes_wire(
+		&Shape::Rect { width: 1, height: 2 },
+		&Shape::Rect { width: 1, height: 3 },
+		r#"{"BothRect":{"width":"Unchanged","height":{"Changed":[2,3]}}}"#,
+	);
+	assert_changes_wire(&Shape::Empty, &Shape::Circle(1), r#"{"Different":["Empty",{"Circle":1}]}"#);
+}
+
+#[test]
+fn test_wire_versions() {
+	assert_eq!(VERSION, 1);
+	let newer = format!(r#"{{"version":{},"changes":"Unchanged"}}"#, VERSION + 1);
+	let error = serde_json::from_str::<Envelope<U8Change>>(&newer).unwrap_err();
+	assert!(error.to_string().contains("unsupported wire format version 2"));
+	assert!(serde_json::from_str::<Envelope<U8Change>>(r#"{"changes":"Unchanged"}"#).is_err());
+}
@@ -0,0 +1,64 @@
+use std::fs;
+use std::path::Path;
+
+use clap::ValueEnum;
+use serde_json::{Map, Number, Value};
+
+/// The formats documents are read in.
+#[derive(Clone, Copy, PartialEq, Debug, ValueEnum)]
+pub enum Format {
+	Json,
+	Yaml,
+	Toml,
+}
+
+impl Format {
+	/// The format given by the extension of a path.
+	fn of(path: &Path) -> Option<Format> {
+		match path.extension()?.to_str()? {
+			"json" => Some(Format::Json),
+			"yaml" | "yml" => Some(Format::Yaml),
+			"toml" => Some(Format::Toml),
+			_ => None,
+		}
+	}
+}
+
+/// Read the document at `path`, in the given format or that of its
+/// extension.
+pub fn load(path: &Path, format: Option<Format>) -> Result<Value, String> {
+	let format = format.or_else(|| Format::of(path)).ok_or_else(|| {
+		format!("{}: the format is not known from the extension, and must be given with --format", path.display())
+	})?;
+	let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+	parse(&text, format).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn parse(text: &str, format: Format) -> Result<Value, String> {
+	match format {
+		Format::Json => serde_json::from_str(text).map_err(|e| e.to_string()),
+		Format::Yaml => serde_yaml::from_str(text).map_err(|e| e.to_string()),
+		Format::Toml => {
+			let table: toml::Table = text.parse().map_err(|e: toml::de::Error| e.to_string())?;
+			Ok(from_toml(toml::Value::Table(table)))
+		}
+	}
+}
+
+// TOML values as JSON values. Dates and times, which JSON lacks, become
+// strings, as do floats that are not finite.
+fn from_toml(value: toml::Value) -> Value {
+	match value {
+		toml::Value::String(string) => Value::String(string),
+		toml::Value::Integer(integer) => Value::from(integer),
+		toml::Value::Float(float) => {
+			Number::from_f64(float).map_or_else(|| Value::String(float.to_string()), Value::Number)
+		}
+		toml::Value::Boolean(boolean) => Value::Bool(boolean),
+		toml::Value::Datetime(datetime) => Value::String(datetime.to_string()),
+		toml::Value::Array(array) => Value::Array(array.into_iter().map(from_toml).collect()),
+		toml::Value::Table(table) => {
+			Value::Object(table.into_iter().map(|(k, v)| (k, from_toml(v))).collect::<Map<_, _>>())
+		}
+	}
+}
@@ -0,0 +1,120 @@
+//! Compare two JSON, YAML or TOML documents, such as configuration files,
+//! and report the changes from the first to the second.
+//!
+//! Each document is read as a `serde_json::Value`, in the format given by its
+//! extension or by `--format`, so that documents in different formats can be
+//! compared, and the two are compared as `comparable` compares any value. The
+//! changes are written as colored lines of text, one for each value added,
+//! removed, modified or moved and named by its JSON Pointer, or as a JSON
+//! Patch or JSON Merge Patch document with `--output`.
+//!
+//! As with `diff`, the exit status is 0 if the documents are the same, 1 if
+//! they differ, and 2 if either could not be read.
+
+mod load;
+mod render;
+
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Parser, ValueEnum};
+use comparable::format::{json_patch, merge_patch};
+use comparable::{with_diff_options, Changed, Comparable, DiffAlgorithm, DiffOptions};
+
+use crate::load::{load, Format};
+
+#[derive(Parser)]
+#[command(version, about = "Compare two JSON, YAML or TOML documents")]
+struct Args {
+	/// The document before the changes.
+	before: PathBuf,
+	/// The document after the changes.
+	after: PathBuf,
+	/// The format of both documents, rather than that given by their
+	/// extensions.
+	#[arg(long, value_enum)]
+	format: Option<Format>,
+	/// How to write the changes.
+	#[arg(long, value_enum, default_value_t = Output::Text)]
+	output: Output,
+	/// When to color the changes written as text.
+	#[arg(long, value_enum, default_value_t = Color::Auto)]
+	color: Color,
+	/// Report array elements and object members found unchanged elsewhere as
+	/// moved, rather than as removed and added.
+	#[arg(long)]
+	moves: bool,
+	/// Align the elements of arrays before comparing them, so that elements
+	/// inserted or removed are reported as such, rather than as changes to
+	/// every element after them.
+	#[arg(long)]
+	align: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Output {
+	/// A line for each change.
+	Text,
+	/// A JSON Patch (RFC 6902) document.
+	JsonPatch,
+	/// A JSON Merge Patch (RFC 7386) document.
+	MergePatch,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Color {
+	/// Color the changes when writing to a terminal, unless `NO_COLOR` is set.
+	Auto,
+	/// Always color the changes.
+	Always,
+	/// Never color the changes.
+	Never,
+}
+
+fn main() -> ExitCode {
+	match run(Args::parse()) {
+		Ok(false) => ExitCode::SUCCESS,
+		Ok(true) => ExitCode::from(1),
+		Err(message) => {
+			eprintln!("delta-cli: {}", message);
+			ExitCode::from(2)
+		}
+	}
+}
+
+// Write the changes between the documents, returning whether there are any.
+fn run(args: Args) -> Result<bool, String> {
+	let before = load(&args.before, args.format)?;
+	let after = load(&args.after, args.format)?;
+	let options = DiffOptions {
+		map_moves: if args.moves { Some(1.0) } else { None },
+		sequence_moves: args.moves,
+		sequence_algorithm: if args.align { Some(DiffAlgorithm::Myers) } else { None },
+		..DiffOptions::default()
+	};
+	let stdout = io::stdout();
+	let mut out = stdout.lock();
+	let written = with_diff_options(options, || match args.output {
+		Output::Text => {
+			let color = match args.color {
+				Color::Auto => io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none(),
+				Color::Always => true,
+				Color::Never => false,
+			};
+			match before.comparison(&after) {
+				Changed::Changed(change) => render::write(&mut out, &before, &change, color),
+				Changed::Unchanged => Ok(()),
+			}
+		}
+		Output::JsonPatch => write_json(&mut out, &json_patch::diff(&before, &after)),
+		Output::MergePatch => write_json(&mut out, &merge_patch::diff(&before, &after)),
+	});
+	written.map_err(|e| e.to_string())?;
+	Ok(before.differs(&after))
+}
+
+fn write_json<T: serde::Serialize>(out: &mut impl Write, value: &T) -> io::Result<()> {
+	serde_json::to_writer_pretty(&mut *out, value)?;
+	writeln!(out)
+}
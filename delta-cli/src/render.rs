@@ -0,0 +1,125 @@
+use std::io::{self, Write};
+
+use comparable::json::ValueChange;
+use comparable::{Changed, MapChange, VecChange};
+use serde_json::Value;
+
+// The kinds of lines written, each marked by its first character and color.
+#[derive(Clone, Copy)]
+enum Kind {
+	Added,
+	Removed,
+	Modified,
+	Moved,
+}
+
+impl Kind {
+	fn mark(self) -> char {
+		match self {
+			Kind::Added => '+',
+			Kind::Removed => '-',
+			Kind::Modified => '~',
+			Kind::Moved => '>',
+		}
+	}
+
+	// The ANSI escape code for the color of the line.
+	fn color(self) -> &'static str {
+		match self {
+			Kind::Added => "\x1b[32m",
+			Kind::Removed => "\x1b[31m",
+			Kind::Modified => "\x1b[33m",
+			Kind::Moved => "\x1b[36m",
+		}
+	}
+}
+
+/// Write a line for each value added, removed, modified or moved, named by
+/// its JSON Pointer and colored by what happened to it if `color` is set. The
+/// values of object members removed are found in `before`, since their
+/// changes do not hold them.
+pub fn write(out: &mut impl Write, before: &Value, change: &ValueChange, color: bool) -> io::Result<()> {
+	let mut lines = Vec::new();
+	push_lines(change, Some(before), "", &mut lines);
+	for (kind, text) in lines {
+		if color {
+			writeln!(out, "{}{} {}\x1b[0m", kind.color(), kind.mark(), text)?;
+		} else {
+			writeln!(out, "{} {}", kind.mark(), text)?;
+		}
+	}
+	Ok(())
+}
+
+// The index before the changes of each element of an array after them, or
+// `None` for those added: the elements removed and moved leave their places,
+// and those kept fill the places not taken by those added and moved, in order.
+fn origins(len: usize, changes: &[VecChange<Value, ValueChange>]) -> Vec<Option<usize>> {
+	let mut gone = vec![false; len];
+	let mut placed = Vec::new();
+	for change in changes {
+		match change {
+			VecChange::Added(to, _) => placed.push((*to, None)),
+			VecChange::Removed(from, _) => gone[*from] = true,
+			VecChange::Moved { from, to } => {
+				gone[*from] = true;
+				placed.push((*to, Some(*from)))
+			}
+			VecChange::Changed(..) => (),
+		}
+	}
+	let mut slots = vec![None; gone.iter().filter(|gone| !**gone).count() + placed.len()];
+	for (to, from) in placed {
+		slots[to] = Some(from);
+	}
+	let mut kept = (0..len).filter(|from| !gone[*from]);
+	slots.into_iter().map(|slot| slot.unwrap_or_else(|| kept.next())).collect()
+}
+
+fn push_lines(change: &ValueChange, before: Option<&Value>, path: &str, lines: &mut Vec<(Kind, String)>) {
+	let at = |token: &str| format!("{}/{}", path, token.replace('~', "~0").replace('/', "~1"));
+	let value = |kind, path: String, value: &Value| (kind, format!("{}: {}", path, value));
+	match change {
+		ValueChange::Replaced(before, after) => {
+			let path = if path.is_empty() { "(root)" } else { path };
+			lines.push((Kind::Modified, format!("{}: {} → {}", path, before, after)))
+		}
+		ValueChange::Array(changes) => {
+			let elements = before.and_then(Value::as_array);
+			let origins = elements.map(|elements| origins(elements.len(), changes));
+			for change in changes {
+				match change {
+					VecChange::Added(to, desc) => lines.push(value(Kind::Added, at(&to.to_string()), desc)),
+					VecChange::Changed(to, change) => {
+						let from = origins.as_ref().and_then(|origins| origins.get(*to).copied().flatten());
+						let before = elements.zip(from).and_then(|(elements, from)| elements.get(from));
+						push_lines(change, before, &at(&to.to_string()), lines)
+					}
+					VecChange::Removed(from, desc) => lines.push(value(Kind::Removed, at(&from.to_string()), desc)),
+					VecChange::Moved { from, to } => {
+						lines.push((Kind::Moved, format!("{} → {}", at(&from.to_string()), at(&to.to_string()))))
+					}
+				}
+			}
+		}
+		ValueChange::Object(changes) => {
+			let member = |name: &String| before.and_then(|before| before.get(name));
+			for change in changes {
+				match change {
+					MapChange::Added(name, desc) => lines.push(value(Kind::Added, at(name), desc)),
+					MapChange::Changed(name, change) => push_lines(change, member(name), &at(name), lines),
+					MapChange::Removed(name) => match member(name) {
+						Some(desc) => lines.push(value(Kind::Removed, at(name), desc)),
+						None => lines.push((Kind::Removed, at(name))),
+					},
+					MapChange::Moved { from, to, change } => {
+						lines.push((Kind::Moved, format!("{} → {}", at(from), at(to))));
+						if let Changed::Changed(change) = change {
+							push_lines(change, member(from), &at(to), lines)
+						}
+					}
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,89 @@
+use std::process::{Command, Output};
+
+use comparable::format::json_patch::{apply, Operation};
+use serde_json::{json, Value};
+
+fn delta(args: &[&str]) -> Output {
+	Command::new(env!("CARGO_BIN_EXE_delta-cli"))
+		.current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data"))
+		.args(args)
+		.output()
+		.unwrap()
+}
+
+fn stdout(output: &Output) -> &str {
+	std::str::from_utf8(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_text() {
+	let output = delta(&["before.json", "after.yaml", "--color", "never"]);
+	assert_eq!(output.status.code(), Some(1));
+	assert_eq!(
+		stdout(&output),
+		"~ /hosts/1: \"b.example.com\" → \"c.example.com\"\n\
+		 + /hosts/2: \"d.example.com\"\n\
+		 ~ /port: 80 → 8080\n\
+		 + /tls/cert: \"/etc/cert.pem\"\n\
+		 ~ /tls/enabled: false → true\n\
+		 - /debug: true\n"
+	);
+
+	let output = delta(&["before.json", "after.toml", "--color", "always"]);
+	assert!(stdout(&output).starts_with("\x1b[33m~ /hosts/1: \"b.example.com\" → \"c.example.com\"\x1b[0m\n"));
+
+	// Documents in different formats holding the same values are the same.
+	let output = delta(&["after.toml", "after.yaml"]);
+	assert_eq!(output.status.code(), Some(0));
+	assert_eq!(stdout(&output), "");
+}
+
+#[test]
+fn test_moves() {
+	let output = delta(&["before.json", "moved.json", "--moves", "--color", "never"]);
+	assert_eq!(output.status.code(), Some(1));
+	assert_eq!(stdout(&output), "> /hosts/1 → /hosts/0\n> /hosts/0 → /hosts/1\n> /debug → /verbose\n");
+}
+
+#[test]
+fn test_patches() {
+	let output = delta(&["before.json", "after.toml", "--output", "json-patch"]);
+	assert_eq!(output.status.code(), Some(1));
+	let patch: Vec<Operation> = serde_json::from_slice(&output.stdout).unwrap();
+	let mut doc: Value = serde_json::from_str(include_str!("data/before.json")).unwrap();
+	apply(&mut doc, &patch).unwrap();
+	assert_eq!(
+		doc,
+		json!({
+			"name": "web",
+			"port": 8080,
+			"hosts": ["a.example.com", "c.example.com", "d.example.com"],
+			"tls": { "enabled": true, "cert": "/etc/cert.pem" },
+		})
+	);
+
+	let output = delta(&["before.json", "after.yaml", "--output", "merge-patch"]);
+	let patch: Value = serde_json::from_slice(&output.stdout).unwrap();
+	assert_eq!(
+		patch,
+		json!({
+			"debug": null,
+			"hosts": ["a.example.com", "c.example.com", "d.example.com"],
+			"port": 8080,
+			"tls": { "enabled": true, "cert": "/etc/cert.pem" },
+		})
+	);
+}
+
+#[test]
+fn test_errors() {
+	let output = delta(&["before.txt", "after.yaml"]);
+	assert_eq!(output.status.code(), Some(2));
+	assert_eq!(
+		std::str::from_utf8(&output.stderr).unwrap(),
+		"delta-cli: before.txt: the format is not known from the extension, and must be given with --format\n"
+	);
+	assert_eq!(delta(&["before.txt", "before.json", "--format", "json"]).status.code(), Some(1));
+	assert_eq!(delta(&["missing.json", "before.json"]).status.code(), Some(2));
+	assert_eq!(delta(&["after.yaml", "after.toml", "--format", "json"]).status.code(), Some(2));
+}